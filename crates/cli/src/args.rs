@@ -1,12 +1,26 @@
 use anyhow::{Context, Error, Result};
-use clap::{Parser, ValueEnum};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use std::{
     ffi::OsStr,
+    fs,
     net::SocketAddr,
     path::{Path, PathBuf},
     str::FromStr,
 };
-use wasmi_wasi::{ambient_authority, Dir, TcpListener, WasiCtx, WasiCtxBuilder};
+use wasmi::{Config, StackLimits, StoreLimits, StoreLimitsBuilder};
+use wasmi_wasi::preview1::{
+    SeededRandom,
+    StdDir,
+    StdFile,
+    StdTracer,
+    Stderr,
+    Stdin,
+    Stdout,
+    TcpListener,
+    VirtualClocks,
+    WasiCtx,
+    WasiCtxBuilder,
+};
 
 /// A CLI flag value key-value argument.
 #[derive(Debug, Clone)]
@@ -36,10 +50,53 @@ impl FromStr for KeyValue {
     }
 }
 
+/// A function invocation given via `--invoke` or `--invoke-script`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// The name of the invoked function.
+    pub name: String,
+    /// The unparsed arguments given to the invoked function.
+    pub args: Vec<String>,
+}
+
+impl Invocation {
+    /// Parses the invocations of an `--invoke-script`.
+    ///
+    /// Each line holds the function name followed by its whitespace separated arguments.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn parse_script(script: &str) -> Vec<Self> {
+        script
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut words = line.split_whitespace().map(String::from);
+                let name = words.next().unwrap_or_default();
+                Self {
+                    name,
+                    args: words.collect(),
+                }
+            })
+            .collect()
+    }
+}
+
 /// The Wasmi CLI application arguments.
 #[derive(Parser, Debug)]
-#[clap(author, version, about, long_about = None, trailing_var_arg = true)]
+#[clap(
+    author,
+    version,
+    about,
+    long_about = None,
+    trailing_var_arg = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Args {
+    /// The subcommand to run instead of executing a Wasm module.
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The host directory to pre-open for the `guest` to use.
     #[clap(
         long = "dir",
@@ -66,25 +123,83 @@ pub struct Args {
     )]
     envs: Vec<KeyValue>,
 
-    /// The file containing the WebAssembly module to execute.
+    /// The file read by the guest as its stdin instead of the stdin of the host.
+    #[clap(
+        long = "stdin",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    stdin: Option<PathBuf>,
+
+    /// The file the stdout of the guest is written to instead of the stdout of the host.
+    ///
+    /// The file is created if it does not exist and truncated otherwise.
+    #[clap(
+        long = "stdout",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    stdout: Option<PathBuf>,
+
+    /// The file the stderr of the guest is written to instead of the stderr of the host.
+    ///
+    /// The file is created if it does not exist and truncated otherwise.
+    #[clap(
+        long = "stderr",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    stderr: Option<PathBuf>,
+
+    /// The file containing the WebAssembly module or component to execute.
+    ///
+    /// Components are run via their exported `wasi:cli/run` entry point using WASI `preview2`.
     #[clap(
         value_name = "MODULE",
         value_hint = clap::ValueHint::FilePath,
+        required = true,
     )]
-    wasm_file: PathBuf,
+    wasm_file: Option<PathBuf>,
 
-    /// The function to invoke.
+    /// The function to invoke followed by its arguments.
+    ///
+    /// May be given multiple times in which case the functions are invoked
+    /// in order on the same instance and the results of each are printed.
     ///
     /// If this argument is missing, Wasmi CLI will try to run `""` or `_start`.
     ///
     /// If neither are exported the Wasmi CLI will display out all exported
     /// functions of the Wasm module and return with an error.
-    #[clap(long = "invoke", value_name = "FUNCTION")]
-    invoke: Option<String>,
+    #[clap(
+        long = "invoke",
+        value_name = "FUNCTION",
+        num_args = 1..,
+        allow_negative_numbers = true,
+        action = clap::ArgAction::Append,
+    )]
+    invoke: Vec<String>,
 
-    /// Enable lazy Wasm compilation.
-    #[clap(long = "compilation-mode", value_enum, default_value_t=CompilationMode::Eager)]
-    compilation_mode: CompilationMode,
+    /// The `--invoke` values grouped by occurrence.
+    #[clap(skip)]
+    invoke_groups: Vec<Vec<String>>,
+
+    /// The file with the functions to invoke in order, one per line.
+    ///
+    /// Each line holds the name of the function followed by its arguments
+    /// separated by whitespace. Empty lines and lines starting with `#` are ignored.
+    #[clap(
+        long = "invoke-script",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "invoke",
+    )]
+    invoke_script: Option<PathBuf>,
+
+    /// Start an interactive session to call functions and inspect the state of the Wasm module.
+    ///
+    /// Type `help` in the session to list the available commands.
+    #[clap(long = "repl", conflicts_with_all = ["invoke", "invoke_script"])]
+    repl: bool,
 
     /// Enable execution fiel metering with N units of fuel.
     ///
@@ -92,11 +207,166 @@ pub struct Args {
     #[clap(long = "fuel", value_name = "N")]
     fuel: Option<u64>,
 
+    /// Run the WASI guest deterministically.
+    ///
+    /// The clocks are virtual and advance by a fixed step whenever they are read
+    /// and random numbers are generated by a seeded pseudo random number generator.
+    #[clap(long = "deterministic")]
+    deterministic: bool,
+
+    /// The seed of the random number generator in `--deterministic` mode.
+    ///
+    /// Defaults to 0.
+    #[clap(long = "seed", value_name = "N", requires = "deterministic")]
+    seed: Option<u64>,
+
+    /// The nanoseconds the clocks advance per reading in `--deterministic` mode.
+    #[clap(
+        long = "clock-step",
+        value_name = "NANOS",
+        requires = "deterministic",
+        conflicts_with = "clock_fuel_rate"
+    )]
+    clock_step: Option<u64>,
+
+    /// The nanoseconds the clocks advance per unit of fuel consumed in `--deterministic` mode.
+    #[clap(
+        long = "clock-fuel-rate",
+        value_name = "NANOS",
+        requires_all = ["deterministic", "fuel"]
+    )]
+    clock_fuel_rate: Option<u64>,
+
+    /// Print every WASI `preview1` call of the guest to stderr, similar to `strace`.
+    ///
+    /// Each line shows the decoded arguments, the returned errno and the duration of the call.
+    #[clap(long = "trace-wasi")]
+    trace_wasi: bool,
+
+    /// The configuration of the Wasmi engine and the limits of the store.
+    #[clap(flatten)]
+    engine: EngineArgs,
+
     /// Arguments given to the Wasm module or the invoked function.
     #[clap(value_name = "ARGS")]
     func_args: Vec<String>,
 }
 
+/// The Wasmi [`Config`] and [`StoreLimits`] given to the CLI app.
+///
+/// Wasm proposals not given keep the defaults of the Wasmi CLI which
+/// in contrast to [`Config::default`] enables `tail-call` and `extended-const`.
+#[derive(clap::Args, Debug)]
+#[clap(next_help_heading = "Engine")]
+struct EngineArgs {
+    /// Enable lazy Wasm compilation.
+    #[clap(long = "compilation-mode", value_enum, default_value_t=CompilationMode::Eager)]
+    compilation_mode: CompilationMode,
+
+    /// Enable or disable the `mutable-global` Wasm proposal.
+    #[clap(long = "wasm-mutable-global", value_name = "BOOL")]
+    mutable_global: Option<bool>,
+
+    /// Enable or disable the `sign-extension` Wasm proposal.
+    #[clap(long = "wasm-sign-extension", value_name = "BOOL")]
+    sign_extension: Option<bool>,
+
+    /// Enable or disable the `saturating-float-to-int` Wasm proposal.
+    #[clap(long = "wasm-saturating-float-to-int", value_name = "BOOL")]
+    saturating_float_to_int: Option<bool>,
+
+    /// Enable or disable the `multi-value` Wasm proposal.
+    #[clap(long = "wasm-multi-value", value_name = "BOOL")]
+    multi_value: Option<bool>,
+
+    /// Enable or disable the `bulk-memory` Wasm proposal.
+    #[clap(long = "wasm-bulk-memory", value_name = "BOOL")]
+    bulk_memory: Option<bool>,
+
+    /// Enable or disable the `reference-types` Wasm proposal.
+    #[clap(long = "wasm-reference-types", value_name = "BOOL")]
+    reference_types: Option<bool>,
+
+    /// Enable or disable the `tail-call` Wasm proposal.
+    #[clap(long = "wasm-tail-call", value_name = "BOOL")]
+    tail_call: Option<bool>,
+
+    /// Enable or disable the `extended-const` Wasm proposal.
+    #[clap(long = "wasm-extended-const", value_name = "BOOL")]
+    extended_const: Option<bool>,
+
+    /// Enable or disable the `threads` Wasm proposal.
+    #[clap(long = "wasm-threads", value_name = "BOOL")]
+    threads: Option<bool>,
+
+    /// Enable or disable Wasm floating point types and instructions.
+    #[clap(long = "floats", value_name = "BOOL")]
+    floats: Option<bool>,
+
+    /// The initial height of the value stack in number of values.
+    #[clap(long = "initial-value-stack-height", value_name = "N")]
+    initial_value_stack_height: Option<usize>,
+
+    /// The maximum height of the value stack in number of values.
+    #[clap(long = "max-value-stack-height", value_name = "N")]
+    maximum_value_stack_height: Option<usize>,
+
+    /// The maximum number of nested calls.
+    #[clap(long = "max-recursion-depth", value_name = "N")]
+    maximum_recursion_depth: Option<usize>,
+
+    /// The maximum number of stacks cached for reuse.
+    #[clap(long = "cached-stacks", value_name = "N")]
+    cached_stacks: Option<usize>,
+
+    /// The backend used to allocate linear memories.
+    #[clap(long = "memory-backend", value_enum, default_value_t = MemoryBackend::Vec)]
+    memory_backend: MemoryBackend,
+
+    /// The optimization level applied to Wasmi bytecode after translation.
+    #[clap(long = "opt-level", value_enum, default_value_t = OptLevel::None)]
+    opt_level: OptLevel,
+
+    /// The maximum number of instructions of functions inlined at their call sites.
+    #[clap(long = "inline-budget", value_name = "N")]
+    inline_budget: Option<u32>,
+
+    /// The maximum number of bytes each linear memory can grow to.
+    #[clap(long = "max-memory-size", value_name = "BYTES")]
+    max_memory_size: Option<usize>,
+
+    /// The maximum number of elements each table can grow to.
+    #[clap(long = "max-table-elements", value_name = "N")]
+    max_table_elements: Option<u32>,
+
+    /// The maximum number of instances that can be created.
+    #[clap(long = "max-instances", value_name = "N")]
+    max_instances: Option<usize>,
+
+    /// The maximum number of tables that can be created.
+    #[clap(long = "max-tables", value_name = "N")]
+    max_tables: Option<usize>,
+
+    /// The maximum number of linear memories that can be created.
+    #[clap(long = "max-memories", value_name = "N")]
+    max_memories: Option<usize>,
+
+    /// Trap instead of returning -1 when growing a memory or table fails.
+    #[clap(long = "trap-on-grow-failure")]
+    trap_on_grow_failure: bool,
+}
+
+/// The subcommands of the Wasmi CLI application.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Runs the directives of a `.wast` script file and prints a summary.
+    Wast {
+        /// The `.wast` script file to run.
+        #[clap(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+    },
+}
+
 /// The chosen Wasmi compilation mode.
 #[derive(Debug, Default, Copy, Clone, ValueEnum)]
 enum CompilationMode {
@@ -116,15 +386,106 @@ impl From<CompilationMode> for wasmi::CompilationMode {
     }
 }
 
+/// The chosen backend for linear memories.
+#[derive(Debug, Default, Copy, Clone, ValueEnum)]
+enum MemoryBackend {
+    #[default]
+    Vec,
+    VirtualMemory,
+}
+
+impl From<MemoryBackend> for wasmi::MemoryBackend {
+    fn from(backend: MemoryBackend) -> Self {
+        match backend {
+            MemoryBackend::Vec => Self::Vec,
+            MemoryBackend::VirtualMemory => Self::VirtualMemory,
+        }
+    }
+}
+
+/// The chosen optimization level for Wasmi bytecode.
+#[derive(Debug, Default, Copy, Clone, ValueEnum)]
+enum OptLevel {
+    #[default]
+    None,
+    Speed,
+}
+
+impl From<OptLevel> for wasmi::OptLevel {
+    fn from(level: OptLevel) -> Self {
+        match level {
+            OptLevel::None => Self::None,
+            OptLevel::Speed => Self::Speed,
+        }
+    }
+}
+
 impl Args {
+    /// Parses the [`Args`] from the command line arguments of the process.
+    ///
+    /// Exits the process with a usage error if parsing failed.
+    pub fn from_env() -> Self {
+        let matches = Self::command().get_matches();
+        let mut args = Self::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+        // Clap flattens the values of all `--invoke` occurrences so we re-group them.
+        args.invoke_groups = matches
+            .get_occurrences::<String>("invoke")
+            .map(|groups| groups.map(|group| group.cloned().collect()).collect())
+            .unwrap_or_default();
+        args
+    }
+
+    /// Returns the subcommand given to the CLI app if any.
+    pub fn subcommand(&self) -> Option<&Command> {
+        self.command.as_ref()
+    }
+
     /// Returns the Wasm file path given to the CLI app.
+    ///
+    /// # Panics
+    ///
+    /// If a subcommand was given instead of a Wasm file.
     pub fn wasm_file(&self) -> &Path {
-        &self.wasm_file
+        self.wasm_file
+            .as_deref()
+            .expect("the Wasm file is required without a subcommand")
     }
 
-    /// Returns the name of the invoked function if any.
-    pub fn invoked(&self) -> Option<&str> {
-        self.invoke.as_deref()
+    /// Returns `true` if functions to invoke were given via `--invoke` or `--invoke-script`.
+    pub fn has_invocations(&self) -> bool {
+        !self.invoke.is_empty() || self.invoke_script.is_some()
+    }
+
+    /// Returns the functions to invoke in order together with their arguments.
+    ///
+    /// # Note
+    ///
+    /// If a single `--invoke` is given without inline arguments the
+    /// trailing positional arguments are given to the invoked function.
+    ///
+    /// # Errors
+    ///
+    /// If the file given in `--invoke-script` cannot be read.
+    pub fn invocations(&self) -> Result<Vec<Invocation>> {
+        if let Some(path) = &self.invoke_script {
+            let script = fs::read_to_string(path)
+                .with_context(|| format!("failed to read invoke script '{path:?}'"))?;
+            return Ok(Invocation::parse_script(&script));
+        }
+        let invocations = match &self.invoke_groups[..] {
+            [group] if group.len() == 1 => vec![Invocation {
+                name: group[0].clone(),
+                args: self.func_args.clone(),
+            }],
+            groups => groups
+                .iter()
+                .map(|group| Invocation {
+                    name: group[0].clone(),
+                    args: group[1..].to_vec(),
+                })
+                .collect(),
+        };
+        Ok(invocations)
     }
 
     /// Returns the function arguments given to the CLI app.
@@ -132,14 +493,110 @@ impl Args {
         &self.func_args[..]
     }
 
+    /// Returns `true` if the interactive REPL session was requested.
+    pub fn repl(&self) -> bool {
+        self.repl
+    }
+
     /// Returns the amount of fuel given to the CLI app if any.
     pub fn fuel(&self) -> Option<u64> {
         self.fuel
     }
 
-    /// Returns `true` if lazy Wasm compilation is enabled.
-    pub fn compilation_mode(&self) -> wasmi::CompilationMode {
-        self.compilation_mode.into()
+    /// Returns the [`Config`] for the Wasmi engine given to the CLI app.
+    ///
+    /// # Errors
+    ///
+    /// If the given stack limits are invalid.
+    pub fn config(&self) -> Result<Config> {
+        let engine = &self.engine;
+        let mut config = Config::default();
+        config
+            .wasm_tail_call(true)
+            .wasm_extended_const(true)
+            .consume_fuel(self.fuel.is_some())
+            .compilation_mode(engine.compilation_mode.into())
+            .memory_backend(engine.memory_backend.into())
+            .opt_level(engine.opt_level.into());
+        if let Some(enable) = engine.mutable_global {
+            config.wasm_mutable_global(enable);
+        }
+        if let Some(enable) = engine.sign_extension {
+            config.wasm_sign_extension(enable);
+        }
+        if let Some(enable) = engine.saturating_float_to_int {
+            config.wasm_saturating_float_to_int(enable);
+        }
+        if let Some(enable) = engine.multi_value {
+            config.wasm_multi_value(enable);
+        }
+        if let Some(enable) = engine.bulk_memory {
+            config.wasm_bulk_memory(enable);
+        }
+        if let Some(enable) = engine.reference_types {
+            config.wasm_reference_types(enable);
+        }
+        if let Some(enable) = engine.tail_call {
+            config.wasm_tail_call(enable);
+        }
+        if let Some(enable) = engine.extended_const {
+            config.wasm_extended_const(enable);
+        }
+        if let Some(enable) = engine.threads {
+            config.wasm_threads(enable);
+        }
+        if let Some(enable) = engine.floats {
+            config.floats(enable);
+        }
+        if engine.initial_value_stack_height.is_some()
+            || engine.maximum_value_stack_height.is_some()
+            || engine.maximum_recursion_depth.is_some()
+        {
+            let default = StackLimits::default();
+            let limits = StackLimits::new(
+                engine
+                    .initial_value_stack_height
+                    .unwrap_or(default.initial_value_stack_height),
+                engine
+                    .maximum_value_stack_height
+                    .unwrap_or(default.maximum_value_stack_height),
+                engine
+                    .maximum_recursion_depth
+                    .unwrap_or(default.maximum_recursion_depth),
+            )
+            .map_err(|error| anyhow::anyhow!("invalid stack limits: {error}"))?;
+            config.set_stack_limits(limits);
+        }
+        if let Some(amount) = engine.cached_stacks {
+            config.set_cached_stacks(amount);
+        }
+        if let Some(budget) = engine.inline_budget {
+            config.inline_budget(budget);
+        }
+        Ok(config)
+    }
+
+    /// Returns the [`StoreLimits`] given to the CLI app.
+    pub fn store_limits(&self) -> StoreLimits {
+        let engine = &self.engine;
+        let mut limits =
+            StoreLimitsBuilder::new().trap_on_grow_failure(engine.trap_on_grow_failure);
+        if let Some(limit) = engine.max_memory_size {
+            limits = limits.memory_size(limit);
+        }
+        if let Some(limit) = engine.max_table_elements {
+            limits = limits.table_elements(limit);
+        }
+        if let Some(limit) = engine.max_instances {
+            limits = limits.instances(limit);
+        }
+        if let Some(limit) = engine.max_tables {
+            limits = limits.tables(limit);
+        }
+        if let Some(limit) = engine.max_memories {
+            limits = limits.memories(limit);
+        }
+        limits.build()
     }
 
     /// Pre-opens all directories given in `--dir` and returns them for use by the [`WasiCtx`].
@@ -147,13 +604,12 @@ impl Args {
     /// # Errors
     ///
     /// If any of the given directions in `--dir` cannot be opened.
-    fn preopen_dirs(&self) -> Result<Vec<(&Path, Dir)>> {
+    fn preopen_dirs(&self) -> Result<Vec<(&Path, StdDir)>> {
         self.dirs
             .iter()
             .map(|path| {
-                let dir = Dir::open_ambient_dir(path, ambient_authority()).with_context(|| {
-                    format!("failed to open directory '{path:?}' with ambient authority")
-                })?;
+                let dir = StdDir::open(path)
+                    .with_context(|| format!("failed to open directory '{path:?}'"))?;
                 Ok((path.as_ref(), dir))
             })
             .collect::<Result<Vec<_>>>()
@@ -171,7 +627,7 @@ impl Args {
                 let std_tcp_listener = std::net::TcpListener::bind(addr)
                     .with_context(|| format!("failed to bind to tcp address '{addr}'"))?;
                 std_tcp_listener.set_nonblocking(true)?;
-                Ok(TcpListener::from_std(std_tcp_listener))
+                Ok(TcpListener::new(std_tcp_listener))
             })
             .collect::<Result<Vec<_>>>()
     }
@@ -187,7 +643,7 @@ impl Args {
         // The WebAssembly filename is expected to be the first argument to WASI.
         // Note that the module name still has it's `.wasm` file extension.
         let module_name = self
-            .wasm_file
+            .wasm_file()
             .file_name()
             .and_then(OsStr::to_str)
             .unwrap_or("")
@@ -198,23 +654,59 @@ impl Args {
     }
 
     /// Creates the [`WasiCtx`] for this session.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the files given in `--stdin`, `--stdout` or `--stderr` cannot be opened.
+    /// - If any of the sockets or directories cannot be pre-opened.
     pub fn wasi_context(&self) -> Result<WasiCtx, Error> {
-        let mut wasi_builder = WasiCtxBuilder::new();
-        for KeyValue { key, value } in &self.envs {
-            wasi_builder = wasi_builder.env(key, value)?;
+        let mut wasi_builder = WasiCtxBuilder::new()
+            .envs(self.envs.iter().map(|kv| (&kv.key[..], &kv.value[..])))
+            .args(self.argv());
+        wasi_builder = match &self.stdin {
+            Some(path) => wasi_builder
+                .stdin(StdFile::new(fs::File::open(path).with_context(|| {
+                    format!("failed to open stdin file '{path:?}'")
+                })?)),
+            None => wasi_builder.stdin(Stdin),
+        };
+        wasi_builder = match &self.stdout {
+            Some(path) => wasi_builder
+                .stdout(StdFile::new(fs::File::create(path).with_context(|| {
+                    format!("failed to create stdout file '{path:?}'")
+                })?)),
+            None => wasi_builder.stdout(Stdout),
+        };
+        wasi_builder = match &self.stderr {
+            Some(path) => wasi_builder
+                .stderr(StdFile::new(fs::File::create(path).with_context(|| {
+                    format!("failed to create stderr file '{path:?}'")
+                })?)),
+            None => wasi_builder.stderr(Stderr),
+        };
+        if self.deterministic {
+            let clocks = match (self.clock_step, self.clock_fuel_rate) {
+                (_, Some(rate)) => VirtualClocks::with_fuel_rate(rate),
+                (Some(step), None) => VirtualClocks::with_step(step),
+                (None, None) => VirtualClocks::new(),
+            };
+            wasi_builder = wasi_builder
+                .clocks(clocks)
+                .random(SeededRandom::new(self.seed.unwrap_or(0)));
+        }
+        if self.trace_wasi {
+            wasi_builder = wasi_builder.tracer(StdTracer::stderr());
         }
-        wasi_builder = wasi_builder.args(&self.argv())?;
         // Add pre-opened TCP sockets.
         //
-        // Note that `num_fd` starts at 3 because the inherited `stdin`, `stdout` and `stderr`
-        // are already mapped to `0, 1, 2` respectively.
-        wasi_builder = wasi_builder.inherit_stdio();
-        for (socket, num_fd) in self.preopen_sockets()?.into_iter().zip(3..) {
-            wasi_builder = wasi_builder.preopened_socket(num_fd, socket)?;
+        // Note that sockets are assigned file descriptors starting at 3 because
+        // `stdin`, `stdout` and `stderr` are already mapped to `0, 1, 2` respectively.
+        for socket in self.preopen_sockets()? {
+            wasi_builder = wasi_builder.preopened_socket(socket);
         }
         // Add pre-opened directories.
         for (dir_name, dir) in self.preopen_dirs()? {
-            wasi_builder = wasi_builder.preopened_dir(dir, dir_name)?;
+            wasi_builder = wasi_builder.preopened_dir(dir, dir_name.to_string_lossy());
         }
         Ok(wasi_builder.build())
     }
@@ -0,0 +1,95 @@
+use crate::{
+    args::Args,
+    context::{self, StoreData},
+};
+use anyhow::{anyhow, bail, Error};
+use std::process;
+use wasmi::{
+    component::{Component, ComponentInstance, Linker, Val},
+    Engine,
+};
+use wasmi_wasi::{
+    preview1,
+    preview2::{self, WasiCtx},
+};
+
+/// The prefix of the name of the instance exporting the WASI `preview2` command entry point.
+const RUN_INTERFACE: &str = "wasi:cli/run@0.2";
+
+/// Runs the `wasi:cli/run` entry point of the Wasm component `wasm_bytes`.
+///
+/// Exits the process with the exit code of the component if it exits
+/// via `wasi:cli/exit` or returns an error from its entry point.
+///
+/// # Errors
+///
+/// - If parsing or instantiating the Wasm component failed.
+/// - If the Wasm component does not export `wasi:cli/run`.
+/// - If the execution of the Wasm component traps.
+pub fn run(args: &Args, wasm_bytes: &[u8], wasi_ctx: preview1::WasiCtx) -> Result<(), Error> {
+    if args.has_invocations() {
+        bail!("`--invoke` is not supported for Wasm components")
+    }
+    if args.repl() {
+        bail!("`--repl` is not supported for Wasm components")
+    }
+    let wasm_file = args.wasm_file();
+    let engine = Engine::new(&args.config()?);
+    let component = Component::new(&engine, wasm_bytes).map_err(|error| {
+        anyhow!("failed to parse and validate Wasm component {wasm_file:?}: {error}")
+    })?;
+    let mut store = context::new_store(
+        &engine,
+        WasiCtx::new(wasi_ctx),
+        args.fuel(),
+        args.store_limits(),
+    );
+    let mut linker = <Linker<StoreData<WasiCtx>>>::new(&engine);
+    preview2::add_to_linker(&mut linker, |data| &mut data.wasi)
+        .map_err(|error| anyhow!("failed to add WASI definitions to the linker: {error}"))?;
+    let instance = linker
+        .instantiate(&mut store, &component)
+        .map_err(|error| anyhow!("failed to instantiate the Wasm component: {error}"))?;
+    let (interface, func) = find_run(&instance)?;
+    println!("executing File({wasm_file:?})::{interface}#run() ...");
+    let mut result = [Val::Bool(false)];
+    let status = match func.call(&mut store, &[], &mut result) {
+        Ok(()) => match result {
+            [Val::Result(Ok(_))] => 0,
+            _ => 1,
+        },
+        Err(error) => match error.i32_exit_status() {
+            Some(status) => status,
+            None => bail!("failed during execution of {interface}#run: {error}"),
+        },
+    };
+    if let Some(total_fuel) = args.fuel() {
+        let consumed = store.fuel_consumed().unwrap_or_else(|| {
+            panic!("fuel metering is enabled but could not query consumed fuel")
+        });
+        let remaining = total_fuel - consumed;
+        println!("fuel consumed: {consumed}, fuel remaining: {remaining}");
+    }
+    if status != 0 {
+        process::exit(status)
+    }
+    Ok(())
+}
+
+/// Returns the name of the exported `wasi:cli/run` instance and its `run` function.
+///
+/// # Errors
+///
+/// If the component does not export a compatible `wasi:cli/run` instance.
+fn find_run(
+    instance: &ComponentInstance,
+) -> Result<(&str, &wasmi::component::ComponentFunc), Error> {
+    instance
+        .exports()
+        .filter(|name| name.starts_with(RUN_INTERFACE))
+        .find_map(|name| Some((name, instance.get_instance_func(name, "run")?)))
+        .ok_or_else(|| {
+            let exports = instance.exports().collect::<Vec<_>>().join(", ");
+            anyhow!("the Wasm component does not export `{RUN_INTERFACE}.x` but only: {exports}")
+        })
+}
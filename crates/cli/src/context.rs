@@ -1,8 +1,46 @@
-use crate::utils;
 use anyhow::{anyhow, Error};
 use std::path::Path;
-use wasmi::{CompilationMode, Config, ExternType, Func, FuncType, Instance, Module, Store};
-use wasmi_wasi::WasiCtx;
+use wasmi::{
+    Config,
+    Engine,
+    ExternType,
+    Func,
+    FuncType,
+    Global,
+    GlobalType,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    StoreLimits,
+};
+use wasmi_wasi::preview1::{self, WasiCtx};
+
+/// The data of the [`Store`] used by the Wasmi CLI application.
+pub struct StoreData<W> {
+    /// The WASI context of the guest.
+    pub wasi: W,
+    /// The resource limits applied to the [`Store`].
+    limits: StoreLimits,
+}
+
+/// Creates a new [`Store`] with the `wasi` context, `fuel` and resource `limits`.
+pub fn new_store<W: 'static>(
+    engine: &Engine,
+    wasi: W,
+    fuel: Option<u64>,
+    limits: StoreLimits,
+) -> Store<StoreData<W>> {
+    let mut store = Store::new(engine, StoreData { wasi, limits });
+    store.limiter(|data| &mut data.limits);
+    if let Some(fuel) = fuel {
+        store.add_fuel(fuel).unwrap_or_else(|error| {
+            panic!("error: fuel metering is enabled but encountered: {error}")
+        });
+    }
+    store
+}
 
 /// The [`Context`] for the Wasmi CLI application.
 ///
@@ -11,7 +49,7 @@ pub struct Context {
     /// The given Wasm module.
     module: Module,
     /// The used Wasm store.
-    store: Store<WasiCtx>,
+    store: Store<StoreData<WasiCtx>>,
     /// The Wasm module instance to operate on.
     instance: Instance,
 }
@@ -23,37 +61,33 @@ impl Context {
     ///
     /// - If parsing, validating, compiling or instantiating the Wasm module failed.
     /// - If adding WASI defintions to the linker failed.
+    /// - If the exported `_initialize` function of a WASI reactor traps.
     pub fn new(
         wasm_file: &Path,
+        wasm_bytes: &[u8],
         wasi_ctx: WasiCtx,
+        config: &Config,
         fuel: Option<u64>,
-        compilation_mode: CompilationMode,
+        limits: StoreLimits,
     ) -> Result<Self, Error> {
-        let mut config = Config::default();
-        config.wasm_tail_call(true);
-        config.wasm_extended_const(true);
-        if fuel.is_some() {
-            config.consume_fuel(true);
-        }
-        config.compilation_mode(compilation_mode);
-        let engine = wasmi::Engine::new(&config);
-        let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
-        let module = wasmi::Module::new(&engine, &mut &wasm_bytes[..]).map_err(|error| {
+        let engine = Engine::new(config);
+        let module = Module::new(&engine, &mut &wasm_bytes[..]).map_err(|error| {
             anyhow!("failed to parse and validate Wasm module {wasm_file:?}: {error}")
         })?;
-        let mut store = wasmi::Store::new(&engine, wasi_ctx);
-        if let Some(fuel) = fuel {
-            store.add_fuel(fuel).unwrap_or_else(|error| {
-                panic!("error: fuel metering is enabled but encountered: {error}")
-            });
-        }
-        let mut linker = <wasmi::Linker<WasiCtx>>::new(&engine);
-        wasmi_wasi::add_to_linker(&mut linker, |ctx| ctx)
+        let mut store = new_store(&engine, wasi_ctx, fuel, limits);
+        let mut linker = <Linker<StoreData<WasiCtx>>>::new(&engine);
+        preview1::add_to_linker(&mut linker, |data| &mut data.wasi)
             .map_err(|error| anyhow!("failed to add WASI definitions to the linker: {error}"))?;
         let instance = linker
             .instantiate(&mut store, &module)
             .and_then(|pre| pre.start(&mut store))
             .map_err(|error| anyhow!("failed to instantiate and start the Wasm module: {error}"))?;
+        // WASI reactors export `_initialize` which must run before any other export is called.
+        if let Ok(initialize) = instance.get_typed_func::<(), ()>(&store, "_initialize") {
+            initialize
+                .call(&mut store, ())
+                .map_err(|error| anyhow!("failed during execution of _initialize: {error}"))?;
+        }
         Ok(Self {
             module,
             store,
@@ -74,13 +108,37 @@ impl Context {
         })
     }
 
+    /// Returns the exported named global variables of the Wasm [`Module`].
+    ///
+    /// [`Module`]: wasmi::Module
+    pub fn exported_globals(&self) -> impl Iterator<Item = (&str, GlobalType)> {
+        self.module
+            .exports()
+            .filter_map(|export| match export.ty() {
+                ExternType::Global(global_type) => Some((export.name(), *global_type)),
+                _ => None,
+            })
+    }
+
+    /// Returns the names of the exported linear memories of the Wasm [`Module`].
+    ///
+    /// [`Module`]: wasmi::Module
+    pub fn exported_memories(&self) -> impl Iterator<Item = &str> {
+        self.module
+            .exports()
+            .filter_map(|export| match export.ty() {
+                ExternType::Memory(_) => Some(export.name()),
+                _ => None,
+            })
+    }
+
     /// Returns a shared reference to the [`Store`] of the [`Context`].
-    pub fn store(&self) -> &Store<WasiCtx> {
+    pub fn store(&self) -> &Store<StoreData<WasiCtx>> {
         &self.store
     }
 
     /// Returns an exclusive reference to the [`Store`] of the [`Context`].
-    pub fn store_mut(&mut self) -> &mut Store<WasiCtx> {
+    pub fn store_mut(&mut self) -> &mut Store<StoreData<WasiCtx>> {
         &mut self.store
     }
 
@@ -90,4 +148,18 @@ impl Context {
             .get_func(&self.store, name)
             .ok_or_else(|| anyhow!("failed to find function named {name:?} in the Wasm module"))
     }
+
+    /// Returns the exported global variable named `name` if any.
+    pub fn get_global(&self, name: &str) -> Result<Global, Error> {
+        self.instance
+            .get_global(&self.store, name)
+            .ok_or_else(|| anyhow!("failed to find global named {name:?} in the Wasm module"))
+    }
+
+    /// Returns the exported linear memory named `name` if any.
+    pub fn get_memory(&self, name: &str) -> Result<Memory, Error> {
+        self.instance
+            .get_memory(&self.store, name)
+            .ok_or_else(|| anyhow!("failed to find memory named {name:?} in the Wasm module"))
+    }
 }
@@ -1,45 +1,99 @@
 use crate::{
-    args::Args,
+    args::{Args, Command, Invocation},
     display::{DisplayExportedFuncs, DisplayFuncType, DisplaySequence, DisplayValue},
 };
 use anyhow::{anyhow, bail, Error, Result};
-use clap::Parser;
 use context::Context;
 use std::{path::Path, process};
 use wasmi::{Func, FuncType, Value};
 
 mod args;
+mod component;
 mod context;
 mod display;
+mod repl;
 mod utils;
+mod wast;
 
 #[cfg(test)]
 mod tests;
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let args = Args::from_env();
+    if let Some(Command::Wast { file }) = args.subcommand() {
+        return wast::run(&args, file);
+    }
     let wasm_file = args.wasm_file();
     let wasi_ctx = args.wasi_context()?;
-    let mut ctx = Context::new(wasm_file, wasi_ctx, args.fuel(), args.compilation_mode())?;
-    let (func_name, func) = get_invoked_func(&args, &ctx)?;
+    let wasm_bytes = utils::read_wasm_or_wat(wasm_file)?;
+    if wasmi::component::is_component(&wasm_bytes) {
+        return component::run(&args, &wasm_bytes, wasi_ctx);
+    }
+    let mut ctx = Context::new(
+        wasm_file,
+        &wasm_bytes,
+        wasi_ctx,
+        &args.config()?,
+        args.fuel(),
+        args.store_limits(),
+    )?;
+    if args.repl() {
+        return repl::run(&args, &wasm_bytes, ctx);
+    }
+    let invocations = args.invocations()?;
+    if invocations.is_empty() {
+        // The positional arguments are the WASI arguments of the entry point.
+        let (func_name, func) = get_entry_point(&ctx)?;
+        return invoke(&args, &mut ctx, &func_name, func, args.func_args(), false);
+    }
+    for Invocation {
+        name,
+        args: func_args,
+    } in &invocations
+    {
+        let func = ctx
+            .get_func(name)
+            .map_err(|error| anyhow!("{error}\n\n{}", DisplayExportedFuncs::from(&ctx)))?;
+        invoke(&args, &mut ctx, name, func, func_args, true)?;
+    }
+    Ok(())
+}
+
+/// Invokes `func` named `func_name` with the decoded `func_args` and prints its results.
+///
+/// If `check_arity` is `true` the amount of `func_args` must match the function parameters.
+/// Exits the process if the Wasm guest exits with an exit code.
+///
+/// # Errors
+///
+/// - If the `func_args` cannot be decoded or are too many or too few.
+/// - If the execution of `func` traps.
+fn invoke(
+    args: &Args,
+    ctx: &mut Context,
+    func_name: &str,
+    func: Func,
+    func_args: &[String],
+    check_arity: bool,
+) -> Result<(), Error> {
     let ty = func.ty(ctx.store());
-    let func_args = utils::decode_func_args(&ty, args.func_args())?;
+    let decoded_args = utils::decode_func_args(&ty, func_args)?;
     let mut func_results = utils::prepare_func_results(&ty);
-    typecheck_args(&func_name, &ty, &func_args)?;
+    typecheck_args(func_name, &ty, &decoded_args)?;
 
-    print_execution_start(args.wasm_file(), &func_name, &func_args);
-    if args.invoked().is_some() && ty.params().len() != args.func_args().len() {
+    print_execution_start(args.wasm_file(), func_name, &decoded_args);
+    if check_arity && ty.params().len() != func_args.len() {
         bail!(
             "invalid amount of arguments given to function {}. expected {} but received {}",
-            DisplayFuncType::new(&func_name, &ty),
+            DisplayFuncType::new(func_name, &ty),
             ty.params().len(),
-            args.func_args().len()
+            func_args.len()
         )
     }
 
-    match func.call(ctx.store_mut(), &func_args, &mut func_results) {
+    match func.call(ctx.store_mut(), &decoded_args, &mut func_results) {
         Ok(()) => {
-            print_remaining_fuel(&args, &ctx);
+            print_remaining_fuel(args, ctx);
             print_pretty_results(&func_results);
             Ok(())
         }
@@ -48,7 +102,7 @@ fn main() -> Result<()> {
                 // We received an exit code from the WASI program,
                 // therefore we exit with the same exit code after
                 // pretty printing the results.
-                print_remaining_fuel(&args, &ctx);
+                print_remaining_fuel(args, ctx);
                 print_pretty_results(&func_results);
                 process::exit(exit_code)
             }
@@ -89,35 +143,23 @@ fn typecheck_args(func_name: &str, func_ty: &FuncType, args: &[Value]) -> Result
     Ok(())
 }
 
-/// Returns the invoked named function or the WASI entry point to the Wasm module if any.
+/// Returns the WASI entry point to the Wasm module if any.
 ///
 /// # Errors
 ///
-/// - If the function given via `--invoke` could not be found in the Wasm module.
-/// - If `--invoke` was not given and no WASI entry points were exported.
-fn get_invoked_func(args: &Args, ctx: &Context) -> Result<(String, Func), Error> {
-    match args.invoked() {
-        Some(func_name) => {
-            let func = ctx
-                .get_func(func_name)
-                .map_err(|error| anyhow!("{error}\n\n{}", DisplayExportedFuncs::from(ctx)))?;
-            let func_name = func_name.into();
-            Ok((func_name, func))
-        }
-        None => {
-            // No `--invoke` flag was provided so we try to find
-            // the conventional WASI entry points `""` and `"_start"`.
-            if let Ok(func) = ctx.get_func("") {
-                Ok(("".into(), func))
-            } else if let Ok(func) = ctx.get_func("_start") {
-                Ok(("_start".into(), func))
-            } else {
-                bail!(
-                    "did not specify `--invoke` and could not find exported WASI entry point functions\n\n{}",
-                    DisplayExportedFuncs::from(ctx)
-                )
-            }
-        }
+/// If no WASI entry points were exported.
+fn get_entry_point(ctx: &Context) -> Result<(String, Func), Error> {
+    // No `--invoke` flag was provided so we try to find
+    // the conventional WASI entry points `""` and `"_start"`.
+    if let Ok(func) = ctx.get_func("") {
+        Ok(("".into(), func))
+    } else if let Ok(func) = ctx.get_func("_start") {
+        Ok(("_start".into(), func))
+    } else {
+        bail!(
+            "did not specify `--invoke` and could not find exported WASI entry point functions\n\n{}",
+            DisplayExportedFuncs::from(ctx)
+        )
     }
 }
 
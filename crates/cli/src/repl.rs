@@ -0,0 +1,230 @@
+use crate::{
+    args::Args,
+    context::Context,
+    display::{DisplayExportedFuncs, DisplaySequence, DisplayValue, DisplayValueType},
+    print_pretty_results,
+    print_remaining_fuel,
+    utils,
+};
+use anyhow::{anyhow, bail, Error};
+use std::io::{self, BufRead, Write};
+use wasmi::Mutability;
+
+/// The help text listing all commands of the REPL.
+const HELP: &str = "\
+commands:
+  exports                          list the exported functions, globals and memories
+  call <FUNCTION> [<ARGS>]*        invoke an exported function with the given arguments
+  global <NAME> [<VALUE>]          read or write an exported global variable
+  memory <OFFSET> <LEN> [<NAME>]   hex-dump a range of an exported memory (default: \"memory\")
+  fuel                             show the consumed and remaining fuel
+  reinstantiate                    discard all state and instantiate the Wasm module again
+  help                             show this help text
+  quit                             leave the REPL";
+
+/// The number of bytes shown per line by the `memory` command.
+const BYTES_PER_LINE: usize = 16;
+
+/// Runs the interactive REPL for the Wasm module `wasm_bytes` reading commands from stdin.
+///
+/// Errors of individual commands are printed and do not end the REPL.
+///
+/// # Errors
+///
+/// If reading from stdin or writing to stdout failed.
+pub fn run(args: &Args, wasm_bytes: &[u8], mut ctx: Context) -> Result<(), Error> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            println!();
+            return Ok(());
+        };
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            continue;
+        };
+        let operands = words.collect::<Vec<_>>();
+        let result = match command {
+            "exports" => {
+                print_exports(&ctx);
+                Ok(())
+            }
+            "call" => call(args, &mut ctx, &operands),
+            "global" => global(&mut ctx, &operands),
+            "memory" => memory(&ctx, &operands),
+            "fuel" => fuel(args, &ctx),
+            "reinstantiate" => reinstantiate(args, wasm_bytes).map(|new_ctx| ctx = new_ctx),
+            "help" => {
+                println!("{HELP}");
+                Ok(())
+            }
+            "quit" | "exit" => return Ok(()),
+            _ => Err(anyhow!(
+                "unknown command `{command}`, type `help` for a list of commands"
+            )),
+        };
+        if let Err(error) = result {
+            println!("error: {error}");
+        }
+    }
+}
+
+/// Prints the exported functions, global variables and linear memories.
+fn print_exports(ctx: &Context) {
+    print!("{}", DisplayExportedFuncs::from(ctx));
+    for (name, global_type) in ctx.exported_globals() {
+        let mutability = match global_type.mutability() {
+            Mutability::Const => "",
+            Mutability::Var => "mut ",
+        };
+        let content = global_type.content();
+        let content = DisplayValueType::from(&content);
+        println!(" - global {name}: {mutability}{content}");
+    }
+    for name in ctx.exported_memories() {
+        println!(" - memory {name}");
+    }
+}
+
+/// Invokes the exported function `operands[0]` with the remaining `operands` as arguments.
+///
+/// # Errors
+///
+/// - If the function does not exist or the arguments do not match its parameters.
+/// - If the execution of the function traps or exits.
+fn call(args: &Args, ctx: &mut Context, operands: &[&str]) -> Result<(), Error> {
+    let Some((name, func_args)) = operands.split_first() else {
+        bail!("missing function name: call <FUNCTION> [<ARGS>]*")
+    };
+    let func = ctx.get_func(name)?;
+    let ty = func.ty(ctx.store());
+    if ty.params().len() != func_args.len() {
+        bail!(
+            "invalid amount of arguments given to function {name}. expected {} but received {}",
+            ty.params().len(),
+            func_args.len()
+        )
+    }
+    let func_args = func_args.iter().map(|&arg| arg.into()).collect::<Vec<_>>();
+    let func_args = utils::decode_func_args(&ty, &func_args)?;
+    let mut func_results = utils::prepare_func_results(&ty);
+    if let Err(error) = func.call(ctx.store_mut(), &func_args, &mut func_results) {
+        if let Some(exit_code) = error.i32_exit_status() {
+            bail!("{name} exited with status {exit_code}")
+        }
+        bail!("failed during execution of {name}: {error}")
+    }
+    print_remaining_fuel(args, ctx);
+    print_pretty_results(&func_results);
+    Ok(())
+}
+
+/// Prints the value of the exported global `operands[0]` or writes `operands[1]` to it.
+///
+/// # Errors
+///
+/// - If the global does not exist.
+/// - If the new value cannot be decoded or the global is immutable.
+fn global(ctx: &mut Context, operands: &[&str]) -> Result<(), Error> {
+    let global = match operands {
+        [name] | [name, _] => ctx.get_global(name)?,
+        _ => bail!("invalid operands: global <NAME> [<VALUE>]"),
+    };
+    if let [_, value] = operands {
+        let value = utils::decode_value(global.ty(ctx.store()).content(), value)?;
+        global
+            .set(ctx.store_mut(), value)
+            .map_err(|error| anyhow!("failed to write global: {error}"))?;
+    }
+    println!("{}", DisplayValue::from(&global.get(ctx.store())));
+    Ok(())
+}
+
+/// Prints a hex-dump of `operands[1]` bytes at offset `operands[0]` of an exported memory.
+///
+/// # Errors
+///
+/// - If the memory does not exist.
+/// - If the range is out of bounds of the memory.
+fn memory(ctx: &Context, operands: &[&str]) -> Result<(), Error> {
+    let (offset, len, name) = match operands {
+        [offset, len] => (offset, len, "memory"),
+        [offset, len, name] => (offset, len, *name),
+        _ => bail!("invalid operands: memory <OFFSET> <LEN> [<NAME>]"),
+    };
+    let offset = parse_usize(offset)?;
+    let len = parse_usize(len)?;
+    let memory = ctx.get_memory(name)?;
+    let size = memory
+        .current_pages(ctx.store())
+        .to_bytes()
+        .unwrap_or(usize::MAX);
+    if offset.checked_add(len).filter(|&end| end <= size).is_none() {
+        bail!("range {offset}..{offset}+{len} is out of bounds for memory {name:?} of {size} bytes")
+    }
+    // Note: We read a copy of the bytes since shared memories cannot be borrowed.
+    let mut bytes = vec![0x00_u8; len];
+    memory
+        .read(ctx.store(), offset, &mut bytes)
+        .map_err(|error| anyhow!("failed to read memory: {error}"))?;
+    for (n, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let hex = DisplaySequence::new(" ", line.iter().map(|byte| format!("{byte:02x}")));
+        let ascii = line
+            .iter()
+            .map(|&byte| match byte {
+                0x20..=0x7E => char::from(byte),
+                _ => '.',
+            })
+            .collect::<String>();
+        let width = BYTES_PER_LINE * 3 - 1;
+        println!(
+            "{:08x}: {:width$}  |{ascii}|",
+            offset + n * BYTES_PER_LINE,
+            hex.to_string()
+        );
+    }
+    Ok(())
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal `usize`.
+fn parse_usize(input: &str) -> Result<usize, Error> {
+    match input.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => input.parse(),
+    }
+    .map_err(|error| anyhow!("failed to parse {input} as offset or length: {error}"))
+}
+
+/// Prints the consumed and remaining fuel.
+///
+/// # Errors
+///
+/// If fuel metering is disabled.
+fn fuel(args: &Args, ctx: &Context) -> Result<(), Error> {
+    if args.fuel().is_none() {
+        bail!("fuel metering is disabled, enable it via `--fuel`")
+    }
+    print_remaining_fuel(args, ctx);
+    Ok(())
+}
+
+/// Creates a new [`Context`] with a fresh WASI context, store and instance.
+///
+/// # Errors
+///
+/// If creating the WASI context or instantiating the Wasm module failed.
+fn reinstantiate(args: &Args, wasm_bytes: &[u8]) -> Result<Context, Error> {
+    let ctx = Context::new(
+        args.wasm_file(),
+        wasm_bytes,
+        args.wasi_context()?,
+        &args.config()?,
+        args.fuel(),
+        args.store_limits(),
+    )?;
+    println!("reinstantiated {:?}", args.wasm_file());
+    Ok(ctx)
+}
@@ -56,37 +56,42 @@ pub fn decode_func_args(ty: &FuncType, args: &[String]) -> Result<Box<[Value]>,
         .zip(args)
         .enumerate()
         .map(|(n, (param_type, arg))| {
-            macro_rules! make_err {
-                () => {
-                    |_| {
-                        anyhow!(
-                            "failed to parse function argument \
-                            {arg} at index {n} as {}",
-                            DisplayValueType::from(param_type)
-                        )
-                    }
-                };
-            }
-            match param_type {
-                ValueType::I32 => arg.parse::<i32>().map(Value::from).map_err(make_err!()),
-                ValueType::I64 => arg.parse::<i64>().map(Value::from).map_err(make_err!()),
-                ValueType::F32 => arg
-                    .parse::<f32>()
-                    .map(F32::from)
-                    .map(Value::from)
-                    .map_err(make_err!()),
-                ValueType::F64 => arg
-                    .parse::<f64>()
-                    .map(F64::from)
-                    .map(Value::from)
-                    .map_err(make_err!()),
-                ValueType::FuncRef => {
-                    bail!("the wasmi CLI cannot take arguments of type funcref")
-                }
-                ValueType::ExternRef => {
-                    bail!("the wasmi CLI cannot take arguments of type externref")
-                }
-            }
+            decode_value(*param_type, arg)
+                .map_err(|error| anyhow!("{error} for function argument at index {n}"))
         })
         .collect::<Result<Box<[_]>, _>>()
 }
+
+/// Decodes the given `arg` as [`Value`] of [`ValueType`] `ty`.
+///
+/// # Errors
+///
+/// - If `arg` cannot be parsed as a value of [`ValueType`] `ty`.
+/// - If `ty` is the unsupported [`ValueType::FuncRef`] or [`ValueType::ExternRef`].
+pub fn decode_value(ty: ValueType, arg: &str) -> Result<Value, Error> {
+    macro_rules! make_err {
+        () => {
+            |_| anyhow!("failed to parse {arg} as {}", DisplayValueType::from(&ty))
+        };
+    }
+    match ty {
+        ValueType::I32 => arg.parse::<i32>().map(Value::from).map_err(make_err!()),
+        ValueType::I64 => arg.parse::<i64>().map(Value::from).map_err(make_err!()),
+        ValueType::F32 => arg
+            .parse::<f32>()
+            .map(F32::from)
+            .map(Value::from)
+            .map_err(make_err!()),
+        ValueType::F64 => arg
+            .parse::<f64>()
+            .map(F64::from)
+            .map(Value::from)
+            .map_err(make_err!()),
+        ValueType::FuncRef => {
+            bail!("the wasmi CLI cannot take arguments of type funcref")
+        }
+        ValueType::ExternRef => {
+            bail!("the wasmi CLI cannot take arguments of type externref")
+        }
+    }
+}
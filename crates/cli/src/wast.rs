@@ -0,0 +1,29 @@
+use crate::args::Args;
+use anyhow::{anyhow, bail, Error};
+use std::{fs, path::Path};
+use wasmi_wast::{run_wast, TestContext, TestDescriptor};
+
+/// Runs the directives of the `.wast` script file `wast_file`.
+///
+/// Prints a summary of the executed directives derived from the [`TestProfile`].
+///
+/// # Errors
+///
+/// - If the `.wast` file cannot be read or parsed.
+/// - If any of the directives fails.
+///
+/// [`TestProfile`]: wasmi_wast::TestProfile
+pub fn run(args: &Args, wast_file: &Path) -> Result<(), Error> {
+    let wast = fs::read_to_string(wast_file)
+        .map_err(|error| anyhow!("failed to read .wast file {wast_file:?}: {error}"))?;
+    let path = wast_file.to_string_lossy();
+    let test = TestDescriptor::new(&path, &wast);
+    let mut context = TestContext::new(&test, args.config()?);
+    let result = run_wast(&mut context);
+    print!("{}", context.profile());
+    if let Err(error) = result {
+        bail!("{error}")
+    }
+    println!("all directives of {wast_file:?} passed");
+    Ok(())
+}
@@ -30,6 +30,215 @@ fn test_proc_exit() {
     assert.failure().code(1);
 }
 
+#[test]
+#[cfg(unix)]
+fn test_preopen_dir() {
+    let sandbox = std::env::temp_dir().join(format!("wasmi_cli_dir_{}", std::process::id()));
+    let dir = sandbox.join("dir");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("inside.txt"), "inside\n").unwrap();
+    std::fs::write(sandbox.join("outside.txt"), "outside\n").unwrap();
+    std::os::unix::fs::symlink("../outside.txt", dir.join("link")).unwrap();
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--dir")
+        .arg(&dir)
+        .arg(get_bin_path("preopen_dir"))
+        .assert();
+    let output = assert.get_output().clone();
+    std::fs::remove_dir_all(&sandbox).unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.ends_with(b"\ninside\n"));
+    assert!(!contains_slice(&output.stdout, b"outside"));
+}
+
+#[test]
+fn test_stdout_redirect() {
+    let stdout = std::env::temp_dir().join(format!("wasmi_cli_stdout_{}", std::process::id()));
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--stdout")
+        .arg(&stdout)
+        .arg(get_bin_path("simple_print"))
+        .assert();
+    let output = assert.get_output();
+    let redirected = std::fs::read(&stdout).unwrap();
+    std::fs::remove_file(&stdout).unwrap();
+    assert!(output.status.success());
+    assert!(!contains_slice(&output.stdout, b"Hello World"));
+    assert_eq!(redirected, b"Hello World\n");
+}
+
+#[test]
+fn test_deterministic() {
+    let run = |seed: &str| {
+        let mut cmd = get_cmd();
+        let assert = cmd
+            .args(["--deterministic", "--seed", seed, "--clock-step", "1000"])
+            .arg(get_bin_path("clock_random"))
+            .assert()
+            .success();
+        let stdout = assert.get_output().stdout.clone();
+        let start = stdout
+            .windows(24)
+            .position(|window| window[..8] == 0_u64.to_le_bytes())
+            .expect("missing clock output");
+        stdout[start..start + 24].to_vec()
+    };
+    let output = run("42");
+    assert_eq!(&output[..8], &0_u64.to_le_bytes());
+    assert_eq!(&output[8..16], &1000_u64.to_le_bytes());
+    assert_eq!(output, run("42"));
+    assert_ne!(output[16..], run("7")[16..]);
+}
+
+#[test]
+fn test_trace_wasi() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--trace-wasi")
+        .arg(get_bin_path("simple_print"))
+        .assert()
+        .success();
+    let stderr = std::str::from_utf8(&assert.get_output().stderr).unwrap();
+    assert!(
+        stderr.starts_with("fd_write(fd=1, iovs=[12], nwritten=20) = Success (errno 0) <"),
+        "unexpected trace: {stderr}"
+    );
+}
+
+#[test]
+fn test_component() {
+    let mut cmd = get_cmd();
+    let assert = cmd.arg(get_bin_path("component_print")).assert().success();
+    assert!(contains_slice(
+        &assert.get_output().stdout,
+        b"Hello Component\n"
+    ));
+}
+
+#[test]
+fn test_reactor_invocations() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg(get_bin_path("reactor"))
+        .args(["--invoke", "add", "5", "--invoke", "add", "-3"])
+        .assert()
+        .success();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    assert!(
+        stdout.contains("::add(5) ...\n15\n"),
+        "unexpected output: {stdout}"
+    );
+    assert!(
+        stdout.contains("::add(-3) ...\n12\n"),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn test_reactor_invoke_script() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg(get_bin_path("reactor"))
+        .arg("--invoke-script")
+        .arg("tests/wats/reactor.invoke")
+        .assert()
+        .success();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    assert!(
+        stdout.ends_with(
+            "::add(5) ...\n15\nexecuting File(\"tests/wats/reactor.wat\")::add(-3) ...\n12\n"
+        ),
+        "unexpected output: {stdout}"
+    );
+}
+
+#[test]
+fn test_repl() {
+    let mut cmd = get_cmd();
+    let assert = cmd
+        .arg("--repl")
+        .arg("--fuel=1000")
+        .arg(get_bin_path("reactor"))
+        .write_stdin(
+            "exports\n\
+            call add 5\n\
+            global counter 100\n\
+            call add 1\n\
+            memory 16 5\n\
+            reinstantiate\n\
+            global counter\n\
+            unknown\n",
+        )
+        .assert()
+        .success();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    let expected = [
+        " - fn add(i32) -> i32\n",
+        " - global counter: mut i32\n",
+        " - memory memory\n",
+        "> fuel consumed: ",
+        "15\n> 100\n> fuel consumed: ",
+        "101\n> 00000010: 48 65 6c 6c 6f",
+        "|Hello|\n> reinstantiated ",
+        "> 10\n> error: unknown command `unknown`",
+    ];
+    for expected in expected {
+        assert!(
+            stdout.contains(expected),
+            "missing {expected:?} in output: {stdout}"
+        );
+    }
+}
+
+#[test]
+fn test_wast() {
+    let mut cmd = get_cmd();
+    let assert = cmd.args(["wast", "tests/wats/add.wast"]).assert().success();
+    let stdout = std::str::from_utf8(&assert.get_output().stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "directives: 6\n \
+            - module: 2\n \
+            - register: 1\n \
+            - assert_trap: 1\n \
+            - assert_return: 2\n\
+        all directives of \"tests/wats/add.wast\" passed\n"
+    );
+}
+
+#[test]
+fn test_store_limits() {
+    let grow = |limit: &str| {
+        let mut cmd = get_cmd();
+        let assert = cmd
+            .args(["--max-memory-size", limit])
+            .arg(get_bin_path("engine_config"))
+            .args(["--invoke", "grow", "1"])
+            .assert()
+            .success();
+        String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+    };
+    assert!(grow("131072").ends_with("\n1\n"));
+    assert!(grow("65536").ends_with("\n-1\n"));
+}
+
+#[test]
+fn test_wasm_proposal_flags() {
+    let mut cmd = get_cmd();
+    cmd.arg(get_bin_path("engine_config"))
+        .args(["--invoke", "tail", "7"])
+        .assert()
+        .success();
+    let mut cmd = get_cmd();
+    cmd.args(["--wasm-tail-call", "false"])
+        .arg(get_bin_path("engine_config"))
+        .args(["--invoke", "tail", "7"])
+        .assert()
+        .failure();
+}
+
 /// UTILS
 
 /// gets the path to a wasm binary given it's name
@@ -0,0 +1,21 @@
+;; Test Notes
+;; Tests running `.wast` scripts via `wasmi_cli wast`.
+
+(module $adder
+  (func (export "add") (param i32 i32) (result i32)
+    (i32.add (local.get 0) (local.get 1))
+  )
+)
+(register "adder" $adder)
+
+(module
+  (import "adder" "add" (func $add (param i32 i32) (result i32)))
+  (func (export "add3") (param i32 i32 i32) (result i32)
+    (call $add (call $add (local.get 0) (local.get 1)) (local.get 2))
+  )
+  (func (export "trap") unreachable)
+)
+
+(assert_return (invoke "add3" (i32.const 1) (i32.const 2) (i32.const 3)) (i32.const 6))
+(assert_return (invoke $adder "add" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_trap (invoke "trap") "unreachable")
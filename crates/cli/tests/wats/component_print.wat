@@ -0,0 +1,64 @@
+(component
+    (import "wasi:io/error@0.2.0" (instance $error
+        (export "error" (type (sub resource)))
+    ))
+    (alias export $error "error" (type $error))
+    (import "wasi:io/streams@0.2.0" (instance $streams
+        (export "output-stream" (type $output-stream (sub resource)))
+        (alias outer 1 $error (type $error))
+        (type $stream-error (variant
+            (case "last-operation-failed" (own $error))
+            (case "closed")
+        ))
+        (export "[method]output-stream.blocking-write-and-flush" (func
+            (param "self" (borrow $output-stream))
+            (param "contents" (list u8))
+            (result (result (error $stream-error)))
+        ))
+    ))
+    (alias export $streams "output-stream" (type $output-stream))
+    (alias export $streams "[method]output-stream.blocking-write-and-flush" (func $write))
+    (import "wasi:cli/stdout@0.2.0" (instance $stdout
+        (alias outer 1 $output-stream (type $output-stream))
+        (export "get-stdout" (func (result (own $output-stream))))
+    ))
+    (alias export $stdout "get-stdout" (func $get-stdout))
+
+    (core module $memory
+        (memory (export "memory") 1)
+    )
+    (core instance $memory (instantiate $memory))
+    (alias core export $memory "memory" (core memory $mem))
+    (core func $write (canon lower (func $write) (memory $mem)))
+    (core func $get-stdout (canon lower (func $get-stdout)))
+    (core func $drop (canon resource.drop $output-stream))
+
+    (core module $main
+        (import "env" "memory" (memory 1))
+        (import "wasi" "write" (func $write (param i32 i32 i32 i32)))
+        (import "wasi" "get-stdout" (func $get-stdout (result i32)))
+        (import "wasi" "drop" (func $drop (param i32)))
+        (data (i32.const 16) "Hello Component\n")
+        (func (export "run") (result i32)
+            (local $stdout i32)
+            (local.set $stdout (call $get-stdout))
+            (call $write (local.get $stdout) (i32.const 16) (i32.const 16) (i32.const 32))
+            (call $drop (local.get $stdout))
+            ;; Succeed if the write succeeded.
+            (i32.load8_u (i32.const 32))
+        )
+    )
+    (core instance $main
+        (instantiate $main
+            (with "env" (instance (export "memory" (memory $mem))))
+            (with "wasi" (instance
+                (export "write" (func $write))
+                (export "get-stdout" (func $get-stdout))
+                (export "drop" (func $drop))
+            ))
+        )
+    )
+    (func $run (result (result)) (canon lift (core func $main "run")))
+    (instance $run (export "run" (func $run)))
+    (export "wasi:cli/run@0.2.0" (instance $run))
+)
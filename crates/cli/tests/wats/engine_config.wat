@@ -0,0 +1,15 @@
+;; Test Notes
+;; Tests the CLI flags for the engine configuration and store limits.
+
+(module
+  (memory 1)
+  (func (export "grow") (param i32) (result i32)
+    (memory.grow (local.get 0))
+  )
+  (func $id (param i32) (result i32)
+    (local.get 0)
+  )
+  (func (export "tail") (param i32) (result i32)
+    (return_call $id (local.get 0))
+  )
+)
@@ -0,0 +1,47 @@
+;; Test Notes
+;; Tests that the directory pre-opened via --dir is accessible
+;; but that paths escaping it are rejected, even via symbolic links.
+;; Exits with 2 if `../outside.txt` could be opened and with 3 if `link` could be opened.
+
+(module
+  (import "wasi_snapshot_preview1" "path_open" (func $path_open
+    (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read" (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_write" (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+
+  (memory (export "memory") 1)
+
+  (data (i32.const 32) "inside.txt")
+  (data (i32.const 48) "../outside.txt")
+  (data (i32.const 64) "link")
+
+  (func $open (param $path i32) (param $len i32) (result i32)
+    (call $path_open
+      (i32.const 3) ;; fd of the pre-opened directory
+      (i32.const 1) ;; follow symbolic links
+      (local.get $path)
+      (local.get $len)
+      (i32.const 0) ;; oflags
+      (i64.const 2) ;; rights: fd_read
+      (i64.const 0) ;; rights inheriting
+      (i32.const 0) ;; fdflags
+      (i32.const 0) ;; opened fd
+    )
+  )
+
+  (func $main (export "")
+    ;; read `inside.txt` and print its contents
+    (if (call $open (i32.const 32) (i32.const 10))
+      (then (call $proc_exit (i32.const 1))))
+    (i32.store (i32.const 8) (i32.const 128)) ;; iovec base
+    (i32.store (i32.const 12) (i32.const 64)) ;; iovec length
+    (drop (call $fd_read (i32.load (i32.const 0)) (i32.const 8) (i32.const 1) (i32.const 12)))
+    (drop (call $fd_write (i32.const 1) (i32.const 8) (i32.const 1) (i32.const 16)))
+    ;; paths escaping the pre-opened directory must be rejected
+    (if (i32.eqz (call $open (i32.const 48) (i32.const 14)))
+      (then (call $proc_exit (i32.const 2))))
+    (if (i32.eqz (call $open (i32.const 64) (i32.const 4)))
+      (then (call $proc_exit (i32.const 3))))
+  )
+)
@@ -0,0 +1,17 @@
+;; Test Notes
+;; Tests that `_initialize` of WASI reactors runs before
+;; multiple invocations that share the same instance.
+;; Also used to test the `--repl` commands.
+
+(module
+  (global $counter (export "counter") (mut i32) (i32.const 0))
+  (memory (export "memory") 1)
+  (data (i32.const 16) "Hello")
+  (func (export "_initialize")
+    (global.set $counter (i32.const 10))
+  )
+  (func (export "add") (param i32) (result i32)
+    (global.set $counter (i32.add (global.get $counter) (local.get 0)))
+    (global.get $counter)
+  )
+)
@@ -291,6 +291,14 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// Attempt to execute an atomic operation on an address that is not
+    /// naturally aligned to the access width of the atomic operation.
+    UnalignedAtomic,
+
+    /// Attempt to execute `memory.atomic.wait32` or `memory.atomic.wait64`
+    /// on a linear memory that is not shared.
+    AtomicWaitNonSharedMemory,
 }
 
 impl TrapCode {
@@ -313,6 +321,8 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::UnalignedAtomic => "unaligned atomic",
+            Self::AtomicWaitNonSharedMemory => "expected shared memory",
         }
     }
 }
@@ -0,0 +1,710 @@
+//! Implementation of the `bindgen!` macro.
+//!
+//! Parses a small WIT-like interface definition and generates typed host and guest
+//! bindings that pass values according to the canonical ABI of the component model.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::path::PathBuf;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident,
+    LitStr,
+    Token,
+};
+
+/// The input of the `bindgen!` macro.
+pub enum Input {
+    /// The interface is read from a file relative to `CARGO_MANIFEST_DIR`.
+    Path(LitStr),
+    /// The interface is given as string literal.
+    Inline(LitStr),
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: LitStr = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        match key.to_string().as_str() {
+            "path" => Ok(Self::Path(value)),
+            "inline" => Ok(Self::Inline(value)),
+            _ => Err(syn::Error::new(
+                key.span(),
+                "expected either `path = \"...\"` or `inline = \"...\"`",
+            )),
+        }
+    }
+}
+
+/// Expands the `bindgen!` macro for the given `input`.
+pub fn expand(input: Input) -> syn::Result<TokenStream2> {
+    let (source, span, track) = match input {
+        Input::Inline(lit) => (lit.value(), lit.span(), quote! {}),
+        Input::Path(lit) => {
+            let root = std::env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            let path = root.join(lit.value());
+            let source = std::fs::read_to_string(&path).map_err(|error| {
+                syn::Error::new(
+                    lit.span(),
+                    format!("failed to read `{}`: {error}", path.display()),
+                )
+            })?;
+            let path = path.display().to_string();
+            // Makes the compiler rebuild the bindings whenever the interface file changes.
+            let track = quote! { const _: &str = ::core::include_str!(#path); };
+            (source, lit.span(), track)
+        }
+    };
+    let world = Parser::new(&source)
+        .parse_world()
+        .map_err(|error| syn::Error::new(span, error))?;
+    let bindings = Generator::new(&world, span).generate()?;
+    Ok(quote! {
+        #track
+        #bindings
+    })
+}
+
+/// A type of the interface definition.
+#[derive(Debug, Clone)]
+enum Type {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    S8,
+    S16,
+    S32,
+    S64,
+    F32,
+    F64,
+    Char,
+    String,
+    List(Box<Type>),
+    Tuple(Vec<Type>),
+    Record(String),
+}
+
+/// A named and typed function parameter or record field.
+#[derive(Debug)]
+struct Field {
+    name: String,
+    ty: Type,
+}
+
+/// A record type definition.
+#[derive(Debug)]
+struct Record {
+    name: String,
+    fields: Vec<Field>,
+}
+
+/// An imported or exported function.
+#[derive(Debug)]
+struct Function {
+    name: String,
+    params: Vec<Field>,
+    result: Option<Type>,
+}
+
+/// A parsed `world` definition.
+#[derive(Debug)]
+struct World {
+    name: String,
+    records: Vec<Record>,
+    imports: Vec<Function>,
+    exports: Vec<Function>,
+}
+
+/// A lexical token of the interface definition.
+#[derive(Debug, Clone, PartialEq)]
+enum Lexeme {
+    Ident(String),
+    Punct(&'static str),
+}
+
+/// A hand written recursive descent parser for the supported WIT subset.
+struct Parser {
+    /// The tokens and the line they are located at.
+    tokens: Vec<(Lexeme, usize)>,
+    /// The position of the next token.
+    pos: usize,
+}
+
+impl Parser {
+    /// Creates a new [`Parser`] for the `source` interface definition.
+    fn new(source: &str) -> Self {
+        const PUNCTS: [&str; 11] = ["->", "{", "}", "(", ")", "<", ">", ":", ";", ",", "="];
+        let mut tokens = Vec::new();
+        for (n, line) in source.lines().enumerate() {
+            let mut rest = line.split("//").next().unwrap_or_default().trim_start();
+            while !rest.is_empty() {
+                if let Some(punct) = PUNCTS.into_iter().find(|punct| rest.starts_with(punct)) {
+                    tokens.push((Lexeme::Punct(punct), n + 1));
+                    rest = &rest[punct.len()..];
+                } else {
+                    let end = rest
+                        .char_indices()
+                        .find(|&(i, c)| {
+                            !(c.is_alphanumeric() || c == '-' || c == '%' || c == '_')
+                                || rest[i..].starts_with("->")
+                        })
+                        .map_or(rest.len(), |(i, _)| i)
+                        .max(1);
+                    // Note: `%` escapes identifiers that would otherwise be keywords.
+                    let ident = rest[..end].trim_start_matches('%');
+                    tokens.push((Lexeme::Ident(ident.into()), n + 1));
+                    rest = &rest[end..];
+                }
+                rest = rest.trim_start();
+            }
+        }
+        Self { tokens, pos: 0 }
+    }
+
+    /// Returns an error message for the current position.
+    fn error(&self, message: impl Into<String>) -> String {
+        match self.tokens.get(self.pos) {
+            Some((token, line)) => format!("{} at line {line}, found {token:?}", message.into()),
+            None => format!("{} at end of input", message.into()),
+        }
+    }
+
+    /// Returns the next token without consuming it.
+    fn peek(&self) -> Option<&Lexeme> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    /// Consumes the next token if it is the `punct`.
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Lexeme::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Consumes the `punct` token or returns an error.
+    fn expect(&mut self, punct: &str) -> Result<(), String> {
+        if !self.eat(punct) {
+            return Err(self.error(format!("expected `{punct}`")));
+        }
+        Ok(())
+    }
+
+    /// Consumes an identifier and returns it.
+    fn ident(&mut self) -> Result<String, String> {
+        let Some(Lexeme::Ident(ident)) = self.peek().cloned() else {
+            return Err(self.error("expected identifier"));
+        };
+        let valid = ident
+            .split('-')
+            .all(|part| part.starts_with(|c: char| c.is_ascii_alphabetic()))
+            && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(self.error("expected kebab-case identifier"));
+        }
+        self.pos += 1;
+        Ok(ident)
+    }
+
+    /// Consumes the keyword `kw` or returns an error.
+    fn keyword(&mut self, kw: &str) -> Result<(), String> {
+        match self.peek() {
+            Some(Lexeme::Ident(ident)) if ident == kw => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.error(format!("expected `{kw}`"))),
+        }
+    }
+
+    /// Parses `world <name> { <item>* }`.
+    fn parse_world(mut self) -> Result<World, String> {
+        self.keyword("world")?;
+        let mut world = World {
+            name: self.ident()?,
+            records: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+        };
+        self.expect("{")?;
+        while !self.eat("}") {
+            match self.ident()?.as_str() {
+                "record" => world.records.push(self.parse_record()?),
+                "import" => world.imports.push(self.parse_function()?),
+                "export" => world.exports.push(self.parse_function()?),
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.error("expected `record`, `import` or `export`"));
+                }
+            }
+        }
+        if self.pos != self.tokens.len() {
+            return Err(self.error("unexpected trailing input"));
+        }
+        Ok(world)
+    }
+
+    /// Parses `<name> { <field>* }` after the `record` keyword.
+    fn parse_record(&mut self) -> Result<Record, String> {
+        let name = self.ident()?;
+        self.expect("{")?;
+        let fields = self.parse_fields("}")?;
+        Ok(Record { name, fields })
+    }
+
+    /// Parses `<name>: func(<field>*) [-> <type>];` after the `import` or `export` keyword.
+    fn parse_function(&mut self) -> Result<Function, String> {
+        let name = self.ident()?;
+        self.expect(":")?;
+        self.keyword("func")?;
+        self.expect("(")?;
+        let params = self.parse_fields(")")?;
+        let result = match self.eat("->") {
+            true => Some(self.parse_type()?),
+            false => None,
+        };
+        self.expect(";")?;
+        Ok(Function {
+            name,
+            params,
+            result,
+        })
+    }
+
+    /// Parses comma separated `<name>: <type>` pairs up to and including `end`.
+    fn parse_fields(&mut self, end: &str) -> Result<Vec<Field>, String> {
+        let mut fields = Vec::new();
+        while !self.eat(end) {
+            let name = self.ident()?;
+            self.expect(":")?;
+            let ty = self.parse_type()?;
+            fields.push(Field { name, ty });
+            if !self.eat(",") {
+                self.expect(end)?;
+                break;
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Parses a type.
+    fn parse_type(&mut self) -> Result<Type, String> {
+        let ty = match self.ident()?.as_str() {
+            "bool" => Type::Bool,
+            "u8" => Type::U8,
+            "u16" => Type::U16,
+            "u32" => Type::U32,
+            "u64" => Type::U64,
+            "s8" => Type::S8,
+            "s16" => Type::S16,
+            "s32" => Type::S32,
+            "s64" => Type::S64,
+            "f32" | "float32" => Type::F32,
+            "f64" | "float64" => Type::F64,
+            "char" => Type::Char,
+            "string" => Type::String,
+            "list" => {
+                self.expect("<")?;
+                let element = self.parse_type()?;
+                self.expect(">")?;
+                Type::List(Box::new(element))
+            }
+            "tuple" => {
+                self.expect("<")?;
+                let mut elements = Vec::new();
+                while !self.eat(">") {
+                    elements.push(self.parse_type()?);
+                    if !self.eat(",") {
+                        self.expect(">")?;
+                        break;
+                    }
+                }
+                Type::Tuple(elements)
+            }
+            name => Type::Record(name.into()),
+        };
+        Ok(ty)
+    }
+}
+
+/// Converts a kebab-case identifier to snake_case.
+fn snake_case(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Converts a kebab-case identifier to PascalCase.
+fn pascal_case(name: &str) -> String {
+    name.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Generates the Rust bindings for a parsed [`World`].
+struct Generator<'a> {
+    world: &'a World,
+    span: Span,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates a new [`Generator`] reporting errors at `span`.
+    fn new(world: &'a World, span: Span) -> Self {
+        Self { world, span }
+    }
+
+    /// Returns a Rust identifier for the kebab-case `name`.
+    fn ident(&self, name: &str) -> Ident {
+        let name = snake_case(name);
+        match syn::parse_str::<Ident>(&name) {
+            Ok(_) => Ident::new(&name, self.span),
+            Err(_) => Ident::new_raw(&name, self.span),
+        }
+    }
+
+    /// Returns the Rust type for the interface type `ty`.
+    fn ty(&self, ty: &Type) -> syn::Result<TokenStream2> {
+        let ty = match ty {
+            Type::Bool => quote! { bool },
+            Type::U8 => quote! { u8 },
+            Type::U16 => quote! { u16 },
+            Type::U32 => quote! { u32 },
+            Type::U64 => quote! { u64 },
+            Type::S8 => quote! { i8 },
+            Type::S16 => quote! { i16 },
+            Type::S32 => quote! { i32 },
+            Type::S64 => quote! { i64 },
+            Type::F32 => quote! { f32 },
+            Type::F64 => quote! { f64 },
+            Type::Char => quote! { char },
+            Type::String => quote! { ::std::string::String },
+            Type::List(element) => {
+                let element = self.ty(element)?;
+                quote! { ::std::vec::Vec<#element> }
+            }
+            Type::Tuple(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| self.ty(element))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! { ( #( #elements, )* ) }
+            }
+            Type::Record(name) => {
+                if !self.world.records.iter().any(|record| record.name == *name) {
+                    return Err(syn::Error::new(self.span, format!("unknown type `{name}`")));
+                }
+                let name = format_ident!("{}", pascal_case(name), span = self.span);
+                quote! { #name }
+            }
+        };
+        Ok(ty)
+    }
+
+    /// Returns the Rust parameter names, parameter types and result type of `func`.
+    fn signature(
+        &self,
+        func: &Function,
+    ) -> syn::Result<(Vec<Ident>, Vec<TokenStream2>, TokenStream2)> {
+        let names = func
+            .params
+            .iter()
+            .map(|param| self.ident(&param.name))
+            .collect();
+        let types = func
+            .params
+            .iter()
+            .map(|param| self.ty(&param.ty))
+            .collect::<syn::Result<_>>()?;
+        let result = match &func.result {
+            Some(result) => self.ty(result)?,
+            None => quote! { () },
+        };
+        Ok((names, types, result))
+    }
+
+    /// Generates all bindings of the world.
+    fn generate(&self) -> syn::Result<TokenStream2> {
+        self.check_records()?;
+        let records = self
+            .world
+            .records
+            .iter()
+            .map(|record| self.record(record))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let imports = self.imports()?;
+        let exports = self.exports()?;
+        Ok(quote! {
+            #( #records )*
+            #imports
+            #exports
+        })
+    }
+
+    /// Checks that record types are unique and not recursive.
+    fn check_records(&self) -> syn::Result<()> {
+        fn contains(world: &World, ty: &Type, name: &str, depth: usize) -> bool {
+            if depth > world.records.len() {
+                return true;
+            }
+            match ty {
+                Type::List(element) => contains(world, element, name, depth),
+                Type::Tuple(elements) => elements
+                    .iter()
+                    .any(|element| contains(world, element, name, depth)),
+                Type::Record(other) => {
+                    other == name
+                        || world
+                            .records
+                            .iter()
+                            .filter(|record| record.name == *other)
+                            .flat_map(|record| &record.fields)
+                            .any(|field| contains(world, &field.ty, name, depth + 1))
+                }
+                _ => false,
+            }
+        }
+        for (n, record) in self.world.records.iter().enumerate() {
+            if self.world.records[..n]
+                .iter()
+                .any(|other| other.name == record.name)
+            {
+                return Err(syn::Error::new(
+                    self.span,
+                    format!("duplicate record `{}`", record.name),
+                ));
+            }
+            if record
+                .fields
+                .iter()
+                .any(|field| contains(self.world, &field.ty, &record.name, 0))
+            {
+                return Err(syn::Error::new(
+                    self.span,
+                    format!("record `{}` must not be recursive", record.name),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates the Rust struct and its `ComponentType` implementation for `record`.
+    fn record(&self, record: &Record) -> syn::Result<TokenStream2> {
+        let name = format_ident!("{}", pascal_case(&record.name), span = self.span);
+        let fields: Vec<_> = record
+            .fields
+            .iter()
+            .map(|field| self.ident(&field.name))
+            .collect();
+        let types = record
+            .fields
+            .iter()
+            .map(|field| self.ty(&field.ty))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let ct = quote! { ::wasmi::bindgen::ComponentType };
+        Ok(quote! {
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct #name {
+                #( pub #fields: #types, )*
+            }
+
+            impl #ct for #name {
+                const SIZE: usize = ::wasmi::bindgen::record_size(&[
+                    #( (<#types as #ct>::SIZE, <#types as #ct>::ALIGN) ),*
+                ]);
+                const ALIGN: usize = ::wasmi::bindgen::record_align(&[
+                    #( <#types as #ct>::ALIGN ),*
+                ]);
+                const FLAT_LEN: usize = 0 #( + <#types as #ct>::FLAT_LEN )*;
+
+                fn flat_types(out: &mut ::std::vec::Vec<::wasmi::core::ValueType>) {
+                    #( <#types as #ct>::flat_types(out); )*
+                }
+
+                fn lower_flat<T>(
+                    &self,
+                    cx: &mut ::wasmi::bindgen::LowerContext<T>,
+                    out: &mut ::std::vec::Vec<::wasmi::Value>,
+                ) -> ::core::result::Result<(), ::wasmi::Error> {
+                    #( <#types as #ct>::lower_flat(&self.#fields, cx, out)?; )*
+                    Ok(())
+                }
+
+                fn store<T>(
+                    &self,
+                    cx: &mut ::wasmi::bindgen::LowerContext<T>,
+                    offset: usize,
+                ) -> ::core::result::Result<(), ::wasmi::Error> {
+                    let mut layout = ::wasmi::bindgen::RecordLayout::new(offset);
+                    #(
+                        let field = layout.field(<#types as #ct>::SIZE, <#types as #ct>::ALIGN);
+                        <#types as #ct>::store(&self.#fields, cx, field)?;
+                    )*
+                    Ok(())
+                }
+
+                fn lift_flat(
+                    cx: &::wasmi::bindgen::LiftContext,
+                    values: &mut ::core::slice::Iter<::wasmi::Value>,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    Ok(Self {
+                        #( #fields: <#types as #ct>::lift_flat(cx, values)?, )*
+                    })
+                }
+
+                fn load(
+                    cx: &::wasmi::bindgen::LiftContext,
+                    offset: usize,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    let mut layout = ::wasmi::bindgen::RecordLayout::new(offset);
+                    Ok(Self {
+                        #(
+                            #fields: <#types as #ct>::load(
+                                cx,
+                                layout.field(<#types as #ct>::SIZE, <#types as #ct>::ALIGN),
+                            )?,
+                        )*
+                    })
+                }
+            }
+        })
+    }
+
+    /// Generates the host trait for the imports and its `add_to_linker` function.
+    fn imports(&self) -> syn::Result<TokenStream2> {
+        let world = &self.world.name;
+        let trait_name = format_ident!("{}Imports", pascal_case(world), span = self.span);
+        let mut methods = Vec::new();
+        let mut registrations = Vec::new();
+        for func in &self.world.imports {
+            let method = self.ident(&func.name);
+            let name = &func.name;
+            let (params, types, result) = self.signature(func)?;
+            methods.push(quote! {
+                fn #method(&mut self, #( #params: #types ),*) -> ::core::result::Result<#result, ::wasmi::Error>;
+            });
+            registrations.push(quote! {
+                linker.func_new(
+                    #world,
+                    #name,
+                    ::wasmi::bindgen::import_func_type::<( #( #types, )* ), #result>()?,
+                    move |mut caller: ::wasmi::Caller<'_, T>, params: &[::wasmi::Value], results: &mut [::wasmi::Value]| {
+                        let ( #( #params, )* ) = ::wasmi::bindgen::lift_params::<T, ( #( #types, )* )>(&caller, params)?;
+                        let result: #result = <U as #trait_name>::#method(get(caller.data_mut()), #( #params ),*)?;
+                        ::wasmi::bindgen::lower_result(&mut caller, params, results, result)
+                    },
+                )?;
+            });
+        }
+        let doc = format!("Host functions imported by the `{world}` world.");
+        let add_to_linker_doc = format!(
+            "Defines all imports of the `{world}` world in the `linker` under the `{world}` module.\n\n\
+             The `get` function projects the host state `T` to the implementation of [`{trait_name}`]."
+        );
+        Ok(quote! {
+            #[doc = #doc]
+            pub trait #trait_name {
+                #( #methods )*
+            }
+
+            #[doc = #add_to_linker_doc]
+            ///
+            /// # Errors
+            ///
+            /// If an import has too many parameters or is already defined in the `linker`.
+            pub fn add_to_linker<T, U>(
+                linker: &mut ::wasmi::Linker<T>,
+                get: fn(&mut T) -> &mut U,
+            ) -> ::core::result::Result<(), ::wasmi::Error>
+            where
+                T: 'static,
+                U: #trait_name + 'static,
+            {
+                #( #registrations )*
+                let _ = (linker, get);
+                Ok(())
+            }
+        })
+    }
+
+    /// Generates the struct providing typed access to the exports.
+    fn exports(&self) -> syn::Result<TokenStream2> {
+        let world = &self.world.name;
+        let struct_name = format_ident!("{}", pascal_case(world), span = self.span);
+        let mut fields = Vec::new();
+        let mut names = Vec::new();
+        let mut methods = Vec::new();
+        for func in &self.world.exports {
+            let method = self.ident(&func.name);
+            if method == "new" {
+                return Err(syn::Error::new(
+                    self.span,
+                    "the `new` export conflicts with the generated constructor",
+                ));
+            }
+            let field = format_ident!("export_{}", snake_case(&func.name), span = self.span);
+            let name = &func.name;
+            let (params, types, result) = self.signature(func)?;
+            let doc = format!("Calls the `{name}` export of the guest.");
+            fields.push(field.clone());
+            names.push(name);
+            methods.push(quote! {
+                #[doc = #doc]
+                ///
+                /// # Errors
+                ///
+                /// If passing the values via guest memory failed or the guest traps.
+                pub fn #method<T>(
+                    &self,
+                    ctx: impl ::wasmi::AsContextMut<UserState = T>,
+                    #( #params: #types ),*
+                ) -> ::core::result::Result<#result, ::wasmi::Error> {
+                    ::wasmi::bindgen::call_export::<T, ( #( #types, )* ), #result>(
+                        ctx,
+                        &self.guest,
+                        &self.#field,
+                        ( #( #params, )* ),
+                    )
+                }
+            });
+        }
+        let doc = format!("Typed access to the exports of a guest of the `{world}` world.");
+        Ok(quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone)]
+            pub struct #struct_name {
+                guest: ::wasmi::bindgen::Guest,
+                #( #fields: ::wasmi::Func, )*
+            }
+
+            impl #struct_name {
+                /// Resolves the exports of the guest `instance`.
+                ///
+                /// # Errors
+                ///
+                /// If the guest is missing one of the exported functions.
+                pub fn new(
+                    ctx: impl ::wasmi::AsContext,
+                    instance: &::wasmi::Instance,
+                ) -> ::core::result::Result<Self, ::wasmi::Error> {
+                    Ok(Self {
+                        guest: ::wasmi::bindgen::Guest::new(&ctx, instance),
+                        #( #fields: ::wasmi::bindgen::export_func(&ctx, instance, #names)?, )*
+                    })
+                }
+
+                #( #methods )*
+            }
+        })
+    }
+}
@@ -0,0 +1,209 @@
+//! Derive macros for the Wasmi WebAssembly interpreter.
+//!
+//! Use them through the `derive` crate feature of `wasmi` instead of depending on this crate directly.
+
+mod bindgen;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Index};
+
+/// Derives `wasmi::WasmTypeList` for a `struct` whose fields are all `wasmi::WasmType`.
+///
+/// The fields are mapped in declaration order to a sequence of Wasm values.
+/// This allows to use the `struct` as results of host functions created via
+/// `Func::wrap` or `Linker::func_wrap` as well as parameters or results of `TypedFunc`.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::{core::F64, WasmTypeList};
+///
+/// #[derive(WasmTypeList)]
+/// struct Point {
+///     x: F64,
+///     y: F64,
+/// }
+///
+/// #[derive(WasmTypeList)]
+/// struct Handle(u32);
+/// ```
+#[proc_macro_derive(WasmTypeList)]
+pub fn derive_wasm_type_list(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_wasm_type_list(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates typed bindings for a WIT-like interface definition.
+///
+/// The interface is either read from a file relative to the crate root via
+/// `bindgen!(path = "...")` or given inline via `bindgen!(inline = "...")`.
+/// It consists of a single `world` with `record` definitions as well as
+/// `import`ed and `export`ed functions:
+///
+/// ```text
+/// world greeter {
+///     record point { x: f64, y: f64 }
+///     import log: func(message: string);
+///     export greet: func(name: string, times: u32) -> string;
+///     export centroid: func(points: list<point>) -> point;
+/// }
+/// ```
+///
+/// Supported types are `bool`, `u8`, `u16`, `u32`, `u64`, `s8`, `s16`, `s32`,
+/// `s64`, `f32`, `f64`, `char`, `string`, `list<T>`, `tuple<..>` and records.
+///
+/// The macro generates:
+///
+/// - A `struct` for every `record` with fields mapped to Rust types.
+/// - A `<World>Imports` trait with a method per import to be implemented by the host.
+/// - An `add_to_linker` function that defines all imports in a `wasmi::Linker`
+///   under the world name as module name.
+/// - A `<World>` struct with a typed method per export calling into the guest.
+///
+/// Values are passed according to the canonical ABI of the component model.
+/// Guests that receive strings or lists from the host must export `memory`
+/// and `cabi_realloc`.
+///
+/// # Example
+///
+/// ```
+/// mod bindings {
+///     wasmi::bindgen!(inline = "
+///         world calc {
+///             import offset: func() -> s32;
+///             export add: func(a: s32, b: s32) -> s32;
+///         }
+///     ");
+/// }
+///
+/// struct Host;
+///
+/// impl bindings::CalcImports for Host {
+///     fn offset(&mut self) -> Result<i32, wasmi::Error> {
+///         Ok(100)
+///     }
+/// }
+///
+/// let wasm = wat::parse_str(r#"
+///     (module
+///         (import "calc" "offset" (func $offset (result i32)))
+///         (func (export "add") (param i32 i32) (result i32)
+///             (i32.add (i32.add (local.get 0) (local.get 1)) (call $offset))
+///         )
+///     )
+/// "#)?;
+/// let engine = wasmi::Engine::default();
+/// let module = wasmi::Module::new(&engine, &wasm[..])?;
+/// let mut store = wasmi::Store::new(&engine, Host);
+/// let mut linker = wasmi::Linker::new(&engine);
+/// bindings::add_to_linker(&mut linker, |host| host)?;
+/// let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+/// let calc = bindings::Calc::new(&store, &instance)?;
+/// assert_eq!(calc.add(&mut store, 1, 2)?, 103);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[proc_macro]
+pub fn bindgen(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as bindgen::Input);
+    bindgen::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_wasm_type_list(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`WasmTypeList` can only be derived for structs",
+        ));
+    };
+    let name = &input.ident;
+    let field_tys: Vec<_> = data.fields.iter().map(|field| &field.ty).collect();
+    let bindings: Vec<_> = (0..field_tys.len())
+        .map(|n| format_ident!("__field_{}", n))
+        .collect();
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { Self { #( #names: #bindings ),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self( #( #bindings ),* ) },
+        Fields::Unit => quote! { Self },
+    };
+    let destruct = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { let Self { #( #names: #bindings ),* } = self; }
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..field_tys.len()).map(Index::from);
+            quote! { #( let #bindings = self.#indices; )* }
+        }
+        Fields::Unit => quote! {},
+    };
+    let tuple = quote! { ( #( #field_tys, )* ) };
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    let predicates = &mut generics.make_where_clause().predicates;
+    predicates.push(parse_quote! { #tuple: ::wasmi::WasmTypeList });
+    predicates.push(parse_quote! { #name #ty_generics: ::core::marker::Send });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::wasmi::core::DecodeUntypedSlice for #name #ty_generics #where_clause {
+            #[inline]
+            fn decode_untyped_slice(
+                results: &[::wasmi::core::UntypedValue],
+            ) -> ::core::result::Result<Self, ::wasmi::core::UntypedError> {
+                <#tuple as ::wasmi::core::DecodeUntypedSlice>::decode_untyped_slice(results)
+                    .map(|( #( #bindings, )* )| #construct)
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::wasmi::core::EncodeUntypedSlice for #name #ty_generics #where_clause {
+            #[inline]
+            fn encode_untyped_slice(
+                self,
+                results: &mut [::wasmi::core::UntypedValue],
+            ) -> ::core::result::Result<(), ::wasmi::core::UntypedError> {
+                #destruct
+                <#tuple as ::wasmi::core::EncodeUntypedSlice>::encode_untyped_slice(
+                    ( #( #bindings, )* ),
+                    results,
+                )
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::wasmi::WasmTypeList for #name #ty_generics #where_clause {
+            const LEN: usize = <#tuple as ::wasmi::WasmTypeList>::LEN;
+
+            type Types = <#tuple as ::wasmi::WasmTypeList>::Types;
+            type TypesIter = <#tuple as ::wasmi::WasmTypeList>::TypesIter;
+            type Values = <#tuple as ::wasmi::WasmTypeList>::Values;
+            type ValuesIter = <#tuple as ::wasmi::WasmTypeList>::ValuesIter;
+
+            #[inline]
+            fn types() -> Self::Types {
+                <#tuple as ::wasmi::WasmTypeList>::types()
+            }
+
+            #[inline]
+            fn values(self) -> Self::Values {
+                #destruct
+                <#tuple as ::wasmi::WasmTypeList>::values(( #( #bindings, )* ))
+            }
+
+            #[inline]
+            fn from_values(values: &[::wasmi::core::UntypedValue]) -> ::core::option::Option<Self> {
+                <#tuple as ::wasmi::WasmTypeList>::from_values(values)
+                    .map(|( #( #bindings, )* )| #construct)
+            }
+        }
+    })
+}
@@ -0,0 +1,184 @@
+use wasmi::{
+    bindgen::{ComponentType, LiftContext},
+    Engine,
+    Error,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+mod greeter {
+    wasmi::bindgen!(path = "tests/wit/greeter.wit");
+}
+
+use greeter::{Greeter, GreeterImports, NamedPoint, Point};
+
+#[derive(Debug, Default)]
+struct Host {
+    name: String,
+    logs: Vec<String>,
+}
+
+impl GreeterImports for Host {
+    fn log(&mut self, message: String) -> Result<(), Error> {
+        self.logs.push(message);
+        Ok(())
+    }
+
+    fn get_name(&mut self) -> Result<String, Error> {
+        Ok(self.name.clone())
+    }
+
+    fn make_point(&mut self, name: String, x: f64, y: f64) -> Result<NamedPoint, Error> {
+        Ok(NamedPoint {
+            tags: name.bytes().rev().collect(),
+            name,
+            at: Point { x, y },
+        })
+    }
+}
+
+/// A guest of the `greeter` world with a bump allocator as `cabi_realloc`.
+const GUEST: &str = r#"
+    (module
+        (import "greeter" "log" (func $log (param i32 i32)))
+        (import "greeter" "get-name" (func $get_name (param i32)))
+        (import "greeter" "make-point" (func $make_point (param i32 i32 f64 f64 i32)))
+        (memory (export "memory") 1)
+        (global $heap (mut i32) (i32.const 1024))
+        (data (i32.const 512) "origin")
+        (func (export "cabi_realloc") (param i32 i32 i32 i32) (result i32)
+            (local $ptr i32)
+            ;; Align the heap pointer and bump it by the requested size.
+            (local.set $ptr
+                (i32.and
+                    (i32.add (global.get $heap) (i32.sub (local.get 2) (i32.const 1)))
+                    (i32.sub (i32.const 0) (local.get 2))
+                )
+            )
+            (global.set $heap (i32.add (local.get $ptr) (local.get 3)))
+            (local.get $ptr)
+        )
+        (func (export "greet") (param $times i32) (result i32)
+            (call $get_name (i32.const 0))
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.eqz (local.get $times)))
+                    (call $log (i32.load (i32.const 0)) (i32.load (i32.const 4)))
+                    (local.set $times (i32.sub (local.get $times) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i32.const 0)
+        )
+        (func (export "centroid") (param $ptr i32) (param $len i32) (result i32)
+            (local $n i32)
+            (local $x f64)
+            (local $y f64)
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.ge_u (local.get $n) (local.get $len)))
+                    (local.set $x (f64.add (local.get $x) (f64.load (local.get $ptr))))
+                    (local.set $y (f64.add (local.get $y) (f64.load offset=8 (local.get $ptr))))
+                    (local.set $ptr (i32.add (local.get $ptr) (i32.const 16)))
+                    (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (f64.store (i32.const 16) (f64.div (local.get $x) (f64.convert_i32_u (local.get $len))))
+            (f64.store (i32.const 24) (f64.div (local.get $y) (f64.convert_i32_u (local.get $len))))
+            (i32.const 16)
+        )
+        (func (export "describe")
+            (param $name_ptr i32) (param $name_len i32)
+            (param $x f64) (param $y f64)
+            (param $tags_ptr i32) (param $tags_len i32)
+            (result i32)
+            (i32.store (i32.const 32) (i32.add (local.get $name_len) (local.get $tags_len)))
+            (i32.store8 (i32.const 36) (f64.gt (local.get $x) (local.get $y)))
+            (i32.const 32)
+        )
+        (func (export "make") (result i32)
+            (call $make_point (i32.const 512) (i32.const 6) (f64.const 1.5) (f64.const -2) (i32.const 64))
+            (i32.const 64)
+        )
+    )
+"#;
+
+fn setup(name: &str) -> (Store<Host>, Instance, Greeter) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(GUEST).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let host = Host {
+        name: name.into(),
+        ..Host::default()
+    };
+    let mut store = Store::new(&engine, host);
+    let mut linker = <Linker<Host>>::new(&engine);
+    greeter::add_to_linker(&mut linker, |host| host).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let greeter = Greeter::new(&store, &instance).unwrap();
+    (store, instance, greeter)
+}
+
+#[test]
+fn strings_flow_through_imports_and_exports() {
+    let (mut store, _, greeter) = setup("Wasmi");
+    assert_eq!(greeter.greet(&mut store, 3).unwrap(), "Wasmi");
+    assert_eq!(store.data().logs, ["Wasmi", "Wasmi", "Wasmi"]);
+}
+
+#[test]
+fn lists_of_records_are_lowered_into_guest_memory() {
+    let (mut store, _, greeter) = setup("");
+    let points = vec![
+        Point { x: 1.0, y: 2.0 },
+        Point { x: 3.0, y: 4.0 },
+        Point { x: 5.0, y: 9.0 },
+    ];
+    let centroid = greeter.centroid(&mut store, points).unwrap();
+    assert_eq!(centroid, Point { x: 3.0, y: 5.0 });
+}
+
+#[test]
+fn nested_records_are_flattened() {
+    let (mut store, _, greeter) = setup("");
+    let point = NamedPoint {
+        name: "here".into(),
+        at: Point { x: 2.0, y: 1.0 },
+        tags: vec![1, 2, 3],
+    };
+    assert_eq!(greeter.describe(&mut store, point).unwrap(), (7, true));
+}
+
+#[test]
+fn import_results_are_stored_via_return_pointer() {
+    let (mut store, instance, _) = setup("");
+    let make = instance.get_typed_func::<(), i32>(&store, "make").unwrap();
+    let ptr = make.call(&mut store, ()).unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    let cx = LiftContext::new(Some(memory.data(&store)));
+    let point = NamedPoint::load(&cx, ptr as usize).unwrap();
+    assert_eq!(
+        point,
+        NamedPoint {
+            name: "origin".into(),
+            at: Point { x: 1.5, y: -2.0 },
+            tags: b"nigiro".to_vec(),
+        }
+    );
+}
+
+#[test]
+fn record_layout_follows_canonical_abi() {
+    assert_eq!(Point::SIZE, 16);
+    assert_eq!(Point::ALIGN, 8);
+    assert_eq!(NamedPoint::SIZE, 32);
+    assert_eq!(NamedPoint::ALIGN, 8);
+    assert_eq!(NamedPoint::FLAT_LEN, 6);
+}
@@ -0,0 +1,140 @@
+use wasmi::{
+    core::{ValueType, F64},
+    Caller,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+    WasmTypeList,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, WasmTypeList)]
+struct Point {
+    x: F64,
+    y: F64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, WasmTypeList)]
+struct Handle(u32);
+
+#[derive(Debug, Copy, Clone, PartialEq, WasmTypeList)]
+struct Unit;
+
+#[derive(Debug, Copy, Clone, PartialEq, WasmTypeList)]
+struct Pair<T> {
+    first: T,
+    second: i64,
+}
+
+#[test]
+fn value_types_follow_field_order() {
+    assert_eq!(<Point as WasmTypeList>::LEN, 2);
+    assert_eq!(
+        <Pair<i32> as WasmTypeList>::types(),
+        [ValueType::I32, ValueType::I64]
+    );
+    assert_eq!(<Handle as WasmTypeList>::types(), [ValueType::I32]);
+    assert_eq!(<Unit as WasmTypeList>::LEN, 0);
+}
+
+#[test]
+fn values_round_trip() {
+    let pair = Pair {
+        first: 7_u32,
+        second: -1,
+    };
+    let values = pair.values();
+    assert_eq!(<Pair<u32>>::from_values(&values), Some(pair));
+    assert_eq!(<Pair<u32>>::from_values(&values[..1]), None);
+}
+
+fn setup() -> (Store<()>, Linker<()>) {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "midpoint", |a: F64, b: F64, c: F64, d: F64| Point {
+            x: F64::from(0.5 * (f64::from(a) + f64::from(c))),
+            y: F64::from(0.5 * (f64::from(b) + f64::from(d))),
+        })
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "open",
+            |_caller: Caller<()>, fd: u32| -> Result<Handle, Error> {
+                if fd == 0 {
+                    return Err(Error::new("invalid file descriptor"));
+                }
+                Ok(Handle(fd + 100))
+            },
+        )
+        .unwrap();
+    (Store::new(linker.engine(), ()), linker)
+}
+
+#[test]
+fn structs_as_host_and_typed_func_results() {
+    let (mut store, linker) = setup();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "midpoint" (func $midpoint (param f64 f64 f64 f64) (result f64 f64)))
+            (import "env" "open" (func $open (param i32) (result i32)))
+            (func (export "midpoint") (param f64 f64 f64 f64) (result f64 f64)
+                (call $midpoint (local.get 0) (local.get 1) (local.get 2) (local.get 3))
+            )
+            (func (export "open") (param i32) (result i32)
+                (call $open (local.get 0))
+            )
+            (func (export "swap") (param i32 i64) (result i32 i64)
+                (local.get 0) (local.get 1)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let midpoint = instance
+        .get_typed_func::<(F64, F64, F64, F64), Point>(&store, "midpoint")
+        .unwrap();
+    let result = midpoint
+        .call(&mut store, (1.0.into(), 2.0.into(), 3.0.into(), 6.0.into()))
+        .unwrap();
+    assert_eq!(
+        result,
+        Point {
+            x: 2.0.into(),
+            y: 4.0.into()
+        }
+    );
+    let open = instance
+        .get_typed_func::<u32, Handle>(&store, "open")
+        .unwrap();
+    assert_eq!(open.call(&mut store, 1).unwrap(), Handle(101));
+    assert!(open.call(&mut store, 0).is_err());
+    let swap = instance
+        .get_typed_func::<Pair<u32>, Pair<i32>>(&store, "swap")
+        .unwrap();
+    let pair = swap
+        .call(
+            &mut store,
+            Pair {
+                first: 1,
+                second: 2,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pair,
+        Pair {
+            first: 1,
+            second: 2
+        }
+    );
+}
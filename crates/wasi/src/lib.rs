@@ -1,13 +1,29 @@
 //! This crate provides support for WASI `preview1` for the Wasmi interpreter.
 //!
 //! Use [`add_to_linker`] to add all supported WASI definitions to the Wasmi linker.
+//!
+//! The [`preview1`] module provides a native implementation that does not depend
+//! on `wasi-common` and can be used in `no_std` environments. With the `preview2`
+//! crate feature the [`preview2`] module provides WASI `preview2` for Wasm components.
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "sync")]
 mod guest_memory;
+pub mod preview1;
+#[cfg(feature = "preview2")]
+pub mod preview2;
 
 #[cfg(feature = "sync")]
 pub mod sync;
 
+#[cfg(feature = "sync")]
 pub use self::guest_memory::WasmiGuestMemory;
+#[cfg(feature = "sync")]
 pub use wasi_common::{Error, WasiCtx, WasiDir, WasiFile};
 
 /// Sync mode is the "default" of this crate, so we also export it at the top level.
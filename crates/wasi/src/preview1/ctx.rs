@@ -0,0 +1,437 @@
+use super::{
+    deterministic::{SeededRandom, VirtualClocks},
+    host::{NullFile, WasiClocks, WasiDir, WasiFile, WasiRandom, WasiTracer},
+    types::{Errno, Rights},
+};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+/// An entry of the file descriptor table of a [`WasiCtx`].
+pub(crate) enum Descriptor {
+    /// An open file, pipe or character device.
+    File {
+        file: Box<dyn WasiFile>,
+        rights: Rights,
+        inheriting: Rights,
+    },
+    /// An open directory within one of the mounted [`WasiDir`]s.
+    Dir {
+        /// The index of the [`Mount`] of the directory.
+        mount: usize,
+        /// The normalized path of the directory relative to the root of the [`Mount`].
+        path: String,
+        /// `true` if the directory is the root of a preopen.
+        preopen: bool,
+        rights: Rights,
+        inheriting: Rights,
+    },
+}
+
+impl Descriptor {
+    /// Creates a new file [`Descriptor`] with the default rights for its file type.
+    pub(crate) fn file(file: Box<dyn WasiFile>) -> Self {
+        let rights = Rights::for_filetype(file.filetype());
+        Self::File {
+            file,
+            rights,
+            inheriting: Rights::empty(),
+        }
+    }
+
+    /// Returns the base and inheriting [`Rights`] of the [`Descriptor`].
+    pub(crate) fn rights(&self) -> (Rights, Rights) {
+        match self {
+            Self::File {
+                rights, inheriting, ..
+            }
+            | Self::Dir {
+                rights, inheriting, ..
+            } => (*rights, *inheriting),
+        }
+    }
+
+    /// Returns mutable references to the base and inheriting [`Rights`] of the [`Descriptor`].
+    pub(crate) fn rights_mut(&mut self) -> (&mut Rights, &mut Rights) {
+        match self {
+            Self::File {
+                rights, inheriting, ..
+            }
+            | Self::Dir {
+                rights, inheriting, ..
+            } => (rights, inheriting),
+        }
+    }
+}
+
+/// A [`WasiDir`] mounted as preopen.
+pub(crate) struct Mount {
+    /// The mounted directory tree.
+    pub dir: Box<dyn WasiDir>,
+    /// The path under which the guest sees the mounted directory.
+    pub guest_path: String,
+}
+
+/// The state of the native `wasi_snapshot_preview1` implementation.
+///
+/// Use [`WasiCtxBuilder`] to create a [`WasiCtx`].
+pub struct WasiCtx {
+    /// The command line arguments of the guest.
+    pub(crate) args: Vec<String>,
+    /// The environment variables of the guest.
+    pub(crate) env: Vec<(String, String)>,
+    /// The open file descriptors.
+    pub(crate) fds: BTreeMap<u32, Descriptor>,
+    /// The mounted preopened directories.
+    pub(crate) mounts: Vec<Mount>,
+    /// The source of time.
+    pub(crate) clocks: Box<dyn WasiClocks>,
+    /// The source of random bytes.
+    pub(crate) random: Box<dyn WasiRandom>,
+    /// Observes the WASI function calls of the guest if any.
+    pub(crate) tracer: Option<Box<dyn WasiTracer>>,
+}
+
+impl core::fmt::Debug for WasiCtx {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("WasiCtx")
+            .field("args", &self.args)
+            .field("env", &self.env)
+            .field("fds", &self.fds.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasiCtx {
+    /// Returns the command line arguments of the guest.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns the environment variables of the guest.
+    pub fn env(&self) -> &[(String, String)] {
+        &self.env
+    }
+
+    /// Inserts the `file` at the lowest free file descriptor and returns it.
+    ///
+    /// # Errors
+    ///
+    /// If there are no more free file descriptors.
+    pub fn push_file(&mut self, file: Box<dyn WasiFile>) -> Result<u32, Errno> {
+        self.push(Descriptor::file(file))
+    }
+
+    /// Inserts the `file` at file descriptor `fd` replacing any previous file descriptor.
+    pub fn insert_file(&mut self, fd: u32, file: Box<dyn WasiFile>) {
+        self.fds.insert(fd, Descriptor::file(file));
+    }
+
+    /// Inserts the `descriptor` at the lowest free file descriptor and returns it.
+    pub(crate) fn push(&mut self, descriptor: Descriptor) -> Result<u32, Errno> {
+        let fd = self
+            .fds
+            .keys()
+            .zip(0..)
+            .find(|(used, free)| *used != free)
+            .map_or(self.fds.len(), |(_, free)| free as usize);
+        let fd = u32::try_from(fd).map_err(|_| Errno::Mfile)?;
+        self.fds.insert(fd, descriptor);
+        Ok(fd)
+    }
+
+    /// Returns the [`Descriptor`] at `fd` if it has all `required` rights.
+    pub(crate) fn descriptor(
+        &mut self,
+        fd: u32,
+        required: Rights,
+    ) -> Result<&mut Descriptor, Errno> {
+        let descriptor = self.fds.get_mut(&fd).ok_or(Errno::Badf)?;
+        if !descriptor.rights().0.contains(required) {
+            return Err(Errno::Notcapable);
+        }
+        Ok(descriptor)
+    }
+
+    /// Returns the [`WasiFile`] at `fd` if it has all `required` rights.
+    pub(crate) fn file(&mut self, fd: u32, required: Rights) -> Result<&mut dyn WasiFile, Errno> {
+        match self.descriptor(fd, required)? {
+            Descriptor::File { file, .. } => Ok(&mut **file),
+            Descriptor::Dir { .. } => Err(Errno::Badf),
+        }
+    }
+
+    /// Returns the mount index, path and inheriting rights of the directory at `fd`.
+    ///
+    /// # Errors
+    ///
+    /// If `fd` does not refer to a directory with all `required` rights.
+    pub(crate) fn dir(
+        &mut self,
+        fd: u32,
+        required: Rights,
+    ) -> Result<(usize, &str, Rights), Errno> {
+        match self.descriptor(fd, required)? {
+            Descriptor::Dir {
+                mount,
+                path,
+                inheriting,
+                ..
+            } => Ok((*mount, path, *inheriting)),
+            Descriptor::File { .. } => Err(Errno::Notdir),
+        }
+    }
+
+    /// Resolves the guest `path` relative to the directory at `fd`.
+    ///
+    /// Returns the index of the mount of the directory and the normalized path within it.
+    ///
+    /// # Errors
+    ///
+    /// - If `fd` does not refer to a directory with all `required` rights.
+    /// - If `path` is absolute or escapes the mounted directory.
+    pub(crate) fn resolve(
+        &mut self,
+        fd: u32,
+        path: &str,
+        required: Rights,
+    ) -> Result<(usize, String), Errno> {
+        let (mount, base, _) = self.dir(fd, required)?;
+        let path = normalize_path(base, path)?;
+        Ok((mount, path))
+    }
+
+    /// Returns the [`WasiDir`] mounted at index `mount`.
+    pub(crate) fn mount(&mut self, mount: usize) -> &mut dyn WasiDir {
+        &mut *self.mounts[mount].dir
+    }
+}
+
+/// Joins the guest `path` to the normalized `base` path and normalizes the result.
+///
+/// # Errors
+///
+/// - If `path` is empty.
+/// - If `path` is absolute or escapes `base` via `..` beyond the root.
+pub(crate) fn normalize_path(base: &str, path: &str) -> Result<String, Errno> {
+    if path.is_empty() {
+        return Err(Errno::Noent);
+    }
+    if path.starts_with('/') || path.contains('\0') {
+        return Err(Errno::Notcapable);
+    }
+    let mut components: Vec<&str> = base.split('/').filter(|c| !c.is_empty()).collect();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop().ok_or(Errno::Notcapable)?;
+            }
+            component => components.push(component),
+        }
+    }
+    Ok(components.join("/"))
+}
+
+/// Builds a [`WasiCtx`] for the native `wasi_snapshot_preview1` implementation.
+///
+/// By default the guest has no arguments, environment variables or preopened
+/// directories, reads EOF from stdin and its stdout and stderr are discarded.
+/// With the `std` crate feature the host clocks and random number source are
+/// used, without it clocks and random numbers are unavailable until provided.
+pub struct WasiCtxBuilder {
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    stdin: Box<dyn WasiFile>,
+    stdout: Box<dyn WasiFile>,
+    stderr: Box<dyn WasiFile>,
+    sockets: Vec<Box<dyn WasiFile>>,
+    preopens: Vec<Mount>,
+    clocks: Box<dyn WasiClocks>,
+    random: Box<dyn WasiRandom>,
+    tracer: Option<Box<dyn WasiTracer>>,
+}
+
+impl Default for WasiCtxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for WasiCtxBuilder {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("WasiCtxBuilder")
+            .field("args", &self.args)
+            .field("env", &self.env)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasiCtxBuilder {
+    /// Creates a new [`WasiCtxBuilder`] with the defaults described above.
+    pub fn new() -> Self {
+        #[cfg(feature = "std")]
+        let (clocks, random): (Box<dyn WasiClocks>, Box<dyn WasiRandom>) = (
+            Box::new(super::std::StdClocks::new()),
+            Box::new(super::std::StdRandom),
+        );
+        #[cfg(not(feature = "std"))]
+        let (clocks, random): (Box<dyn WasiClocks>, Box<dyn WasiRandom>) = (
+            Box::new(super::host::Unsupported),
+            Box::new(super::host::Unsupported),
+        );
+        Self {
+            args: Vec::new(),
+            env: Vec::new(),
+            stdin: Box::new(NullFile),
+            stdout: Box::new(NullFile),
+            stderr: Box::new(NullFile),
+            sockets: Vec::new(),
+            preopens: Vec::new(),
+            clocks,
+            random,
+            tracer: None,
+        }
+    }
+
+    /// Appends a command line argument.
+    ///
+    /// # Note
+    ///
+    /// By convention the first argument is the name of the program.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Appends all command line arguments of `args`.
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Appends an environment variable.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Appends all environment variables of `envs`.
+    pub fn envs<I, K, V>(mut self, envs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env
+            .extend(envs.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Sets the file used as stdin of the guest.
+    pub fn stdin(mut self, file: impl WasiFile + 'static) -> Self {
+        self.stdin = Box::new(file);
+        self
+    }
+
+    /// Sets the file used as stdout of the guest.
+    pub fn stdout(mut self, file: impl WasiFile + 'static) -> Self {
+        self.stdout = Box::new(file);
+        self
+    }
+
+    /// Sets the file used as stderr of the guest.
+    pub fn stderr(mut self, file: impl WasiFile + 'static) -> Self {
+        self.stderr = Box::new(file);
+        self
+    }
+
+    /// Adds the listening `socket` as preopened socket.
+    ///
+    /// Preopened sockets are assigned file descriptors starting at 3 in the order
+    /// they are added, followed by the preopened directories.
+    pub fn preopened_socket(mut self, socket: impl WasiFile + 'static) -> Self {
+        self.sockets.push(Box::new(socket));
+        self
+    }
+
+    /// Mounts `dir` as preopened directory that the guest sees at `guest_path`.
+    ///
+    /// Preopens are assigned file descriptors in the order they are added
+    /// following the preopened sockets.
+    pub fn preopened_dir(
+        mut self,
+        dir: impl WasiDir + 'static,
+        guest_path: impl Into<String>,
+    ) -> Self {
+        self.preopens.push(Mount {
+            dir: Box::new(dir),
+            guest_path: guest_path.into(),
+        });
+        self
+    }
+
+    /// Sets the source of time for the clock functions and `poll_oneoff`.
+    pub fn clocks(mut self, clocks: impl WasiClocks + 'static) -> Self {
+        self.clocks = Box::new(clocks);
+        self
+    }
+
+    /// Sets the source of random bytes for `random_get`.
+    pub fn random(mut self, random: impl WasiRandom + 'static) -> Self {
+        self.random = Box::new(random);
+        self
+    }
+
+    /// Sets the `tracer` observing all WASI function calls of the guest.
+    pub fn tracer(mut self, tracer: impl WasiTracer + 'static) -> Self {
+        self.tracer = Some(Box::new(tracer));
+        self
+    }
+
+    /// Makes the clocks and random number source deterministic.
+    ///
+    /// Uses [`VirtualClocks`] advancing by [`VirtualClocks::DEFAULT_STEP`] per reading
+    /// and [`SeededRandom`] with the given `seed`. Use [`WasiCtxBuilder::clocks`] with
+    /// custom [`VirtualClocks`] to advance the clocks differently.
+    pub fn deterministic(self, seed: u64) -> Self {
+        self.clocks(VirtualClocks::new())
+            .random(SeededRandom::new(seed))
+    }
+
+    /// Builds the [`WasiCtx`].
+    pub fn build(self) -> WasiCtx {
+        let mut fds = BTreeMap::new();
+        fds.insert(0, Descriptor::file(self.stdin));
+        fds.insert(1, Descriptor::file(self.stdout));
+        fds.insert(2, Descriptor::file(self.stderr));
+        let mut fd = 3;
+        for socket in self.sockets {
+            fds.insert(fd, Descriptor::file(socket));
+            fd += 1;
+        }
+        for (n, _) in self.preopens.iter().enumerate() {
+            fds.insert(
+                fd + n as u32,
+                Descriptor::Dir {
+                    mount: n,
+                    path: String::new(),
+                    preopen: true,
+                    rights: Rights::DIRECTORY,
+                    inheriting: Rights::DIRECTORY | Rights::REGULAR_FILE,
+                },
+            );
+        }
+        WasiCtx {
+            args: self.args,
+            env: self.env,
+            fds,
+            mounts: self.preopens,
+            clocks: self.clocks,
+            random: self.random,
+            tracer: self.tracer,
+        }
+    }
+}
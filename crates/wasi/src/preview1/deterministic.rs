@@ -0,0 +1,163 @@
+//! Deterministic clocks and random number sources for reproducible guest execution.
+
+use super::{
+    host::{WasiClocks, WasiRandom},
+    types::{ClockId, Errno},
+};
+
+/// How [`VirtualClocks`] advance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Advance {
+    /// Advance by the given nanoseconds whenever a clock is read.
+    Step(u64),
+    /// Advance by the given nanoseconds per unit of fuel consumed by the guest.
+    Fuel(u64),
+}
+
+/// Virtual [`WasiClocks`] that only advance deterministically.
+///
+/// All clocks start at zero, the realtime clock is offset by a configurable epoch.
+/// Sleeping advances the clocks by the requested duration without blocking.
+///
+/// # Note
+///
+/// Clocks advanced by fuel require fuel metering to be enabled for the
+/// [`Store`](wasmi::Store), otherwise they only advance while sleeping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VirtualClocks {
+    /// The realtime clock value at the start in nanoseconds since the Unix epoch.
+    epoch: u64,
+    /// How the clocks advance.
+    advance: Advance,
+    /// The nanoseconds the clocks have been advanced by steps and sleeps.
+    elapsed: u64,
+    /// The total amount of fuel consumed by the guest so far.
+    fuel: u64,
+}
+
+impl Default for VirtualClocks {
+    fn default() -> Self {
+        Self::with_step(Self::DEFAULT_STEP)
+    }
+}
+
+impl VirtualClocks {
+    /// The default step of one microsecond used by [`VirtualClocks::new`].
+    pub const DEFAULT_STEP: u64 = 1_000;
+
+    /// Creates new [`VirtualClocks`] that advance by [`VirtualClocks::DEFAULT_STEP`] per reading.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new [`VirtualClocks`] that advance by `step` nanoseconds whenever a clock is read.
+    pub fn with_step(step: u64) -> Self {
+        Self {
+            epoch: 0,
+            advance: Advance::Step(step),
+            elapsed: 0,
+            fuel: 0,
+        }
+    }
+
+    /// Creates new [`VirtualClocks`] that advance by `nanos` nanoseconds per unit of fuel consumed.
+    pub fn with_fuel_rate(nanos: u64) -> Self {
+        Self {
+            advance: Advance::Fuel(nanos),
+            ..Self::with_step(0)
+        }
+    }
+
+    /// Sets the value of the realtime clock at the start in nanoseconds since the Unix epoch.
+    pub fn epoch(mut self, epoch: u64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Returns the nanoseconds elapsed since the start.
+    fn elapsed(&self) -> u64 {
+        let fuel = match self.advance {
+            Advance::Step(_) => 0,
+            Advance::Fuel(nanos) => self.fuel.saturating_mul(nanos),
+        };
+        self.elapsed.saturating_add(fuel)
+    }
+}
+
+impl WasiClocks for VirtualClocks {
+    fn resolution(&self, _clock: ClockId) -> Result<u64, Errno> {
+        match self.advance {
+            Advance::Step(nanos) | Advance::Fuel(nanos) => Ok(nanos.max(1)),
+        }
+    }
+
+    fn now(&mut self, clock: ClockId) -> Result<u64, Errno> {
+        let elapsed = self.elapsed();
+        if let Advance::Step(step) = self.advance {
+            self.elapsed = self.elapsed.saturating_add(step);
+        }
+        match clock {
+            ClockId::Realtime => Ok(self.epoch.saturating_add(elapsed)),
+            _ => Ok(elapsed),
+        }
+    }
+
+    fn sleep(&mut self, nanos: u64) -> Result<(), Errno> {
+        self.elapsed = self.elapsed.saturating_add(nanos);
+        Ok(())
+    }
+
+    fn fuel_consumed(&mut self, fuel: u64) {
+        self.fuel = fuel;
+    }
+}
+
+/// A [`WasiRandom`] producing a reproducible sequence of bytes for a given seed.
+///
+/// # Note
+///
+/// This uses the `xoshiro256**` generator and is not cryptographically secure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRandom {
+    state: [u64; 4],
+}
+
+impl SeededRandom {
+    /// Creates a new [`SeededRandom`] from the `seed`.
+    pub fn new(seed: u64) -> Self {
+        // Note: the state is initialized via `splitmix64` as recommended by the `xoshiro` authors.
+        let mut seed = seed;
+        let state = [(); 4].map(|()| {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        });
+        Self { state }
+    }
+
+    /// Returns the next random `u64`.
+    fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s1.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+}
+
+impl WasiRandom for SeededRandom {
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Errno> {
+        for chunk in buf.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
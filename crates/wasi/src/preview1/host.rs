@@ -0,0 +1,291 @@
+//! Pluggable host traits backing the native `wasi_snapshot_preview1` implementation.
+
+use super::types::{ClockId, Errno, FdFlags, FileType, Filestat, Whence};
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// An open file, pipe or character device that can be used as WASI file descriptor.
+///
+/// Only [`WasiFile::filetype`] is required. All other methods default to
+/// the behavior of a device that does not support the operation.
+pub trait WasiFile: Send {
+    /// Returns the [`FileType`] of the file.
+    fn filetype(&self) -> FileType;
+
+    /// Reads bytes from the current position into `buf` and returns the number of bytes read.
+    ///
+    /// Returns `Ok(0)` at the end of the file.
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Errno> {
+        Err(Errno::Badf)
+    }
+
+    /// Writes bytes from `buf` to the current position and returns the number of bytes written.
+    fn write(&mut self, _buf: &[u8]) -> Result<usize, Errno> {
+        Err(Errno::Badf)
+    }
+
+    /// Reads bytes at `offset` into `buf` without changing the current position.
+    fn pread(&mut self, _buf: &mut [u8], _offset: u64) -> Result<usize, Errno> {
+        Err(Errno::Spipe)
+    }
+
+    /// Writes bytes from `buf` at `offset` without changing the current position.
+    fn pwrite(&mut self, _buf: &[u8], _offset: u64) -> Result<usize, Errno> {
+        Err(Errno::Spipe)
+    }
+
+    /// Moves the current position by `offset` relative to `whence` and returns the new position.
+    fn seek(&mut self, _offset: i64, _whence: Whence) -> Result<u64, Errno> {
+        Err(Errno::Spipe)
+    }
+
+    /// Returns the attributes of the file.
+    fn stat(&mut self) -> Result<Filestat, Errno> {
+        Ok(Filestat {
+            filetype: self.filetype(),
+            nlink: 1,
+            ..Filestat::default()
+        })
+    }
+
+    /// Truncates or extends the file to `size` bytes.
+    fn set_size(&mut self, _size: u64) -> Result<(), Errno> {
+        Err(Errno::Inval)
+    }
+
+    /// Sets the access and modification timestamps of the file if given.
+    fn set_times(&mut self, _atim: Option<u64>, _mtim: Option<u64>) -> Result<(), Errno> {
+        Ok(())
+    }
+
+    /// Ensures that the file has space for `len` bytes starting at `offset`.
+    fn allocate(&mut self, _offset: u64, _len: u64) -> Result<(), Errno> {
+        Err(Errno::Notsup)
+    }
+
+    /// Returns the [`FdFlags`] of the file.
+    fn flags(&self) -> FdFlags {
+        FdFlags::empty()
+    }
+
+    /// Sets the [`FdFlags`] of the file.
+    fn set_flags(&mut self, flags: FdFlags) -> Result<(), Errno> {
+        if flags != self.flags() {
+            return Err(Errno::Notsup);
+        }
+        Ok(())
+    }
+
+    /// Synchronizes the data and metadata of the file to its storage.
+    fn sync(&mut self) -> Result<(), Errno> {
+        Ok(())
+    }
+
+    /// Accepts a new connection on a listening socket.
+    fn accept(&mut self, _flags: FdFlags) -> Result<Box<dyn WasiFile>, Errno> {
+        Err(Errno::Notsock)
+    }
+
+    /// Shuts down the `read` and/or `write` half of a connected socket.
+    fn shutdown(&mut self, _read: bool, _write: bool) -> Result<(), Errno> {
+        Err(Errno::Notsock)
+    }
+}
+
+/// The options to open a file with via [`WasiDir::open`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct OpenOptions {
+    /// Open the file for reading.
+    pub read: bool,
+    /// Open the file for writing.
+    pub write: bool,
+    /// Create the file if it does not exist.
+    pub create: bool,
+    /// Fail if the file already exists. Only used together with `create`.
+    pub exclusive: bool,
+    /// Truncate the file to size 0.
+    pub truncate: bool,
+    /// Fail if the path does not refer to a directory.
+    pub directory: bool,
+    /// Follow a symbolic link at the end of the path.
+    pub follow: bool,
+    /// The initial [`FdFlags`] of the opened file.
+    pub flags: FdFlags,
+}
+
+/// The result of [`WasiDir::open`].
+pub enum Opened {
+    /// A file that has been opened.
+    File(Box<dyn WasiFile>),
+    /// A directory with its normalized path relative to the root of the [`WasiDir`].
+    Dir(String),
+}
+
+impl core::fmt::Debug for Opened {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::File(file) => f.debug_tuple("File").field(&file.filetype()).finish(),
+            Self::Dir(path) => f.debug_tuple("Dir").field(path).finish(),
+        }
+    }
+}
+
+/// An entry of a directory listed via [`WasiDir::read_dir`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    /// The name of the entry.
+    pub name: String,
+    /// The serial number of the entry.
+    pub ino: u64,
+    /// The type of the entry.
+    pub filetype: FileType,
+}
+
+/// A directory tree that can be mounted as WASI preopen.
+///
+/// All paths are relative to the root of the tree, use `/` as separator and
+/// never contain `.` or `..` components or leading slashes. The root itself
+/// is denoted by the empty path.
+///
+/// Implementations must make sure that symbolic links do not resolve to
+/// locations outside of the tree.
+pub trait WasiDir: Send {
+    /// Opens the file or directory at `path`.
+    fn open(&mut self, path: &str, options: &OpenOptions) -> Result<Opened, Errno>;
+
+    /// Returns the attributes of the file or directory at `path`.
+    ///
+    /// Symbolic links at the end of the `path` are followed if `follow` is `true`.
+    fn stat(&mut self, path: &str, follow: bool) -> Result<Filestat, Errno>;
+
+    /// Sets the access and modification timestamps of the file or directory at `path` if given.
+    fn set_times(
+        &mut self,
+        _path: &str,
+        _follow: bool,
+        _atim: Option<u64>,
+        _mtim: Option<u64>,
+    ) -> Result<(), Errno> {
+        Err(Errno::Notsup)
+    }
+
+    /// Returns the entries of the directory at `path` excluding `.` and `..`.
+    fn read_dir(&mut self, path: &str) -> Result<Vec<DirEntry>, Errno>;
+
+    /// Creates a directory at `path`.
+    fn create_dir(&mut self, _path: &str) -> Result<(), Errno> {
+        Err(Errno::Rofs)
+    }
+
+    /// Removes the empty directory at `path`.
+    fn remove_dir(&mut self, _path: &str) -> Result<(), Errno> {
+        Err(Errno::Rofs)
+    }
+
+    /// Removes the file or symbolic link at `path`.
+    fn unlink_file(&mut self, _path: &str) -> Result<(), Errno> {
+        Err(Errno::Rofs)
+    }
+
+    /// Renames the file or directory at `from` to `to`.
+    fn rename(&mut self, _from: &str, _to: &str) -> Result<(), Errno> {
+        Err(Errno::Rofs)
+    }
+
+    /// Creates a symbolic link at `path` pointing to `target`.
+    fn symlink(&mut self, _target: &str, _path: &str) -> Result<(), Errno> {
+        Err(Errno::Rofs)
+    }
+
+    /// Returns the target of the symbolic link at `path`.
+    fn read_link(&mut self, _path: &str) -> Result<String, Errno> {
+        Err(Errno::Inval)
+    }
+
+    /// Creates a hard link at `to` for the file at `from`.
+    fn link(&mut self, _from: &str, _to: &str, _follow: bool) -> Result<(), Errno> {
+        Err(Errno::Notsup)
+    }
+}
+
+/// A source of time for the WASI clock functions.
+pub trait WasiClocks: Send {
+    /// Returns the resolution of the clock in nanoseconds.
+    fn resolution(&self, clock: ClockId) -> Result<u64, Errno>;
+
+    /// Returns the current time of the clock in nanoseconds.
+    fn now(&mut self, clock: ClockId) -> Result<u64, Errno>;
+
+    /// Blocks the guest for `nanos` nanoseconds.
+    fn sleep(&mut self, nanos: u64) -> Result<(), Errno>;
+
+    /// Informs the clocks about the total amount of fuel consumed by the guest so far.
+    ///
+    /// This is called before every WASI function call if fuel metering is enabled.
+    fn fuel_consumed(&mut self, _fuel: u64) {}
+}
+
+/// A source of random bytes for `random_get`.
+pub trait WasiRandom: Send {
+    /// Fills `buf` with random bytes.
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Errno>;
+}
+
+/// A [`WasiFile`] that reads as empty and discards all writes, similar to `/dev/null`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NullFile;
+
+impl WasiFile for NullFile {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize, Errno> {
+        Ok(0)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        Ok(buf.len())
+    }
+}
+
+/// A stand-in for hosts without clocks or random number sources.
+///
+/// All operations fail with [`Errno::Nosys`].
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Unsupported;
+
+impl WasiClocks for Unsupported {
+    fn resolution(&self, _clock: ClockId) -> Result<u64, Errno> {
+        Err(Errno::Nosys)
+    }
+
+    fn now(&mut self, _clock: ClockId) -> Result<u64, Errno> {
+        Err(Errno::Nosys)
+    }
+
+    fn sleep(&mut self, _nanos: u64) -> Result<(), Errno> {
+        Err(Errno::Nosys)
+    }
+}
+
+impl WasiRandom for Unsupported {
+    fn fill(&mut self, _buf: &mut [u8]) -> Result<(), Errno> {
+        Err(Errno::Nosys)
+    }
+}
+
+/// Observes the WASI functions called by a guest, similar to `strace`.
+///
+/// Install a tracer via [`WasiCtxBuilder::tracer`](super::WasiCtxBuilder::tracer).
+pub trait WasiTracer: Send {
+    /// Called before the WASI function `name` is executed.
+    ///
+    /// The `args` are decoded for display: paths are read from guest memory,
+    /// iovecs are shown as the list of their lengths and rights in hexadecimal.
+    fn enter(&mut self, name: &str, args: &str);
+
+    /// Called after the WASI function `name` returned with `errno`.
+    ///
+    /// The `errno` is `None` if the function does not return such as `proc_exit`.
+    fn exit(&mut self, name: &str, errno: Option<Errno>);
+}
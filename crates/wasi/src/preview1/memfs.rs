@@ -0,0 +1,928 @@
+//! An in-memory directory tree that can be mounted as WASI preopen.
+
+use super::{
+    ctx::normalize_path,
+    host::{DirEntry, OpenOptions, Opened, WasiDir, WasiFile},
+    types::{Errno, FdFlags, FileType, Filestat, Whence},
+};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+use spin::Mutex;
+
+/// The serial number of the root directory of every [`MemFs`].
+const ROOT: u64 = 1;
+
+/// The maximum number of symbolic links followed while resolving a single path.
+const MAX_SYMLINKS: usize = 32;
+
+/// The contents of a [`Node`].
+#[derive(Debug)]
+enum NodeKind {
+    File(Vec<u8>),
+    Dir(BTreeMap<String, u64>),
+    Symlink(String),
+}
+
+/// A file, directory or symbolic link of a [`MemFs`].
+#[derive(Debug)]
+struct Node {
+    kind: NodeKind,
+    /// The number of directory entries referring to the [`Node`].
+    nlink: u64,
+    /// The number of open [`MemFile`]s referring to the [`Node`].
+    open: usize,
+    atim: u64,
+    mtim: u64,
+    ctim: u64,
+}
+
+impl Node {
+    /// Creates a new [`Node`] with a single link.
+    fn new(kind: NodeKind) -> Self {
+        Self {
+            kind,
+            nlink: 1,
+            open: 0,
+            atim: 0,
+            mtim: 0,
+            ctim: 0,
+        }
+    }
+
+    /// Returns the [`FileType`] of the [`Node`].
+    fn filetype(&self) -> FileType {
+        match self.kind {
+            NodeKind::File(_) => FileType::RegularFile,
+            NodeKind::Dir(_) => FileType::Directory,
+            NodeKind::Symlink(_) => FileType::SymbolicLink,
+        }
+    }
+
+    /// Returns the [`Filestat`] of the [`Node`] with serial number `ino`.
+    fn stat(&self, ino: u64) -> Filestat {
+        let size = match &self.kind {
+            NodeKind::File(data) => data.len() as u64,
+            NodeKind::Dir(entries) => entries.len() as u64,
+            NodeKind::Symlink(target) => target.len() as u64,
+        };
+        Filestat {
+            dev: 0,
+            ino,
+            filetype: self.filetype(),
+            nlink: self.nlink,
+            size,
+            atim: self.atim,
+            mtim: self.mtim,
+            ctim: self.ctim,
+        }
+    }
+
+    /// Sets the access and modification timestamps of the [`Node`] if given.
+    fn set_times(&mut self, atim: Option<u64>, mtim: Option<u64>) {
+        if let Some(atim) = atim {
+            self.atim = atim;
+        }
+        if let Some(mtim) = mtim {
+            self.mtim = mtim;
+        }
+    }
+}
+
+/// The shared state of a [`MemFs`].
+#[derive(Debug)]
+struct Tree {
+    nodes: BTreeMap<u64, Node>,
+    next_ino: u64,
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        let mut root = Node::new(NodeKind::Dir(BTreeMap::new()));
+        root.nlink = 2;
+        Self {
+            nodes: BTreeMap::from([(ROOT, root)]),
+            next_ino: ROOT + 1,
+        }
+    }
+}
+
+impl Tree {
+    /// Returns the [`Node`] with serial number `ino`.
+    fn node(&self, ino: u64) -> &Node {
+        &self.nodes[&ino]
+    }
+
+    /// Returns the [`Node`] with serial number `ino` mutably.
+    fn node_mut(&mut self, ino: u64) -> &mut Node {
+        self.nodes
+            .get_mut(&ino)
+            .unwrap_or_else(|| panic!("missing memfs node: {ino}"))
+    }
+
+    /// Returns the entries of the directory `ino`.
+    fn entries(&self, ino: u64) -> Result<&BTreeMap<String, u64>, Errno> {
+        match &self.node(ino).kind {
+            NodeKind::Dir(entries) => Ok(entries),
+            _ => Err(Errno::Notdir),
+        }
+    }
+
+    /// Returns the entries of the directory `ino` mutably.
+    fn entries_mut(&mut self, ino: u64) -> Result<&mut BTreeMap<String, u64>, Errno> {
+        match &mut self.node_mut(ino).kind {
+            NodeKind::Dir(entries) => Ok(entries),
+            _ => Err(Errno::Notdir),
+        }
+    }
+
+    /// Resolves the normalized `path` and returns the `(name, ino)` pairs from the root to its [`Node`].
+    ///
+    /// A symbolic link at the end of the `path` is only followed if `follow` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// - If a symbolic link resolves to a location outside of the tree.
+    /// - If more than [`MAX_SYMLINKS`] symbolic links are followed.
+    fn lookup(&self, path: &str, follow: bool) -> Result<Vec<(String, u64)>, Errno> {
+        let mut stack = vec![(String::new(), ROOT)];
+        let mut pending = path.split('/').map(String::from).collect::<VecDeque<_>>();
+        let mut followed = 0;
+        while let Some(name) = pending.pop_front() {
+            match name.as_str() {
+                "" | "." => continue,
+                ".." => {
+                    if stack.len() == 1 {
+                        return Err(Errno::Notcapable);
+                    }
+                    stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+            let dir = stack[stack.len() - 1].1;
+            let ino = *self.entries(dir)?.get(&name).ok_or(Errno::Noent)?;
+            if let NodeKind::Symlink(target) = &self.node(ino).kind {
+                if follow || !pending.is_empty() {
+                    followed += 1;
+                    if followed > MAX_SYMLINKS {
+                        return Err(Errno::Loop);
+                    }
+                    if target.starts_with('/') {
+                        return Err(Errno::Notcapable);
+                    }
+                    for component in target.split('/').rev() {
+                        pending.push_front(component.into());
+                    }
+                    continue;
+                }
+            }
+            stack.push((name, ino));
+        }
+        Ok(stack)
+    }
+
+    /// Resolves the normalized `path` and returns the serial number of its [`Node`].
+    fn resolve(&self, path: &str, follow: bool) -> Result<u64, Errno> {
+        let stack = self.lookup(path, follow)?;
+        Ok(stack[stack.len() - 1].1)
+    }
+
+    /// Resolves the parent directory of the normalized `path` and returns it together with the last component.
+    ///
+    /// # Errors
+    ///
+    /// If `path` refers to the root directory.
+    fn parent<'a>(&self, path: &'a str) -> Result<(u64, &'a str), Errno> {
+        if path.is_empty() {
+            return Err(Errno::Inval);
+        }
+        let (dir, name) = path.rsplit_once('/').unwrap_or(("", path));
+        let dir = self.resolve(dir, true)?;
+        self.entries(dir)?;
+        Ok((dir, name))
+    }
+
+    /// Inserts a new [`Node`] with `kind` as `name` into the directory `dir`.
+    fn insert(&mut self, dir: u64, name: &str, kind: NodeKind) -> Result<u64, Errno> {
+        if self.entries(dir)?.contains_key(name) {
+            return Err(Errno::Exist);
+        }
+        let ino = self.next_ino;
+        self.next_ino += 1;
+        let mut node = Node::new(kind);
+        if let NodeKind::Dir(_) = node.kind {
+            node.nlink = 2;
+        }
+        self.nodes.insert(ino, node);
+        self.entries_mut(dir)?.insert(name.into(), ino);
+        Ok(ino)
+    }
+
+    /// Removes the entry `name` from the directory `dir` and releases its [`Node`].
+    fn remove_entry(&mut self, dir: u64, name: &str) -> Result<(), Errno> {
+        let ino = self.entries_mut(dir)?.remove(name).ok_or(Errno::Noent)?;
+        let node = self.node_mut(ino);
+        node.nlink = match node.kind {
+            NodeKind::Dir(_) => 0,
+            _ => node.nlink - 1,
+        };
+        self.release(ino);
+        Ok(())
+    }
+
+    /// Drops the [`Node`] `ino` if it is neither linked nor open anymore.
+    fn release(&mut self, ino: u64) {
+        let node = self.node(ino);
+        if node.nlink == 0 && node.open == 0 {
+            self.nodes.remove(&ino);
+        }
+    }
+
+    /// Creates the directory at the normalized `path` and all of its missing parents.
+    fn create_dir_all(&mut self, path: &str) -> Result<u64, Errno> {
+        let mut dir = ROOT;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            dir = match self.entries(dir)?.get(name) {
+                Some(&ino) => {
+                    self.entries(ino)?;
+                    ino
+                }
+                None => self.insert(dir, name, NodeKind::Dir(BTreeMap::new()))?,
+            };
+        }
+        Ok(dir)
+    }
+
+    /// Collects all regular files below directory `dir` at `prefix` into `files`.
+    fn collect_files(&self, dir: u64, prefix: &str, files: &mut BTreeMap<String, Vec<u8>>) {
+        let Ok(entries) = self.entries(dir) else {
+            return;
+        };
+        for (name, &ino) in entries {
+            let path = match prefix {
+                "" => name.clone(),
+                prefix => alloc::format!("{prefix}/{name}"),
+            };
+            match &self.node(ino).kind {
+                NodeKind::File(data) => {
+                    files.insert(path, data.clone());
+                }
+                NodeKind::Dir(_) => self.collect_files(ino, &path, files),
+                NodeKind::Symlink(_) => {}
+            }
+        }
+    }
+}
+
+/// Normalizes a host provided `path` which may start with `/`.
+fn host_path(path: &str) -> Result<String, Errno> {
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        return Ok(String::new());
+    }
+    normalize_path("", path)
+}
+
+/// An in-memory directory tree that can be mounted as WASI preopen.
+///
+/// A [`MemFs`] is a cheap handle to shared state: keep a clone around to populate
+/// the tree before and to inspect it after the guest ran.
+///
+/// Supports regular files, directories, hard links and symbolic links.
+/// Symbolic links are resolved within the tree and never escape it.
+///
+/// # Example
+///
+/// ```
+/// use wasmi_wasi::preview1::{MemFs, WasiCtxBuilder};
+///
+/// let fs = MemFs::from_map([("etc/motd", "hello"), ("data/input.txt", "1 2 3")])?;
+/// let wasi = WasiCtxBuilder::new().preopened_dir(fs.clone(), "/").build();
+/// // ... run the guest ...
+/// assert_eq!(fs.read_file("etc/motd")?, b"hello");
+/// # Ok::<(), wasmi_wasi::preview1::Errno>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct MemFs {
+    tree: Arc<Mutex<Tree>>,
+}
+
+impl fmt::Debug for MemFs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemFs")
+            .field("nodes", &self.tree.lock().nodes.len())
+            .finish()
+    }
+}
+
+impl MemFs {
+    /// Creates a new empty [`MemFs`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`MemFs`] with the regular `files` given as `(path, contents)` pairs.
+    ///
+    /// Missing parent directories are created.
+    ///
+    /// # Errors
+    ///
+    /// If a path is invalid or conflicts with another one.
+    pub fn from_map<I, P, C>(files: I) -> Result<Self, Errno>
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: AsRef<str>,
+        C: Into<Vec<u8>>,
+    {
+        let fs = Self::new();
+        for (path, contents) in files {
+            fs.write_file(path.as_ref(), contents)?;
+        }
+        Ok(fs)
+    }
+
+    /// Creates a new [`MemFs`] from the entries of the uncompressed tar `archive`.
+    ///
+    /// Regular files, directories, symbolic links and hard links are supported.
+    /// Long path names in GNU and PAX format are honored, all other entry types are skipped.
+    ///
+    /// # Errors
+    ///
+    /// If the `archive` is malformed or contains conflicting entries.
+    pub fn from_tar(archive: &[u8]) -> Result<Self, TarError> {
+        let fs = Self::new();
+        fs.extract_tar(archive)?;
+        Ok(fs)
+    }
+
+    /// Creates the directory at `path` and all of its missing parents.
+    ///
+    /// # Errors
+    ///
+    /// If `path` is invalid or a component of it is not a directory.
+    pub fn create_dir_all(&self, path: &str) -> Result<(), Errno> {
+        let path = host_path(path)?;
+        self.tree.lock().create_dir_all(&path)?;
+        Ok(())
+    }
+
+    /// Writes `contents` to the regular file at `path` replacing any previous contents.
+    ///
+    /// Missing parent directories are created.
+    ///
+    /// # Errors
+    ///
+    /// If `path` is invalid or refers to a directory.
+    pub fn write_file(&self, path: &str, contents: impl Into<Vec<u8>>) -> Result<(), Errno> {
+        let path = host_path(path)?;
+        let mut tree = self.tree.lock();
+        let (dir, name) = path.rsplit_once('/').unwrap_or(("", &path));
+        if name.is_empty() {
+            return Err(Errno::Isdir);
+        }
+        let dir = tree.create_dir_all(dir)?;
+        let ino = match tree.entries(dir)?.get(name) {
+            Some(&ino) => ino,
+            None => tree.insert(dir, name, NodeKind::File(Vec::new()))?,
+        };
+        match &mut tree.node_mut(ino).kind {
+            NodeKind::File(data) => *data = contents.into(),
+            NodeKind::Dir(_) => return Err(Errno::Isdir),
+            NodeKind::Symlink(_) => return Err(Errno::Exist),
+        }
+        Ok(())
+    }
+
+    /// Creates a symbolic link at `path` pointing to `target`.
+    ///
+    /// Missing parent directories are created.
+    ///
+    /// # Errors
+    ///
+    /// If `path` is invalid or already exists.
+    pub fn create_symlink(&self, target: &str, path: &str) -> Result<(), Errno> {
+        let path = host_path(path)?;
+        let mut tree = self.tree.lock();
+        let (dir, name) = path.rsplit_once('/').unwrap_or(("", &path));
+        if name.is_empty() {
+            return Err(Errno::Exist);
+        }
+        let dir = tree.create_dir_all(dir)?;
+        tree.insert(dir, name, NodeKind::Symlink(target.into()))?;
+        Ok(())
+    }
+
+    /// Returns the contents of the regular file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If `path` does not refer to a regular file.
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, Errno> {
+        let path = host_path(path)?;
+        let tree = self.tree.lock();
+        match &tree.node(tree.resolve(&path, true)?).kind {
+            NodeKind::File(data) => Ok(data.clone()),
+            NodeKind::Dir(_) => Err(Errno::Isdir),
+            NodeKind::Symlink(_) => Err(Errno::Loop),
+        }
+    }
+
+    /// Returns the attributes of the file or directory at `path` following symbolic links.
+    ///
+    /// # Errors
+    ///
+    /// If `path` does not exist.
+    pub fn metadata(&self, path: &str) -> Result<Filestat, Errno> {
+        let path = host_path(path)?;
+        let tree = self.tree.lock();
+        let ino = tree.resolve(&path, true)?;
+        Ok(tree.node(ino).stat(ino))
+    }
+
+    /// Returns `true` if `path` exists without following a symbolic link at its end.
+    pub fn exists(&self, path: &str) -> bool {
+        host_path(path).is_ok_and(|path| self.tree.lock().resolve(&path, false).is_ok())
+    }
+
+    /// Returns the entries of the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If `path` does not refer to a directory.
+    pub fn entries(&self, path: &str) -> Result<Vec<DirEntry>, Errno> {
+        let path = host_path(path)?;
+        self.clone().read_dir(&path)
+    }
+
+    /// Returns the contents of all regular files in the tree by their path.
+    pub fn files(&self) -> BTreeMap<String, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        self.tree.lock().collect_files(ROOT, "", &mut files);
+        files
+    }
+
+    /// Adds the entries of the tar `archive` to the [`MemFs`].
+    fn extract_tar(&self, archive: &[u8]) -> Result<(), TarError> {
+        let mut offset = 0;
+        let mut long_name = None;
+        let mut long_link = None;
+        while let Some(header) = archive.get(offset..offset + 512) {
+            if header.iter().all(|&byte| byte == 0) {
+                return Ok(());
+            }
+            let expected =
+                parse_octal(&header[148..156]).ok_or(TarError::InvalidHeader { offset })?;
+            let checksum = header
+                .iter()
+                .enumerate()
+                .map(|(i, &byte)| match i {
+                    148..=155 => u64::from(b' '),
+                    _ => u64::from(byte),
+                })
+                .sum::<u64>();
+            if checksum != expected {
+                return Err(TarError::InvalidHeader { offset });
+            }
+            let size = parse_octal(&header[124..136]).ok_or(TarError::InvalidHeader { offset })?;
+            let size = usize::try_from(size).map_err(|_| TarError::UnexpectedEof)?;
+            let data_start = offset + 512;
+            let data = data_start
+                .checked_add(size)
+                .and_then(|data_end| archive.get(data_start..data_end))
+                .ok_or(TarError::UnexpectedEof)?;
+            offset = data_start + size.next_multiple_of(512);
+            let typeflag = header[156];
+            match typeflag {
+                b'L' => {
+                    long_name = Some(tar_string(data));
+                    continue;
+                }
+                b'K' => {
+                    long_link = Some(tar_string(data));
+                    continue;
+                }
+                b'x' => {
+                    for (key, value) in pax_records(data) {
+                        match key {
+                            "path" => long_name = Some(value.into()),
+                            "linkpath" => long_link = Some(value.into()),
+                            _ => {}
+                        }
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            let path = long_name.take().unwrap_or_else(|| {
+                let name = tar_string(&header[0..100]);
+                let prefix = match &header[257..263] {
+                    b"ustar\0" => tar_string(&header[345..500]),
+                    _ => String::new(),
+                };
+                match prefix.is_empty() {
+                    true => name,
+                    false => alloc::format!("{prefix}/{name}"),
+                }
+            });
+            let link = long_link
+                .take()
+                .unwrap_or_else(|| tar_string(&header[157..257]));
+            let mtim = parse_octal(&header[136..148])
+                .unwrap_or(0)
+                .saturating_mul(1_000_000_000);
+            let result = match typeflag {
+                b'0' | b'\0' | b'7' => self.write_file(&path, data),
+                b'5' => self.create_dir_all(&path),
+                b'2' => self.create_symlink(&link, &path),
+                b'1' => host_path(&link)
+                    .and_then(|link| host_path(&path).map(|path| (link, path)))
+                    .and_then(|(link, path)| self.clone().link(&link, &path, false)),
+                _ => continue,
+            };
+            result.map_err(|errno| TarError::InvalidEntry {
+                path: path.clone(),
+                errno,
+            })?;
+            if let Ok(path) = host_path(&path) {
+                let mut tree = self.tree.lock();
+                if let Ok(ino) = tree.resolve(&path, false) {
+                    tree.node_mut(ino).set_times(Some(mtim), Some(mtim));
+                }
+            }
+        }
+        match offset >= archive.len() {
+            true => Ok(()),
+            false => Err(TarError::UnexpectedEof),
+        }
+    }
+}
+
+/// Parses a NUL or space terminated octal number or a base-256 number of a tar header.
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    if field.first().is_some_and(|&byte| byte & 0x80 != 0) {
+        return field[1..]
+            .iter()
+            .try_fold(u64::from(field[0] & 0x7F), |acc, &byte| {
+                acc.checked_mul(256)?.checked_add(u64::from(byte))
+            });
+    }
+    field
+        .iter()
+        .skip_while(|&&byte| byte == b' ')
+        .take_while(|&&byte| byte != 0 && byte != b' ')
+        .try_fold(0_u64, |acc, &byte| match byte {
+            b'0'..=b'7' => acc.checked_mul(8)?.checked_add(u64::from(byte - b'0')),
+            _ => None,
+        })
+}
+
+/// Returns the NUL terminated string of a tar header field.
+fn tar_string(field: &[u8]) -> String {
+    let len = field
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..len]).into_owned()
+}
+
+/// Returns the `key=value` pairs of the PAX extended header `data`.
+fn pax_records(data: &[u8]) -> impl Iterator<Item = (&str, &str)> {
+    core::str::from_utf8(data)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|record| {
+            let (_len, record) = record.split_once(' ')?;
+            record.split_once('=')
+        })
+}
+
+/// An error that may occur when reading a tar archive via [`MemFs::from_tar`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TarError {
+    /// The archive ended in the middle of an entry.
+    UnexpectedEof,
+    /// The header at `offset` is malformed.
+    InvalidHeader { offset: usize },
+    /// The entry at `path` could not be added to the [`MemFs`].
+    InvalidEntry { path: String, errno: Errno },
+}
+
+impl fmt::Display for TarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "unexpected end of tar archive"),
+            Self::InvalidHeader { offset } => write!(f, "invalid tar header at offset {offset}"),
+            Self::InvalidEntry { path, errno } => write!(f, "invalid tar entry {path}: {errno}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TarError {}
+
+impl WasiDir for MemFs {
+    fn open(&mut self, path: &str, options: &OpenOptions) -> Result<Opened, Errno> {
+        let mut tree = self.tree.lock();
+        let ino = match tree.lookup(path, options.follow) {
+            Ok(stack) => {
+                let (_, ino) = stack[stack.len() - 1];
+                if options.create && options.exclusive {
+                    return Err(Errno::Exist);
+                }
+                match &mut tree.node_mut(ino).kind {
+                    NodeKind::Dir(_) if options.write => return Err(Errno::Isdir),
+                    NodeKind::Dir(_) => {
+                        let path = stack[1..]
+                            .iter()
+                            .map(|(name, _)| name.as_str())
+                            .collect::<Vec<_>>()
+                            .join("/");
+                        return Ok(Opened::Dir(path));
+                    }
+                    NodeKind::Symlink(_) => return Err(Errno::Loop),
+                    NodeKind::File(_) if options.directory => return Err(Errno::Notdir),
+                    NodeKind::File(data) => {
+                        if options.truncate {
+                            data.clear();
+                        }
+                    }
+                }
+                ino
+            }
+            Err(Errno::Noent) if options.create => {
+                if options.directory {
+                    return Err(Errno::Inval);
+                }
+                let (dir, name) = tree.parent(path)?;
+                tree.insert(dir, name, NodeKind::File(Vec::new()))?
+            }
+            Err(errno) => return Err(errno),
+        };
+        tree.node_mut(ino).open += 1;
+        drop(tree);
+        Ok(Opened::File(Box::new(MemFile {
+            fs: self.clone(),
+            ino,
+            position: 0,
+            read: options.read,
+            write: options.write,
+            flags: options.flags & FdFlags::APPEND,
+        })))
+    }
+
+    fn stat(&mut self, path: &str, follow: bool) -> Result<Filestat, Errno> {
+        let tree = self.tree.lock();
+        let ino = tree.resolve(path, follow)?;
+        Ok(tree.node(ino).stat(ino))
+    }
+
+    fn set_times(
+        &mut self,
+        path: &str,
+        follow: bool,
+        atim: Option<u64>,
+        mtim: Option<u64>,
+    ) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        let ino = tree.resolve(path, follow)?;
+        tree.node_mut(ino).set_times(atim, mtim);
+        Ok(())
+    }
+
+    fn read_dir(&mut self, path: &str) -> Result<Vec<DirEntry>, Errno> {
+        let tree = self.tree.lock();
+        let entries = tree.entries(tree.resolve(path, true)?)?;
+        Ok(entries
+            .iter()
+            .map(|(name, &ino)| DirEntry {
+                name: name.clone(),
+                ino,
+                filetype: tree.node(ino).filetype(),
+            })
+            .collect())
+    }
+
+    fn create_dir(&mut self, path: &str) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        if path.is_empty() {
+            return Err(Errno::Exist);
+        }
+        let (dir, name) = tree.parent(path)?;
+        tree.insert(dir, name, NodeKind::Dir(BTreeMap::new()))?;
+        Ok(())
+    }
+
+    fn remove_dir(&mut self, path: &str) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        if path.is_empty() {
+            return Err(Errno::Busy);
+        }
+        let (dir, name) = tree.parent(path)?;
+        let ino = *tree.entries(dir)?.get(name).ok_or(Errno::Noent)?;
+        if !tree.entries(ino)?.is_empty() {
+            return Err(Errno::Notempty);
+        }
+        tree.remove_entry(dir, name)
+    }
+
+    fn unlink_file(&mut self, path: &str) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        let (dir, name) = tree.parent(path)?;
+        let ino = *tree.entries(dir)?.get(name).ok_or(Errno::Noent)?;
+        if let NodeKind::Dir(_) = tree.node(ino).kind {
+            return Err(Errno::Isdir);
+        }
+        tree.remove_entry(dir, name)
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        let (from_dir, from_name) = tree.parent(from)?;
+        let (to_dir, to_name) = tree.parent(to)?;
+        let ino = *tree.entries(from_dir)?.get(from_name).ok_or(Errno::Noent)?;
+        let is_dir = matches!(tree.node(ino).kind, NodeKind::Dir(_));
+        if is_dir {
+            let to_parents = tree.lookup(to.rsplit_once('/').map_or("", |(dir, _)| dir), true)?;
+            if to_parents.iter().any(|&(_, parent)| parent == ino) {
+                return Err(Errno::Inval);
+            }
+        }
+        if let Some(&existing) = tree.entries(to_dir)?.get(to_name) {
+            if existing == ino {
+                return Ok(());
+            }
+            match (&tree.node(existing).kind, is_dir) {
+                (NodeKind::Dir(entries), true) if !entries.is_empty() => {
+                    return Err(Errno::Notempty)
+                }
+                (NodeKind::Dir(_), true) => {}
+                (NodeKind::Dir(_), false) => return Err(Errno::Isdir),
+                (_, true) => return Err(Errno::Notdir),
+                (_, false) => {}
+            }
+            tree.remove_entry(to_dir, to_name)?;
+        }
+        tree.entries_mut(from_dir)?.remove(from_name);
+        tree.entries_mut(to_dir)?.insert(to_name.to_string(), ino);
+        Ok(())
+    }
+
+    fn symlink(&mut self, target: &str, path: &str) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        let (dir, name) = tree.parent(path)?;
+        tree.insert(dir, name, NodeKind::Symlink(target.into()))?;
+        Ok(())
+    }
+
+    fn read_link(&mut self, path: &str) -> Result<String, Errno> {
+        let tree = self.tree.lock();
+        match &tree.node(tree.resolve(path, false)?).kind {
+            NodeKind::Symlink(target) => Ok(target.clone()),
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn link(&mut self, from: &str, to: &str, follow: bool) -> Result<(), Errno> {
+        let mut tree = self.tree.lock();
+        let ino = tree.resolve(from, follow)?;
+        if let NodeKind::Dir(_) = tree.node(ino).kind {
+            return Err(Errno::Perm);
+        }
+        let (dir, name) = tree.parent(to)?;
+        if tree.entries(dir)?.contains_key(name) {
+            return Err(Errno::Exist);
+        }
+        tree.entries_mut(dir)?.insert(name.into(), ino);
+        tree.node_mut(ino).nlink += 1;
+        Ok(())
+    }
+}
+
+/// A regular file of a [`MemFs`] opened by the guest.
+struct MemFile {
+    fs: MemFs,
+    ino: u64,
+    position: u64,
+    read: bool,
+    write: bool,
+    flags: FdFlags,
+}
+
+impl Drop for MemFile {
+    fn drop(&mut self) {
+        let mut tree = self.fs.tree.lock();
+        tree.node_mut(self.ino).open -= 1;
+        tree.release(self.ino);
+    }
+}
+
+impl MemFile {
+    /// Calls `f` with the contents of the file.
+    fn with_data<R>(&self, f: impl FnOnce(&mut Vec<u8>) -> R) -> Result<R, Errno> {
+        match &mut self.fs.tree.lock().node_mut(self.ino).kind {
+            NodeKind::File(data) => Ok(f(data)),
+            _ => Err(Errno::Badf),
+        }
+    }
+}
+
+impl WasiFile for MemFile {
+    fn filetype(&self) -> FileType {
+        FileType::RegularFile
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Errno> {
+        if !self.read {
+            return Err(Errno::Badf);
+        }
+        let len = self.pread(buf, self.position)?;
+        self.position += len as u64;
+        Ok(len)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        if !self.write {
+            return Err(Errno::Badf);
+        }
+        if self.flags.contains(FdFlags::APPEND) {
+            self.position = self.with_data(|data| data.len() as u64)?;
+        }
+        let len = self.pwrite(buf, self.position)?;
+        self.position += len as u64;
+        Ok(len)
+    }
+
+    fn pread(&mut self, buf: &mut [u8], offset: u64) -> Result<usize, Errno> {
+        self.with_data(|data| {
+            let start = usize::try_from(offset).map_or(data.len(), |offset| offset.min(data.len()));
+            let len = buf.len().min(data.len() - start);
+            buf[..len].copy_from_slice(&data[start..start + len]);
+            len
+        })
+    }
+
+    fn pwrite(&mut self, buf: &[u8], offset: u64) -> Result<usize, Errno> {
+        let start = usize::try_from(offset).map_err(|_| Errno::Fbig)?;
+        let end = start.checked_add(buf.len()).ok_or(Errno::Fbig)?;
+        self.with_data(|data| {
+            if data.len() < end {
+                data.resize(end, 0x00);
+            }
+            data[start..end].copy_from_slice(buf);
+            buf.len()
+        })
+    }
+
+    fn seek(&mut self, offset: i64, whence: Whence) -> Result<u64, Errno> {
+        let base = match whence {
+            Whence::Set => 0,
+            Whence::Cur => self.position,
+            Whence::End => self.with_data(|data| data.len() as u64)?,
+        };
+        self.position = base.checked_add_signed(offset).ok_or(Errno::Inval)?;
+        Ok(self.position)
+    }
+
+    fn stat(&mut self) -> Result<Filestat, Errno> {
+        Ok(self.fs.tree.lock().node(self.ino).stat(self.ino))
+    }
+
+    fn set_size(&mut self, size: u64) -> Result<(), Errno> {
+        let size = usize::try_from(size).map_err(|_| Errno::Fbig)?;
+        self.with_data(|data| data.resize(size, 0x00))
+    }
+
+    fn set_times(&mut self, atim: Option<u64>, mtim: Option<u64>) -> Result<(), Errno> {
+        self.fs.tree.lock().node_mut(self.ino).set_times(atim, mtim);
+        Ok(())
+    }
+
+    fn allocate(&mut self, offset: u64, len: u64) -> Result<(), Errno> {
+        let end = offset.checked_add(len).ok_or(Errno::Fbig)?;
+        let end = usize::try_from(end).map_err(|_| Errno::Fbig)?;
+        self.with_data(|data| {
+            if data.len() < end {
+                data.resize(end, 0x00);
+            }
+        })
+    }
+
+    fn flags(&self) -> FdFlags {
+        self.flags
+    }
+
+    fn set_flags(&mut self, flags: FdFlags) -> Result<(), Errno> {
+        if !(flags & !FdFlags::APPEND).is_empty() {
+            return Err(Errno::Notsup);
+        }
+        self.flags = flags;
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use super::types::Errno;
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+use wasmi::SharedMemory;
+
+/// Bounds checked access to the linear memory of a WASI guest.
+///
+/// All out of bounds accesses result in [`Errno::Fault`].
+///
+/// # Note
+///
+/// The bytes of a shared linear memory may be accessed by other threads
+/// concurrently and are therefore copied instead of borrowed.
+pub(crate) enum GuestMemory<'a> {
+    /// The borrowed bytes of an unshared linear memory.
+    Bytes(&'a mut [u8]),
+    /// A shared linear memory.
+    Shared(SharedMemory),
+}
+
+impl<'a> GuestMemory<'a> {
+    /// Creates a new [`GuestMemory`] for the `bytes` of a linear memory.
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Self::Bytes(bytes)
+    }
+
+    /// Creates a new [`GuestMemory`] for a `shared` linear memory.
+    pub fn shared(shared: SharedMemory) -> Self {
+        Self::Shared(shared)
+    }
+
+    /// Returns the size of the linear memory in bytes.
+    fn len(&self) -> usize {
+        match self {
+            Self::Bytes(bytes) => bytes.len(),
+            Self::Shared(shared) => shared.data_size(),
+        }
+    }
+
+    /// Returns the byte range of `len` bytes at `ptr`.
+    fn range(&self, ptr: u32, len: u32) -> Result<core::ops::Range<usize>, Errno> {
+        let start = ptr as usize;
+        let end = start.checked_add(len as usize).ok_or(Errno::Fault)?;
+        if end > self.len() {
+            return Err(Errno::Fault);
+        }
+        Ok(start..end)
+    }
+
+    /// Returns the `len` bytes at `ptr`.
+    pub fn slice(&self, ptr: u32, len: u32) -> Result<Cow<'_, [u8]>, Errno> {
+        let range = self.range(ptr, len)?;
+        match self {
+            Self::Bytes(bytes) => Ok(Cow::Borrowed(&bytes[range])),
+            Self::Shared(shared) => {
+                let mut buffer = vec![0x00; range.len()];
+                shared
+                    .read(range.start, &mut buffer)
+                    .map_err(|_| Errno::Fault)?;
+                Ok(Cow::Owned(buffer))
+            }
+        }
+    }
+
+    /// Calls `f` with a buffer for the `len` bytes at `ptr` and stores the first `n` bytes
+    /// filled by `f` in the linear memory where `n` is returned by `f`.
+    ///
+    /// Returns `n` upon success.
+    pub fn fill_with(
+        &mut self,
+        ptr: u32,
+        len: u32,
+        f: impl FnOnce(&mut [u8]) -> Result<usize, Errno>,
+    ) -> Result<usize, Errno> {
+        let range = self.range(ptr, len)?;
+        match self {
+            Self::Bytes(bytes) => f(&mut bytes[range]),
+            Self::Shared(shared) => {
+                let mut buffer = vec![0x00; range.len()];
+                let filled = f(&mut buffer)?;
+                let filled_bytes = buffer.get(..filled).ok_or(Errno::Fault)?;
+                shared
+                    .write(range.start, filled_bytes)
+                    .map_err(|_| Errno::Fault)?;
+                Ok(filled)
+            }
+        }
+    }
+
+    /// Returns the UTF-8 encoded string of `len` bytes at `ptr`.
+    pub fn str(&self, ptr: u32, len: u32) -> Result<Cow<'_, str>, Errno> {
+        match self.slice(ptr, len)? {
+            Cow::Borrowed(bytes) => core::str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|_| Errno::Ilseq),
+            Cow::Owned(bytes) => String::from_utf8(bytes)
+                .map(Cow::Owned)
+                .map_err(|_| Errno::Ilseq),
+        }
+    }
+
+    /// Reads the little-endian `N` bytes at `ptr`.
+    fn read<const N: usize>(&self, ptr: u32) -> Result<[u8; N], Errno> {
+        let mut bytes = [0x00; N];
+        bytes.copy_from_slice(&self.slice(ptr, N as u32)?);
+        Ok(bytes)
+    }
+
+    /// Reads the `u8` at `ptr`.
+    pub fn read_u8(&self, ptr: u32) -> Result<u8, Errno> {
+        self.read::<1>(ptr).map(u8::from_le_bytes)
+    }
+
+    /// Reads the `u16` at `ptr`.
+    pub fn read_u16(&self, ptr: u32) -> Result<u16, Errno> {
+        self.read::<2>(ptr).map(u16::from_le_bytes)
+    }
+
+    /// Reads the `u32` at `ptr`.
+    pub fn read_u32(&self, ptr: u32) -> Result<u32, Errno> {
+        self.read::<4>(ptr).map(u32::from_le_bytes)
+    }
+
+    /// Reads the `u64` at `ptr`.
+    pub fn read_u64(&self, ptr: u32) -> Result<u64, Errno> {
+        self.read::<8>(ptr).map(u64::from_le_bytes)
+    }
+
+    /// Writes `bytes` at `ptr`.
+    pub fn write(&mut self, ptr: u32, bytes: &[u8]) -> Result<(), Errno> {
+        let len = u32::try_from(bytes.len()).map_err(|_| Errno::Fault)?;
+        let range = self.range(ptr, len)?;
+        match self {
+            Self::Bytes(memory) => memory[range].copy_from_slice(bytes),
+            Self::Shared(shared) => shared.write(range.start, bytes).map_err(|_| Errno::Fault)?,
+        }
+        Ok(())
+    }
+
+    /// Writes the `u8` at `ptr`.
+    pub fn write_u8(&mut self, ptr: u32, value: u8) -> Result<(), Errno> {
+        self.write(ptr, &value.to_le_bytes())
+    }
+
+    /// Writes the `u16` at `ptr`.
+    pub fn write_u16(&mut self, ptr: u32, value: u16) -> Result<(), Errno> {
+        self.write(ptr, &value.to_le_bytes())
+    }
+
+    /// Writes the `u32` at `ptr`.
+    pub fn write_u32(&mut self, ptr: u32, value: u32) -> Result<(), Errno> {
+        self.write(ptr, &value.to_le_bytes())
+    }
+
+    /// Writes the `u64` at `ptr`.
+    pub fn write_u64(&mut self, ptr: u32, value: u64) -> Result<(), Errno> {
+        self.write(ptr, &value.to_le_bytes())
+    }
+
+    /// Returns the `(buf, buf_len)` pairs of the `len` I/O vectors at `ptr`.
+    pub fn iovecs(&self, ptr: u32, len: u32) -> Result<Vec<(u32, u32)>, Errno> {
+        let bytes = self.slice(ptr, len.checked_mul(8).ok_or(Errno::Fault)?)?;
+        let iovecs = bytes
+            .chunks_exact(8)
+            .map(|iovec| {
+                let buf = u32::from_le_bytes([iovec[0], iovec[1], iovec[2], iovec[3]]);
+                let len = u32::from_le_bytes([iovec[4], iovec[5], iovec[6], iovec[7]]);
+                (buf, len)
+            })
+            .collect::<Vec<_>>();
+        for &(buf, len) in &iovecs {
+            self.range(buf, len)?;
+        }
+        Ok(iovecs)
+    }
+}
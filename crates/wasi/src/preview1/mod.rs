@@ -0,0 +1,295 @@
+//! Native implementation of WASI `preview1` built directly on top of the Wasmi [`Linker`].
+//!
+//! Unlike the `wasi-common` based implementation this one does not require `std`
+//! and does not pull in any dependencies besides `wasmi` itself. The host side
+//! is pluggable via the [`WasiFile`], [`WasiDir`], [`WasiClocks`] and [`WasiRandom`]
+//! traits. With the `std` crate feature implementations backed by the host
+//! operating system are provided. [`MemFs`] provides an in-memory directory
+//! tree for tests and sandboxed environments, [`ReadPipe`] and [`WritePipe`]
+//! feed and capture the stdio of a guest. [`VirtualClocks`] and [`SeededRandom`]
+//! make guest execution reproducible. A [`WasiTracer`] observes the WASI calls of
+//! a guest similar to `strace`.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{Engine, Linker, Module, Store};
+//! use wasmi_wasi::preview1::{self, WasiCtx, WasiCtxBuilder};
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (import "wasi_snapshot_preview1" "args_sizes_get"
+//!             (func $args_sizes_get (param i32 i32) (result i32))
+//!         )
+//!         (memory (export "memory") 1)
+//!         (func (export "argc") (result i32)
+//!             (drop (call $args_sizes_get (i32.const 0) (i32.const 4)))
+//!             (i32.load (i32.const 0))
+//!         )
+//!     )
+//! "#)?;
+//! let engine = Engine::default();
+//! let module = Module::new(&engine, &wasm[..])?;
+//! let wasi = WasiCtxBuilder::new().args(["main.wasm", "--verbose"]).build();
+//! let mut store = Store::new(&engine, wasi);
+//! let mut linker = <Linker<WasiCtx>>::new(&engine);
+//! preview1::add_to_linker(&mut linker, |ctx| ctx)?;
+//! let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+//! let argc = instance.get_typed_func::<(), i32>(&store, "argc")?;
+//! assert_eq!(argc.call(&mut store, ())?, 2);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+mod ctx;
+mod deterministic;
+mod host;
+mod memfs;
+mod memory;
+mod pipe;
+#[cfg(feature = "std")]
+mod std;
+mod syscalls;
+mod trace;
+mod types;
+
+#[cfg(feature = "preview2")]
+pub(crate) use self::ctx::{normalize_path, Descriptor};
+#[cfg(feature = "std")]
+pub use self::std::{
+    StdClocks,
+    StdDir,
+    StdFile,
+    StdRandom,
+    StdTracer,
+    Stderr,
+    Stdin,
+    Stdout,
+    TcpListener,
+    TcpStream,
+};
+pub use self::{
+    ctx::{WasiCtx, WasiCtxBuilder},
+    deterministic::{SeededRandom, VirtualClocks},
+    host::{
+        DirEntry,
+        NullFile,
+        OpenOptions,
+        Opened,
+        Unsupported,
+        WasiClocks,
+        WasiDir,
+        WasiFile,
+        WasiRandom,
+        WasiTracer,
+    },
+    memfs::{MemFs, TarError},
+    pipe::{ReadPipe, WritePipe},
+    types::{
+        ClockId,
+        Errno,
+        FdFlags,
+        FileType,
+        Filestat,
+        FstFlags,
+        LookupFlags,
+        OFlags,
+        Rights,
+        Whence,
+    },
+};
+pub(crate) use self::{
+    memory::GuestMemory,
+    trace::{decode_args, TraceArg},
+};
+use wasmi::{Caller, Extern, Linker};
+
+/// The module name under which all WASI `preview1` functions are defined.
+const MODULE: &str = "wasi_snapshot_preview1";
+
+/// Runs the WASI function `f` with the [`WasiCtx`] and linear memory of the `caller`.
+///
+/// The call is reported to the [`WasiTracer`] of the [`WasiCtx`] if any
+/// using the `name` of the WASI function and its raw `args`.
+///
+/// # Note
+///
+/// - Guests without a `memory` export are provided an empty linear memory so that
+///   functions that do not access memory can still be used.
+/// - Shared linear memories are accessed via [`SharedMemory::read`] and
+///   [`SharedMemory::write`] since other threads may access them concurrently.
+///
+/// [`SharedMemory::read`]: wasmi::SharedMemory::read
+/// [`SharedMemory::write`]: wasmi::SharedMemory::write
+fn call<T>(
+    caller: &mut Caller<'_, T>,
+    get: impl Fn(&mut T) -> &mut WasiCtx,
+    name: &str,
+    args: &[(&str, TraceArg)],
+    f: impl FnOnce(&mut WasiCtx, &mut GuestMemory) -> Result<(), Errno>,
+) -> i32 {
+    let fuel = caller.fuel_consumed();
+    let memory = caller.get_export("memory").and_then(Extern::into_memory);
+    let (mut memory, data) = match memory {
+        Some(memory) => match memory.shared(&*caller) {
+            Some(shared) => (GuestMemory::shared(shared), caller.data_mut()),
+            None => {
+                let (bytes, data) = memory.data_and_store_mut(caller);
+                (GuestMemory::new(bytes), data)
+            }
+        },
+        None => (GuestMemory::new(&mut []), caller.data_mut()),
+    };
+    let ctx = get(data);
+    if let Some(fuel) = fuel {
+        ctx.clocks.fuel_consumed(fuel);
+    }
+    let mut tracer = ctx.tracer.take();
+    if let Some(tracer) = &mut tracer {
+        tracer.enter(name, &decode_args(&memory, args));
+    }
+    let errno = match f(ctx, &mut memory) {
+        Ok(()) => Errno::Success,
+        Err(errno) => errno,
+    };
+    if let Some(tracer) = &mut tracer {
+        tracer.exit(name, Some(errno));
+    }
+    ctx.tracer = tracer;
+    errno as i32
+}
+
+macro_rules! impl_add_to_linker {
+    ( $( fn $name:ident($( $arg:ident: $ty:ty ),* $(,)?); )* ) => {
+        /// Adds the native WASI `preview1` functions to the [`Linker`].
+        ///
+        /// The `get` function projects the host state `T` to its [`WasiCtx`].
+        ///
+        /// # Note
+        ///
+        /// `proc_exit` traps with an `i32` exit status that can be queried
+        /// via [`wasmi::Error::i32_exit_status`].
+        ///
+        /// # Errors
+        ///
+        /// If any of the WASI functions is already defined in the [`Linker`].
+        pub fn add_to_linker<T>(
+            linker: &mut Linker<T>,
+            get: impl Fn(&mut T) -> &mut WasiCtx + Send + Sync + Copy + 'static,
+        ) -> Result<(), wasmi::Error> {
+            $(
+                linker.func_wrap(
+                    MODULE,
+                    stringify!($name),
+                    move |mut caller: Caller<'_, T>, $( $arg: $ty ),*| -> i32 {
+                        call(
+                            &mut caller,
+                            get,
+                            stringify!($name),
+                            &[$( (stringify!($arg), TraceArg::from($arg)) ),*],
+                            |ctx, memory| syscalls::$name(ctx, memory, $( $arg ),*),
+                        )
+                    },
+                )?;
+            )*
+            linker.func_wrap(
+                MODULE,
+                "proc_exit",
+                move |mut caller: Caller<'_, T>, status: i32| -> Result<(), wasmi::Error> {
+                    if let Some(tracer) = &mut get(caller.data_mut()).tracer {
+                        tracer.enter("proc_exit", &alloc::format!("status={status}"));
+                        tracer.exit("proc_exit", None);
+                    }
+                    Err(wasmi::Error::i32_exit(status))
+                },
+            )?;
+            Ok(())
+        }
+    };
+}
+impl_add_to_linker! {
+    fn args_get(argv: u32, argv_buf: u32);
+    fn args_sizes_get(argc: u32, argv_buf_size: u32);
+    fn environ_get(environ: u32, environ_buf: u32);
+    fn environ_sizes_get(environc: u32, environ_buf_size: u32);
+    fn clock_res_get(id: u32, resolution: u32);
+    fn clock_time_get(id: u32, precision: u64, time: u32);
+    fn fd_advise(fd: u32, offset: u64, len: u64, advice: u32);
+    fn fd_allocate(fd: u32, offset: u64, len: u64);
+    fn fd_close(fd: u32);
+    fn fd_datasync(fd: u32);
+    fn fd_fdstat_get(fd: u32, stat: u32);
+    fn fd_fdstat_set_flags(fd: u32, flags: u32);
+    fn fd_fdstat_set_rights(fd: u32, base: u64, inheriting: u64);
+    fn fd_filestat_get(fd: u32, stat: u32);
+    fn fd_filestat_set_size(fd: u32, size: u64);
+    fn fd_filestat_set_times(fd: u32, atim: u64, mtim: u64, fst_flags: u32);
+    fn fd_pread(fd: u32, iovs: u32, iovs_len: u32, offset: u64, nread: u32);
+    fn fd_prestat_get(fd: u32, prestat: u32);
+    fn fd_prestat_dir_name(fd: u32, path: u32, path_len: u32);
+    fn fd_pwrite(fd: u32, iovs: u32, iovs_len: u32, offset: u64, nwritten: u32);
+    fn fd_read(fd: u32, iovs: u32, iovs_len: u32, nread: u32);
+    fn fd_readdir(fd: u32, buf: u32, buf_len: u32, cookie: u64, bufused: u32);
+    fn fd_renumber(fd: u32, to: u32);
+    fn fd_seek(fd: u32, offset: i64, whence: u32, newoffset: u32);
+    fn fd_sync(fd: u32);
+    fn fd_tell(fd: u32, offset: u32);
+    fn fd_write(fd: u32, iovs: u32, iovs_len: u32, nwritten: u32);
+    fn path_create_directory(fd: u32, path: u32, path_len: u32);
+    fn path_filestat_get(fd: u32, flags: u32, path: u32, path_len: u32, stat: u32);
+    fn path_filestat_set_times(
+        fd: u32,
+        flags: u32,
+        path: u32,
+        path_len: u32,
+        atim: u64,
+        mtim: u64,
+        fst_flags: u32,
+    );
+    fn path_link(
+        old_fd: u32,
+        old_flags: u32,
+        old_path: u32,
+        old_path_len: u32,
+        new_fd: u32,
+        new_path: u32,
+        new_path_len: u32,
+    );
+    fn path_open(
+        fd: u32,
+        dirflags: u32,
+        path: u32,
+        path_len: u32,
+        oflags: u32,
+        rights_base: u64,
+        rights_inheriting: u64,
+        fdflags: u32,
+        opened_fd: u32,
+    );
+    fn path_readlink(fd: u32, path: u32, path_len: u32, buf: u32, buf_len: u32, bufused: u32);
+    fn path_remove_directory(fd: u32, path: u32, path_len: u32);
+    fn path_rename(
+        fd: u32,
+        old_path: u32,
+        old_path_len: u32,
+        new_fd: u32,
+        new_path: u32,
+        new_path_len: u32,
+    );
+    fn path_symlink(old_path: u32, old_path_len: u32, fd: u32, new_path: u32, new_path_len: u32);
+    fn path_unlink_file(fd: u32, path: u32, path_len: u32);
+    fn poll_oneoff(subscriptions: u32, events: u32, nsubscriptions: u32, nevents: u32);
+    fn proc_raise(sig: u32);
+    fn sched_yield();
+    fn random_get(buf: u32, buf_len: u32);
+    fn sock_accept(fd: u32, flags: u32, opened_fd: u32);
+    fn sock_recv(
+        fd: u32,
+        ri_data: u32,
+        ri_data_len: u32,
+        ri_flags: u32,
+        ro_datalen: u32,
+        ro_flags: u32,
+    );
+    fn sock_send(fd: u32, si_data: u32, si_data_len: u32, si_flags: u32, so_datalen: u32);
+    fn sock_shutdown(fd: u32, how: u32);
+}
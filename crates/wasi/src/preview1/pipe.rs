@@ -0,0 +1,184 @@
+//! In-memory pipes to feed and capture the stdio of a guest.
+
+use super::{
+    host::WasiFile,
+    types::{Errno, FileType},
+};
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use spin::Mutex;
+
+/// The buffered bytes of a pipe together with its size limit.
+#[derive(Debug, Default)]
+struct Buffer {
+    bytes: VecDeque<u8>,
+    limit: Option<usize>,
+}
+
+impl Buffer {
+    /// Creates a new empty [`Buffer`] that holds at most `limit` bytes if any.
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            bytes: VecDeque::new(),
+            limit,
+        }
+    }
+
+    /// Appends as many of `bytes` as fit into the [`Buffer`] and returns their number.
+    fn push(&mut self, bytes: &[u8]) -> usize {
+        let capacity = match self.limit {
+            Some(limit) => limit.saturating_sub(self.bytes.len()),
+            None => usize::MAX,
+        };
+        let len = bytes.len().min(capacity);
+        self.bytes.extend(&bytes[..len]);
+        len
+    }
+}
+
+/// A [`WasiFile`] from which the guest reads bytes provided by the host.
+///
+/// A [`ReadPipe`] is a cheap handle to a shared buffer so that the host may
+/// keep a clone around to provide more input while the guest is running.
+/// Reading from an empty [`ReadPipe`] reports the end of the file.
+///
+/// # Example
+///
+/// ```
+/// use wasmi_wasi::preview1::{ReadPipe, WasiCtxBuilder};
+///
+/// let wasi = WasiCtxBuilder::new().stdin(ReadPipe::from("1 2 3\n")).build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReadPipe {
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl<T> From<T> for ReadPipe
+where
+    T: AsRef<[u8]>,
+{
+    fn from(bytes: T) -> Self {
+        let pipe = Self::new();
+        pipe.push(bytes.as_ref());
+        pipe
+    }
+}
+
+impl ReadPipe {
+    /// Creates a new empty [`ReadPipe`] without size limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty [`ReadPipe`] that buffers at most `limit` bytes.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(Buffer::new(Some(limit)))),
+        }
+    }
+
+    /// Appends `bytes` to the input of the guest.
+    ///
+    /// Returns the number of bytes appended which is less than `bytes.len()`
+    /// if the size limit of the [`ReadPipe`] has been reached.
+    pub fn push(&self, bytes: &[u8]) -> usize {
+        self.buffer.lock().push(bytes)
+    }
+
+    /// Returns the number of bytes that have not yet been read by the guest.
+    pub fn len(&self) -> usize {
+        self.buffer.lock().bytes.len()
+    }
+
+    /// Returns `true` if the guest has read all bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl WasiFile for ReadPipe {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Errno> {
+        let mut buffer = self.buffer.lock();
+        let len = buf.len().min(buffer.bytes.len());
+        for (dst, src) in buf.iter_mut().zip(buffer.bytes.drain(..len)) {
+            *dst = src;
+        }
+        Ok(len)
+    }
+}
+
+/// A [`WasiFile`] capturing the bytes written by the guest.
+///
+/// A [`WritePipe`] is a cheap handle to a shared buffer so that the host may
+/// keep a clone around to inspect the output after or while the guest is running.
+///
+/// # Example
+///
+/// ```
+/// use wasmi_wasi::preview1::{WasiCtxBuilder, WritePipe};
+///
+/// let stdout = WritePipe::with_limit(1024 * 1024);
+/// let wasi = WasiCtxBuilder::new().stdout(stdout.clone()).build();
+/// // ... run the guest ...
+/// assert!(stdout.contents().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WritePipe {
+    buffer: Arc<Mutex<Buffer>>,
+}
+
+impl WritePipe {
+    /// Creates a new empty [`WritePipe`] without size limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty [`WritePipe`] that buffers at most `limit` bytes.
+    ///
+    /// Guest writes beyond the limit are truncated and fail with [`Errno::Fbig`]
+    /// once no byte fits anymore.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(Buffer::new(Some(limit)))),
+        }
+    }
+
+    /// Returns a copy of the bytes written by the guest.
+    pub fn contents(&self) -> Vec<u8> {
+        self.buffer.lock().bytes.iter().copied().collect()
+    }
+
+    /// Removes and returns the bytes written by the guest.
+    ///
+    /// This frees up space for further guest writes if the [`WritePipe`] is limited.
+    pub fn take(&self) -> Vec<u8> {
+        self.buffer.lock().bytes.drain(..).collect()
+    }
+
+    /// Returns the number of bytes written by the guest.
+    pub fn len(&self) -> usize {
+        self.buffer.lock().bytes.len()
+    }
+
+    /// Returns `true` if the guest has not written any bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl WasiFile for WritePipe {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        match self.buffer.lock().push(buf) {
+            0 if !buf.is_empty() => Err(Errno::Fbig),
+            written => Ok(written),
+        }
+    }
+}
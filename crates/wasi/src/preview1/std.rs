@@ -0,0 +1,659 @@
+//! Host implementations backed by the Rust standard library.
+
+use super::{
+    host::{DirEntry, OpenOptions, Opened, WasiClocks, WasiDir, WasiFile, WasiRandom, WasiTracer},
+    types::{ClockId, Errno, FdFlags, FileType, Filestat, Whence},
+};
+use std::{
+    boxed::Box,
+    collections::VecDeque,
+    fs,
+    io::{self, Read, Seek, SeekFrom, Write},
+    net,
+    path::{Component, Path, PathBuf},
+    string::String,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    vec::Vec,
+};
+
+impl From<io::Error> for Errno {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => Self::Noent,
+            io::ErrorKind::PermissionDenied => Self::Acces,
+            io::ErrorKind::AlreadyExists => Self::Exist,
+            io::ErrorKind::WouldBlock => Self::Again,
+            io::ErrorKind::InvalidInput => Self::Inval,
+            io::ErrorKind::Interrupted => Self::Intr,
+            io::ErrorKind::Unsupported => Self::Notsup,
+            io::ErrorKind::OutOfMemory => Self::Nomem,
+            io::ErrorKind::BrokenPipe => Self::Pipe,
+            io::ErrorKind::NotADirectory => Self::Notdir,
+            io::ErrorKind::IsADirectory => Self::Isdir,
+            io::ErrorKind::DirectoryNotEmpty => Self::Notempty,
+            io::ErrorKind::ReadOnlyFilesystem => Self::Rofs,
+            io::ErrorKind::StorageFull => Self::Nospc,
+            io::ErrorKind::NotSeekable => Self::Spipe,
+            io::ErrorKind::FileTooLarge => Self::Fbig,
+            io::ErrorKind::CrossesDevices => Self::Xdev,
+            io::ErrorKind::TooManyLinks => Self::Mlink,
+            io::ErrorKind::InvalidFilename => Self::Nametoolong,
+            _ => Self::Io,
+        }
+    }
+}
+
+/// Returns the nanoseconds since the Unix epoch of `time` or 0 if unavailable.
+fn nanos(time: io::Result<SystemTime>) -> u64 {
+    time.ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+/// Returns the [`FileType`] of the host `file_type`.
+fn filetype(file_type: fs::FileType) -> FileType {
+    if file_type.is_dir() {
+        FileType::Directory
+    } else if file_type.is_symlink() {
+        FileType::SymbolicLink
+    } else if file_type.is_file() {
+        FileType::RegularFile
+    } else {
+        FileType::Unknown
+    }
+}
+
+/// Converts host file `metadata` into a [`Filestat`].
+fn filestat(metadata: &fs::Metadata) -> Filestat {
+    #[cfg(unix)]
+    let (dev, ino, nlink, ctim) = {
+        use std::os::unix::fs::MetadataExt as _;
+        let ctim = (metadata.ctime() as u64)
+            .saturating_mul(1_000_000_000)
+            .saturating_add(metadata.ctime_nsec() as u64);
+        (metadata.dev(), metadata.ino(), metadata.nlink(), ctim)
+    };
+    #[cfg(not(unix))]
+    let (dev, ino, nlink, ctim) = (0, 0, 1, nanos(metadata.created()));
+    Filestat {
+        dev,
+        ino,
+        filetype: filetype(metadata.file_type()),
+        nlink,
+        size: metadata.len(),
+        atim: nanos(metadata.accessed()),
+        mtim: nanos(metadata.modified()),
+        ctim,
+    }
+}
+
+/// Returns the [`SystemTime`] for `nanos` since the Unix epoch.
+fn system_time(nanos: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_nanos(nanos)
+}
+
+/// Returns the [`fs::FileTimes`] for the optional access and modification times.
+fn file_times(atim: Option<u64>, mtim: Option<u64>) -> fs::FileTimes {
+    let mut times = fs::FileTimes::new();
+    if let Some(atim) = atim {
+        times = times.set_accessed(system_time(atim));
+    }
+    if let Some(mtim) = mtim {
+        times = times.set_modified(system_time(mtim));
+    }
+    times
+}
+
+/// A [`WasiFile`] backed by a host [`fs::File`].
+#[derive(Debug)]
+pub struct StdFile {
+    file: fs::File,
+    flags: FdFlags,
+}
+
+impl StdFile {
+    /// Creates a new [`StdFile`] for the host `file`.
+    pub fn new(file: fs::File) -> Self {
+        Self {
+            file,
+            flags: FdFlags::empty(),
+        }
+    }
+
+    /// Runs `f` at `offset` and restores the current position afterwards.
+    fn at<R>(
+        &mut self,
+        offset: u64,
+        f: impl FnOnce(&mut fs::File) -> io::Result<R>,
+    ) -> Result<R, Errno> {
+        let position = self.file.stream_position()?;
+        self.file.seek(SeekFrom::Start(offset))?;
+        let result = f(&mut self.file);
+        self.file.seek(SeekFrom::Start(position))?;
+        Ok(result?)
+    }
+}
+
+impl WasiFile for StdFile {
+    fn filetype(&self) -> FileType {
+        FileType::RegularFile
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Errno> {
+        Ok(self.file.read(buf)?)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        if self.flags.contains(FdFlags::APPEND) {
+            self.file.seek(SeekFrom::End(0))?;
+        }
+        Ok(self.file.write(buf)?)
+    }
+
+    fn pread(&mut self, buf: &mut [u8], offset: u64) -> Result<usize, Errno> {
+        self.at(offset, |file| file.read(buf))
+    }
+
+    fn pwrite(&mut self, buf: &[u8], offset: u64) -> Result<usize, Errno> {
+        self.at(offset, |file| file.write(buf))
+    }
+
+    fn seek(&mut self, offset: i64, whence: Whence) -> Result<u64, Errno> {
+        let position = match whence {
+            Whence::Set => SeekFrom::Start(u64::try_from(offset).map_err(|_| Errno::Inval)?),
+            Whence::Cur => SeekFrom::Current(offset),
+            Whence::End => SeekFrom::End(offset),
+        };
+        Ok(self.file.seek(position)?)
+    }
+
+    fn stat(&mut self) -> Result<Filestat, Errno> {
+        Ok(filestat(&self.file.metadata()?))
+    }
+
+    fn set_size(&mut self, size: u64) -> Result<(), Errno> {
+        Ok(self.file.set_len(size)?)
+    }
+
+    fn set_times(&mut self, atim: Option<u64>, mtim: Option<u64>) -> Result<(), Errno> {
+        Ok(self.file.set_times(file_times(atim, mtim))?)
+    }
+
+    fn allocate(&mut self, offset: u64, len: u64) -> Result<(), Errno> {
+        let size = offset.checked_add(len).ok_or(Errno::Fbig)?;
+        if size > self.file.metadata()?.len() {
+            self.file.set_len(size)?;
+        }
+        Ok(())
+    }
+
+    fn flags(&self) -> FdFlags {
+        self.flags
+    }
+
+    fn set_flags(&mut self, flags: FdFlags) -> Result<(), Errno> {
+        if !(flags & !FdFlags::APPEND).is_empty() {
+            return Err(Errno::Notsup);
+        }
+        self.flags = flags;
+        Ok(())
+    }
+
+    fn sync(&mut self) -> Result<(), Errno> {
+        Ok(self.file.sync_all()?)
+    }
+}
+
+/// The maximum number of symbolic links followed while resolving a single path.
+const MAX_SYMLINKS: usize = 32;
+
+/// A [`WasiDir`] backed by a directory of the host file system.
+///
+/// Symbolic links are resolved by [`StdDir`] itself one component at a time so
+/// that paths resolving to locations outside of the directory, for example via
+/// dangling symbolic links, are rejected with [`Errno::Notcapable`].
+#[derive(Debug)]
+pub struct StdDir {
+    /// The canonicalized host path of the directory.
+    root: PathBuf,
+}
+
+impl StdDir {
+    /// Opens the host directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If `path` does not refer to an accessible directory.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let root = path.as_ref().canonicalize()?;
+        if !root.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotADirectory,
+                "not a directory",
+            ));
+        }
+        Ok(Self { root })
+    }
+
+    /// Returns the host path for the normalized guest `path`.
+    ///
+    /// Symbolic links are resolved one component at a time relative to the directory.
+    /// A symbolic link at the end of the `path` is only followed if `follow` is `true`.
+    /// The returned host path does not contain symbolic links except for the last
+    /// component if `follow` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// - If a symbolic link resolves to a location outside of the directory.
+    /// - If more than [`MAX_SYMLINKS`] symbolic links are followed.
+    /// - If a component other than the last does not exist.
+    fn host_path(&self, path: &str, follow: bool) -> Result<PathBuf, Errno> {
+        let mut resolved = self.root.clone();
+        let mut depth = 0_usize;
+        let mut pending = path.split('/').map(String::from).collect::<VecDeque<_>>();
+        let mut followed = 0;
+        while let Some(name) = pending.pop_front() {
+            match name.as_str() {
+                "" | "." => continue,
+                ".." => {
+                    if depth == 0 {
+                        return Err(Errno::Notcapable);
+                    }
+                    resolved.pop();
+                    depth -= 1;
+                    continue;
+                }
+                _ => {}
+            }
+            let candidate = resolved.join(&name);
+            if !follow && pending.is_empty() {
+                return Ok(candidate);
+            }
+            match fs::symlink_metadata(&candidate) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    followed += 1;
+                    if followed > MAX_SYMLINKS {
+                        return Err(Errno::Loop);
+                    }
+                    let target = fs::read_link(&candidate)?
+                        .into_os_string()
+                        .into_string()
+                        .map_err(|_| Errno::Ilseq)?;
+                    if Path::new(&target).is_absolute() || Path::new(&target).has_root() {
+                        return Err(Errno::Notcapable);
+                    }
+                    for component in target.split('/').rev() {
+                        pending.push_front(component.into());
+                    }
+                    continue;
+                }
+                Ok(_) => {}
+                // Note: The last component may be missing since it might be about to be created.
+                Err(error) if error.kind() == io::ErrorKind::NotFound && pending.is_empty() => {}
+                Err(error) => return Err(error.into()),
+            }
+            resolved = candidate;
+            depth += 1;
+        }
+        Ok(resolved)
+    }
+
+    /// Returns the normalized guest path of the resolved host `path`.
+    fn guest_path(&self, path: &Path) -> Result<String, Errno> {
+        let relative = path
+            .strip_prefix(&self.root)
+            .map_err(|_| Errno::Notcapable)?;
+        let components = relative
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => name.to_str().ok_or(Errno::Ilseq),
+                _ => Err(Errno::Notcapable),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(components.join("/"))
+    }
+}
+
+impl WasiDir for StdDir {
+    fn open(&mut self, path: &str, options: &OpenOptions) -> Result<Opened, Errno> {
+        let host_path = self.host_path(path, options.follow)?;
+        let metadata = match options.follow {
+            true => fs::metadata(&host_path),
+            false => fs::symlink_metadata(&host_path),
+        };
+        match metadata {
+            Ok(metadata) if metadata.is_dir() => {
+                if options.write || (options.create && options.exclusive) {
+                    return Err(Errno::Isdir);
+                }
+                return self.guest_path(&host_path).map(Opened::Dir);
+            }
+            Ok(metadata) if metadata.file_type().is_symlink() => return Err(Errno::Loop),
+            Ok(_) if options.directory => return Err(Errno::Notdir),
+            _ => {}
+        }
+        let file = fs::OpenOptions::new()
+            .read(options.read || !options.write)
+            .write(options.write)
+            .create(options.create && !options.exclusive)
+            .create_new(options.create && options.exclusive)
+            .truncate(options.truncate)
+            .open(&host_path)?;
+        let mut file = StdFile::new(file);
+        file.set_flags(options.flags & FdFlags::APPEND)?;
+        Ok(Opened::File(Box::new(file)))
+    }
+
+    fn stat(&mut self, path: &str, follow: bool) -> Result<Filestat, Errno> {
+        let host_path = self.host_path(path, follow)?;
+        let metadata = match follow {
+            true => fs::metadata(host_path)?,
+            false => fs::symlink_metadata(host_path)?,
+        };
+        Ok(filestat(&metadata))
+    }
+
+    fn set_times(
+        &mut self,
+        path: &str,
+        follow: bool,
+        atim: Option<u64>,
+        mtim: Option<u64>,
+    ) -> Result<(), Errno> {
+        let host_path = self.host_path(path, follow)?;
+        if !follow && fs::symlink_metadata(&host_path)?.file_type().is_symlink() {
+            // Note: The host would follow the symbolic link upon opening it
+            //       which might point outside of the directory.
+            return Err(Errno::Loop);
+        }
+        let file = fs::File::open(host_path)?;
+        Ok(file.set_times(file_times(atim, mtim))?)
+    }
+
+    fn read_dir(&mut self, path: &str) -> Result<Vec<DirEntry>, Errno> {
+        let host_path = self.host_path(path, true)?;
+        let mut entries = fs::read_dir(host_path)?
+            .map(|entry| {
+                let entry = entry?;
+                let name = entry.file_name().into_string().map_err(|_| Errno::Ilseq)?;
+                #[cfg(unix)]
+                let ino = std::os::unix::fs::DirEntryExt::ino(&entry);
+                #[cfg(not(unix))]
+                let ino = 0;
+                Ok(DirEntry {
+                    name,
+                    ino,
+                    filetype: filetype(entry.file_type()?),
+                })
+            })
+            .collect::<Result<Vec<_>, Errno>>()?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    fn create_dir(&mut self, path: &str) -> Result<(), Errno> {
+        Ok(fs::create_dir(self.host_path(path, false)?)?)
+    }
+
+    fn remove_dir(&mut self, path: &str) -> Result<(), Errno> {
+        Ok(fs::remove_dir(self.host_path(path, false)?)?)
+    }
+
+    fn unlink_file(&mut self, path: &str) -> Result<(), Errno> {
+        let host_path = self.host_path(path, false)?;
+        if fs::symlink_metadata(&host_path)?.is_dir() {
+            return Err(Errno::Isdir);
+        }
+        Ok(fs::remove_file(host_path)?)
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> Result<(), Errno> {
+        let from = self.host_path(from, false)?;
+        let to = self.host_path(to, false)?;
+        Ok(fs::rename(from, to)?)
+    }
+
+    #[cfg(unix)]
+    fn symlink(&mut self, target: &str, path: &str) -> Result<(), Errno> {
+        if target.starts_with('/') {
+            return Err(Errno::Notcapable);
+        }
+        Ok(std::os::unix::fs::symlink(
+            target,
+            self.host_path(path, false)?,
+        )?)
+    }
+
+    fn read_link(&mut self, path: &str) -> Result<String, Errno> {
+        let target = fs::read_link(self.host_path(path, false)?)?;
+        target
+            .into_os_string()
+            .into_string()
+            .map_err(|_| Errno::Ilseq)
+    }
+
+    fn link(&mut self, from: &str, to: &str, follow: bool) -> Result<(), Errno> {
+        let from = self.host_path(from, follow)?;
+        let to = self.host_path(to, false)?;
+        Ok(fs::hard_link(from, to)?)
+    }
+}
+
+/// A [`WasiFile`] reading from the stdin of the host process.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Stdin;
+
+impl WasiFile for Stdin {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Errno> {
+        Ok(io::stdin().read(buf)?)
+    }
+}
+
+/// A [`WasiFile`] writing to the stdout of the host process.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Stdout;
+
+impl WasiFile for Stdout {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        let mut stdout = io::stdout();
+        let written = stdout.write(buf)?;
+        stdout.flush()?;
+        Ok(written)
+    }
+}
+
+/// A [`WasiFile`] writing to the stderr of the host process.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Stderr;
+
+impl WasiFile for Stderr {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        Ok(io::stderr().write(buf)?)
+    }
+}
+
+/// A listening TCP socket that can be preopened for the guest.
+///
+/// Accepted connections are provided to the guest as [`TcpStream`].
+#[derive(Debug)]
+pub struct TcpListener {
+    listener: net::TcpListener,
+}
+
+impl TcpListener {
+    /// Creates a new [`TcpListener`] from a bound `listener`.
+    pub fn new(listener: net::TcpListener) -> Self {
+        Self { listener }
+    }
+}
+
+impl WasiFile for TcpListener {
+    fn filetype(&self) -> FileType {
+        FileType::SocketStream
+    }
+
+    fn accept(&mut self, flags: FdFlags) -> Result<Box<dyn WasiFile>, Errno> {
+        let (stream, _addr) = self.listener.accept()?;
+        stream.set_nonblocking(flags.contains(FdFlags::NONBLOCK))?;
+        Ok(Box::new(TcpStream::new(stream)))
+    }
+}
+
+/// A connected TCP socket.
+#[derive(Debug)]
+pub struct TcpStream {
+    stream: net::TcpStream,
+}
+
+impl TcpStream {
+    /// Creates a new [`TcpStream`] from a connected `stream`.
+    pub fn new(stream: net::TcpStream) -> Self {
+        Self { stream }
+    }
+}
+
+impl WasiFile for TcpStream {
+    fn filetype(&self) -> FileType {
+        FileType::SocketStream
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Errno> {
+        Ok(self.stream.read(buf)?)
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        Ok(self.stream.write(buf)?)
+    }
+
+    fn shutdown(&mut self, read: bool, write: bool) -> Result<(), Errno> {
+        let how = match (read, write) {
+            (true, true) => net::Shutdown::Both,
+            (true, false) => net::Shutdown::Read,
+            (false, true) => net::Shutdown::Write,
+            (false, false) => return Ok(()),
+        };
+        Ok(self.stream.shutdown(how)?)
+    }
+}
+
+/// [`WasiClocks`] backed by the system clocks of the host.
+///
+/// The monotonic clock starts at zero when the [`StdClocks`] are created.
+#[derive(Debug, Copy, Clone)]
+pub struct StdClocks {
+    start: Instant,
+}
+
+impl Default for StdClocks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StdClocks {
+    /// Creates new [`StdClocks`].
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl WasiClocks for StdClocks {
+    fn resolution(&self, clock: ClockId) -> Result<u64, Errno> {
+        match clock {
+            ClockId::Realtime | ClockId::Monotonic => Ok(1),
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn now(&mut self, clock: ClockId) -> Result<u64, Errno> {
+        match clock {
+            ClockId::Realtime => Ok(nanos(Ok(SystemTime::now()))),
+            ClockId::Monotonic => Ok(self.start.elapsed().as_nanos() as u64),
+            _ => Err(Errno::Inval),
+        }
+    }
+
+    fn sleep(&mut self, nanos: u64) -> Result<(), Errno> {
+        std::thread::sleep(Duration::from_nanos(nanos));
+        Ok(())
+    }
+}
+
+/// [`WasiRandom`] backed by the random number source of the host operating system.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StdRandom;
+
+impl WasiRandom for StdRandom {
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), Errno> {
+        getrandom::getrandom(buf).map_err(|_| Errno::Io)
+    }
+}
+
+/// A [`WasiTracer`] writing one line per WASI call to a host writer, similar to `strace`.
+///
+/// Each line shows the decoded arguments, the returned errno and the duration of the call:
+///
+/// ```text
+/// fd_write(fd=1, iovs=[12], nwritten=20) = Success (errno 0) <4.1µs>
+/// ```
+pub struct StdTracer {
+    /// The writer receiving the trace.
+    out: Box<dyn Write + Send>,
+    /// The call in progress with its start time.
+    call: Option<(String, Instant)>,
+}
+
+impl core::fmt::Debug for StdTracer {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StdTracer")
+            .field("call", &self.call)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StdTracer {
+    /// Creates a new [`StdTracer`] writing to `out`.
+    pub fn new(out: impl Write + Send + 'static) -> Self {
+        Self {
+            out: Box::new(out),
+            call: None,
+        }
+    }
+
+    /// Creates a new [`StdTracer`] writing to the stderr of the host.
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl WasiTracer for StdTracer {
+    fn enter(&mut self, name: &str, args: &str) {
+        self.call = Some((std::format!("{name}({args})"), Instant::now()));
+    }
+
+    fn exit(&mut self, name: &str, errno: Option<Errno>) {
+        let (call, start) = self
+            .call
+            .take()
+            .unwrap_or_else(|| (std::format!("{name}(?)"), Instant::now()));
+        // Note: tracing is best effort and must not affect the guest.
+        _ = match errno {
+            Some(errno) => writeln!(self.out, "{call} = {errno} <{:?}>", start.elapsed()),
+            None => writeln!(self.out, "{call} = ?"),
+        };
+    }
+}
@@ -0,0 +1,869 @@
+//! Implementations of the `wasi_snapshot_preview1` functions.
+//!
+//! Every function receives the [`WasiCtx`] of the guest together with its
+//! linear memory and returns [`Errno::Success`] or the [`Errno`] describing the failure.
+
+use super::{
+    ctx::{normalize_path, Descriptor, WasiCtx},
+    host::{OpenOptions, Opened, WasiFile},
+    memory::GuestMemory,
+    types::{ClockId, Errno, FdFlags, FileType, FstFlags, LookupFlags, OFlags, Rights, Whence},
+};
+use alloc::{string::String, vec::Vec};
+
+/// The result type of all WASI functions.
+pub(crate) type Result<T = ()> = core::result::Result<T, Errno>;
+
+/// Writes the `strings` as zero terminated strings to `buf` and pointers to them to `ptrs`.
+fn write_strings<'a>(
+    memory: &mut GuestMemory,
+    ptrs: u32,
+    buf: u32,
+    strings: impl Iterator<Item = &'a [u8]>,
+) -> Result {
+    let mut offset = buf;
+    for (n, string) in strings.enumerate() {
+        let ptr = ptrs.checked_add(4 * n as u32).ok_or(Errno::Fault)?;
+        memory.write_u32(ptr, offset)?;
+        memory.write(offset, string)?;
+        offset = offset
+            .checked_add(string.len() as u32)
+            .ok_or(Errno::Fault)?;
+        memory.write_u8(offset, 0x00)?;
+        offset = offset.checked_add(1).ok_or(Errno::Fault)?;
+    }
+    Ok(())
+}
+
+pub fn args_get(ctx: &mut WasiCtx, memory: &mut GuestMemory, argv: u32, argv_buf: u32) -> Result {
+    let args = ctx.args.iter().map(|arg| arg.as_bytes());
+    write_strings(memory, argv, argv_buf, args)
+}
+
+pub fn args_sizes_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    argc: u32,
+    argv_buf_size: u32,
+) -> Result {
+    let size: usize = ctx.args.iter().map(|arg| arg.len() + 1).sum();
+    memory.write_u32(argc, ctx.args.len() as u32)?;
+    memory.write_u32(argv_buf_size, size as u32)
+}
+
+pub fn environ_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    environ: u32,
+    environ_buf: u32,
+) -> Result {
+    let env: Vec<String> = ctx
+        .env
+        .iter()
+        .map(|(key, value)| alloc::format!("{key}={value}"))
+        .collect();
+    let env = env.iter().map(|var| var.as_bytes());
+    write_strings(memory, environ, environ_buf, env)
+}
+
+pub fn environ_sizes_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    environc: u32,
+    environ_buf_size: u32,
+) -> Result {
+    let size: usize = ctx
+        .env
+        .iter()
+        .map(|(key, value)| key.len() + value.len() + 2)
+        .sum();
+    memory.write_u32(environc, ctx.env.len() as u32)?;
+    memory.write_u32(environ_buf_size, size as u32)
+}
+
+pub fn clock_res_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    id: u32,
+    resolution: u32,
+) -> Result {
+    let id = ClockId::from_raw(id).ok_or(Errno::Inval)?;
+    let value = ctx.clocks.resolution(id)?;
+    memory.write_u64(resolution, value)
+}
+
+pub fn clock_time_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    id: u32,
+    _precision: u64,
+    time: u32,
+) -> Result {
+    let id = ClockId::from_raw(id).ok_or(Errno::Inval)?;
+    let value = ctx.clocks.now(id)?;
+    memory.write_u64(time, value)
+}
+
+pub fn fd_advise(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    _offset: u64,
+    _len: u64,
+    advice: u32,
+) -> Result {
+    if advice > 5 {
+        return Err(Errno::Inval);
+    }
+    ctx.file(fd, Rights::FD_ADVISE)?;
+    Ok(())
+}
+
+pub fn fd_allocate(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    offset: u64,
+    len: u64,
+) -> Result {
+    ctx.file(fd, Rights::FD_ALLOCATE)?.allocate(offset, len)
+}
+
+pub fn fd_close(ctx: &mut WasiCtx, _memory: &mut GuestMemory, fd: u32) -> Result {
+    ctx.fds.remove(&fd).ok_or(Errno::Badf)?;
+    Ok(())
+}
+
+pub fn fd_datasync(ctx: &mut WasiCtx, _memory: &mut GuestMemory, fd: u32) -> Result {
+    ctx.file(fd, Rights::FD_DATASYNC)?.sync()
+}
+
+pub fn fd_fdstat_get(ctx: &mut WasiCtx, memory: &mut GuestMemory, fd: u32, stat: u32) -> Result {
+    let descriptor = ctx.descriptor(fd, Rights::empty())?;
+    let (rights, inheriting) = descriptor.rights();
+    let (filetype, flags) = match descriptor {
+        Descriptor::File { file, .. } => (file.filetype(), file.flags()),
+        Descriptor::Dir { .. } => (FileType::Directory, FdFlags::empty()),
+    };
+    let mut bytes = [0x00; 24];
+    bytes[0] = filetype as u8;
+    bytes[2..4].copy_from_slice(&flags.bits().to_le_bytes());
+    bytes[8..16].copy_from_slice(&rights.bits().to_le_bytes());
+    bytes[16..24].copy_from_slice(&inheriting.bits().to_le_bytes());
+    memory.write(stat, &bytes)
+}
+
+pub fn fd_fdstat_set_flags(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    flags: u32,
+) -> Result {
+    let flags = u16::try_from(flags).map_err(|_| Errno::Inval)?;
+    let flags = FdFlags::from_bits_truncate(flags);
+    match ctx.descriptor(fd, Rights::FD_FDSTAT_SET_FLAGS)? {
+        Descriptor::File { file, .. } => file.set_flags(flags),
+        Descriptor::Dir { .. } if flags.is_empty() => Ok(()),
+        Descriptor::Dir { .. } => Err(Errno::Notsup),
+    }
+}
+
+pub fn fd_fdstat_set_rights(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    base: u64,
+    inheriting: u64,
+) -> Result {
+    let (rights, inherited) = ctx.descriptor(fd, Rights::empty())?.rights_mut();
+    let base = Rights::from_bits_truncate(base);
+    let inheriting = Rights::from_bits_truncate(inheriting);
+    // Note: rights can only ever be dropped, never gained.
+    if !rights.contains(base) || !inherited.contains(inheriting) {
+        return Err(Errno::Notcapable);
+    }
+    *rights = base;
+    *inherited = inheriting;
+    Ok(())
+}
+
+pub fn fd_filestat_get(ctx: &mut WasiCtx, memory: &mut GuestMemory, fd: u32, stat: u32) -> Result {
+    let filestat = match ctx.descriptor(fd, Rights::FD_FILESTAT_GET)? {
+        Descriptor::File { file, .. } => file.stat()?,
+        Descriptor::Dir { mount, path, .. } => {
+            let (mount, path) = (*mount, path.clone());
+            ctx.mount(mount).stat(&path, true)?
+        }
+    };
+    memory.write(stat, &filestat.encode())
+}
+
+pub fn fd_filestat_set_size(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    size: u64,
+) -> Result {
+    ctx.file(fd, Rights::FD_FILESTAT_SET_SIZE)?.set_size(size)
+}
+
+/// Returns the access and modification timestamps to set according to `fst_flags`.
+fn set_times(
+    ctx: &mut WasiCtx,
+    atim: u64,
+    mtim: u64,
+    fst_flags: u32,
+) -> Result<(Option<u64>, Option<u64>)> {
+    let flags = u16::try_from(fst_flags).map_err(|_| Errno::Inval)?;
+    let flags = FstFlags::from_bits_truncate(flags);
+    if flags.contains(FstFlags::ATIM | FstFlags::ATIM_NOW)
+        || flags.contains(FstFlags::MTIM | FstFlags::MTIM_NOW)
+    {
+        return Err(Errno::Inval);
+    }
+    let mut resolve = |set, now, time| -> Result<Option<u64>> {
+        if flags.contains(now) {
+            return ctx.clocks.now(ClockId::Realtime).map(Some);
+        }
+        Ok(flags.contains(set).then_some(time))
+    };
+    let atim = resolve(FstFlags::ATIM, FstFlags::ATIM_NOW, atim)?;
+    let mtim = resolve(FstFlags::MTIM, FstFlags::MTIM_NOW, mtim)?;
+    Ok((atim, mtim))
+}
+
+pub fn fd_filestat_set_times(
+    ctx: &mut WasiCtx,
+    _memory: &mut GuestMemory,
+    fd: u32,
+    atim: u64,
+    mtim: u64,
+    fst_flags: u32,
+) -> Result {
+    let (atim, mtim) = set_times(ctx, atim, mtim, fst_flags)?;
+    match ctx.descriptor(fd, Rights::FD_FILESTAT_SET_TIMES)? {
+        Descriptor::File { file, .. } => file.set_times(atim, mtim),
+        Descriptor::Dir { mount, path, .. } => {
+            let (mount, path) = (*mount, path.clone());
+            ctx.mount(mount).set_times(&path, true, atim, mtim)
+        }
+    }
+}
+
+pub fn fd_pread(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    iovs: u32,
+    iovs_len: u32,
+    offset: u64,
+    nread: u32,
+) -> Result {
+    let iovecs = memory.iovecs(iovs, iovs_len)?;
+    let file = ctx.file(fd, Rights::FD_READ | Rights::FD_SEEK)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let read = memory.fill_with(buf, len, |buf| file.pread(buf, offset + u64::from(total)))?;
+        total += read as u32;
+        if read < len as usize {
+            break;
+        }
+    }
+    memory.write_u32(nread, total)
+}
+
+pub fn fd_prestat_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    prestat: u32,
+) -> Result {
+    let Some(Descriptor::Dir {
+        mount,
+        preopen: true,
+        ..
+    }) = ctx.fds.get(&fd)
+    else {
+        return Err(Errno::Badf);
+    };
+    let name_len = ctx.mounts[*mount].guest_path.len() as u32;
+    memory.write_u32(prestat, 0)?;
+    memory.write_u32(prestat + 4, name_len)
+}
+
+pub fn fd_prestat_dir_name(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    path: u32,
+    path_len: u32,
+) -> Result {
+    let Some(Descriptor::Dir {
+        mount,
+        preopen: true,
+        ..
+    }) = ctx.fds.get(&fd)
+    else {
+        return Err(Errno::Badf);
+    };
+    let name = ctx.mounts[*mount].guest_path.as_bytes();
+    if name.len() > path_len as usize {
+        return Err(Errno::Nametoolong);
+    }
+    memory.write(path, name)
+}
+
+pub fn fd_pwrite(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    iovs: u32,
+    iovs_len: u32,
+    offset: u64,
+    nwritten: u32,
+) -> Result {
+    let iovecs = memory.iovecs(iovs, iovs_len)?;
+    let file = ctx.file(fd, Rights::FD_WRITE | Rights::FD_SEEK)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let buf = &*memory.slice(buf, len)?;
+        let written = file.pwrite(buf, offset + u64::from(total))?;
+        total += written as u32;
+        if written < buf.len() {
+            break;
+        }
+    }
+    memory.write_u32(nwritten, total)
+}
+
+pub fn fd_read(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    iovs: u32,
+    iovs_len: u32,
+    nread: u32,
+) -> Result {
+    let iovecs = memory.iovecs(iovs, iovs_len)?;
+    let file = ctx.file(fd, Rights::FD_READ)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let read = memory.fill_with(buf, len, |buf| file.read(buf))?;
+        total += read as u32;
+        if read < len as usize {
+            break;
+        }
+    }
+    memory.write_u32(nread, total)
+}
+
+pub fn fd_readdir(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    buf: u32,
+    buf_len: u32,
+    cookie: u64,
+    bufused: u32,
+) -> Result {
+    let (mount, path, _) = ctx.dir(fd, Rights::FD_READDIR)?;
+    let path = String::from(path);
+    let entries = ctx.mount(mount).read_dir(&path)?;
+    let dots = [".", ".."].map(|name| (name, 0, FileType::Directory));
+    let entries = dots.into_iter().chain(
+        entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.ino, entry.filetype)),
+    );
+    let mut bytes = Vec::new();
+    for (n, (name, ino, filetype)) in entries.enumerate().skip(cookie as usize) {
+        if bytes.len() >= buf_len as usize {
+            break;
+        }
+        let mut dirent = [0x00; 24];
+        dirent[0..8].copy_from_slice(&(n as u64 + 1).to_le_bytes());
+        dirent[8..16].copy_from_slice(&ino.to_le_bytes());
+        dirent[16..20].copy_from_slice(&(name.len() as u32).to_le_bytes());
+        dirent[20] = filetype as u8;
+        bytes.extend_from_slice(&dirent);
+        bytes.extend_from_slice(name.as_bytes());
+    }
+    // Note: a truncated last entry signals the guest to call again with a larger buffer.
+    bytes.truncate(buf_len as usize);
+    memory.write(buf, &bytes)?;
+    memory.write_u32(bufused, bytes.len() as u32)
+}
+
+pub fn fd_renumber(ctx: &mut WasiCtx, _memory: &mut GuestMemory, fd: u32, to: u32) -> Result {
+    if !ctx.fds.contains_key(&to) {
+        return Err(Errno::Badf);
+    }
+    let descriptor = ctx.fds.remove(&fd).ok_or(Errno::Badf)?;
+    ctx.fds.insert(to, descriptor);
+    Ok(())
+}
+
+pub fn fd_seek(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    offset: i64,
+    whence: u32,
+    newoffset: u32,
+) -> Result {
+    let whence = u8::try_from(whence)
+        .ok()
+        .and_then(Whence::from_raw)
+        .ok_or(Errno::Inval)?;
+    let required = match (offset, whence) {
+        (0, Whence::Cur) => Rights::FD_TELL,
+        _ => Rights::FD_SEEK,
+    };
+    let position = ctx.file(fd, required)?.seek(offset, whence)?;
+    memory.write_u64(newoffset, position)
+}
+
+pub fn fd_sync(ctx: &mut WasiCtx, _memory: &mut GuestMemory, fd: u32) -> Result {
+    ctx.file(fd, Rights::FD_SYNC)?.sync()
+}
+
+pub fn fd_tell(ctx: &mut WasiCtx, memory: &mut GuestMemory, fd: u32, offset: u32) -> Result {
+    let position = ctx.file(fd, Rights::FD_TELL)?.seek(0, Whence::Cur)?;
+    memory.write_u64(offset, position)
+}
+
+pub fn fd_write(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    iovs: u32,
+    iovs_len: u32,
+    nwritten: u32,
+) -> Result {
+    let iovecs = memory.iovecs(iovs, iovs_len)?;
+    let file = ctx.file(fd, Rights::FD_WRITE)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let buf = &*memory.slice(buf, len)?;
+        let written = file.write(buf)?;
+        total += written as u32;
+        if written < buf.len() {
+            break;
+        }
+    }
+    memory.write_u32(nwritten, total)
+}
+
+pub fn path_create_directory(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    path: u32,
+    path_len: u32,
+) -> Result {
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_CREATE_DIRECTORY)?;
+    ctx.mount(mount).create_dir(&path)
+}
+
+pub fn path_filestat_get(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    flags: u32,
+    path: u32,
+    path_len: u32,
+    stat: u32,
+) -> Result {
+    let follow = LookupFlags::from_bits_truncate(flags).contains(LookupFlags::SYMLINK_FOLLOW);
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_FILESTAT_GET)?;
+    let filestat = ctx.mount(mount).stat(&path, follow)?;
+    memory.write(stat, &filestat.encode())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn path_filestat_set_times(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    flags: u32,
+    path: u32,
+    path_len: u32,
+    atim: u64,
+    mtim: u64,
+    fst_flags: u32,
+) -> Result {
+    let follow = LookupFlags::from_bits_truncate(flags).contains(LookupFlags::SYMLINK_FOLLOW);
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_FILESTAT_SET_TIMES)?;
+    let (atim, mtim) = set_times(ctx, atim, mtim, fst_flags)?;
+    ctx.mount(mount).set_times(&path, follow, atim, mtim)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn path_link(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    old_fd: u32,
+    old_flags: u32,
+    old_path: u32,
+    old_path_len: u32,
+    new_fd: u32,
+    new_path: u32,
+    new_path_len: u32,
+) -> Result {
+    let follow = LookupFlags::from_bits_truncate(old_flags).contains(LookupFlags::SYMLINK_FOLLOW);
+    let old_path = &*memory.str(old_path, old_path_len)?;
+    let new_path = &*memory.str(new_path, new_path_len)?;
+    let (old_mount, old_path) = ctx.resolve(old_fd, old_path, Rights::PATH_LINK_SOURCE)?;
+    let (new_mount, new_path) = ctx.resolve(new_fd, new_path, Rights::PATH_LINK_TARGET)?;
+    if old_mount != new_mount {
+        return Err(Errno::Xdev);
+    }
+    ctx.mount(old_mount).link(&old_path, &new_path, follow)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn path_open(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    dirflags: u32,
+    path: u32,
+    path_len: u32,
+    oflags: u32,
+    rights_base: u64,
+    rights_inheriting: u64,
+    fdflags: u32,
+    opened_fd: u32,
+) -> Result {
+    let oflags = OFlags::from_bits_truncate(u16::try_from(oflags).map_err(|_| Errno::Inval)?);
+    let fdflags = FdFlags::from_bits_truncate(u16::try_from(fdflags).map_err(|_| Errno::Inval)?);
+    let base = Rights::from_bits_truncate(rights_base);
+    let inheriting = Rights::from_bits_truncate(rights_inheriting);
+    let mut required = Rights::PATH_OPEN;
+    if oflags.contains(OFlags::CREAT) {
+        required = required | Rights::PATH_CREATE_FILE;
+    }
+    if oflags.contains(OFlags::TRUNC) {
+        required = required | Rights::PATH_FILESTAT_SET_SIZE;
+    }
+    let path = &*memory.str(path, path_len)?;
+    let (mount, base_path, dir_inheriting) = ctx.dir(fd, required)?;
+    let path = normalize_path(base_path, path)?;
+    let write_rights = Rights::FD_WRITE | Rights::FD_ALLOCATE | Rights::FD_FILESTAT_SET_SIZE;
+    let options = OpenOptions {
+        read: base.contains(Rights::FD_READ) || base.contains(Rights::FD_READDIR),
+        write: !(base & write_rights).is_empty() || fdflags.contains(FdFlags::APPEND),
+        create: oflags.contains(OFlags::CREAT),
+        exclusive: oflags.contains(OFlags::EXCL),
+        truncate: oflags.contains(OFlags::TRUNC),
+        directory: oflags.contains(OFlags::DIRECTORY),
+        follow: LookupFlags::from_bits_truncate(dirflags).contains(LookupFlags::SYMLINK_FOLLOW),
+        flags: fdflags,
+    };
+    let descriptor = match ctx.mount(mount).open(&path, &options)? {
+        Opened::File(_) if options.directory => return Err(Errno::Notdir),
+        Opened::File(file) => {
+            let rights = base & dir_inheriting & Rights::for_filetype(file.filetype());
+            Descriptor::File {
+                file,
+                rights,
+                inheriting: inheriting & dir_inheriting,
+            }
+        }
+        Opened::Dir(path) => Descriptor::Dir {
+            mount,
+            path,
+            preopen: false,
+            rights: base & dir_inheriting & Rights::DIRECTORY,
+            inheriting: inheriting & dir_inheriting,
+        },
+    };
+    let fd = ctx.push(descriptor)?;
+    memory.write_u32(opened_fd, fd)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn path_readlink(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    path: u32,
+    path_len: u32,
+    buf: u32,
+    buf_len: u32,
+    bufused: u32,
+) -> Result {
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_READLINK)?;
+    let target = ctx.mount(mount).read_link(&path)?;
+    let len = target.len().min(buf_len as usize);
+    memory.write(buf, &target.as_bytes()[..len])?;
+    memory.write_u32(bufused, len as u32)
+}
+
+pub fn path_remove_directory(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    path: u32,
+    path_len: u32,
+) -> Result {
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_REMOVE_DIRECTORY)?;
+    if path.is_empty() {
+        return Err(Errno::Busy);
+    }
+    ctx.mount(mount).remove_dir(&path)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn path_rename(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    old_path: u32,
+    old_path_len: u32,
+    new_fd: u32,
+    new_path: u32,
+    new_path_len: u32,
+) -> Result {
+    let old_path = &*memory.str(old_path, old_path_len)?;
+    let new_path = &*memory.str(new_path, new_path_len)?;
+    let (old_mount, old_path) = ctx.resolve(fd, old_path, Rights::PATH_RENAME_SOURCE)?;
+    let (new_mount, new_path) = ctx.resolve(new_fd, new_path, Rights::PATH_RENAME_TARGET)?;
+    if old_mount != new_mount {
+        return Err(Errno::Xdev);
+    }
+    ctx.mount(old_mount).rename(&old_path, &new_path)
+}
+
+pub fn path_symlink(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    old_path: u32,
+    old_path_len: u32,
+    fd: u32,
+    new_path: u32,
+    new_path_len: u32,
+) -> Result {
+    let target = &*memory.str(old_path, old_path_len)?;
+    let path = &*memory.str(new_path, new_path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_SYMLINK)?;
+    ctx.mount(mount).symlink(target, &path)
+}
+
+pub fn path_unlink_file(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    path: u32,
+    path_len: u32,
+) -> Result {
+    let path = &*memory.str(path, path_len)?;
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_UNLINK_FILE)?;
+    ctx.mount(mount).unlink_file(&path)
+}
+
+/// The size of a subscription in guest memory.
+const SUBSCRIPTION_SIZE: u32 = 48;
+
+/// The size of an event in guest memory.
+const EVENT_SIZE: u32 = 32;
+
+/// A decoded event produced by `poll_oneoff`.
+struct Event {
+    userdata: u64,
+    error: Errno,
+    kind: u8,
+}
+
+pub fn poll_oneoff(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    subscriptions: u32,
+    events: u32,
+    nsubscriptions: u32,
+    nevents: u32,
+) -> Result {
+    if nsubscriptions == 0 {
+        return Err(Errno::Inval);
+    }
+    let mut fd_events = Vec::new();
+    let mut timers = Vec::new();
+    for n in 0..nsubscriptions {
+        let sub = n
+            .checked_mul(SUBSCRIPTION_SIZE)
+            .and_then(|offset| subscriptions.checked_add(offset))
+            .ok_or(Errno::Fault)?;
+        let userdata = memory.read_u64(sub)?;
+        match memory.read_u8(sub + 8)? {
+            0 => {
+                let id = memory.read_u32(sub + 16)?;
+                let timeout = memory.read_u64(sub + 24)?;
+                let absolute = memory.read_u16(sub + 40)? & 1 != 0;
+                let timeout = match ClockId::from_raw(id) {
+                    Some(id) if absolute => {
+                        ctx.clocks.now(id).map(|now| timeout.saturating_sub(now))
+                    }
+                    Some(_) => Ok(timeout),
+                    None => Err(Errno::Inval),
+                };
+                match timeout {
+                    Ok(timeout) => timers.push((timeout, userdata)),
+                    Err(error) => fd_events.push(Event {
+                        userdata,
+                        error,
+                        kind: 0,
+                    }),
+                }
+            }
+            kind @ (1 | 2) => {
+                let fd = memory.read_u32(sub + 16)?;
+                let required = Rights::POLL_FD_READWRITE;
+                // Note: all host files are treated as always ready.
+                let error = match ctx.file(fd, required) {
+                    Ok(_) => Errno::Success,
+                    Err(error) => error,
+                };
+                fd_events.push(Event {
+                    userdata,
+                    error,
+                    kind,
+                });
+            }
+            _ => return Err(Errno::Inval),
+        }
+    }
+    let ready = match fd_events.is_empty() {
+        false => fd_events,
+        true => {
+            let timeout = timers
+                .iter()
+                .map(|(timeout, _)| *timeout)
+                .min()
+                .unwrap_or(0);
+            ctx.clocks.sleep(timeout)?;
+            timers
+                .into_iter()
+                .filter(|(t, _)| *t == timeout)
+                .map(|(_, userdata)| Event {
+                    userdata,
+                    error: Errno::Success,
+                    kind: 0,
+                })
+                .collect()
+        }
+    };
+    for (n, event) in ready.iter().enumerate() {
+        let mut bytes = [0x00; EVENT_SIZE as usize];
+        bytes[0..8].copy_from_slice(&event.userdata.to_le_bytes());
+        bytes[8..10].copy_from_slice(&(event.error as u16).to_le_bytes());
+        bytes[10] = event.kind;
+        let ptr = events
+            .checked_add(n as u32 * EVENT_SIZE)
+            .ok_or(Errno::Fault)?;
+        memory.write(ptr, &bytes)?;
+    }
+    memory.write_u32(nevents, ready.len() as u32)
+}
+
+pub fn proc_raise(_ctx: &mut WasiCtx, _memory: &mut GuestMemory, _sig: u32) -> Result {
+    Err(Errno::Nosys)
+}
+
+pub fn sched_yield(_ctx: &mut WasiCtx, _memory: &mut GuestMemory) -> Result {
+    Ok(())
+}
+
+pub fn random_get(ctx: &mut WasiCtx, memory: &mut GuestMemory, buf: u32, buf_len: u32) -> Result {
+    memory.fill_with(buf, buf_len, |buf| {
+        ctx.random.fill(buf)?;
+        Ok(buf.len())
+    })?;
+    Ok(())
+}
+
+/// Returns the socket at `fd` if it has all `required` rights.
+fn socket(ctx: &mut WasiCtx, fd: u32, required: Rights) -> Result<&mut dyn WasiFile> {
+    let file = ctx.file(fd, required)?;
+    match file.filetype() {
+        FileType::SocketDgram | FileType::SocketStream => Ok(file),
+        _ => Err(Errno::Notsock),
+    }
+}
+
+pub fn sock_accept(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    flags: u32,
+    opened_fd: u32,
+) -> Result {
+    let flags = FdFlags::from_bits_truncate(u16::try_from(flags).map_err(|_| Errno::Inval)?);
+    let connection = socket(ctx, fd, Rights::SOCK_ACCEPT)?.accept(flags)?;
+    let fd = ctx.push(Descriptor::file(connection))?;
+    memory.write_u32(opened_fd, fd)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sock_recv(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    ri_data: u32,
+    ri_data_len: u32,
+    ri_flags: u32,
+    ro_datalen: u32,
+    ro_flags: u32,
+) -> Result {
+    if ri_flags != 0 {
+        return Err(Errno::Notsup);
+    }
+    let iovecs = memory.iovecs(ri_data, ri_data_len)?;
+    let socket = socket(ctx, fd, Rights::FD_READ)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let read = memory.fill_with(buf, len, |buf| socket.read(buf))?;
+        total += read as u32;
+        if read < len as usize {
+            break;
+        }
+    }
+    memory.write_u32(ro_datalen, total)?;
+    memory.write_u16(ro_flags, 0)
+}
+
+pub fn sock_send(
+    ctx: &mut WasiCtx,
+    memory: &mut GuestMemory,
+    fd: u32,
+    si_data: u32,
+    si_data_len: u32,
+    _si_flags: u32,
+    so_datalen: u32,
+) -> Result {
+    let iovecs = memory.iovecs(si_data, si_data_len)?;
+    let socket = socket(ctx, fd, Rights::FD_WRITE)?;
+    let mut total = 0_u32;
+    for (buf, len) in iovecs {
+        let buf = &*memory.slice(buf, len)?;
+        let written = socket.write(buf)?;
+        total += written as u32;
+        if written < buf.len() {
+            break;
+        }
+    }
+    memory.write_u32(so_datalen, total)
+}
+
+pub fn sock_shutdown(ctx: &mut WasiCtx, _memory: &mut GuestMemory, fd: u32, how: u32) -> Result {
+    const RD: u32 = 1 << 0;
+    const WR: u32 = 1 << 1;
+    if how & !(RD | WR) != 0 {
+        return Err(Errno::Inval);
+    }
+    socket(ctx, fd, Rights::SOCK_SHUTDOWN)?.shutdown(how & RD != 0, how & WR != 0)
+}
@@ -0,0 +1,109 @@
+//! Decoding of WASI function arguments for [`WasiTracer`](super::WasiTracer)s.
+
+use super::memory::GuestMemory;
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write as _};
+
+/// A raw argument of a WASI function.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum TraceArg {
+    U32(u32),
+    U64(u64),
+    I64(i64),
+}
+
+impl From<u32> for TraceArg {
+    fn from(value: u32) -> Self {
+        Self::U32(value)
+    }
+}
+
+impl From<i32> for TraceArg {
+    fn from(value: i32) -> Self {
+        Self::U32(value as u32)
+    }
+}
+
+impl From<u64> for TraceArg {
+    fn from(value: u64) -> Self {
+        Self::U64(value)
+    }
+}
+
+impl From<i64> for TraceArg {
+    fn from(value: i64) -> Self {
+        Self::I64(value)
+    }
+}
+
+impl fmt::Display for TraceArg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::U32(value) => write!(f, "{value}"),
+            Self::U64(value) => write!(f, "{value}"),
+            Self::I64(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl TraceArg {
+    /// Returns the argument as guest pointer or length if it is an `u32`.
+    fn as_u32(self) -> Option<u32> {
+        match self {
+            Self::U32(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bits of the argument for hexadecimal display.
+    fn bits(self) -> u64 {
+        match self {
+            Self::U32(value) => u64::from(value),
+            Self::U64(value) => value,
+            Self::I64(value) => value as u64,
+        }
+    }
+}
+
+/// Decodes the named `args` of a WASI function for display.
+///
+/// A pointer argument followed by its `<name>_len` argument is decoded as string
+/// if its name ends with `path` and as list of iovec lengths if it is an iovec array.
+pub(crate) fn decode_args(memory: &GuestMemory, args: &[(&str, TraceArg)]) -> String {
+    let mut decoded = String::new();
+    let mut args = args.iter().peekable();
+    while let Some(&(name, value)) = args.next() {
+        if !decoded.is_empty() {
+            decoded.push_str(", ");
+        }
+        let len = args
+            .peek()
+            .filter(|(next, _)| next.strip_suffix("_len") == Some(name))
+            .and_then(|(_, len)| len.as_u32());
+        let is_path = name.ends_with("path");
+        let is_iovecs = matches!(name, "iovs" | "ri_data" | "si_data");
+        let result = match (value.as_u32(), len) {
+            (Some(ptr), Some(len)) if is_path => {
+                args.next();
+                match memory.str(ptr, len) {
+                    Ok(path) => write!(decoded, "{name}={path:?}"),
+                    Err(errno) => write!(decoded, "{name}=<{errno}>"),
+                }
+            }
+            (Some(ptr), Some(len)) if is_iovecs => {
+                args.next();
+                match memory.iovecs(ptr, len) {
+                    Ok(iovecs) => {
+                        let lens = iovecs.iter().map(|(_, len)| len).collect::<Vec<_>>();
+                        write!(decoded, "{name}={lens:?}")
+                    }
+                    Err(errno) => write!(decoded, "{name}=<{errno}>"),
+                }
+            }
+            _ if name.contains("rights") => write!(decoded, "{name}={:#x}", value.bits()),
+            _ => write!(decoded, "{name}={value}"),
+        };
+        debug_assert!(result.is_ok(), "writing to a `String` cannot fail");
+    }
+    decoded
+}
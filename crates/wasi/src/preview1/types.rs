@@ -0,0 +1,566 @@
+//! Type definitions of the `wasi_snapshot_preview1` ABI.
+
+use core::fmt;
+
+macro_rules! define_errno {
+    ( $( $(#[$doc:meta])* $name:ident = $value:literal ),* $(,)? ) => {
+        /// Error codes returned by WASI functions.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[repr(u16)]
+        pub enum Errno {
+            $(
+                $(#[$doc])*
+                $name = $value,
+            )*
+        }
+
+        impl Errno {
+            /// Returns the [`Errno`] for its raw `value` if any.
+            pub fn from_raw(value: u16) -> Option<Self> {
+                match value {
+                    $( $value => Some(Self::$name), )*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+define_errno! {
+    /// No error occurred.
+    Success = 0,
+    /// Argument list too long.
+    TooBig = 1,
+    /// Permission denied.
+    Acces = 2,
+    /// Address in use.
+    Addrinuse = 3,
+    /// Address not available.
+    Addrnotavail = 4,
+    /// Address family not supported.
+    Afnosupport = 5,
+    /// Resource unavailable, or operation would block.
+    Again = 6,
+    /// Connection already in progress.
+    Already = 7,
+    /// Bad file descriptor.
+    Badf = 8,
+    /// Bad message.
+    Badmsg = 9,
+    /// Device or resource busy.
+    Busy = 10,
+    /// Operation canceled.
+    Canceled = 11,
+    /// No child processes.
+    Child = 12,
+    /// Connection aborted.
+    Connaborted = 13,
+    /// Connection refused.
+    Connrefused = 14,
+    /// Connection reset.
+    Connreset = 15,
+    /// Resource deadlock would occur.
+    Deadlk = 16,
+    /// Destination address required.
+    Destaddrreq = 17,
+    /// Mathematics argument out of domain of function.
+    Dom = 18,
+    /// Reserved.
+    Dquot = 19,
+    /// File exists.
+    Exist = 20,
+    /// Bad address.
+    Fault = 21,
+    /// File too large.
+    Fbig = 22,
+    /// Host is unreachable.
+    Hostunreach = 23,
+    /// Identifier removed.
+    Idrm = 24,
+    /// Illegal byte sequence.
+    Ilseq = 25,
+    /// Operation in progress.
+    Inprogress = 26,
+    /// Interrupted function.
+    Intr = 27,
+    /// Invalid argument.
+    Inval = 28,
+    /// I/O error.
+    Io = 29,
+    /// Socket is connected.
+    Isconn = 30,
+    /// Is a directory.
+    Isdir = 31,
+    /// Too many levels of symbolic links.
+    Loop = 32,
+    /// File descriptor value too large.
+    Mfile = 33,
+    /// Too many links.
+    Mlink = 34,
+    /// Message too large.
+    Msgsize = 35,
+    /// Reserved.
+    Multihop = 36,
+    /// Filename too long.
+    Nametoolong = 37,
+    /// Network is down.
+    Netdown = 38,
+    /// Connection aborted by network.
+    Netreset = 39,
+    /// Network unreachable.
+    Netunreach = 40,
+    /// Too many files open in system.
+    Nfile = 41,
+    /// No buffer space available.
+    Nobufs = 42,
+    /// No such device.
+    Nodev = 43,
+    /// No such file or directory.
+    Noent = 44,
+    /// Executable file format error.
+    Noexec = 45,
+    /// No locks available.
+    Nolck = 46,
+    /// Reserved.
+    Nolink = 47,
+    /// Not enough space.
+    Nomem = 48,
+    /// No message of the desired type.
+    Nomsg = 49,
+    /// Protocol not available.
+    Noprotoopt = 50,
+    /// No space left on device.
+    Nospc = 51,
+    /// Function not supported.
+    Nosys = 52,
+    /// The socket is not connected.
+    Notconn = 53,
+    /// Not a directory or a symbolic link to a directory.
+    Notdir = 54,
+    /// Directory not empty.
+    Notempty = 55,
+    /// State not recoverable.
+    Notrecoverable = 56,
+    /// Not a socket.
+    Notsock = 57,
+    /// Not supported, or operation not supported on socket.
+    Notsup = 58,
+    /// Inappropriate I/O control operation.
+    Notty = 59,
+    /// No such device or address.
+    Nxio = 60,
+    /// Value too large to be stored in data type.
+    Overflow = 61,
+    /// Previous owner died.
+    Ownerdead = 62,
+    /// Operation not permitted.
+    Perm = 63,
+    /// Broken pipe.
+    Pipe = 64,
+    /// Protocol error.
+    Proto = 65,
+    /// Protocol not supported.
+    Protonosupport = 66,
+    /// Protocol wrong type for socket.
+    Prototype = 67,
+    /// Result too large.
+    Range = 68,
+    /// Read-only file system.
+    Rofs = 69,
+    /// Invalid seek.
+    Spipe = 70,
+    /// No such process.
+    Srch = 71,
+    /// Reserved.
+    Stale = 72,
+    /// Connection timed out.
+    Timedout = 73,
+    /// Text file busy.
+    Txtbsy = 74,
+    /// Cross-device link.
+    Xdev = 75,
+    /// Extension: Capabilities insufficient.
+    Notcapable = 76,
+}
+
+impl fmt::Display for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?} (errno {})", *self as u16)
+    }
+}
+
+/// The type of a file descriptor or file.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum FileType {
+    /// The type of the file descriptor or file is unknown or is different from any of the other types specified.
+    #[default]
+    Unknown = 0,
+    /// The file descriptor or file refers to a block device inode.
+    BlockDevice = 1,
+    /// The file descriptor or file refers to a character device inode.
+    CharacterDevice = 2,
+    /// The file descriptor or file refers to a directory inode.
+    Directory = 3,
+    /// The file descriptor or file refers to a regular file inode.
+    RegularFile = 4,
+    /// The file descriptor or file refers to a datagram socket.
+    SocketDgram = 5,
+    /// The file descriptor or file refers to a byte-stream socket.
+    SocketStream = 6,
+    /// The file refers to a symbolic link inode.
+    SymbolicLink = 7,
+}
+
+/// Identifiers for clocks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ClockId {
+    /// The clock measuring real time.
+    Realtime = 0,
+    /// The store-wide monotonic clock.
+    Monotonic = 1,
+    /// The CPU-time clock associated with the current process.
+    ProcessCputime = 2,
+    /// The CPU-time clock associated with the current thread.
+    ThreadCputime = 3,
+}
+
+impl ClockId {
+    /// Returns the [`ClockId`] for its raw `value` if any.
+    pub fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Realtime),
+            1 => Some(Self::Monotonic),
+            2 => Some(Self::ProcessCputime),
+            3 => Some(Self::ThreadCputime),
+            _ => None,
+        }
+    }
+}
+
+/// The position relative to which to set the offset of a file descriptor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Whence {
+    /// Seek relative to start-of-file.
+    Set = 0,
+    /// Seek relative to current position.
+    Cur = 1,
+    /// Seek relative to end-of-file.
+    End = 2,
+}
+
+impl Whence {
+    /// Returns the [`Whence`] for its raw `value` if any.
+    pub fn from_raw(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Set),
+            1 => Some(Self::Cur),
+            2 => Some(Self::End),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! define_flags {
+    (
+        $(#[$doc:meta])*
+        pub struct $name:ident($repr:ty) {
+            $( $(#[$flag_doc:meta])* const $flag:ident = $value:expr; )*
+        }
+    ) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+        pub struct $name($repr);
+
+        impl $name {
+            $(
+                $(#[$flag_doc])*
+                pub const $flag: Self = Self($value);
+            )*
+
+            /// Returns a value with no flags set.
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Returns a value with all flags set.
+            pub const fn all() -> Self {
+                Self(0 $( | $value )*)
+            }
+
+            /// Returns the flags for the raw `bits` ignoring unknown bits.
+            pub const fn from_bits_truncate(bits: $repr) -> Self {
+                Self(bits & Self::all().0)
+            }
+
+            /// Returns the raw bits of the flags.
+            pub const fn bits(self) -> $repr {
+                self.0
+            }
+
+            /// Returns `true` if all flags of `other` are set in `self`.
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Returns `true` if no flags are set.
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl core::ops::Not for $name {
+            type Output = Self;
+
+            fn not(self) -> Self {
+                Self(!self.0 & Self::all().0)
+            }
+        }
+    };
+}
+
+define_flags! {
+    /// File descriptor flags.
+    pub struct FdFlags(u16) {
+        /// Append mode: Data written to the file is always appended to the file's end.
+        const APPEND = 1 << 0;
+        /// Write according to synchronized I/O data integrity completion.
+        const DSYNC = 1 << 1;
+        /// Non-blocking mode.
+        const NONBLOCK = 1 << 2;
+        /// Synchronized read I/O operations.
+        const RSYNC = 1 << 3;
+        /// Write according to synchronized I/O file integrity completion.
+        const SYNC = 1 << 4;
+    }
+}
+
+define_flags! {
+    /// Open flags used by `path_open`.
+    pub struct OFlags(u16) {
+        /// Create file if it does not exist.
+        const CREAT = 1 << 0;
+        /// Fail if not a directory.
+        const DIRECTORY = 1 << 1;
+        /// Fail if file already exists.
+        const EXCL = 1 << 2;
+        /// Truncate file to size 0.
+        const TRUNC = 1 << 3;
+    }
+}
+
+define_flags! {
+    /// Flags determining the method of how paths are resolved.
+    pub struct LookupFlags(u32) {
+        /// As long as the resolved path corresponds to a symbolic link, it is expanded.
+        const SYMLINK_FOLLOW = 1 << 0;
+    }
+}
+
+define_flags! {
+    /// Which file time attributes to adjust.
+    pub struct FstFlags(u16) {
+        /// Adjust the last data access timestamp to the given value.
+        const ATIM = 1 << 0;
+        /// Adjust the last data access timestamp to the time of clock `realtime`.
+        const ATIM_NOW = 1 << 1;
+        /// Adjust the last data modification timestamp to the given value.
+        const MTIM = 1 << 2;
+        /// Adjust the last data modification timestamp to the time of clock `realtime`.
+        const MTIM_NOW = 1 << 3;
+    }
+}
+
+define_flags! {
+    /// File descriptor rights, determining which actions may be performed.
+    pub struct Rights(u64) {
+        /// The right to invoke `fd_datasync`.
+        const FD_DATASYNC = 1 << 0;
+        /// The right to invoke `fd_read` and `sock_recv`.
+        const FD_READ = 1 << 1;
+        /// The right to invoke `fd_seek`.
+        const FD_SEEK = 1 << 2;
+        /// The right to invoke `fd_fdstat_set_flags`.
+        const FD_FDSTAT_SET_FLAGS = 1 << 3;
+        /// The right to invoke `fd_sync`.
+        const FD_SYNC = 1 << 4;
+        /// The right to invoke `fd_seek` in such a way that the file offset remains unaltered.
+        const FD_TELL = 1 << 5;
+        /// The right to invoke `fd_write` and `sock_send`.
+        const FD_WRITE = 1 << 6;
+        /// The right to invoke `fd_advise`.
+        const FD_ADVISE = 1 << 7;
+        /// The right to invoke `fd_allocate`.
+        const FD_ALLOCATE = 1 << 8;
+        /// The right to invoke `path_create_directory`.
+        const PATH_CREATE_DIRECTORY = 1 << 9;
+        /// If `PATH_OPEN` is set, the right to invoke `path_open` with `CREAT`.
+        const PATH_CREATE_FILE = 1 << 10;
+        /// The right to invoke `path_link` with the file descriptor as the source directory.
+        const PATH_LINK_SOURCE = 1 << 11;
+        /// The right to invoke `path_link` with the file descriptor as the target directory.
+        const PATH_LINK_TARGET = 1 << 12;
+        /// The right to invoke `path_open`.
+        const PATH_OPEN = 1 << 13;
+        /// The right to invoke `fd_readdir`.
+        const FD_READDIR = 1 << 14;
+        /// The right to invoke `path_readlink`.
+        const PATH_READLINK = 1 << 15;
+        /// The right to invoke `path_rename` with the file descriptor as the source directory.
+        const PATH_RENAME_SOURCE = 1 << 16;
+        /// The right to invoke `path_rename` with the file descriptor as the target directory.
+        const PATH_RENAME_TARGET = 1 << 17;
+        /// The right to invoke `path_filestat_get`.
+        const PATH_FILESTAT_GET = 1 << 18;
+        /// The right to change a file's size.
+        const PATH_FILESTAT_SET_SIZE = 1 << 19;
+        /// The right to invoke `path_filestat_set_times`.
+        const PATH_FILESTAT_SET_TIMES = 1 << 20;
+        /// The right to invoke `fd_filestat_get`.
+        const FD_FILESTAT_GET = 1 << 21;
+        /// The right to invoke `fd_filestat_set_size`.
+        const FD_FILESTAT_SET_SIZE = 1 << 22;
+        /// The right to invoke `fd_filestat_set_times`.
+        const FD_FILESTAT_SET_TIMES = 1 << 23;
+        /// The right to invoke `path_symlink`.
+        const PATH_SYMLINK = 1 << 24;
+        /// The right to invoke `path_remove_directory`.
+        const PATH_REMOVE_DIRECTORY = 1 << 25;
+        /// The right to invoke `path_unlink_file`.
+        const PATH_UNLINK_FILE = 1 << 26;
+        /// The right to invoke `poll_oneoff` to subscribe to read or write readiness.
+        const POLL_FD_READWRITE = 1 << 27;
+        /// The right to invoke `sock_shutdown`.
+        const SOCK_SHUTDOWN = 1 << 28;
+        /// The right to invoke `sock_accept`.
+        const SOCK_ACCEPT = 1 << 29;
+    }
+}
+
+impl Rights {
+    /// The rights of file descriptors referring to directories.
+    pub const DIRECTORY: Self = Self(
+        Self::FD_FDSTAT_SET_FLAGS.0
+            | Self::FD_SYNC.0
+            | Self::FD_ADVISE.0
+            | Self::PATH_CREATE_DIRECTORY.0
+            | Self::PATH_CREATE_FILE.0
+            | Self::PATH_LINK_SOURCE.0
+            | Self::PATH_LINK_TARGET.0
+            | Self::PATH_OPEN.0
+            | Self::FD_READDIR.0
+            | Self::PATH_READLINK.0
+            | Self::PATH_RENAME_SOURCE.0
+            | Self::PATH_RENAME_TARGET.0
+            | Self::PATH_FILESTAT_GET.0
+            | Self::PATH_FILESTAT_SET_SIZE.0
+            | Self::PATH_FILESTAT_SET_TIMES.0
+            | Self::FD_FILESTAT_GET.0
+            | Self::FD_FILESTAT_SET_TIMES.0
+            | Self::PATH_SYMLINK.0
+            | Self::PATH_REMOVE_DIRECTORY.0
+            | Self::PATH_UNLINK_FILE.0
+            | Self::POLL_FD_READWRITE.0,
+    );
+
+    /// The rights of file descriptors referring to regular files.
+    pub const REGULAR_FILE: Self = Self(
+        Self::FD_DATASYNC.0
+            | Self::FD_READ.0
+            | Self::FD_SEEK.0
+            | Self::FD_FDSTAT_SET_FLAGS.0
+            | Self::FD_SYNC.0
+            | Self::FD_TELL.0
+            | Self::FD_WRITE.0
+            | Self::FD_ADVISE.0
+            | Self::FD_ALLOCATE.0
+            | Self::FD_FILESTAT_GET.0
+            | Self::FD_FILESTAT_SET_SIZE.0
+            | Self::FD_FILESTAT_SET_TIMES.0
+            | Self::POLL_FD_READWRITE.0,
+    );
+
+    /// The rights of file descriptors referring to character devices such as stdio.
+    pub const CHARACTER_DEVICE: Self = Self(
+        Self::FD_DATASYNC.0
+            | Self::FD_READ.0
+            | Self::FD_FDSTAT_SET_FLAGS.0
+            | Self::FD_SYNC.0
+            | Self::FD_WRITE.0
+            | Self::FD_ADVISE.0
+            | Self::FD_FILESTAT_GET.0
+            | Self::POLL_FD_READWRITE.0,
+    );
+
+    /// The rights of file descriptors referring to sockets.
+    pub const SOCKET: Self =
+        Self(Self::CHARACTER_DEVICE.0 | Self::SOCK_ACCEPT.0 | Self::SOCK_SHUTDOWN.0);
+
+    /// Returns the rights of file descriptors referring to files of type `filetype`.
+    pub fn for_filetype(filetype: FileType) -> Self {
+        match filetype {
+            FileType::Directory => Self::DIRECTORY,
+            FileType::RegularFile => Self::REGULAR_FILE,
+            FileType::SocketDgram | FileType::SocketStream => Self::SOCKET,
+            _ => Self::CHARACTER_DEVICE,
+        }
+    }
+}
+
+/// File attributes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Filestat {
+    /// Device ID of device containing the file.
+    pub dev: u64,
+    /// File serial number.
+    pub ino: u64,
+    /// File type.
+    pub filetype: FileType,
+    /// Number of hard links to the file.
+    pub nlink: u64,
+    /// For regular files, the file size in bytes.
+    /// For symbolic links, the length in bytes of the pathname contained in the symbolic link.
+    pub size: u64,
+    /// Last data access timestamp in nanoseconds.
+    pub atim: u64,
+    /// Last data modification timestamp in nanoseconds.
+    pub mtim: u64,
+    /// Last file status change timestamp in nanoseconds.
+    pub ctim: u64,
+}
+
+impl Filestat {
+    /// The size of an encoded [`Filestat`] in guest memory.
+    pub(crate) const SIZE: usize = 64;
+
+    /// Encodes the [`Filestat`] into its guest memory representation.
+    pub(crate) fn encode(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0x00; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.dev.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.ino.to_le_bytes());
+        bytes[16] = self.filetype as u8;
+        bytes[24..32].copy_from_slice(&self.nlink.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.size.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.atim.to_le_bytes());
+        bytes[48..56].copy_from_slice(&self.mtim.to_le_bytes());
+        bytes[56..64].copy_from_slice(&self.ctim.to_le_bytes());
+        bytes
+    }
+}
@@ -0,0 +1,70 @@
+//! Implementation of the `wasi:cli` interfaces.
+
+use super::{option, Entry, Position, Result, WasiCtx};
+use alloc::vec::Vec;
+use wasmi::component::Val;
+
+pub fn get_environment(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let env = ctx
+        .inner
+        .env
+        .iter()
+        .map(|(key, value)| {
+            Val::Tuple(Vec::from([
+                Val::String(key.clone()),
+                Val::String(value.clone()),
+            ]))
+        })
+        .collect();
+    Ok(Some(Val::List(env)))
+}
+
+pub fn get_arguments(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let args = ctx.inner.args.iter().cloned().map(Val::String).collect();
+    Ok(Some(Val::List(args)))
+}
+
+pub fn initial_cwd(_ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(option(None)))
+}
+
+pub fn exit(_ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let status = match params.first() {
+        Some(Val::Result(Ok(_))) => 0,
+        _ => 1,
+    };
+    Err(wasmi::Error::i32_exit(status))
+}
+
+pub fn exit_with_code(_ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let status = match params.first() {
+        Some(Val::U8(status)) => i32::from(*status),
+        _ => 1,
+    };
+    Err(wasmi::Error::i32_exit(status))
+}
+
+pub fn get_stdin(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(ctx.push(Entry::InputStream {
+        fd: 0,
+        offset: None,
+    })))
+}
+
+pub fn get_stdout(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(ctx.push(Entry::OutputStream {
+        fd: 1,
+        position: Position::Current,
+    })))
+}
+
+pub fn get_stderr(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(ctx.push(Entry::OutputStream {
+        fd: 2,
+        position: Position::Current,
+    })))
+}
+
+pub fn get_terminal(_ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(option(None)))
+}
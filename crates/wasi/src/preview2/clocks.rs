@@ -0,0 +1,48 @@
+//! Implementation of the `wasi:clocks` interfaces.
+
+use super::{datetime, trap, u64, Entry, Result, WasiCtx};
+use crate::preview1::ClockId;
+use wasmi::component::Val;
+
+pub fn wall_clock_now(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let now = ctx.inner.clocks.now(ClockId::Realtime).map_err(trap)?;
+    Ok(Some(datetime(now)))
+}
+
+pub fn wall_clock_resolution(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let resolution = ctx
+        .inner
+        .clocks
+        .resolution(ClockId::Realtime)
+        .map_err(trap)?;
+    Ok(Some(datetime(resolution)))
+}
+
+pub fn monotonic_clock_now(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let now = ctx.inner.clocks.now(ClockId::Monotonic).map_err(trap)?;
+    Ok(Some(Val::U64(now)))
+}
+
+pub fn monotonic_clock_resolution(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let resolution = ctx
+        .inner
+        .clocks
+        .resolution(ClockId::Monotonic)
+        .map_err(trap)?;
+    Ok(Some(Val::U64(resolution)))
+}
+
+pub fn subscribe_instant(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let deadline = u64(&params[0])?;
+    Ok(Some(ctx.push(Entry::Pollable {
+        deadline: Some(deadline),
+    })))
+}
+
+pub fn subscribe_duration(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let now = ctx.inner.clocks.now(ClockId::Monotonic).map_err(trap)?;
+    let deadline = now.saturating_add(u64(&params[0])?);
+    Ok(Some(ctx.push(Entry::Pollable {
+        deadline: Some(deadline),
+    })))
+}
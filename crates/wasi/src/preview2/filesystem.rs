@@ -0,0 +1,639 @@
+//! Implementation of the `wasi:filesystem` interfaces.
+
+use super::{
+    bytes,
+    datetime,
+    flag,
+    invalid_param,
+    list_u8,
+    option,
+    record,
+    result,
+    string,
+    u64,
+    Entry,
+    Position,
+    Result,
+    WasiCtx,
+};
+use crate::preview1::{
+    self,
+    normalize_path,
+    ClockId,
+    Descriptor,
+    Errno,
+    FdFlags,
+    FileType,
+    Filestat,
+    OpenOptions,
+    Opened,
+    Rights,
+};
+use alloc::{string::String, vec, vec::Vec};
+use wasmi::component::Val;
+
+/// The result of a filesystem operation before it is converted to a `result` value.
+type FsResult<T = Option<Val>> = core::result::Result<T, Errno>;
+
+/// The rights required to write to a file.
+const WRITE_RIGHTS: Rights = Rights::from_bits_truncate(
+    Rights::FD_WRITE.bits() | Rights::FD_ALLOCATE.bits() | Rights::FD_FILESTAT_SET_SIZE.bits(),
+);
+
+/// The rights required to modify the contents of a directory.
+const MUTATE_RIGHTS: Rights = Rights::from_bits_truncate(
+    Rights::PATH_CREATE_DIRECTORY.bits()
+        | Rights::PATH_CREATE_FILE.bits()
+        | Rights::PATH_LINK_TARGET.bits()
+        | Rights::PATH_RENAME_SOURCE.bits()
+        | Rights::PATH_RENAME_TARGET.bits()
+        | Rights::PATH_FILESTAT_SET_SIZE.bits()
+        | Rights::PATH_FILESTAT_SET_TIMES.bits()
+        | Rights::PATH_SYMLINK.bits()
+        | Rights::PATH_REMOVE_DIRECTORY.bits()
+        | Rights::PATH_UNLINK_FILE.bits(),
+);
+
+/// Converts the `result` of a filesystem operation into a `result<_, error-code>` value.
+fn respond(outcome: FsResult) -> Result {
+    Ok(Some(result(outcome.map_err(error_code))))
+}
+
+/// Returns the `error-code` value for `errno`.
+fn error_code(errno: Errno) -> Val {
+    let name = match errno {
+        Errno::Acces | Errno::Notcapable => "access",
+        Errno::Again => "would-block",
+        Errno::Already => "already",
+        Errno::Badf => "bad-descriptor",
+        Errno::Busy => "busy",
+        Errno::Deadlk => "deadlock",
+        Errno::Dquot => "quota",
+        Errno::Exist => "exist",
+        Errno::Fbig => "file-too-large",
+        Errno::Ilseq => "illegal-byte-sequence",
+        Errno::Inprogress => "in-progress",
+        Errno::Intr => "interrupted",
+        Errno::Inval => "invalid",
+        Errno::Isdir => "is-directory",
+        Errno::Loop => "loop",
+        Errno::Mlink => "too-many-links",
+        Errno::Msgsize => "message-size",
+        Errno::Nametoolong => "name-too-long",
+        Errno::Nodev => "no-device",
+        Errno::Noent => "no-entry",
+        Errno::Nolck => "no-lock",
+        Errno::Nomem => "insufficient-memory",
+        Errno::Nospc => "insufficient-space",
+        Errno::Notdir => "not-directory",
+        Errno::Notempty => "not-empty",
+        Errno::Notrecoverable => "not-recoverable",
+        Errno::Notsup | Errno::Nosys => "unsupported",
+        Errno::Notty => "no-tty",
+        Errno::Nxio => "no-such-device",
+        Errno::Overflow => "overflow",
+        Errno::Perm => "not-permitted",
+        Errno::Pipe => "pipe",
+        Errno::Rofs => "read-only",
+        Errno::Spipe => "invalid-seek",
+        Errno::Txtbsy => "text-file-busy",
+        Errno::Xdev => "cross-device",
+        _ => "io",
+    };
+    Val::Enum(name.into())
+}
+
+/// Returns the `descriptor-type` value for `filetype`.
+fn descriptor_type(filetype: FileType) -> Val {
+    let name = match filetype {
+        FileType::Unknown => "unknown",
+        FileType::BlockDevice => "block-device",
+        FileType::CharacterDevice => "character-device",
+        FileType::Directory => "directory",
+        FileType::RegularFile => "regular-file",
+        FileType::SocketDgram | FileType::SocketStream => "socket",
+        FileType::SymbolicLink => "symbolic-link",
+    };
+    Val::Enum(name.into())
+}
+
+/// Returns the `descriptor-stat` value for `stat`.
+fn descriptor_stat(stat: &Filestat) -> Val {
+    let timestamp = |nanos| option(Some(datetime(nanos)));
+    record([
+        ("type", descriptor_type(stat.filetype)),
+        ("link-count", Val::U64(stat.nlink)),
+        ("size", Val::U64(stat.size)),
+        ("data-access-timestamp", timestamp(stat.atim)),
+        ("data-modification-timestamp", timestamp(stat.mtim)),
+        ("status-change-timestamp", timestamp(stat.ctim)),
+    ])
+}
+
+/// Returns the `metadata-hash-value` value for `stat`.
+fn metadata_hash_value(stat: &Filestat) -> Val {
+    record([("lower", Val::U64(stat.ino)), ("upper", Val::U64(stat.dev))])
+}
+
+/// Returns `true` if the `path-flags` value `val` has `symlink-follow` set.
+fn follow(val: &Val) -> Result<bool> {
+    flag(val, "symlink-follow")
+}
+
+/// The value of a `new-timestamp` parameter.
+enum NewTimestamp {
+    /// Leave the timestamp unchanged.
+    NoChange,
+    /// Set the timestamp to the current time.
+    Now,
+    /// Set the timestamp to the given nanoseconds.
+    Timestamp(u64),
+}
+
+impl NewTimestamp {
+    /// Decodes the `new-timestamp` value `val`.
+    fn decode(val: &Val) -> Result<Self> {
+        match val {
+            Val::Variant(case, None) if case == "no-change" => Ok(Self::NoChange),
+            Val::Variant(case, None) if case == "now" => Ok(Self::Now),
+            Val::Variant(case, Some(datetime)) if case == "timestamp" => match &**datetime {
+                Val::Record(fields) => match &fields[..] {
+                    [(_, Val::U64(seconds)), (_, Val::U32(nanos))] => Ok(Self::Timestamp(
+                        seconds
+                            .saturating_mul(1_000_000_000)
+                            .saturating_add(u64::from(*nanos)),
+                    )),
+                    _ => Err(invalid_param()),
+                },
+                _ => Err(invalid_param()),
+            },
+            _ => Err(invalid_param()),
+        }
+    }
+
+    /// Resolves the timestamp to set using the clocks of `ctx`.
+    fn resolve(self, ctx: &mut preview1::WasiCtx) -> FsResult<Option<u64>> {
+        match self {
+            Self::NoChange => Ok(None),
+            Self::Now => ctx.clocks.now(ClockId::Realtime).map(Some),
+            Self::Timestamp(nanos) => Ok(Some(nanos)),
+        }
+    }
+}
+
+/// Returns the attributes of the file or directory at `fd`.
+fn stat_fd(ctx: &mut preview1::WasiCtx, fd: u32) -> FsResult<Filestat> {
+    match ctx.descriptor(fd, Rights::FD_FILESTAT_GET)? {
+        Descriptor::File { file, .. } => file.stat(),
+        Descriptor::Dir { mount, path, .. } => {
+            let (mount, path) = (*mount, path.clone());
+            ctx.mount(mount).stat(&path, true)
+        }
+    }
+}
+
+/// Returns the attributes of the file or directory at `path` relative to `fd`.
+fn filestat_at(
+    ctx: &mut preview1::WasiCtx,
+    fd: u32,
+    path: &str,
+    follow: bool,
+) -> FsResult<Filestat> {
+    let (mount, path) = ctx.resolve(fd, path, Rights::PATH_FILESTAT_GET)?;
+    ctx.mount(mount).stat(&path, follow)
+}
+
+pub fn get_directories(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let preopens: Vec<(u32, String)> = ctx
+        .inner
+        .fds
+        .iter()
+        .filter_map(|(fd, descriptor)| match descriptor {
+            Descriptor::Dir {
+                mount,
+                preopen: true,
+                ..
+            } => Some((*fd, ctx.inner.mounts[*mount].guest_path.clone())),
+            _ => None,
+        })
+        .collect();
+    let preopens = preopens
+        .into_iter()
+        .map(|(fd, guest_path)| {
+            let descriptor = ctx.push(Entry::Descriptor { fd, owned: false });
+            Val::Tuple(vec![descriptor, Val::String(guest_path)])
+        })
+        .collect();
+    Ok(Some(Val::List(preopens)))
+}
+
+pub fn read_via_stream(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let offset = u64(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    let stream = fd.and_then(|fd| {
+        ctx.inner.file(fd, Rights::FD_READ)?;
+        Ok(fd)
+    });
+    respond(stream.map(|fd| {
+        Some(ctx.push(Entry::InputStream {
+            fd,
+            offset: Some(offset),
+        }))
+    }))
+}
+
+/// Creates an output stream for the descriptor `val` writing at `position`.
+fn output_stream(ctx: &mut WasiCtx, val: &Val, position: Position) -> Result {
+    let fd = ctx.fd(val);
+    let stream = fd.and_then(|fd| {
+        ctx.inner.file(fd, Rights::FD_WRITE)?;
+        Ok(fd)
+    });
+    respond(stream.map(|fd| Some(ctx.push(Entry::OutputStream { fd, position }))))
+}
+
+pub fn write_via_stream(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let offset = u64(&params[1])?;
+    output_stream(ctx, &params[0], Position::Offset(offset))
+}
+
+pub fn append_via_stream(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    output_stream(ctx, &params[0], Position::Append)
+}
+
+pub fn advise(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        ctx.inner.file(fd, Rights::empty())?;
+        Ok(None)
+    }))
+}
+
+pub fn sync(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(
+        fd.and_then(|fd| match ctx.inner.descriptor(fd, Rights::empty())? {
+            Descriptor::File { file, .. } => file.sync().map(|()| None),
+            Descriptor::Dir { .. } => Ok(None),
+        }),
+    )
+}
+
+pub fn get_flags(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let descriptor = ctx.inner.descriptor(fd, Rights::empty())?;
+        let (rights, _) = descriptor.rights();
+        let mut flags = Vec::new();
+        if rights.contains(Rights::FD_READ) || rights.contains(Rights::FD_READDIR) {
+            flags.push(String::from("read"));
+        }
+        if rights.contains(Rights::FD_WRITE) {
+            flags.push(String::from("write"));
+        }
+        if let Descriptor::File { file, .. } = descriptor {
+            let fdflags = file.flags();
+            if fdflags.contains(FdFlags::SYNC) {
+                flags.push(String::from("file-integrity-sync"));
+            }
+            if fdflags.contains(FdFlags::DSYNC) {
+                flags.push(String::from("data-integrity-sync"));
+            }
+            if fdflags.contains(FdFlags::RSYNC) {
+                flags.push(String::from("requested-write-sync"));
+            }
+        }
+        if !(rights & MUTATE_RIGHTS).is_empty() {
+            flags.push(String::from("mutate-directory"));
+        }
+        Ok(Some(Val::Flags(flags)))
+    }))
+}
+
+pub fn get_type(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let filetype = match ctx.inner.descriptor(fd, Rights::empty())? {
+            Descriptor::File { file, .. } => file.filetype(),
+            Descriptor::Dir { .. } => FileType::Directory,
+        };
+        Ok(Some(descriptor_type(filetype)))
+    }))
+}
+
+pub fn set_size(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let size = u64(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        ctx.inner
+            .file(fd, Rights::FD_FILESTAT_SET_SIZE)?
+            .set_size(size)
+            .map(|()| None)
+    }))
+}
+
+pub fn set_times(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let atim = NewTimestamp::decode(&params[1])?;
+    let mtim = NewTimestamp::decode(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    let ctx = &mut ctx.inner;
+    respond(fd.and_then(|fd| {
+        let (atim, mtim) = (atim.resolve(ctx)?, mtim.resolve(ctx)?);
+        match ctx.descriptor(fd, Rights::FD_FILESTAT_SET_TIMES)? {
+            Descriptor::File { file, .. } => file.set_times(atim, mtim)?,
+            Descriptor::Dir { mount, path, .. } => {
+                let (mount, path) = (*mount, path.clone());
+                ctx.mount(mount).set_times(&path, true, atim, mtim)?
+            }
+        }
+        Ok(None)
+    }))
+}
+
+pub fn read(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = u64(&params[1])?;
+    let offset = u64(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let file = ctx.inner.file(fd, Rights::FD_READ)?;
+        let mut buf = vec![0x00; len.min(1 << 20) as usize];
+        let read = file.pread(&mut buf, offset)?;
+        let eof = read == 0 && !buf.is_empty();
+        Ok(Some(Val::Tuple(vec![
+            list_u8(&buf[..read]),
+            Val::Bool(eof),
+        ])))
+    }))
+}
+
+pub fn write(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let buffer = bytes(&params[1])?;
+    let offset = u64(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let written = ctx
+            .inner
+            .file(fd, Rights::FD_WRITE)?
+            .pwrite(&buffer, offset)?;
+        Ok(Some(Val::U64(written as u64)))
+    }))
+}
+
+pub fn read_directory(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    let entries = fd.and_then(|fd| {
+        let (mount, path, _) = ctx.inner.dir(fd, Rights::FD_READDIR)?;
+        let path = String::from(path);
+        ctx.inner.mount(mount).read_dir(&path)
+    });
+    respond(entries.map(|entries| Some(ctx.push(Entry::DirectoryEntryStream { entries, pos: 0 }))))
+}
+
+pub fn read_directory_entry(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let Entry::DirectoryEntryStream { entries, pos } = ctx.entry(&params[0])? else {
+        return Err(invalid_param());
+    };
+    let entry = entries.get(*pos).map(|entry| {
+        record([
+            ("type", descriptor_type(entry.filetype)),
+            ("name", Val::String(entry.name.clone())),
+        ])
+    });
+    *pos += usize::from(entry.is_some());
+    respond(Ok(Some(option(entry))))
+}
+
+pub fn create_directory_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let path = string(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.inner.resolve(fd, path, Rights::PATH_CREATE_DIRECTORY)?;
+        ctx.inner.mount(mount).create_dir(&path).map(|()| None)
+    }))
+}
+
+pub fn stat(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| Ok(Some(descriptor_stat(&stat_fd(&mut ctx.inner, fd)?)))))
+}
+
+pub fn stat_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let follow = follow(&params[1])?;
+    let path = string(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let stat = filestat_at(&mut ctx.inner, fd, path, follow)?;
+        Ok(Some(descriptor_stat(&stat)))
+    }))
+}
+
+pub fn set_times_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let follow = follow(&params[1])?;
+    let path = string(&params[2])?;
+    let atim = NewTimestamp::decode(&params[3])?;
+    let mtim = NewTimestamp::decode(&params[4])?;
+    let fd = ctx.fd(&params[0]);
+    let ctx = &mut ctx.inner;
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.resolve(fd, path, Rights::PATH_FILESTAT_SET_TIMES)?;
+        let (atim, mtim) = (atim.resolve(ctx)?, mtim.resolve(ctx)?);
+        ctx.mount(mount)
+            .set_times(&path, follow, atim, mtim)
+            .map(|()| None)
+    }))
+}
+
+pub fn link_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let follow = follow(&params[1])?;
+    let old_path = string(&params[2])?;
+    let new_path = string(&params[4])?;
+    let old_fd = ctx.fd(&params[0]);
+    let new_fd = ctx.fd(&params[3]);
+    respond(old_fd.and_then(|old_fd| {
+        let (old_mount, old_path) =
+            ctx.inner
+                .resolve(old_fd, old_path, Rights::PATH_LINK_SOURCE)?;
+        let (new_mount, new_path) =
+            ctx.inner
+                .resolve(new_fd?, new_path, Rights::PATH_LINK_TARGET)?;
+        if old_mount != new_mount {
+            return Err(Errno::Xdev);
+        }
+        ctx.inner
+            .mount(old_mount)
+            .link(&old_path, &new_path, follow)
+            .map(|()| None)
+    }))
+}
+
+pub fn open_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let follow = follow(&params[1])?;
+    let path = string(&params[2])?;
+    let open_flags = &params[3];
+    let flags = &params[4];
+    let (read, write, mutate) = (
+        flag(flags, "read")?,
+        flag(flags, "write")?,
+        flag(flags, "mutate-directory")?,
+    );
+    let mut base = Rights::DIRECTORY | Rights::REGULAR_FILE;
+    if !read {
+        base = base & !(Rights::FD_READ | Rights::FD_READDIR);
+    }
+    if !write {
+        base = base & !WRITE_RIGHTS;
+    }
+    if !mutate {
+        base = base & !MUTATE_RIGHTS;
+    }
+    let mut fdflags = FdFlags::empty();
+    for (name, fdflag) in [
+        ("file-integrity-sync", FdFlags::SYNC),
+        ("data-integrity-sync", FdFlags::DSYNC),
+        ("requested-write-sync", FdFlags::RSYNC),
+    ] {
+        if flag(flags, name)? {
+            fdflags = fdflags | fdflag;
+        }
+    }
+    let options = OpenOptions {
+        read,
+        write,
+        create: flag(open_flags, "create")?,
+        exclusive: flag(open_flags, "exclusive")?,
+        truncate: flag(open_flags, "truncate")?,
+        directory: flag(open_flags, "directory")?,
+        follow,
+        flags: fdflags,
+    };
+    let fd = ctx.fd(&params[0]);
+    let opened = fd.and_then(|fd| {
+        let mut required = Rights::PATH_OPEN;
+        if options.create {
+            required = required | Rights::PATH_CREATE_FILE;
+        }
+        if options.truncate {
+            required = required | Rights::PATH_FILESTAT_SET_SIZE;
+        }
+        let (mount, base_path, inheriting) = ctx.inner.dir(fd, required)?;
+        let path = normalize_path(base_path, path)?;
+        let descriptor = match ctx.inner.mount(mount).open(&path, &options)? {
+            Opened::File(_) if options.directory => return Err(Errno::Notdir),
+            Opened::File(file) => Descriptor::File {
+                rights: base & inheriting & Rights::for_filetype(file.filetype()),
+                file,
+                inheriting: Rights::empty(),
+            },
+            Opened::Dir(path) => Descriptor::Dir {
+                mount,
+                path,
+                preopen: false,
+                rights: base & inheriting & Rights::DIRECTORY,
+                inheriting: base & inheriting,
+            },
+        };
+        ctx.inner.push(descriptor)
+    });
+    respond(opened.map(|fd| Some(ctx.push(Entry::Descriptor { fd, owned: true }))))
+}
+
+pub fn readlink_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let path = string(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.inner.resolve(fd, path, Rights::PATH_READLINK)?;
+        let target = ctx.inner.mount(mount).read_link(&path)?;
+        Ok(Some(Val::String(target)))
+    }))
+}
+
+pub fn remove_directory_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let path = string(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.inner.resolve(fd, path, Rights::PATH_REMOVE_DIRECTORY)?;
+        if path.is_empty() {
+            return Err(Errno::Busy);
+        }
+        ctx.inner.mount(mount).remove_dir(&path).map(|()| None)
+    }))
+}
+
+pub fn rename_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let old_path = string(&params[1])?;
+    let new_path = string(&params[3])?;
+    let old_fd = ctx.fd(&params[0]);
+    let new_fd = ctx.fd(&params[2]);
+    respond(old_fd.and_then(|old_fd| {
+        let (old_mount, old_path) =
+            ctx.inner
+                .resolve(old_fd, old_path, Rights::PATH_RENAME_SOURCE)?;
+        let (new_mount, new_path) =
+            ctx.inner
+                .resolve(new_fd?, new_path, Rights::PATH_RENAME_TARGET)?;
+        if old_mount != new_mount {
+            return Err(Errno::Xdev);
+        }
+        ctx.inner
+            .mount(old_mount)
+            .rename(&old_path, &new_path)
+            .map(|()| None)
+    }))
+}
+
+pub fn symlink_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let target = string(&params[1])?;
+    let path = string(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.inner.resolve(fd, path, Rights::PATH_SYMLINK)?;
+        ctx.inner.mount(mount).symlink(target, &path).map(|()| None)
+    }))
+}
+
+pub fn unlink_file_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let path = string(&params[1])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let (mount, path) = ctx.inner.resolve(fd, path, Rights::PATH_UNLINK_FILE)?;
+        ctx.inner.mount(mount).unlink_file(&path).map(|()| None)
+    }))
+}
+
+pub fn is_same_object(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let mut stat = |val| {
+        let fd = ctx.fd(val)?;
+        stat_fd(&mut ctx.inner, fd)
+    };
+    let same = match (stat(&params[0]), stat(&params[1])) {
+        (Ok(a), Ok(b)) => (a.dev, a.ino) == (b.dev, b.ino),
+        _ => false,
+    };
+    Ok(Some(Val::Bool(same)))
+}
+
+pub fn metadata_hash(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let stat = stat_fd(&mut ctx.inner, fd)?;
+        Ok(Some(metadata_hash_value(&stat)))
+    }))
+}
+
+pub fn metadata_hash_at(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let follow = follow(&params[1])?;
+    let path = string(&params[2])?;
+    let fd = ctx.fd(&params[0]);
+    respond(fd.and_then(|fd| {
+        let stat = filestat_at(&mut ctx.inner, fd, path, follow)?;
+        Ok(Some(metadata_hash_value(&stat)))
+    }))
+}
+
+pub fn filesystem_error_code(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let code = match ctx.entry(&params[0])? {
+        Entry::Error(errno) => Some(error_code(*errno)),
+        _ => None,
+    };
+    Ok(Some(option(code)))
+}
@@ -0,0 +1,270 @@
+//! Implementation of the `wasi:io` interfaces.
+
+use super::{
+    bytes,
+    invalid_param,
+    list_u8,
+    rep,
+    result,
+    trap,
+    u64,
+    Entry,
+    Position,
+    Result,
+    WasiCtx,
+};
+use crate::preview1::{ClockId, Errno, Rights, Whence};
+use alloc::{format, vec, vec::Vec};
+use wasmi::component::Val;
+
+/// The number of bytes that output streams accept per write.
+const WRITE_BUDGET: u64 = 1 << 20;
+
+/// The maximum number of bytes read or skipped at once.
+const READ_BUDGET: u64 = 1 << 20;
+
+/// Returns the `stream-error` value for the result of a stream operation.
+///
+/// `None` signals that the stream has been closed.
+fn stream_error(ctx: &mut WasiCtx, errno: Option<Errno>) -> Val {
+    let error = match errno {
+        Some(errno) => Val::Variant(
+            "last-operation-failed".into(),
+            Some(ctx.push(Entry::Error(errno)).into()),
+        ),
+        None => Val::Variant("closed".into(), None),
+    };
+    result(Err(error))
+}
+
+/// Reads up to `len` bytes from the input stream `stream`.
+///
+/// Returns `Ok(None)` if the end of the stream has been reached.
+fn read_stream(
+    ctx: &mut WasiCtx,
+    stream: &Val,
+    len: u64,
+) -> Result<core::result::Result<Option<Vec<u8>>, Errno>> {
+    let (fd, offset) = match ctx.entry(stream)? {
+        Entry::InputStream { fd, offset } => (*fd, *offset),
+        _ => return Err(invalid_param()),
+    };
+    let mut buf = vec![0x00; len.min(READ_BUDGET) as usize];
+    let read = ctx
+        .inner
+        .file(fd, Rights::empty())
+        .and_then(|file| match offset {
+            Some(offset) => file.pread(&mut buf, offset),
+            None => file.read(&mut buf),
+        });
+    let read = match read {
+        Ok(read) => read,
+        Err(errno) => return Ok(Err(errno)),
+    };
+    if read == 0 && !buf.is_empty() {
+        return Ok(Ok(None));
+    }
+    if let Entry::InputStream {
+        offset: Some(offset),
+        ..
+    } = ctx.entry(stream)?
+    {
+        *offset += read as u64;
+    }
+    buf.truncate(read);
+    Ok(Ok(Some(buf)))
+}
+
+/// Writes all `bytes` to the output stream `stream`.
+fn write_stream(
+    ctx: &mut WasiCtx,
+    stream: &Val,
+    bytes: &[u8],
+) -> Result<core::result::Result<(), Errno>> {
+    let (fd, position) = match ctx.entry(stream)? {
+        Entry::OutputStream { fd, position } => (*fd, *position),
+        _ => return Err(invalid_param()),
+    };
+    let written = ctx
+        .inner
+        .file(fd, Rights::empty())
+        .and_then(|file| write_all(file, position, bytes));
+    if let (
+        Ok(()),
+        Entry::OutputStream {
+            position: Position::Offset(offset),
+            ..
+        },
+    ) = (&written, ctx.entry(stream)?)
+    {
+        *offset += bytes.len() as u64;
+    }
+    Ok(written)
+}
+
+/// Writes all `bytes` to the `file` at `position`.
+fn write_all(
+    file: &mut dyn crate::preview1::WasiFile,
+    position: Position,
+    mut bytes: &[u8],
+) -> core::result::Result<(), Errno> {
+    let mut offset = match position {
+        Position::Offset(offset) => Some(offset),
+        Position::Append => {
+            file.seek(0, Whence::End)?;
+            None
+        }
+        Position::Current => None,
+    };
+    while !bytes.is_empty() {
+        let written = match &mut offset {
+            Some(offset) => {
+                let written = file.pwrite(bytes, *offset)?;
+                *offset += written as u64;
+                written
+            }
+            None => file.write(bytes)?,
+        };
+        if written == 0 {
+            return Err(Errno::Io);
+        }
+        bytes = &bytes[written..];
+    }
+    Ok(())
+}
+
+pub fn error_to_debug_string(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    match ctx.entry(&params[0])? {
+        Entry::Error(errno) => Ok(Some(Val::String(format!("{errno}")))),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns `true` if the pollable `pollable` is ready.
+fn is_ready(ctx: &mut WasiCtx, pollable: &Val) -> Result<bool> {
+    let deadline = match ctx.entry(pollable)? {
+        Entry::Pollable { deadline } => *deadline,
+        _ => return Err(invalid_param()),
+    };
+    match deadline {
+        Some(deadline) => Ok(ctx.inner.clocks.now(ClockId::Monotonic).map_err(trap)? >= deadline),
+        None => Ok(true),
+    }
+}
+
+/// Blocks until the monotonic clock reaches the earliest deadline of `pollables`.
+fn block(ctx: &mut WasiCtx, pollables: &[Val]) -> Result<()> {
+    let mut earliest = None;
+    for pollable in pollables {
+        match ctx.entry(pollable)? {
+            Entry::Pollable { deadline: None } => return Ok(()),
+            Entry::Pollable {
+                deadline: Some(deadline),
+            } => {
+                earliest = Some(earliest.map_or(*deadline, |earliest: u64| earliest.min(*deadline)))
+            }
+            _ => return Err(invalid_param()),
+        }
+    }
+    if let Some(deadline) = earliest {
+        let now = ctx.inner.clocks.now(ClockId::Monotonic).map_err(trap)?;
+        if deadline > now {
+            ctx.inner.clocks.sleep(deadline - now).map_err(trap)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn pollable_ready(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    Ok(Some(Val::Bool(is_ready(ctx, &params[0])?)))
+}
+
+pub fn pollable_block(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    block(ctx, &params[..1])?;
+    Ok(None)
+}
+
+pub fn poll(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let Val::List(pollables) = &params[0] else {
+        return Err(invalid_param());
+    };
+    if pollables.is_empty() {
+        return Err(wasmi::Error::new("cannot poll an empty list of pollables"));
+    }
+    block(ctx, pollables)?;
+    let mut ready = Vec::new();
+    for (index, pollable) in pollables.iter().enumerate() {
+        if is_ready(ctx, pollable)? {
+            ready.push(Val::U32(index as u32));
+        }
+    }
+    Ok(Some(Val::List(ready)))
+}
+
+pub fn subscribe(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    rep(&params[0])?;
+    Ok(Some(ctx.push(Entry::Pollable { deadline: None })))
+}
+
+pub fn input_stream_read(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = u64(&params[1])?;
+    Ok(Some(match read_stream(ctx, &params[0], len)? {
+        Ok(Some(bytes)) => result(Ok(Some(list_u8(&bytes)))),
+        Ok(None) => stream_error(ctx, None),
+        Err(errno) => stream_error(ctx, Some(errno)),
+    }))
+}
+
+pub fn input_stream_skip(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = u64(&params[1])?;
+    Ok(Some(match read_stream(ctx, &params[0], len)? {
+        Ok(Some(bytes)) => result(Ok(Some(Val::U64(bytes.len() as u64)))),
+        Ok(None) => stream_error(ctx, None),
+        Err(errno) => stream_error(ctx, Some(errno)),
+    }))
+}
+
+pub fn output_stream_check_write(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    match ctx.entry(&params[0])? {
+        Entry::OutputStream { .. } => Ok(Some(result(Ok(Some(Val::U64(WRITE_BUDGET)))))),
+        _ => Err(invalid_param()),
+    }
+}
+
+pub fn output_stream_write(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let contents = bytes(&params[1])?;
+    Ok(Some(match write_stream(ctx, &params[0], &contents)? {
+        Ok(()) => result(Ok(None)),
+        Err(errno) => stream_error(ctx, Some(errno)),
+    }))
+}
+
+pub fn output_stream_write_zeroes(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = u64(&params[1])?.min(WRITE_BUDGET);
+    Ok(Some(
+        match write_stream(ctx, &params[0], &vec![0x00; len as usize])? {
+            Ok(()) => result(Ok(None)),
+            Err(errno) => stream_error(ctx, Some(errno)),
+        },
+    ))
+}
+
+pub fn output_stream_flush(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    match ctx.entry(&params[0])? {
+        Entry::OutputStream { .. } => Ok(Some(result(Ok(None)))),
+        _ => Err(invalid_param()),
+    }
+}
+
+pub fn output_stream_splice(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = u64(&params[2])?;
+    let bytes = match read_stream(ctx, &params[1], len)? {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => return Ok(Some(stream_error(ctx, None))),
+        Err(errno) => return Ok(Some(stream_error(ctx, Some(errno)))),
+    };
+    Ok(Some(match write_stream(ctx, &params[0], &bytes)? {
+        Ok(()) => result(Ok(Some(Val::U64(bytes.len() as u64)))),
+        Err(errno) => stream_error(ctx, Some(errno)),
+    }))
+}
@@ -0,0 +1,438 @@
+//! Native implementation of WASI `preview2` for Wasm components.
+//!
+//! The host functions are defined on a component [`Linker`] and are built on
+//! top of the [`preview1`] implementation: the same [`WasiFile`], [`WasiDir`],
+//! [`WasiClocks`] and [`WasiRandom`] backends serve both, so a [`WasiCtx`] is
+//! created from a [`preview1::WasiCtx`] built by [`WasiCtxBuilder`].
+//!
+//! The following `0.2` interfaces are supported:
+//!
+//! - `wasi:cli/{environment, exit, stdin, stdout, stderr}` and the `terminal-*` interfaces
+//! - `wasi:io/{error, poll, streams}`
+//! - `wasi:clocks/{wall-clock, monotonic-clock}`
+//! - `wasi:random/{random, insecure, insecure-seed}`
+//! - `wasi:filesystem/{types, preopens}`
+//!
+//! All streams are backed by blocking host files and therefore always ready.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{component::{Component, Linker, Val}, Engine, Store};
+//! use wasmi_wasi::{preview1::WasiCtxBuilder, preview2::{self, WasiCtx}};
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (component
+//!         (import "wasi:random/random@0.2.0" (instance $random
+//!             (export "get-random-u64" (func (result u64)))
+//!         ))
+//!         (alias export $random "get-random-u64" (func $get))
+//!         (core func $get (canon lower (func $get)))
+//!         (core module $m
+//!             (import "random" "get" (func $get (result i64)))
+//!             (func (export "roll") (result i64)
+//!                 (i64.rem_u (call $get) (i64.const 6))
+//!             )
+//!         )
+//!         (core instance $m (instantiate $m (with "random" (instance (export "get" (func $get))))))
+//!         (func (export "roll") (result u64) (canon lift (core func $m "roll")))
+//!     )
+//! "#)?;
+//! let engine = Engine::default();
+//! let component = Component::new(&engine, &wasm)?;
+//! let wasi = WasiCtx::new(WasiCtxBuilder::new().deterministic(42).build());
+//! let mut store = Store::new(&engine, wasi);
+//! let mut linker = <Linker<WasiCtx>>::new(&engine);
+//! preview2::add_to_linker(&mut linker, |ctx| ctx)?;
+//! let instance = linker.instantiate(&mut store, &component)?;
+//! let mut result = [Val::U64(0)];
+//! instance.get_func("roll").unwrap().call(&mut store, &[], &mut result)?;
+//! assert!(matches!(result[0], Val::U64(0..=5)));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`preview1`]: crate::preview1
+//! [`preview1::WasiCtx`]: crate::preview1::WasiCtx
+//! [`WasiCtxBuilder`]: crate::preview1::WasiCtxBuilder
+//! [`WasiFile`]: crate::preview1::WasiFile
+//! [`WasiDir`]: crate::preview1::WasiDir
+//! [`WasiClocks`]: crate::preview1::WasiClocks
+//! [`WasiRandom`]: crate::preview1::WasiRandom
+
+mod cli;
+mod clocks;
+mod filesystem;
+mod io;
+mod random;
+
+use crate::preview1::{self, DirEntry, Errno};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use wasmi::component::{Linker, Val};
+
+/// The result type of all WASI `preview2` host functions.
+type Result<T = Option<Val>> = core::result::Result<T, wasmi::Error>;
+
+/// The position at which an output stream writes to its file.
+#[derive(Debug, Copy, Clone)]
+enum Position {
+    /// Writes at the current position of the file.
+    Current,
+    /// Writes at the given offset.
+    Offset(u64),
+    /// Writes at the end of the file.
+    Append,
+}
+
+/// A resource owned by the guest.
+#[derive(Debug)]
+enum Entry {
+    /// A file or directory at the `preview1` file descriptor `fd`.
+    ///
+    /// The file descriptor is closed when the entry is dropped if it is `owned`.
+    Descriptor { fd: u32, owned: bool },
+    /// An input stream reading from the file at `fd`.
+    InputStream { fd: u32, offset: Option<u64> },
+    /// An output stream writing to the file at `fd`.
+    OutputStream { fd: u32, position: Position },
+    /// A pollable that is ready once the monotonic clock reaches `deadline` if any.
+    Pollable { deadline: Option<u64> },
+    /// A stream of directory entries.
+    DirectoryEntryStream { entries: Vec<DirEntry>, pos: usize },
+    /// An error returned by a stream operation.
+    Error(Errno),
+}
+
+/// The state of the native WASI `preview2` implementation.
+///
+/// Wraps a [`preview1::WasiCtx`] that provides the arguments, environment,
+/// stdio, preopens, clocks and random number source.
+#[derive(Debug)]
+pub struct WasiCtx {
+    /// The underlying `preview1` state.
+    inner: preview1::WasiCtx,
+    /// The resources owned by the guest by their representation.
+    table: BTreeMap<u32, Entry>,
+    /// The representation of the next resource.
+    next_rep: u32,
+}
+
+impl From<preview1::WasiCtx> for WasiCtx {
+    fn from(inner: preview1::WasiCtx) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl WasiCtx {
+    /// Creates a new [`WasiCtx`] from the `preview1` state `inner`.
+    pub fn new(inner: preview1::WasiCtx) -> Self {
+        Self {
+            inner,
+            table: BTreeMap::new(),
+            next_rep: 1,
+        }
+    }
+
+    /// Returns a shared reference to the underlying [`preview1::WasiCtx`].
+    pub fn preview1(&self) -> &preview1::WasiCtx {
+        &self.inner
+    }
+
+    /// Returns an exclusive reference to the underlying [`preview1::WasiCtx`].
+    pub fn preview1_mut(&mut self) -> &mut preview1::WasiCtx {
+        &mut self.inner
+    }
+
+    /// Inserts the `entry` and returns an owned handle to it.
+    fn push(&mut self, entry: Entry) -> Val {
+        let rep = self.next_rep;
+        self.next_rep = self.next_rep.wrapping_add(1).max(1);
+        self.table.insert(rep, entry);
+        Val::Own(rep)
+    }
+
+    /// Returns the [`Entry`] referred to by the handle `val`.
+    fn entry(&mut self, val: &Val) -> Result<&mut Entry> {
+        let rep = rep(val)?;
+        self.table
+            .get_mut(&rep)
+            .ok_or_else(|| wasmi::Error::new("unknown WASI resource"))
+    }
+
+    /// Drops the resource `rep` and closes its file descriptor if owned.
+    fn drop_entry(&mut self, rep: u32) {
+        if let Some(Entry::Descriptor { fd, owned: true }) = self.table.remove(&rep) {
+            self.inner.fds.remove(&fd);
+        }
+    }
+
+    /// Returns the `preview1` file descriptor of the descriptor handle `val`.
+    fn fd(&mut self, val: &Val) -> core::result::Result<u32, Errno> {
+        match self.entry(val) {
+            Ok(Entry::Descriptor { fd, .. }) => Ok(*fd),
+            _ => Err(Errno::Badf),
+        }
+    }
+}
+
+/// Returns an error for a parameter that does not match its WASI type.
+fn invalid_param() -> wasmi::Error {
+    wasmi::Error::new("invalid WASI parameter")
+}
+
+/// Returns the trap for an `errno` of a WASI backend that cannot be reported to the guest.
+fn trap(errno: Errno) -> wasmi::Error {
+    wasmi::Error::new(alloc::format!("WASI host error: {errno}"))
+}
+
+/// Returns the representation of the resource handle `val`.
+fn rep(val: &Val) -> Result<u32> {
+    match val {
+        Val::Own(rep) | Val::Borrow(rep) => Ok(*rep),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns the `u64` value of `val`.
+fn u64(val: &Val) -> Result<u64> {
+    match val {
+        Val::U64(value) => Ok(*value),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns the `string` value of `val`.
+fn string(val: &Val) -> Result<&str> {
+    match val {
+        Val::String(value) => Ok(value),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns the `list<u8>` value of `val`.
+fn bytes(val: &Val) -> Result<Vec<u8>> {
+    match val {
+        Val::List(values) => values
+            .iter()
+            .map(|value| match value {
+                Val::U8(byte) => Ok(*byte),
+                _ => Err(invalid_param()),
+            })
+            .collect(),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns `true` if the `flags` value `val` has the flag `name` set.
+fn flag(val: &Val, name: &str) -> Result<bool> {
+    match val {
+        Val::Flags(flags) => Ok(flags.iter().any(|flag| flag == name)),
+        _ => Err(invalid_param()),
+    }
+}
+
+/// Returns the `list<u8>` value for `bytes`.
+fn list_u8(bytes: &[u8]) -> Val {
+    Val::List(bytes.iter().copied().map(Val::U8).collect())
+}
+
+/// Returns the `result` value for `result`.
+fn result(result: core::result::Result<Option<Val>, Val>) -> Val {
+    match result {
+        Ok(val) => Val::Result(Ok(val.map(Box::new))),
+        Err(val) => Val::Result(Err(Some(Box::new(val)))),
+    }
+}
+
+/// Returns the `option` value for `option`.
+fn option(option: Option<Val>) -> Val {
+    Val::Option(option.map(Box::new))
+}
+
+/// Returns the `record` value with the given fields.
+fn record<const N: usize>(fields: [(&str, Val); N]) -> Val {
+    Val::Record(
+        fields
+            .into_iter()
+            .map(|(name, val)| (String::from(name), val))
+            .collect(),
+    )
+}
+
+/// Returns the `wasi:clocks/wall-clock.datetime` value for `nanos` nanoseconds.
+fn datetime(nanos: u64) -> Val {
+    record([
+        ("seconds", Val::U64(nanos / 1_000_000_000)),
+        ("nanoseconds", Val::U32((nanos % 1_000_000_000) as u32)),
+    ])
+}
+
+macro_rules! impl_add_to_linker {
+    (
+        $(
+            $instance:literal {
+                $( resource $resource:literal; )*
+                $( fn $name:literal = $func:path; )*
+            }
+        )*
+    ) => {
+        /// Adds the native WASI `preview2` interfaces to the component [`Linker`].
+        ///
+        /// The `get` function projects the host state `T` to its [`WasiCtx`].
+        /// The interfaces are defined in version `0.2.0` which satisfies imports
+        /// of all compatible `0.2` versions.
+        ///
+        /// # Note
+        ///
+        /// `wasi:cli/exit.exit` traps with an `i32` exit status that can be queried
+        /// via [`wasmi::Error::i32_exit_status`].
+        ///
+        /// # Errors
+        ///
+        /// If any of the WASI definitions is already defined in the [`Linker`].
+        pub fn add_to_linker<T>(
+            linker: &mut Linker<T>,
+            get: impl Fn(&mut T) -> &mut WasiCtx + Send + Sync + Copy + 'static,
+        ) -> core::result::Result<(), wasmi::Error> {
+            $(
+                let mut instance = linker.instance($instance);
+                $(
+                    instance.resource($resource, move |mut store, rep| {
+                        get(store.data_mut()).drop_entry(rep);
+                        Ok(())
+                    })?;
+                )*
+                $(
+                    instance.func_new($name, move |mut store, params, results| {
+                        let result = $func(get(store.data_mut()), params)?;
+                        if let (Some(slot), Some(result)) = (results.first_mut(), result) {
+                            *slot = result;
+                        }
+                        Ok(())
+                    })?;
+                )*
+            )*
+            Ok(())
+        }
+    };
+}
+impl_add_to_linker! {
+    "wasi:cli/environment@0.2.0" {
+        fn "get-environment" = cli::get_environment;
+        fn "get-arguments" = cli::get_arguments;
+        fn "initial-cwd" = cli::initial_cwd;
+    }
+    "wasi:cli/exit@0.2.0" {
+        fn "exit" = cli::exit;
+        fn "exit-with-code" = cli::exit_with_code;
+    }
+    "wasi:cli/stdin@0.2.0" {
+        fn "get-stdin" = cli::get_stdin;
+    }
+    "wasi:cli/stdout@0.2.0" {
+        fn "get-stdout" = cli::get_stdout;
+    }
+    "wasi:cli/stderr@0.2.0" {
+        fn "get-stderr" = cli::get_stderr;
+    }
+    "wasi:cli/terminal-input@0.2.0" {
+        resource "terminal-input";
+    }
+    "wasi:cli/terminal-output@0.2.0" {
+        resource "terminal-output";
+    }
+    "wasi:cli/terminal-stdin@0.2.0" {
+        fn "get-terminal-stdin" = cli::get_terminal;
+    }
+    "wasi:cli/terminal-stdout@0.2.0" {
+        fn "get-terminal-stdout" = cli::get_terminal;
+    }
+    "wasi:cli/terminal-stderr@0.2.0" {
+        fn "get-terminal-stderr" = cli::get_terminal;
+    }
+    "wasi:io/error@0.2.0" {
+        resource "error";
+        fn "[method]error.to-debug-string" = io::error_to_debug_string;
+    }
+    "wasi:io/poll@0.2.0" {
+        resource "pollable";
+        fn "[method]pollable.ready" = io::pollable_ready;
+        fn "[method]pollable.block" = io::pollable_block;
+        fn "poll" = io::poll;
+    }
+    "wasi:io/streams@0.2.0" {
+        resource "input-stream";
+        resource "output-stream";
+        fn "[method]input-stream.read" = io::input_stream_read;
+        fn "[method]input-stream.blocking-read" = io::input_stream_read;
+        fn "[method]input-stream.skip" = io::input_stream_skip;
+        fn "[method]input-stream.blocking-skip" = io::input_stream_skip;
+        fn "[method]input-stream.subscribe" = io::subscribe;
+        fn "[method]output-stream.check-write" = io::output_stream_check_write;
+        fn "[method]output-stream.write" = io::output_stream_write;
+        fn "[method]output-stream.blocking-write-and-flush" = io::output_stream_write;
+        fn "[method]output-stream.flush" = io::output_stream_flush;
+        fn "[method]output-stream.blocking-flush" = io::output_stream_flush;
+        fn "[method]output-stream.subscribe" = io::subscribe;
+        fn "[method]output-stream.write-zeroes" = io::output_stream_write_zeroes;
+        fn "[method]output-stream.blocking-write-zeroes-and-flush" = io::output_stream_write_zeroes;
+        fn "[method]output-stream.splice" = io::output_stream_splice;
+        fn "[method]output-stream.blocking-splice" = io::output_stream_splice;
+    }
+    "wasi:clocks/wall-clock@0.2.0" {
+        fn "now" = clocks::wall_clock_now;
+        fn "resolution" = clocks::wall_clock_resolution;
+    }
+    "wasi:clocks/monotonic-clock@0.2.0" {
+        fn "now" = clocks::monotonic_clock_now;
+        fn "resolution" = clocks::monotonic_clock_resolution;
+        fn "subscribe-instant" = clocks::subscribe_instant;
+        fn "subscribe-duration" = clocks::subscribe_duration;
+    }
+    "wasi:random/random@0.2.0" {
+        fn "get-random-bytes" = random::get_random_bytes;
+        fn "get-random-u64" = random::get_random_u64;
+    }
+    "wasi:random/insecure@0.2.0" {
+        fn "get-insecure-random-bytes" = random::get_random_bytes;
+        fn "get-insecure-random-u64" = random::get_random_u64;
+    }
+    "wasi:random/insecure-seed@0.2.0" {
+        fn "insecure-seed" = random::insecure_seed;
+    }
+    "wasi:filesystem/preopens@0.2.0" {
+        fn "get-directories" = filesystem::get_directories;
+    }
+    "wasi:filesystem/types@0.2.0" {
+        resource "descriptor";
+        resource "directory-entry-stream";
+        fn "[method]descriptor.read-via-stream" = filesystem::read_via_stream;
+        fn "[method]descriptor.write-via-stream" = filesystem::write_via_stream;
+        fn "[method]descriptor.append-via-stream" = filesystem::append_via_stream;
+        fn "[method]descriptor.advise" = filesystem::advise;
+        fn "[method]descriptor.sync-data" = filesystem::sync;
+        fn "[method]descriptor.get-flags" = filesystem::get_flags;
+        fn "[method]descriptor.get-type" = filesystem::get_type;
+        fn "[method]descriptor.set-size" = filesystem::set_size;
+        fn "[method]descriptor.set-times" = filesystem::set_times;
+        fn "[method]descriptor.read" = filesystem::read;
+        fn "[method]descriptor.write" = filesystem::write;
+        fn "[method]descriptor.read-directory" = filesystem::read_directory;
+        fn "[method]descriptor.sync" = filesystem::sync;
+        fn "[method]descriptor.create-directory-at" = filesystem::create_directory_at;
+        fn "[method]descriptor.stat" = filesystem::stat;
+        fn "[method]descriptor.stat-at" = filesystem::stat_at;
+        fn "[method]descriptor.set-times-at" = filesystem::set_times_at;
+        fn "[method]descriptor.link-at" = filesystem::link_at;
+        fn "[method]descriptor.open-at" = filesystem::open_at;
+        fn "[method]descriptor.readlink-at" = filesystem::readlink_at;
+        fn "[method]descriptor.remove-directory-at" = filesystem::remove_directory_at;
+        fn "[method]descriptor.rename-at" = filesystem::rename_at;
+        fn "[method]descriptor.symlink-at" = filesystem::symlink_at;
+        fn "[method]descriptor.unlink-file-at" = filesystem::unlink_file_at;
+        fn "[method]descriptor.is-same-object" = filesystem::is_same_object;
+        fn "[method]descriptor.metadata-hash" = filesystem::metadata_hash;
+        fn "[method]descriptor.metadata-hash-at" = filesystem::metadata_hash_at;
+        fn "[method]directory-entry-stream.read-directory-entry" = filesystem::read_directory_entry;
+        fn "filesystem-error-code" = filesystem::filesystem_error_code;
+    }
+}
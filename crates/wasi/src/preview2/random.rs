@@ -0,0 +1,29 @@
+//! Implementation of the `wasi:random` interfaces.
+
+use super::{list_u8, trap, u64, Result, WasiCtx};
+use alloc::{vec, vec::Vec};
+use wasmi::component::Val;
+
+/// Returns a random `u64` from the random number source of `ctx`.
+fn random_u64(ctx: &mut WasiCtx) -> Result<u64> {
+    let mut bytes = [0x00; 8];
+    ctx.inner.random.fill(&mut bytes).map_err(trap)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+pub fn get_random_bytes(ctx: &mut WasiCtx, params: &[Val]) -> Result {
+    let len = usize::try_from(u64(&params[0])?)
+        .map_err(|_| wasmi::Error::new("requested too many random bytes"))?;
+    let mut bytes = vec![0x00; len];
+    ctx.inner.random.fill(&mut bytes).map_err(trap)?;
+    Ok(Some(list_u8(&bytes)))
+}
+
+pub fn get_random_u64(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    Ok(Some(Val::U64(random_u64(ctx)?)))
+}
+
+pub fn insecure_seed(ctx: &mut WasiCtx, _params: &[Val]) -> Result {
+    let seed = Vec::from([Val::U64(random_u64(ctx)?), Val::U64(random_u64(ctx)?)]);
+    Ok(Some(Val::Tuple(seed)))
+}
@@ -1,6 +1,11 @@
-use crate::WasmiGuestMemory;
+use crate::{
+    preview1::{decode_args, Errno, GuestMemory, TraceArg, WasiTracer},
+    WasmiGuestMemory,
+};
 use std::{
+    boxed::Box,
     pin::Pin,
+    string::String,
     task::{Context, RawWaker, RawWakerVTable, Waker},
 };
 use wasi_common::Error;
@@ -53,12 +58,34 @@ macro_rules! impl_add_to_linker_for_funcs {
         ///
         /// `WASI` is versioned in snapshots. It's still a WIP. Currently, this crate supports `preview_1`
         /// Look [here](https://github.com/WebAssembly/WASI/blob/main/phases/snapshot/docs.md) for more details.
+        ///
+        /// WASI calls of guests exporting a shared linear memory fail with an error.
+        /// Use [`preview1::add_to_linker`](crate::preview1::add_to_linker) for such guests.
         pub fn add_wasi_snapshot_preview1_to_linker<T, U>(
             linker: &mut Linker<T>,
             wasi_ctx: impl Fn(&mut T) -> &mut U + Send + Sync + Copy + 'static,
         ) -> Result<(), Error>
         where U: wasi_common::snapshots::preview_1::wasi_snapshot_preview1::WasiSnapshotPreview1 +
                  wasi_common::snapshots::preview_1::wasi_snapshot_preview1::UserErrorConversion
+        {
+            add_wasi_snapshot_preview1_to_linker_with_tracer::<T, U, dyn WasiTracer>(linker, wasi_ctx, |_| None)
+        }
+
+        /// Adds the entire `WASI API` to the [`Linker`] reporting all calls to a [`WasiTracer`].
+        ///
+        /// The `tracer` function projects the host state `T` to its [`WasiTracer`] if any.
+        /// Arguments and returned errno values are reported the same way as for the native
+        /// [`preview1::add_to_linker`](crate::preview1::add_to_linker) so that both produce the same traces.
+        ///
+        /// See [`add_wasi_snapshot_preview1_to_linker`] for more information.
+        pub fn add_wasi_snapshot_preview1_to_linker_with_tracer<T, U, W>(
+            linker: &mut Linker<T>,
+            wasi_ctx: impl Fn(&mut T) -> &mut U + Send + Sync + Copy + 'static,
+            tracer: impl Fn(&mut T) -> Option<&mut W> + Send + Sync + Copy + 'static,
+        ) -> Result<(), Error>
+        where U: wasi_common::snapshots::preview_1::wasi_snapshot_preview1::WasiSnapshotPreview1 +
+                 wasi_common::snapshots::preview_1::wasi_snapshot_preview1::UserErrorConversion,
+              W: WasiTracer + ?Sized,
         {
             $(
                 // $(#[$docs])* // TODO: find place for docs
@@ -71,14 +98,26 @@ macro_rules! impl_add_to_linker_for_funcs {
                                 Some(Extern::Memory(m)) => m,
                                 _ => return Err(wasmi::Error::new(String::from("missing required WASI memory export"))),
                             };
-                            let(memory, ctx) = memory.data_and_store_mut(&mut caller);
-                            let ctx = wasi_ctx(ctx);
-                            let memory = WasmiGuestMemory::new(memory);
-                            match wasi_common::snapshots::preview_1::wasi_snapshot_preview1::$fname(ctx, &memory, $($arg,)*).await {
+                            // Note: wiggle borrows the guest memory as slices which is
+                            //       impossible for shared memories accessed by other threads.
+                            let (memory, ctx) = memory
+                                .try_data_and_store_mut(&mut caller)
+                                .map_err(|_| wasmi::Error::new(String::from("shared linear memories are unsupported, use `wasmi_wasi::preview1::add_to_linker` instead")))?;
+                            if let Some(tracer) = tracer(ctx) {
+                                let args = [$( (stringify!($arg), TraceArg::from($arg)) ),*];
+                                tracer.enter(stringify!($fname), &decode_args(&GuestMemory::new(memory), &args));
+                            }
+                            let wasi = wasi_ctx(ctx);
+                            let result = match wasi_common::snapshots::preview_1::wasi_snapshot_preview1::$fname(wasi, &WasmiGuestMemory::new(memory), $($arg,)*).await {
                                 Ok(r) => Ok(<$ret>::from(r)),
                                 Err(wiggle::Trap::String(err)) => Err(wasmi::Error::new(err)),
                                 Err(wiggle::Trap::I32Exit(i)) => Err(wasmi::Error::i32_exit(i)),
+                            };
+                            if let Some(tracer) = tracer(ctx) {
+                                let errno = result.as_ref().ok().and_then(TraceErrno::errno);
+                                tracer.exit(stringify!($fname), errno);
                             }
+                            result
                         };
                         run_in_dummy_executor(result)?
                     }
@@ -89,6 +128,24 @@ macro_rules! impl_add_to_linker_for_funcs {
     }
 }
 
+/// The return value of a WASI function reported to a [`WasiTracer`].
+trait TraceErrno {
+    /// Returns the [`Errno`] of the return value if any.
+    fn errno(&self) -> Option<Errno>;
+}
+
+impl TraceErrno for i32 {
+    fn errno(&self) -> Option<Errno> {
+        u16::try_from(*self).ok().and_then(Errno::from_raw)
+    }
+}
+
+impl TraceErrno for () {
+    fn errno(&self) -> Option<Errno> {
+        None
+    }
+}
+
 impl_add_to_linker_for_funcs!(
     /// Read command-line argument data.
     ///
@@ -103,8 +160,8 @@ impl_add_to_linker_for_funcs!(
     /// # Note
     ///
     /// Returns the number of arguments and the size of the argument string data, or an error.
-    /// Note that `offset0` and `offset1` are offsets into memory where the two results are stored
-    fn args_sizes_get(offset0: i32, offset1: i32) -> i32;
+    /// Note that `argc` and `argv_buf_size` are offsets into memory where the two results are stored
+    fn args_sizes_get(argc: i32, argv_buf_size: i32) -> i32;
 
     /// Read environment variable data.
     ///
@@ -119,8 +176,8 @@ impl_add_to_linker_for_funcs!(
     /// # Note
     ///
     /// Returns the number of environment variable arguments and the size of the environment variable data.
-    /// Note that `offset0` and `offset1` are offsets into memory where the two results are stored.
-    fn environ_sizes_get(offset0: i32, offset1: i32) -> i32;
+    /// Note that `environc` and `environ_buf_size` are offsets into memory where the two results are stored.
+    fn environ_sizes_get(environc: i32, environ_buf_size: i32) -> i32;
 
     /// Return the resolution of a clock.
     ///
@@ -130,15 +187,15 @@ impl_add_to_linker_for_funcs!(
     /// # Note
     ///
     /// This is similar to `clock_getres` in POSIX.
-    /// The `id` is the `ClockID` and `offset0` is the offset into memory where the result is written.
-    fn clock_res_get(id: i32, offset0: i32) -> i32;
+    /// The `id` is the `ClockID` and `resolution` is the offset into memory where the result is written.
+    fn clock_res_get(id: i32, resolution: i32) -> i32;
 
     /// Return the time value of a clock.
     ///
     /// # Note
     ///
-    /// This is similar to `clock_gettime` in POSIX. The result is stored in `offset0`.
-    fn clock_time_get(id: i32, precision: i64, offset0: i32) -> i32;
+    /// This is similar to `clock_gettime` in POSIX. The result is stored in `time`.
+    fn clock_time_get(id: i32, precision: i64, time: i32) -> i32;
 
     /// Provide file advisory information on a file descriptor.
     ///
@@ -198,8 +255,8 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset0`: The offset into memory where the result is written to.
-    fn fd_fdstat_get(fd: i32, offset0: i32) -> i32;
+    /// - `stat`: The offset into memory where the result is written to.
+    fn fd_fdstat_get(fd: i32, stat: i32) -> i32;
 
     /// Adjust the flags associated with a file descriptor.
     ///
@@ -223,17 +280,17 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `fs_rights_base`: The desired rights of the file descriptor.
-    /// - `fs_rights_inheriting`: The inherited rights.
-    fn fd_fdstat_set_rights(fd: i32, fs_rights_base: i64, fs_rights_inheriting: i64) -> i32;
+    /// - `base`: The desired rights of the file descriptor.
+    /// - `inheriting`: The inherited rights.
+    fn fd_fdstat_set_rights(fd: i32, base: i64, inheriting: i64) -> i32;
 
     /// Returns the attributes of an open file.
     ///
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset0`: The offset into memory where the buffer of the file's attributes is written.
-    fn fd_filestat_get(fd: i32, offset0: i32) -> i32;
+    /// - `stat`: The offset into memory where the buffer of the file's attributes is written.
+    fn fd_filestat_get(fd: i32, stat: i32) -> i32;
 
     /// Adjust the size of an open file.
     ///
@@ -271,19 +328,19 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `iov_buf`, `iov_buf_len`: Used to create `iovec`,
-    ///                             which is the list of scatter/gather vectors in which to store data.
+    /// - `iovs`, `iovs_len`: Used to create `iovec`,
+    ///                       which is the list of scatter/gather vectors in which to store data.
     /// - `offset`: The offset within the file at which to read.
-    /// - `offsset0`: The size of bytes read is written here.
-    fn fd_pread(fd: i32, iov_buf: i32, iov_buf_len: i32, offset: i64, offset0: i32) -> i32;
+    /// - `nread`: The size of bytes read is written here.
+    fn fd_pread(fd: i32, iovs: i32, iovs_len: i32, offset: i64, nread: i32) -> i32;
 
     /// Return a description of the given preopened file descriptor.
     ///
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset0`: The location in the memory where the buffer that stores the description is written.
-    fn fd_prestat_get(fd: i32, offset0: i32) -> i32;
+    /// - `prestat`: The location in the memory where the buffer that stores the description is written.
+    fn fd_prestat_get(fd: i32, prestat: i32) -> i32;
 
     /// Return a description of the given preopened file descriptor.
     ///
@@ -303,21 +360,20 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: file descriptor
-    /// - `ciov_buf`, `ciov_buf_len`: Used to create `ciovec`,
-    ///                               which is the list of scatter/gather vectors from which to retrieve data.
+    /// - `iovs`, `iovs_len`: Used to create `ciovec`,
+    ///                       which is the list of scatter/gather vectors from which to retrieve data.
     /// - `offset`: The offset within the file at which to write.
-    /// - `offsset0`: The size of bytes written is written here.
-    fn fd_pwrite(fd: i32, ciov_buf: i32, ciov_buf_len: i32, offset: i64, offset0: i32) -> i32;
+    /// - `nwritten`: The size of bytes written is written here.
+    fn fd_pwrite(fd: i32, iovs: i32, iovs_len: i32, offset: i64, nwritten: i32) -> i32;
 
     /// Read from a file descriptor. Note: This is similar to readv in POSIX.
     ///
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `iov_buf`, `iov_buf_len`: used to create iovec, which is the list of scatter/gather vectors in which to store data.
-    /// - `offset`: The offset within the file at which to read.
-    /// - `offsset0`: size of bytes read is written here
-    fn fd_read(fd: i32, iov_buf: i32, iov_buf_len: i32, offset1: i32) -> i32;
+    /// - `iovs`, `iovs_len`: used to create iovec, which is the list of scatter/gather vectors in which to store data.
+    /// - `nread`: size of bytes read is written here
+    fn fd_read(fd: i32, iovs: i32, iovs_len: i32, nread: i32) -> i32;
 
     /// Read directory entries from a directory.
     ///
@@ -337,9 +393,9 @@ impl_add_to_linker_for_funcs!(
     /// - `buf`: The buffer where directory entries are stored.
     /// - `buf_len`: The length of the `buf` buffer.
     /// - `cookie`: The location within the directory to start reading.
-    /// - `offset0`: The result, i.e. the number of bytes stored in the read buffer, is stored at this offset in memory
+    /// - `bufused`: The result, i.e. the number of bytes stored in the read buffer, is stored at this offset in memory
     ///              if less than the size of the read buffer, the end of the directory has been reached.
-    fn fd_readdir(fd: i32, buf: i32, buf_len: i32, cookie: i64, offset0: i32) -> i32;
+    fn fd_readdir(fd: i32, buf: i32, buf_len: i32, cookie: i64, bufused: i32) -> i32;
 
     /// Atomically replace a file descriptor by renumbering another file descriptor.
     ///
@@ -369,9 +425,9 @@ impl_add_to_linker_for_funcs!(
     /// - `fd`: The file descriptor.
     /// - `offset`: The number of bytes to move.
     /// - `whence`: The base from which the offset is relative
-    /// - `offset0`: The memory location to which the new offset of the file descriptor,
-    ///              relative to the start of the file is stored.
-    fn fd_seek(fd: i32, offset: i64, whence: i32, offset0: i32) -> i32;
+    /// - `newoffset`: The memory location to which the new offset of the file descriptor,
+    ///                relative to the start of the file is stored.
+    fn fd_seek(fd: i32, offset: i64, whence: i32, newoffset: i32) -> i32;
 
     /// Synchronize the data and metadata of a file to disk.
     ///
@@ -393,9 +449,9 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset0`: Offset into the memory where result is stored upon success.
+    /// - `offset`: Offset into the memory where result is stored upon success.
     /// - `result`: The current offset of the file descriptor, relative to the start of the file.
-    fn fd_tell(fd: i32, offset0: i32) -> i32;
+    fn fd_tell(fd: i32, offset: i32) -> i32;
 
     /// Write to a file descriptor.
     ///
@@ -406,10 +462,10 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `ciov_buf`, `ciov_buf_len`: used to create ciovec, which is the list of
-    ///                               scatter/gather vectors from which to retrieve data.
-    /// - `offset0`: The offset into the memory where result (size written) is stored
-    fn fd_write(fd: i32, ciov_buf: i32, ciov_buf_len: i32, offset0: i32) -> i32;
+    /// - `iovs`, `iovs_len`: used to create ciovec, which is the list of
+    ///                       scatter/gather vectors from which to retrieve data.
+    /// - `nwritten`: The offset into the memory where result (size written) is stored
+    fn fd_write(fd: i32, iovs: i32, iovs_len: i32, nwritten: i32) -> i32;
 
     /// Create a directory.
     ///
@@ -420,9 +476,9 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path string at which to create the directory.
-    fn path_create_directory(fd: i32, offset: i32, length: i32) -> i32;
+    fn path_create_directory(fd: i32, path: i32, path_len: i32) -> i32;
 
     /// Return the attributes of a file or directory.
     ///
@@ -434,10 +490,10 @@ impl_add_to_linker_for_funcs!(
     ///
     /// - `fd`: The file descriptor.
     /// - `flags`: Flags determining the method of how the path is resolved.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path string of the file or directory to inspect.
-    /// - `offset0`: The buffer where the file's attributes are stored.
-    fn path_filestat_get(fd: i32, flags: i32, offset: i32, length: i32, offset0: i32) -> i32;
+    /// - `stat`: The buffer where the file's attributes are stored.
+    fn path_filestat_get(fd: i32, flags: i32, path: i32, path_len: i32, stat: i32) -> i32;
 
     /// Adjust the timestamps of a file or directory.
     ///
@@ -449,7 +505,7 @@ impl_add_to_linker_for_funcs!(
     ///
     /// - `fd`: The file descriptor.
     /// - `flags`: Flags determining the method of how the path is resolved.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path string of the file or directory to operate on.
     /// - `atim`: The desired values of the data access timestamp.
     /// - `mtim`: The desired values of the data modification timestamp.
@@ -457,8 +513,8 @@ impl_add_to_linker_for_funcs!(
     fn path_filestat_set_times(
         fd: i32,
         flags: i32,
-        offset: i32,
-        length: i32,
+        path: i32,
+        path_len: i32,
         atim: i64,
         mtim: i64,
         fst_flags: i32,
@@ -474,20 +530,20 @@ impl_add_to_linker_for_funcs!(
     ///
     /// - `old_fd`: file descriptor
     /// - `old_flags`: Flags determining the method of how the path is resolved.
-    /// - `old_offset`, `old_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `old_path`, `old_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the path string source path from which to link.
     /// - `new_fd`: The working directory at which the resolution of the new path starts.
-    /// - `new_offset`, `new_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `new_path`, `new_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the path string, i.e. ehe destination path at
     ///                               which to create the hard link.
     fn path_link(
         old_fd: i32,
         old_flags: i32,
-        old_offset: i32,
-        old_length: i32,
+        old_path: i32,
+        old_path_len: i32,
         new_fd: i32,
-        new_offset: i32,
-        new_length: i32,
+        new_path: i32,
+        new_path_len: i32,
     ) -> i32;
 
     /// Open a file or directory.
@@ -504,25 +560,25 @@ impl_add_to_linker_for_funcs!(
     ///
     /// - `fd`: The file descriptor.
     /// - `dirflags`: Flags determining the method of how the path is resolved.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the relative path of the file or directory to open,
     ///                       relative to the `path_open::fd` directory.
     /// - `oflags`: The method by which to open the file.
-    /// - `fs_rights_base`: The initial rights of the newly created file descriptor
-    /// - `fs_rights_inheriting`: The rights to inherit.
+    /// - `rights_base`: The initial rights of the newly created file descriptor
+    /// - `rights_inheriting`: The rights to inherit.
     /// - `fdflags`: The file descriptor flags.
-    /// - `offset0`: The offset into memory where result is stored.
-    ///              The result is the file descriptor of the file that has been opened.
+    /// - `opened_fd`: The offset into memory where result is stored.
+    ///                The result is the file descriptor of the file that has been opened.
     fn path_open(
         fd: i32,
         dirflags: i32,
-        offset: i32,
-        length: i32,
+        path: i32,
+        path_len: i32,
         oflags: i32,
-        fs_rights_base: i64,
-        fdflags: i64,
-        fs_rights_inheriting: i32,
-        offset0: i32,
+        rights_base: i64,
+        rights_inheriting: i64,
+        fdflags: i32,
+        opened_fd: i32,
     ) -> i32;
 
     /// Read the contents of a symbolic link.
@@ -534,19 +590,19 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path of the symbolic link from which to read.
     /// - `buf`: The buffer to which to write the contents of the symbolic link.
     /// - `buf_len`: The length of the `buf` buffer.
-    /// - `offset0`: The offset into memory where result is stored.
+    /// - `bufused`: The offset into memory where result is stored.
     ///              The result is the number of bytes placed in the buffer.
     fn path_readlink(
         fd: i32,
-        offset: i32,
-        length: i32,
+        path: i32,
+        path_len: i32,
         buf: i32,
         buf_len: i32,
-        offset0: i32,
+        bufused: i32,
     ) -> i32;
 
     /// Remove a directory.
@@ -559,9 +615,9 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path to the directory to remove.
-    fn path_remove_directory(fd: i32, offset: i32, length: i32) -> i32;
+    fn path_remove_directory(fd: i32, path: i32, path_len: i32) -> i32;
 
     /// Rename a file or directory.
     ///
@@ -573,18 +629,18 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `old_offset`, `old_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `old_path`, `old_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the source path of the file or directory to rename.
     /// - `new_fd`: The working directory at which the resolution of the new path starts.
-    /// - `new_offset`, `new_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `new_path`, `new_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the destination path to which to rename the file or directory.
     fn path_rename(
         fd: i32,
-        old_offset: i32,
-        old_length: i32,
+        old_path: i32,
+        old_path_len: i32,
         new_fd: i32,
-        new_offset: i32,
-        new_length: i32,
+        new_path: i32,
+        new_path_len: i32,
     ) -> i32;
 
     /// Create a symbolic link.
@@ -595,17 +651,17 @@ impl_add_to_linker_for_funcs!(
     ///
     /// # Parameters
     ///
-    /// - `old_offset`, `old_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `old_path`, `old_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the path to the contents of the symbolic link.
     /// - `fd`: The file descriptor.
-    /// - `new_offset`, `new_length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `new_path`, `new_path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                               This pointer references the destination path at which to create the symbolic link.
     fn path_symlink(
-        old_offset: i32,
-        old_length: i32,
+        old_path: i32,
+        old_path_len: i32,
         fd: i32,
-        new_offset: i32,
-        new_length: i32,
+        new_path: i32,
+        new_path_len: i32,
     ) -> i32;
 
     /// Unlink a file.
@@ -618,19 +674,19 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `offset`, `length`: The offset/length pair used to create a guest pointer into host memory.
+    /// - `path`, `path_len`: The offset/length pair used to create a guest pointer into host memory.
     ///                       This pointer references the path to a file to unlink.
-    fn path_unlink_file(fd: i32, offset: i32, length: i32) -> i32;
+    fn path_unlink_file(fd: i32, path: i32, path_len: i32) -> i32;
 
     /// Concurrently poll for the occurrence of a set of events.
     ///
     /// # Parameters
     ///
-    /// - `in_`: The events to which to subscribe.
-    /// - `out`: The events that have occurred.
+    /// - `subscriptions`: The events to which to subscribe.
+    /// - `events`: The events that have occurred.
     /// - `nsubscriptions`: Both the number of subscriptions and events.
-    /// - `offset0`: The offset into memory where the number of events is stored.
-    fn poll_oneoff(in_: i32, out: i32, nsubscriptions: i32, offset0: i32) -> i32;
+    /// - `nevents`: The offset into memory where the number of events is stored.
+    fn poll_oneoff(subscriptions: i32, events: i32, nsubscriptions: i32, nevents: i32) -> i32;
 
     /// Terminate the process normally.
     ///
@@ -642,7 +698,7 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `rval`: The exit code returned by the process.
-    fn proc_exit(rval: i32) -> ();
+    fn proc_exit(status: i32) -> ();
 
     /// Send a signal to the process of the calling thread.
     /// Note: This is similar to `raise` in POSIX.
@@ -676,8 +732,8 @@ impl_add_to_linker_for_funcs!(
     ///
     /// - `fd`: The listening socket.
     /// - `flags`: The desired values of the file descriptor flags.
-    /// - `offset0`: The offset into memory where the new socket connection `fd` is stored.
-    fn sock_accept(fd: i32, flags: i32, offset0: i32) -> i32;
+    /// - `opened_fd`: The offset into memory where the new socket connection `fd` is stored.
+    fn sock_accept(fd: i32, flags: i32, opened_fd: i32) -> i32;
 
     /// Receive a message from a socket.
     ///
@@ -689,18 +745,18 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `iov_buf`, `iov_buf_len`: Used to create `iovec`, which is the list of scatter/gather
+    /// - `ri_data`, `ri_data_len`: Used to create `iovec`, which is the list of scatter/gather
     ///                             vectors in which to store data.
     /// - `ri_flags`: The message flags.
-    /// - `offset0`, `offset1`: The offset into memory where the number of
+    /// - `ro_datalen`, `ro_flags`: The offset into memory where the number of
     ///                         bytes in `ri_data` and message flags are stored.
     fn sock_recv(
         fd: i32,
-        iov_buf: i32,
-        iov_buf_len: i32,
+        ri_data: i32,
+        ri_data_len: i32,
         ri_flags: i32,
-        offset0: i32,
-        offset1: i32,
+        ro_datalen: i32,
+        ro_flags: i32,
     ) -> i32;
 
     /// Send a message on a socket.
@@ -713,11 +769,11 @@ impl_add_to_linker_for_funcs!(
     /// # Parameters
     ///
     /// - `fd`: The file descriptor.
-    /// - `ciov_buf`, `ciov_buf_len`: Used to create ciovec, which is the list of
+    /// - `si_data`, `si_data_len`: Used to create ciovec, which is the list of
     ///                               scatter/gather vectors from which to retrieve data.
     /// - `si_flags`: The message flags.
-    /// - `offset0`: The offset into memory where number of bytes transmitted is stored.
-    fn sock_send(fd: i32, ciov_buf: i32, ciov_buf_len: i32, si_flags: i32, offset0: i32) -> i32;
+    /// - `so_datalen`: The offset into memory where number of bytes transmitted is stored.
+    fn sock_send(fd: i32, si_data: i32, si_data_len: i32, si_flags: i32, so_datalen: i32) -> i32;
 
     /// Shut down socket send and receive channels.
     ///
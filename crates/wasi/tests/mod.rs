@@ -1 +1,3 @@
+#![cfg(feature = "sync")]
+
 mod wasi_wat;
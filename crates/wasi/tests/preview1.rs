@@ -0,0 +1,693 @@
+use std::sync::{Arc, Mutex};
+use wasmi::{Config, Engine, Linker, Module, Store};
+use wasmi_wasi::preview1::{
+    self,
+    ClockId,
+    Errno,
+    FileType,
+    MemFs,
+    OpenOptions,
+    Opened,
+    ReadPipe,
+    SeededRandom,
+    VirtualClocks,
+    WasiClocks,
+    WasiCtx,
+    WasiCtxBuilder,
+    WasiDir,
+    WasiFile,
+    WasiRandom,
+    WasiTracer,
+    Whence,
+    WritePipe,
+};
+
+/// A [`WasiFile`] capturing everything written to it.
+#[derive(Debug, Default, Clone)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl WasiFile for Capture {
+    fn filetype(&self) -> FileType {
+        FileType::CharacterDevice
+    }
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Errno> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+}
+
+/// Instantiates the `.wat` encoded `wat` with `wasi` and calls its `_start` function.
+fn run(wat: &str, wasi: WasiCtx) -> (Store<WasiCtx>, Result<(), wasmi::Error>) {
+    let wasm = wat::parse_str(wat).unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, wasi);
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    preview1::add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let start = instance.get_typed_func::<(), ()>(&store, "_start").unwrap();
+    let result = start.call(&mut store, ());
+    (store, result)
+}
+
+#[test]
+fn hello_world() {
+    let stdout = Capture::default();
+    let wasi = WasiCtxBuilder::new().stdout(stdout.clone()).build();
+    let (_, result) = run(include_str!("wat/hello_world.wat"), wasi);
+    result.unwrap();
+    assert_eq!(stdout.contents(), "hello world\n");
+}
+
+#[test]
+fn pipes() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "fd_read"
+                (func $fd_read (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (func (export "_start")
+                ;; Echo stdin to stdout twice.
+                (i32.store (i32.const 0) (i32.const 64))
+                (i32.store (i32.const 4) (i32.const 64))
+                (drop (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+                (i32.store (i32.const 4) (i32.load (i32.const 8)))
+                (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 12)))
+                (call $proc_exit
+                    (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 12)))
+            )
+        )
+    "#;
+    let stdin = ReadPipe::with_limit(8);
+    assert_eq!(stdin.push(b"hello"), 5);
+    assert_eq!(stdin.push(b" world"), 3);
+    let stdout = WritePipe::with_limit(8);
+    let wasi = WasiCtxBuilder::new()
+        .stdin(stdin.clone())
+        .stdout(stdout.clone())
+        .build();
+    let (_, result) = run(wasm, wasi);
+    assert_eq!(
+        result.unwrap_err().i32_exit_status(),
+        Some(Errno::Fbig as i32)
+    );
+    assert!(stdin.is_empty());
+    assert_eq!(stdout.take(), b"hello wo");
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn shared_memory() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "fd_read"
+                (func $fd_read (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1 1 shared)
+            (func (export "_start") (result i32)
+                ;; Echo stdin to stdout and return the number of bytes written.
+                (i32.store (i32.const 0) (i32.const 64))
+                (i32.store (i32.const 4) (i32.const 64))
+                (drop (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+                (i32.store (i32.const 4) (i32.load (i32.const 8)))
+                (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 12)))
+                (i32.load (i32.const 12))
+            )
+        )
+    "#;
+    let stdin = ReadPipe::default();
+    stdin.push(b"hello world");
+    let stdout = Capture::default();
+    let wasi = WasiCtxBuilder::new()
+        .stdin(stdin)
+        .stdout(stdout.clone())
+        .build();
+    let mut config = Config::default();
+    config.wasm_threads(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wat::parse_str(wasm).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, wasi);
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    preview1::add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let start = instance
+        .get_typed_func::<(), i32>(&store, "_start")
+        .unwrap();
+    assert_eq!(start.call(&mut store, ()).unwrap(), 11);
+    assert_eq!(stdout.contents(), "hello world");
+}
+
+#[test]
+fn args_and_environ_sizes() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "args_sizes_get"
+                (func $args_sizes_get (param i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "environ_sizes_get"
+                (func $environ_sizes_get (param i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (func (export "_start")
+                (drop (call $args_sizes_get (i32.const 0) (i32.const 4)))
+                (drop (call $environ_sizes_get (i32.const 8) (i32.const 12)))
+                ;; exit with argc * 1000 + argv_buf_size * 100 + environc * 10 + environ_buf_size
+                (call $proc_exit
+                    (i32.add
+                        (i32.add
+                            (i32.mul (i32.load (i32.const 0)) (i32.const 1000))
+                            (i32.mul (i32.load (i32.const 4)) (i32.const 100)))
+                        (i32.add
+                            (i32.mul (i32.load (i32.const 8)) (i32.const 10))
+                            (i32.load (i32.const 12)))))
+            )
+        )
+    "#;
+    let wasi = WasiCtxBuilder::new()
+        .args(["a", "bc"])
+        .env("K", "V")
+        .build();
+    let (_, result) = run(wasm, wasi);
+    // argc = 2, argv_buf_size = 5, environc = 1, environ_buf_size = 4
+    assert_eq!(
+        result.unwrap_err().i32_exit_status(),
+        Some(2000 + 500 + 10 + 4)
+    );
+}
+
+#[test]
+fn path_open_without_preopen_is_bad_fd() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "file.txt")
+            (func (export "_start")
+                (call $proc_exit
+                    (call $path_open
+                        (i32.const 3) (i32.const 0) (i32.const 16) (i32.const 8)
+                        (i32.const 0) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+            )
+        )
+    "#;
+    let wasi = WasiCtxBuilder::new().build();
+    let (_, result) = run(wasm, wasi);
+    // There is no preopened directory so file descriptor 3 is invalid.
+    assert_eq!(
+        result.unwrap_err().i32_exit_status(),
+        Some(Errno::Badf as i32)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn std_dir_write_and_read() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_close" (func $fd_close (param i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "out.txt")
+            (data (i32.const 32) "../escape.txt")
+            (data (i32.const 64) "written by guest")
+            (func (export "_start")
+                (local $errno i32)
+                ;; Escaping the preopened directory must fail.
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 0) (i32.const 32) (i32.const 13)
+                        (i32.const 1) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (i32.ne (local.get $errno) (i32.const 76))
+                    (then (call $proc_exit (i32.add (i32.const 100) (local.get $errno)))))
+                ;; Create `out.txt` and write to it.
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 0) (i32.const 16) (i32.const 7)
+                        (i32.const 1) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 200) (local.get $errno)))))
+                (i32.store (i32.const 4) (i32.const 64))
+                (i32.store (i32.const 8) (i32.const 16))
+                (drop (call $fd_write (i32.load (i32.const 0)) (i32.const 4) (i32.const 1) (i32.const 12)))
+                (drop (call $fd_close (i32.load (i32.const 0))))
+            )
+        )
+    "#;
+    let dir = std::env::temp_dir().join(format!("wasmi_wasi_preview1_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let wasi = WasiCtxBuilder::new()
+        .preopened_dir(preview1::StdDir::open(&dir).unwrap(), "/")
+        .build();
+    let (_, result) = run(wasm, wasi);
+    let contents = std::fs::read_to_string(dir.join("out.txt"));
+    std::fs::remove_dir_all(&dir).unwrap();
+    result.unwrap();
+    assert_eq!(contents.unwrap(), "written by guest");
+}
+
+#[test]
+#[cfg(all(feature = "std", unix))]
+fn std_dir_dangling_symlinks_do_not_escape() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_symlink"
+                (func $path_symlink (param i32 i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "../escaped")
+            (data (i32.const 32) "link")
+            (data (i32.const 48) "inside.txt")
+            (data (i32.const 64) "ok")
+            (func (export "_start")
+                (local $errno i32)
+                ;; Create a dangling symbolic link pointing outside of the preopened directory.
+                (local.set $errno
+                    (call $path_symlink
+                        (i32.const 16) (i32.const 10) (i32.const 3) (i32.const 32) (i32.const 4)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 100) (local.get $errno)))))
+                ;; Creating a file through the dangling symbolic link must fail.
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 1) (i32.const 32) (i32.const 4)
+                        (i32.const 1) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (i32.ne (local.get $errno) (i32.const 76))
+                    (then (call $proc_exit (i32.add (i32.const 200) (local.get $errno)))))
+                ;; Dangling symbolic links within the preopened directory can still be followed.
+                (local.set $errno
+                    (call $path_symlink
+                        (i32.const 48) (i32.const 10) (i32.const 3) (i32.const 64) (i32.const 2)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 300) (local.get $errno)))))
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 1) (i32.const 64) (i32.const 2)
+                        (i32.const 1) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 400) (local.get $errno)))))
+            )
+        )
+    "#;
+    let sandbox = std::env::temp_dir().join(format!("wasmi_wasi_sandbox_{}", std::process::id()));
+    let inner = sandbox.join("inner");
+    std::fs::create_dir_all(&inner).unwrap();
+    let wasi = WasiCtxBuilder::new()
+        .preopened_dir(preview1::StdDir::open(&inner).unwrap(), "/")
+        .build();
+    let (_, result) = run(wasm, wasi);
+    let escaped = sandbox.join("escaped").exists();
+    let inside = inner.join("inside.txt").exists();
+    std::fs::remove_dir_all(&sandbox).unwrap();
+    result.unwrap();
+    assert!(!escaped);
+    assert!(inside);
+}
+
+#[test]
+#[cfg(all(feature = "std", unix))]
+fn std_dir_set_times_does_not_follow_escaping_symlinks() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_symlink"
+                (func $path_symlink (param i32 i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "path_filestat_set_times"
+                (func $path_filestat_set_times (param i32 i32 i32 i32 i64 i64 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "../outside")
+            (data (i32.const 32) "x")
+            (func (export "_start")
+                (local $errno i32)
+                (local.set $errno
+                    (call $path_symlink
+                        (i32.const 16) (i32.const 10) (i32.const 3) (i32.const 32) (i32.const 1)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 100) (local.get $errno)))))
+                ;; Setting the times of the symbolic link itself must not touch its target.
+                (local.set $errno
+                    (call $path_filestat_set_times
+                        (i32.const 3) (i32.const 0) (i32.const 32) (i32.const 1)
+                        (i64.const 0) (i64.const 0) (i32.const 5)))
+                (if (i32.ne (local.get $errno) (i32.const 32))
+                    (then (call $proc_exit (i32.add (i32.const 200) (local.get $errno)))))
+                ;; Following the symbolic link must fail since it escapes the directory.
+                (local.set $errno
+                    (call $path_filestat_set_times
+                        (i32.const 3) (i32.const 1) (i32.const 32) (i32.const 1)
+                        (i64.const 0) (i64.const 0) (i32.const 5)))
+                (if (i32.ne (local.get $errno) (i32.const 76))
+                    (then (call $proc_exit (i32.add (i32.const 300) (local.get $errno)))))
+            )
+        )
+    "#;
+    let sandbox = std::env::temp_dir().join(format!("wasmi_wasi_set_times_{}", std::process::id()));
+    let inner = sandbox.join("inner");
+    std::fs::create_dir_all(&inner).unwrap();
+    let outside = sandbox.join("outside");
+    std::fs::write(&outside, "outside").unwrap();
+    let modified = std::fs::metadata(&outside).unwrap().modified().unwrap();
+    let wasi = WasiCtxBuilder::new()
+        .preopened_dir(preview1::StdDir::open(&inner).unwrap(), "/")
+        .build();
+    let (_, result) = run(wasm, wasi);
+    let modified_after = std::fs::metadata(&outside).unwrap().modified().unwrap();
+    std::fs::remove_dir_all(&sandbox).unwrap();
+    result.unwrap();
+    assert_eq!(modified, modified_after);
+}
+
+#[test]
+fn memfs_guest_read_write() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_read"
+                (func $fd_read (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "link/in.txt")
+            (data (i32.const 32) "out/result.txt")
+            (func (export "_start")
+                (local $errno i32)
+                ;; Open `link/in.txt` via the symbolic link to `data` and read it.
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 1) (i32.const 16) (i32.const 11)
+                        (i32.const 0) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 100) (local.get $errno)))))
+                (i32.store (i32.const 4) (i32.const 64))
+                (i32.store (i32.const 8) (i32.const 64))
+                (drop (call $fd_read (i32.load (i32.const 0)) (i32.const 4) (i32.const 1) (i32.const 12)))
+                ;; Create `out/result.txt` and write the contents read before.
+                (local.set $errno
+                    (call $path_open
+                        (i32.const 3) (i32.const 0) (i32.const 32) (i32.const 14)
+                        (i32.const 1) (i64.const -1) (i64.const -1) (i32.const 0)
+                        (i32.const 0)))
+                (if (local.get $errno)
+                    (then (call $proc_exit (i32.add (i32.const 200) (local.get $errno)))))
+                (i32.store (i32.const 8) (i32.load (i32.const 12)))
+                (drop (call $fd_write (i32.load (i32.const 0)) (i32.const 4) (i32.const 1) (i32.const 12)))
+            )
+        )
+    "#;
+    let fs = MemFs::from_map([("data/in.txt", "from the host")]).unwrap();
+    fs.create_dir_all("out").unwrap();
+    fs.create_symlink("data", "link").unwrap();
+    let wasi = WasiCtxBuilder::new().preopened_dir(fs.clone(), "/").build();
+    let (store, result) = run(wasm, wasi);
+    result.unwrap();
+    drop(store);
+    assert_eq!(fs.read_file("/out/result.txt").unwrap(), b"from the host");
+    assert_eq!(
+        fs.files().into_keys().collect::<Vec<_>>(),
+        ["data/in.txt", "out/result.txt"]
+    );
+}
+
+#[test]
+fn memfs_symlinks_do_not_escape() {
+    let mut fs = MemFs::new();
+    fs.write_file("dir/file", "contents").unwrap();
+    fs.create_symlink("..", "dir/parent").unwrap();
+    fs.create_symlink("../..", "dir/escape").unwrap();
+    fs.create_symlink("/dir/file", "absolute").unwrap();
+    fs.create_symlink("loop", "loop").unwrap();
+    let options = OpenOptions {
+        read: true,
+        follow: true,
+        ..OpenOptions::default()
+    };
+    assert_eq!(
+        fs.open("dir/escape/x", &options).unwrap_err(),
+        Errno::Notcapable
+    );
+    assert_eq!(
+        fs.open("absolute", &options).unwrap_err(),
+        Errno::Notcapable
+    );
+    assert_eq!(fs.open("loop", &options).unwrap_err(), Errno::Loop);
+    match fs.open("dir/parent/dir", &options).unwrap() {
+        Opened::Dir(path) => assert_eq!(path, "dir"),
+        Opened::File(_) => panic!("expected a directory"),
+    }
+}
+
+#[test]
+fn memfs_file_operations() {
+    let mut fs = MemFs::from_map([("file", "0123456789")]).unwrap();
+    let options = OpenOptions {
+        read: true,
+        write: true,
+        ..OpenOptions::default()
+    };
+    let Opened::File(mut file) = fs.open("file", &options).unwrap() else {
+        panic!("expected a file")
+    };
+    let mut buf = [0x00; 4];
+    assert_eq!(file.seek(-4, Whence::End).unwrap(), 6);
+    assert_eq!(file.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf, b"6789");
+    assert_eq!(file.pread(&mut buf, 2).unwrap(), 4);
+    assert_eq!(&buf, b"2345");
+    assert_eq!(file.pwrite(b"ab", 12).unwrap(), 2);
+    assert_eq!(file.write(b"XY").unwrap(), 2);
+    assert_eq!(file.seek(0, Whence::Cur).unwrap(), 12);
+    assert_eq!(fs.read_file("file").unwrap(), b"0123456789XYab");
+    // Unlinked files stay accessible while they are open.
+    fs.link("file", "hardlink", false).unwrap();
+    fs.unlink_file("file").unwrap();
+    fs.unlink_file("hardlink").unwrap();
+    assert!(!fs.exists("hardlink"));
+    assert_eq!(file.pread(&mut buf, 0).unwrap(), 4);
+    assert_eq!(&buf, b"0123");
+    assert_eq!(file.stat().unwrap().nlink, 0);
+}
+
+#[test]
+fn memfs_rename_and_remove() {
+    let mut fs = MemFs::from_map([("a/b/file", "x"), ("c/other", "y")]).unwrap();
+    assert_eq!(fs.rename("a", "a/b/a").unwrap_err(), Errno::Inval);
+    assert_eq!(fs.rename("a/b", "c").unwrap_err(), Errno::Notempty);
+    assert_eq!(fs.remove_dir("a").unwrap_err(), Errno::Notempty);
+    fs.rename("a/b/file", "c/other").unwrap();
+    fs.rename("a/b", "d").unwrap();
+    fs.remove_dir("a").unwrap();
+    assert_eq!(
+        fs.files().into_iter().collect::<Vec<_>>(),
+        [("c/other".into(), b"x".to_vec())]
+    );
+    let names = fs
+        .entries("/")
+        .unwrap()
+        .into_iter()
+        .map(|entry| (entry.name, entry.filetype))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("c".into(), FileType::Directory),
+            ("d".into(), FileType::Directory)
+        ]
+    );
+}
+
+/// Appends a tar entry with `path`, `typeflag`, `link` and `data` to `archive`.
+fn push_tar_entry(archive: &mut Vec<u8>, path: &str, typeflag: u8, link: &str, data: &[u8]) {
+    let mut header = [0x00_u8; 512];
+    header[..path.len()].copy_from_slice(path.as_bytes());
+    header[100..107].copy_from_slice(b"0000644");
+    header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+    header[136..147].copy_from_slice(b"00000000001");
+    header[148..156].fill(b' ');
+    header[156] = typeflag;
+    header[157..157 + link.len()].copy_from_slice(link.as_bytes());
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    let checksum = header.iter().map(|&byte| u32::from(byte)).sum::<u32>();
+    header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+    archive.extend_from_slice(&header);
+    archive.extend_from_slice(data);
+    archive.resize(archive.len().next_multiple_of(512), 0x00);
+}
+
+#[test]
+fn memfs_from_tar() {
+    let mut archive = Vec::new();
+    push_tar_entry(&mut archive, "etc/", b'5', "", b"");
+    push_tar_entry(&mut archive, "etc/motd", b'0', "", b"hello");
+    push_tar_entry(&mut archive, "etc/motd.link", b'2', "motd", b"");
+    push_tar_entry(&mut archive, "motd.hard", b'1', "etc/motd", b"");
+    archive.resize(archive.len() + 1024, 0x00);
+    let mut fs = MemFs::from_tar(&archive).unwrap();
+    assert_eq!(fs.read_file("etc/motd.link").unwrap(), b"hello");
+    assert_eq!(fs.read_link("etc/motd.link").unwrap(), "motd");
+    let stat = fs.metadata("motd.hard").unwrap();
+    assert_eq!(stat.nlink, 2);
+    assert_eq!(stat.mtim, 1_000_000_000);
+    archive[0] = b'x';
+    assert!(matches!(
+        MemFs::from_tar(&archive),
+        Err(preview1::TarError::InvalidHeader { offset: 0 })
+    ));
+    assert!(matches!(
+        MemFs::from_tar(&archive[512..1000]),
+        Err(preview1::TarError::UnexpectedEof)
+    ));
+}
+
+/// A [`WasiTracer`] recording all WASI calls.
+#[derive(Debug, Default, Clone)]
+struct Trace(Arc<Mutex<Vec<String>>>);
+
+impl WasiTracer for Trace {
+    fn enter(&mut self, name: &str, args: &str) {
+        self.0.lock().unwrap().push(format!("{name}({args})"));
+    }
+
+    fn exit(&mut self, _name: &str, errno: Option<Errno>) {
+        let mut calls = self.0.lock().unwrap();
+        let call = calls.last_mut().unwrap();
+        match errno {
+            Some(errno) => call.push_str(&format!(" = {errno:?}")),
+            None => call.push_str(" = ?"),
+        }
+    }
+}
+
+#[test]
+fn tracer() {
+    let wasm = r#"
+        (module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "missing.txt")
+            (data (i32.const 32) "hello")
+            (func (export "_start")
+                (drop
+                    (call $path_open
+                        (i32.const 3) (i32.const 1) (i32.const 16) (i32.const 11)
+                        (i32.const 0) (i64.const -1) (i64.const 0) (i32.const 0)
+                        (i32.const 0)))
+                (i32.store (i32.const 0) (i32.const 32))
+                (i32.store (i32.const 4) (i32.const 5))
+                (i32.store (i32.const 8) (i32.const 32))
+                (i32.store (i32.const 12) (i32.const 3))
+                (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 2) (i32.const 48)))
+                (call $proc_exit (i32.const 7))
+            )
+        )
+    "#;
+    let trace = Trace::default();
+    let wasi = WasiCtxBuilder::new()
+        .preopened_dir(MemFs::new(), "/")
+        .tracer(trace.clone())
+        .build();
+    let (_, result) = run(wasm, wasi);
+    assert_eq!(result.unwrap_err().i32_exit_status(), Some(7));
+    assert_eq!(
+        *trace.0.lock().unwrap(),
+        [
+            "path_open(fd=3, dirflags=1, path=\"missing.txt\", oflags=0, \
+             rights_base=0xffffffffffffffff, rights_inheriting=0x0, fdflags=0, opened_fd=0) = Noent",
+            "fd_write(fd=1, iovs=[5, 3], nwritten=48) = Success",
+            "proc_exit(status=7) = ?",
+        ]
+    );
+}
+
+#[test]
+fn deterministic_step_clocks_and_random() {
+    let mut clocks = VirtualClocks::with_step(10).epoch(1_000);
+    assert_eq!(clocks.now(ClockId::Realtime), Ok(1_000));
+    assert_eq!(clocks.now(ClockId::Monotonic), Ok(10));
+    clocks.sleep(100).unwrap();
+    assert_eq!(clocks.now(ClockId::Monotonic), Ok(120));
+    let [mut a, mut b] = [[0x00_u8; 13]; 2];
+    SeededRandom::new(42).fill(&mut a).unwrap();
+    SeededRandom::new(42).fill(&mut b).unwrap();
+    assert_eq!(a, b);
+    SeededRandom::new(43).fill(&mut b).unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn deterministic_fuel_clocks() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "wasi_snapshot_preview1" "clock_time_get"
+                (func $clock_time_get (param i32 i64 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "now") (result i64)
+                (drop (call $clock_time_get (i32.const 1) (i64.const 1) (i32.const 0)))
+                (i64.load (i32.const 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let wasi = WasiCtxBuilder::new()
+        .clocks(VirtualClocks::with_fuel_rate(3))
+        .build();
+    let mut store = Store::new(&engine, wasi);
+    store.add_fuel(1_000).unwrap();
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    preview1::add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let now = instance.get_typed_func::<(), i64>(&store, "now").unwrap();
+    let first = now.call(&mut store, ()).unwrap();
+    let second = now.call(&mut store, ()).unwrap();
+    let consumed = store.fuel_consumed().unwrap() as i64;
+    assert!(first > 0);
+    assert_eq!(second, 2 * first);
+    assert!(second <= consumed * 3);
+}
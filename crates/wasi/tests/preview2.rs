@@ -0,0 +1,301 @@
+#![cfg(feature = "preview2")]
+
+use wasmi::{
+    component::{Component, ComponentInstance, Linker, Val},
+    Engine,
+    Store,
+};
+use wasmi_wasi::{
+    preview1::{MemFs, WasiCtxBuilder, WritePipe},
+    preview2::{self, WasiCtx},
+};
+
+/// A core module providing the linear memory and a bump allocator for the component.
+const LIBC: &str = r#"
+    (core module $libc
+        (memory (export "memory") 1)
+        (global $bump (mut i32) (i32.const 1024))
+        (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+            (local $ptr i32)
+            (local.set $ptr
+                (i32.and
+                    (i32.add (global.get $bump) (i32.sub (local.get 2) (i32.const 1)))
+                    (i32.sub (i32.const 0) (local.get 2))
+                )
+            )
+            (global.set $bump (i32.add (local.get $ptr) (local.get 3)))
+            (local.get $ptr)
+        )
+    )
+    (core instance $libc (instantiate $libc))
+    (alias core export $libc "memory" (core memory $mem))
+    (alias core export $libc "realloc" (core func $realloc))
+"#;
+
+/// Instantiates the component `wat` with the WASI `preview2` host and `wasi`.
+///
+/// The `{libc}` placeholder in `wat` is replaced by [`LIBC`].
+fn instantiate(wat: &str, wasi: WasiCtx) -> (Store<WasiCtx>, ComponentInstance) {
+    let wasm = wat::parse_str(wat.replace("{libc}", LIBC)).unwrap();
+    let engine = Engine::default();
+    let component = Component::new(&engine, &wasm).unwrap();
+    let mut store = Store::new(&engine, wasi);
+    let mut linker = <Linker<WasiCtx>>::new(&engine);
+    preview2::add_to_linker(&mut linker, |ctx| ctx).unwrap();
+    let instance = linker.instantiate(&mut store, &component).unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `run` of `instance` returning a `u32`.
+fn run(store: &mut Store<WasiCtx>, instance: &ComponentInstance) -> Result<u32, wasmi::Error> {
+    let mut results = [Val::U32(0)];
+    instance
+        .get_func("run")
+        .unwrap()
+        .call(store, &[], &mut results)?;
+    match results {
+        [Val::U32(value)] => Ok(value),
+        _ => panic!("unexpected result: {results:?}"),
+    }
+}
+
+#[test]
+fn stdout_and_exit() {
+    let wat = r#"
+        (component
+            (import "wasi:io/error@0.2.0" (instance $error
+                (export "error" (type (sub resource)))
+            ))
+            (alias export $error "error" (type $error))
+            (import "wasi:io/streams@0.2.1" (instance $streams
+                (export "output-stream" (type $output-stream (sub resource)))
+                (alias outer 1 $error (type $error))
+                (type $stream-error (variant
+                    (case "last-operation-failed" (own $error))
+                    (case "closed")
+                ))
+                (export "[method]output-stream.blocking-write-and-flush" (func
+                    (param "self" (borrow $output-stream))
+                    (param "contents" (list u8))
+                    (result (result (error $stream-error)))
+                ))
+            ))
+            (alias export $streams "output-stream" (type $output-stream))
+            (alias export $streams "[method]output-stream.blocking-write-and-flush" (func $write))
+            (import "wasi:cli/stdout@0.2.0" (instance $stdout
+                (alias outer 1 $output-stream (type $output-stream))
+                (export "get-stdout" (func (result (own $output-stream))))
+            ))
+            (alias export $stdout "get-stdout" (func $get-stdout))
+            (import "wasi:cli/exit@0.2.0" (instance $exit
+                (export "exit" (func (param "status" (result))))
+            ))
+            (alias export $exit "exit" (func $exit))
+            {libc}
+            (core func $write (canon lower (func $write) (memory $mem)))
+            (core func $get-stdout (canon lower (func $get-stdout)))
+            (core func $drop (canon resource.drop $output-stream))
+            (core func $exit (canon lower (func $exit)))
+            (core module $m
+                (import "libc" "memory" (memory 1))
+                (import "wasi" "write" (func $write (param i32 i32 i32 i32)))
+                (import "wasi" "get-stdout" (func $get-stdout (result i32)))
+                (import "wasi" "drop" (func $drop (param i32)))
+                (import "wasi" "exit" (func $exit (param i32)))
+                (data (i32.const 16) "hello preview2\n")
+                (func (export "run") (result i32)
+                    (local $stdout i32)
+                    (local.set $stdout (call $get-stdout))
+                    (call $write (local.get $stdout) (i32.const 16) (i32.const 15) (i32.const 32))
+                    (call $drop (local.get $stdout))
+                    (call $exit (i32.load8_u (i32.const 32)))
+                    (unreachable)
+                )
+            )
+            (core instance $m
+                (instantiate $m
+                    (with "libc" (instance $libc))
+                    (with "wasi" (instance
+                        (export "write" (func $write))
+                        (export "get-stdout" (func $get-stdout))
+                        (export "drop" (func $drop))
+                        (export "exit" (func $exit))
+                    ))
+                )
+            )
+            (func (export "run") (result u32) (canon lift (core func $m "run")))
+        )
+    "#;
+    let stdout = WritePipe::new();
+    let wasi = WasiCtxBuilder::new().stdout(stdout.clone()).build();
+    let (mut store, instance) = instantiate(wat, WasiCtx::new(wasi));
+    let error = run(&mut store, &instance).unwrap_err();
+    assert_eq!(error.i32_exit_status(), Some(0));
+    assert_eq!(stdout.take(), b"hello preview2\n");
+}
+
+#[test]
+fn filesystem() {
+    let wat = r#"
+        (component
+            (import "wasi:filesystem/types@0.2.0" (instance $types
+                (export "descriptor" (type $descriptor (sub resource)))
+                (type $error-code (enum
+                    "access" "would-block" "already" "bad-descriptor" "busy" "deadlock"
+                    "quota" "exist" "file-too-large" "illegal-byte-sequence" "in-progress"
+                    "interrupted" "invalid" "io" "is-directory" "loop" "too-many-links"
+                    "message-size" "name-too-long" "no-device" "no-entry" "no-lock"
+                    "insufficient-memory" "insufficient-space" "not-directory" "not-empty"
+                    "not-recoverable" "unsupported" "no-tty" "no-such-device" "overflow"
+                    "not-permitted" "pipe" "read-only" "invalid-seek" "text-file-busy"
+                    "cross-device"
+                ))
+                (type $path-flags (flags "symlink-follow"))
+                (type $open-flags (flags "create" "directory" "exclusive" "truncate"))
+                (type $descriptor-flags (flags
+                    "read" "write" "file-integrity-sync" "data-integrity-sync"
+                    "requested-write-sync" "mutate-directory"
+                ))
+                (export "[method]descriptor.open-at" (func
+                    (param "self" (borrow $descriptor))
+                    (param "path-flags" $path-flags)
+                    (param "path" string)
+                    (param "open-flags" $open-flags)
+                    (param "flags" $descriptor-flags)
+                    (result (result (own $descriptor) (error $error-code)))
+                ))
+                (export "[method]descriptor.write" (func
+                    (param "self" (borrow $descriptor))
+                    (param "buffer" (list u8))
+                    (param "offset" u64)
+                    (result (result u64 (error $error-code)))
+                ))
+            ))
+            (alias export $types "descriptor" (type $descriptor))
+            (alias export $types "[method]descriptor.open-at" (func $open-at))
+            (alias export $types "[method]descriptor.write" (func $write))
+            (import "wasi:filesystem/preopens@0.2.0" (instance $preopens
+                (alias outer 1 $descriptor (type $descriptor))
+                (export "get-directories" (func
+                    (result (list (tuple (own $descriptor) string)))
+                ))
+            ))
+            (alias export $preopens "get-directories" (func $get-directories))
+            {libc}
+            (core func $get-directories
+                (canon lower (func $get-directories) (memory $mem) (realloc $realloc))
+            )
+            (core func $open-at (canon lower (func $open-at) (memory $mem)))
+            (core func $write (canon lower (func $write) (memory $mem)))
+            (core func $drop (canon resource.drop $descriptor))
+            (core module $m
+                (import "libc" "memory" (memory 1))
+                (import "wasi" "get-directories" (func $get-directories (param i32)))
+                (import "wasi" "open-at" (func $open-at (param i32 i32 i32 i32 i32 i32 i32)))
+                (import "wasi" "write" (func $write (param i32 i32 i32 i64 i32)))
+                (import "wasi" "drop" (func $drop (param i32)))
+                (data (i32.const 100) "out.txt")
+                (data (i32.const 120) "missing.txt")
+                (data (i32.const 140) "hello")
+                (func (export "run") (result i32)
+                    (local $dir i32) (local $file i32)
+                    (call $get-directories (i32.const 200))
+                    (local.set $dir (i32.load (i32.load (i32.const 200))))
+                    ;; Create `out.txt` for reading and writing and write `hello` to it.
+                    (call $open-at
+                        (local.get $dir) (i32.const 0) (i32.const 100) (i32.const 7)
+                        (i32.const 1) (i32.const 3) (i32.const 208))
+                    (if (i32.load8_u (i32.const 208)) (then (unreachable)))
+                    (local.set $file (i32.load (i32.const 212)))
+                    (call $write
+                        (local.get $file) (i32.const 140) (i32.const 5) (i64.const 0)
+                        (i32.const 216))
+                    (if (i32.load8_u (i32.const 216)) (then (unreachable)))
+                    (call $drop (local.get $file))
+                    ;; Opening `missing.txt` without `create` fails with `no-entry`.
+                    (call $open-at
+                        (local.get $dir) (i32.const 0) (i32.const 120) (i32.const 11)
+                        (i32.const 0) (i32.const 1) (i32.const 208))
+                    (if (i32.eqz (i32.load8_u (i32.const 208))) (then (unreachable)))
+                    (i32.add
+                        (i32.mul (i32.load8_u (i32.const 212)) (i32.const 100))
+                        (i32.wrap_i64 (i64.load (i32.const 224)))
+                    )
+                )
+            )
+            (core instance $m
+                (instantiate $m
+                    (with "libc" (instance $libc))
+                    (with "wasi" (instance
+                        (export "get-directories" (func $get-directories))
+                        (export "open-at" (func $open-at))
+                        (export "write" (func $write))
+                        (export "drop" (func $drop))
+                    ))
+                )
+            )
+            (func (export "run") (result u32) (canon lift (core func $m "run")))
+        )
+    "#;
+    let fs = MemFs::new();
+    let wasi = WasiCtxBuilder::new().preopened_dir(fs.clone(), "/").build();
+    let (mut store, instance) = instantiate(wat, WasiCtx::new(wasi));
+    // The `no-entry` error code (20) and the 5 bytes written.
+    assert_eq!(run(&mut store, &instance).unwrap(), 2005);
+    drop(store);
+    assert_eq!(fs.read_file("out.txt").unwrap(), b"hello");
+}
+
+#[test]
+fn clocks_and_poll() {
+    let wat = r#"
+        (component
+            (import "wasi:io/poll@0.2.0" (instance $poll
+                (export "pollable" (type $pollable (sub resource)))
+                (export "[method]pollable.block" (func (param "self" (borrow $pollable))))
+            ))
+            (alias export $poll "pollable" (type $pollable))
+            (alias export $poll "[method]pollable.block" (func $block))
+            (import "wasi:clocks/monotonic-clock@0.2.0" (instance $clock
+                (alias outer 1 $pollable (type $pollable))
+                (export "now" (func (result u64)))
+                (export "subscribe-duration" (func (param "when" u64) (result (own $pollable))))
+            ))
+            (alias export $clock "now" (func $now))
+            (alias export $clock "subscribe-duration" (func $subscribe))
+            (core func $block (canon lower (func $block)))
+            (core func $now (canon lower (func $now)))
+            (core func $subscribe (canon lower (func $subscribe)))
+            (core func $drop (canon resource.drop $pollable))
+            (core module $m
+                (import "wasi" "block" (func $block (param i32)))
+                (import "wasi" "now" (func $now (result i64)))
+                (import "wasi" "subscribe" (func $subscribe (param i64) (result i32)))
+                (import "wasi" "drop" (func $drop (param i32)))
+                (func (export "run") (result i32)
+                    (local $start i64) (local $pollable i32)
+                    (local.set $start (call $now))
+                    (local.set $pollable (call $subscribe (i64.const 1000000)))
+                    (call $block (local.get $pollable))
+                    (call $drop (local.get $pollable))
+                    (i32.wrap_i64 (i64.sub (call $now) (local.get $start)))
+                )
+            )
+            (core instance $m
+                (instantiate $m
+                    (with "wasi" (instance
+                        (export "block" (func $block))
+                        (export "now" (func $now))
+                        (export "subscribe" (func $subscribe))
+                        (export "drop" (func $drop))
+                    ))
+                )
+            )
+            (func (export "run") (result u32) (canon lift (core func $m "run")))
+        )
+    "#;
+    let wasi = WasiCtxBuilder::new().deterministic(0).build();
+    let (mut store, instance) = instantiate(wat, WasiCtx::new(wasi));
+    let elapsed = run(&mut store, &instance).unwrap();
+    assert!(elapsed >= 1_000_000, "elapsed: {elapsed}");
+}
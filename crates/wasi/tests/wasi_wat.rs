@@ -1,6 +1,14 @@
+#![cfg(feature = "sync")]
+
+use std::sync::{Arc, Mutex};
 use wasi_cap_std_sync::WasiCtxBuilder;
 use wasmi::{Config, Engine, Extern, Instance, Linker, Module, Store};
-use wasmi_wasi::{add_to_linker, WasiCtx};
+use wasmi_wasi::{
+    add_to_linker,
+    add_to_linker_with_tracer,
+    preview1::{Errno, WasiTracer},
+    WasiCtx,
+};
 
 pub fn load_instance_from_wat(wat_bytes: &[u8]) -> (Store<WasiCtx>, wasmi::Instance) {
     let wasm = wat2wasm(wat_bytes);
@@ -45,3 +53,76 @@ fn test_hello_world() {
     let mut result = [];
     f.call(&mut store, &[], &mut result).unwrap();
 }
+
+/// A [`WasiTracer`] recording all WASI calls.
+#[derive(Debug, Default, Clone)]
+struct Trace(Arc<Mutex<Vec<String>>>);
+
+impl WasiTracer for Trace {
+    fn enter(&mut self, name: &str, args: &str) {
+        self.0.lock().unwrap().push(format!("{name}({args})"));
+    }
+
+    fn exit(&mut self, _name: &str, errno: Option<Errno>) {
+        let mut calls = self.0.lock().unwrap();
+        let call = calls.last_mut().unwrap();
+        match errno {
+            Some(errno) => call.push_str(&format!(" = {errno:?}")),
+            None => call.push_str(" = ?"),
+        }
+    }
+}
+
+#[test]
+fn test_tracer() {
+    let wasm = wat2wasm(
+        br#"
+        (module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 16) "missing.txt")
+            (data (i32.const 32) "hello")
+            (func (export "_start")
+                (drop
+                    (call $path_open
+                        (i32.const 3) (i32.const 1) (i32.const 16) (i32.const 11)
+                        (i32.const 0) (i64.const 2) (i64.const 0) (i32.const 0)
+                        (i32.const 0)))
+                (i32.store (i32.const 0) (i32.const 32))
+                (i32.store (i32.const 4) (i32.const 5))
+                (i32.store (i32.const 8) (i32.const 32))
+                (i32.store (i32.const 12) (i32.const 3))
+                (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 2) (i32.const 48)))
+                (call $proc_exit (i32.const 7))
+            )
+        )
+    "#,
+    );
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let trace = Trace::default();
+    let mut store = Store::new(&engine, (WasiCtxBuilder::new().build(), trace.clone()));
+    let mut linker = <Linker<(WasiCtx, Trace)>>::new(&engine);
+    add_to_linker_with_tracer(&mut linker, |(ctx, _)| ctx, |(_, trace)| Some(trace)).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let start = instance.get_typed_func::<(), ()>(&store, "_start").unwrap();
+    let result = start.call(&mut store, ());
+    assert_eq!(result.unwrap_err().i32_exit_status(), Some(7));
+    assert_eq!(
+        *trace.0.lock().unwrap(),
+        [
+            "path_open(fd=3, dirflags=1, path=\"missing.txt\", oflags=0, \
+             rights_base=0x2, rights_inheriting=0x0, fdflags=0, opened_fd=0) = Badf",
+            "fd_write(fd=1, iovs=[5, 3], nwritten=48) = Success",
+            "proc_exit(status=7) = ?",
+        ]
+    );
+}
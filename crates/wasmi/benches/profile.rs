@@ -0,0 +1,107 @@
+//! Profiles the most frequent adjacent Wasmi bytecode instruction pairs of the benchmark corpus.
+//!
+//! Translated pairs are recorded for all `benches/wasm/*.wasm` modules and
+//! executed pairs are recorded for a selection of the `benches/wat` kernels.
+//! This helps to decide which instruction pairs are worth fusing into superinstructions.
+//!
+//! Run via `cargo bench --bench profile --features instr-profiling`.
+
+use std::fs;
+use wasmi::{Config, Engine, InstrPairs, Linker, Module, Store, Value};
+
+/// The number of most frequent instruction pairs that are printed.
+const TOP_N: usize = 25;
+
+/// The executed `benches/wat` kernels with their exported function and inputs.
+const KERNELS: &[(&str, &[u8], &str, &[Value])] = &[
+    (
+        "count_until",
+        include_bytes!("wat/count_until.wat"),
+        "count_until",
+        &[Value::I32(100_000)],
+    ),
+    (
+        "fuse",
+        include_bytes!("wat/fuse.wat"),
+        "test",
+        &[Value::I32(100_000)],
+    ),
+    (
+        "divrem",
+        include_bytes!("wat/divrem.wat"),
+        "test",
+        &[Value::I32(25_000)],
+    ),
+    (
+        "fibonacci/rec",
+        include_bytes!("wat/fibonacci.wat"),
+        "fibonacci_rec",
+        &[Value::I64(20)],
+    ),
+    (
+        "fibonacci/iter",
+        include_bytes!("wat/fibonacci.wat"),
+        "fibonacci_iter",
+        &[Value::I64(100_000)],
+    ),
+    (
+        "memory/sum_bytes",
+        include_bytes!("wat/memory-sum.wat"),
+        "sum_bytes",
+        &[Value::I32(60_000)],
+    ),
+    (
+        "memory/fill_bytes",
+        include_bytes!("wat/memory-fill.wat"),
+        "fill_bytes",
+        &[Value::I32(0x100), Value::I32(60_000), Value::I32(0x42)],
+    ),
+];
+
+fn main() {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    let engine = Engine::new(&config);
+    let mut paths = fs::read_dir("benches/wasm")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    for path in &paths {
+        let wasm = fs::read(path).unwrap();
+        Module::new(&engine, &wasm[..])
+            .unwrap_or_else(|error| panic!("failed to translate {}: {error}", path.display()));
+    }
+    print_pairs("translated", engine.instr_profile().translated());
+    engine.reset_instr_profile();
+    for (name, wat, func_name, inputs) in KERNELS {
+        let wasm = wat::parse_bytes(wat).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let func = instance.get_func(&store, func_name).unwrap();
+        let mut outputs = vec![Value::I32(0); func.ty(&store).results().len()];
+        func.call(&mut store, inputs, &mut outputs)
+            .unwrap_or_else(|error| panic!("failed to execute {name}: {error}"));
+    }
+    print_pairs("executed", engine.instr_profile().executed());
+}
+
+/// Prints the [`TOP_N`] most frequent instruction `pairs` and their share of all pairs.
+fn print_pairs(title: &str, pairs: &InstrPairs) {
+    let total = pairs.total();
+    println!("most frequent {title} instruction pairs ({total} in total):");
+    for pair in pairs.most_frequent(TOP_N) {
+        let share = pair.count as f64 / total as f64 * 100.0;
+        println!(
+            "{:>12} {share:>6.2}%  {} -> {}",
+            pair.count, pair.first, pair.second
+        );
+    }
+    println!();
+}
@@ -0,0 +1,531 @@
+//! Runtime support for bindings generated by the [`bindgen!`] macro.
+//!
+//! Values are passed between host and guest following the canonical ABI
+//! of the Wasm component model for the supported subset of types:
+//!
+//! - `bool`, `u8`, `u16`, `u32`, `u64`, `s8`, `s16`, `s32`, `s64`, `f32`, `f64` and `char`
+//! - `string` and `list<T>` which are passed as pointer and length pair
+//! - `record` types which are laid out and flattened field by field
+//!
+//! Guests that pass strings or lists from the host must export a `memory`
+//! as well as a `cabi_realloc` function with the signature
+//! `(func (param i32 i32 i32 i32) (result i32))`.
+//!
+//! [`bindgen!`]: crate::bindgen!
+
+use crate::{
+    canonical_abi::{
+        self,
+        core_func_type,
+        invalid_value,
+        next_i32,
+        next_ptr_len,
+        FLAT_PTR_LEN,
+        MAX_FLAT_PARAMS,
+        MAX_FLAT_RESULTS,
+    },
+    core::{ValueType, F32, F64},
+    AsContext,
+    AsContextMut,
+    Caller,
+    Error,
+    Extern,
+    Func,
+    FuncType,
+    Instance,
+    Memory,
+    StoreContextMut,
+    Value,
+};
+use core::{array, slice};
+use std::{string::String, vec::Vec};
+
+#[doc(hidden)]
+pub use crate::canonical_abi::{record_align, record_size, LiftContext, RecordLayout};
+
+/// Context for lowering values into guest linear memory.
+pub type LowerContext<'a, T> = canonical_abi::LowerContext<StoreContextMut<'a, T>>;
+
+/// The name of the linear memory export required for passing strings and lists.
+const MEMORY_EXPORT: &str = "memory";
+
+/// The name of the allocation function export required for passing strings and lists to the guest.
+const REALLOC_EXPORT: &str = "cabi_realloc";
+
+/// Types that can be passed between host and guest via the canonical ABI.
+pub trait ComponentType: Sized {
+    /// The size of the type when stored in linear memory.
+    const SIZE: usize;
+    /// The alignment of the type when stored in linear memory.
+    const ALIGN: usize;
+    /// The number of core Wasm values of the flattened type.
+    const FLAT_LEN: usize;
+
+    /// Pushes the core Wasm value types of the flattened type to `out`.
+    fn flat_types(out: &mut Vec<ValueType>);
+
+    /// Lowers `self` into a sequence of core Wasm values pushed to `out`.
+    ///
+    /// # Errors
+    ///
+    /// If allocating or writing guest memory failed.
+    fn lower_flat<T>(&self, cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error>;
+
+    /// Stores `self` in linear memory at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If allocating or writing guest memory failed.
+    fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error>;
+
+    /// Lifts a value from the sequence of core Wasm values.
+    ///
+    /// # Errors
+    ///
+    /// If the core Wasm values or the referenced guest memory are invalid.
+    fn lift_flat(cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error>;
+
+    /// Loads a value stored in linear memory at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If the referenced guest memory is invalid.
+    fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error>;
+}
+
+/// Returns the core Wasm value types of the flattened `T`.
+fn flat_types<T: ComponentType>() -> Vec<ValueType> {
+    let mut types = Vec::with_capacity(T::FLAT_LEN);
+    T::flat_types(&mut types);
+    types
+}
+
+macro_rules! impl_component_type_for_int {
+    ( $( $ty:ty => $flat:ident as $core:ty ),* $(,)? ) => {
+        $(
+            impl ComponentType for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+                const ALIGN: usize = core::mem::size_of::<$ty>();
+                const FLAT_LEN: usize = 1;
+
+                fn flat_types(out: &mut Vec<ValueType>) {
+                    out.push(ValueType::$flat);
+                }
+
+                fn lower_flat<T>(&self, _cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+                    out.push(Value::$flat(*self as $core));
+                    Ok(())
+                }
+
+                fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+                    cx.write(offset, &self.to_le_bytes())
+                }
+
+                fn lift_flat(_cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+                    // Note: the canonical ABI truncates narrow integers.
+                    match values.next() {
+                        Some(Value::$flat(value)) => Ok(*value as $ty),
+                        _ => Err(invalid_value(stringify!($ty))),
+                    }
+                }
+
+                fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+                    let bytes = cx.bytes(offset, Self::SIZE)?;
+                    Ok(<$ty>::from_le_bytes(array::from_fn(|i| bytes[i])))
+                }
+            }
+        )*
+    };
+}
+impl_component_type_for_int! {
+    u8 => I32 as i32,
+    u16 => I32 as i32,
+    u32 => I32 as i32,
+    u64 => I64 as i64,
+    i8 => I32 as i32,
+    i16 => I32 as i32,
+    i32 => I32 as i32,
+    i64 => I64 as i64,
+}
+
+macro_rules! impl_component_type_for_float {
+    ( $( $ty:ty => $flat:ident ),* $(,)? ) => {
+        $(
+            impl ComponentType for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+                const ALIGN: usize = core::mem::size_of::<$ty>();
+                const FLAT_LEN: usize = 1;
+
+                fn flat_types(out: &mut Vec<ValueType>) {
+                    out.push(ValueType::$flat);
+                }
+
+                fn lower_flat<T>(&self, _cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+                    out.push(Value::$flat($flat::from(*self)));
+                    Ok(())
+                }
+
+                fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+                    cx.write(offset, &self.to_le_bytes())
+                }
+
+                fn lift_flat(_cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+                    match values.next() {
+                        Some(Value::$flat(value)) => Ok(<$ty>::from(*value)),
+                        _ => Err(invalid_value(stringify!($ty))),
+                    }
+                }
+
+                fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+                    let bytes = cx.bytes(offset, Self::SIZE)?;
+                    Ok(<$ty>::from_le_bytes(array::from_fn(|i| bytes[i])))
+                }
+            }
+        )*
+    };
+}
+impl_component_type_for_float! {
+    f32 => F32,
+    f64 => F64,
+}
+
+impl ComponentType for bool {
+    const SIZE: usize = 1;
+    const ALIGN: usize = 1;
+    const FLAT_LEN: usize = 1;
+
+    fn flat_types(out: &mut Vec<ValueType>) {
+        out.push(ValueType::I32);
+    }
+
+    fn lower_flat<T>(&self, _cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+        out.push(Value::I32(i32::from(*self)));
+        Ok(())
+    }
+
+    fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+        cx.write(offset, &[u8::from(*self)])
+    }
+
+    fn lift_flat(_cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+        Ok(next_i32(values)? != 0)
+    }
+
+    fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+        Ok(cx.bytes(offset, 1)?[0] != 0)
+    }
+}
+
+impl ComponentType for char {
+    const SIZE: usize = 4;
+    const ALIGN: usize = 4;
+    const FLAT_LEN: usize = 1;
+
+    fn flat_types(out: &mut Vec<ValueType>) {
+        out.push(ValueType::I32);
+    }
+
+    fn lower_flat<T>(&self, _cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+        out.push(Value::I32(u32::from(*self) as i32));
+        Ok(())
+    }
+
+    fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+        u32::from(*self).store(cx, offset)
+    }
+
+    fn lift_flat(_cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+        char::from_u32(next_i32(values)? as u32).ok_or_else(|| invalid_value("char"))
+    }
+
+    fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+        char::from_u32(u32::load(cx, offset)?).ok_or_else(|| invalid_value("char"))
+    }
+}
+
+impl ComponentType for String {
+    const SIZE: usize = 8;
+    const ALIGN: usize = 4;
+    const FLAT_LEN: usize = 2;
+
+    fn flat_types(out: &mut Vec<ValueType>) {
+        out.extend(FLAT_PTR_LEN);
+    }
+
+    fn lower_flat<T>(&self, cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+        let (ptr, len) = cx.string(self)?;
+        out.extend([Value::I32(ptr as i32), Value::I32(len as i32)]);
+        Ok(())
+    }
+
+    fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+        let (ptr, len) = cx.string(self)?;
+        cx.store_ptr_len(offset, ptr, len)
+    }
+
+    fn lift_flat(cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+        let (ptr, len) = next_ptr_len(values)?;
+        cx.string(ptr, len).map(String::from)
+    }
+
+    fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+        let (ptr, len) = cx.ptr_len(offset)?;
+        cx.string(ptr, len).map(String::from)
+    }
+}
+
+impl<E: ComponentType> ComponentType for Vec<E> {
+    const SIZE: usize = 8;
+    const ALIGN: usize = 4;
+    const FLAT_LEN: usize = 2;
+
+    fn flat_types(out: &mut Vec<ValueType>) {
+        out.extend(FLAT_PTR_LEN);
+    }
+
+    fn lower_flat<T>(&self, cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+        let ptr = lower_list(cx, self)?;
+        out.extend([Value::I32(ptr as i32), Value::I32(self.len() as i32)]);
+        Ok(())
+    }
+
+    fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+        let ptr = lower_list(cx, self)?;
+        cx.store_ptr_len(offset, ptr, self.len())
+    }
+
+    fn lift_flat(cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+        let (ptr, len) = next_ptr_len(values)?;
+        lift_list(cx, ptr, len)
+    }
+
+    fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+        let (ptr, len) = cx.ptr_len(offset)?;
+        lift_list(cx, ptr, len)
+    }
+}
+
+/// Lowers the `list` into guest memory and returns its pointer.
+fn lower_list<T, E: ComponentType>(cx: &mut LowerContext<T>, list: &[E]) -> Result<usize, Error> {
+    let ptr = cx.list(list.len(), E::SIZE, E::ALIGN)?;
+    for (n, element) in list.iter().enumerate() {
+        element.store(cx, ptr + n * E::SIZE)?;
+    }
+    Ok(ptr)
+}
+
+/// Lifts the `list` with `len` elements at `ptr` out of guest memory.
+fn lift_list<E: ComponentType>(cx: &LiftContext, ptr: usize, len: usize) -> Result<Vec<E>, Error> {
+    // Bounds check the whole list before allocating space for its elements.
+    cx.list(ptr, len, E::SIZE, E::ALIGN)?;
+    (0..len).map(|n| E::load(cx, ptr + n * E::SIZE)).collect()
+}
+
+macro_rules! impl_component_type_for_tuple {
+    ( $n:literal $( $tuple:ident )* ) => {
+        impl<$($tuple),*> ComponentType for ($($tuple,)*)
+        where
+            $(
+                $tuple: ComponentType,
+            )*
+        {
+            const SIZE: usize = record_size(&[$( ($tuple::SIZE, $tuple::ALIGN) ),*]);
+            const ALIGN: usize = record_align(&[$( $tuple::ALIGN ),*]);
+            const FLAT_LEN: usize = 0 $( + $tuple::FLAT_LEN )*;
+
+            #[allow(unused_variables)]
+            fn flat_types(out: &mut Vec<ValueType>) {
+                $( $tuple::flat_types(out); )*
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            fn lower_flat<T>(&self, cx: &mut LowerContext<T>, out: &mut Vec<Value>) -> Result<(), Error> {
+                let ($($tuple,)*) = self;
+                $( $tuple.lower_flat(cx, out)?; )*
+                Ok(())
+            }
+
+            #[allow(non_snake_case, unused_variables, unused_mut)]
+            fn store<T>(&self, cx: &mut LowerContext<T>, offset: usize) -> Result<(), Error> {
+                let ($($tuple,)*) = self;
+                let mut layout = RecordLayout::new(offset);
+                $( $tuple.store(cx, layout.field($tuple::SIZE, $tuple::ALIGN))?; )*
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn lift_flat(cx: &LiftContext, values: &mut slice::Iter<Value>) -> Result<Self, Error> {
+                Ok(($( $tuple::lift_flat(cx, values)?, )*))
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn load(cx: &LiftContext, offset: usize) -> Result<Self, Error> {
+                let mut layout = RecordLayout::new(offset);
+                Ok(($( $tuple::load(cx, layout.field($tuple::SIZE, $tuple::ALIGN))?, )*))
+            }
+        }
+    };
+}
+for_each_tuple!(impl_component_type_for_tuple);
+
+/// The linear memory and allocation function exported by a guest [`Instance`].
+#[derive(Debug, Copy, Clone)]
+pub struct Guest {
+    /// The `memory` export of the guest if any.
+    memory: Option<Memory>,
+    /// The `cabi_realloc` export of the guest if any.
+    realloc: Option<Func>,
+}
+
+impl Guest {
+    /// Resolves the exports of the guest `instance` required to pass strings and lists.
+    pub fn new(ctx: impl AsContext, instance: &Instance) -> Self {
+        let memory = instance.get_memory(&ctx, MEMORY_EXPORT);
+        let realloc = instance
+            .get_func(&ctx, REALLOC_EXPORT)
+            .filter(|func| func.typed::<(i32, i32, i32, i32), i32>(&ctx).is_ok());
+        Self { memory, realloc }
+    }
+
+    /// Resolves the exports of the calling guest required to pass strings and lists.
+    fn from_caller<T>(caller: &Caller<T>) -> Self {
+        let memory = caller
+            .get_export(MEMORY_EXPORT)
+            .and_then(Extern::into_memory);
+        let realloc = caller
+            .get_export(REALLOC_EXPORT)
+            .and_then(Extern::into_func)
+            .filter(|func| func.typed::<(i32, i32, i32, i32), i32>(caller).is_ok());
+        Self { memory, realloc }
+    }
+
+    /// Returns a [`LiftContext`] for the bytes of the guest linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If the guest linear memory is shared.
+    fn lift_context<'a, T: 'a>(
+        &self,
+        ctx: impl Into<crate::StoreContext<'a, T>>,
+    ) -> Result<LiftContext<'a>, Error> {
+        let memory = self.memory.map(|memory| memory.try_data(ctx)).transpose()?;
+        Ok(LiftContext::new(memory))
+    }
+
+    /// Returns a [`LowerContext`] for the guest linear memory and allocation function.
+    fn lower_context<'a, T>(&self, ctx: impl Into<StoreContextMut<'a, T>>) -> LowerContext<'a, T> {
+        LowerContext::new(ctx.into(), self.memory, self.realloc)
+    }
+}
+
+/// Returns the core Wasm [`FuncType`] of a host function import with params `P` and result `R`.
+///
+/// # Errors
+///
+/// If the flattened parameters need to be passed via linear memory which is unsupported.
+pub fn import_func_type<P, R>() -> Result<FuncType, Error>
+where
+    P: ComponentType,
+    R: ComponentType,
+{
+    if P::FLAT_LEN > MAX_FLAT_PARAMS {
+        return Err(Error::new("too many flattened parameters"));
+    }
+    Ok(core_func_type(flat_types::<P>(), flat_types::<R>(), true))
+}
+
+/// Lifts the parameters `P` of a host function import called by the guest.
+///
+/// # Errors
+///
+/// If the guest provided invalid values or its linear memory is shared.
+pub fn lift_params<T, P>(caller: &Caller<T>, params: &[Value]) -> Result<P, Error>
+where
+    P: ComponentType,
+{
+    let cx = Guest::from_caller(caller).lift_context(caller)?;
+    P::lift_flat(&cx, &mut params[..P::FLAT_LEN].iter())
+}
+
+/// Lowers the `result` of a host function import into the guest.
+///
+/// # Errors
+///
+/// If allocating or writing guest memory failed.
+pub fn lower_result<T, R>(
+    caller: &mut Caller<T>,
+    params: &[Value],
+    results: &mut [Value],
+    result: R,
+) -> Result<(), Error>
+where
+    R: ComponentType,
+{
+    let mut cx = Guest::from_caller(caller).lower_context(caller);
+    if R::FLAT_LEN > MAX_FLAT_RESULTS {
+        let Some(Value::I32(retptr)) = params.last() else {
+            return Err(invalid_value("missing return pointer"));
+        };
+        return result.store(&mut cx, *retptr as u32 as usize);
+    }
+    let mut flat = Vec::with_capacity(R::FLAT_LEN);
+    result.lower_flat(&mut cx, &mut flat)?;
+    for (result, value) in results.iter_mut().zip(flat) {
+        *result = value;
+    }
+    Ok(())
+}
+
+/// Calls the guest export `func` with parameters `P` returning a result `R`.
+///
+/// # Errors
+///
+/// - If the flattened parameters need to be passed via linear memory which is unsupported.
+/// - If passing parameters or results via guest memory failed.
+/// - If the guest function traps.
+/// - If the guest linear memory is shared.
+pub fn call_export<T, P, R>(
+    mut ctx: impl AsContextMut<UserState = T>,
+    guest: &Guest,
+    func: &Func,
+    params: P,
+) -> Result<R, Error>
+where
+    P: ComponentType,
+    R: ComponentType,
+{
+    if P::FLAT_LEN > MAX_FLAT_PARAMS {
+        return Err(Error::new("too many flattened parameters"));
+    }
+    let mut flat = Vec::with_capacity(P::FLAT_LEN);
+    params.lower_flat(&mut guest.lower_context(ctx.as_context_mut()), &mut flat)?;
+    let func_type = core_func_type(Vec::new(), flat_types::<R>(), false);
+    let mut results: Vec<Value> = func_type
+        .results()
+        .iter()
+        .copied()
+        .map(Value::default)
+        .collect();
+    func.call(&mut ctx, &flat, &mut results)?;
+    let cx = guest.lift_context(&ctx)?;
+    if R::FLAT_LEN > MAX_FLAT_RESULTS {
+        let Some(Value::I32(retptr)) = results.first() else {
+            return Err(invalid_value("missing return pointer"));
+        };
+        return R::load(&cx, *retptr as u32 as usize);
+    }
+    R::lift_flat(&cx, &mut results.iter())
+}
+
+/// Returns the exported function `name` of the guest `instance`.
+///
+/// # Errors
+///
+/// If the guest does not export a function under `name`.
+pub fn export_func(ctx: impl AsContext, instance: &Instance, name: &str) -> Result<Func, Error> {
+    instance.get_func(ctx, name).ok_or_else(|| {
+        Error::new(std::format!(
+            "guest is missing the `{name}` function export"
+        ))
+    })
+}
@@ -0,0 +1,351 @@
+//! Layout, flattening and guest memory access following the canonical ABI of the Wasm component model.
+//!
+//! This is shared by the runtime support of the `bindgen!` macro
+//! and the component model layer which lift and lower their own value representations
+//! on top of it.
+
+#[cfg(feature = "component-model")]
+use crate::component::ComponentError;
+use crate::{
+    core::ValueType,
+    errors::MemoryError,
+    AsContextMut,
+    Error,
+    Func,
+    FuncType,
+    Memory,
+    Value,
+};
+use core::slice;
+use std::{vec, vec::Vec};
+
+/// The maximum number of flattened parameters passed as core parameters.
+pub const MAX_FLAT_PARAMS: usize = 16;
+
+/// The maximum number of flattened results returned as core results.
+pub const MAX_FLAT_RESULTS: usize = 1;
+
+/// The flattened core types of a `string` or `list` which are passed as pointer and length pair.
+pub const FLAT_PTR_LEN: [ValueType; 2] = [ValueType::I32, ValueType::I32];
+
+/// Returns `offset` rounded up to the next multiple of `align`.
+pub const fn align_to(offset: usize, align: usize) -> usize {
+    offset.div_ceil(align) * align
+}
+
+/// Returns the alignment of a record with fields of the given `aligns`.
+#[cfg(feature = "derive")]
+pub const fn record_align(aligns: &[usize]) -> usize {
+    let mut align = 1;
+    let mut i = 0;
+    while i < aligns.len() {
+        if aligns[i] > align {
+            align = aligns[i];
+        }
+        i += 1;
+    }
+    align
+}
+
+/// Returns the size of a record with fields of the given `(size, align)` pairs.
+#[cfg(feature = "derive")]
+pub const fn record_size(fields: &[(usize, usize)]) -> usize {
+    let mut layout = RecordLayout::new(0);
+    let mut i = 0;
+    while i < fields.len() {
+        let (size, align) = fields[i];
+        layout.field(size, align);
+        i += 1;
+    }
+    layout.size()
+}
+
+/// Computes the offsets of consecutive record fields in linear memory.
+#[derive(Debug)]
+pub struct RecordLayout {
+    /// The offset of the record.
+    start: usize,
+    /// The offset right after the last field.
+    offset: usize,
+    /// The maximum alignment of all fields so far.
+    align: usize,
+}
+
+impl RecordLayout {
+    /// Creates a new [`RecordLayout`] for a record stored at `offset`.
+    pub const fn new(offset: usize) -> Self {
+        Self {
+            start: offset,
+            offset,
+            align: 1,
+        }
+    }
+
+    /// Returns the offset of the next field with the given `size` and `align`.
+    pub const fn field(&mut self, size: usize, align: usize) -> usize {
+        let offset = align_to(self.offset, align);
+        self.offset = offset + size;
+        if align > self.align {
+            self.align = align;
+        }
+        offset
+    }
+
+    /// Returns the size of the record with all fields so far.
+    pub const fn size(&self) -> usize {
+        align_to(self.offset - self.start, self.align)
+    }
+}
+
+/// Returns the core function type for the flattened `params` and `results`.
+///
+/// Too many flattened parameters are passed via a pointer into linear memory.
+/// Too many flattened results are returned via a pointer to linear memory which
+/// is returned by lifted functions and passed as last parameter to lowered functions.
+pub fn core_func_type(
+    mut params: Vec<ValueType>,
+    mut results: Vec<ValueType>,
+    lower: bool,
+) -> FuncType {
+    if params.len() > MAX_FLAT_PARAMS {
+        params = vec![ValueType::I32];
+    }
+    if results.len() > MAX_FLAT_RESULTS {
+        results = vec![ValueType::I32];
+        if lower {
+            params.append(&mut results);
+        }
+    }
+    FuncType::new(params, results)
+}
+
+/// Returns an [`Error`] for an invalid value in the canonical ABI representation.
+pub fn invalid_value(what: &'static str) -> Error {
+    #[cfg(feature = "component-model")]
+    return ComponentError::InvalidAbiValue { what }.into();
+    #[cfg(not(feature = "component-model"))]
+    return Error::new(std::format!("invalid canonical ABI value: {what}"));
+}
+
+/// Returns an [`Error`] for a missing `memory` or `realloc` required to pass a value.
+fn missing_option(option: &'static str) -> Error {
+    #[cfg(feature = "component-model")]
+    return ComponentError::MissingOption { option }.into();
+    #[cfg(not(feature = "component-model"))]
+    return Error::new(std::format!(
+        "missing canonical option `{option}` to pass the value"
+    ));
+}
+
+/// Returns the next flattened core value.
+///
+/// # Errors
+///
+/// If there are no more `values`.
+pub fn next_value(values: &mut slice::Iter<Value>) -> Result<Value, Error> {
+    values
+        .next()
+        .cloned()
+        .ok_or_else(|| invalid_value("missing flattened value"))
+}
+
+/// Returns the next flattened core value as `i32`.
+///
+/// # Errors
+///
+/// If the next value is missing or not an `i32`.
+pub fn next_i32(values: &mut slice::Iter<Value>) -> Result<i32, Error> {
+    match next_value(values)? {
+        Value::I32(value) => Ok(value),
+        _ => Err(invalid_value("expected an i32 value")),
+    }
+}
+
+/// Returns the next flattened pointer and length pair of a `string` or `list`.
+///
+/// # Errors
+///
+/// If the next values are not a pointer and length pair.
+pub fn next_ptr_len(values: &mut slice::Iter<Value>) -> Result<(usize, usize), Error> {
+    let ptr = next_i32(values)? as u32 as usize;
+    let len = next_i32(values)? as u32 as usize;
+    Ok((ptr, len))
+}
+
+/// Context for lifting values out of guest linear memory.
+#[derive(Debug, Copy, Clone)]
+pub struct LiftContext<'a> {
+    /// The bytes of the guest linear memory if any.
+    memory: Option<&'a [u8]>,
+}
+
+impl<'a> LiftContext<'a> {
+    /// Creates a new [`LiftContext`] for the bytes of the guest linear `memory` if any.
+    pub fn new(memory: Option<&'a [u8]>) -> Self {
+        Self { memory }
+    }
+
+    /// Returns the `len` bytes at `offset` of the guest linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If there is no guest linear memory or the bytes are out of bounds.
+    pub fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], Error> {
+        let memory = self.memory.ok_or_else(|| missing_option("memory"))?;
+        offset
+            .checked_add(len)
+            .and_then(|end| memory.get(offset..end))
+            .ok_or_else(|| MemoryError::OutOfBoundsAccess.into())
+    }
+
+    /// Returns the `N` bytes at `offset` of the guest linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If there is no guest linear memory or the bytes are out of bounds.
+    pub fn array<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+        let mut array = [0x00; N];
+        array.copy_from_slice(self.bytes(offset, N)?);
+        Ok(array)
+    }
+
+    /// Loads the pointer and length pair of a `string` or `list` stored at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no guest linear memory or the bytes are out of bounds.
+    pub fn ptr_len(&self, offset: usize) -> Result<(usize, usize), Error> {
+        let ptr = u32::from_le_bytes(self.array(offset)?) as usize;
+        let len = u32::from_le_bytes(self.array(offset + 4)?) as usize;
+        Ok((ptr, len))
+    }
+
+    /// Returns the `string` with `len` bytes at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes are out of bounds or not valid UTF-8.
+    pub fn string(&self, ptr: usize, len: usize) -> Result<&'a str, Error> {
+        let bytes = self.bytes(ptr, len)?;
+        core::str::from_utf8(bytes).map_err(|_| MemoryError::InvalidUtf8.into())
+    }
+
+    /// Checks the `list` at `ptr` with `len` elements of the given `size` and `align`.
+    ///
+    /// The elements are found at `ptr + n * size` afterwards.
+    ///
+    /// # Errors
+    ///
+    /// If the `list` is misaligned or out of bounds.
+    pub fn list(&self, ptr: usize, len: usize, size: usize, align: usize) -> Result<(), Error> {
+        if !ptr.is_multiple_of(align) {
+            return Err(invalid_value("misaligned list pointer"));
+        }
+        let bytes = size
+            .checked_mul(len)
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        self.bytes(ptr, bytes)?;
+        Ok(())
+    }
+}
+
+/// Context for lowering values into guest linear memory.
+#[derive(Debug)]
+pub struct LowerContext<C> {
+    /// The store context used to access guest memory and call the allocation function.
+    ctx: C,
+    /// The guest linear memory if any.
+    memory: Option<Memory>,
+    /// The guest allocation function with signature `(func (param i32 i32 i32 i32) (result i32))` if any.
+    realloc: Option<Func>,
+}
+
+impl<C: AsContextMut> LowerContext<C> {
+    /// Creates a new [`LowerContext`] for the guest `memory` and `realloc` function if any.
+    pub fn new(ctx: C, memory: Option<Memory>, realloc: Option<Func>) -> Self {
+        Self {
+            ctx,
+            memory,
+            realloc,
+        }
+    }
+
+    /// Returns the store context of the [`LowerContext`].
+    pub fn into_context(self) -> C {
+        self.ctx
+    }
+
+    /// Allocates `size` bytes with `align` in guest memory and returns their offset.
+    ///
+    /// # Errors
+    ///
+    /// If the guest does not provide an allocation function or it traps.
+    pub fn alloc(&mut self, size: usize, align: usize) -> Result<usize, Error> {
+        let realloc = self.realloc.ok_or_else(|| missing_option("realloc"))?;
+        let to_i32 =
+            |value: usize| i32::try_from(value).map_err(|_| invalid_value("allocation too large"));
+        let mut ptr = [Value::I32(0)];
+        realloc.call(
+            &mut self.ctx,
+            &[
+                Value::I32(0),
+                Value::I32(0),
+                Value::I32(to_i32(align)?),
+                Value::I32(to_i32(size)?),
+            ],
+            &mut ptr,
+        )?;
+        match ptr[0] {
+            Value::I32(ptr) if (ptr as u32 as usize).is_multiple_of(align) => {
+                Ok(ptr as u32 as usize)
+            }
+            _ => Err(invalid_value("misaligned pointer returned by `realloc`")),
+        }
+    }
+
+    /// Writes `bytes` to `offset` of the guest linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If there is no guest linear memory or the bytes are out of bounds.
+    pub fn write(&mut self, offset: usize, bytes: &[u8]) -> Result<(), Error> {
+        let memory = self.memory.ok_or_else(|| missing_option("memory"))?;
+        memory.write(&mut self.ctx, offset, bytes)?;
+        Ok(())
+    }
+
+    /// Stores the pointer and length pair of a `string` or `list` at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If there is no guest linear memory or the bytes are out of bounds.
+    pub fn store_ptr_len(&mut self, offset: usize, ptr: usize, len: usize) -> Result<(), Error> {
+        self.write(offset, &(ptr as u32).to_le_bytes())?;
+        self.write(offset + 4, &(len as u32).to_le_bytes())
+    }
+
+    /// Lowers the `string` into guest memory and returns its pointer and length.
+    ///
+    /// # Errors
+    ///
+    /// If allocating or writing guest memory failed.
+    pub fn string(&mut self, string: &str) -> Result<(usize, usize), Error> {
+        let ptr = self.alloc(string.len(), 1)?;
+        self.write(ptr, string.as_bytes())?;
+        Ok((ptr, string.len()))
+    }
+
+    /// Allocates a `list` with `len` elements of the given `size` and `align` in guest memory.
+    ///
+    /// Returns the pointer to the `list` whose elements are stored at `ptr + n * size`.
+    ///
+    /// # Errors
+    ///
+    /// If allocating guest memory failed.
+    pub fn list(&mut self, len: usize, size: usize, align: usize) -> Result<usize, Error> {
+        let bytes = size
+            .checked_mul(len)
+            .ok_or_else(|| invalid_value("list too large"))?;
+        self.alloc(bytes, align)
+    }
+}
@@ -0,0 +1,766 @@
+//! Lifting and lowering of component values following the canonical ABI.
+
+use super::{
+    instance::State,
+    types::{FuncType, Type},
+    values::Val,
+    ComponentError,
+};
+use crate::{
+    canonical_abi::{
+        self,
+        align_to,
+        invalid_value as invalid,
+        next_i32,
+        next_ptr_len,
+        next_value,
+        LiftContext,
+        LowerContext,
+        RecordLayout,
+        FLAT_PTR_LEN,
+        MAX_FLAT_PARAMS,
+        MAX_FLAT_RESULTS,
+    },
+    core::{ValueType, F32, F64},
+    AsContextMut,
+    Error,
+    Func,
+    Memory,
+    Value,
+};
+use core::slice;
+use std::{boxed::Box, string::String, vec, vec::Vec};
+
+/// Returns the size in bytes of the discriminant of a variant with `cases` cases.
+fn discriminant_size(cases: usize) -> usize {
+    match cases {
+        0..=0x100 => 1,
+        0x101..=0x1_0000 => 2,
+        _ => 4,
+    }
+}
+
+/// Returns the number of 32-bit words used to represent `flags` flags.
+fn flag_words(flags: usize) -> usize {
+    flags.div_ceil(32)
+}
+
+/// Returns the maximum alignment of the payloads of the `cases`.
+fn max_case_align(cases: &[Option<&Type>]) -> usize {
+    cases
+        .iter()
+        .flatten()
+        .map(|ty| align(ty))
+        .max()
+        .unwrap_or(1)
+}
+
+/// Returns the alignment in bytes of values of type `ty` in linear memory.
+fn align(ty: &Type) -> usize {
+    match ty {
+        Type::Bool | Type::S8 | Type::U8 => 1,
+        Type::S16 | Type::U16 => 2,
+        Type::S32 | Type::U32 | Type::F32 | Type::Char | Type::Own(_) | Type::Borrow(_) => 4,
+        Type::S64 | Type::U64 | Type::F64 => 8,
+        Type::String | Type::List(_) => 4,
+        Type::Record(fields) => fields.iter().map(|(_, ty)| align(ty)).max().unwrap_or(1),
+        Type::Tuple(tys) => tys.iter().map(align).max().unwrap_or(1),
+        Type::Flags(names) => match names.len() {
+            0..=8 => 1,
+            9..=16 => 2,
+            _ => 4,
+        },
+        ty => {
+            let cases = ty.cases().expect("expected a variant-like type");
+            discriminant_size(cases.len()).max(max_case_align(&cases))
+        }
+    }
+}
+
+/// Returns the size in bytes of values of type `ty` in linear memory.
+fn size(ty: &Type) -> usize {
+    match ty {
+        Type::Bool | Type::S8 | Type::U8 => 1,
+        Type::S16 | Type::U16 => 2,
+        Type::S32 | Type::U32 | Type::F32 | Type::Char | Type::Own(_) | Type::Borrow(_) => 4,
+        Type::S64 | Type::U64 | Type::F64 => 8,
+        Type::String | Type::List(_) => 8,
+        Type::Record(fields) => record_size(fields.iter().map(|(_, ty)| ty)),
+        Type::Tuple(tys) => record_size(tys.iter()),
+        Type::Flags(names) => match names.len() {
+            0..=8 => 1,
+            9..=16 => 2,
+            n => 4 * flag_words(n),
+        },
+        ty => {
+            let payload = payload_offset(ty);
+            let cases = ty.cases().expect("expected a variant-like type");
+            let max_size = cases.iter().flatten().map(|ty| size(ty)).max().unwrap_or(0);
+            align_to(payload + max_size, align(ty))
+        }
+    }
+}
+
+/// Returns the size in bytes of a record with fields of the given types.
+fn record_size<'a>(fields: impl Iterator<Item = &'a Type>) -> usize {
+    let mut layout = RecordLayout::new(0);
+    for ty in fields {
+        layout.field(size(ty), align(ty));
+    }
+    layout.size()
+}
+
+/// Returns the offset of the payload of variant-like type `ty` in linear memory.
+fn payload_offset(ty: &Type) -> usize {
+    let cases = ty.cases().expect("expected a variant-like type");
+    align_to(discriminant_size(cases.len()), max_case_align(&cases))
+}
+
+/// Returns the core type that represents both `a` and `b` in flattened variants.
+fn join(a: ValueType, b: ValueType) -> ValueType {
+    match (a, b) {
+        (a, b) if a == b => a,
+        (ValueType::I32, ValueType::F32) | (ValueType::F32, ValueType::I32) => ValueType::I32,
+        _ => ValueType::I64,
+    }
+}
+
+/// Appends the flattened core types of values of type `ty` to `out`.
+fn flatten(ty: &Type, out: &mut Vec<ValueType>) {
+    match ty {
+        Type::Bool
+        | Type::S8
+        | Type::U8
+        | Type::S16
+        | Type::U16
+        | Type::S32
+        | Type::U32
+        | Type::Char
+        | Type::Own(_)
+        | Type::Borrow(_) => out.push(ValueType::I32),
+        Type::S64 | Type::U64 => out.push(ValueType::I64),
+        Type::F32 => out.push(ValueType::F32),
+        Type::F64 => out.push(ValueType::F64),
+        Type::String | Type::List(_) => out.extend(FLAT_PTR_LEN),
+        Type::Record(fields) => fields.iter().for_each(|(_, ty)| flatten(ty, out)),
+        Type::Tuple(tys) => tys.iter().for_each(|ty| flatten(ty, out)),
+        Type::Flags(names) => out.extend((0..flag_words(names.len())).map(|_| ValueType::I32)),
+        ty => {
+            out.push(ValueType::I32);
+            let mut joined: Vec<ValueType> = Vec::new();
+            for case in ty
+                .cases()
+                .expect("expected a variant-like type")
+                .into_iter()
+                .flatten()
+            {
+                let mut flat = Vec::new();
+                flatten(case, &mut flat);
+                for (n, ty) in flat.into_iter().enumerate() {
+                    match joined.get_mut(n) {
+                        Some(joined) => *joined = join(*joined, ty),
+                        None => joined.push(ty),
+                    }
+                }
+            }
+            out.extend(joined);
+        }
+    }
+}
+
+/// Returns the flattened core types of values of all `tys`.
+fn flatten_all<'a>(tys: impl IntoIterator<Item = &'a Type>) -> Vec<ValueType> {
+    let mut out = Vec::new();
+    for ty in tys {
+        flatten(ty, &mut out);
+    }
+    out
+}
+
+/// Returns the core function type of a lifted or lowered function of type `ty`.
+pub fn core_func_type(ty: &FuncType, lower: bool) -> crate::FuncType {
+    let params = flatten_all(ty.params().map(|(_, ty)| ty));
+    let results = flatten_all(ty.result());
+    canonical_abi::core_func_type(params, results, lower)
+}
+
+/// Returns the parameters of `ty` as a tuple type.
+fn params_tuple(ty: &FuncType) -> Type {
+    Type::Tuple(ty.params().map(|(_, ty)| ty.clone()).collect())
+}
+
+/// Converts a `value` of a flattened variant to the core type `ty` of its case.
+fn narrow(value: Value, ty: ValueType) -> Value {
+    match (value, ty) {
+        (Value::I64(x), ValueType::I32) => Value::I32(x as i32),
+        (Value::I64(x), ValueType::F32) => Value::F32(F32::from_bits(x as u32)),
+        (Value::I64(x), ValueType::F64) => Value::F64(F64::from_bits(x as u64)),
+        (Value::I32(x), ValueType::F32) => Value::F32(F32::from_bits(x as u32)),
+        (value, _) => value,
+    }
+}
+
+/// Converts a `value` of a variant case to the joined core type `ty` of the flattened variant.
+fn widen(value: Value, ty: ValueType) -> Value {
+    match (value, ty) {
+        (Value::I32(x), ValueType::I64) => Value::I64(i64::from(x as u32)),
+        (Value::F32(x), ValueType::I32) => Value::I32(x.to_bits() as i32),
+        (Value::F32(x), ValueType::I64) => Value::I64(i64::from(x.to_bits())),
+        (Value::F64(x), ValueType::I64) => Value::I64(x.to_bits() as i64),
+        (value, _) => value,
+    }
+}
+
+/// Returns the index and payload of the case of the variant-like `val` of type `ty`.
+fn case_of<'a>(ty: &Type, val: &'a Val) -> Option<(usize, Option<&'a Val>)> {
+    match (ty, val) {
+        (Type::Variant(cases), Val::Variant(name, payload)) => cases
+            .iter()
+            .position(|(case, _)| case == name)
+            .map(|index| (index, payload.as_deref())),
+        (Type::Enum(cases), Val::Enum(name)) => cases
+            .iter()
+            .position(|case| case == name)
+            .map(|index| (index, None)),
+        (Type::Option(_), Val::Option(None)) => Some((0, None)),
+        (Type::Option(_), Val::Option(Some(payload))) => Some((1, Some(payload))),
+        (Type::Result { .. }, Val::Result(Ok(payload))) => Some((0, payload.as_deref())),
+        (Type::Result { .. }, Val::Result(Err(payload))) => Some((1, payload.as_deref())),
+        _ => None,
+    }
+}
+
+/// Creates the variant-like value of type `ty` for the case at `index` with `payload`.
+fn make_case(ty: &Type, index: usize, payload: Option<Val>) -> Val {
+    let payload = payload.map(Box::new);
+    match ty {
+        Type::Variant(cases) => Val::Variant(cases[index].0.clone(), payload),
+        Type::Enum(cases) => Val::Enum(cases[index].clone()),
+        Type::Option(_) => Val::Option(payload),
+        Type::Result { .. } if index == 0 => Val::Result(Ok(payload)),
+        _ => Val::Result(Err(payload)),
+    }
+}
+
+/// Returns a [`ComponentError::TypeMismatch`] for a value not matching `ty`.
+fn mismatch(ty: &Type) -> Error {
+    ComponentError::TypeMismatch {
+        expected: ty.clone(),
+    }
+    .into()
+}
+
+/// Returns the next flattened core value as `i64`.
+fn next_i64(values: &mut slice::Iter<Value>) -> Result<i64, Error> {
+    match next_value(values)? {
+        Value::I64(value) => Ok(value),
+        _ => Err(invalid("expected an i64 value")),
+    }
+}
+
+/// Lifts component values from their canonical ABI representation.
+pub struct Lift<'a> {
+    /// The linear memory of the canonical options if any.
+    cx: LiftContext<'a>,
+    /// The state of the component instance.
+    state: &'a State,
+}
+
+impl<'a> Lift<'a> {
+    /// Creates a new [`Lift`] context.
+    pub fn new(memory: Option<&'a [u8]>, state: &'a State) -> Self {
+        Self {
+            cx: LiftContext::new(memory),
+            state,
+        }
+    }
+
+    /// Lifts the parameters of a function of type `ty` from the core `args`.
+    pub fn params(&self, ty: &FuncType, args: &[Value]) -> Result<Vec<Val>, Error> {
+        let flat = flatten_all(ty.params().map(|(_, ty)| ty));
+        let mut args = args.iter();
+        if flat.len() > MAX_FLAT_PARAMS {
+            let ptr = next_i32(&mut args)? as u32 as usize;
+            return match self.load(&params_tuple(ty), ptr)? {
+                Val::Tuple(params) => Ok(params),
+                _ => unreachable!("loaded a tuple type as non-tuple value"),
+            };
+        }
+        ty.params()
+            .map(|(_, ty)| self.flat(ty, &mut args))
+            .collect()
+    }
+
+    /// Lifts the result of a function of type `ty` from the core `results`.
+    pub fn result(&self, ty: &FuncType, results: &[Value]) -> Result<Option<Val>, Error> {
+        let Some(result) = ty.result() else {
+            return Ok(None);
+        };
+        let mut results = results.iter();
+        if flatten_all([result]).len() > MAX_FLAT_RESULTS {
+            let ptr = next_i32(&mut results)? as u32 as usize;
+            return self.load(result, ptr).map(Some);
+        }
+        self.flat(result, &mut results).map(Some)
+    }
+
+    /// Returns the `N` bytes of linear memory at `ptr`.
+    fn array<const N: usize>(&self, ptr: usize) -> Result<[u8; N], Error> {
+        self.cx.array(ptr)
+    }
+
+    /// Lifts a `string` from linear memory.
+    fn string(&self, ptr: usize, len: usize) -> Result<Val, Error> {
+        let string = self.cx.string(ptr, len)?;
+        Ok(Val::String(String::from(string)))
+    }
+
+    /// Lifts a `list` with elements of type `elem` from linear memory.
+    fn list(&self, elem: &Type, ptr: usize, len: usize) -> Result<Val, Error> {
+        let size = size(elem);
+        self.cx.list(ptr, len, size, align(elem))?;
+        (0..len)
+            .map(|n| self.load(elem, ptr + n * size))
+            .collect::<Result<_, _>>()
+            .map(Val::List)
+    }
+
+    /// Lifts the handle to a resource of type `ty`.
+    fn handle(&self, ty: &Type, handle: u32) -> Result<Val, Error> {
+        match ty {
+            Type::Own(resource) => self.state.lift_own(*resource, handle).map(Val::Own),
+            Type::Borrow(resource) => self.state.lift_borrow(*resource, handle).map(Val::Borrow),
+            _ => unreachable!("expected a handle type"),
+        }
+    }
+
+    /// Lifts the set `flags` of type `ty` from their bits.
+    fn flags(names: &[String], words: impl IntoIterator<Item = u32>) -> Val {
+        let flags = words
+            .into_iter()
+            .enumerate()
+            .flat_map(|(n, bits)| {
+                (0..32)
+                    .filter(move |bit| bits & (1 << bit) != 0)
+                    .map(move |bit| n * 32 + bit)
+            })
+            .filter_map(|index| names.get(index).cloned())
+            .collect();
+        Val::Flags(flags)
+    }
+
+    /// Lifts a value of type `ty` from the flattened core `values`.
+    fn flat(&self, ty: &Type, values: &mut slice::Iter<Value>) -> Result<Val, Error> {
+        let val = match ty {
+            Type::Bool => Val::Bool(next_i32(values)? != 0),
+            Type::S8 => Val::S8(next_i32(values)? as i8),
+            Type::U8 => Val::U8(next_i32(values)? as u8),
+            Type::S16 => Val::S16(next_i32(values)? as i16),
+            Type::U16 => Val::U16(next_i32(values)? as u16),
+            Type::S32 => Val::S32(next_i32(values)?),
+            Type::U32 => Val::U32(next_i32(values)? as u32),
+            Type::S64 => Val::S64(next_i64(values)?),
+            Type::U64 => Val::U64(next_i64(values)? as u64),
+            Type::F32 => match next_value(values)? {
+                Value::F32(value) => Val::F32(value.to_float()),
+                _ => return Err(invalid("expected an f32 value")),
+            },
+            Type::F64 => match next_value(values)? {
+                Value::F64(value) => Val::F64(value.to_float()),
+                _ => return Err(invalid("expected an f64 value")),
+            },
+            Type::Char => char::from_u32(next_i32(values)? as u32)
+                .map(Val::Char)
+                .ok_or_else(|| invalid("invalid char"))?,
+            Type::String => {
+                let (ptr, len) = next_ptr_len(values)?;
+                self.string(ptr, len)?
+            }
+            Type::List(elem) => {
+                let (ptr, len) = next_ptr_len(values)?;
+                self.list(elem, ptr, len)?
+            }
+            Type::Record(fields) => Val::Record(
+                fields
+                    .iter()
+                    .map(|(name, ty)| Ok((name.clone(), self.flat(ty, values)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Type::Tuple(tys) => Val::Tuple(
+                tys.iter()
+                    .map(|ty| self.flat(ty, values))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Type::Flags(names) => {
+                let words = (0..flag_words(names.len()))
+                    .map(|_| next_i32(values).map(|bits| bits as u32))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::flags(names, words)
+            }
+            Type::Own(_) | Type::Borrow(_) => self.handle(ty, next_i32(values)? as u32)?,
+            ty => {
+                let cases = ty.cases().expect("expected a variant-like type");
+                let index = next_i32(values)? as u32 as usize;
+                let mut flat = Vec::new();
+                flatten(ty, &mut flat);
+                let joined = flat[1..]
+                    .iter()
+                    .map(|_| next_value(values))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let case = cases
+                    .get(index)
+                    .ok_or_else(|| invalid("invalid variant discriminant"))?;
+                let payload = match case {
+                    Some(case) => {
+                        let case_flat = flatten_all([*case]);
+                        let narrowed: Vec<Value> = joined
+                            .into_iter()
+                            .zip(case_flat)
+                            .map(|(value, ty)| narrow(value, ty))
+                            .collect();
+                        Some(self.flat(case, &mut narrowed.iter())?)
+                    }
+                    None => None,
+                };
+                make_case(ty, index, payload)
+            }
+        };
+        Ok(val)
+    }
+
+    /// Loads a value of type `ty` from linear memory at `ptr`.
+    fn load(&self, ty: &Type, ptr: usize) -> Result<Val, Error> {
+        let val = match ty {
+            Type::Bool => Val::Bool(self.array::<1>(ptr)?[0] != 0),
+            Type::S8 => Val::S8(i8::from_le_bytes(self.array(ptr)?)),
+            Type::U8 => Val::U8(u8::from_le_bytes(self.array(ptr)?)),
+            Type::S16 => Val::S16(i16::from_le_bytes(self.array(ptr)?)),
+            Type::U16 => Val::U16(u16::from_le_bytes(self.array(ptr)?)),
+            Type::S32 => Val::S32(i32::from_le_bytes(self.array(ptr)?)),
+            Type::U32 => Val::U32(u32::from_le_bytes(self.array(ptr)?)),
+            Type::S64 => Val::S64(i64::from_le_bytes(self.array(ptr)?)),
+            Type::U64 => Val::U64(u64::from_le_bytes(self.array(ptr)?)),
+            Type::F32 => Val::F32(f32::from_le_bytes(self.array(ptr)?)),
+            Type::F64 => Val::F64(f64::from_le_bytes(self.array(ptr)?)),
+            Type::Char => char::from_u32(u32::from_le_bytes(self.array(ptr)?))
+                .map(Val::Char)
+                .ok_or_else(|| invalid("invalid char"))?,
+            Type::String | Type::List(_) => {
+                let (data, len) = self.cx.ptr_len(ptr)?;
+                match ty {
+                    Type::List(elem) => self.list(elem, data, len)?,
+                    _ => self.string(data, len)?,
+                }
+            }
+            Type::Record(fields) => {
+                let mut layout = RecordLayout::new(ptr);
+                let mut vals = Vec::with_capacity(fields.len());
+                for (name, ty) in fields {
+                    let offset = layout.field(size(ty), align(ty));
+                    vals.push((name.clone(), self.load(ty, offset)?));
+                }
+                Val::Record(vals)
+            }
+            Type::Tuple(tys) => {
+                let mut layout = RecordLayout::new(ptr);
+                let mut vals = Vec::with_capacity(tys.len());
+                for ty in tys {
+                    vals.push(self.load(ty, layout.field(size(ty), align(ty)))?);
+                }
+                Val::Tuple(vals)
+            }
+            Type::Flags(names) => match names.len() {
+                0..=8 => Self::flags(names, [u32::from(self.array::<1>(ptr)?[0])]),
+                9..=16 => Self::flags(names, [u32::from(u16::from_le_bytes(self.array(ptr)?))]),
+                n => {
+                    let words = (0..flag_words(n))
+                        .map(|word| self.array(ptr + 4 * word).map(u32::from_le_bytes))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Self::flags(names, words)
+                }
+            },
+            Type::Own(_) | Type::Borrow(_) => {
+                self.handle(ty, u32::from_le_bytes(self.array(ptr)?))?
+            }
+            ty => {
+                let cases = ty.cases().expect("expected a variant-like type");
+                let index = match discriminant_size(cases.len()) {
+                    1 => usize::from(self.array::<1>(ptr)?[0]),
+                    2 => usize::from(u16::from_le_bytes(self.array(ptr)?)),
+                    _ => u32::from_le_bytes(self.array(ptr)?) as usize,
+                };
+                let case = cases
+                    .get(index)
+                    .ok_or_else(|| invalid("invalid variant discriminant"))?;
+                let payload = match case {
+                    Some(case) => Some(self.load(case, ptr + payload_offset(ty))?),
+                    None => None,
+                };
+                make_case(ty, index, payload)
+            }
+        };
+        Ok(val)
+    }
+}
+
+/// Lowers component values into their canonical ABI representation.
+pub struct Lower<'a, C> {
+    /// The store context with the linear memory and `realloc` function of the canonical options.
+    cx: LowerContext<C>,
+    /// The state of the component instance.
+    state: &'a State,
+    /// The temporary handles of borrowed resources lent to the guest.
+    borrows: Vec<u32>,
+}
+
+impl<'a, C: AsContextMut> Lower<'a, C> {
+    /// Creates a new [`Lower`] context.
+    pub fn new(ctx: C, memory: Option<Memory>, realloc: Option<Func>, state: &'a State) -> Self {
+        Self {
+            cx: LowerContext::new(ctx, memory, realloc),
+            state,
+            borrows: Vec::new(),
+        }
+    }
+
+    /// Returns the store context and the temporary handles of borrowed resources.
+    pub fn finish(self) -> (C, Vec<u32>) {
+        (self.cx.into_context(), self.borrows)
+    }
+
+    /// Lowers the `params` of a function of type `ty` to core arguments.
+    pub fn params(&mut self, ty: &FuncType, params: &[Val]) -> Result<Vec<Value>, Error> {
+        let flat = flatten_all(ty.params().map(|(_, ty)| ty));
+        if flat.len() > MAX_FLAT_PARAMS {
+            let tuple = params_tuple(ty);
+            let ptr = self.cx.alloc(size(&tuple), align(&tuple))?;
+            self.store(&Val::Tuple(params.to_vec()), &tuple, ptr)?;
+            return Ok(vec![Value::I32(ptr as i32)]);
+        }
+        let mut args = Vec::with_capacity(flat.len());
+        for ((_, ty), param) in ty.params().zip(params) {
+            self.flat(param, ty, &mut args)?;
+        }
+        Ok(args)
+    }
+
+    /// Lowers the `result` of a function of type `ty` to the core `results`.
+    ///
+    /// The `args` are the core arguments passed to the lowered function which
+    /// provide the pointer to store the result at if it is not returned as core result.
+    pub fn result(
+        &mut self,
+        ty: &FuncType,
+        result: Option<&Val>,
+        args: &[Value],
+        results: &mut [Value],
+    ) -> Result<(), Error> {
+        let (Some(ty), Some(result)) = (ty.result(), result) else {
+            return Ok(());
+        };
+        if flatten_all([ty]).len() > MAX_FLAT_RESULTS {
+            let ptr = match args.last() {
+                Some(Value::I32(ptr)) => *ptr as u32 as usize,
+                _ => return Err(invalid("missing result pointer")),
+            };
+            if !ptr.is_multiple_of(align(ty)) {
+                return Err(invalid("misaligned result pointer"));
+            }
+            return self.store(result, ty, ptr);
+        }
+        let mut flat = Vec::with_capacity(1);
+        self.flat(result, ty, &mut flat)?;
+        for (result, value) in results.iter_mut().zip(flat) {
+            *result = value;
+        }
+        Ok(())
+    }
+
+    /// Writes `bytes` to linear memory at `ptr`.
+    fn write(&mut self, ptr: usize, bytes: &[u8]) -> Result<(), Error> {
+        self.cx.write(ptr, bytes)
+    }
+
+    /// Lowers a `list` with elements of type `elem` into linear memory and returns its pointer and length.
+    fn list(&mut self, elem: &Type, vals: &[Val]) -> Result<(usize, usize), Error> {
+        let size = size(elem);
+        let ptr = self.cx.list(vals.len(), size, align(elem))?;
+        for (n, val) in vals.iter().enumerate() {
+            self.store(val, elem, ptr + n * size)?;
+        }
+        Ok((ptr, vals.len()))
+    }
+
+    /// Lowers the handle to a resource of type `ty`.
+    fn handle(&mut self, ty: &Type, rep: u32) -> u32 {
+        match ty {
+            Type::Own(resource) => self.state.lower_own(*resource, rep),
+            Type::Borrow(resource) => {
+                let (handle, temporary) = self.state.lower_borrow(*resource, rep);
+                if temporary {
+                    self.borrows.push(handle);
+                }
+                handle
+            }
+            _ => unreachable!("expected a handle type"),
+        }
+    }
+
+    /// Returns the bits of the set `flags` of type `ty` in 32-bit words.
+    fn flags(ty: &Type, names: &[String], flags: &[String]) -> Result<Vec<u32>, Error> {
+        let mut words = vec![0_u32; flag_words(names.len())];
+        for flag in flags {
+            let index = names
+                .iter()
+                .position(|name| name == flag)
+                .ok_or_else(|| mismatch(ty))?;
+            words[index / 32] |= 1 << (index % 32);
+        }
+        Ok(words)
+    }
+
+    /// Lowers the `val` of type `ty` to flattened core values appended to `out`.
+    fn flat(&mut self, val: &Val, ty: &Type, out: &mut Vec<Value>) -> Result<(), Error> {
+        match (ty, val) {
+            (Type::Bool, Val::Bool(x)) => out.push(Value::I32(i32::from(*x))),
+            (Type::S8, Val::S8(x)) => out.push(Value::I32(i32::from(*x))),
+            (Type::U8, Val::U8(x)) => out.push(Value::I32(i32::from(*x))),
+            (Type::S16, Val::S16(x)) => out.push(Value::I32(i32::from(*x))),
+            (Type::U16, Val::U16(x)) => out.push(Value::I32(i32::from(*x))),
+            (Type::S32, Val::S32(x)) => out.push(Value::I32(*x)),
+            (Type::U32, Val::U32(x)) => out.push(Value::I32(*x as i32)),
+            (Type::S64, Val::S64(x)) => out.push(Value::I64(*x)),
+            (Type::U64, Val::U64(x)) => out.push(Value::I64(*x as i64)),
+            (Type::F32, Val::F32(x)) => out.push(Value::F32(F32::from_float(*x))),
+            (Type::F64, Val::F64(x)) => out.push(Value::F64(F64::from_float(*x))),
+            (Type::Char, Val::Char(x)) => out.push(Value::I32(*x as i32)),
+            (Type::String, Val::String(x)) => {
+                let (ptr, len) = self.cx.string(x)?;
+                out.extend([Value::I32(ptr as i32), Value::I32(len as i32)]);
+            }
+            (Type::List(elem), Val::List(vals)) => {
+                let (ptr, len) = self.list(elem, vals)?;
+                out.extend([Value::I32(ptr as i32), Value::I32(len as i32)]);
+            }
+            (Type::Record(fields), Val::Record(vals)) if fields.len() == vals.len() => {
+                for ((name, ty), (field, val)) in fields.iter().zip(vals) {
+                    if name != field {
+                        return Err(mismatch(ty));
+                    }
+                    self.flat(val, ty, out)?;
+                }
+            }
+            (Type::Tuple(tys), Val::Tuple(vals)) if tys.len() == vals.len() => {
+                for (ty, val) in tys.iter().zip(vals) {
+                    self.flat(val, ty, out)?;
+                }
+            }
+            (Type::Flags(names), Val::Flags(flags)) => {
+                let words = Self::flags(ty, names, flags)?;
+                out.extend(words.into_iter().map(|bits| Value::I32(bits as i32)));
+            }
+            (Type::Own(_), Val::Own(rep)) | (Type::Borrow(_), Val::Borrow(rep)) => {
+                let handle = self.handle(ty, *rep);
+                out.push(Value::I32(handle as i32));
+            }
+            (ty, val) => {
+                let (index, payload) = case_of(ty, val).ok_or_else(|| mismatch(ty))?;
+                let cases = ty.cases().expect("expected a variant-like type");
+                let mut flat = Vec::new();
+                flatten(ty, &mut flat);
+                out.push(Value::I32(index as i32));
+                let mut case_values = Vec::new();
+                match (cases[index], payload) {
+                    (Some(case), Some(payload)) => self.flat(payload, case, &mut case_values)?,
+                    (None, None) => {}
+                    _ => return Err(mismatch(ty)),
+                }
+                let mut case_values = case_values.into_iter();
+                for joined in flat[1..].iter().copied() {
+                    let value = match case_values.next() {
+                        Some(value) => widen(value, joined),
+                        None => Value::default(joined),
+                    };
+                    out.push(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores the `val` of type `ty` into linear memory at `ptr`.
+    fn store(&mut self, val: &Val, ty: &Type, ptr: usize) -> Result<(), Error> {
+        match (ty, val) {
+            (Type::Bool, Val::Bool(x)) => self.write(ptr, &[u8::from(*x)]),
+            (Type::S8, Val::S8(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::U8, Val::U8(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::S16, Val::S16(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::U16, Val::U16(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::S32, Val::S32(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::U32, Val::U32(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::S64, Val::S64(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::U64, Val::U64(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::F32, Val::F32(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::F64, Val::F64(x)) => self.write(ptr, &x.to_le_bytes()),
+            (Type::Char, Val::Char(x)) => self.write(ptr, &u32::from(*x).to_le_bytes()),
+            (Type::String, Val::String(_)) | (Type::List(_), Val::List(_)) => {
+                let (data, len) = match (ty, val) {
+                    (Type::List(elem), Val::List(vals)) => self.list(elem, vals)?,
+                    (_, Val::String(string)) => self.cx.string(string)?,
+                    _ => unreachable!(),
+                };
+                self.cx.store_ptr_len(ptr, data, len)
+            }
+            (Type::Record(fields), Val::Record(vals)) if fields.len() == vals.len() => {
+                let mut layout = RecordLayout::new(ptr);
+                for ((name, ty), (field, val)) in fields.iter().zip(vals) {
+                    if name != field {
+                        return Err(mismatch(ty));
+                    }
+                    self.store(val, ty, layout.field(size(ty), align(ty)))?;
+                }
+                Ok(())
+            }
+            (Type::Tuple(tys), Val::Tuple(vals)) if tys.len() == vals.len() => {
+                let mut layout = RecordLayout::new(ptr);
+                for (ty, val) in tys.iter().zip(vals) {
+                    self.store(val, ty, layout.field(size(ty), align(ty)))?;
+                }
+                Ok(())
+            }
+            (Type::Flags(names), Val::Flags(flags)) => {
+                let words = Self::flags(ty, names, flags)?;
+                match names.len() {
+                    0..=8 => self.write(ptr, &[words[0] as u8]),
+                    9..=16 => self.write(ptr, &(words[0] as u16).to_le_bytes()),
+                    _ => {
+                        for (n, bits) in words.into_iter().enumerate() {
+                            self.write(ptr + 4 * n, &bits.to_le_bytes())?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            (Type::Own(_), Val::Own(rep)) | (Type::Borrow(_), Val::Borrow(rep)) => {
+                let handle = self.handle(ty, *rep);
+                self.write(ptr, &handle.to_le_bytes())
+            }
+            (ty, val) => {
+                let (index, payload) = case_of(ty, val).ok_or_else(|| mismatch(ty))?;
+                let cases = ty.cases().expect("expected a variant-like type");
+                match discriminant_size(cases.len()) {
+                    1 => self.write(ptr, &[index as u8])?,
+                    2 => self.write(ptr, &(index as u16).to_le_bytes())?,
+                    _ => self.write(ptr, &(index as u32).to_le_bytes())?,
+                }
+                match (cases[index], payload) {
+                    (Some(case), Some(payload)) => {
+                        self.store(payload, case, ptr + payload_offset(ty))
+                    }
+                    (None, None) => Ok(()),
+                    _ => Err(mismatch(ty)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use super::{
+    abi::{core_func_type, Lift, Lower},
+    linker::{Definition, Linker},
+    parse::{
+        Component,
+        CoreExport,
+        CoreFunc,
+        CoreInstanceDef,
+        CoreItem,
+        ExportDef,
+        FuncDef,
+        InstanceDef,
+        Options,
+        ResourceDef,
+    },
+    types::{FuncType, ResourceType},
+    values::Val,
+    ComponentError,
+};
+use crate::{
+    core::ValueType,
+    errors::FuncError,
+    AsContext,
+    AsContextMut,
+    Caller,
+    Error,
+    Extern,
+    Func,
+    Instance,
+    Memory,
+    Value,
+};
+use spin::{Mutex, RwLock};
+use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+
+/// An entry of the resource handle table of a component instance.
+#[derive(Debug, Copy, Clone)]
+struct Handle {
+    /// The resource type of the handle.
+    resource: ResourceType,
+    /// The representation of the resource.
+    rep: u32,
+    /// `true` if the handle owns the resource.
+    own: bool,
+}
+
+/// The resource handles held by a component instance.
+///
+/// Handle `0` is never used so that it can serve as sentinel in guest code.
+#[derive(Debug, Default)]
+struct HandleTable {
+    /// The handle slots indexed by handle minus one.
+    slots: Vec<Option<Handle>>,
+    /// The indices of free slots.
+    free: Vec<usize>,
+}
+
+impl HandleTable {
+    /// Inserts `handle` and returns its index.
+    fn insert(&mut self, handle: Handle) -> u32 {
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(handle);
+                index
+            }
+            None => {
+                self.slots.push(Some(handle));
+                self.slots.len() - 1
+            }
+        };
+        index as u32 + 1
+    }
+
+    /// Returns the handle at `index` if it refers to `resource`.
+    fn get(&self, resource: ResourceType, index: u32) -> Result<Handle, Error> {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.slots.get(index as usize))
+            .copied()
+            .flatten()
+            .filter(|handle| handle.resource == resource)
+            .ok_or_else(|| ComponentError::InvalidHandle { handle: index }.into())
+    }
+
+    /// Removes the handle at `index` if it refers to `resource`.
+    fn remove(&mut self, resource: ResourceType, index: u32) -> Result<Handle, Error> {
+        let handle = self.get(resource, index)?;
+        self.slots[index as usize - 1] = None;
+        self.free.push(index as usize - 1);
+        Ok(handle)
+    }
+}
+
+/// The runtime state shared by the functions of a component instance.
+#[derive(Debug)]
+pub struct State {
+    /// The instantiated component.
+    component: Component,
+    /// The resource handles held by the component instance.
+    handles: Mutex<HandleTable>,
+    /// The destructors of the resources defined by the component instance.
+    dtors: RwLock<Vec<Option<Func>>>,
+}
+
+impl State {
+    /// Returns `true` if `resource` is defined by the component itself.
+    fn is_defined(&self, resource: ResourceType) -> bool {
+        matches!(
+            self.component.inner().resources[resource.0 as usize],
+            ResourceDef::Defined { .. }
+        )
+    }
+
+    /// Takes the owned `handle` of `resource` and returns its representation.
+    pub fn lift_own(&self, resource: ResourceType, handle: u32) -> Result<u32, Error> {
+        let mut handles = self.handles.lock();
+        match handles.get(resource, handle)? {
+            Handle { own: true, .. } => handles.remove(resource, handle).map(|handle| handle.rep),
+            _ => Err(ComponentError::InvalidHandle { handle }.into()),
+        }
+    }
+
+    /// Returns the representation of the borrowed `handle` of `resource`.
+    pub fn lift_borrow(&self, resource: ResourceType, handle: u32) -> Result<u32, Error> {
+        self.handles
+            .lock()
+            .get(resource, handle)
+            .map(|handle| handle.rep)
+    }
+
+    /// Returns a new owned handle to the resource `rep` of type `resource`.
+    pub fn lower_own(&self, resource: ResourceType, rep: u32) -> u32 {
+        self.handles.lock().insert(Handle {
+            resource,
+            rep,
+            own: true,
+        })
+    }
+
+    /// Returns a borrowed handle to the resource `rep` of type `resource`.
+    ///
+    /// Resources defined by the component are borrowed by their representation.
+    /// Otherwise a temporary handle is created and `true` is returned alongside it.
+    pub fn lower_borrow(&self, resource: ResourceType, rep: u32) -> (u32, bool) {
+        if self.is_defined(resource) {
+            return (rep, false);
+        }
+        let handle = self.handles.lock().insert(Handle {
+            resource,
+            rep,
+            own: false,
+        });
+        (handle, true)
+    }
+
+    /// Releases the temporary borrowed `handles` after a call has returned.
+    pub fn release(&self, handles: &[u32]) {
+        let mut table = self.handles.lock();
+        for &handle in handles {
+            if let Some(Handle { own: false, .. }) = table.slots[handle as usize - 1] {
+                table.slots[handle as usize - 1] = None;
+                table.free.push(handle as usize - 1);
+            }
+        }
+    }
+}
+
+/// A core instance of a component instance.
+#[derive(Debug)]
+enum CoreInstance {
+    /// An instantiated core module.
+    Instance(Instance),
+    /// A bundle of individual core items.
+    Exports(BTreeMap<String, Extern>),
+}
+
+/// A function exported by a [`ComponentInstance`].
+#[derive(Debug, Clone)]
+pub struct ComponentFunc {
+    /// The type of the function.
+    ty: Arc<FuncType>,
+    /// The lifted core function.
+    func: Func,
+    /// The `memory` canonical option if any.
+    memory: Option<Memory>,
+    /// The `realloc` canonical option if any.
+    realloc: Option<Func>,
+    /// The `post-return` canonical option if any.
+    post_return: Option<Func>,
+    /// The state of the component instance.
+    state: Arc<State>,
+}
+
+impl ComponentFunc {
+    /// Returns the [`FuncType`] of the [`ComponentFunc`].
+    pub fn ty(&self) -> &FuncType {
+        &self.ty
+    }
+
+    /// Calls the [`ComponentFunc`] with the given `params` and writes its result to `results`.
+    ///
+    /// # Errors
+    ///
+    /// - If the number of `params` or `results` does not match the [`FuncType`].
+    /// - If any of the `params` does not match its parameter type.
+    /// - If the called Wasm code traps.
+    /// - If the returned result is not a valid canonical ABI value.
+    pub fn call(
+        &self,
+        mut ctx: impl AsContextMut,
+        params: &[Val],
+        results: &mut [Val],
+    ) -> Result<(), Error> {
+        if params.len() != self.ty.params().len() {
+            return Err(FuncError::MismatchingParameterLen.into());
+        }
+        if results.len() != usize::from(self.ty.result().is_some()) {
+            return Err(FuncError::MismatchingResultLen.into());
+        }
+        let mut lower = Lower::new(&mut ctx, self.memory, self.realloc, &self.state);
+        let args = lower.params(&self.ty, params)?;
+        let (_, borrows) = lower.finish();
+        let core_ty = core_func_type(&self.ty, false);
+        let mut core_results: Vec<Value> = core_ty
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect();
+        let outcome = self.func.call(&mut ctx, &args, &mut core_results);
+        self.state.release(&borrows);
+        outcome?;
+        let memory = self
+            .memory
+            .map(|memory| memory.try_data(ctx.as_context()))
+            .transpose()?;
+        let result = Lift::new(memory, &self.state).result(&self.ty, &core_results)?;
+        if let (Some(slot), Some(result)) = (results.first_mut(), result) {
+            *slot = result;
+        }
+        if let Some(post_return) = self.post_return {
+            post_return.call(&mut ctx, &core_results, &mut [])?;
+        }
+        Ok(())
+    }
+}
+
+/// An instantiated [`Component`].
+#[derive(Debug, Clone)]
+pub struct ComponentInstance {
+    /// The functions exported at the top-level.
+    funcs: BTreeMap<String, ComponentFunc>,
+    /// The functions of the exported instances.
+    instances: BTreeMap<String, BTreeMap<String, ComponentFunc>>,
+}
+
+impl ComponentInstance {
+    /// Returns the function exported under `name` if any.
+    pub fn get_func(&self, name: &str) -> Option<&ComponentFunc> {
+        self.funcs.get(name)
+    }
+
+    /// Returns the function `name` of the instance exported under `instance` if any.
+    pub fn get_instance_func(&self, instance: &str, name: &str) -> Option<&ComponentFunc> {
+        self.instances.get(instance)?.get(name)
+    }
+
+    /// Returns the names of the exported functions and instances.
+    pub fn exports(&self) -> impl Iterator<Item = &str> {
+        self.funcs
+            .keys()
+            .chain(self.instances.keys())
+            .map(String::as_str)
+    }
+}
+
+/// Instantiates components for a [`Linker`].
+pub(super) struct Instantiator<'a, T> {
+    /// The linker providing the host definitions.
+    linker: &'a Linker<T>,
+    /// The state of the new component instance.
+    state: Arc<State>,
+    /// The core instances in order of definition.
+    core_instances: Vec<CoreInstance>,
+}
+
+impl<'a, T: 'static> Instantiator<'a, T> {
+    /// Creates a new [`Instantiator`] for `component`.
+    pub fn new(linker: &'a Linker<T>, component: &Component) -> Self {
+        let resources = component.inner().resources.len();
+        Self {
+            linker,
+            state: Arc::new(State {
+                component: component.clone(),
+                handles: Mutex::new(HandleTable::default()),
+                dtors: RwLock::new(vec![None; resources]),
+            }),
+            core_instances: Vec::new(),
+        }
+    }
+
+    /// Instantiates the component in `ctx`.
+    pub fn run(
+        mut self,
+        mut ctx: impl AsContextMut<UserState = T>,
+    ) -> Result<ComponentInstance, Error> {
+        let component = self.state.component.clone();
+        let inner = component.inner();
+        for def in &inner.core_instances {
+            let instance = match def {
+                CoreInstanceDef::Instantiate { module, args } => {
+                    let mut linker = <crate::Linker<T>>::new(&inner.engine);
+                    for (name, instance) in args {
+                        for (export, item) in self.core_exports(&ctx, *instance) {
+                            linker.define(name, &export, item)?;
+                        }
+                    }
+                    let instance = linker
+                        .instantiate(&mut ctx, &inner.modules[*module as usize])?
+                        .start(&mut ctx)?;
+                    CoreInstance::Instance(instance)
+                }
+                CoreInstanceDef::Exports(items) => {
+                    let mut exports = BTreeMap::new();
+                    for (name, item) in items {
+                        let item = match item {
+                            CoreItem::Func(func) => self.core_func(&mut ctx, func)?.into(),
+                            CoreItem::Table(export)
+                            | CoreItem::Memory(export)
+                            | CoreItem::Global(export) => self.core_export(&ctx, export)?,
+                        };
+                        exports.insert(name.clone(), item);
+                    }
+                    CoreInstance::Exports(exports)
+                }
+            };
+            self.core_instances.push(instance);
+        }
+        for (index, def) in inner.resources.iter().enumerate() {
+            if let ResourceDef::Defined { dtor: Some(dtor) } = def {
+                let dtor = self.core_export_func(&ctx, dtor)?;
+                self.state.dtors.write()[index] = Some(dtor);
+            }
+        }
+        let mut instance = ComponentInstance {
+            funcs: BTreeMap::new(),
+            instances: BTreeMap::new(),
+        };
+        for (name, def) in &inner.exports {
+            match def {
+                ExportDef::Func(func) => {
+                    let func = self.lift(&ctx, func)?;
+                    instance.funcs.insert(name.clone(), func);
+                }
+                ExportDef::Instance(InstanceDef::Exports(items)) => {
+                    let mut funcs = BTreeMap::new();
+                    for (export, def) in items {
+                        if let ExportDef::Func(func) = def {
+                            funcs.insert(export.clone(), self.lift(&ctx, func)?);
+                        }
+                    }
+                    instance.instances.insert(name.clone(), funcs);
+                }
+                ExportDef::Instance(InstanceDef::Import { .. }) => {
+                    return Err(ComponentError::Unsupported {
+                        offset: 0,
+                        feature: "re-exporting imported instances",
+                    }
+                    .into())
+                }
+                ExportDef::Type(_) => {}
+            }
+        }
+        Ok(instance)
+    }
+
+    /// Returns the exports of the core instance at `index`.
+    fn core_exports(&self, ctx: &impl AsContext, index: u32) -> Vec<(String, Extern)> {
+        match &self.core_instances[index as usize] {
+            CoreInstance::Instance(instance) => instance
+                .exports(ctx.as_context())
+                .map(|export| (export.name().to_string(), export.into_extern()))
+                .collect(),
+            CoreInstance::Exports(exports) => exports
+                .iter()
+                .map(|(name, item)| (name.clone(), *item))
+                .collect(),
+        }
+    }
+
+    /// Resolves the core `export`.
+    fn core_export(&self, ctx: &impl AsContext, export: &CoreExport) -> Result<Extern, Error> {
+        let item = match &self.core_instances[export.instance as usize] {
+            CoreInstance::Instance(instance) => instance.get_export(ctx.as_context(), &export.name),
+            CoreInstance::Exports(exports) => exports.get(&export.name).copied(),
+        };
+        item.ok_or_else(|| {
+            ComponentError::MissingCoreExport {
+                name: export.name.clone(),
+            }
+            .into()
+        })
+    }
+
+    /// Resolves the core `export` which must be a function.
+    fn core_export_func(&self, ctx: &impl AsContext, export: &CoreExport) -> Result<Func, Error> {
+        self.core_export(ctx, export)?.into_func().ok_or_else(|| {
+            ComponentError::MissingCoreExport {
+                name: export.name.clone(),
+            }
+            .into()
+        })
+    }
+
+    /// Resolves the `memory` and `realloc` canonical `options`.
+    fn options(
+        &self,
+        ctx: &impl AsContext,
+        options: &Options,
+    ) -> Result<(Option<Memory>, Option<Func>), Error> {
+        let memory = match &options.memory {
+            Some(memory) => Some(self.core_export(ctx, memory)?.into_memory().ok_or_else(
+                || ComponentError::MissingCoreExport {
+                    name: memory.name.clone(),
+                },
+            )?),
+            None => None,
+        };
+        let realloc = match &options.realloc {
+            Some(realloc) => Some(self.core_export_func(ctx, realloc)?),
+            None => None,
+        };
+        Ok((memory, realloc))
+    }
+
+    /// Lifts the component function `def` exported by the component.
+    fn lift(&self, ctx: &impl AsContext, def: &FuncDef) -> Result<ComponentFunc, Error> {
+        let FuncDef::Lift { func, ty, options } = def else {
+            return Err(ComponentError::Unsupported {
+                offset: 0,
+                feature: "re-exporting imported functions",
+            }
+            .into());
+        };
+        let (memory, realloc) = self.options(ctx, options)?;
+        let post_return = match &options.post_return {
+            Some(post_return) => Some(self.core_export_func(ctx, post_return)?),
+            None => None,
+        };
+        Ok(ComponentFunc {
+            ty: ty.clone(),
+            func: self.core_export_func(ctx, func)?,
+            memory,
+            realloc,
+            post_return,
+            state: self.state.clone(),
+        })
+    }
+
+    /// Creates the core function `func` of the component.
+    fn core_func(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        func: &CoreFunc,
+    ) -> Result<Func, Error> {
+        let state = self.state.clone();
+        let func = match func {
+            CoreFunc::Export(export) => return self.core_export_func(&ctx, export),
+            CoreFunc::Lower { func, options } => {
+                let Some(Definition::Func(host)) = self
+                    .linker
+                    .get(func.instance.as_deref().unwrap_or(""), &func.name)
+                else {
+                    return Err(ComponentError::MissingDefinition {
+                        name: func.display_name(),
+                    }
+                    .into());
+                };
+                let host = host.clone();
+                let ty = func.ty.clone();
+                let (memory, realloc) = self.options(&ctx, options)?;
+                Func::new(
+                    &mut ctx,
+                    core_func_type(&ty, true),
+                    move |mut caller: Caller<T>, args, results| {
+                        let memory_data = memory
+                            .map(|memory| memory.try_data(caller.as_context()))
+                            .transpose()?;
+                        let params = Lift::new(memory_data, &state).params(&ty, args)?;
+                        let mut result = vec![Val::Bool(false); usize::from(ty.result().is_some())];
+                        host(caller.as_context_mut(), &params, &mut result)?;
+                        let mut lower = Lower::new(&mut caller, memory, realloc, &state);
+                        lower.result(&ty, result.first(), args, results)
+                    },
+                )
+            }
+            CoreFunc::ResourceNew(resource) => {
+                let resource = *resource;
+                Func::new(
+                    &mut ctx,
+                    crate::FuncType::new([ValueType::I32], [ValueType::I32]),
+                    move |_caller: Caller<T>, args, results| {
+                        let rep = args[0].i32().unwrap_or_default() as u32;
+                        results[0] = Value::I32(state.lower_own(resource, rep) as i32);
+                        Ok(())
+                    },
+                )
+            }
+            CoreFunc::ResourceRep(resource) => {
+                let resource = *resource;
+                Func::new(
+                    &mut ctx,
+                    crate::FuncType::new([ValueType::I32], [ValueType::I32]),
+                    move |_caller: Caller<T>, args, results| {
+                        let handle = args[0].i32().unwrap_or_default() as u32;
+                        let rep = state.handles.lock().get(resource, handle)?.rep;
+                        results[0] = Value::I32(rep as i32);
+                        Ok(())
+                    },
+                )
+            }
+            CoreFunc::ResourceDrop(resource) => {
+                let resource = *resource;
+                let host_dtor = match &self.state.component.inner().resources[resource.0 as usize] {
+                    ResourceDef::Imported { instance, name } => {
+                        match self.linker.get(instance, name) {
+                            Some(Definition::Resource(dtor)) => Some(dtor.clone()),
+                            _ => {
+                                return Err(ComponentError::MissingDefinition {
+                                    name: match instance.is_empty() {
+                                        true => name.clone(),
+                                        false => std::format!("{instance}#{name}"),
+                                    },
+                                }
+                                .into())
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+                Func::new(
+                    &mut ctx,
+                    crate::FuncType::new([ValueType::I32], []),
+                    move |mut caller: Caller<T>, args, _results| {
+                        let handle = args[0].i32().unwrap_or_default() as u32;
+                        let handle = state.handles.lock().remove(resource, handle)?;
+                        if !handle.own {
+                            return Ok(());
+                        }
+                        if let Some(host_dtor) = &host_dtor {
+                            return host_dtor(caller.as_context_mut(), handle.rep);
+                        }
+                        let dtor = state.dtors.read()[resource.0 as usize];
+                        if let Some(dtor) = dtor {
+                            dtor.call(&mut caller, &[Value::I32(handle.rep as i32)], &mut [])?;
+                        }
+                        Ok(())
+                    },
+                )
+            }
+        };
+        Ok(func)
+    }
+}
@@ -0,0 +1,218 @@
+use super::{
+    instance::{ComponentInstance, Instantiator},
+    parse::Component,
+    values::Val,
+    ComponentError,
+};
+use crate::{AsContextMut, Engine, Error, StoreContextMut};
+use core::fmt::{self, Debug};
+use std::{collections::BTreeMap, string::String, sync::Arc};
+
+/// A host function of a component [`Linker`].
+type HostFunc<T> =
+    Arc<dyn Fn(StoreContextMut<T>, &[Val], &mut [Val]) -> Result<(), Error> + Send + Sync>;
+
+/// The destructor of a host resource of a component [`Linker`].
+type HostDtor<T> = Arc<dyn Fn(StoreContextMut<T>, u32) -> Result<(), Error> + Send + Sync>;
+
+/// A host definition of a component [`Linker`].
+pub(super) enum Definition<T> {
+    /// A host function.
+    Func(HostFunc<T>),
+    /// A host resource with its destructor.
+    Resource(HostDtor<T>),
+}
+
+impl<T> Clone for Definition<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Func(func) => Self::Func(func.clone()),
+            Self::Resource(dtor) => Self::Resource(dtor.clone()),
+        }
+    }
+}
+
+/// Returns the semver compatibility class of `version`.
+///
+/// Versions `1.2.3` and `1.4.0` are compatible while `0.2.0` and `0.3.0` are not.
+fn compat(version: &str) -> &str {
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next().unwrap_or_default();
+    match (major, parts.next()) {
+        ("0", Some(minor)) => &version[..major.len() + 1 + minor.len()],
+        _ => major,
+    }
+}
+
+/// Returns `true` if the instance names `a` and `b` refer to compatible interfaces.
+///
+/// This allows hosts to provide a single version of an interface for components
+/// that have been built against a compatible version such as `wasi:cli/stdout@0.2.0`
+/// and `wasi:cli/stdout@0.2.3`.
+fn is_compatible(a: &str, b: &str) -> bool {
+    match (a.split_once('@'), b.split_once('@')) {
+        (Some((a, va)), Some((b, vb))) => a == b && compat(va) == compat(vb),
+        _ => a == b,
+    }
+}
+
+/// Defines host functions and resources for the imports of Wasm components.
+///
+/// This is the component model counterpart of the core [`Linker`](crate::Linker).
+pub struct Linker<T> {
+    /// The engine of the linker.
+    engine: Engine,
+    /// The host definitions by instance name with `""` for top-level definitions.
+    instances: BTreeMap<String, BTreeMap<String, Definition<T>>>,
+}
+
+impl<T> Debug for Linker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Linker")
+            .field("engine", &self.engine)
+            .field("instances", &DebugNames(&self.instances))
+            .finish()
+    }
+}
+
+/// Formats the names of the definitions of a [`Linker`].
+struct DebugNames<'a, T>(&'a BTreeMap<String, BTreeMap<String, Definition<T>>>);
+
+impl<T> Debug for DebugNames<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.0
+                    .iter()
+                    .map(|(name, defs)| (name, defs.keys().collect::<std::vec::Vec<_>>())),
+            )
+            .finish()
+    }
+}
+
+impl<T> Clone for Linker<T> {
+    fn clone(&self) -> Self {
+        Self {
+            engine: self.engine.clone(),
+            instances: self.instances.clone(),
+        }
+    }
+}
+
+impl<T> Linker<T> {
+    /// Creates a new empty [`Linker`].
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            instances: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the underlying [`Engine`] of the [`Linker`].
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Returns a [`LinkerInstance`] to define top-level imports.
+    pub fn root(&mut self) -> LinkerInstance<'_, T> {
+        self.instance("")
+    }
+
+    /// Returns a [`LinkerInstance`] to define the imports of instance `name`.
+    ///
+    /// Instance names with a version such as `wasi:cli/stdout@0.2.0` also
+    /// satisfy imports of semver compatible versions.
+    pub fn instance(&mut self, name: &str) -> LinkerInstance<'_, T> {
+        LinkerInstance {
+            definitions: self.instances.entry(name.into()).or_default(),
+        }
+    }
+
+    /// Returns the definition `name` of the instance `instance` if any.
+    pub(super) fn get(&self, instance: &str, name: &str) -> Option<&Definition<T>> {
+        let definitions = match self.instances.get(instance) {
+            Some(definitions) => definitions,
+            None => {
+                self.instances
+                    .iter()
+                    .find(|(candidate, _)| is_compatible(candidate, instance))?
+                    .1
+            }
+        };
+        definitions.get(name)
+    }
+
+    /// Instantiates the [`Component`] using the definitions in the [`Linker`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `ctx` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define the imports used by the [`Component`].
+    /// - If instantiating any of the embedded core modules fails.
+    pub fn instantiate(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        component: &Component,
+    ) -> Result<ComponentInstance, Error>
+    where
+        T: 'static,
+    {
+        assert!(Engine::same(self.engine(), ctx.as_context().engine()));
+        Instantiator::new(self, component).run(&mut ctx)
+    }
+}
+
+/// Defines the imports of a single instance of a component [`Linker`].
+pub struct LinkerInstance<'a, T> {
+    /// The definitions of the instance.
+    definitions: &'a mut BTreeMap<String, Definition<T>>,
+}
+
+impl<T> LinkerInstance<'_, T> {
+    /// Inserts the `definition` under `name`.
+    fn insert(&mut self, name: &str, definition: Definition<T>) -> Result<&mut Self, Error> {
+        if self.definitions.contains_key(name) {
+            return Err(ComponentError::DuplicateDefinition { name: name.into() }.into());
+        }
+        self.definitions.insert(name.into(), definition);
+        Ok(self)
+    }
+
+    /// Defines the host function `name` operating on dynamically typed [`Val`]s.
+    ///
+    /// The `params` match the parameter types of the imported function and
+    /// `results` has a single slot if the imported function has a result.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name.
+    pub fn func_new(
+        &mut self,
+        name: &str,
+        func: impl Fn(StoreContextMut<T>, &[Val], &mut [Val]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<&mut Self, Error> {
+        self.insert(name, Definition::Func(Arc::new(func)))
+    }
+
+    /// Defines the host resource `name` with its destructor.
+    ///
+    /// The destructor is called with the representation of the resource when
+    /// the component drops an owned handle to it.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name.
+    pub fn resource(
+        &mut self,
+        name: &str,
+        dtor: impl Fn(StoreContextMut<T>, u32) -> Result<(), Error> + Send + Sync + 'static,
+    ) -> Result<&mut Self, Error> {
+        self.insert(name, Definition::Resource(Arc::new(dtor)))
+    }
+}
@@ -0,0 +1,181 @@
+//! Support for Wasm components on top of Wasmi core modules.
+//!
+//! A [`Component`] is parsed from the binary format of the Wasm component model.
+//! Its embedded core modules are compiled to ordinary Wasmi [`Module`]s and
+//! [`Linker::instantiate`] wires them together the way the component describes,
+//! resolving the component imports to host functions defined in the [`Linker`].
+//! Values cross the component boundary following the canonical ABI.
+//!
+//! # Supported Subset
+//!
+//! The supported subset covers components as produced by `wit-component`,
+//! e.g. by toolchains targeting `wasm32-wasip2`:
+//!
+//! - All value types except `stream`, `future` and `error-context`.
+//! - Resources, both imported from the host and defined by the component.
+//! - UTF-8 string encoding with `memory`, `realloc` and `post-return` options.
+//! - Imported and exported functions and instances.
+//!
+//! Nested components, component-level start functions and values are not supported.
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::{component::{Component, Linker, Val}, Engine, Store};
+//!
+//! let wasm = wat::parse_str(r#"
+//!     (component
+//!         (import "host" (instance $host (export "double" (func (param "x" u32) (result u32)))))
+//!         (alias export $host "double" (func $double))
+//!         (core func $double (canon lower (func $double)))
+//!         (core module $m
+//!             (import "host" "double" (func $double (param i32) (result i32)))
+//!             (func (export "quadruple") (param i32) (result i32)
+//!                 (call $double (call $double (local.get 0)))
+//!             )
+//!         )
+//!         (core instance $host (export "double" (func $double)))
+//!         (core instance $m (instantiate $m (with "host" (instance $host))))
+//!         (func (export "quadruple") (param "x" u32) (result u32)
+//!             (canon lift (core func $m "quadruple"))
+//!         )
+//!     )
+//! "#)?;
+//! let engine = Engine::default();
+//! let component = Component::new(&engine, &wasm)?;
+//! let mut store = Store::new(&engine, ());
+//! let mut linker = <Linker<()>>::new(&engine);
+//! linker.instance("host").func_new("double", |_ctx, params, results| {
+//!     let Val::U32(x) = params[0] else { unreachable!() };
+//!     results[0] = Val::U32(2 * x);
+//!     Ok(())
+//! })?;
+//! let instance = linker.instantiate(&mut store, &component)?;
+//! let quadruple = instance.get_func("quadruple").unwrap();
+//! let mut result = [Val::U32(0)];
+//! quadruple.call(&mut store, &[Val::U32(3)], &mut result)?;
+//! assert_eq!(result[0], Val::U32(12));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`Module`]: crate::Module
+
+mod abi;
+mod instance;
+mod linker;
+mod parse;
+mod reader;
+mod types;
+mod values;
+
+pub use self::{
+    instance::{ComponentFunc, ComponentInstance},
+    linker::{Linker, LinkerInstance},
+    parse::Component,
+    types::{FuncType, ResourceType, Type},
+    values::Val,
+};
+use core::{fmt, fmt::Display};
+use std::string::String;
+
+/// The preamble of component binaries following the magic bytes.
+const COMPONENT_PREAMBLE: [u8; 4] = [0x0d, 0x00, 0x01, 0x00];
+
+/// Returns `true` if `wasm` starts with the preamble of a Wasm component binary.
+///
+/// This allows to tell components apart from core Wasm modules before parsing them.
+pub fn is_component(wasm: &[u8]) -> bool {
+    wasm.len() >= 8 && wasm[..4] == *b"\0asm" && wasm[4..8] == COMPONENT_PREAMBLE
+}
+
+/// An error that may occur upon parsing, linking or calling Wasm components.
+#[derive(Debug)]
+pub enum ComponentError {
+    /// Encountered a malformed component binary.
+    Malformed {
+        /// The offset within the component binary.
+        offset: usize,
+        /// A description of the problem.
+        message: &'static str,
+    },
+    /// Encountered a component model feature that is not supported.
+    Unsupported {
+        /// The offset within the component binary.
+        offset: usize,
+        /// The unsupported feature.
+        feature: &'static str,
+    },
+    /// Encountered duplicate host definitions for the same name.
+    DuplicateDefinition {
+        /// The name of the duplicate definition.
+        name: String,
+    },
+    /// Encountered a component import without host definition.
+    MissingDefinition {
+        /// The name of the import.
+        name: String,
+    },
+    /// Encountered a value that does not match its component type.
+    TypeMismatch {
+        /// The expected type of the value.
+        expected: Type,
+    },
+    /// Encountered an unknown resource handle or a handle of another resource type.
+    InvalidHandle {
+        /// The invalid handle.
+        handle: u32,
+    },
+    /// Encountered an invalid value in the canonical ABI representation.
+    InvalidAbiValue {
+        /// What is invalid about the value.
+        what: &'static str,
+    },
+    /// Encountered missing canonical options required to pass a value.
+    MissingOption {
+        /// The name of the missing option.
+        option: &'static str,
+    },
+    /// Encountered a reference to a missing or mismatched export of a core instance.
+    MissingCoreExport {
+        /// The name of the core export.
+        name: String,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ComponentError {}
+
+impl Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed { offset, message } => {
+                write!(f, "malformed component at offset {offset}: {message}")
+            }
+            Self::Unsupported { offset, feature } => {
+                write!(
+                    f,
+                    "unsupported component feature at offset {offset}: {feature}"
+                )
+            }
+            Self::DuplicateDefinition { name } => {
+                write!(f, "encountered duplicate definition for `{name}`")
+            }
+            Self::MissingDefinition { name } => {
+                write!(f, "cannot find definition for component import `{name}`")
+            }
+            Self::TypeMismatch { expected } => {
+                write!(f, "value does not match the component type `{expected}`")
+            }
+            Self::InvalidHandle { handle } => {
+                write!(f, "unknown or mismatched resource handle {handle}")
+            }
+            Self::InvalidAbiValue { what } => write!(f, "invalid canonical ABI value: {what}"),
+            Self::MissingOption { option } => {
+                write!(f, "missing canonical option `{option}` to pass the value")
+            }
+            Self::MissingCoreExport { name } => {
+                write!(f, "cannot find core instance export `{name}`")
+            }
+        }
+    }
+}
@@ -0,0 +1,936 @@
+use super::{
+    is_component,
+    reader::Reader,
+    types::{FuncType, ResourceType, Type},
+    ComponentError,
+};
+use crate::{Engine, Error, Module};
+use std::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+/// A reference to an export of a core instance of a component.
+#[derive(Debug, Clone)]
+pub struct CoreExport {
+    /// The index of the core instance.
+    pub instance: u32,
+    /// The name of the export.
+    pub name: String,
+}
+
+/// The canonical options of lifted and lowered functions.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The linear memory through which strings and lists are passed.
+    pub memory: Option<CoreExport>,
+    /// The function that allocates guest memory for passed values.
+    pub realloc: Option<CoreExport>,
+    /// The function called after the results of a lifted function have been read.
+    pub post_return: Option<CoreExport>,
+}
+
+/// A function imported from the host.
+#[derive(Debug, Clone)]
+pub struct ImportedFunc {
+    /// The name of the imported instance or `None` for top-level imports.
+    pub instance: Option<String>,
+    /// The name of the function.
+    pub name: String,
+    /// The type of the function.
+    pub ty: Arc<FuncType>,
+}
+
+impl ImportedFunc {
+    /// Returns the full name of the imported function for error messages.
+    pub fn display_name(&self) -> String {
+        match &self.instance {
+            Some(instance) => std::format!("{instance}#{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A core function of a component.
+#[derive(Debug, Clone)]
+pub enum CoreFunc {
+    /// A function exported by a core instance.
+    Export(CoreExport),
+    /// An imported function lowered to a core function.
+    Lower {
+        func: ImportedFunc,
+        options: Options,
+    },
+    /// The `resource.new` built-in of a resource defined by the component.
+    ResourceNew(ResourceType),
+    /// The `resource.rep` built-in of a resource defined by the component.
+    ResourceRep(ResourceType),
+    /// The `resource.drop` built-in of a resource.
+    ResourceDrop(ResourceType),
+}
+
+/// An item of a core instance created from individual exports.
+#[derive(Debug, Clone)]
+pub enum CoreItem {
+    Func(CoreFunc),
+    Table(CoreExport),
+    Memory(CoreExport),
+    Global(CoreExport),
+}
+
+/// The definition of a core instance of a component.
+#[derive(Debug)]
+pub enum CoreInstanceDef {
+    /// Instantiates the core module with the given core instances as imports.
+    Instantiate {
+        /// The index of the core module.
+        module: u32,
+        /// The import module names and the core instances providing them.
+        args: Vec<(String, u32)>,
+    },
+    /// Bundles individual core items into a core instance.
+    Exports(Vec<(String, CoreItem)>),
+}
+
+/// A component function.
+#[derive(Debug, Clone)]
+pub enum FuncDef {
+    /// A function imported from the host.
+    Import(ImportedFunc),
+    /// A core function lifted to a component function.
+    Lift {
+        func: CoreExport,
+        ty: Arc<FuncType>,
+        options: Options,
+    },
+}
+
+/// A component instance.
+#[derive(Debug, Clone)]
+pub enum InstanceDef {
+    /// An instance imported from the host.
+    Import { name: String, ty: Arc<InstanceType> },
+    /// An instance bundling individual items.
+    Exports(Vec<(String, ExportDef)>),
+}
+
+/// An item exported from a component or a bundled component instance.
+#[derive(Debug, Clone)]
+pub enum ExportDef {
+    Func(FuncDef),
+    Instance(InstanceDef),
+    Type(TypeDef),
+}
+
+/// A resource type declared by a component.
+#[derive(Debug, Clone)]
+pub enum ResourceDef {
+    /// A resource exported by an instance type that has not been imported yet.
+    Abstract,
+    /// A resource imported from the host.
+    Imported {
+        /// The name of the imported instance or the empty string for top-level imports.
+        instance: String,
+        /// The name of the resource.
+        name: String,
+    },
+    /// A resource defined by the component.
+    Defined {
+        /// The destructor of the resource if any.
+        dtor: Option<CoreExport>,
+    },
+}
+
+/// An entry of the type index space of a component.
+#[derive(Debug, Clone)]
+pub enum TypeDef {
+    Val(Type),
+    Func(Arc<FuncType>),
+    Instance(Arc<InstanceType>),
+    Resource(ResourceType),
+}
+
+/// The type of a component instance.
+#[derive(Debug, Default)]
+pub struct InstanceType {
+    /// The exports of the instance.
+    exports: BTreeMap<String, ExternDesc>,
+}
+
+/// The type of an imported or exported item.
+#[derive(Debug, Clone)]
+enum ExternDesc {
+    Func(Arc<FuncType>),
+    Type(TypeDef),
+    Instance(Arc<InstanceType>),
+    Other,
+}
+
+/// The sort of an item of the component or core index spaces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Sort {
+    Core(CoreSort),
+    Func,
+    Value,
+    Type,
+    Component,
+    Instance,
+}
+
+/// The sort of an item of the core index spaces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum CoreSort {
+    Func,
+    Table,
+    Memory,
+    Global,
+    Tag,
+    Type,
+    Module,
+    Instance,
+}
+
+/// The parsed contents of a [`Component`].
+#[derive(Debug)]
+pub struct ComponentInner {
+    /// The engine the core modules have been compiled with.
+    pub engine: Engine,
+    /// The core modules in order of definition.
+    pub modules: Vec<Module>,
+    /// The core instances in order of definition.
+    pub core_instances: Vec<CoreInstanceDef>,
+    /// The resource types declared by the component.
+    pub resources: Vec<ResourceDef>,
+    /// The names of the component imports.
+    pub imports: Vec<String>,
+    /// The component exports.
+    pub exports: Vec<(String, ExportDef)>,
+}
+
+/// A parsed Wasm component.
+///
+/// Use [`Linker::instantiate`](super::Linker::instantiate) to instantiate a [`Component`].
+#[derive(Debug, Clone)]
+pub struct Component {
+    inner: Arc<ComponentInner>,
+}
+
+impl Component {
+    /// Parses the Wasm component binary `wasm` and compiles its core modules.
+    ///
+    /// # Errors
+    ///
+    /// - If `wasm` is not a valid component binary.
+    /// - If the component uses features that are not supported.
+    /// - If any of the embedded core modules fails to compile.
+    pub fn new(engine: &Engine, wasm: &[u8]) -> Result<Self, Error> {
+        let inner = Parser::new(engine).parse(wasm)?;
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Returns the [`Engine`] used to compile the [`Component`].
+    pub fn engine(&self) -> &Engine {
+        &self.inner.engine
+    }
+
+    /// Returns the names of the imports of the [`Component`].
+    pub fn imports(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.inner.imports.iter().map(String::as_str)
+    }
+
+    /// Returns the names of the exports of the [`Component`].
+    pub fn exports(&self) -> impl ExactSizeIterator<Item = &str> {
+        self.inner.exports.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the parsed contents of the [`Component`].
+    pub(super) fn inner(&self) -> &ComponentInner {
+        &self.inner
+    }
+}
+
+/// Returns the primitive [`Type`] encoded by `byte` if any.
+fn primitive(byte: u8) -> Option<Type> {
+    let ty = match byte {
+        0x7f => Type::Bool,
+        0x7e => Type::S8,
+        0x7d => Type::U8,
+        0x7c => Type::S16,
+        0x7b => Type::U16,
+        0x7a => Type::S32,
+        0x79 => Type::U32,
+        0x78 => Type::S64,
+        0x77 => Type::U64,
+        0x76 => Type::F32,
+        0x75 => Type::F64,
+        0x74 => Type::Char,
+        0x73 => Type::String,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Parses a component binary into its [`ComponentInner`].
+struct Parser {
+    engine: Engine,
+    modules: Vec<Module>,
+    core_instances: Vec<CoreInstanceDef>,
+    core_funcs: Vec<CoreFunc>,
+    core_tables: Vec<CoreExport>,
+    core_memories: Vec<CoreExport>,
+    core_globals: Vec<CoreExport>,
+    funcs: Vec<FuncDef>,
+    instances: Vec<InstanceDef>,
+    /// The type index spaces of the component and the instance types being parsed.
+    scopes: Vec<Vec<TypeDef>>,
+    resources: Vec<ResourceDef>,
+    imports: Vec<String>,
+    exports: Vec<(String, ExportDef)>,
+}
+
+/// Returns the item at `index` of an index space.
+fn get<'a, T>(r: &Reader, items: &'a [T], index: u32) -> Result<&'a T, ComponentError> {
+    items
+        .get(index as usize)
+        .ok_or_else(|| r.malformed("index out of bounds"))
+}
+
+impl Parser {
+    /// Creates a new [`Parser`] compiling core modules with `engine`.
+    fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            modules: Vec::new(),
+            core_instances: Vec::new(),
+            core_funcs: Vec::new(),
+            core_tables: Vec::new(),
+            core_memories: Vec::new(),
+            core_globals: Vec::new(),
+            funcs: Vec::new(),
+            instances: Vec::new(),
+            scopes: [Vec::new()].into(),
+            resources: Vec::new(),
+            imports: Vec::new(),
+            exports: Vec::new(),
+        }
+    }
+
+    /// Returns the type index space of the innermost scope.
+    fn types(&mut self) -> &mut Vec<TypeDef> {
+        self.scopes
+            .last_mut()
+            .expect("missing component type scope")
+    }
+
+    /// Returns the type at `index` of the innermost scope.
+    fn ty(&self, r: &Reader, index: u32) -> Result<&TypeDef, ComponentError> {
+        let types = self.scopes.last().expect("missing component type scope");
+        get(r, types, index)
+    }
+
+    /// Parses the component binary `wasm`.
+    fn parse(mut self, wasm: &[u8]) -> Result<ComponentInner, Error> {
+        let mut r = Reader::new(wasm, 0);
+        if !is_component(wasm) {
+            return Err(r.malformed("expected a component binary").into());
+        }
+        r.bytes(8)?;
+        while !r.is_empty() {
+            let id = r.u8()?;
+            let len = r.u32()? as usize;
+            let mut section = r.split(len)?;
+            match id {
+                0 => continue,
+                1 => {
+                    let module = Module::new(&self.engine, section.bytes(len)?)?;
+                    self.modules.push(module);
+                }
+                2 => self.core_instance_section(&mut section)?,
+                3 => {
+                    section.vec(|r| self.core_type(r))?;
+                }
+                4 => return Err(section.unsupported("nested components").into()),
+                5 => self.instance_section(&mut section)?,
+                6 => self.alias_section(&mut section)?,
+                7 => {
+                    for _ in 0..section.u32()? {
+                        let ty = self.def_type(&mut section)?;
+                        self.types().push(ty);
+                    }
+                }
+                8 => self.canon_section(&mut section)?,
+                9 => return Err(section.unsupported("component start functions").into()),
+                10 => self.import_section(&mut section)?,
+                11 => self.export_section(&mut section)?,
+                12 => return Err(section.unsupported("component values").into()),
+                _ => return Err(section.malformed("unknown section").into()),
+            }
+            if !section.is_empty() {
+                return Err(section.malformed("section size mismatch").into());
+            }
+        }
+        Ok(ComponentInner {
+            engine: self.engine,
+            modules: self.modules,
+            core_instances: self.core_instances,
+            resources: self.resources,
+            imports: self.imports,
+            exports: self.exports,
+        })
+    }
+
+    /// Reads the sort of an item.
+    fn sort(r: &mut Reader) -> Result<Sort, ComponentError> {
+        let sort = match r.u8()? {
+            0x00 => Sort::Core(Self::core_sort(r)?),
+            0x01 => Sort::Func,
+            0x02 => Sort::Value,
+            0x03 => Sort::Type,
+            0x04 => Sort::Component,
+            0x05 => Sort::Instance,
+            _ => return Err(r.malformed("invalid sort")),
+        };
+        Ok(sort)
+    }
+
+    /// Reads the sort of a core item.
+    fn core_sort(r: &mut Reader) -> Result<CoreSort, ComponentError> {
+        let sort = match r.u8()? {
+            0x00 => CoreSort::Func,
+            0x01 => CoreSort::Table,
+            0x02 => CoreSort::Memory,
+            0x03 => CoreSort::Global,
+            0x04 => CoreSort::Tag,
+            0x10 => CoreSort::Type,
+            0x11 => CoreSort::Module,
+            0x12 => CoreSort::Instance,
+            _ => return Err(r.malformed("invalid core sort")),
+        };
+        Ok(sort)
+    }
+
+    /// Reads an import or export name.
+    fn extern_name(r: &mut Reader) -> Result<String, ComponentError> {
+        match r.u8()? {
+            0x00 | 0x01 => Ok(r.name()?.to_string()),
+            _ => Err(r.malformed("invalid import or export name")),
+        }
+    }
+
+    /// Skips a core type definition.
+    ///
+    /// Core types are only used to describe imports of nested core modules
+    /// and are therefore not needed.
+    fn core_type(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        match r.u8()? {
+            0x60 => {
+                for _ in 0..2 {
+                    r.vec(|r| match r.u8()? {
+                        0x7f | 0x7e | 0x7d | 0x7c | 0x7b | 0x70 | 0x6f => Ok(()),
+                        _ => Err(r.unsupported("core reference types")),
+                    })?;
+                }
+                Ok(())
+            }
+            0x50 => Err(r.unsupported("core module types")),
+            _ => Err(r.unsupported("core types other than function types")),
+        }
+    }
+
+    /// Reads a value type.
+    fn val_type(&self, r: &mut Reader) -> Result<Type, ComponentError> {
+        if let Some(ty) = primitive(r.peek()?) {
+            r.u8()?;
+            return Ok(ty);
+        }
+        let index = r.s33()?;
+        let index = u32::try_from(index).map_err(|_| r.malformed("invalid value type"))?;
+        match self.ty(r, index)? {
+            TypeDef::Val(ty) => Ok(ty.clone()),
+            _ => Err(r.malformed("expected a value type")),
+        }
+    }
+
+    /// Reads an optional value type.
+    fn opt_val_type(&self, r: &mut Reader) -> Result<Option<Type>, ComponentError> {
+        match r.u8()? {
+            0x00 => Ok(None),
+            0x01 => self.val_type(r).map(Some),
+            _ => Err(r.malformed("invalid optional value type")),
+        }
+    }
+
+    /// Reads the index of a resource type.
+    fn resource_type(&self, r: &mut Reader) -> Result<ResourceType, ComponentError> {
+        let index = r.u32()?;
+        match self.ty(r, index)? {
+            TypeDef::Resource(resource) => Ok(*resource),
+            _ => Err(r.malformed("expected a resource type")),
+        }
+    }
+
+    /// Reads the index of a core function that must be a core instance export.
+    fn core_export_func(&self, r: &mut Reader) -> Result<CoreExport, ComponentError> {
+        let index = r.u32()?;
+        match get(r, &self.core_funcs, index)? {
+            CoreFunc::Export(export) => Ok(export.clone()),
+            _ => Err(r.unsupported("passing built-in core functions as canonical options")),
+        }
+    }
+
+    /// Adds a new resource type and returns it.
+    fn new_resource(&mut self, def: ResourceDef) -> ResourceType {
+        let resource = ResourceType(self.resources.len() as u32);
+        self.resources.push(def);
+        resource
+    }
+
+    /// Reads a type definition.
+    fn def_type(&mut self, r: &mut Reader) -> Result<TypeDef, ComponentError> {
+        let byte = r.u8()?;
+        if let Some(ty) = primitive(byte) {
+            return Ok(TypeDef::Val(ty));
+        }
+        let ty = match byte {
+            0x3f => {
+                if self.scopes.len() != 1 || r.u8()? != 0x7f {
+                    return Err(r.malformed("invalid resource type definition"));
+                }
+                let dtor = match r.u8()? {
+                    0x00 => None,
+                    0x01 => Some(self.core_export_func(r)?),
+                    _ => return Err(r.malformed("invalid resource destructor")),
+                };
+                return Ok(TypeDef::Resource(
+                    self.new_resource(ResourceDef::Defined { dtor }),
+                ));
+            }
+            0x40 => {
+                let params = r.vec(|r| Ok((r.name()?.to_string(), self.val_type(r)?)))?;
+                let result = match r.u8()? {
+                    0x00 => Some(self.val_type(r)?),
+                    0x01 if r.u8()? == 0x00 => None,
+                    _ => return Err(r.malformed("invalid function results")),
+                };
+                return Ok(TypeDef::Func(Arc::new(FuncType::new(params, result))));
+            }
+            0x41 => return Err(r.unsupported("component types")),
+            0x42 => return self.instance_type(r).map(TypeDef::Instance),
+            0x43 => return Err(r.unsupported("async functions")),
+            0x72 => Type::Record(r.vec(|r| Ok((r.name()?.to_string(), self.val_type(r)?)))?),
+            0x71 => Type::Variant(r.vec(|r| {
+                let name = r.name()?.to_string();
+                let ty = self.opt_val_type(r)?;
+                if r.u8()? != 0x00 {
+                    return Err(r.malformed("invalid variant case"));
+                }
+                Ok((name, ty))
+            })?),
+            0x70 => Type::List(Box::new(self.val_type(r)?)),
+            0x6f => Type::Tuple(r.vec(|r| self.val_type(r))?),
+            0x6e => Type::Flags(r.vec(|r| Ok(r.name()?.to_string()))?),
+            0x6d => Type::Enum(r.vec(|r| Ok(r.name()?.to_string()))?),
+            0x6b => Type::Option(Box::new(self.val_type(r)?)),
+            0x6a => Type::Result {
+                ok: self.opt_val_type(r)?.map(Box::new),
+                err: self.opt_val_type(r)?.map(Box::new),
+            },
+            0x69 => Type::Own(self.resource_type(r)?),
+            0x68 => Type::Borrow(self.resource_type(r)?),
+            0x63..=0x67 => return Err(r.unsupported("async and fixed-size value types")),
+            _ => return Err(r.malformed("invalid type definition")),
+        };
+        Ok(TypeDef::Val(ty))
+    }
+
+    /// Reads an instance type within a new type scope.
+    fn instance_type(&mut self, r: &mut Reader) -> Result<Arc<InstanceType>, ComponentError> {
+        self.scopes.push(Vec::new());
+        let ty = self.instance_decls(r);
+        self.scopes.pop();
+        ty.map(Arc::new)
+    }
+
+    /// Reads the declarations of an instance type.
+    fn instance_decls(&mut self, r: &mut Reader) -> Result<InstanceType, ComponentError> {
+        let mut ty = InstanceType::default();
+        for _ in 0..r.u32()? {
+            match r.u8()? {
+                0x00 => self.core_type(r)?,
+                0x01 => {
+                    let def = self.def_type(r)?;
+                    self.types().push(def);
+                }
+                0x02 => {
+                    let sort = Self::sort(r)?;
+                    if r.u8()? != 0x02 {
+                        return Err(r.malformed("expected an outer alias"));
+                    }
+                    let count = r.u32()? as usize;
+                    let index = r.u32()?;
+                    match sort {
+                        Sort::Type => {
+                            let scope = self
+                                .scopes
+                                .len()
+                                .checked_sub(count + 1)
+                                .ok_or_else(|| r.malformed("invalid outer alias"))?;
+                            let def = get(r, &self.scopes[scope], index)?.clone();
+                            self.types().push(def);
+                        }
+                        Sort::Core(CoreSort::Type) => {}
+                        _ => return Err(r.malformed("invalid outer alias sort")),
+                    }
+                }
+                0x04 => {
+                    let name = Self::extern_name(r)?;
+                    let desc = self.extern_desc(r)?;
+                    ty.exports.insert(name, desc);
+                }
+                _ => return Err(r.malformed("invalid instance type declaration")),
+            }
+        }
+        Ok(ty)
+    }
+
+    /// Reads the type of an imported or exported item.
+    ///
+    /// Types introduced by the item are added to the innermost type index space.
+    fn extern_desc(&mut self, r: &mut Reader) -> Result<ExternDesc, ComponentError> {
+        let desc = match Self::sort(r)? {
+            Sort::Core(CoreSort::Module) | Sort::Component => {
+                r.u32()?;
+                ExternDesc::Other
+            }
+            Sort::Value => {
+                self.val_type(r)?;
+                ExternDesc::Other
+            }
+            Sort::Func => {
+                let index = r.u32()?;
+                match self.ty(r, index)? {
+                    TypeDef::Func(ty) => ExternDesc::Func(ty.clone()),
+                    _ => return Err(r.malformed("expected a function type")),
+                }
+            }
+            Sort::Instance => {
+                let index = r.u32()?;
+                match self.ty(r, index)? {
+                    TypeDef::Instance(ty) => ExternDesc::Instance(ty.clone()),
+                    _ => return Err(r.malformed("expected an instance type")),
+                }
+            }
+            Sort::Type => {
+                let def = match r.u8()? {
+                    0x00 => {
+                        let index = r.u32()?;
+                        self.ty(r, index)?.clone()
+                    }
+                    0x01 => TypeDef::Resource(self.new_resource(ResourceDef::Abstract)),
+                    _ => return Err(r.malformed("invalid type bounds")),
+                };
+                self.types().push(def.clone());
+                ExternDesc::Type(def)
+            }
+            Sort::Core(_) => return Err(r.malformed("invalid extern sort")),
+        };
+        Ok(desc)
+    }
+
+    /// Names the abstract resources exported by the imported instance type `ty`.
+    fn bind_resources(&mut self, instance: &str, ty: &InstanceType) {
+        for (name, desc) in &ty.exports {
+            if let ExternDesc::Type(TypeDef::Resource(resource)) = desc {
+                let def = &mut self.resources[resource.0 as usize];
+                if let ResourceDef::Abstract = def {
+                    *def = ResourceDef::Imported {
+                        instance: instance.to_string(),
+                        name: name.clone(),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Reads the core instance section.
+    fn core_instance_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            let def = match r.u8()? {
+                0x00 => {
+                    let module = r.u32()?;
+                    get(r, &self.modules, module)?;
+                    let args = r.vec(|r| {
+                        let name = r.name()?.to_string();
+                        if r.u8()? != 0x12 {
+                            return Err(r.malformed("invalid instantiation argument"));
+                        }
+                        let instance = r.u32()?;
+                        get(r, &self.core_instances, instance)?;
+                        Ok((name, instance))
+                    })?;
+                    CoreInstanceDef::Instantiate { module, args }
+                }
+                0x01 => CoreInstanceDef::Exports(r.vec(|r| {
+                    let name = r.name()?.to_string();
+                    let sort = Self::core_sort(r)?;
+                    let index = r.u32()?;
+                    let item = match sort {
+                        CoreSort::Func => CoreItem::Func(get(r, &self.core_funcs, index)?.clone()),
+                        CoreSort::Table => {
+                            CoreItem::Table(get(r, &self.core_tables, index)?.clone())
+                        }
+                        CoreSort::Memory => {
+                            CoreItem::Memory(get(r, &self.core_memories, index)?.clone())
+                        }
+                        CoreSort::Global => {
+                            CoreItem::Global(get(r, &self.core_globals, index)?.clone())
+                        }
+                        _ => return Err(r.unsupported("core instance exports of this sort")),
+                    };
+                    Ok((name, item))
+                })?),
+                _ => return Err(r.malformed("invalid core instance")),
+            };
+            self.core_instances.push(def);
+        }
+        Ok(())
+    }
+
+    /// Reads the component instance section.
+    fn instance_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            if r.u8()? != 0x01 {
+                return Err(r.unsupported("nested components"));
+            }
+            let exports = r.vec(|r| {
+                let name = Self::extern_name(r)?;
+                let sort = Self::sort(r)?;
+                let index = r.u32()?;
+                Ok((name, self.export_def(r, sort, index)?))
+            })?;
+            self.instances.push(InstanceDef::Exports(exports));
+        }
+        Ok(())
+    }
+
+    /// Returns the item at `index` of the index space of `sort`.
+    fn export_def(&self, r: &Reader, sort: Sort, index: u32) -> Result<ExportDef, ComponentError> {
+        let def = match sort {
+            Sort::Func => ExportDef::Func(get(r, &self.funcs, index)?.clone()),
+            Sort::Instance => ExportDef::Instance(get(r, &self.instances, index)?.clone()),
+            Sort::Type => ExportDef::Type(self.ty(r, index)?.clone()),
+            _ => return Err(r.unsupported("exports of modules, components and values")),
+        };
+        Ok(def)
+    }
+
+    /// Adds the `def` to the index space of its sort.
+    fn push_def(&mut self, def: ExportDef) {
+        match def {
+            ExportDef::Func(func) => self.funcs.push(func),
+            ExportDef::Instance(instance) => self.instances.push(instance),
+            ExportDef::Type(ty) => self.types().push(ty),
+        }
+    }
+
+    /// Reads the alias section.
+    fn alias_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            let sort = Self::sort(r)?;
+            match (r.u8()?, sort) {
+                (0x00, sort) => {
+                    let index = r.u32()?;
+                    let name = r.name()?;
+                    let def = match get(r, &self.instances, index)? {
+                        InstanceDef::Import { name: instance, ty } => {
+                            match (sort, ty.exports.get(name)) {
+                                (Sort::Func, Some(ExternDesc::Func(ty))) => {
+                                    ExportDef::Func(FuncDef::Import(ImportedFunc {
+                                        instance: Some(instance.clone()),
+                                        name: name.to_string(),
+                                        ty: ty.clone(),
+                                    }))
+                                }
+                                (Sort::Type, Some(ExternDesc::Type(def))) => {
+                                    ExportDef::Type(def.clone())
+                                }
+                                (Sort::Instance, Some(ExternDesc::Instance(_))) => {
+                                    return Err(r.unsupported("nested instance imports"))
+                                }
+                                _ => return Err(r.malformed("invalid instance export alias")),
+                            }
+                        }
+                        InstanceDef::Exports(exports) => exports
+                            .iter()
+                            .find(|(export, _)| export == name)
+                            .map(|(_, def)| def.clone())
+                            .ok_or_else(|| r.malformed("invalid instance export alias"))?,
+                    };
+                    self.push_def(def);
+                }
+                (0x01, Sort::Core(sort)) => {
+                    let instance = r.u32()?;
+                    get(r, &self.core_instances, instance)?;
+                    let export = CoreExport {
+                        instance,
+                        name: r.name()?.to_string(),
+                    };
+                    match sort {
+                        CoreSort::Func => self.core_funcs.push(CoreFunc::Export(export)),
+                        CoreSort::Table => self.core_tables.push(export),
+                        CoreSort::Memory => self.core_memories.push(export),
+                        CoreSort::Global => self.core_globals.push(export),
+                        _ => return Err(r.unsupported("core instance exports of this sort")),
+                    }
+                }
+                (0x02, sort) => {
+                    let count = r.u32()?;
+                    let index = r.u32()?;
+                    match (count, sort) {
+                        (0, Sort::Type) => {
+                            let def = self.ty(r, index)?.clone();
+                            self.types().push(def);
+                        }
+                        (0, Sort::Core(CoreSort::Type)) => {}
+                        _ => return Err(r.unsupported("outer aliases of nested components")),
+                    }
+                }
+                _ => return Err(r.malformed("invalid alias")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the canonical options.
+    fn options(&self, r: &mut Reader) -> Result<Options, ComponentError> {
+        let mut options = Options::default();
+        for _ in 0..r.u32()? {
+            match r.u8()? {
+                0x00 => {}
+                0x01 | 0x02 => return Err(r.unsupported("UTF-16 string encodings")),
+                0x03 => {
+                    let index = r.u32()?;
+                    options.memory = Some(get(r, &self.core_memories, index)?.clone());
+                }
+                0x04 => options.realloc = Some(self.core_export_func(r)?),
+                0x05 => options.post_return = Some(self.core_export_func(r)?),
+                0x06..=0x09 => return Err(r.unsupported("async and GC canonical options")),
+                _ => return Err(r.malformed("invalid canonical option")),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Reads the canonical function section.
+    fn canon_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            match r.u8()? {
+                0x00 => {
+                    if r.u8()? != 0x00 {
+                        return Err(r.malformed("invalid canonical lift"));
+                    }
+                    let func = self.core_export_func(r)?;
+                    let options = self.options(r)?;
+                    let index = r.u32()?;
+                    let ty = match self.ty(r, index)? {
+                        TypeDef::Func(ty) => ty.clone(),
+                        _ => return Err(r.malformed("expected a function type")),
+                    };
+                    self.funcs.push(FuncDef::Lift { func, ty, options });
+                }
+                0x01 => {
+                    if r.u8()? != 0x00 {
+                        return Err(r.malformed("invalid canonical lower"));
+                    }
+                    let index = r.u32()?;
+                    let func = match get(r, &self.funcs, index)? {
+                        FuncDef::Import(func) => func.clone(),
+                        FuncDef::Lift { .. } => {
+                            return Err(r.unsupported("lowering lifted functions"))
+                        }
+                    };
+                    let options = self.options(r)?;
+                    self.core_funcs.push(CoreFunc::Lower { func, options });
+                }
+                0x02 => {
+                    let resource = self.resource_type(r)?;
+                    self.core_funcs.push(CoreFunc::ResourceNew(resource));
+                }
+                0x03 => {
+                    let resource = self.resource_type(r)?;
+                    self.core_funcs.push(CoreFunc::ResourceDrop(resource));
+                }
+                0x04 => {
+                    let resource = self.resource_type(r)?;
+                    self.core_funcs.push(CoreFunc::ResourceRep(resource));
+                }
+                _ => return Err(r.unsupported("async and thread canonical built-ins")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the import section.
+    fn import_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            let name = Self::extern_name(r)?;
+            match self.extern_desc(r)? {
+                ExternDesc::Func(ty) => self.funcs.push(FuncDef::Import(ImportedFunc {
+                    instance: None,
+                    name: name.clone(),
+                    ty,
+                })),
+                ExternDesc::Instance(ty) => {
+                    self.bind_resources(&name, &ty);
+                    self.instances.push(InstanceDef::Import {
+                        name: name.clone(),
+                        ty,
+                    });
+                }
+                ExternDesc::Type(TypeDef::Resource(resource)) => {
+                    let def = &mut self.resources[resource.0 as usize];
+                    if let ResourceDef::Abstract = def {
+                        *def = ResourceDef::Imported {
+                            instance: String::new(),
+                            name: name.clone(),
+                        };
+                    }
+                }
+                ExternDesc::Type(_) => {}
+                ExternDesc::Other => {
+                    return Err(r.unsupported("imports of modules, components and values"))
+                }
+            }
+            self.imports.push(name);
+        }
+        Ok(())
+    }
+
+    /// Reads the export section.
+    fn export_section(&mut self, r: &mut Reader) -> Result<(), ComponentError> {
+        for _ in 0..r.u32()? {
+            let name = Self::extern_name(r)?;
+            let sort = Self::sort(r)?;
+            let index = r.u32()?;
+            let def = self.export_def(r, sort, index)?;
+            match r.u8()? {
+                0x00 => {}
+                0x01 => {
+                    // Note: the exported item is used as is instead of the ascribed type.
+                    let len = self.types().len();
+                    self.extern_desc(r)?;
+                    self.types().truncate(len);
+                }
+                _ => return Err(r.malformed("invalid export type ascription")),
+            }
+            self.push_def(def.clone());
+            self.exports.push((name, def));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use super::ComponentError;
+use std::vec::Vec;
+
+/// A reader over the bytes of a component binary.
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    /// The bytes to read.
+    bytes: &'a [u8],
+    /// The current position within `bytes`.
+    pos: usize,
+    /// The offset of `bytes` within the whole component binary.
+    base: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a new [`Reader`] for `bytes` located at `base` within the component binary.
+    pub fn new(bytes: &'a [u8], base: usize) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            base,
+        }
+    }
+
+    /// Returns the current offset within the component binary.
+    pub fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    /// Returns `true` if all bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len()
+    }
+
+    /// Returns a [`ComponentError::Malformed`] at the current offset.
+    pub fn malformed(&self, message: &'static str) -> ComponentError {
+        ComponentError::Malformed {
+            offset: self.offset(),
+            message,
+        }
+    }
+
+    /// Returns a [`ComponentError::Unsupported`] at the current offset.
+    pub fn unsupported(&self, feature: &'static str) -> ComponentError {
+        ComponentError::Unsupported {
+            offset: self.offset(),
+            feature,
+        }
+    }
+
+    /// Returns the next byte without consuming it.
+    pub fn peek(&self) -> Result<u8, ComponentError> {
+        self.bytes
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| self.malformed("unexpected end of input"))
+    }
+
+    /// Reads a single byte.
+    pub fn u8(&mut self) -> Result<u8, ComponentError> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    /// Reads `len` raw bytes.
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], ComponentError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| self.malformed("unexpected end of input"))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Reads a LEB128 encoded unsigned 32-bit integer.
+    pub fn u32(&mut self) -> Result<u32, ComponentError> {
+        let mut result = 0_u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.u8()?;
+            result |= u32::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                if shift == 28 && byte > 0x0F {
+                    return Err(self.malformed("integer too large"));
+                }
+                return Ok(result);
+            }
+        }
+        Err(self.malformed("integer representation too long"))
+    }
+
+    /// Reads a LEB128 encoded signed 33-bit integer.
+    pub fn s33(&mut self) -> Result<i64, ComponentError> {
+        let mut result = 0_i64;
+        for shift in (0..35).step_by(7) {
+            let byte = self.u8()?;
+            result |= i64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                let bits = shift + 7;
+                if bits < 64 && byte & 0x40 != 0 {
+                    result |= -1 << bits;
+                }
+                return Ok(result);
+            }
+        }
+        Err(self.malformed("integer representation too long"))
+    }
+
+    /// Reads a length prefixed UTF-8 string.
+    pub fn name(&mut self) -> Result<&'a str, ComponentError> {
+        let len = self.u32()? as usize;
+        let bytes = self.bytes(len)?;
+        core::str::from_utf8(bytes).map_err(|_| self.malformed("invalid UTF-8 encoding"))
+    }
+
+    /// Reads a length prefixed vector of items read by `f`.
+    pub fn vec<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, ComponentError>,
+    ) -> Result<Vec<T>, ComponentError> {
+        let len = self.u32()?;
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(f(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Splits off a [`Reader`] for the next `len` bytes.
+    pub fn split(&mut self, len: usize) -> Result<Self, ComponentError> {
+        let base = self.offset();
+        let bytes = self.bytes(len)?;
+        Ok(Self::new(bytes, base))
+    }
+}
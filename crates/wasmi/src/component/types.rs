@@ -0,0 +1,196 @@
+use core::{fmt, fmt::Display};
+use std::{boxed::Box, string::String, vec::Vec};
+
+/// A resource type of a [`Component`](super::Component).
+///
+/// Resource types are only meaningful with respect to the component that declared them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceType(pub(crate) u32);
+
+/// The type of a value passed across the boundary of a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// The `bool` type.
+    Bool,
+    /// The `s8` type.
+    S8,
+    /// The `u8` type.
+    U8,
+    /// The `s16` type.
+    S16,
+    /// The `u16` type.
+    U16,
+    /// The `s32` type.
+    S32,
+    /// The `u32` type.
+    U32,
+    /// The `s64` type.
+    S64,
+    /// The `u64` type.
+    U64,
+    /// The `f32` type.
+    F32,
+    /// The `f64` type.
+    F64,
+    /// The `char` type.
+    Char,
+    /// The `string` type.
+    String,
+    /// A `list` with elements of the given type.
+    List(Box<Type>),
+    /// A `record` with the given named fields.
+    Record(Vec<(String, Type)>),
+    /// A `tuple` with the given element types.
+    Tuple(Vec<Type>),
+    /// A `variant` with the given named cases and their optional payload types.
+    Variant(Vec<(String, Option<Type>)>),
+    /// An `enum` with the given named cases.
+    Enum(Vec<String>),
+    /// An `option` with the given payload type.
+    Option(Box<Type>),
+    /// A `result` with the given optional `ok` and `err` payload types.
+    Result {
+        /// The payload type of the `ok` case if any.
+        ok: Option<Box<Type>>,
+        /// The payload type of the `err` case if any.
+        err: Option<Box<Type>>,
+    },
+    /// A set of `flags` with the given names.
+    Flags(Vec<String>),
+    /// An owned handle to a resource.
+    Own(ResourceType),
+    /// A borrowed handle to a resource.
+    Borrow(ResourceType),
+}
+
+impl Type {
+    /// Returns the payload types of all cases if `self` is a variant-like type.
+    ///
+    /// Variant-like types are `variant`, `enum`, `option` and `result`
+    /// which share the same canonical ABI representation.
+    pub(crate) fn cases(&self) -> Option<Vec<Option<&Type>>> {
+        let cases = match self {
+            Self::Variant(cases) => cases.iter().map(|(_, ty)| ty.as_ref()).collect(),
+            Self::Enum(cases) => cases.iter().map(|_| None).collect(),
+            Self::Option(ty) => [None, Some(&**ty)].into(),
+            Self::Result { ok, err } => [ok.as_deref(), err.as_deref()].into(),
+            _ => return None,
+        };
+        Some(cases)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        /// Writes the `items` separated by commas.
+        fn list<T>(
+            f: &mut fmt::Formatter,
+            items: impl IntoIterator<Item = T>,
+            mut item: impl FnMut(&mut fmt::Formatter, T) -> fmt::Result,
+        ) -> fmt::Result {
+            for (n, x) in items.into_iter().enumerate() {
+                if n != 0 {
+                    write!(f, ", ")?;
+                }
+                item(f, x)?;
+            }
+            Ok(())
+        }
+        match self {
+            Self::Bool => write!(f, "bool"),
+            Self::S8 => write!(f, "s8"),
+            Self::U8 => write!(f, "u8"),
+            Self::S16 => write!(f, "s16"),
+            Self::U16 => write!(f, "u16"),
+            Self::S32 => write!(f, "s32"),
+            Self::U32 => write!(f, "u32"),
+            Self::S64 => write!(f, "s64"),
+            Self::U64 => write!(f, "u64"),
+            Self::F32 => write!(f, "f32"),
+            Self::F64 => write!(f, "f64"),
+            Self::Char => write!(f, "char"),
+            Self::String => write!(f, "string"),
+            Self::List(ty) => write!(f, "list<{ty}>"),
+            Self::Record(fields) => {
+                write!(f, "record {{ ")?;
+                list(f, fields, |f, (name, ty)| write!(f, "{name}: {ty}"))?;
+                write!(f, " }}")
+            }
+            Self::Tuple(tys) => {
+                write!(f, "tuple<")?;
+                list(f, tys, |f, ty| write!(f, "{ty}"))?;
+                write!(f, ">")
+            }
+            Self::Variant(cases) => {
+                write!(f, "variant {{ ")?;
+                list(f, cases, |f, (name, ty)| match ty {
+                    Some(ty) => write!(f, "{name}({ty})"),
+                    None => write!(f, "{name}"),
+                })?;
+                write!(f, " }}")
+            }
+            Self::Enum(cases) => {
+                write!(f, "enum {{ ")?;
+                list(f, cases, |f, name| write!(f, "{name}"))?;
+                write!(f, " }}")
+            }
+            Self::Option(ty) => write!(f, "option<{ty}>"),
+            Self::Result { ok, err } => match (ok, err) {
+                (None, None) => write!(f, "result"),
+                (Some(ok), None) => write!(f, "result<{ok}>"),
+                (None, Some(err)) => write!(f, "result<_, {err}>"),
+                (Some(ok), Some(err)) => write!(f, "result<{ok}, {err}>"),
+            },
+            Self::Flags(names) => {
+                write!(f, "flags {{ ")?;
+                list(f, names, |f, name| write!(f, "{name}"))?;
+                write!(f, " }}")
+            }
+            Self::Own(resource) => write!(f, "own<resource-{}>", resource.0),
+            Self::Borrow(resource) => write!(f, "borrow<resource-{}>", resource.0),
+        }
+    }
+}
+
+/// The type of a function imported or exported by a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuncType {
+    /// The named parameters of the function.
+    params: Vec<(String, Type)>,
+    /// The result of the function if any.
+    result: Option<Type>,
+}
+
+impl FuncType {
+    /// Creates a new [`FuncType`].
+    pub(crate) fn new(params: Vec<(String, Type)>, result: Option<Type>) -> Self {
+        Self { params, result }
+    }
+
+    /// Returns the names and types of the parameters of the function.
+    pub fn params(&self) -> impl ExactSizeIterator<Item = (&str, &Type)> {
+        self.params.iter().map(|(name, ty)| (name.as_str(), ty))
+    }
+
+    /// Returns the type of the result of the function if any.
+    pub fn result(&self) -> Option<&Type> {
+        self.result.as_ref()
+    }
+}
+
+impl Display for FuncType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "func(")?;
+        for (n, (name, ty)) in self.params.iter().enumerate() {
+            if n != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}: {ty}")?;
+        }
+        write!(f, ")")?;
+        if let Some(result) = &self.result {
+            write!(f, " -> {result}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use core::{fmt, fmt::Display};
+use std::{boxed::Box, string::String, vec::Vec};
+
+/// A value passed across the boundary of a component.
+///
+/// The variants correspond to the variants of [`Type`](super::Type).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Val {
+    /// A `bool` value.
+    Bool(bool),
+    /// An `s8` value.
+    S8(i8),
+    /// A `u8` value.
+    U8(u8),
+    /// An `s16` value.
+    S16(i16),
+    /// A `u16` value.
+    U16(u16),
+    /// An `s32` value.
+    S32(i32),
+    /// A `u32` value.
+    U32(u32),
+    /// An `s64` value.
+    S64(i64),
+    /// A `u64` value.
+    U64(u64),
+    /// An `f32` value.
+    F32(f32),
+    /// An `f64` value.
+    F64(f64),
+    /// A `char` value.
+    Char(char),
+    /// A `string` value.
+    String(String),
+    /// A `list` value.
+    List(Vec<Val>),
+    /// A `record` value with its named fields in order.
+    Record(Vec<(String, Val)>),
+    /// A `tuple` value.
+    Tuple(Vec<Val>),
+    /// A `variant` value with the name of its case and its optional payload.
+    Variant(String, Option<Box<Val>>),
+    /// An `enum` value with the name of its case.
+    Enum(String),
+    /// An `option` value.
+    Option(Option<Box<Val>>),
+    /// A `result` value with optional payloads.
+    Result(Result<Option<Box<Val>>, Option<Box<Val>>>),
+    /// A `flags` value with the names of the set flags.
+    Flags(Vec<String>),
+    /// An owned handle to a resource identified by its representation.
+    ///
+    /// For resources defined by the host the representation is chosen by the
+    /// host and the host is responsible for the resource once the handle
+    /// has been passed to it.
+    Own(u32),
+    /// A borrowed handle to a resource identified by its representation.
+    Borrow(u32),
+}
+
+impl Display for Val {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        /// Writes the `items` separated by commas.
+        fn list<T>(
+            f: &mut fmt::Formatter,
+            items: impl IntoIterator<Item = T>,
+            mut item: impl FnMut(&mut fmt::Formatter, T) -> fmt::Result,
+        ) -> fmt::Result {
+            for (n, x) in items.into_iter().enumerate() {
+                if n != 0 {
+                    write!(f, ", ")?;
+                }
+                item(f, x)?;
+            }
+            Ok(())
+        }
+        /// Writes the `payload` in parentheses if any.
+        fn payload(f: &mut fmt::Formatter, payload: &Option<Box<Val>>) -> fmt::Result {
+            match payload {
+                Some(val) => write!(f, "({val})"),
+                None => Ok(()),
+            }
+        }
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::S8(value) => write!(f, "{value}"),
+            Self::U8(value) => write!(f, "{value}"),
+            Self::S16(value) => write!(f, "{value}"),
+            Self::U16(value) => write!(f, "{value}"),
+            Self::S32(value) => write!(f, "{value}"),
+            Self::U32(value) => write!(f, "{value}"),
+            Self::S64(value) => write!(f, "{value}"),
+            Self::U64(value) => write!(f, "{value}"),
+            Self::F32(value) => write!(f, "{value}"),
+            Self::F64(value) => write!(f, "{value}"),
+            Self::Char(value) => write!(f, "{value:?}"),
+            Self::String(value) => write!(f, "{value:?}"),
+            Self::List(values) => {
+                write!(f, "[")?;
+                list(f, values, |f, val| write!(f, "{val}"))?;
+                write!(f, "]")
+            }
+            Self::Record(fields) => {
+                write!(f, "{{")?;
+                list(f, fields, |f, (name, val)| write!(f, "{name}: {val}"))?;
+                write!(f, "}}")
+            }
+            Self::Tuple(values) => {
+                write!(f, "(")?;
+                list(f, values, |f, val| write!(f, "{val}"))?;
+                write!(f, ")")
+            }
+            Self::Variant(name, value) => {
+                write!(f, "{name}")?;
+                payload(f, value)
+            }
+            Self::Enum(name) => write!(f, "{name}"),
+            Self::Option(None) => write!(f, "none"),
+            Self::Option(Some(value)) => write!(f, "some({value})"),
+            Self::Result(Ok(value)) => {
+                write!(f, "ok")?;
+                payload(f, value)
+            }
+            Self::Result(Err(value)) => {
+                write!(f, "err")?;
+                payload(f, value)
+            }
+            Self::Flags(names) => {
+                write!(f, "{{")?;
+                list(f, names, |f, name| write!(f, "{name}"))?;
+                write!(f, "}}")
+            }
+            Self::Own(rep) => write!(f, "own({rep})"),
+            Self::Borrow(rep) => write!(f, "borrow({rep})"),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     utils::{BranchOffset16, Sign},
     AnyConst32,
+    AtomicRmwInstr,
     BinInstr,
     BinInstrImm,
     BinInstrImm16,
@@ -308,6 +309,16 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::LocalGetSpilled`].
+    pub fn local_get_spilled(result: Register, slot: u32) -> Self {
+        Self::LocalGetSpilled { result, slot }
+    }
+
+    /// Creates a new [`Instruction::LocalSetSpilled`].
+    pub fn local_set_spilled(slot: u32, value: Register) -> Self {
+        Self::LocalSetSpilled { slot, value }
+    }
+
     /// Creates a new [`Instruction::GlobalGet`].
     pub fn global_get(result: Register, global: GlobalIdx) -> Self {
         Self::GlobalGet { result, global }
@@ -680,6 +691,11 @@ impl Instruction {
         Self::MemorySize { result }
     }
 
+    /// Creates a new [`Instruction::AtomicFence`].
+    pub fn atomic_fence() -> Self {
+        Self::AtomicFence
+    }
+
     /// Creates a new [`Instruction::MemoryGrow`] with the given `result`, `delta`.
     pub fn memory_grow(result: Register, delta: Register) -> Self {
         Self::MemoryGrow { result, delta }
@@ -1460,6 +1476,8 @@ constructor_for_load_instrs! {
     fn i32_load() -> Self::I32Load;
     fn i32_load_at(at) -> Self::I32LoadAt;
     fn i32_load_offset16(offset16) -> Self::I32LoadOffset16;
+    fn i32_load_offset16_add(offset16) -> Self::I32LoadOffset16Add;
+    fn i32_load_offset16_add_imm(offset16) -> Self::I32LoadOffset16AddImm;
 
     fn i32_load8_s() -> Self::I32Load8s;
     fn i32_load8_s_at(at) -> Self::I32Load8sAt;
@@ -1573,6 +1591,7 @@ constructor_for_store_instrs! {
     fn i32_store() -> Self::I32Store;
     fn i32_store_offset16(offset16) -> Self::I32StoreOffset16;
     fn i32_store_offset16_imm16({offset16_imm<i16>}) -> Self::I32StoreOffset16Imm16;
+    fn i32_store_offset16_add_imm(offset16) -> Self::I32StoreOffset16AddImm;
     fn i32_store_at(at) -> Self::I32StoreAt;
     fn i32_store_at_imm16({at_imm<i16>}) -> Self::I32StoreAtImm16;
 
@@ -1749,3 +1768,91 @@ constructor_for_divrem_imm_instrs! {
     fn i64_rem_s_imm16(NonZeroI64) -> Self::I64RemSImm16;
     fn i64_rem_u_imm16(NonZeroU64) -> Self::I64RemUImm16;
 }
+
+constructor_for_load_instrs! {
+    fn i32_atomic_load() -> Self::I32AtomicLoad;
+    fn i64_atomic_load() -> Self::I64AtomicLoad;
+    fn i32_atomic_load8_u() -> Self::I32AtomicLoad8u;
+    fn i32_atomic_load16_u() -> Self::I32AtomicLoad16u;
+    fn i64_atomic_load8_u() -> Self::I64AtomicLoad8u;
+    fn i64_atomic_load16_u() -> Self::I64AtomicLoad16u;
+    fn i64_atomic_load32_u() -> Self::I64AtomicLoad32u;
+}
+
+constructor_for_store_instrs! {
+    fn i32_atomic_store() -> Self::I32AtomicStore;
+    fn i64_atomic_store() -> Self::I64AtomicStore;
+    fn i32_atomic_store8() -> Self::I32AtomicStore8;
+    fn i32_atomic_store16() -> Self::I32AtomicStore16;
+    fn i64_atomic_store8() -> Self::I64AtomicStore8;
+    fn i64_atomic_store16() -> Self::I64AtomicStore16;
+    fn i64_atomic_store32() -> Self::I64AtomicStore32;
+}
+
+macro_rules! constructor_for_atomic_rmw_instrs {
+    ( $( fn $name:ident() -> Self::$op_code:ident; )* ) => {
+        impl Instruction {
+            $(
+                #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
+                pub fn $name(result: Register, ptr: Register, value: Register) -> Self {
+                    Self::$op_code(AtomicRmwInstr::new(result, ptr, value))
+                }
+            )*
+        }
+    }
+}
+constructor_for_atomic_rmw_instrs! {
+    fn memory_atomic_notify() -> Self::MemoryAtomicNotify;
+    fn memory_atomic_wait32() -> Self::MemoryAtomicWait32;
+    fn memory_atomic_wait64() -> Self::MemoryAtomicWait64;
+
+    fn i32_atomic_rmw_add() -> Self::I32AtomicRmwAdd;
+    fn i64_atomic_rmw_add() -> Self::I64AtomicRmwAdd;
+    fn i32_atomic_rmw8_add_u() -> Self::I32AtomicRmw8AddU;
+    fn i32_atomic_rmw16_add_u() -> Self::I32AtomicRmw16AddU;
+    fn i64_atomic_rmw8_add_u() -> Self::I64AtomicRmw8AddU;
+    fn i64_atomic_rmw16_add_u() -> Self::I64AtomicRmw16AddU;
+    fn i64_atomic_rmw32_add_u() -> Self::I64AtomicRmw32AddU;
+    fn i32_atomic_rmw_sub() -> Self::I32AtomicRmwSub;
+    fn i64_atomic_rmw_sub() -> Self::I64AtomicRmwSub;
+    fn i32_atomic_rmw8_sub_u() -> Self::I32AtomicRmw8SubU;
+    fn i32_atomic_rmw16_sub_u() -> Self::I32AtomicRmw16SubU;
+    fn i64_atomic_rmw8_sub_u() -> Self::I64AtomicRmw8SubU;
+    fn i64_atomic_rmw16_sub_u() -> Self::I64AtomicRmw16SubU;
+    fn i64_atomic_rmw32_sub_u() -> Self::I64AtomicRmw32SubU;
+    fn i32_atomic_rmw_and() -> Self::I32AtomicRmwAnd;
+    fn i64_atomic_rmw_and() -> Self::I64AtomicRmwAnd;
+    fn i32_atomic_rmw8_and_u() -> Self::I32AtomicRmw8AndU;
+    fn i32_atomic_rmw16_and_u() -> Self::I32AtomicRmw16AndU;
+    fn i64_atomic_rmw8_and_u() -> Self::I64AtomicRmw8AndU;
+    fn i64_atomic_rmw16_and_u() -> Self::I64AtomicRmw16AndU;
+    fn i64_atomic_rmw32_and_u() -> Self::I64AtomicRmw32AndU;
+    fn i32_atomic_rmw_or() -> Self::I32AtomicRmwOr;
+    fn i64_atomic_rmw_or() -> Self::I64AtomicRmwOr;
+    fn i32_atomic_rmw8_or_u() -> Self::I32AtomicRmw8OrU;
+    fn i32_atomic_rmw16_or_u() -> Self::I32AtomicRmw16OrU;
+    fn i64_atomic_rmw8_or_u() -> Self::I64AtomicRmw8OrU;
+    fn i64_atomic_rmw16_or_u() -> Self::I64AtomicRmw16OrU;
+    fn i64_atomic_rmw32_or_u() -> Self::I64AtomicRmw32OrU;
+    fn i32_atomic_rmw_xor() -> Self::I32AtomicRmwXor;
+    fn i64_atomic_rmw_xor() -> Self::I64AtomicRmwXor;
+    fn i32_atomic_rmw8_xor_u() -> Self::I32AtomicRmw8XorU;
+    fn i32_atomic_rmw16_xor_u() -> Self::I32AtomicRmw16XorU;
+    fn i64_atomic_rmw8_xor_u() -> Self::I64AtomicRmw8XorU;
+    fn i64_atomic_rmw16_xor_u() -> Self::I64AtomicRmw16XorU;
+    fn i64_atomic_rmw32_xor_u() -> Self::I64AtomicRmw32XorU;
+    fn i32_atomic_rmw_xchg() -> Self::I32AtomicRmwXchg;
+    fn i64_atomic_rmw_xchg() -> Self::I64AtomicRmwXchg;
+    fn i32_atomic_rmw8_xchg_u() -> Self::I32AtomicRmw8XchgU;
+    fn i32_atomic_rmw16_xchg_u() -> Self::I32AtomicRmw16XchgU;
+    fn i64_atomic_rmw8_xchg_u() -> Self::I64AtomicRmw8XchgU;
+    fn i64_atomic_rmw16_xchg_u() -> Self::I64AtomicRmw16XchgU;
+    fn i64_atomic_rmw32_xchg_u() -> Self::I64AtomicRmw32XchgU;
+    fn i32_atomic_rmw_cmpxchg() -> Self::I32AtomicRmwCmpxchg;
+    fn i64_atomic_rmw_cmpxchg() -> Self::I64AtomicRmwCmpxchg;
+    fn i32_atomic_rmw8_cmpxchg_u() -> Self::I32AtomicRmw8CmpxchgU;
+    fn i32_atomic_rmw16_cmpxchg_u() -> Self::I32AtomicRmw16CmpxchgU;
+    fn i64_atomic_rmw8_cmpxchg_u() -> Self::I64AtomicRmw8CmpxchgU;
+    fn i64_atomic_rmw16_cmpxchg_u() -> Self::I64AtomicRmw16CmpxchgU;
+    fn i64_atomic_rmw32_cmpxchg_u() -> Self::I64AtomicRmw32CmpxchgU;
+}
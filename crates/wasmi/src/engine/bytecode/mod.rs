@@ -10,6 +10,7 @@ pub(crate) use self::{
     immediate::{AnyConst16, AnyConst32, Const16, Const32},
     provider::{Provider, ProviderSliceStack, UntypedProvider},
     utils::{
+        AtomicRmwInstr,
         BinInstr,
         BinInstrImm,
         BinInstrImm16,
@@ -67,7 +68,11 @@ use wasmi_core::TrapCode;
 ///
 /// - `rN`: Register
 /// - `cN`: Constant (immediate) value
+///
+/// With the `handler-table` crate feature the [`Instruction`] has a primitive `u16`
+/// representation so that the executor can dispatch on its discriminant directly.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "handler-table", repr(u16))]
 pub enum Instruction {
     /// A [`TableIdx`] instruction parameter.
     ///
@@ -773,6 +778,31 @@ pub enum Instruction {
         values: [Register; 2],
     },
 
+    /// Wasm `local.get` equivalent Wasmi instruction for spilled local variables.
+    ///
+    /// # Note
+    ///
+    /// Functions with more local variables than fit into the register space
+    /// store the excess local variables in slots behind the registers of
+    /// their call frame. Those local variables are called spilled.
+    LocalGetSpilled {
+        /// The register storing the value of the spilled local variable.
+        result: Register,
+        /// The index of the spilled local variable within the call frame.
+        slot: u32,
+    },
+    /// Wasm `local.set` equivalent Wasmi instruction for spilled local variables.
+    ///
+    /// # Note
+    ///
+    /// See [`Instruction::LocalGetSpilled`] for more information about spilled local variables.
+    LocalSetSpilled {
+        /// The register holding the value to be stored in the spilled local variable.
+        value: Register,
+        /// The index of the spilled local variable within the call frame.
+        slot: u32,
+    },
+
     /// Wasm `return_call` equivalent Wasmi instruction.
     ///
     /// # Note
@@ -1841,10 +1871,10 @@ pub enum Instruction {
     },
     /// Wasm `global.set` equivalent Wasmi instruction.
     GlobalSet {
-        /// The index identifying the global variable for the `global.set` instruction.
-        global: GlobalIdx,
         /// The register holding the value to be stored in the global variable.
         input: Register,
+        /// The index identifying the global variable for the `global.set` instruction.
+        global: GlobalIdx,
     },
     /// Wasm `global.set` equivalent Wasmi instruction.
     ///
@@ -1852,10 +1882,10 @@ pub enum Instruction {
     ///
     /// Variant of [`Instruction::GlobalSet`] for 16-bit encoded `i32` immutable `input` values.
     GlobalSetI32Imm16 {
-        /// The index identifying the global variable for the `global.set` instruction.
-        global: GlobalIdx,
         /// The 16-bit encoded `i32` value.
         input: Const16<i32>,
+        /// The index identifying the global variable for the `global.set` instruction.
+        global: GlobalIdx,
     },
     /// Wasm `global.set` equivalent Wasmi instruction.
     ///
@@ -1863,10 +1893,10 @@ pub enum Instruction {
     ///
     /// Variant of [`Instruction::GlobalSet`] for 16-bit encoded `i64` immutable `input` values.
     GlobalSetI64Imm16 {
-        /// The index identifying the global variable for the `global.set` instruction.
-        global: GlobalIdx,
         /// The 16-bit encoded `i64` value.
         input: Const16<i64>,
+        /// The index identifying the global variable for the `global.set` instruction.
+        global: GlobalIdx,
     },
 
     /// Wasm `i32.load` equivalent Wasmi instruction.
@@ -1888,6 +1918,32 @@ pub enum Instruction {
     ///
     /// Variant of [`Instruction::I32Load`] for small offset values.
     I32LoadOffset16(LoadOffset16Instr),
+    /// Fused Wasm `i32.load` and `i32.add` equivalent Wasmi instruction.
+    ///
+    /// Adds the `rhs` register to the loaded value: `result = i32.load(ptr+offset) + rhs`.
+    ///
+    /// # Note
+    ///
+    /// Superinstruction for [`Instruction::I32LoadOffset16`] followed by [`Instruction::I32Add`].
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Register`]
+    /// that represents the `rhs` of the addition.
+    I32LoadOffset16Add(LoadOffset16Instr),
+    /// Fused Wasm `i32.load` and `i32.add` equivalent Wasmi instruction.
+    ///
+    /// Adds the constant `rhs` to the loaded value: `result = i32.load(ptr+offset) + rhs`.
+    ///
+    /// # Note
+    ///
+    /// Superinstruction for [`Instruction::I32LoadOffset16`] followed by [`Instruction::I32AddImm16`].
+    ///
+    /// # Encoding
+    ///
+    /// This [`Instruction`] must be followed by an [`Instruction::Const32`]
+    /// that represents the `rhs` of the addition.
+    I32LoadOffset16AddImm(LoadOffset16Instr),
 
     /// Wasm `i64.load` equivalent Wasmi instruction.
     ///
@@ -2159,6 +2215,18 @@ pub enum Instruction {
     I32StoreOffset16(StoreOffset16Instr<Register>),
     /// Variant of [`Instruction::I32StoreOffset16`] for constant 16-bit `value`.
     I32StoreOffset16Imm16(StoreOffset16Instr<Const16<i32>>),
+    /// Fused Wasm `i32.add` and `i32.store` equivalent Wasmi instruction.
+    ///
+    /// Stores the sum of `value` and the constant `rhs`: `i32.store(ptr+offset, value + rhs)`.
+    ///
+    /// # Note
+    ///
+    /// Superinstruction for [`Instruction::I32AddImm16`] followed by [`Instruction::I32StoreOffset16`].
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by [`Instruction::Const32`] to encode `rhs`.
+    I32StoreOffset16AddImm(StoreOffset16Instr<Register>),
     /// Variant of [`Instruction::I32Store`] for constant `address`.
     I32StoreAt(StoreAtInstr<Register>),
     /// Variant of [`Instruction::I32StoreAt`] for constant 16-bit `value`.
@@ -2276,6 +2344,459 @@ pub enum Instruction {
     /// Variant of [`Instruction::F32Store`] for constant `address`.
     F64StoreAt(StoreAtInstr<Register>),
 
+    /// Wasm `atomic.fence` equivalent Wasmi instruction.
+    AtomicFence,
+
+    /// Wasm `memory.atomic.notify` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the number of waiters to notify.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    MemoryAtomicNotify(AtomicRmwInstr),
+    /// Wasm `memory.atomic.wait32` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `timeout`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    MemoryAtomicWait32(AtomicRmwInstr),
+    /// Wasm `memory.atomic.wait64` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `timeout`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    MemoryAtomicWait64(AtomicRmwInstr),
+
+    /// Wasm `i32.atomic.load` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicLoad(LoadInstr),
+    /// Wasm `i64.atomic.load` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicLoad(LoadInstr),
+    /// Wasm `i32.atomic.load8_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicLoad8u(LoadInstr),
+    /// Wasm `i32.atomic.load16_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicLoad16u(LoadInstr),
+    /// Wasm `i64.atomic.load8_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicLoad8u(LoadInstr),
+    /// Wasm `i64.atomic.load16_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicLoad16u(LoadInstr),
+    /// Wasm `i64.atomic.load32_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicLoad32u(LoadInstr),
+
+    /// Wasm `i32.atomic.store` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I32AtomicStore(StoreInstr),
+    /// Wasm `i64.atomic.store` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I64AtomicStore(StoreInstr),
+    /// Wasm `i32.atomic.store8` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I32AtomicStore8(StoreInstr),
+    /// Wasm `i32.atomic.store16` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I32AtomicStore16(StoreInstr),
+    /// Wasm `i64.atomic.store8` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I64AtomicStore8(StoreInstr),
+    /// Wasm `i64.atomic.store16` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I64AtomicStore16(StoreInstr),
+    /// Wasm `i64.atomic.store32` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Register`] to encode `value`.
+    I64AtomicStore32(StoreInstr),
+
+    /// Wasm `i32.atomic.rmw.add` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwAdd(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.add` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwAdd(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.add_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8AddU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.add_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16AddU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.add_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8AddU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.add_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16AddU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.add_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32AddU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw.sub` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwSub(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.sub` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwSub(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.sub_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8SubU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.sub_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16SubU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.sub_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8SubU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.sub_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16SubU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.sub_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32SubU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw.and` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwAnd(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.and` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwAnd(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.and_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8AndU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.and_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16AndU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.and_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8AndU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.and_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16AndU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.and_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32AndU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw.or` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwOr(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.or` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwOr(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.or_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8OrU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.or_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16OrU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.or_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8OrU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.or_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16OrU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.or_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32OrU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw.xor` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwXor(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.xor` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwXor(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.xor_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8XorU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.xor_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16XorU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.xor_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8XorU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.xor_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16XorU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.xor_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32XorU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw.xchg` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwXchg(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.xchg` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwXchg(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.xchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8XchgU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.xchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16XchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.xchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8XchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.xchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16XchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.xchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Encoding
+    ///
+    /// Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32XchgU(AtomicRmwInstr),
+
+    /// Wasm `i32.atomic.rmw.cmpxchg` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmwCmpxchg(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw.cmpxchg` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmwCmpxchg(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw8.cmpxchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw8CmpxchgU(AtomicRmwInstr),
+    /// Wasm `i32.atomic.rmw16.cmpxchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I32AtomicRmw16CmpxchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw8.cmpxchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw8CmpxchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw16.cmpxchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw16CmpxchgU(AtomicRmwInstr),
+    /// Wasm `i64.atomic.rmw32.cmpxchg_u` equivalent Wasmi instruction.
+    ///
+    /// # Note
+    ///
+    /// The `value` field of the [`AtomicRmwInstr`] refers to the expected value.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed by an [`Instruction::Register`] to encode the `replacement`.
+    /// 2. Must be followed by an [`Instruction::Const32`] to encode the `offset`.
+    I64AtomicRmw32CmpxchgU(AtomicRmwInstr),
+
     /// `i32` equality comparison instruction: `r0 = r1 == r2`
     I32Eq(BinInstr),
     /// `i32` equality comparison instruction with immediate: `r0 = r1 == c0`
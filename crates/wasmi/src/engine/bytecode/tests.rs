@@ -8,6 +8,7 @@ fn bytecode_size() {
     assert_eq!(size_of::<BinInstr>(), 6);
     assert_eq!(size_of::<BinInstrImm16<i32>>(), 6);
     assert_eq!(size_of::<BinInstrImm16<i64>>(), 6);
+    assert_eq!(size_of::<AtomicRmwInstr>(), 6);
     assert_eq!(size_of::<Instruction>(), 8);
 }
 
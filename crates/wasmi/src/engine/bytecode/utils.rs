@@ -458,6 +458,35 @@ impl<T> StoreAtInstr<T> {
     }
 }
 
+/// A general atomic read-modify-write instruction.
+///
+/// # Note
+///
+/// Also used by `memory.atomic.wait` and `memory.atomic.notify` instructions
+/// where `value` refers to the expected value or the number of waiters to notify.
+///
+/// # Encoding
+///
+/// This instruction stores its offset parameter in a separate
+/// [`Instruction::Const32`] instruction that must follow this
+/// [`Instruction`] or its additional [`Instruction::Register`] parameter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AtomicRmwInstr {
+    /// The register storing the result of the instruction.
+    pub result: Register,
+    /// The register storing the pointer of the instruction.
+    pub ptr: Register,
+    /// The register storing the value operand of the instruction.
+    pub value: Register,
+}
+
+impl AtomicRmwInstr {
+    /// Creates a new [`AtomicRmwInstr`].
+    pub fn new(result: Register, ptr: Register, value: Register) -> Self {
+        Self { result, ptr, value }
+    }
+}
+
 /// The sign of a value.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Sign {
@@ -1,7 +1,7 @@
 use crate::{
     engine::bytecode::{DataSegmentIdx, ElementSegmentIdx, FuncIdx, GlobalIdx, TableIdx},
     instance::InstanceEntity,
-    memory::DataSegment,
+    memory::{DataSegment, MemoryEntity},
     module::DEFAULT_MEMORY_INDEX,
     store::Fuel,
     table::TableEntity,
@@ -10,6 +10,7 @@ use crate::{
     Func,
     Instance,
     Memory,
+    SharedMemory,
     StoreInner,
     Table,
 };
@@ -21,7 +22,7 @@ use wasmi_core::UntypedValue;
 #[repr(C)]
 pub struct InstanceCache {
     /// The bytes of a default linear memory of the currently used [`Instance`].
-    default_memory_bytes: Option<NonNull<[u8]>>,
+    default_memory_bytes: Option<CachedMemoryBytes>,
     /// The last accessed global variable value of the currently used [`Instance`].
     last_global: Option<(GlobalIdx, NonNull<UntypedValue>)>,
     /// The current instance in use.
@@ -34,6 +35,26 @@ pub struct InstanceCache {
     last_func: Option<(FuncIdx, Func)>,
 }
 
+/// The cached bytes of the default linear memory.
+#[derive(Debug)]
+enum CachedMemoryBytes {
+    /// The bytes of an unshared linear memory.
+    Unshared(NonNull<[u8]>),
+    /// A shared linear memory whose bytes must not be borrowed.
+    Shared(SharedMemory),
+}
+
+/// The bytes of the default linear memory returned by [`InstanceCache::default_memory_bytes`].
+#[derive(Debug)]
+pub enum DefaultMemoryBytes<'a> {
+    /// The bytes of an unshared linear memory.
+    Unshared(&'a mut [u8]),
+    /// A shared linear memory.
+    ///
+    /// Its bytes must be accessed atomically since other threads may access them concurrently.
+    Shared(&'a SharedMemory),
+}
+
 impl From<&'_ Instance> for InstanceCache {
     fn from(instance: &Instance) -> Self {
         Self {
@@ -119,11 +140,11 @@ impl InstanceCache {
         &mut self,
         ctx: &'a mut StoreInner,
         segment: DataSegmentIdx,
-    ) -> (&'a mut [u8], &'a [u8], &'a mut Fuel) {
+    ) -> (&'a mut MemoryEntity, &'a [u8], &'a mut Fuel) {
         let seg = self.get_data_segment(ctx, segment.to_u32());
         let mem = self.default_memory(ctx);
         let (memory, segment, fuel) = ctx.resolve_memory_init_triplet(mem, &seg);
-        (memory.data_mut(), segment.bytes(), fuel)
+        (memory, segment.bytes(), fuel)
     }
 
     /// Returns all necessary data required to execute a `table.init` instruction.
@@ -185,14 +206,25 @@ impl InstanceCache {
     ///
     /// # Note
     ///
-    /// This avoids one indirection compared to using the `default_memory`.
+    /// - This avoids one indirection compared to using the `default_memory`.
+    /// - The bytes of a shared default linear memory are not borrowed since
+    ///   other threads may access them concurrently. Instead the [`SharedMemory`]
+    ///   is returned so that callers can access its bytes atomically.
     #[inline]
-    pub fn default_memory_bytes<'ctx>(&mut self, ctx: &'ctx mut StoreInner) -> &'ctx mut [u8] {
-        let bytes = match self.default_memory_bytes {
+    pub fn default_memory_bytes<'a>(
+        &'a mut self,
+        ctx: &'a mut StoreInner,
+    ) -> DefaultMemoryBytes<'a> {
+        let cached = match self.default_memory_bytes {
             Some(ref mut cached) => cached,
             None => self.load_default_memory_bytes(ctx),
         };
-        unsafe { bytes.as_mut() }
+        match cached {
+            CachedMemoryBytes::Unshared(bytes) => {
+                DefaultMemoryBytes::Unshared(unsafe { bytes.as_mut() })
+            }
+            CachedMemoryBytes::Shared(shared) => DefaultMemoryBytes::Shared(shared),
+        }
     }
 
     /// Loads and populates the cached default memory instance.
@@ -200,10 +232,14 @@ impl InstanceCache {
     /// Returns an exclusive reference to the cached default memory.
     #[cold]
     #[inline]
-    fn load_default_memory_bytes(&mut self, ctx: &mut StoreInner) -> &mut NonNull<[u8]> {
+    fn load_default_memory_bytes(&mut self, ctx: &mut StoreInner) -> &mut CachedMemoryBytes {
         let memory = *self.default_memory(ctx);
-        self.default_memory_bytes
-            .insert(ctx.resolve_memory_mut(&memory).data_mut().into())
+        let memory = ctx.resolve_memory_mut(&memory);
+        let cached = match memory.shared() {
+            Some(shared) => CachedMemoryBytes::Shared(shared.clone()),
+            None => CachedMemoryBytes::Unshared(memory.data_mut().into()),
+        };
+        self.default_memory_bytes.insert(cached)
     }
 
     /// Clears the cached default memory instance.
@@ -266,6 +266,12 @@ pub struct CompiledFuncEntity {
     /// This includes registers to store the function local constant values,
     /// function parameters, function locals and dynamically used registers.
     len_registers: u16,
+    /// The number of spilled local variables of the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// Spilled local variables are stored in the call frame right after the registers.
+    len_spilled: u32,
     /// The constant values local to the [`CompiledFunc`].
     consts: Box<[UntypedValue]>,
 }
@@ -277,7 +283,7 @@ impl CompiledFuncEntity {
     ///
     /// - If `instrs` is empty.
     /// - If `instrs` contains more than `u32::MAX` instructions.
-    pub fn new<I, C>(len_registers: u16, instrs: I, consts: C) -> Self
+    pub fn new<I, C>(len_registers: u16, len_spilled: u32, instrs: I, consts: C) -> Self
     where
         I: IntoIterator<Item = Instruction>,
         C: IntoIterator<Item = UntypedValue>,
@@ -291,6 +297,7 @@ impl CompiledFuncEntity {
         Self {
             instrs,
             len_registers,
+            len_spilled,
             consts,
         }
     }
@@ -300,6 +307,7 @@ impl CompiledFuncEntity {
         Self {
             instrs: [].into(),
             len_registers: 0,
+            len_spilled: 0,
             consts: [].into(),
         }
     }
@@ -314,6 +322,11 @@ impl CompiledFuncEntity {
         self.len_registers
     }
 
+    /// Returns the number of spilled local variables of the [`CompiledFunc`].
+    pub fn len_spilled(&self) -> u32 {
+        self.len_spilled
+    }
+
     /// Returns the number of mutable registers used by the [`CompiledFunc`].
     ///
     /// # Note
@@ -707,6 +720,22 @@ impl CodeMap {
         func.init_uncompiled(func_idx, bytes, module, func_to_validate);
     }
 
+    /// Returns the [`CompiledFuncEntity`] of the [`CompiledFunc`] if it has already been compiled.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`CodeMap::get`] this never compiles the [`CompiledFunc`].
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn get_compiled(&self, func: CompiledFunc) -> Option<&CompiledFuncEntity> {
+        let Some(entity) = self.funcs.get(func) else {
+            panic!("invalid compiled func: {func:?}")
+        };
+        entity.get_compiled()
+    }
+
     /// Returns the [`InternalFuncEntity`] of the [`CompiledFunc`].
     ///
     /// # Errors
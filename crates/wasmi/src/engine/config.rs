@@ -31,6 +31,8 @@ pub struct Config {
     tail_call: bool,
     /// Is `true` if the [`extended-const`] Wasm proposal is enabled.
     extended_const: bool,
+    /// Is `true` if the [`threads`] Wasm proposal is enabled.
+    threads: bool,
     /// Is `true` if Wasm instructions on `f32` and `f64` types are allowed.
     floats: bool,
     /// Is `true` if Wasmi executions shall consume fuel.
@@ -39,6 +41,12 @@ pub struct Config {
     fuel_costs: FuelCosts,
     /// The mode of Wasm to Wasmi bytecode compilation.
     compilation_mode: CompilationMode,
+    /// The backend used to allocate the bytes of linear memories.
+    memory_backend: MemoryBackend,
+    /// The optimization level applied to translated Wasmi bytecode.
+    opt_level: OptLevel,
+    /// The maximum number of instructions of functions inlined at their call sites.
+    inline_budget: u32,
 }
 
 /// Type storing all kinds of fuel costs of instructions.
@@ -163,6 +171,50 @@ pub enum CompilationMode {
     Lazy,
 }
 
+/// The optimization level applied to Wasmi bytecode after translation.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OptLevel {
+    /// No optimizations are applied after translation.
+    ///
+    /// # Note
+    ///
+    /// The translator still applies its local optimizations such as constant folding.
+    #[default]
+    None,
+    /// Optimizations for execution speed are applied after translation.
+    ///
+    /// # Note
+    ///
+    /// This removes redundant `copy` instructions as well as instructions without
+    /// side effects whose results are never read, threads branches through
+    /// unconditional branches and replaces branches to `return` instructions
+    /// with the `return` itself. This slightly increases translation time.
+    Speed,
+}
+
+/// The backend used to allocate the bytes of linear memories.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryBackend {
+    /// Linear memories are backed by heap allocated `Vec<u8>` buffers.
+    ///
+    /// # Note
+    ///
+    /// Growing a linear memory may reallocate and copy all of its bytes.
+    #[default]
+    Vec,
+    /// Linear memories are backed by OS provided virtual memory.
+    ///
+    /// # Note
+    ///
+    /// - The virtual address space for the maximum size of a linear memory
+    ///   is reserved upfront so that growing it never reallocates or copies.
+    ///   Linear memories without a maximum size reserve 4GiB of address space.
+    /// - The reserved address space is followed by inaccessible guard pages.
+    /// - This is only supported on Linux with the `std` crate feature enabled.
+    ///   On all other targets this falls back to [`MemoryBackend::Vec`].
+    VirtualMemory,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -176,10 +228,14 @@ impl Default for Config {
             reference_types: true,
             tail_call: false,
             extended_const: false,
+            threads: false,
             floats: true,
             consume_fuel: false,
             fuel_costs: FuelCosts::default(),
             compilation_mode: CompilationMode::default(),
+            memory_backend: MemoryBackend::default(),
+            opt_level: OptLevel::default(),
+            inline_budget: 0,
         }
     }
 }
@@ -308,6 +364,19 @@ impl Config {
         self
     }
 
+    /// Enable or disable the [`threads`] Wasm proposal for the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// - Enables shared linear memories and atomic instructions.
+    /// - Disabled by default.
+    ///
+    /// [`threads`]: https://github.com/WebAssembly/threads
+    pub fn wasm_threads(&mut self, enable: bool) -> &mut Self {
+        self.threads = enable;
+        self
+    }
+
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
     /// Enabled by default.
@@ -365,6 +434,58 @@ impl Config {
         self.compilation_mode
     }
 
+    /// Sets the [`MemoryBackend`] used to allocate linear memories.
+    ///
+    /// # Note
+    ///
+    /// Defaults to [`MemoryBackend::Vec`].
+    pub fn memory_backend(&mut self, backend: MemoryBackend) -> &mut Self {
+        self.memory_backend = backend;
+        self
+    }
+
+    /// Returns the [`MemoryBackend`] used to allocate linear memories.
+    pub(crate) fn get_memory_backend(&self) -> MemoryBackend {
+        self.memory_backend
+    }
+
+    /// Sets the [`OptLevel`] applied to Wasmi bytecode after translation.
+    ///
+    /// # Note
+    ///
+    /// - Optimizations never change fuel consumption.
+    /// - Defaults to [`OptLevel::None`].
+    pub fn opt_level(&mut self, level: OptLevel) -> &mut Self {
+        self.opt_level = level;
+        self
+    }
+
+    /// Returns the [`OptLevel`] applied to Wasmi bytecode after translation.
+    pub(crate) fn get_opt_level(&self) -> OptLevel {
+        self.opt_level
+    }
+
+    /// Sets the maximum number of Wasmi bytecode instructions of functions inlined at their call sites.
+    ///
+    /// # Note
+    ///
+    /// - Only small leaf functions that have already been compiled when translating
+    ///   the `call` are inlined. Those functions must not contain calls or branches,
+    ///   must not use function local constants and must have at most one result.
+    /// - Inlining never changes fuel consumption or trap semantics with the exception
+    ///   that inlined calls can no longer exhaust the call stack.
+    /// - A `budget` of 0 disables inlining.
+    /// - Defaults to 0.
+    pub fn inline_budget(&mut self, budget: u32) -> &mut Self {
+        self.inline_budget = budget;
+        self
+    }
+
+    /// Returns the maximum number of Wasmi bytecode instructions of inlined functions.
+    pub(crate) fn get_inline_budget(&self) -> u32 {
+        self.inline_budget
+    }
+
     /// Returns the [`WasmFeatures`] represented by the [`Config`].
     pub(crate) fn wasm_features(&self) -> WasmFeatures {
         WasmFeatures {
@@ -380,7 +501,7 @@ impl Config {
             component_model: false,
             simd: false,
             relaxed_simd: false,
-            threads: false,
+            threads: self.threads,
             multi_memory: false,
             exceptions: false,
             memory64: false,
@@ -1,5 +1,7 @@
 pub use self::call::CallKind;
 use self::{call::CallOutcome, return_::ReturnOutcome};
+#[cfg(feature = "instr-profiling")]
+use crate::engine::profile::InstrRecorder;
 use crate::{
     core::{TrapCode, UntypedValue},
     engine::{
@@ -20,6 +22,7 @@ use crate::{
         executor::stack::{CallFrame, CallStack, FrameRegisters, ValueStack},
         func_types::FuncTypeRegistry,
         CodeMap,
+        EngineResources,
     },
     store::ResourceLimiterRef,
     Error,
@@ -28,6 +31,7 @@ use crate::{
     StoreInner,
 };
 
+mod atomic;
 mod binary;
 mod branch;
 mod call;
@@ -36,6 +40,7 @@ mod conversion;
 mod copy;
 mod global;
 mod load;
+mod local;
 mod memory;
 mod return_;
 mod select;
@@ -43,6 +48,7 @@ mod store;
 mod table;
 mod unary;
 
+#[cfg_attr(feature = "handler-table", allow(unused_macros))]
 macro_rules! forward_call {
     ($expr:expr) => {{
         if let CallOutcome::Call {
@@ -60,6 +66,7 @@ macro_rules! forward_call {
     }};
 }
 
+#[cfg_attr(feature = "handler-table", allow(unused_macros))]
 macro_rules! forward_return {
     ($expr:expr) => {{
         if let ReturnOutcome::Host = $expr {
@@ -68,6 +75,163 @@ macro_rules! forward_return {
     }};
 }
 
+/// Defines how the [`Executor`] dispatches to the execution handler of each [`Instruction`].
+///
+/// Takes a list of `Variant pattern => handler` arms, one per [`Instruction`] variant,
+/// in the declaration order of the [`Instruction`] variants.
+///
+/// # Note
+///
+/// By default this generates a single loop around a `match` on the current [`Instruction`].
+/// With the `handler-table` crate feature enabled each arm is instead turned into its own
+/// handler function which are indexed by the [`Instruction`] discriminant through a table
+/// of function pointers.
+macro_rules! define_executor {
+    (
+        $this:ident, $limiter:ident;
+        $( $name:ident $fields:tt => $handler:expr ),* $(,)?
+    ) => {
+        #[cfg(not(feature = "handler-table"))]
+        impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+            /// Executes the function frame until it returns or traps.
+            #[inline(always)]
+            fn execute(
+                mut $this,
+                $limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+            ) -> Result<WasmOutcome, Error> {
+                loop {
+                    #[cfg(feature = "instr-profiling")]
+                    $this.recorder.record(*$this.ip.get());
+                    match *$this.ip.get() {
+                        $( Instruction::$name $fields => { $handler; } )*
+                    }
+                }
+            }
+        }
+
+        /// The opcode of an [`Instruction`] in the order of the `define_executor!` arms.
+        ///
+        /// # Note
+        ///
+        /// This is only used to assert that the arms are listed in the declaration
+        /// order of the [`Instruction`] variants. The exhaustive `match` in
+        /// [`Opcode::of`] guarantees that each variant is listed exactly once.
+        #[cfg(feature = "handler-table")]
+        #[derive(Debug, Copy, Clone)]
+        #[repr(u16)]
+        enum Opcode {
+            $( $name ),*
+        }
+
+        #[cfg(feature = "handler-table")]
+        impl Opcode {
+            /// The number of distinct [`Opcode`]s.
+            const COUNT: usize = [$( Opcode::$name ),*].len();
+
+            /// Returns the [`Opcode`] of the [`Instruction`].
+            #[allow(dead_code)] // only used in debug assertions
+            fn of(instr: &Instruction) -> Self {
+                match instr {
+                    $( Instruction::$name { .. } => Self::$name ),*
+                }
+            }
+        }
+
+        /// The function pointer type of an [`Instruction`] execution handler.
+        ///
+        /// Returns `Some` if the execution of the function frame has to be suspended.
+        #[cfg(feature = "handler-table")]
+        type Handler<'ctx, 'engine> = fn(
+            &mut Executor<'ctx, 'engine>,
+            &mut ResourceLimiterRef<'ctx>,
+        ) -> Result<Option<WasmOutcome>, Error>;
+
+        #[cfg(feature = "handler-table")]
+        impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+            /// The execution handlers indexed by the discriminant of their [`Instruction`].
+            const HANDLERS: [Handler<'ctx, 'engine>; Opcode::COUNT] = [$( Self::$name ),*];
+
+            /// Returns the discriminant of the [`Instruction`].
+            ///
+            /// # Note
+            ///
+            /// Reading the discriminant directly avoids a `match` on the [`Instruction`]
+            /// before dispatching to its handler.
+            #[inline(always)]
+            fn discriminant(instr: &Instruction) -> usize {
+                // Safety: With the `handler-table` crate feature the [`Instruction`] has a
+                //         primitive `u16` representation which guarantees that every variant
+                //         starts with its `u16` discriminant.
+                let discriminant = unsafe { *<*const Instruction>::from(instr).cast::<u16>() };
+                debug_assert_eq!(
+                    discriminant,
+                    Opcode::of(instr) as u16,
+                    "the define_executor! arms must be in the declaration order of the Instruction variants",
+                );
+                usize::from(discriminant)
+            }
+
+            /// Executes the function frame until it returns or traps.
+            #[inline(always)]
+            fn execute(
+                mut $this,
+                $limiter: &'ctx mut ResourceLimiterRef<'ctx>,
+            ) -> Result<WasmOutcome, Error> {
+                loop {
+                    #[cfg(feature = "instr-profiling")]
+                    $this.recorder.record(*$this.ip.get());
+                    let handler = Self::HANDLERS[Self::discriminant($this.ip.get())];
+                    if let Some(outcome) = handler(&mut $this, &mut *$limiter)? {
+                        return Ok(outcome);
+                    }
+                }
+            }
+
+            $(
+                #[allow(non_snake_case, unused_variables)]
+                fn $name(
+                    &mut $this,
+                    $limiter: &mut ResourceLimiterRef<'ctx>,
+                ) -> Result<Option<WasmOutcome>, Error> {
+                    // Shadow the outer macros since handlers cannot return a `WasmOutcome` directly.
+                    #[allow(unused_macros)]
+                    macro_rules! forward_call {
+                        ($expr:expr) => {{
+                            if let CallOutcome::Call {
+                                results,
+                                host_func,
+                                call_kind,
+                            } = $expr?
+                            {
+                                return Ok(Some(WasmOutcome::Call {
+                                    results,
+                                    host_func,
+                                    call_kind,
+                                }));
+                            }
+                        }};
+                    }
+                    #[allow(unused_macros)]
+                    macro_rules! forward_return {
+                        ($expr:expr) => {{
+                            if let ReturnOutcome::Host = $expr {
+                                return Ok(Some(WasmOutcome::Return));
+                            }
+                        }};
+                    }
+                    let Instruction::$name $fields = *$this.ip.get() else {
+                        unreachable!(
+                            "the define_executor! arms must be in the declaration order of the Instruction variants",
+                        )
+                    };
+                    $handler;
+                    Ok(None)
+                }
+            )*
+        }
+    };
+}
+
 /// The outcome of a Wasm execution.
 ///
 /// # Note
@@ -101,12 +265,20 @@ pub fn execute_instrs<'ctx, 'engine>(
     cache: &'engine mut InstanceCache,
     value_stack: &'engine mut ValueStack,
     call_stack: &'engine mut CallStack,
-    code_map: &'engine CodeMap,
-    func_types: &'engine FuncTypeRegistry,
+    res: &'engine EngineResources,
     resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
 ) -> Result<WasmOutcome, Error> {
-    Executor::new(ctx, cache, value_stack, call_stack, code_map, func_types)
-        .execute(resource_limiter)
+    Executor::new(
+        ctx,
+        cache,
+        value_stack,
+        call_stack,
+        &res.code_map,
+        &res.func_types,
+        #[cfg(feature = "instr-profiling")]
+        InstrRecorder::new(&res.profile),
+    )
+    .execute(resource_limiter)
 }
 
 /// An execution context for executing a Wasmi function frame.
@@ -147,6 +319,9 @@ struct Executor<'ctx, 'engine> {
     ///
     /// This is used to lookup Wasm function information.
     func_types: &'engine FuncTypeRegistry,
+    /// Records the consecutively executed instructions.
+    #[cfg(feature = "instr-profiling")]
+    recorder: InstrRecorder<'engine>,
 }
 
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
@@ -159,6 +334,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         call_stack: &'engine mut CallStack,
         code_map: &'engine CodeMap,
         func_types: &'engine FuncTypeRegistry,
+        #[cfg(feature = "instr-profiling")] recorder: InstrRecorder<'engine>,
     ) -> Self {
         let frame = call_stack
             .peek()
@@ -177,691 +353,776 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             call_stack,
             code_map,
             func_types,
+            #[cfg(feature = "instr-profiling")]
+            recorder,
         }
     }
+}
 
-    /// Executes the function frame until it returns or traps.
-    #[inline(always)]
-    fn execute(
-        mut self,
-        resource_limiter: &'ctx mut ResourceLimiterRef<'ctx>,
-    ) -> Result<WasmOutcome, Error> {
-        use Instruction as Instr;
-        loop {
-            match *self.ip.get() {
-                Instr::TableIdx(_)
-                | Instr::DataSegmentIdx(_)
-                | Instr::ElementSegmentIdx(_)
-                | Instr::Const32(_)
-                | Instr::I64Const32(_)
-                | Instr::F64Const32(_)
-                | Instr::Register(_)
-                | Instr::Register2(_)
-                | Instr::Register3(_)
-                | Instr::RegisterList(_)
-                | Instr::CallIndirectParams(_)
-                | Instr::CallIndirectParamsImm16(_) => self.invalid_instruction_word()?,
-                Instr::Trap(trap_code) => self.execute_trap(trap_code)?,
-                Instr::ConsumeFuel(block_fuel) => self.execute_consume_fuel(block_fuel)?,
-                Instr::Return => {
-                    forward_return!(self.execute_return())
-                }
-                Instr::ReturnReg { value } => {
-                    forward_return!(self.execute_return_reg(value))
-                }
-                Instr::ReturnReg2 { values } => {
-                    forward_return!(self.execute_return_reg2(values))
-                }
-                Instr::ReturnReg3 { values } => {
-                    forward_return!(self.execute_return_reg3(values))
-                }
-                Instr::ReturnImm32 { value } => {
-                    forward_return!(self.execute_return_imm32(value))
-                }
-                Instr::ReturnI64Imm32 { value } => {
-                    forward_return!(self.execute_return_i64imm32(value))
-                }
-                Instr::ReturnF64Imm32 { value } => {
-                    forward_return!(self.execute_return_f64imm32(value))
-                }
-                Instr::ReturnSpan { values } => {
-                    forward_return!(self.execute_return_span(values))
-                }
-                Instr::ReturnMany { values } => {
-                    forward_return!(self.execute_return_many(values))
-                }
-                Instr::ReturnNez { condition } => {
-                    forward_return!(self.execute_return_nez(condition))
-                }
-                Instr::ReturnNezReg { condition, value } => {
-                    forward_return!(self.execute_return_nez_reg(condition, value))
-                }
-                Instr::ReturnNezReg2 { condition, values } => {
-                    forward_return!(self.execute_return_nez_reg2(condition, values))
-                }
-                Instr::ReturnNezImm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_imm32(condition, value))
-                }
-                Instr::ReturnNezI64Imm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_i64imm32(condition, value))
-                }
-                Instr::ReturnNezF64Imm32 { condition, value } => {
-                    forward_return!(self.execute_return_nez_f64imm32(condition, value))
-                }
-                Instr::ReturnNezSpan { condition, values } => {
-                    forward_return!(self.execute_return_nez_span(condition, values))
-                }
-                Instr::ReturnNezMany { condition, values } => {
-                    forward_return!(self.execute_return_nez_many(condition, values))
-                }
-                Instr::Branch { offset } => self.execute_branch(offset),
-                Instr::BranchTable { index, len_targets } => {
-                    self.execute_branch_table(index, len_targets)
-                }
-                Instr::BranchCmpFallback { lhs, rhs, params } => {
-                    self.execute_branch_cmp_fallback(lhs, rhs, params)
-                }
-                Instr::BranchI32And(instr) => self.execute_branch_i32_and(instr),
-                Instr::BranchI32AndImm(instr) => self.execute_branch_i32_and_imm(instr),
-                Instr::BranchI32Or(instr) => self.execute_branch_i32_or(instr),
-                Instr::BranchI32OrImm(instr) => self.execute_branch_i32_or_imm(instr),
-                Instr::BranchI32Xor(instr) => self.execute_branch_i32_xor(instr),
-                Instr::BranchI32XorImm(instr) => self.execute_branch_i32_xor_imm(instr),
-                Instr::BranchI32AndEqz(instr) => self.execute_branch_i32_and_eqz(instr),
-                Instr::BranchI32AndEqzImm(instr) => self.execute_branch_i32_and_eqz_imm(instr),
-                Instr::BranchI32OrEqz(instr) => self.execute_branch_i32_or_eqz(instr),
-                Instr::BranchI32OrEqzImm(instr) => self.execute_branch_i32_or_eqz_imm(instr),
-                Instr::BranchI32XorEqz(instr) => self.execute_branch_i32_xor_eqz(instr),
-                Instr::BranchI32XorEqzImm(instr) => self.execute_branch_i32_xor_eqz_imm(instr),
-                Instr::BranchI32Eq(instr) => self.execute_branch_i32_eq(instr),
-                Instr::BranchI32EqImm(instr) => self.execute_branch_i32_eq_imm(instr),
-                Instr::BranchI32Ne(instr) => self.execute_branch_i32_ne(instr),
-                Instr::BranchI32NeImm(instr) => self.execute_branch_i32_ne_imm(instr),
-                Instr::BranchI32LtS(instr) => self.execute_branch_i32_lt_s(instr),
-                Instr::BranchI32LtSImm(instr) => self.execute_branch_i32_lt_s_imm(instr),
-                Instr::BranchI32LtU(instr) => self.execute_branch_i32_lt_u(instr),
-                Instr::BranchI32LtUImm(instr) => self.execute_branch_i32_lt_u_imm(instr),
-                Instr::BranchI32LeS(instr) => self.execute_branch_i32_le_s(instr),
-                Instr::BranchI32LeSImm(instr) => self.execute_branch_i32_le_s_imm(instr),
-                Instr::BranchI32LeU(instr) => self.execute_branch_i32_le_u(instr),
-                Instr::BranchI32LeUImm(instr) => self.execute_branch_i32_le_u_imm(instr),
-                Instr::BranchI32GtS(instr) => self.execute_branch_i32_gt_s(instr),
-                Instr::BranchI32GtSImm(instr) => self.execute_branch_i32_gt_s_imm(instr),
-                Instr::BranchI32GtU(instr) => self.execute_branch_i32_gt_u(instr),
-                Instr::BranchI32GtUImm(instr) => self.execute_branch_i32_gt_u_imm(instr),
-                Instr::BranchI32GeS(instr) => self.execute_branch_i32_ge_s(instr),
-                Instr::BranchI32GeSImm(instr) => self.execute_branch_i32_ge_s_imm(instr),
-                Instr::BranchI32GeU(instr) => self.execute_branch_i32_ge_u(instr),
-                Instr::BranchI32GeUImm(instr) => self.execute_branch_i32_ge_u_imm(instr),
-                Instr::BranchI64Eq(instr) => self.execute_branch_i64_eq(instr),
-                Instr::BranchI64EqImm(instr) => self.execute_branch_i64_eq_imm(instr),
-                Instr::BranchI64Ne(instr) => self.execute_branch_i64_ne(instr),
-                Instr::BranchI64NeImm(instr) => self.execute_branch_i64_ne_imm(instr),
-                Instr::BranchI64LtS(instr) => self.execute_branch_i64_lt_s(instr),
-                Instr::BranchI64LtSImm(instr) => self.execute_branch_i64_lt_s_imm(instr),
-                Instr::BranchI64LtU(instr) => self.execute_branch_i64_lt_u(instr),
-                Instr::BranchI64LtUImm(instr) => self.execute_branch_i64_lt_u_imm(instr),
-                Instr::BranchI64LeS(instr) => self.execute_branch_i64_le_s(instr),
-                Instr::BranchI64LeSImm(instr) => self.execute_branch_i64_le_s_imm(instr),
-                Instr::BranchI64LeU(instr) => self.execute_branch_i64_le_u(instr),
-                Instr::BranchI64LeUImm(instr) => self.execute_branch_i64_le_u_imm(instr),
-                Instr::BranchI64GtS(instr) => self.execute_branch_i64_gt_s(instr),
-                Instr::BranchI64GtSImm(instr) => self.execute_branch_i64_gt_s_imm(instr),
-                Instr::BranchI64GtU(instr) => self.execute_branch_i64_gt_u(instr),
-                Instr::BranchI64GtUImm(instr) => self.execute_branch_i64_gt_u_imm(instr),
-                Instr::BranchI64GeS(instr) => self.execute_branch_i64_ge_s(instr),
-                Instr::BranchI64GeSImm(instr) => self.execute_branch_i64_ge_s_imm(instr),
-                Instr::BranchI64GeU(instr) => self.execute_branch_i64_ge_u(instr),
-                Instr::BranchI64GeUImm(instr) => self.execute_branch_i64_ge_u_imm(instr),
-                Instr::BranchF32Eq(instr) => self.execute_branch_f32_eq(instr),
-                Instr::BranchF32Ne(instr) => self.execute_branch_f32_ne(instr),
-                Instr::BranchF32Lt(instr) => self.execute_branch_f32_lt(instr),
-                Instr::BranchF32Le(instr) => self.execute_branch_f32_le(instr),
-                Instr::BranchF32Gt(instr) => self.execute_branch_f32_gt(instr),
-                Instr::BranchF32Ge(instr) => self.execute_branch_f32_ge(instr),
-                Instr::BranchF64Eq(instr) => self.execute_branch_f64_eq(instr),
-                Instr::BranchF64Ne(instr) => self.execute_branch_f64_ne(instr),
-                Instr::BranchF64Lt(instr) => self.execute_branch_f64_lt(instr),
-                Instr::BranchF64Le(instr) => self.execute_branch_f64_le(instr),
-                Instr::BranchF64Gt(instr) => self.execute_branch_f64_gt(instr),
-                Instr::BranchF64Ge(instr) => self.execute_branch_f64_ge(instr),
-                Instr::Copy { result, value } => self.execute_copy(result, value),
-                Instr::Copy2 { results, values } => self.execute_copy_2(results, values),
-                Instr::CopyImm32 { result, value } => self.execute_copy_imm32(result, value),
-                Instr::CopyI64Imm32 { result, value } => self.execute_copy_i64imm32(result, value),
-                Instr::CopyF64Imm32 { result, value } => self.execute_copy_f64imm32(result, value),
-                Instr::CopySpan {
-                    results,
-                    values,
-                    len,
-                } => self.execute_copy_span(results, values, len),
-                Instr::CopySpanNonOverlapping {
-                    results,
-                    values,
-                    len,
-                } => self.execute_copy_span_non_overlapping(results, values, len),
-                Instr::CopyMany { results, values } => self.execute_copy_many(results, values),
-                Instr::CopyManyNonOverlapping { results, values } => {
-                    self.execute_copy_many_non_overlapping(results, values)
-                }
-                Instr::ReturnCallInternal0 { func } => self.execute_return_call_internal_0(func)?,
-                Instr::ReturnCallInternal { func } => self.execute_return_call_internal(func)?,
-                Instr::ReturnCallImported0 { func } => {
-                    forward_call!(self.execute_return_call_imported_0(func))
-                }
-                Instr::ReturnCallImported { func } => {
-                    forward_call!(self.execute_return_call_imported(func))
-                }
-                Instr::ReturnCallIndirect0 { func_type } => {
-                    forward_call!(self.execute_return_call_indirect_0(func_type))
-                }
-                Instr::ReturnCallIndirect { func_type } => {
-                    forward_call!(self.execute_return_call_indirect(func_type))
-                }
-                Instr::CallInternal0 { results, func } => {
-                    self.execute_call_internal_0(results, func)?
-                }
-                Instr::CallInternal { results, func } => {
-                    self.execute_call_internal(results, func)?
-                }
-                Instr::CallImported0 { results, func } => {
-                    forward_call!(self.execute_call_imported_0(results, func))
-                }
-                Instr::CallImported { results, func } => {
-                    forward_call!(self.execute_call_imported(results, func))
-                }
-                Instr::CallIndirect0 { results, func_type } => {
-                    forward_call!(self.execute_call_indirect_0(results, func_type))
-                }
-                Instr::CallIndirect { results, func_type } => {
-                    forward_call!(self.execute_call_indirect(results, func_type))
-                }
-                Instr::Select {
-                    result,
-                    condition,
-                    lhs,
-                } => self.execute_select(result, condition, lhs),
-                Instr::SelectRev {
-                    result,
-                    condition,
-                    rhs,
-                } => self.execute_select_rev(result, condition, rhs),
-                Instr::SelectImm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_imm32(result_or_condition, lhs_or_rhs),
-                Instr::SelectI64Imm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_i64imm32(result_or_condition, lhs_or_rhs),
-                Instr::SelectF64Imm32 {
-                    result_or_condition,
-                    lhs_or_rhs,
-                } => self.execute_select_f64imm32(result_or_condition, lhs_or_rhs),
-                Instr::RefFunc { result, func } => self.execute_ref_func(result, func),
-                Instr::TableGet { result, index } => self.execute_table_get(result, index)?,
-                Instr::TableGetImm { result, index } => {
-                    self.execute_table_get_imm(result, index)?
-                }
-                Instr::TableSize { result, table } => self.execute_table_size(result, table),
-                Instr::TableSet { index, value } => self.execute_table_set(index, value)?,
-                Instr::TableSetAt { index, value } => self.execute_table_set_at(index, value)?,
-                Instr::TableCopy { dst, src, len } => self.execute_table_copy(dst, src, len)?,
-                Instr::TableCopyTo { dst, src, len } => {
-                    self.execute_table_copy_to(dst, src, len)?
-                }
-                Instr::TableCopyFrom { dst, src, len } => {
-                    self.execute_table_copy_from(dst, src, len)?
-                }
-                Instr::TableCopyFromTo { dst, src, len } => {
-                    self.execute_table_copy_from_to(dst, src, len)?
-                }
-                Instr::TableCopyExact { dst, src, len } => {
-                    self.execute_table_copy_exact(dst, src, len)?
-                }
-                Instr::TableCopyToExact { dst, src, len } => {
-                    self.execute_table_copy_to_exact(dst, src, len)?
-                }
-                Instr::TableCopyFromExact { dst, src, len } => {
-                    self.execute_table_copy_from_exact(dst, src, len)?
-                }
-                Instr::TableCopyFromToExact { dst, src, len } => {
-                    self.execute_table_copy_from_to_exact(dst, src, len)?
-                }
-                Instr::TableInit { dst, src, len } => self.execute_table_init(dst, src, len)?,
-                Instr::TableInitTo { dst, src, len } => {
-                    self.execute_table_init_to(dst, src, len)?
-                }
-                Instr::TableInitFrom { dst, src, len } => {
-                    self.execute_table_init_from(dst, src, len)?
-                }
-                Instr::TableInitFromTo { dst, src, len } => {
-                    self.execute_table_init_from_to(dst, src, len)?
-                }
-                Instr::TableInitExact { dst, src, len } => {
-                    self.execute_table_init_exact(dst, src, len)?
-                }
-                Instr::TableInitToExact { dst, src, len } => {
-                    self.execute_table_init_to_exact(dst, src, len)?
-                }
-                Instr::TableInitFromExact { dst, src, len } => {
-                    self.execute_table_init_from_exact(dst, src, len)?
-                }
-                Instr::TableInitFromToExact { dst, src, len } => {
-                    self.execute_table_init_from_to_exact(dst, src, len)?
-                }
-                Instr::TableFill { dst, len, value } => self.execute_table_fill(dst, len, value)?,
-                Instr::TableFillAt { dst, len, value } => {
-                    self.execute_table_fill_at(dst, len, value)?
-                }
-                Instr::TableFillExact { dst, len, value } => {
-                    self.execute_table_fill_exact(dst, len, value)?
-                }
-                Instr::TableFillAtExact { dst, len, value } => {
-                    self.execute_table_fill_at_exact(dst, len, value)?
-                }
-                Instr::TableGrow {
-                    result,
-                    delta,
-                    value,
-                } => self.execute_table_grow(result, delta, value, &mut *resource_limiter)?,
-                Instr::TableGrowImm {
-                    result,
-                    delta,
-                    value,
-                } => self.execute_table_grow_imm(result, delta, value, &mut *resource_limiter)?,
-                Instr::ElemDrop(element_index) => self.execute_element_drop(element_index),
-                Instr::DataDrop(data_index) => self.execute_data_drop(data_index),
-                Instr::MemorySize { result } => self.execute_memory_size(result),
-                Instr::MemoryGrow { result, delta } => {
-                    self.execute_memory_grow(result, delta, &mut *resource_limiter)?
-                }
-                Instr::MemoryGrowBy { result, delta } => {
-                    self.execute_memory_grow_by(result, delta, &mut *resource_limiter)?
-                }
-                Instr::MemoryCopy { dst, src, len } => self.execute_memory_copy(dst, src, len)?,
-                Instr::MemoryCopyTo { dst, src, len } => {
-                    self.execute_memory_copy_to(dst, src, len)?
-                }
-                Instr::MemoryCopyFrom { dst, src, len } => {
-                    self.execute_memory_copy_from(dst, src, len)?
-                }
-                Instr::MemoryCopyFromTo { dst, src, len } => {
-                    self.execute_memory_copy_from_to(dst, src, len)?
-                }
-                Instr::MemoryCopyExact { dst, src, len } => {
-                    self.execute_memory_copy_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyToExact { dst, src, len } => {
-                    self.execute_memory_copy_to_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyFromExact { dst, src, len } => {
-                    self.execute_memory_copy_from_exact(dst, src, len)?
-                }
-                Instr::MemoryCopyFromToExact { dst, src, len } => {
-                    self.execute_memory_copy_from_to_exact(dst, src, len)?
-                }
-                Instr::MemoryFill { dst, value, len } => {
-                    self.execute_memory_fill(dst, value, len)?
-                }
-                Instr::MemoryFillAt { dst, value, len } => {
-                    self.execute_memory_fill_at(dst, value, len)?
-                }
-                Instr::MemoryFillImm { dst, value, len } => {
-                    self.execute_memory_fill_imm(dst, value, len)?
-                }
-                Instr::MemoryFillExact { dst, value, len } => {
-                    self.execute_memory_fill_exact(dst, value, len)?
-                }
-                Instr::MemoryFillAtImm { dst, value, len } => {
-                    self.execute_memory_fill_at_imm(dst, value, len)?
-                }
-                Instr::MemoryFillAtExact { dst, value, len } => {
-                    self.execute_memory_fill_at_exact(dst, value, len)?
-                }
-                Instr::MemoryFillImmExact { dst, value, len } => {
-                    self.execute_memory_fill_imm_exact(dst, value, len)?
-                }
-                Instr::MemoryFillAtImmExact { dst, value, len } => {
-                    self.execute_memory_fill_at_imm_exact(dst, value, len)?
-                }
-                Instr::MemoryInit { dst, src, len } => self.execute_memory_init(dst, src, len)?,
-                Instr::MemoryInitTo { dst, src, len } => {
-                    self.execute_memory_init_to(dst, src, len)?
-                }
-                Instr::MemoryInitFrom { dst, src, len } => {
-                    self.execute_memory_init_from(dst, src, len)?
-                }
-                Instr::MemoryInitFromTo { dst, src, len } => {
-                    self.execute_memory_init_from_to(dst, src, len)?
-                }
-                Instr::MemoryInitExact { dst, src, len } => {
-                    self.execute_memory_init_exact(dst, src, len)?
-                }
-                Instr::MemoryInitToExact { dst, src, len } => {
-                    self.execute_memory_init_to_exact(dst, src, len)?
-                }
-                Instr::MemoryInitFromExact { dst, src, len } => {
-                    self.execute_memory_init_from_exact(dst, src, len)?
-                }
-                Instr::MemoryInitFromToExact { dst, src, len } => {
-                    self.execute_memory_init_from_to_exact(dst, src, len)?
-                }
-                Instr::GlobalGet { result, global } => self.execute_global_get(result, global),
-                Instr::GlobalSet { global, input } => self.execute_global_set(global, input),
-                Instr::GlobalSetI32Imm16 { global, input } => {
-                    self.execute_global_set_i32imm16(global, input)
-                }
-                Instr::GlobalSetI64Imm16 { global, input } => {
-                    self.execute_global_set_i64imm16(global, input)
-                }
-                Instr::I32Load(instr) => self.execute_i32_load(instr)?,
-                Instr::I32LoadAt(instr) => self.execute_i32_load_at(instr)?,
-                Instr::I32LoadOffset16(instr) => self.execute_i32_load_offset16(instr)?,
-                Instr::I64Load(instr) => self.execute_i64_load(instr)?,
-                Instr::I64LoadAt(instr) => self.execute_i64_load_at(instr)?,
-                Instr::I64LoadOffset16(instr) => self.execute_i64_load_offset16(instr)?,
-                Instr::F32Load(instr) => self.execute_f32_load(instr)?,
-                Instr::F32LoadAt(instr) => self.execute_f32_load_at(instr)?,
-                Instr::F32LoadOffset16(instr) => self.execute_f32_load_offset16(instr)?,
-                Instr::F64Load(instr) => self.execute_f64_load(instr)?,
-                Instr::F64LoadAt(instr) => self.execute_f64_load_at(instr)?,
-                Instr::F64LoadOffset16(instr) => self.execute_f64_load_offset16(instr)?,
-                Instr::I32Load8s(instr) => self.execute_i32_load8_s(instr)?,
-                Instr::I32Load8sAt(instr) => self.execute_i32_load8_s_at(instr)?,
-                Instr::I32Load8sOffset16(instr) => self.execute_i32_load8_s_offset16(instr)?,
-                Instr::I32Load8u(instr) => self.execute_i32_load8_u(instr)?,
-                Instr::I32Load8uAt(instr) => self.execute_i32_load8_u_at(instr)?,
-                Instr::I32Load8uOffset16(instr) => self.execute_i32_load8_u_offset16(instr)?,
-                Instr::I32Load16s(instr) => self.execute_i32_load16_s(instr)?,
-                Instr::I32Load16sAt(instr) => self.execute_i32_load16_s_at(instr)?,
-                Instr::I32Load16sOffset16(instr) => self.execute_i32_load16_s_offset16(instr)?,
-                Instr::I32Load16u(instr) => self.execute_i32_load16_u(instr)?,
-                Instr::I32Load16uAt(instr) => self.execute_i32_load16_u_at(instr)?,
-                Instr::I32Load16uOffset16(instr) => self.execute_i32_load16_u_offset16(instr)?,
-                Instr::I64Load8s(instr) => self.execute_i64_load8_s(instr)?,
-                Instr::I64Load8sAt(instr) => self.execute_i64_load8_s_at(instr)?,
-                Instr::I64Load8sOffset16(instr) => self.execute_i64_load8_s_offset16(instr)?,
-                Instr::I64Load8u(instr) => self.execute_i64_load8_u(instr)?,
-                Instr::I64Load8uAt(instr) => self.execute_i64_load8_u_at(instr)?,
-                Instr::I64Load8uOffset16(instr) => self.execute_i64_load8_u_offset16(instr)?,
-                Instr::I64Load16s(instr) => self.execute_i64_load16_s(instr)?,
-                Instr::I64Load16sAt(instr) => self.execute_i64_load16_s_at(instr)?,
-                Instr::I64Load16sOffset16(instr) => self.execute_i64_load16_s_offset16(instr)?,
-                Instr::I64Load16u(instr) => self.execute_i64_load16_u(instr)?,
-                Instr::I64Load16uAt(instr) => self.execute_i64_load16_u_at(instr)?,
-                Instr::I64Load16uOffset16(instr) => self.execute_i64_load16_u_offset16(instr)?,
-                Instr::I64Load32s(instr) => self.execute_i64_load32_s(instr)?,
-                Instr::I64Load32sAt(instr) => self.execute_i64_load32_s_at(instr)?,
-                Instr::I64Load32sOffset16(instr) => self.execute_i64_load32_s_offset16(instr)?,
-                Instr::I64Load32u(instr) => self.execute_i64_load32_u(instr)?,
-                Instr::I64Load32uAt(instr) => self.execute_i64_load32_u_at(instr)?,
-                Instr::I64Load32uOffset16(instr) => self.execute_i64_load32_u_offset16(instr)?,
-                Instr::I32Store(instr) => self.execute_i32_store(instr)?,
-                Instr::I32StoreOffset16(instr) => self.execute_i32_store_offset16(instr)?,
-                Instr::I32StoreOffset16Imm16(instr) => {
-                    self.execute_i32_store_offset16_imm16(instr)?
-                }
-                Instr::I32StoreAt(instr) => self.execute_i32_store_at(instr)?,
-                Instr::I32StoreAtImm16(instr) => self.execute_i32_store_at_imm16(instr)?,
-                Instr::I32Store8(instr) => self.execute_i32_store8(instr)?,
-                Instr::I32Store8Offset16(instr) => self.execute_i32_store8_offset16(instr)?,
-                Instr::I32Store8Offset16Imm(instr) => {
-                    self.execute_i32_store8_offset16_imm(instr)?
-                }
-                Instr::I32Store8At(instr) => self.execute_i32_store8_at(instr)?,
-                Instr::I32Store8AtImm(instr) => self.execute_i32_store8_at_imm(instr)?,
-                Instr::I32Store16(instr) => self.execute_i32_store16(instr)?,
-                Instr::I32Store16Offset16(instr) => self.execute_i32_store16_offset16(instr)?,
-                Instr::I32Store16Offset16Imm(instr) => {
-                    self.execute_i32_store16_offset16_imm(instr)?
-                }
-                Instr::I32Store16At(instr) => self.execute_i32_store16_at(instr)?,
-                Instr::I32Store16AtImm(instr) => self.execute_i32_store16_at_imm(instr)?,
-                Instr::I64Store(instr) => self.execute_i64_store(instr)?,
-                Instr::I64StoreOffset16(instr) => self.execute_i64_store_offset16(instr)?,
-                Instr::I64StoreOffset16Imm16(instr) => {
-                    self.execute_i64_store_offset16_imm16(instr)?
-                }
-                Instr::I64StoreAt(instr) => self.execute_i64_store_at(instr)?,
-                Instr::I64StoreAtImm16(instr) => self.execute_i64_store_at_imm16(instr)?,
-                Instr::I64Store8(instr) => self.execute_i64_store8(instr)?,
-                Instr::I64Store8Offset16(instr) => self.execute_i64_store8_offset16(instr)?,
-                Instr::I64Store8Offset16Imm(instr) => {
-                    self.execute_i64_store8_offset16_imm(instr)?
-                }
-                Instr::I64Store8At(instr) => self.execute_i64_store8_at(instr)?,
-                Instr::I64Store8AtImm(instr) => self.execute_i64_store8_at_imm(instr)?,
-                Instr::I64Store16(instr) => self.execute_i64_store16(instr)?,
-                Instr::I64Store16Offset16(instr) => self.execute_i64_store16_offset16(instr)?,
-                Instr::I64Store16Offset16Imm(instr) => {
-                    self.execute_i64_store16_offset16_imm(instr)?
-                }
-                Instr::I64Store16At(instr) => self.execute_i64_store16_at(instr)?,
-                Instr::I64Store16AtImm(instr) => self.execute_i64_store16_at_imm(instr)?,
-                Instr::I64Store32(instr) => self.execute_i64_store32(instr)?,
-                Instr::I64Store32Offset16(instr) => self.execute_i64_store32_offset16(instr)?,
-                Instr::I64Store32Offset16Imm16(instr) => {
-                    self.execute_i64_store32_offset16_imm16(instr)?
-                }
-                Instr::I64Store32At(instr) => self.execute_i64_store32_at(instr)?,
-                Instr::I64Store32AtImm16(instr) => self.execute_i64_store32_at_imm16(instr)?,
-                Instr::F32Store(instr) => self.execute_f32_store(instr)?,
-                Instr::F32StoreOffset16(instr) => self.execute_f32_store_offset16(instr)?,
-                Instr::F32StoreAt(instr) => self.execute_f32_store_at(instr)?,
-                Instr::F64Store(instr) => self.execute_f64_store(instr)?,
-                Instr::F64StoreOffset16(instr) => self.execute_f64_store_offset16(instr)?,
-                Instr::F64StoreAt(instr) => self.execute_f64_store_at(instr)?,
-                Instr::I32Eq(instr) => self.execute_i32_eq(instr),
-                Instr::I32EqImm16(instr) => self.execute_i32_eq_imm16(instr),
-                Instr::I32Ne(instr) => self.execute_i32_ne(instr),
-                Instr::I32NeImm16(instr) => self.execute_i32_ne_imm16(instr),
-                Instr::I32LtS(instr) => self.execute_i32_lt_s(instr),
-                Instr::I32LtSImm16(instr) => self.execute_i32_lt_s_imm16(instr),
-                Instr::I32LtU(instr) => self.execute_i32_lt_u(instr),
-                Instr::I32LtUImm16(instr) => self.execute_i32_lt_u_imm16(instr),
-                Instr::I32LeS(instr) => self.execute_i32_le_s(instr),
-                Instr::I32LeSImm16(instr) => self.execute_i32_le_s_imm16(instr),
-                Instr::I32LeU(instr) => self.execute_i32_le_u(instr),
-                Instr::I32LeUImm16(instr) => self.execute_i32_le_u_imm16(instr),
-                Instr::I32GtS(instr) => self.execute_i32_gt_s(instr),
-                Instr::I32GtSImm16(instr) => self.execute_i32_gt_s_imm16(instr),
-                Instr::I32GtU(instr) => self.execute_i32_gt_u(instr),
-                Instr::I32GtUImm16(instr) => self.execute_i32_gt_u_imm16(instr),
-                Instr::I32GeS(instr) => self.execute_i32_ge_s(instr),
-                Instr::I32GeSImm16(instr) => self.execute_i32_ge_s_imm16(instr),
-                Instr::I32GeU(instr) => self.execute_i32_ge_u(instr),
-                Instr::I32GeUImm16(instr) => self.execute_i32_ge_u_imm16(instr),
-                Instr::I64Eq(instr) => self.execute_i64_eq(instr),
-                Instr::I64EqImm16(instr) => self.execute_i64_eq_imm16(instr),
-                Instr::I64Ne(instr) => self.execute_i64_ne(instr),
-                Instr::I64NeImm16(instr) => self.execute_i64_ne_imm16(instr),
-                Instr::I64LtS(instr) => self.execute_i64_lt_s(instr),
-                Instr::I64LtSImm16(instr) => self.execute_i64_lt_s_imm16(instr),
-                Instr::I64LtU(instr) => self.execute_i64_lt_u(instr),
-                Instr::I64LtUImm16(instr) => self.execute_i64_lt_u_imm16(instr),
-                Instr::I64LeS(instr) => self.execute_i64_le_s(instr),
-                Instr::I64LeSImm16(instr) => self.execute_i64_le_s_imm16(instr),
-                Instr::I64LeU(instr) => self.execute_i64_le_u(instr),
-                Instr::I64LeUImm16(instr) => self.execute_i64_le_u_imm16(instr),
-                Instr::I64GtS(instr) => self.execute_i64_gt_s(instr),
-                Instr::I64GtSImm16(instr) => self.execute_i64_gt_s_imm16(instr),
-                Instr::I64GtU(instr) => self.execute_i64_gt_u(instr),
-                Instr::I64GtUImm16(instr) => self.execute_i64_gt_u_imm16(instr),
-                Instr::I64GeS(instr) => self.execute_i64_ge_s(instr),
-                Instr::I64GeSImm16(instr) => self.execute_i64_ge_s_imm16(instr),
-                Instr::I64GeU(instr) => self.execute_i64_ge_u(instr),
-                Instr::I64GeUImm16(instr) => self.execute_i64_ge_u_imm16(instr),
-                Instr::F32Eq(instr) => self.execute_f32_eq(instr),
-                Instr::F32Ne(instr) => self.execute_f32_ne(instr),
-                Instr::F32Lt(instr) => self.execute_f32_lt(instr),
-                Instr::F32Le(instr) => self.execute_f32_le(instr),
-                Instr::F32Gt(instr) => self.execute_f32_gt(instr),
-                Instr::F32Ge(instr) => self.execute_f32_ge(instr),
-                Instr::F64Eq(instr) => self.execute_f64_eq(instr),
-                Instr::F64Ne(instr) => self.execute_f64_ne(instr),
-                Instr::F64Lt(instr) => self.execute_f64_lt(instr),
-                Instr::F64Le(instr) => self.execute_f64_le(instr),
-                Instr::F64Gt(instr) => self.execute_f64_gt(instr),
-                Instr::F64Ge(instr) => self.execute_f64_ge(instr),
-                Instr::I32Clz(instr) => self.execute_i32_clz(instr),
-                Instr::I64Clz(instr) => self.execute_i64_clz(instr),
-                Instr::I32Ctz(instr) => self.execute_i32_ctz(instr),
-                Instr::I64Ctz(instr) => self.execute_i64_ctz(instr),
-                Instr::I32Popcnt(instr) => self.execute_i32_popcnt(instr),
-                Instr::I64Popcnt(instr) => self.execute_i64_popcnt(instr),
-                Instr::I32Add(instr) => self.execute_i32_add(instr),
-                Instr::I32AddImm16(instr) => self.execute_i32_add_imm16(instr),
-                Instr::I32Sub(instr) => self.execute_i32_sub(instr),
-                Instr::I32SubImm16Rev(instr) => self.execute_i32_sub_imm16_rev(instr),
-                Instr::I32Mul(instr) => self.execute_i32_mul(instr),
-                Instr::I32MulImm16(instr) => self.execute_i32_mul_imm16(instr),
-                Instr::I32DivS(instr) => self.execute_i32_div_s(instr)?,
-                Instr::I32DivSImm16(instr) => self.execute_i32_div_s_imm16(instr)?,
-                Instr::I32DivSImm16Rev(instr) => self.execute_i32_div_s_imm16_rev(instr)?,
-                Instr::I32DivU(instr) => self.execute_i32_div_u(instr)?,
-                Instr::I32DivUImm16(instr) => self.execute_i32_div_u_imm16(instr),
-                Instr::I32DivUImm16Rev(instr) => self.execute_i32_div_u_imm16_rev(instr)?,
-                Instr::I32RemS(instr) => self.execute_i32_rem_s(instr)?,
-                Instr::I32RemSImm16(instr) => self.execute_i32_rem_s_imm16(instr)?,
-                Instr::I32RemSImm16Rev(instr) => self.execute_i32_rem_s_imm16_rev(instr)?,
-                Instr::I32RemU(instr) => self.execute_i32_rem_u(instr)?,
-                Instr::I32RemUImm16(instr) => self.execute_i32_rem_u_imm16(instr),
-                Instr::I32RemUImm16Rev(instr) => self.execute_i32_rem_u_imm16_rev(instr)?,
-                Instr::I32And(instr) => self.execute_i32_and(instr),
-                Instr::I32AndEqz(instr) => self.execute_i32_and_eqz(instr),
-                Instr::I32AndEqzImm16(instr) => self.execute_i32_and_eqz_imm16(instr),
-                Instr::I32AndImm16(instr) => self.execute_i32_and_imm16(instr),
-                Instr::I32Or(instr) => self.execute_i32_or(instr),
-                Instr::I32OrEqz(instr) => self.execute_i32_or_eqz(instr),
-                Instr::I32OrEqzImm16(instr) => self.execute_i32_or_eqz_imm16(instr),
-                Instr::I32OrImm16(instr) => self.execute_i32_or_imm16(instr),
-                Instr::I32Xor(instr) => self.execute_i32_xor(instr),
-                Instr::I32XorEqz(instr) => self.execute_i32_xor_eqz(instr),
-                Instr::I32XorEqzImm16(instr) => self.execute_i32_xor_eqz_imm16(instr),
-                Instr::I32XorImm16(instr) => self.execute_i32_xor_imm16(instr),
-                Instr::I32Shl(instr) => self.execute_i32_shl(instr),
-                Instr::I32ShlImm(instr) => self.execute_i32_shl_imm(instr),
-                Instr::I32ShlImm16Rev(instr) => self.execute_i32_shl_imm16_rev(instr),
-                Instr::I32ShrU(instr) => self.execute_i32_shr_u(instr),
-                Instr::I32ShrUImm(instr) => self.execute_i32_shr_u_imm(instr),
-                Instr::I32ShrUImm16Rev(instr) => self.execute_i32_shr_u_imm16_rev(instr),
-                Instr::I32ShrS(instr) => self.execute_i32_shr_s(instr),
-                Instr::I32ShrSImm(instr) => self.execute_i32_shr_s_imm(instr),
-                Instr::I32ShrSImm16Rev(instr) => self.execute_i32_shr_s_imm16_rev(instr),
-                Instr::I32Rotl(instr) => self.execute_i32_rotl(instr),
-                Instr::I32RotlImm(instr) => self.execute_i32_rotl_imm(instr),
-                Instr::I32RotlImm16Rev(instr) => self.execute_i32_rotl_imm16_rev(instr),
-                Instr::I32Rotr(instr) => self.execute_i32_rotr(instr),
-                Instr::I32RotrImm(instr) => self.execute_i32_rotr_imm(instr),
-                Instr::I32RotrImm16Rev(instr) => self.execute_i32_rotr_imm16_rev(instr),
-                Instr::I64Add(instr) => self.execute_i64_add(instr),
-                Instr::I64AddImm16(instr) => self.execute_i64_add_imm16(instr),
-                Instr::I64Sub(instr) => self.execute_i64_sub(instr),
-                Instr::I64SubImm16Rev(instr) => self.execute_i64_sub_imm16_rev(instr),
-                Instr::I64Mul(instr) => self.execute_i64_mul(instr),
-                Instr::I64MulImm16(instr) => self.execute_i64_mul_imm16(instr),
-                Instr::I64DivS(instr) => self.execute_i64_div_s(instr)?,
-                Instr::I64DivSImm16(instr) => self.execute_i64_div_s_imm16(instr)?,
-                Instr::I64DivSImm16Rev(instr) => self.execute_i64_div_s_imm16_rev(instr)?,
-                Instr::I64DivU(instr) => self.execute_i64_div_u(instr)?,
-                Instr::I64DivUImm16(instr) => self.execute_i64_div_u_imm16(instr),
-                Instr::I64DivUImm16Rev(instr) => self.execute_i64_div_u_imm16_rev(instr)?,
-                Instr::I64RemS(instr) => self.execute_i64_rem_s(instr)?,
-                Instr::I64RemSImm16(instr) => self.execute_i64_rem_s_imm16(instr)?,
-                Instr::I64RemSImm16Rev(instr) => self.execute_i64_rem_s_imm16_rev(instr)?,
-                Instr::I64RemU(instr) => self.execute_i64_rem_u(instr)?,
-                Instr::I64RemUImm16(instr) => self.execute_i64_rem_u_imm16(instr),
-                Instr::I64RemUImm16Rev(instr) => self.execute_i64_rem_u_imm16_rev(instr)?,
-                Instr::I64And(instr) => self.execute_i64_and(instr),
-                Instr::I64AndImm16(instr) => self.execute_i64_and_imm16(instr),
-                Instr::I64Or(instr) => self.execute_i64_or(instr),
-                Instr::I64OrImm16(instr) => self.execute_i64_or_imm16(instr),
-                Instr::I64Xor(instr) => self.execute_i64_xor(instr),
-                Instr::I64XorImm16(instr) => self.execute_i64_xor_imm16(instr),
-                Instr::I64Shl(instr) => self.execute_i64_shl(instr),
-                Instr::I64ShlImm(instr) => self.execute_i64_shl_imm(instr),
-                Instr::I64ShlImm16Rev(instr) => self.execute_i64_shl_imm16_rev(instr),
-                Instr::I64ShrU(instr) => self.execute_i64_shr_u(instr),
-                Instr::I64ShrUImm(instr) => self.execute_i64_shr_u_imm(instr),
-                Instr::I64ShrUImm16Rev(instr) => self.execute_i64_shr_u_imm16_rev(instr),
-                Instr::I64ShrS(instr) => self.execute_i64_shr_s(instr),
-                Instr::I64ShrSImm(instr) => self.execute_i64_shr_s_imm(instr),
-                Instr::I64ShrSImm16Rev(instr) => self.execute_i64_shr_s_imm16_rev(instr),
-                Instr::I64Rotl(instr) => self.execute_i64_rotl(instr),
-                Instr::I64RotlImm(instr) => self.execute_i64_rotl_imm(instr),
-                Instr::I64RotlImm16Rev(instr) => self.execute_i64_rotl_imm16_rev(instr),
-                Instr::I64Rotr(instr) => self.execute_i64_rotr(instr),
-                Instr::I64RotrImm(instr) => self.execute_i64_rotr_imm(instr),
-                Instr::I64RotrImm16Rev(instr) => self.execute_i64_rotr_imm16_rev(instr),
-                Instr::F32Abs(instr) => self.execute_f32_abs(instr),
-                Instr::F32Neg(instr) => self.execute_f32_neg(instr),
-                Instr::F32Ceil(instr) => self.execute_f32_ceil(instr),
-                Instr::F32Floor(instr) => self.execute_f32_floor(instr),
-                Instr::F32Trunc(instr) => self.execute_f32_trunc(instr),
-                Instr::F32Nearest(instr) => self.execute_f32_nearest(instr),
-                Instr::F32Sqrt(instr) => self.execute_f32_sqrt(instr),
-                Instr::F64Abs(instr) => self.execute_f64_abs(instr),
-                Instr::F64Neg(instr) => self.execute_f64_neg(instr),
-                Instr::F64Ceil(instr) => self.execute_f64_ceil(instr),
-                Instr::F64Floor(instr) => self.execute_f64_floor(instr),
-                Instr::F64Trunc(instr) => self.execute_f64_trunc(instr),
-                Instr::F64Nearest(instr) => self.execute_f64_nearest(instr),
-                Instr::F64Sqrt(instr) => self.execute_f64_sqrt(instr),
-                Instr::F32Add(instr) => self.execute_f32_add(instr),
-                Instr::F32Sub(instr) => self.execute_f32_sub(instr),
-                Instr::F32Mul(instr) => self.execute_f32_mul(instr),
-                Instr::F32Div(instr) => self.execute_f32_div(instr),
-                Instr::F32Min(instr) => self.execute_f32_min(instr),
-                Instr::F32Max(instr) => self.execute_f32_max(instr),
-                Instr::F32Copysign(instr) => self.execute_f32_copysign(instr),
-                Instr::F32CopysignImm(instr) => self.execute_f32_copysign_imm(instr),
-                Instr::F64Add(instr) => self.execute_f64_add(instr),
-                Instr::F64Sub(instr) => self.execute_f64_sub(instr),
-                Instr::F64Mul(instr) => self.execute_f64_mul(instr),
-                Instr::F64Div(instr) => self.execute_f64_div(instr),
-                Instr::F64Min(instr) => self.execute_f64_min(instr),
-                Instr::F64Max(instr) => self.execute_f64_max(instr),
-                Instr::F64Copysign(instr) => self.execute_f64_copysign(instr),
-                Instr::F64CopysignImm(instr) => self.execute_f64_copysign_imm(instr),
-                Instr::I32WrapI64(instr) => self.execute_i32_wrap_i64(instr),
-                Instr::I64ExtendI32S(instr) => self.execute_i64_extend_i32_s(instr),
-                Instr::I64ExtendI32U(instr) => self.execute_i64_extend_i32_u(instr),
-                Instr::I32TruncF32S(instr) => self.execute_i32_trunc_f32_s(instr)?,
-                Instr::I32TruncF32U(instr) => self.execute_i32_trunc_f32_u(instr)?,
-                Instr::I32TruncF64S(instr) => self.execute_i32_trunc_f64_s(instr)?,
-                Instr::I32TruncF64U(instr) => self.execute_i32_trunc_f64_u(instr)?,
-                Instr::I64TruncF32S(instr) => self.execute_i64_trunc_f32_s(instr)?,
-                Instr::I64TruncF32U(instr) => self.execute_i64_trunc_f32_u(instr)?,
-                Instr::I64TruncF64S(instr) => self.execute_i64_trunc_f64_s(instr)?,
-                Instr::I64TruncF64U(instr) => self.execute_i64_trunc_f64_u(instr)?,
-                Instr::I32TruncSatF32S(instr) => self.execute_i32_trunc_sat_f32_s(instr),
-                Instr::I32TruncSatF32U(instr) => self.execute_i32_trunc_sat_f32_u(instr),
-                Instr::I32TruncSatF64S(instr) => self.execute_i32_trunc_sat_f64_s(instr),
-                Instr::I32TruncSatF64U(instr) => self.execute_i32_trunc_sat_f64_u(instr),
-                Instr::I64TruncSatF32S(instr) => self.execute_i64_trunc_sat_f32_s(instr),
-                Instr::I64TruncSatF32U(instr) => self.execute_i64_trunc_sat_f32_u(instr),
-                Instr::I64TruncSatF64S(instr) => self.execute_i64_trunc_sat_f64_s(instr),
-                Instr::I64TruncSatF64U(instr) => self.execute_i64_trunc_sat_f64_u(instr),
-                Instr::I32Extend8S(instr) => self.execute_i32_extend8_s(instr),
-                Instr::I32Extend16S(instr) => self.execute_i32_extend16_s(instr),
-                Instr::I64Extend8S(instr) => self.execute_i64_extend8_s(instr),
-                Instr::I64Extend16S(instr) => self.execute_i64_extend16_s(instr),
-                Instr::I64Extend32S(instr) => self.execute_i64_extend32_s(instr),
-                Instr::F32DemoteF64(instr) => self.execute_f32_demote_f64(instr),
-                Instr::F64PromoteF32(instr) => self.execute_f64_promote_f32(instr),
-                Instr::F32ConvertI32S(instr) => self.execute_f32_convert_i32_s(instr),
-                Instr::F32ConvertI32U(instr) => self.execute_f32_convert_i32_u(instr),
-                Instr::F32ConvertI64S(instr) => self.execute_f32_convert_i64_s(instr),
-                Instr::F32ConvertI64U(instr) => self.execute_f32_convert_i64_u(instr),
-                Instr::F64ConvertI32S(instr) => self.execute_f64_convert_i32_s(instr),
-                Instr::F64ConvertI32U(instr) => self.execute_f64_convert_i32_u(instr),
-                Instr::F64ConvertI64S(instr) => self.execute_f64_convert_i64_s(instr),
-                Instr::F64ConvertI64U(instr) => self.execute_f64_convert_i64_u(instr),
-            }
-        }
-    }
+define_executor! {
+    self, resource_limiter;
+    TableIdx(_) => self.invalid_instruction_word()?,
+    DataSegmentIdx(_) => self.invalid_instruction_word()?,
+    ElementSegmentIdx(_) => self.invalid_instruction_word()?,
+    Const32(_) => self.invalid_instruction_word()?,
+    I64Const32(_) => self.invalid_instruction_word()?,
+    F64Const32(_) => self.invalid_instruction_word()?,
+    Register(_) => self.invalid_instruction_word()?,
+    Register2(_) => self.invalid_instruction_word()?,
+    Register3(_) => self.invalid_instruction_word()?,
+    RegisterList(_) => self.invalid_instruction_word()?,
+    CallIndirectParams(_) => self.invalid_instruction_word()?,
+    CallIndirectParamsImm16(_) => self.invalid_instruction_word()?,
+    Trap(trap_code) => self.execute_trap(trap_code)?,
+    ConsumeFuel(block_fuel) => self.execute_consume_fuel(block_fuel)?,
+    Return {} => {
+        forward_return!(self.execute_return())
+    },
+    ReturnReg { value } => {
+        forward_return!(self.execute_return_reg(value))
+    },
+    ReturnReg2 { values } => {
+        forward_return!(self.execute_return_reg2(values))
+    },
+    ReturnReg3 { values } => {
+        forward_return!(self.execute_return_reg3(values))
+    },
+    ReturnImm32 { value } => {
+        forward_return!(self.execute_return_imm32(value))
+    },
+    ReturnI64Imm32 { value } => {
+        forward_return!(self.execute_return_i64imm32(value))
+    },
+    ReturnF64Imm32 { value } => {
+        forward_return!(self.execute_return_f64imm32(value))
+    },
+    ReturnSpan { values } => {
+        forward_return!(self.execute_return_span(values))
+    },
+    ReturnMany { values } => {
+        forward_return!(self.execute_return_many(values))
+    },
+    ReturnNez { condition } => {
+        forward_return!(self.execute_return_nez(condition))
+    },
+    ReturnNezReg { condition, value } => {
+        forward_return!(self.execute_return_nez_reg(condition, value))
+    },
+    ReturnNezReg2 { condition, values } => {
+        forward_return!(self.execute_return_nez_reg2(condition, values))
+    },
+    ReturnNezImm32 { condition, value } => {
+        forward_return!(self.execute_return_nez_imm32(condition, value))
+    },
+    ReturnNezI64Imm32 { condition, value } => {
+        forward_return!(self.execute_return_nez_i64imm32(condition, value))
+    },
+    ReturnNezF64Imm32 { condition, value } => {
+        forward_return!(self.execute_return_nez_f64imm32(condition, value))
+    },
+    ReturnNezSpan { condition, values } => {
+        forward_return!(self.execute_return_nez_span(condition, values))
+    },
+    ReturnNezMany { condition, values } => {
+        forward_return!(self.execute_return_nez_many(condition, values))
+    },
+    Branch { offset } => self.execute_branch(offset),
+    BranchCmpFallback { lhs, rhs, params } => {
+        self.execute_branch_cmp_fallback(lhs, rhs, params)
+    },
+    BranchI32And(instr) => self.execute_branch_i32_and(instr),
+    BranchI32AndImm(instr) => self.execute_branch_i32_and_imm(instr),
+    BranchI32Or(instr) => self.execute_branch_i32_or(instr),
+    BranchI32OrImm(instr) => self.execute_branch_i32_or_imm(instr),
+    BranchI32Xor(instr) => self.execute_branch_i32_xor(instr),
+    BranchI32XorImm(instr) => self.execute_branch_i32_xor_imm(instr),
+    BranchI32AndEqz(instr) => self.execute_branch_i32_and_eqz(instr),
+    BranchI32AndEqzImm(instr) => self.execute_branch_i32_and_eqz_imm(instr),
+    BranchI32OrEqz(instr) => self.execute_branch_i32_or_eqz(instr),
+    BranchI32OrEqzImm(instr) => self.execute_branch_i32_or_eqz_imm(instr),
+    BranchI32XorEqz(instr) => self.execute_branch_i32_xor_eqz(instr),
+    BranchI32XorEqzImm(instr) => self.execute_branch_i32_xor_eqz_imm(instr),
+    BranchI32Eq(instr) => self.execute_branch_i32_eq(instr),
+    BranchI32EqImm(instr) => self.execute_branch_i32_eq_imm(instr),
+    BranchI32Ne(instr) => self.execute_branch_i32_ne(instr),
+    BranchI32NeImm(instr) => self.execute_branch_i32_ne_imm(instr),
+    BranchI32LtS(instr) => self.execute_branch_i32_lt_s(instr),
+    BranchI32LtSImm(instr) => self.execute_branch_i32_lt_s_imm(instr),
+    BranchI32LtU(instr) => self.execute_branch_i32_lt_u(instr),
+    BranchI32LtUImm(instr) => self.execute_branch_i32_lt_u_imm(instr),
+    BranchI32LeS(instr) => self.execute_branch_i32_le_s(instr),
+    BranchI32LeSImm(instr) => self.execute_branch_i32_le_s_imm(instr),
+    BranchI32LeU(instr) => self.execute_branch_i32_le_u(instr),
+    BranchI32LeUImm(instr) => self.execute_branch_i32_le_u_imm(instr),
+    BranchI32GtS(instr) => self.execute_branch_i32_gt_s(instr),
+    BranchI32GtSImm(instr) => self.execute_branch_i32_gt_s_imm(instr),
+    BranchI32GtU(instr) => self.execute_branch_i32_gt_u(instr),
+    BranchI32GtUImm(instr) => self.execute_branch_i32_gt_u_imm(instr),
+    BranchI32GeS(instr) => self.execute_branch_i32_ge_s(instr),
+    BranchI32GeSImm(instr) => self.execute_branch_i32_ge_s_imm(instr),
+    BranchI32GeU(instr) => self.execute_branch_i32_ge_u(instr),
+    BranchI32GeUImm(instr) => self.execute_branch_i32_ge_u_imm(instr),
+    BranchI64Eq(instr) => self.execute_branch_i64_eq(instr),
+    BranchI64EqImm(instr) => self.execute_branch_i64_eq_imm(instr),
+    BranchI64Ne(instr) => self.execute_branch_i64_ne(instr),
+    BranchI64NeImm(instr) => self.execute_branch_i64_ne_imm(instr),
+    BranchI64LtS(instr) => self.execute_branch_i64_lt_s(instr),
+    BranchI64LtSImm(instr) => self.execute_branch_i64_lt_s_imm(instr),
+    BranchI64LtU(instr) => self.execute_branch_i64_lt_u(instr),
+    BranchI64LtUImm(instr) => self.execute_branch_i64_lt_u_imm(instr),
+    BranchI64LeS(instr) => self.execute_branch_i64_le_s(instr),
+    BranchI64LeSImm(instr) => self.execute_branch_i64_le_s_imm(instr),
+    BranchI64LeU(instr) => self.execute_branch_i64_le_u(instr),
+    BranchI64LeUImm(instr) => self.execute_branch_i64_le_u_imm(instr),
+    BranchI64GtS(instr) => self.execute_branch_i64_gt_s(instr),
+    BranchI64GtSImm(instr) => self.execute_branch_i64_gt_s_imm(instr),
+    BranchI64GtU(instr) => self.execute_branch_i64_gt_u(instr),
+    BranchI64GtUImm(instr) => self.execute_branch_i64_gt_u_imm(instr),
+    BranchI64GeS(instr) => self.execute_branch_i64_ge_s(instr),
+    BranchI64GeSImm(instr) => self.execute_branch_i64_ge_s_imm(instr),
+    BranchI64GeU(instr) => self.execute_branch_i64_ge_u(instr),
+    BranchI64GeUImm(instr) => self.execute_branch_i64_ge_u_imm(instr),
+    BranchF32Eq(instr) => self.execute_branch_f32_eq(instr),
+    BranchF32Ne(instr) => self.execute_branch_f32_ne(instr),
+    BranchF32Lt(instr) => self.execute_branch_f32_lt(instr),
+    BranchF32Le(instr) => self.execute_branch_f32_le(instr),
+    BranchF32Gt(instr) => self.execute_branch_f32_gt(instr),
+    BranchF32Ge(instr) => self.execute_branch_f32_ge(instr),
+    BranchF64Eq(instr) => self.execute_branch_f64_eq(instr),
+    BranchF64Ne(instr) => self.execute_branch_f64_ne(instr),
+    BranchF64Lt(instr) => self.execute_branch_f64_lt(instr),
+    BranchF64Le(instr) => self.execute_branch_f64_le(instr),
+    BranchF64Gt(instr) => self.execute_branch_f64_gt(instr),
+    BranchF64Ge(instr) => self.execute_branch_f64_ge(instr),
+    BranchTable { index, len_targets } => {
+        self.execute_branch_table(index, len_targets)
+    },
+    Copy { result, value } => self.execute_copy(result, value),
+    Copy2 { results, values } => self.execute_copy_2(results, values),
+    CopyImm32 { result, value } => self.execute_copy_imm32(result, value),
+    CopyI64Imm32 { result, value } => self.execute_copy_i64imm32(result, value),
+    CopyF64Imm32 { result, value } => self.execute_copy_f64imm32(result, value),
+    CopySpan {
+        results,
+        values,
+        len,
+    } => self.execute_copy_span(results, values, len),
+    CopySpanNonOverlapping {
+        results,
+        values,
+        len,
+    } => self.execute_copy_span_non_overlapping(results, values, len),
+    CopyMany { results, values } => self.execute_copy_many(results, values),
+    CopyManyNonOverlapping { results, values } => {
+        self.execute_copy_many_non_overlapping(results, values)
+    },
+    LocalGetSpilled { result, slot } => {
+        self.execute_local_get_spilled(result, slot)
+    },
+    LocalSetSpilled { slot, value } => {
+        self.execute_local_set_spilled(slot, value)
+    },
+    ReturnCallInternal0 { func } => self.execute_return_call_internal_0(func)?,
+    ReturnCallInternal { func } => self.execute_return_call_internal(func)?,
+    ReturnCallImported0 { func } => {
+        forward_call!(self.execute_return_call_imported_0(func))
+    },
+    ReturnCallImported { func } => {
+        forward_call!(self.execute_return_call_imported(func))
+    },
+    ReturnCallIndirect0 { func_type } => {
+        forward_call!(self.execute_return_call_indirect_0(func_type))
+    },
+    ReturnCallIndirect { func_type } => {
+        forward_call!(self.execute_return_call_indirect(func_type))
+    },
+    CallInternal0 { results, func } => {
+        self.execute_call_internal_0(results, func)?
+    },
+    CallInternal { results, func } => {
+        self.execute_call_internal(results, func)?
+    },
+    CallImported0 { results, func } => {
+        forward_call!(self.execute_call_imported_0(results, func))
+    },
+    CallImported { results, func } => {
+        forward_call!(self.execute_call_imported(results, func))
+    },
+    CallIndirect0 { results, func_type } => {
+        forward_call!(self.execute_call_indirect_0(results, func_type))
+    },
+    CallIndirect { results, func_type } => {
+        forward_call!(self.execute_call_indirect(results, func_type))
+    },
+    Select {
+        result,
+        condition,
+        lhs,
+    } => self.execute_select(result, condition, lhs),
+    SelectRev {
+        result,
+        condition,
+        rhs,
+    } => self.execute_select_rev(result, condition, rhs),
+    SelectImm32 {
+        result_or_condition,
+        lhs_or_rhs,
+    } => self.execute_select_imm32(result_or_condition, lhs_or_rhs),
+    SelectI64Imm32 {
+        result_or_condition,
+        lhs_or_rhs,
+    } => self.execute_select_i64imm32(result_or_condition, lhs_or_rhs),
+    SelectF64Imm32 {
+        result_or_condition,
+        lhs_or_rhs,
+    } => self.execute_select_f64imm32(result_or_condition, lhs_or_rhs),
+    RefFunc { result, func } => self.execute_ref_func(result, func),
+    TableGet { result, index } => self.execute_table_get(result, index)?,
+    TableGetImm { result, index } => {
+        self.execute_table_get_imm(result, index)?
+    },
+    TableSize { result, table } => self.execute_table_size(result, table),
+    TableSet { index, value } => self.execute_table_set(index, value)?,
+    TableSetAt { index, value } => self.execute_table_set_at(index, value)?,
+    TableCopy { dst, src, len } => self.execute_table_copy(dst, src, len)?,
+    TableCopyTo { dst, src, len } => {
+        self.execute_table_copy_to(dst, src, len)?
+    },
+    TableCopyFrom { dst, src, len } => {
+        self.execute_table_copy_from(dst, src, len)?
+    },
+    TableCopyFromTo { dst, src, len } => {
+        self.execute_table_copy_from_to(dst, src, len)?
+    },
+    TableCopyExact { dst, src, len } => {
+        self.execute_table_copy_exact(dst, src, len)?
+    },
+    TableCopyToExact { dst, src, len } => {
+        self.execute_table_copy_to_exact(dst, src, len)?
+    },
+    TableCopyFromExact { dst, src, len } => {
+        self.execute_table_copy_from_exact(dst, src, len)?
+    },
+    TableCopyFromToExact { dst, src, len } => {
+        self.execute_table_copy_from_to_exact(dst, src, len)?
+    },
+    TableInit { dst, src, len } => self.execute_table_init(dst, src, len)?,
+    TableInitTo { dst, src, len } => {
+        self.execute_table_init_to(dst, src, len)?
+    },
+    TableInitFrom { dst, src, len } => {
+        self.execute_table_init_from(dst, src, len)?
+    },
+    TableInitFromTo { dst, src, len } => {
+        self.execute_table_init_from_to(dst, src, len)?
+    },
+    TableInitExact { dst, src, len } => {
+        self.execute_table_init_exact(dst, src, len)?
+    },
+    TableInitToExact { dst, src, len } => {
+        self.execute_table_init_to_exact(dst, src, len)?
+    },
+    TableInitFromExact { dst, src, len } => {
+        self.execute_table_init_from_exact(dst, src, len)?
+    },
+    TableInitFromToExact { dst, src, len } => {
+        self.execute_table_init_from_to_exact(dst, src, len)?
+    },
+    TableFill { dst, len, value } => self.execute_table_fill(dst, len, value)?,
+    TableFillAt { dst, len, value } => {
+        self.execute_table_fill_at(dst, len, value)?
+    },
+    TableFillExact { dst, len, value } => {
+        self.execute_table_fill_exact(dst, len, value)?
+    },
+    TableFillAtExact { dst, len, value } => {
+        self.execute_table_fill_at_exact(dst, len, value)?
+    },
+    TableGrow {
+        result,
+        delta,
+        value,
+    } => self.execute_table_grow(result, delta, value, &mut *resource_limiter)?,
+    TableGrowImm {
+        result,
+        delta,
+        value,
+    } => self.execute_table_grow_imm(result, delta, value, &mut *resource_limiter)?,
+    ElemDrop(element_index) => self.execute_element_drop(element_index),
+    DataDrop(data_index) => self.execute_data_drop(data_index),
+    MemorySize { result } => self.execute_memory_size(result),
+    MemoryGrow { result, delta } => {
+        self.execute_memory_grow(result, delta, &mut *resource_limiter)?
+    },
+    MemoryGrowBy { result, delta } => {
+        self.execute_memory_grow_by(result, delta, &mut *resource_limiter)?
+    },
+    MemoryCopy { dst, src, len } => self.execute_memory_copy(dst, src, len)?,
+    MemoryCopyTo { dst, src, len } => {
+        self.execute_memory_copy_to(dst, src, len)?
+    },
+    MemoryCopyFrom { dst, src, len } => {
+        self.execute_memory_copy_from(dst, src, len)?
+    },
+    MemoryCopyFromTo { dst, src, len } => {
+        self.execute_memory_copy_from_to(dst, src, len)?
+    },
+    MemoryCopyExact { dst, src, len } => {
+        self.execute_memory_copy_exact(dst, src, len)?
+    },
+    MemoryCopyToExact { dst, src, len } => {
+        self.execute_memory_copy_to_exact(dst, src, len)?
+    },
+    MemoryCopyFromExact { dst, src, len } => {
+        self.execute_memory_copy_from_exact(dst, src, len)?
+    },
+    MemoryCopyFromToExact { dst, src, len } => {
+        self.execute_memory_copy_from_to_exact(dst, src, len)?
+    },
+    MemoryFill { dst, value, len } => {
+        self.execute_memory_fill(dst, value, len)?
+    },
+    MemoryFillAt { dst, value, len } => {
+        self.execute_memory_fill_at(dst, value, len)?
+    },
+    MemoryFillImm { dst, value, len } => {
+        self.execute_memory_fill_imm(dst, value, len)?
+    },
+    MemoryFillExact { dst, value, len } => {
+        self.execute_memory_fill_exact(dst, value, len)?
+    },
+    MemoryFillAtImm { dst, value, len } => {
+        self.execute_memory_fill_at_imm(dst, value, len)?
+    },
+    MemoryFillAtExact { dst, value, len } => {
+        self.execute_memory_fill_at_exact(dst, value, len)?
+    },
+    MemoryFillImmExact { dst, value, len } => {
+        self.execute_memory_fill_imm_exact(dst, value, len)?
+    },
+    MemoryFillAtImmExact { dst, value, len } => {
+        self.execute_memory_fill_at_imm_exact(dst, value, len)?
+    },
+    MemoryInit { dst, src, len } => self.execute_memory_init(dst, src, len)?,
+    MemoryInitTo { dst, src, len } => {
+        self.execute_memory_init_to(dst, src, len)?
+    },
+    MemoryInitFrom { dst, src, len } => {
+        self.execute_memory_init_from(dst, src, len)?
+    },
+    MemoryInitFromTo { dst, src, len } => {
+        self.execute_memory_init_from_to(dst, src, len)?
+    },
+    MemoryInitExact { dst, src, len } => {
+        self.execute_memory_init_exact(dst, src, len)?
+    },
+    MemoryInitToExact { dst, src, len } => {
+        self.execute_memory_init_to_exact(dst, src, len)?
+    },
+    MemoryInitFromExact { dst, src, len } => {
+        self.execute_memory_init_from_exact(dst, src, len)?
+    },
+    MemoryInitFromToExact { dst, src, len } => {
+        self.execute_memory_init_from_to_exact(dst, src, len)?
+    },
+    GlobalGet { result, global } => self.execute_global_get(result, global),
+    GlobalSet { global, input } => self.execute_global_set(global, input),
+    GlobalSetI32Imm16 { global, input } => {
+        self.execute_global_set_i32imm16(global, input)
+    },
+    GlobalSetI64Imm16 { global, input } => {
+        self.execute_global_set_i64imm16(global, input)
+    },
+    I32Load(instr) => self.execute_i32_load(instr)?,
+    I32LoadAt(instr) => self.execute_i32_load_at(instr)?,
+    I32LoadOffset16(instr) => self.execute_i32_load_offset16(instr)?,
+    I32LoadOffset16Add(instr) => self.execute_i32_load_offset16_add(instr)?,
+    I32LoadOffset16AddImm(instr) => {
+        self.execute_i32_load_offset16_add_imm(instr)?
+    },
+    I64Load(instr) => self.execute_i64_load(instr)?,
+    I64LoadAt(instr) => self.execute_i64_load_at(instr)?,
+    I64LoadOffset16(instr) => self.execute_i64_load_offset16(instr)?,
+    F32Load(instr) => self.execute_f32_load(instr)?,
+    F32LoadAt(instr) => self.execute_f32_load_at(instr)?,
+    F32LoadOffset16(instr) => self.execute_f32_load_offset16(instr)?,
+    F64Load(instr) => self.execute_f64_load(instr)?,
+    F64LoadAt(instr) => self.execute_f64_load_at(instr)?,
+    F64LoadOffset16(instr) => self.execute_f64_load_offset16(instr)?,
+    I32Load8s(instr) => self.execute_i32_load8_s(instr)?,
+    I32Load8sAt(instr) => self.execute_i32_load8_s_at(instr)?,
+    I32Load8sOffset16(instr) => self.execute_i32_load8_s_offset16(instr)?,
+    I32Load8u(instr) => self.execute_i32_load8_u(instr)?,
+    I32Load8uAt(instr) => self.execute_i32_load8_u_at(instr)?,
+    I32Load8uOffset16(instr) => self.execute_i32_load8_u_offset16(instr)?,
+    I32Load16s(instr) => self.execute_i32_load16_s(instr)?,
+    I32Load16sAt(instr) => self.execute_i32_load16_s_at(instr)?,
+    I32Load16sOffset16(instr) => self.execute_i32_load16_s_offset16(instr)?,
+    I32Load16u(instr) => self.execute_i32_load16_u(instr)?,
+    I32Load16uAt(instr) => self.execute_i32_load16_u_at(instr)?,
+    I32Load16uOffset16(instr) => self.execute_i32_load16_u_offset16(instr)?,
+    I64Load8s(instr) => self.execute_i64_load8_s(instr)?,
+    I64Load8sAt(instr) => self.execute_i64_load8_s_at(instr)?,
+    I64Load8sOffset16(instr) => self.execute_i64_load8_s_offset16(instr)?,
+    I64Load8u(instr) => self.execute_i64_load8_u(instr)?,
+    I64Load8uAt(instr) => self.execute_i64_load8_u_at(instr)?,
+    I64Load8uOffset16(instr) => self.execute_i64_load8_u_offset16(instr)?,
+    I64Load16s(instr) => self.execute_i64_load16_s(instr)?,
+    I64Load16sAt(instr) => self.execute_i64_load16_s_at(instr)?,
+    I64Load16sOffset16(instr) => self.execute_i64_load16_s_offset16(instr)?,
+    I64Load16u(instr) => self.execute_i64_load16_u(instr)?,
+    I64Load16uAt(instr) => self.execute_i64_load16_u_at(instr)?,
+    I64Load16uOffset16(instr) => self.execute_i64_load16_u_offset16(instr)?,
+    I64Load32s(instr) => self.execute_i64_load32_s(instr)?,
+    I64Load32sAt(instr) => self.execute_i64_load32_s_at(instr)?,
+    I64Load32sOffset16(instr) => self.execute_i64_load32_s_offset16(instr)?,
+    I64Load32u(instr) => self.execute_i64_load32_u(instr)?,
+    I64Load32uAt(instr) => self.execute_i64_load32_u_at(instr)?,
+    I64Load32uOffset16(instr) => self.execute_i64_load32_u_offset16(instr)?,
+    I32Store(instr) => self.execute_i32_store(instr)?,
+    I32StoreOffset16(instr) => self.execute_i32_store_offset16(instr)?,
+    I32StoreOffset16Imm16(instr) => {
+        self.execute_i32_store_offset16_imm16(instr)?
+    },
+    I32StoreOffset16AddImm(instr) => {
+        self.execute_i32_store_offset16_add_imm(instr)?
+    },
+    I32StoreAt(instr) => self.execute_i32_store_at(instr)?,
+    I32StoreAtImm16(instr) => self.execute_i32_store_at_imm16(instr)?,
+    I32Store8(instr) => self.execute_i32_store8(instr)?,
+    I32Store8Offset16(instr) => self.execute_i32_store8_offset16(instr)?,
+    I32Store8Offset16Imm(instr) => {
+        self.execute_i32_store8_offset16_imm(instr)?
+    },
+    I32Store8At(instr) => self.execute_i32_store8_at(instr)?,
+    I32Store8AtImm(instr) => self.execute_i32_store8_at_imm(instr)?,
+    I32Store16(instr) => self.execute_i32_store16(instr)?,
+    I32Store16Offset16(instr) => self.execute_i32_store16_offset16(instr)?,
+    I32Store16Offset16Imm(instr) => {
+        self.execute_i32_store16_offset16_imm(instr)?
+    },
+    I32Store16At(instr) => self.execute_i32_store16_at(instr)?,
+    I32Store16AtImm(instr) => self.execute_i32_store16_at_imm(instr)?,
+    I64Store(instr) => self.execute_i64_store(instr)?,
+    I64StoreOffset16(instr) => self.execute_i64_store_offset16(instr)?,
+    I64StoreOffset16Imm16(instr) => {
+        self.execute_i64_store_offset16_imm16(instr)?
+    },
+    I64StoreAt(instr) => self.execute_i64_store_at(instr)?,
+    I64StoreAtImm16(instr) => self.execute_i64_store_at_imm16(instr)?,
+    I64Store8(instr) => self.execute_i64_store8(instr)?,
+    I64Store8Offset16(instr) => self.execute_i64_store8_offset16(instr)?,
+    I64Store8Offset16Imm(instr) => {
+        self.execute_i64_store8_offset16_imm(instr)?
+    },
+    I64Store8At(instr) => self.execute_i64_store8_at(instr)?,
+    I64Store8AtImm(instr) => self.execute_i64_store8_at_imm(instr)?,
+    I64Store16(instr) => self.execute_i64_store16(instr)?,
+    I64Store16Offset16(instr) => self.execute_i64_store16_offset16(instr)?,
+    I64Store16Offset16Imm(instr) => {
+        self.execute_i64_store16_offset16_imm(instr)?
+    },
+    I64Store16At(instr) => self.execute_i64_store16_at(instr)?,
+    I64Store16AtImm(instr) => self.execute_i64_store16_at_imm(instr)?,
+    I64Store32(instr) => self.execute_i64_store32(instr)?,
+    I64Store32Offset16(instr) => self.execute_i64_store32_offset16(instr)?,
+    I64Store32Offset16Imm16(instr) => {
+        self.execute_i64_store32_offset16_imm16(instr)?
+    },
+    I64Store32At(instr) => self.execute_i64_store32_at(instr)?,
+    I64Store32AtImm16(instr) => self.execute_i64_store32_at_imm16(instr)?,
+    F32Store(instr) => self.execute_f32_store(instr)?,
+    F32StoreOffset16(instr) => self.execute_f32_store_offset16(instr)?,
+    F32StoreAt(instr) => self.execute_f32_store_at(instr)?,
+    F64Store(instr) => self.execute_f64_store(instr)?,
+    F64StoreOffset16(instr) => self.execute_f64_store_offset16(instr)?,
+    F64StoreAt(instr) => self.execute_f64_store_at(instr)?,
+    AtomicFence {} => self.execute_atomic_fence(),
+    MemoryAtomicNotify(instr) => self.execute_memory_atomic_notify(instr)?,
+    MemoryAtomicWait32(instr) => self.execute_memory_atomic_wait32(instr)?,
+    MemoryAtomicWait64(instr) => self.execute_memory_atomic_wait64(instr)?,
+    I32AtomicLoad(instr) => self.execute_i32_atomic_load(instr)?,
+    I64AtomicLoad(instr) => self.execute_i64_atomic_load(instr)?,
+    I32AtomicLoad8u(instr) => self.execute_i32_atomic_load8_u(instr)?,
+    I32AtomicLoad16u(instr) => self.execute_i32_atomic_load16_u(instr)?,
+    I64AtomicLoad8u(instr) => self.execute_i64_atomic_load8_u(instr)?,
+    I64AtomicLoad16u(instr) => self.execute_i64_atomic_load16_u(instr)?,
+    I64AtomicLoad32u(instr) => self.execute_i64_atomic_load32_u(instr)?,
+    I32AtomicStore(instr) => self.execute_i32_atomic_store(instr)?,
+    I64AtomicStore(instr) => self.execute_i64_atomic_store(instr)?,
+    I32AtomicStore8(instr) => self.execute_i32_atomic_store8(instr)?,
+    I32AtomicStore16(instr) => self.execute_i32_atomic_store16(instr)?,
+    I64AtomicStore8(instr) => self.execute_i64_atomic_store8(instr)?,
+    I64AtomicStore16(instr) => self.execute_i64_atomic_store16(instr)?,
+    I64AtomicStore32(instr) => self.execute_i64_atomic_store32(instr)?,
+    I32AtomicRmwAdd(instr) => self.execute_i32_atomic_rmw_add(instr)?,
+    I64AtomicRmwAdd(instr) => self.execute_i64_atomic_rmw_add(instr)?,
+    I32AtomicRmw8AddU(instr) => self.execute_i32_atomic_rmw8_add_u(instr)?,
+    I32AtomicRmw16AddU(instr) => self.execute_i32_atomic_rmw16_add_u(instr)?,
+    I64AtomicRmw8AddU(instr) => self.execute_i64_atomic_rmw8_add_u(instr)?,
+    I64AtomicRmw16AddU(instr) => self.execute_i64_atomic_rmw16_add_u(instr)?,
+    I64AtomicRmw32AddU(instr) => self.execute_i64_atomic_rmw32_add_u(instr)?,
+    I32AtomicRmwSub(instr) => self.execute_i32_atomic_rmw_sub(instr)?,
+    I64AtomicRmwSub(instr) => self.execute_i64_atomic_rmw_sub(instr)?,
+    I32AtomicRmw8SubU(instr) => self.execute_i32_atomic_rmw8_sub_u(instr)?,
+    I32AtomicRmw16SubU(instr) => self.execute_i32_atomic_rmw16_sub_u(instr)?,
+    I64AtomicRmw8SubU(instr) => self.execute_i64_atomic_rmw8_sub_u(instr)?,
+    I64AtomicRmw16SubU(instr) => self.execute_i64_atomic_rmw16_sub_u(instr)?,
+    I64AtomicRmw32SubU(instr) => self.execute_i64_atomic_rmw32_sub_u(instr)?,
+    I32AtomicRmwAnd(instr) => self.execute_i32_atomic_rmw_and(instr)?,
+    I64AtomicRmwAnd(instr) => self.execute_i64_atomic_rmw_and(instr)?,
+    I32AtomicRmw8AndU(instr) => self.execute_i32_atomic_rmw8_and_u(instr)?,
+    I32AtomicRmw16AndU(instr) => self.execute_i32_atomic_rmw16_and_u(instr)?,
+    I64AtomicRmw8AndU(instr) => self.execute_i64_atomic_rmw8_and_u(instr)?,
+    I64AtomicRmw16AndU(instr) => self.execute_i64_atomic_rmw16_and_u(instr)?,
+    I64AtomicRmw32AndU(instr) => self.execute_i64_atomic_rmw32_and_u(instr)?,
+    I32AtomicRmwOr(instr) => self.execute_i32_atomic_rmw_or(instr)?,
+    I64AtomicRmwOr(instr) => self.execute_i64_atomic_rmw_or(instr)?,
+    I32AtomicRmw8OrU(instr) => self.execute_i32_atomic_rmw8_or_u(instr)?,
+    I32AtomicRmw16OrU(instr) => self.execute_i32_atomic_rmw16_or_u(instr)?,
+    I64AtomicRmw8OrU(instr) => self.execute_i64_atomic_rmw8_or_u(instr)?,
+    I64AtomicRmw16OrU(instr) => self.execute_i64_atomic_rmw16_or_u(instr)?,
+    I64AtomicRmw32OrU(instr) => self.execute_i64_atomic_rmw32_or_u(instr)?,
+    I32AtomicRmwXor(instr) => self.execute_i32_atomic_rmw_xor(instr)?,
+    I64AtomicRmwXor(instr) => self.execute_i64_atomic_rmw_xor(instr)?,
+    I32AtomicRmw8XorU(instr) => self.execute_i32_atomic_rmw8_xor_u(instr)?,
+    I32AtomicRmw16XorU(instr) => self.execute_i32_atomic_rmw16_xor_u(instr)?,
+    I64AtomicRmw8XorU(instr) => self.execute_i64_atomic_rmw8_xor_u(instr)?,
+    I64AtomicRmw16XorU(instr) => self.execute_i64_atomic_rmw16_xor_u(instr)?,
+    I64AtomicRmw32XorU(instr) => self.execute_i64_atomic_rmw32_xor_u(instr)?,
+    I32AtomicRmwXchg(instr) => self.execute_i32_atomic_rmw_xchg(instr)?,
+    I64AtomicRmwXchg(instr) => self.execute_i64_atomic_rmw_xchg(instr)?,
+    I32AtomicRmw8XchgU(instr) => self.execute_i32_atomic_rmw8_xchg_u(instr)?,
+    I32AtomicRmw16XchgU(instr) => self.execute_i32_atomic_rmw16_xchg_u(instr)?,
+    I64AtomicRmw8XchgU(instr) => self.execute_i64_atomic_rmw8_xchg_u(instr)?,
+    I64AtomicRmw16XchgU(instr) => self.execute_i64_atomic_rmw16_xchg_u(instr)?,
+    I64AtomicRmw32XchgU(instr) => self.execute_i64_atomic_rmw32_xchg_u(instr)?,
+    I32AtomicRmwCmpxchg(instr) => self.execute_i32_atomic_rmw_cmpxchg(instr)?,
+    I64AtomicRmwCmpxchg(instr) => self.execute_i64_atomic_rmw_cmpxchg(instr)?,
+    I32AtomicRmw8CmpxchgU(instr) => {
+        self.execute_i32_atomic_rmw8_cmpxchg_u(instr)?
+    },
+    I32AtomicRmw16CmpxchgU(instr) => {
+        self.execute_i32_atomic_rmw16_cmpxchg_u(instr)?
+    },
+    I64AtomicRmw8CmpxchgU(instr) => {
+        self.execute_i64_atomic_rmw8_cmpxchg_u(instr)?
+    },
+    I64AtomicRmw16CmpxchgU(instr) => {
+        self.execute_i64_atomic_rmw16_cmpxchg_u(instr)?
+    },
+    I64AtomicRmw32CmpxchgU(instr) => {
+        self.execute_i64_atomic_rmw32_cmpxchg_u(instr)?
+    },
+    I32Eq(instr) => self.execute_i32_eq(instr),
+    I32EqImm16(instr) => self.execute_i32_eq_imm16(instr),
+    I64Eq(instr) => self.execute_i64_eq(instr),
+    I64EqImm16(instr) => self.execute_i64_eq_imm16(instr),
+    I32Ne(instr) => self.execute_i32_ne(instr),
+    I32NeImm16(instr) => self.execute_i32_ne_imm16(instr),
+    I64Ne(instr) => self.execute_i64_ne(instr),
+    I64NeImm16(instr) => self.execute_i64_ne_imm16(instr),
+    I32LtS(instr) => self.execute_i32_lt_s(instr),
+    I32LtU(instr) => self.execute_i32_lt_u(instr),
+    I32LtSImm16(instr) => self.execute_i32_lt_s_imm16(instr),
+    I32LtUImm16(instr) => self.execute_i32_lt_u_imm16(instr),
+    I64LtS(instr) => self.execute_i64_lt_s(instr),
+    I64LtU(instr) => self.execute_i64_lt_u(instr),
+    I64LtSImm16(instr) => self.execute_i64_lt_s_imm16(instr),
+    I64LtUImm16(instr) => self.execute_i64_lt_u_imm16(instr),
+    I32GtS(instr) => self.execute_i32_gt_s(instr),
+    I32GtU(instr) => self.execute_i32_gt_u(instr),
+    I32GtSImm16(instr) => self.execute_i32_gt_s_imm16(instr),
+    I32GtUImm16(instr) => self.execute_i32_gt_u_imm16(instr),
+    I64GtS(instr) => self.execute_i64_gt_s(instr),
+    I64GtU(instr) => self.execute_i64_gt_u(instr),
+    I64GtSImm16(instr) => self.execute_i64_gt_s_imm16(instr),
+    I64GtUImm16(instr) => self.execute_i64_gt_u_imm16(instr),
+    I32LeS(instr) => self.execute_i32_le_s(instr),
+    I32LeU(instr) => self.execute_i32_le_u(instr),
+    I32LeSImm16(instr) => self.execute_i32_le_s_imm16(instr),
+    I32LeUImm16(instr) => self.execute_i32_le_u_imm16(instr),
+    I64LeS(instr) => self.execute_i64_le_s(instr),
+    I64LeU(instr) => self.execute_i64_le_u(instr),
+    I64LeSImm16(instr) => self.execute_i64_le_s_imm16(instr),
+    I64LeUImm16(instr) => self.execute_i64_le_u_imm16(instr),
+    I32GeS(instr) => self.execute_i32_ge_s(instr),
+    I32GeU(instr) => self.execute_i32_ge_u(instr),
+    I32GeSImm16(instr) => self.execute_i32_ge_s_imm16(instr),
+    I32GeUImm16(instr) => self.execute_i32_ge_u_imm16(instr),
+    I64GeS(instr) => self.execute_i64_ge_s(instr),
+    I64GeU(instr) => self.execute_i64_ge_u(instr),
+    I64GeSImm16(instr) => self.execute_i64_ge_s_imm16(instr),
+    I64GeUImm16(instr) => self.execute_i64_ge_u_imm16(instr),
+    F32Eq(instr) => self.execute_f32_eq(instr),
+    F64Eq(instr) => self.execute_f64_eq(instr),
+    F32Ne(instr) => self.execute_f32_ne(instr),
+    F64Ne(instr) => self.execute_f64_ne(instr),
+    F32Lt(instr) => self.execute_f32_lt(instr),
+    F64Lt(instr) => self.execute_f64_lt(instr),
+    F32Le(instr) => self.execute_f32_le(instr),
+    F64Le(instr) => self.execute_f64_le(instr),
+    F32Gt(instr) => self.execute_f32_gt(instr),
+    F64Gt(instr) => self.execute_f64_gt(instr),
+    F32Ge(instr) => self.execute_f32_ge(instr),
+    F64Ge(instr) => self.execute_f64_ge(instr),
+    I32Clz(instr) => self.execute_i32_clz(instr),
+    I64Clz(instr) => self.execute_i64_clz(instr),
+    I32Ctz(instr) => self.execute_i32_ctz(instr),
+    I64Ctz(instr) => self.execute_i64_ctz(instr),
+    I32Popcnt(instr) => self.execute_i32_popcnt(instr),
+    I64Popcnt(instr) => self.execute_i64_popcnt(instr),
+    I32Add(instr) => self.execute_i32_add(instr),
+    I64Add(instr) => self.execute_i64_add(instr),
+    I32AddImm16(instr) => self.execute_i32_add_imm16(instr),
+    I64AddImm16(instr) => self.execute_i64_add_imm16(instr),
+    I32Sub(instr) => self.execute_i32_sub(instr),
+    I64Sub(instr) => self.execute_i64_sub(instr),
+    I32SubImm16Rev(instr) => self.execute_i32_sub_imm16_rev(instr),
+    I64SubImm16Rev(instr) => self.execute_i64_sub_imm16_rev(instr),
+    I32Mul(instr) => self.execute_i32_mul(instr),
+    I64Mul(instr) => self.execute_i64_mul(instr),
+    I32MulImm16(instr) => self.execute_i32_mul_imm16(instr),
+    I64MulImm16(instr) => self.execute_i64_mul_imm16(instr),
+    I32DivS(instr) => self.execute_i32_div_s(instr)?,
+    I64DivS(instr) => self.execute_i64_div_s(instr)?,
+    I32DivSImm16(instr) => self.execute_i32_div_s_imm16(instr)?,
+    I64DivSImm16(instr) => self.execute_i64_div_s_imm16(instr)?,
+    I32DivSImm16Rev(instr) => self.execute_i32_div_s_imm16_rev(instr)?,
+    I64DivSImm16Rev(instr) => self.execute_i64_div_s_imm16_rev(instr)?,
+    I32DivU(instr) => self.execute_i32_div_u(instr)?,
+    I64DivU(instr) => self.execute_i64_div_u(instr)?,
+    I32DivUImm16(instr) => self.execute_i32_div_u_imm16(instr),
+    I64DivUImm16(instr) => self.execute_i64_div_u_imm16(instr),
+    I32DivUImm16Rev(instr) => self.execute_i32_div_u_imm16_rev(instr)?,
+    I64DivUImm16Rev(instr) => self.execute_i64_div_u_imm16_rev(instr)?,
+    I32RemS(instr) => self.execute_i32_rem_s(instr)?,
+    I64RemS(instr) => self.execute_i64_rem_s(instr)?,
+    I32RemSImm16(instr) => self.execute_i32_rem_s_imm16(instr)?,
+    I64RemSImm16(instr) => self.execute_i64_rem_s_imm16(instr)?,
+    I32RemSImm16Rev(instr) => self.execute_i32_rem_s_imm16_rev(instr)?,
+    I64RemSImm16Rev(instr) => self.execute_i64_rem_s_imm16_rev(instr)?,
+    I32RemU(instr) => self.execute_i32_rem_u(instr)?,
+    I64RemU(instr) => self.execute_i64_rem_u(instr)?,
+    I32RemUImm16(instr) => self.execute_i32_rem_u_imm16(instr),
+    I64RemUImm16(instr) => self.execute_i64_rem_u_imm16(instr),
+    I32RemUImm16Rev(instr) => self.execute_i32_rem_u_imm16_rev(instr)?,
+    I64RemUImm16Rev(instr) => self.execute_i64_rem_u_imm16_rev(instr)?,
+    I32And(instr) => self.execute_i32_and(instr),
+    I32AndEqz(instr) => self.execute_i32_and_eqz(instr),
+    I32AndEqzImm16(instr) => self.execute_i32_and_eqz_imm16(instr),
+    I32AndImm16(instr) => self.execute_i32_and_imm16(instr),
+    I64And(instr) => self.execute_i64_and(instr),
+    I64AndImm16(instr) => self.execute_i64_and_imm16(instr),
+    I32Or(instr) => self.execute_i32_or(instr),
+    I32OrEqz(instr) => self.execute_i32_or_eqz(instr),
+    I32OrEqzImm16(instr) => self.execute_i32_or_eqz_imm16(instr),
+    I32OrImm16(instr) => self.execute_i32_or_imm16(instr),
+    I64Or(instr) => self.execute_i64_or(instr),
+    I64OrImm16(instr) => self.execute_i64_or_imm16(instr),
+    I32Xor(instr) => self.execute_i32_xor(instr),
+    I32XorEqz(instr) => self.execute_i32_xor_eqz(instr),
+    I32XorEqzImm16(instr) => self.execute_i32_xor_eqz_imm16(instr),
+    I32XorImm16(instr) => self.execute_i32_xor_imm16(instr),
+    I64Xor(instr) => self.execute_i64_xor(instr),
+    I64XorImm16(instr) => self.execute_i64_xor_imm16(instr),
+    I32Shl(instr) => self.execute_i32_shl(instr),
+    I64Shl(instr) => self.execute_i64_shl(instr),
+    I32ShlImm(instr) => self.execute_i32_shl_imm(instr),
+    I64ShlImm(instr) => self.execute_i64_shl_imm(instr),
+    I32ShlImm16Rev(instr) => self.execute_i32_shl_imm16_rev(instr),
+    I64ShlImm16Rev(instr) => self.execute_i64_shl_imm16_rev(instr),
+    I32ShrU(instr) => self.execute_i32_shr_u(instr),
+    I64ShrU(instr) => self.execute_i64_shr_u(instr),
+    I32ShrUImm(instr) => self.execute_i32_shr_u_imm(instr),
+    I64ShrUImm(instr) => self.execute_i64_shr_u_imm(instr),
+    I32ShrUImm16Rev(instr) => self.execute_i32_shr_u_imm16_rev(instr),
+    I64ShrUImm16Rev(instr) => self.execute_i64_shr_u_imm16_rev(instr),
+    I32ShrS(instr) => self.execute_i32_shr_s(instr),
+    I64ShrS(instr) => self.execute_i64_shr_s(instr),
+    I32ShrSImm(instr) => self.execute_i32_shr_s_imm(instr),
+    I64ShrSImm(instr) => self.execute_i64_shr_s_imm(instr),
+    I32ShrSImm16Rev(instr) => self.execute_i32_shr_s_imm16_rev(instr),
+    I64ShrSImm16Rev(instr) => self.execute_i64_shr_s_imm16_rev(instr),
+    I32Rotl(instr) => self.execute_i32_rotl(instr),
+    I64Rotl(instr) => self.execute_i64_rotl(instr),
+    I32RotlImm(instr) => self.execute_i32_rotl_imm(instr),
+    I64RotlImm(instr) => self.execute_i64_rotl_imm(instr),
+    I32RotlImm16Rev(instr) => self.execute_i32_rotl_imm16_rev(instr),
+    I64RotlImm16Rev(instr) => self.execute_i64_rotl_imm16_rev(instr),
+    I32Rotr(instr) => self.execute_i32_rotr(instr),
+    I64Rotr(instr) => self.execute_i64_rotr(instr),
+    I32RotrImm(instr) => self.execute_i32_rotr_imm(instr),
+    I64RotrImm(instr) => self.execute_i64_rotr_imm(instr),
+    I32RotrImm16Rev(instr) => self.execute_i32_rotr_imm16_rev(instr),
+    I64RotrImm16Rev(instr) => self.execute_i64_rotr_imm16_rev(instr),
+    F32Abs(instr) => self.execute_f32_abs(instr),
+    F64Abs(instr) => self.execute_f64_abs(instr),
+    F32Neg(instr) => self.execute_f32_neg(instr),
+    F64Neg(instr) => self.execute_f64_neg(instr),
+    F32Ceil(instr) => self.execute_f32_ceil(instr),
+    F64Ceil(instr) => self.execute_f64_ceil(instr),
+    F32Floor(instr) => self.execute_f32_floor(instr),
+    F64Floor(instr) => self.execute_f64_floor(instr),
+    F32Trunc(instr) => self.execute_f32_trunc(instr),
+    F64Trunc(instr) => self.execute_f64_trunc(instr),
+    F32Nearest(instr) => self.execute_f32_nearest(instr),
+    F64Nearest(instr) => self.execute_f64_nearest(instr),
+    F32Sqrt(instr) => self.execute_f32_sqrt(instr),
+    F64Sqrt(instr) => self.execute_f64_sqrt(instr),
+    F32Add(instr) => self.execute_f32_add(instr),
+    F64Add(instr) => self.execute_f64_add(instr),
+    F32Sub(instr) => self.execute_f32_sub(instr),
+    F64Sub(instr) => self.execute_f64_sub(instr),
+    F32Mul(instr) => self.execute_f32_mul(instr),
+    F64Mul(instr) => self.execute_f64_mul(instr),
+    F32Div(instr) => self.execute_f32_div(instr),
+    F64Div(instr) => self.execute_f64_div(instr),
+    F32Min(instr) => self.execute_f32_min(instr),
+    F64Min(instr) => self.execute_f64_min(instr),
+    F32Max(instr) => self.execute_f32_max(instr),
+    F64Max(instr) => self.execute_f64_max(instr),
+    F32Copysign(instr) => self.execute_f32_copysign(instr),
+    F64Copysign(instr) => self.execute_f64_copysign(instr),
+    F32CopysignImm(instr) => self.execute_f32_copysign_imm(instr),
+    F64CopysignImm(instr) => self.execute_f64_copysign_imm(instr),
+    I32WrapI64(instr) => self.execute_i32_wrap_i64(instr),
+    I64ExtendI32S(instr) => self.execute_i64_extend_i32_s(instr),
+    I64ExtendI32U(instr) => self.execute_i64_extend_i32_u(instr),
+    I32TruncF32S(instr) => self.execute_i32_trunc_f32_s(instr)?,
+    I32TruncF32U(instr) => self.execute_i32_trunc_f32_u(instr)?,
+    I32TruncF64S(instr) => self.execute_i32_trunc_f64_s(instr)?,
+    I32TruncF64U(instr) => self.execute_i32_trunc_f64_u(instr)?,
+    I64TruncF32S(instr) => self.execute_i64_trunc_f32_s(instr)?,
+    I64TruncF32U(instr) => self.execute_i64_trunc_f32_u(instr)?,
+    I64TruncF64S(instr) => self.execute_i64_trunc_f64_s(instr)?,
+    I64TruncF64U(instr) => self.execute_i64_trunc_f64_u(instr)?,
+    I32TruncSatF32S(instr) => self.execute_i32_trunc_sat_f32_s(instr),
+    I32TruncSatF32U(instr) => self.execute_i32_trunc_sat_f32_u(instr),
+    I32TruncSatF64S(instr) => self.execute_i32_trunc_sat_f64_s(instr),
+    I32TruncSatF64U(instr) => self.execute_i32_trunc_sat_f64_u(instr),
+    I64TruncSatF32S(instr) => self.execute_i64_trunc_sat_f32_s(instr),
+    I64TruncSatF32U(instr) => self.execute_i64_trunc_sat_f32_u(instr),
+    I64TruncSatF64S(instr) => self.execute_i64_trunc_sat_f64_s(instr),
+    I64TruncSatF64U(instr) => self.execute_i64_trunc_sat_f64_u(instr),
+    I32Extend8S(instr) => self.execute_i32_extend8_s(instr),
+    I32Extend16S(instr) => self.execute_i32_extend16_s(instr),
+    I64Extend8S(instr) => self.execute_i64_extend8_s(instr),
+    I64Extend16S(instr) => self.execute_i64_extend16_s(instr),
+    I64Extend32S(instr) => self.execute_i64_extend32_s(instr),
+    F32DemoteF64(instr) => self.execute_f32_demote_f64(instr),
+    F64PromoteF32(instr) => self.execute_f64_promote_f32(instr),
+    F32ConvertI32S(instr) => self.execute_f32_convert_i32_s(instr),
+    F32ConvertI32U(instr) => self.execute_f32_convert_i32_u(instr),
+    F32ConvertI64S(instr) => self.execute_f32_convert_i64_s(instr),
+    F32ConvertI64U(instr) => self.execute_f32_convert_i64_u(instr),
+    F64ConvertI32S(instr) => self.execute_f64_convert_i32_s(instr),
+    F64ConvertI32U(instr) => self.execute_f64_convert_i32_u(instr),
+    F64ConvertI64S(instr) => self.execute_f64_convert_i64_s(instr),
+    F64ConvertI64U(instr) => self.execute_f64_convert_i64_u(instr),
+}
 
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Returns the [`Register`] value.
     fn get_register(&self, register: Register) -> UntypedValue {
         // Safety: TODO
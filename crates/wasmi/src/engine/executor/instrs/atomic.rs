@@ -0,0 +1,456 @@
+use super::Executor;
+use crate::{
+    core::{TrapCode, UntypedValue},
+    engine::{
+        bytecode::{AtomicRmwInstr, Instruction, LoadInstr, Register, StoreInstr},
+        cache::DefaultMemoryBytes,
+        code_map::InstructionPtr,
+    },
+    Error,
+};
+use core::{
+    sync::atomic::{self, Ordering},
+    time::Duration,
+};
+
+/// Host integer types accessed by Wasm atomic operations.
+///
+/// # Note
+///
+/// - All accesses are little-endian as required by Wasm.
+/// - Accesses are performed via host atomic operations if the host address
+///   is aligned. Otherwise the linear memory cannot be shared and thus
+///   non-atomic host operations are sufficient.
+trait AtomicAccess: Copy + Eq {
+    /// Creates `Self` from the wrapped `value`.
+    fn from_untyped(value: UntypedValue) -> Self;
+
+    /// Converts `self` into a zero-extended [`UntypedValue`].
+    fn into_untyped(self) -> UntypedValue;
+
+    /// Atomically loads the value at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes of `size_of::<Self>()` bytes.
+    unsafe fn load(ptr: *mut u8) -> Self;
+
+    /// Atomically stores `value` at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes of `size_of::<Self>()` bytes.
+    unsafe fn store(ptr: *mut u8, value: Self);
+
+    /// Atomically replaces the value at `ptr` with `f(old)` and returns `old`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes of `size_of::<Self>()` bytes.
+    unsafe fn update(ptr: *mut u8, f: impl FnMut(Self) -> Self) -> Self;
+
+    /// Atomically replaces the value at `ptr` with `replacement` if it equals `expected`.
+    ///
+    /// Returns the value at `ptr` before the operation.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and writes of `size_of::<Self>()` bytes.
+    unsafe fn compare_exchange(ptr: *mut u8, expected: Self, replacement: Self) -> Self;
+}
+
+macro_rules! impl_atomic_access {
+    ( $( ($ty:ty, $atomic:ident, $bits:literal) ),* $(,)? ) => {
+        $(
+            impl AtomicAccess for $ty {
+                fn from_untyped(value: UntypedValue) -> Self {
+                    // Note: `u64` conversion followed by `as` wraps the value.
+                    u64::from(value) as $ty
+                }
+
+                fn into_untyped(self) -> UntypedValue {
+                    UntypedValue::from(u64::from(self))
+                }
+
+                unsafe fn load(ptr: *mut u8) -> Self {
+                    #[cfg(target_has_atomic = $bits)]
+                    if is_aligned::<Self>(ptr) {
+                        let atomic = &*ptr.cast::<atomic::$atomic>();
+                        return <$ty>::from_le(atomic.load(Ordering::SeqCst));
+                    }
+                    <$ty>::from_le(ptr.cast::<$ty>().read_unaligned())
+                }
+
+                unsafe fn store(ptr: *mut u8, value: Self) {
+                    #[cfg(target_has_atomic = $bits)]
+                    if is_aligned::<Self>(ptr) {
+                        let atomic = &*ptr.cast::<atomic::$atomic>();
+                        return atomic.store(value.to_le(), Ordering::SeqCst);
+                    }
+                    ptr.cast::<$ty>().write_unaligned(value.to_le())
+                }
+
+                unsafe fn update(ptr: *mut u8, mut f: impl FnMut(Self) -> Self) -> Self {
+                    #[cfg(target_has_atomic = $bits)]
+                    if is_aligned::<Self>(ptr) {
+                        let atomic = &*ptr.cast::<atomic::$atomic>();
+                        let old = atomic
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |old| {
+                                Some(f(<$ty>::from_le(old)).to_le())
+                            })
+                            .unwrap_or_else(|old| old);
+                        return <$ty>::from_le(old);
+                    }
+                    let old = Self::load(ptr);
+                    Self::store(ptr, f(old));
+                    old
+                }
+
+                unsafe fn compare_exchange(ptr: *mut u8, expected: Self, replacement: Self) -> Self {
+                    #[cfg(target_has_atomic = $bits)]
+                    if is_aligned::<Self>(ptr) {
+                        let atomic = &*ptr.cast::<atomic::$atomic>();
+                        let old = atomic
+                            .compare_exchange(
+                                expected.to_le(),
+                                replacement.to_le(),
+                                Ordering::SeqCst,
+                                Ordering::SeqCst,
+                            )
+                            .unwrap_or_else(|old| old);
+                        return <$ty>::from_le(old);
+                    }
+                    let old = Self::load(ptr);
+                    if old == expected {
+                        Self::store(ptr, replacement);
+                    }
+                    old
+                }
+            }
+        )*
+    };
+}
+impl_atomic_access! {
+    (u8, AtomicU8, "8"),
+    (u16, AtomicU16, "16"),
+    (u32, AtomicU32, "32"),
+    (u64, AtomicU64, "64"),
+}
+
+/// Returns `true` if `ptr` is aligned for host accesses of type `T`.
+#[allow(dead_code)] // unused on targets without the respective atomics
+fn is_aligned<T>(ptr: *mut u8) -> bool {
+    (ptr as usize).is_multiple_of(core::mem::size_of::<T>())
+}
+
+/// Returns a pointer to the bytes accessed by a Wasm atomic operation.
+///
+/// # Note
+///
+/// `memory` points to the first of `len` bytes of a linear memory.
+///
+/// # Errors
+///
+/// - If the effective address is not naturally aligned for `T`.
+/// - If the accessed bytes are out of bounds for `memory`.
+fn atomic_access_ptr<T: AtomicAccess>(
+    memory: *mut u8,
+    len: usize,
+    address: u32,
+) -> Result<*mut u8, TrapCode> {
+    let size = core::mem::size_of::<T>();
+    let address = address as usize;
+    if !address.is_multiple_of(size) {
+        return Err(TrapCode::UnalignedAtomic);
+    }
+    match address.checked_add(size) {
+        Some(end) if end <= len => Ok(memory.wrapping_add(address)),
+        _ => Err(TrapCode::MemoryOutOfBounds),
+    }
+}
+
+/// Returns the effective address of a Wasm atomic operation.
+///
+/// # Errors
+///
+/// If the effective address overflows.
+fn effective_address(address: UntypedValue, offset: u32) -> Result<u32, TrapCode> {
+    u32::from(address)
+        .checked_add(offset)
+        .ok_or(TrapCode::MemoryOutOfBounds)
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Returns the [`Instruction::Register`] parameter for an [`Instruction`].
+    fn fetch_atomic_param(&self, offset: usize) -> Register {
+        let mut addr: InstructionPtr = self.ip;
+        addr.add(offset);
+        match *addr.get() {
+            Instruction::Register(register) => register,
+            _ => unreachable!("expected an Instruction::Register instruction word"),
+        }
+    }
+
+    /// Returns a pointer to the bytes of the default linear memory accessed by a Wasm atomic operation.
+    ///
+    /// # Errors
+    ///
+    /// - If the effective address is not naturally aligned for `T`.
+    /// - If the accessed bytes are out of bounds.
+    fn atomic_access<T: AtomicAccess>(&mut self, address: u32) -> Result<*mut u8, TrapCode> {
+        match self.cache.default_memory_bytes(self.ctx) {
+            DefaultMemoryBytes::Unshared(memory) => {
+                atomic_access_ptr::<T>(memory.as_mut_ptr(), memory.len(), address)
+            }
+            DefaultMemoryBytes::Shared(shared) => {
+                atomic_access_ptr::<T>(shared.data_ptr(), shared.data_size(), address)
+            }
+        }
+    }
+
+    /// Executes an [`Instruction::AtomicFence`].
+    #[inline(always)]
+    pub fn execute_atomic_fence(&mut self) {
+        atomic::fence(Ordering::SeqCst);
+        self.next_instr()
+    }
+
+    /// Executes a generic Wasm `atomic.load` [`Instruction`].
+    fn execute_atomic_load<T: AtomicAccess>(&mut self, instr: LoadInstr) -> Result<(), Error> {
+        let offset = self.fetch_address_offset(1);
+        let address = effective_address(self.get_register(instr.ptr), offset)?;
+        let ptr = self.atomic_access::<T>(address)?;
+        // Safety: `ptr` points to `size_of::<T>()` bytes of the default linear memory.
+        let value = unsafe { T::load(ptr) };
+        self.set_register(instr.result, value.into_untyped());
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes a generic Wasm `atomic.store` [`Instruction`].
+    fn execute_atomic_store<T: AtomicAccess>(&mut self, instr: StoreInstr) -> Result<(), Error> {
+        let value = T::from_untyped(self.get_register(self.fetch_atomic_param(1)));
+        let address = effective_address(self.get_register(instr.ptr), u32::from(instr.offset))?;
+        let ptr = self.atomic_access::<T>(address)?;
+        // Safety: `ptr` points to `size_of::<T>()` bytes of the default linear memory.
+        unsafe { T::store(ptr, value) };
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes a generic Wasm `atomic.rmw` [`Instruction`].
+    fn execute_atomic_rmw<T: AtomicAccess>(
+        &mut self,
+        instr: AtomicRmwInstr,
+        op: fn(T, T) -> T,
+    ) -> Result<(), Error> {
+        let offset = self.fetch_address_offset(1);
+        let address = effective_address(self.get_register(instr.ptr), offset)?;
+        let value = T::from_untyped(self.get_register(instr.value));
+        let ptr = self.atomic_access::<T>(address)?;
+        // Safety: `ptr` points to `size_of::<T>()` bytes of the default linear memory.
+        let old = unsafe { T::update(ptr, |old| op(old, value)) };
+        self.set_register(instr.result, old.into_untyped());
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes a generic Wasm `atomic.rmw.cmpxchg` [`Instruction`].
+    fn execute_atomic_cmpxchg<T: AtomicAccess>(
+        &mut self,
+        instr: AtomicRmwInstr,
+    ) -> Result<(), Error> {
+        let replacement = self.fetch_atomic_param(1);
+        let offset = self.fetch_address_offset(2);
+        let address = effective_address(self.get_register(instr.ptr), offset)?;
+        let expected = T::from_untyped(self.get_register(instr.value));
+        let replacement = T::from_untyped(self.get_register(replacement));
+        let ptr = self.atomic_access::<T>(address)?;
+        // Safety: `ptr` points to `size_of::<T>()` bytes of the default linear memory.
+        let old = unsafe { T::compare_exchange(ptr, expected, replacement) };
+        self.set_register(instr.result, old.into_untyped());
+        self.try_next_instr_at(3)
+    }
+
+    /// Executes a generic Wasm `memory.atomic.wait` [`Instruction`].
+    fn execute_memory_atomic_wait<T: AtomicAccess>(
+        &mut self,
+        instr: AtomicRmwInstr,
+    ) -> Result<(), Error> {
+        let timeout = self.fetch_atomic_param(1);
+        let offset = self.fetch_address_offset(2);
+        let address = effective_address(self.get_register(instr.ptr), offset)?;
+        let expected = T::from_untyped(self.get_register(instr.value));
+        // Note: A negative timeout waits indefinitely.
+        let timeout = u64::try_from(i64::from(self.get_register(timeout)))
+            .ok()
+            .map(Duration::from_nanos);
+        let ptr = self.atomic_access::<T>(address)?;
+        let memory = self.cache.default_memory(self.ctx);
+        let Some(shared) = self.ctx.resolve_memory(memory).shared().cloned() else {
+            return Err(TrapCode::AtomicWaitNonSharedMemory.into());
+        };
+        // Safety: `ptr` points to `size_of::<T>()` bytes of the shared linear
+        //         memory which never moves and is kept alive by `shared`.
+        let result = shared.wait(
+            address,
+            &mut || unsafe { T::load(ptr) } == expected,
+            timeout,
+        );
+        self.set_register(instr.result, result);
+        self.try_next_instr_at(3)
+    }
+
+    /// Executes an [`Instruction::MemoryAtomicWait32`].
+    #[inline(always)]
+    pub fn execute_memory_atomic_wait32(&mut self, instr: AtomicRmwInstr) -> Result<(), Error> {
+        self.execute_memory_atomic_wait::<u32>(instr)
+    }
+
+    /// Executes an [`Instruction::MemoryAtomicWait64`].
+    #[inline(always)]
+    pub fn execute_memory_atomic_wait64(&mut self, instr: AtomicRmwInstr) -> Result<(), Error> {
+        self.execute_memory_atomic_wait::<u64>(instr)
+    }
+
+    /// Executes an [`Instruction::MemoryAtomicNotify`].
+    ///
+    /// # Note
+    ///
+    /// Notifying an unshared linear memory never wakes up any waiters.
+    pub fn execute_memory_atomic_notify(&mut self, instr: AtomicRmwInstr) -> Result<(), Error> {
+        let offset = self.fetch_address_offset(1);
+        let address = effective_address(self.get_register(instr.ptr), offset)?;
+        let count = u32::from(self.get_register(instr.value));
+        self.atomic_access::<u32>(address)?;
+        let memory = self.cache.default_memory(self.ctx);
+        let woken = match self.ctx.resolve_memory(memory).shared() {
+            Some(shared) => shared.notify(address, count),
+            None => 0,
+        };
+        self.set_register(instr.result, woken);
+        self.try_next_instr_at(2)
+    }
+}
+
+macro_rules! impl_execute_atomic_load {
+    ( $( (Instruction::$var:ident, $fn:ident, $ty:ty) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var), "`].")]
+            #[inline(always)]
+            pub fn $fn(&mut self, instr: LoadInstr) -> Result<(), Error> {
+                self.execute_atomic_load::<$ty>(instr)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_execute_atomic_store {
+    ( $( (Instruction::$var:ident, $fn:ident, $ty:ty) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var), "`].")]
+            #[inline(always)]
+            pub fn $fn(&mut self, instr: StoreInstr) -> Result<(), Error> {
+                self.execute_atomic_store::<$ty>(instr)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_execute_atomic_rmw {
+    ( $( (Instruction::$var:ident, $fn:ident, $ty:ty, $op:expr) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var), "`].")]
+            #[inline(always)]
+            pub fn $fn(&mut self, instr: AtomicRmwInstr) -> Result<(), Error> {
+                self.execute_atomic_rmw::<$ty>(instr, $op)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_execute_atomic_cmpxchg {
+    ( $( (Instruction::$var:ident, $fn:ident, $ty:ty) ),* $(,)? ) => {
+        $(
+            #[doc = concat!("Executes an [`Instruction::", stringify!($var), "`].")]
+            #[inline(always)]
+            pub fn $fn(&mut self, instr: AtomicRmwInstr) -> Result<(), Error> {
+                self.execute_atomic_cmpxchg::<$ty>(instr)
+            }
+        )*
+    };
+}
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    impl_execute_atomic_load! {
+        (Instruction::I32AtomicLoad, execute_i32_atomic_load, u32),
+        (Instruction::I64AtomicLoad, execute_i64_atomic_load, u64),
+        (Instruction::I32AtomicLoad8u, execute_i32_atomic_load8_u, u8),
+        (Instruction::I32AtomicLoad16u, execute_i32_atomic_load16_u, u16),
+        (Instruction::I64AtomicLoad8u, execute_i64_atomic_load8_u, u8),
+        (Instruction::I64AtomicLoad16u, execute_i64_atomic_load16_u, u16),
+        (Instruction::I64AtomicLoad32u, execute_i64_atomic_load32_u, u32),
+    }
+
+    impl_execute_atomic_store! {
+        (Instruction::I32AtomicStore, execute_i32_atomic_store, u32),
+        (Instruction::I64AtomicStore, execute_i64_atomic_store, u64),
+        (Instruction::I32AtomicStore8, execute_i32_atomic_store8, u8),
+        (Instruction::I32AtomicStore16, execute_i32_atomic_store16, u16),
+        (Instruction::I64AtomicStore8, execute_i64_atomic_store8, u8),
+        (Instruction::I64AtomicStore16, execute_i64_atomic_store16, u16),
+        (Instruction::I64AtomicStore32, execute_i64_atomic_store32, u32),
+    }
+
+    impl_execute_atomic_rmw! {
+        (Instruction::I32AtomicRmwAdd, execute_i32_atomic_rmw_add, u32, u32::wrapping_add),
+        (Instruction::I64AtomicRmwAdd, execute_i64_atomic_rmw_add, u64, u64::wrapping_add),
+        (Instruction::I32AtomicRmw8AddU, execute_i32_atomic_rmw8_add_u, u8, u8::wrapping_add),
+        (Instruction::I32AtomicRmw16AddU, execute_i32_atomic_rmw16_add_u, u16, u16::wrapping_add),
+        (Instruction::I64AtomicRmw8AddU, execute_i64_atomic_rmw8_add_u, u8, u8::wrapping_add),
+        (Instruction::I64AtomicRmw16AddU, execute_i64_atomic_rmw16_add_u, u16, u16::wrapping_add),
+        (Instruction::I64AtomicRmw32AddU, execute_i64_atomic_rmw32_add_u, u32, u32::wrapping_add),
+        (Instruction::I32AtomicRmwSub, execute_i32_atomic_rmw_sub, u32, u32::wrapping_sub),
+        (Instruction::I64AtomicRmwSub, execute_i64_atomic_rmw_sub, u64, u64::wrapping_sub),
+        (Instruction::I32AtomicRmw8SubU, execute_i32_atomic_rmw8_sub_u, u8, u8::wrapping_sub),
+        (Instruction::I32AtomicRmw16SubU, execute_i32_atomic_rmw16_sub_u, u16, u16::wrapping_sub),
+        (Instruction::I64AtomicRmw8SubU, execute_i64_atomic_rmw8_sub_u, u8, u8::wrapping_sub),
+        (Instruction::I64AtomicRmw16SubU, execute_i64_atomic_rmw16_sub_u, u16, u16::wrapping_sub),
+        (Instruction::I64AtomicRmw32SubU, execute_i64_atomic_rmw32_sub_u, u32, u32::wrapping_sub),
+        (Instruction::I32AtomicRmwAnd, execute_i32_atomic_rmw_and, u32, |lhs: u32, rhs| lhs & rhs),
+        (Instruction::I64AtomicRmwAnd, execute_i64_atomic_rmw_and, u64, |lhs: u64, rhs| lhs & rhs),
+        (Instruction::I32AtomicRmw8AndU, execute_i32_atomic_rmw8_and_u, u8, |lhs: u8, rhs| lhs & rhs),
+        (Instruction::I32AtomicRmw16AndU, execute_i32_atomic_rmw16_and_u, u16, |lhs: u16, rhs| lhs & rhs),
+        (Instruction::I64AtomicRmw8AndU, execute_i64_atomic_rmw8_and_u, u8, |lhs: u8, rhs| lhs & rhs),
+        (Instruction::I64AtomicRmw16AndU, execute_i64_atomic_rmw16_and_u, u16, |lhs: u16, rhs| lhs & rhs),
+        (Instruction::I64AtomicRmw32AndU, execute_i64_atomic_rmw32_and_u, u32, |lhs: u32, rhs| lhs & rhs),
+        (Instruction::I32AtomicRmwOr, execute_i32_atomic_rmw_or, u32, |lhs: u32, rhs| lhs | rhs),
+        (Instruction::I64AtomicRmwOr, execute_i64_atomic_rmw_or, u64, |lhs: u64, rhs| lhs | rhs),
+        (Instruction::I32AtomicRmw8OrU, execute_i32_atomic_rmw8_or_u, u8, |lhs: u8, rhs| lhs | rhs),
+        (Instruction::I32AtomicRmw16OrU, execute_i32_atomic_rmw16_or_u, u16, |lhs: u16, rhs| lhs | rhs),
+        (Instruction::I64AtomicRmw8OrU, execute_i64_atomic_rmw8_or_u, u8, |lhs: u8, rhs| lhs | rhs),
+        (Instruction::I64AtomicRmw16OrU, execute_i64_atomic_rmw16_or_u, u16, |lhs: u16, rhs| lhs | rhs),
+        (Instruction::I64AtomicRmw32OrU, execute_i64_atomic_rmw32_or_u, u32, |lhs: u32, rhs| lhs | rhs),
+        (Instruction::I32AtomicRmwXor, execute_i32_atomic_rmw_xor, u32, |lhs: u32, rhs| lhs ^ rhs),
+        (Instruction::I64AtomicRmwXor, execute_i64_atomic_rmw_xor, u64, |lhs: u64, rhs| lhs ^ rhs),
+        (Instruction::I32AtomicRmw8XorU, execute_i32_atomic_rmw8_xor_u, u8, |lhs: u8, rhs| lhs ^ rhs),
+        (Instruction::I32AtomicRmw16XorU, execute_i32_atomic_rmw16_xor_u, u16, |lhs: u16, rhs| lhs ^ rhs),
+        (Instruction::I64AtomicRmw8XorU, execute_i64_atomic_rmw8_xor_u, u8, |lhs: u8, rhs| lhs ^ rhs),
+        (Instruction::I64AtomicRmw16XorU, execute_i64_atomic_rmw16_xor_u, u16, |lhs: u16, rhs| lhs ^ rhs),
+        (Instruction::I64AtomicRmw32XorU, execute_i64_atomic_rmw32_xor_u, u32, |lhs: u32, rhs| lhs ^ rhs),
+        (Instruction::I32AtomicRmwXchg, execute_i32_atomic_rmw_xchg, u32, |_: u32, new| new),
+        (Instruction::I64AtomicRmwXchg, execute_i64_atomic_rmw_xchg, u64, |_: u64, new| new),
+        (Instruction::I32AtomicRmw8XchgU, execute_i32_atomic_rmw8_xchg_u, u8, |_: u8, new| new),
+        (Instruction::I32AtomicRmw16XchgU, execute_i32_atomic_rmw16_xchg_u, u16, |_: u16, new| new),
+        (Instruction::I64AtomicRmw8XchgU, execute_i64_atomic_rmw8_xchg_u, u8, |_: u8, new| new),
+        (Instruction::I64AtomicRmw16XchgU, execute_i64_atomic_rmw16_xchg_u, u16, |_: u16, new| new),
+        (Instruction::I64AtomicRmw32XchgU, execute_i64_atomic_rmw32_xchg_u, u32, |_: u32, new| new),
+    }
+
+    impl_execute_atomic_cmpxchg! {
+        (Instruction::I32AtomicRmwCmpxchg, execute_i32_atomic_rmw_cmpxchg, u32),
+        (Instruction::I64AtomicRmwCmpxchg, execute_i64_atomic_rmw_cmpxchg, u64),
+        (Instruction::I32AtomicRmw8CmpxchgU, execute_i32_atomic_rmw8_cmpxchg_u, u8),
+        (Instruction::I32AtomicRmw16CmpxchgU, execute_i32_atomic_rmw16_cmpxchg_u, u16),
+        (Instruction::I64AtomicRmw8CmpxchgU, execute_i64_atomic_rmw8_cmpxchg_u, u8),
+        (Instruction::I64AtomicRmw16CmpxchgU, execute_i64_atomic_rmw16_cmpxchg_u, u16),
+        (Instruction::I64AtomicRmw32CmpxchgU, execute_i64_atomic_rmw32_cmpxchg_u, u32),
+    }
+}
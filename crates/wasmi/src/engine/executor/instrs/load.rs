@@ -1,17 +1,46 @@
 use super::Executor;
 use crate::{
     core::{TrapCode, UntypedValue},
-    engine::bytecode::{LoadAtInstr, LoadInstr, LoadOffset16Instr, Register},
+    engine::{
+        bytecode::{Instruction, LoadAtInstr, LoadInstr, LoadOffset16Instr, Register},
+        cache::DefaultMemoryBytes,
+        code_map::InstructionPtr,
+    },
     Error,
+    SharedMemory,
 };
 
-#[cfg(doc)]
-use crate::engine::bytecode::Instruction;
-
 /// The function signature of Wasm load operations.
 type WasmLoadOp =
     fn(memory: &[u8], address: UntypedValue, offset: u32) -> Result<UntypedValue, TrapCode>;
 
+/// Returns the value loaded by a generic Wasm `load[N_{s|u}]` operation from a `shared` linear memory.
+///
+/// # Note
+///
+/// The accessed bytes are copied into a local buffer using relaxed atomic loads
+/// since other threads may access them concurrently.
+#[inline]
+fn load_extend_shared(
+    shared: &SharedMemory,
+    address: UntypedValue,
+    offset: u32,
+    load_extend: WasmLoadOp,
+) -> Result<UntypedValue, TrapCode> {
+    let address = u32::from(address)
+        .checked_add(offset)
+        .ok_or(TrapCode::MemoryOutOfBounds)? as usize;
+    // Note: Wasm load operations access at most 8 bytes. The buffer is truncated
+    //       to the in bounds bytes so that `load_extend` reports out of bounds accesses.
+    let mut buffer = [0x00_u8; 8];
+    let len = shared.data_size().saturating_sub(address).min(buffer.len());
+    let buffer = &mut buffer[..len];
+    shared
+        .read(address, buffer)
+        .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+    load_extend(buffer, UntypedValue::from(0u32), 0)
+}
+
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Executes a generic Wasm `store[N_{s|u}]` operation.
     ///
@@ -34,12 +63,28 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         offset: u32,
         load_extend: WasmLoadOp,
     ) -> Result<(), Error> {
-        let memory = self.cache.default_memory_bytes(self.ctx);
-        let loaded_value = load_extend(memory, address, offset)?;
+        let loaded_value = self.load_extend(address, offset, load_extend)?;
         self.set_register(result, loaded_value);
         Ok(())
     }
 
+    /// Returns the value loaded by a generic Wasm `load[N_{s|u}]` operation.
+    #[inline(always)]
+    fn load_extend(
+        &mut self,
+        address: UntypedValue,
+        offset: u32,
+        load_extend: WasmLoadOp,
+    ) -> Result<UntypedValue, Error> {
+        let loaded_value = match self.cache.default_memory_bytes(self.ctx) {
+            DefaultMemoryBytes::Unshared(memory) => load_extend(memory, address, offset)?,
+            DefaultMemoryBytes::Shared(shared) => {
+                load_extend_shared(shared, address, offset, load_extend)?
+            }
+        };
+        Ok(loaded_value)
+    }
+
     /// Executes a generic `load` [`Instruction`].
     fn execute_load_impl(
         &mut self,
@@ -197,3 +242,58 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ),
     }
 }
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Returns the [`Instruction::Register`] parameter for an [`Instruction`].
+    fn fetch_load_rhs(&self, offset: usize) -> Register {
+        let mut addr: InstructionPtr = self.ip;
+        addr.add(offset);
+        match *addr.get() {
+            Instruction::Register(register) => register,
+            _ => unreachable!("expected an Instruction::Register instruction word"),
+        }
+    }
+
+    /// Executes a fused `load` and `add` [`Instruction`] with the given `rhs`.
+    #[inline(always)]
+    fn execute_load_offset16_add_impl(
+        &mut self,
+        instr: LoadOffset16Instr,
+        rhs: UntypedValue,
+        load_extend: WasmLoadOp,
+        add: fn(UntypedValue, UntypedValue) -> UntypedValue,
+    ) -> Result<(), Error> {
+        let offset = u32::from(instr.offset);
+        let address = self.get_register(instr.ptr);
+        let lhs = self.load_extend(address, offset, load_extend)?;
+        self.set_register(instr.result, add(lhs, rhs));
+        self.try_next_instr_at(2)
+    }
+
+    /// Executes an [`Instruction::I32LoadOffset16Add`].
+    #[inline(always)]
+    pub fn execute_i32_load_offset16_add(&mut self, instr: LoadOffset16Instr) -> Result<(), Error> {
+        let rhs = self.get_register(self.fetch_load_rhs(1));
+        self.execute_load_offset16_add_impl(
+            instr,
+            rhs,
+            UntypedValue::i32_load,
+            UntypedValue::i32_add,
+        )
+    }
+
+    /// Executes an [`Instruction::I32LoadOffset16AddImm`].
+    #[inline(always)]
+    pub fn execute_i32_load_offset16_add_imm(
+        &mut self,
+        instr: LoadOffset16Instr,
+    ) -> Result<(), Error> {
+        let rhs = UntypedValue::from(i32::from(self.fetch_const32(1)));
+        self.execute_load_offset16_add_impl(
+            instr,
+            rhs,
+            UntypedValue::i32_load,
+            UntypedValue::i32_add,
+        )
+    }
+}
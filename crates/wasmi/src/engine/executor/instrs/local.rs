@@ -0,0 +1,25 @@
+use super::Executor;
+use crate::engine::bytecode::Register;
+
+#[cfg(doc)]
+use crate::engine::bytecode::Instruction;
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Executes an [`Instruction::LocalGetSpilled`].
+    #[inline(always)]
+    pub fn execute_local_get_spilled(&mut self, result: Register, slot: u32) {
+        // Safety: The translator only encodes slots within the call frame of the function.
+        let value = unsafe { self.sp.get_spilled(slot) };
+        self.set_register(result, value);
+        self.next_instr()
+    }
+
+    /// Executes an [`Instruction::LocalSetSpilled`].
+    #[inline(always)]
+    pub fn execute_local_set_spilled(&mut self, slot: u32, value: Register) {
+        let value = self.get_register(value);
+        // Safety: The translator only encodes slots within the call frame of the function.
+        unsafe { self.sp.set_spilled(slot, value) };
+        self.next_instr()
+    }
+}
@@ -229,6 +229,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let dst_index = dst_index as usize;
         let default_memory = self.cache.default_memory(self.ctx);
         let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
+        if let Some(shared) = memory.shared() {
+            shared
+                .check_bounds(src_index, len as usize)
+                .and_then(|_| shared.check_bounds(dst_index, len as usize))
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
+            shared
+                .copy_within(src_index, dst_index, len as usize)
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            return self.try_next_instr();
+        }
         let data = memory.data_mut();
         // These accesses just perform the bounds checks required by the Wasm spec.
         data.get(src_index..)
@@ -356,6 +367,16 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let len = len as usize;
         let default_memory = self.cache.default_memory(self.ctx);
         let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
+        if let Some(shared) = memory.shared() {
+            shared
+                .check_bounds(dst, len)
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+            shared
+                .fill(dst, value, len)
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            return self.try_next_instr();
+        }
         let memory = memory
             .data_mut()
             .get_mut(dst..)
@@ -485,14 +506,25 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let len = len as usize;
         let data_index: DataSegmentIdx = self.fetch_data_segment_index(1);
         let (memory, data, fuel) = self.cache.get_memory_init_triplet(self.ctx, data_index);
-        let memory = memory
-            .get_mut(dst_index..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
         let data = data
             .get(src_index..)
             .and_then(|data| data.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
+        if let Some(shared) = memory.shared() {
+            shared
+                .check_bounds(dst_index, len)
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+            shared
+                .write(dst_index, data)
+                .map_err(|_| TrapCode::MemoryOutOfBounds)?;
+            return self.try_next_instr_at(2);
+        }
+        let memory = memory
+            .data_mut()
+            .get_mut(dst_index..)
+            .and_then(|memory| memory.get_mut(..len))
+            .ok_or(TrapCode::MemoryOutOfBounds)?;
         fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
         memory.copy_from_slice(data);
         self.try_next_instr_at(2)
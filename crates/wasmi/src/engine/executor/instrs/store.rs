@@ -3,18 +3,63 @@ use crate::{
     core::{TrapCode, UntypedValue},
     engine::{
         bytecode::{Const16, Instruction, Register, StoreAtInstr, StoreInstr, StoreOffset16Instr},
+        cache::DefaultMemoryBytes,
         code_map::InstructionPtr,
     },
     Error,
+    SharedMemory,
 };
 
-/// The function signature of Wasm store operations.
-type WasmStoreOp = fn(
-    memory: &mut [u8],
+/// A Wasm store operation.
+#[derive(Copy, Clone)]
+struct WasmStoreOp {
+    /// Stores the wrapped value to the linear memory.
+    store: fn(
+        memory: &mut [u8],
+        address: UntypedValue,
+        offset: u32,
+        value: UntypedValue,
+    ) -> Result<(), TrapCode>,
+    /// The number of bytes written by `store`.
+    len: usize,
+}
+
+impl WasmStoreOp {
+    /// Creates a new [`WasmStoreOp`] that stores values of type `T`.
+    fn new<T>(
+        store: fn(&mut [u8], UntypedValue, u32, UntypedValue) -> Result<(), TrapCode>,
+    ) -> Self {
+        Self {
+            store,
+            len: core::mem::size_of::<T>(),
+        }
+    }
+}
+
+/// Executes a generic Wasm `store[N]` operation on a `shared` linear memory.
+///
+/// # Note
+///
+/// The stored bytes are encoded into a local buffer first and then written
+/// using relaxed atomic stores since other threads may access them concurrently.
+#[inline]
+fn store_wrap_shared(
+    shared: &SharedMemory,
     address: UntypedValue,
     offset: u32,
     value: UntypedValue,
-) -> Result<(), TrapCode>;
+    store_wrap: WasmStoreOp,
+) -> Result<(), TrapCode> {
+    let address = u32::from(address)
+        .checked_add(offset)
+        .ok_or(TrapCode::MemoryOutOfBounds)? as usize;
+    let mut buffer = [0x00_u8; 8];
+    let buffer = &mut buffer[..store_wrap.len];
+    (store_wrap.store)(buffer, UntypedValue::from(0u32), 0, value)?;
+    shared
+        .write(address, buffer)
+        .map_err(|_| TrapCode::MemoryOutOfBounds)
+}
 
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Returns the [`Instruction::Register`] parameter for an [`Instruction`].
@@ -45,8 +90,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         value: UntypedValue,
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
-        let memory = self.cache.default_memory_bytes(self.ctx);
-        store_wrap(memory, address, offset, value)?;
+        match self.cache.default_memory_bytes(self.ctx) {
+            DefaultMemoryBytes::Unshared(memory) => {
+                (store_wrap.store)(memory, address, offset, value)?
+            }
+            DefaultMemoryBytes::Shared(shared) => {
+                store_wrap_shared(shared, address, offset, value, store_wrap)?
+            }
+        }
         Ok(())
     }
 
@@ -140,7 +191,7 @@ macro_rules! impl_execute_istore {
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, WasmStoreOp::new::<$to_ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -149,7 +200,7 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, WasmStoreOp::new::<$to_ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16_imm16), "`].")]
@@ -158,13 +209,13 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreOffset16Instr<$from_ty>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16_imm16::<$to_ty, _>(instr, $impl_fn)
+                self.execute_store_offset16_imm16::<$to_ty, _>(instr, WasmStoreOp::new::<$to_ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, WasmStoreOp::new::<$to_ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at_imm16), "`].")]
@@ -173,7 +224,7 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreAtInstr<$from_ty>,
             ) -> Result<(), Error> {
-                self.execute_store_at_imm16::<$to_ty, _>(instr, $impl_fn)
+                self.execute_store_at_imm16::<$to_ty, _>(instr, WasmStoreOp::new::<$to_ty>($impl_fn))
             }
         )*
     };
@@ -249,6 +300,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
 macro_rules! impl_execute_fstore {
     ( $(
         (
+            ($ty:ty),
             (Instruction::$var_store:ident, $fn_store:ident),
             (Instruction::$var_store_off16:ident, $fn_store_off16:ident),
             (Instruction::$var_store_at:ident, $fn_store_at:ident),
@@ -259,7 +311,7 @@ macro_rules! impl_execute_fstore {
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, WasmStoreOp::new::<$ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -268,13 +320,13 @@ macro_rules! impl_execute_fstore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, WasmStoreOp::new::<$ty>($impl_fn))
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, WasmStoreOp::new::<$ty>($impl_fn))
             }
         )*
     }
@@ -283,12 +335,14 @@ macro_rules! impl_execute_fstore {
 impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     impl_execute_fstore! {
         (
+            (f32),
             (Instruction::F32Store, execute_f32_store),
             (Instruction::F32StoreOffset16, execute_f32_store_offset16),
             (Instruction::F32StoreAt, execute_f32_store_at),
             UntypedValue::f32_store,
         ),
         (
+            (f64),
             (Instruction::F64Store, execute_f64_store),
             (Instruction::F64StoreOffset16, execute_f64_store_offset16),
             (Instruction::F64StoreAt, execute_f64_store_at),
@@ -296,3 +350,22 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         ),
     }
 }
+
+impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+    /// Executes an [`Instruction::I32StoreOffset16AddImm`].
+    #[inline(always)]
+    pub fn execute_i32_store_offset16_add_imm(
+        &mut self,
+        instr: StoreOffset16Instr<Register>,
+    ) -> Result<(), Error> {
+        let rhs = UntypedValue::from(i32::from(self.fetch_const32(1)));
+        let value = UntypedValue::i32_add(self.get_register(instr.value), rhs);
+        self.execute_store_wrap(
+            self.get_register(instr.ptr),
+            u32::from(instr.offset),
+            value,
+            WasmStoreOp::new::<i32>(UntypedValue::i32_store),
+        )?;
+        self.try_next_instr_at(2)
+    }
+}
@@ -497,15 +497,12 @@ impl<'engine> EngineExecutor<'engine> {
         let (store_inner, mut resource_limiter) = ctx.store.store_inner_and_resource_limiter_ref();
         let value_stack = &mut self.stack.values;
         let call_stack = &mut self.stack.calls;
-        let code_map = &self.res.code_map;
-        let func_types = &self.res.func_types;
         execute_instrs(
             store_inner,
             cache,
             value_stack,
             call_stack,
-            code_map,
-            func_types,
+            self.res,
             &mut resource_limiter,
         )
     }
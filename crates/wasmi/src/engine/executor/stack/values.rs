@@ -257,9 +257,11 @@ impl ValueStack {
         func: &CompiledFuncEntity,
     ) -> Result<(BaseValueStackOffset, FrameValueStackOffset), TrapCode> {
         let len_registers = func.len_registers();
-        self.reserve(len_registers as usize)?;
+        let len_spilled = func.len_spilled() as usize;
+        self.reserve(len_registers as usize + len_spilled)?;
         let frame_offset = FrameValueStackOffset(self.extend_slice(func.consts()));
-        let base_offset = BaseValueStackOffset(self.extend_zeros(func.len_cells() as usize));
+        let base_offset =
+            BaseValueStackOffset(self.extend_zeros(func.len_cells() as usize + len_spilled));
         Ok((base_offset, frame_offset))
     }
 
@@ -429,6 +431,26 @@ impl FrameRegisters {
         ptr::write(self.register_offset(register), value)
     }
 
+    /// Returns the [`UntypedValue`] of the spilled local variable at `slot`.
+    ///
+    /// # Safety
+    ///
+    /// It is the callers responsibility to provide a `slot` that
+    /// does not access the underlying [`ValueStack`] out of bounds.
+    pub unsafe fn get_spilled(&self, slot: u32) -> UntypedValue {
+        ptr::read(self.ptr.add(slot as usize))
+    }
+
+    /// Sets the value of the spilled local variable at `slot` to `value`.
+    ///
+    /// # Safety
+    ///
+    /// It is the callers responsibility to provide a `slot` that
+    /// does not access the underlying [`ValueStack`] out of bounds.
+    pub unsafe fn set_spilled(&mut self, slot: u32, value: UntypedValue) {
+        ptr::write(self.ptr.add(slot as usize), value)
+    }
+
     /// Returns the underlying pointer offset by the [`Register`] index.
     unsafe fn register_offset(&self, register: Register) -> *mut UntypedValue {
         unsafe { self.ptr.offset(register.to_i16() as isize) }
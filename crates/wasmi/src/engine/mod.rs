@@ -9,6 +9,8 @@ mod executor;
 mod func_args;
 mod func_types;
 mod limits;
+#[cfg(feature = "instr-profiling")]
+mod profile;
 mod resumable;
 mod traits;
 mod translator;
@@ -19,6 +21,8 @@ mod tests;
 #[cfg(test)]
 use self::bytecode::RegisterSpan;
 
+#[cfg(feature = "instr-profiling")]
+pub use self::profile::{InstrPair, InstrPairs, InstrProfile};
 pub(crate) use self::{
     block_type::BlockType,
     config::FuelCosts,
@@ -36,7 +40,7 @@ pub(crate) use self::{
 };
 pub use self::{
     code_map::CompiledFunc,
-    config::{CompilationMode, Config},
+    config::{CompilationMode, Config, MemoryBackend, OptLevel},
     limits::StackLimits,
     resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
     traits::{CallParams, CallResults},
@@ -52,6 +56,7 @@ use crate::{
     Error,
     Func,
     FuncType,
+    MemoryCreator,
     StoreContextMut,
 };
 use core::sync::atomic::{AtomicU32, Ordering};
@@ -63,7 +68,7 @@ use std::{
 use wasmi_arena::{ArenaIndex, GuardedEntity};
 use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
 
-#[cfg(test)]
+#[cfg(any(test, feature = "instr-profiling"))]
 use self::bytecode::Instruction;
 
 #[cfg(test)]
@@ -150,7 +155,18 @@ impl Engine {
     /// Users should ues [`Engine::default`] to construct a default [`Engine`].
     pub fn new(config: &Config) -> Self {
         Self {
-            inner: Arc::new(EngineInner::new(config)),
+            inner: Arc::new(EngineInner::new(config, None)),
+        }
+    }
+
+    /// Creates a new [`Engine`] that allocates linear memories via the [`MemoryCreator`].
+    ///
+    /// # Note
+    ///
+    /// The [`MemoryCreator`] takes precedence over the [`MemoryBackend`] of the [`Config`].
+    pub fn with_memory_creator(config: &Config, creator: Arc<dyn MemoryCreator>) -> Self {
+        Self {
+            inner: Arc::new(EngineInner::new(config, Some(creator))),
         }
     }
 
@@ -166,6 +182,39 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the [`MemoryCreator`] of the [`Engine`] if any.
+    pub(crate) fn memory_creator(&self) -> Option<&dyn MemoryCreator> {
+        self.inner.memory_creator.as_deref()
+    }
+
+    /// Returns a snapshot of the instruction pairs profiled by the [`Engine`] so far.
+    ///
+    /// # Note
+    ///
+    /// This is useful to find frequent instruction sequences that
+    /// are candidates for fusion into superinstructions.
+    #[cfg(feature = "instr-profiling")]
+    pub fn instr_profile(&self) -> InstrProfile {
+        self.inner.res.read().profile.lock().clone()
+    }
+
+    /// Clears the instruction pairs profiled by the [`Engine`] so far.
+    #[cfg(feature = "instr-profiling")]
+    pub fn reset_instr_profile(&self) {
+        *self.inner.res.read().profile.lock() = InstrProfile::default();
+    }
+
+    /// Records the adjacent instruction pairs of the translated `instrs`.
+    #[cfg(feature = "instr-profiling")]
+    pub(super) fn record_translated_instrs(&self, instrs: &[Instruction]) {
+        self.inner
+            .res
+            .read()
+            .profile
+            .lock()
+            .record_translated(instrs)
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
@@ -307,6 +356,20 @@ impl Engine {
             .init_lazy_func(func_idx, func, bytes, module, func_to_validate)
     }
 
+    /// Applies `f` to the [`CompiledFuncEntity`] of `func` if it has already been compiled.
+    ///
+    /// Returns `None` if `func` has not yet been compiled, e.g. due to lazy compilation.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    pub(super) fn resolve_compiled_func<F, R>(&self, func: CompiledFunc, f: F) -> Option<R>
+    where
+        F: FnOnce(&CompiledFuncEntity) -> R,
+    {
+        self.inner.resolve_compiled_func(func, f)
+    }
+
     /// Resolves the [`CompiledFunc`] to the underlying Wasmi bytecode instructions.
     ///
     /// # Note
@@ -476,6 +539,8 @@ impl Engine {
 pub struct EngineInner {
     /// The [`Config`] of the engine.
     config: Config,
+    /// The optional [`MemoryCreator`] used to allocate linear memories.
+    memory_creator: Option<Arc<dyn MemoryCreator>>,
     /// Engine resources shared across multiple engine executors.
     res: RwLock<EngineResources>,
     /// Reusable allocation stacks.
@@ -596,10 +661,11 @@ impl EngineStacks {
 }
 
 impl EngineInner {
-    /// Creates a new [`EngineInner`] with the given [`Config`].
-    fn new(config: &Config) -> Self {
+    /// Creates a new [`EngineInner`] with the given [`Config`] and optional [`MemoryCreator`].
+    fn new(config: &Config, memory_creator: Option<Arc<dyn MemoryCreator>>) -> Self {
         Self {
             config: *config,
+            memory_creator,
             res: RwLock::new(EngineResources::new()),
             allocs: Mutex::new(ReusableAllocationStack::default()),
             stacks: Mutex::new(EngineStacks::new(config)),
@@ -729,6 +795,20 @@ impl EngineInner {
             .init_lazy_func(func, func_idx, bytes, module, func_to_validate)
     }
 
+    /// Applies `f` to the [`CompiledFuncEntity`] of `func` if it has already been compiled.
+    ///
+    /// Returns `None` if `func` has not yet been compiled.
+    ///
+    /// # Panics
+    ///
+    /// If [`CompiledFunc`] is invalid for [`Engine`].
+    fn resolve_compiled_func<F, R>(&self, func: CompiledFunc, f: F) -> Option<R>
+    where
+        F: FnOnce(&CompiledFuncEntity) -> R,
+    {
+        self.res.read().code_map.get_compiled(func).map(f)
+    }
+
     /// Resolves the [`InternalFuncEntity`] for [`CompiledFunc`] and applies `f` to it.
     ///
     /// # Panics
@@ -806,6 +886,9 @@ pub struct EngineResources {
     /// The engine deduplicates function types to make the equality
     /// comparison very fast. This helps to speed up indirect calls.
     func_types: FuncTypeRegistry,
+    /// The adjacent instruction pairs recorded during translation and execution.
+    #[cfg(feature = "instr-profiling")]
+    profile: Mutex<InstrProfile>,
 }
 
 impl EngineResources {
@@ -815,6 +898,8 @@ impl EngineResources {
         Self {
             code_map: CodeMap::default(),
             func_types: FuncTypeRegistry::new(engine_idx),
+            #[cfg(feature = "instr-profiling")]
+            profile: Mutex::new(InstrProfile::default()),
         }
     }
 }
@@ -0,0 +1,197 @@
+//! Profiling of adjacent Wasmi bytecode [`Instruction`] pairs.
+//!
+//! # Note
+//!
+//! This is only available with the `instr-profiling` crate feature and is used
+//! to find out which [`Instruction`] pairs are worth fusing into superinstructions.
+
+use super::bytecode::Instruction;
+use core::mem::{discriminant, Discriminant};
+use spin::Mutex;
+use std::{
+    collections::HashMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// The key of an [`Instruction`] pair.
+type PairKey = (Discriminant<Instruction>, Discriminant<Instruction>);
+
+/// Counts the occurrences of adjacent [`Instruction`] pairs.
+#[derive(Debug, Default, Clone)]
+pub struct InstrPairs {
+    /// The number of occurrences and an example of each [`Instruction`] pair.
+    counts: HashMap<PairKey, (u64, Instruction, Instruction)>,
+}
+
+/// An adjacent pair of Wasmi bytecode instructions and how often it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstrPair {
+    /// The name of the first instruction of the pair.
+    pub first: String,
+    /// The name of the second instruction of the pair.
+    pub second: String,
+    /// The number of occurrences of the pair.
+    pub count: u64,
+}
+
+impl InstrPairs {
+    /// Records `count` occurrences of the `first` and `second` [`Instruction`] pair.
+    fn record(&mut self, first: Instruction, second: Instruction, count: u64) {
+        self.counts
+            .entry((discriminant(&first), discriminant(&second)))
+            .or_insert((0, first, second))
+            .0 += count;
+    }
+
+    /// Merges the counts of `other` into `self`.
+    fn merge(&mut self, other: &Self) {
+        for (count, first, second) in other.counts.values() {
+            self.record(*first, *second, *count);
+        }
+    }
+
+    /// Returns the total number of recorded instruction pairs.
+    pub fn total(&self) -> u64 {
+        self.counts.values().map(|(count, _, _)| count).sum()
+    }
+
+    /// Returns the `n` most frequent instruction pairs in descending order.
+    pub fn most_frequent(&self, n: usize) -> Vec<InstrPair> {
+        let mut pairs: Vec<InstrPair> = self
+            .counts
+            .values()
+            .map(|(count, first, second)| InstrPair {
+                first: instr_name(first),
+                second: instr_name(second),
+                count: *count,
+            })
+            .collect();
+        pairs.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.first.cmp(&b.first))
+                .then_with(|| a.second.cmp(&b.second))
+        });
+        pairs.truncate(n);
+        pairs
+    }
+}
+
+/// Returns the name of the variant of `instr`.
+fn instr_name(instr: &Instruction) -> String {
+    let name = std::format!("{instr:?}");
+    match name.find(|c: char| !c.is_alphanumeric()) {
+        Some(end) => name[..end].to_string(),
+        None => name,
+    }
+}
+
+/// Returns `true` if `instr` is an instruction word that is never dispatched.
+///
+/// # Note
+///
+/// Those instruction words only carry additional parameters of their preceding instruction.
+fn is_param_word(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::TableIdx(_)
+            | Instruction::DataSegmentIdx(_)
+            | Instruction::ElementSegmentIdx(_)
+            | Instruction::Const32(_)
+            | Instruction::I64Const32(_)
+            | Instruction::F64Const32(_)
+            | Instruction::Register(_)
+            | Instruction::Register2(_)
+            | Instruction::Register3(_)
+            | Instruction::RegisterList(_)
+            | Instruction::CallIndirectParams(_)
+            | Instruction::CallIndirectParamsImm16(_)
+    )
+}
+
+/// The adjacent instruction pairs recorded by an [`Engine`].
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug, Default, Clone)]
+pub struct InstrProfile {
+    /// Adjacent instructions of all translated functions.
+    translated: InstrPairs,
+    /// Consecutively executed instructions.
+    executed: InstrPairs,
+}
+
+impl InstrProfile {
+    /// Returns the adjacent instruction pairs of all translated functions.
+    ///
+    /// # Note
+    ///
+    /// Every pair is counted once per translated function that contains it.
+    pub fn translated(&self) -> &InstrPairs {
+        &self.translated
+    }
+
+    /// Returns the consecutively executed instruction pairs.
+    ///
+    /// # Note
+    ///
+    /// Every pair is counted each time it is executed.
+    pub fn executed(&self) -> &InstrPairs {
+        &self.executed
+    }
+
+    /// Records the adjacent instruction pairs of the translated `instrs`.
+    pub(crate) fn record_translated(&mut self, instrs: &[Instruction]) {
+        let mut instrs = instrs.iter().filter(|instr| !is_param_word(instr));
+        let Some(mut prev) = instrs.next() else {
+            return;
+        };
+        for instr in instrs {
+            self.translated.record(*prev, *instr, 1);
+            prev = instr;
+        }
+    }
+}
+
+/// Records consecutively executed instructions of a single Wasm execution.
+///
+/// # Note
+///
+/// Recording is done without synchronization and merged
+/// into the [`InstrProfile`] of the [`Engine`] upon drop.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct InstrRecorder<'engine> {
+    /// The [`InstrProfile`] into which the recorded pairs are merged.
+    profile: &'engine Mutex<InstrProfile>,
+    /// The previously executed [`Instruction`] if any.
+    prev: Option<Instruction>,
+    /// The recorded instruction pairs.
+    pairs: InstrPairs,
+}
+
+impl<'engine> InstrRecorder<'engine> {
+    /// Creates a new [`InstrRecorder`] merging into `profile`.
+    pub fn new(profile: &'engine Mutex<InstrProfile>) -> Self {
+        Self {
+            profile,
+            prev: None,
+            pairs: InstrPairs::default(),
+        }
+    }
+
+    /// Records the execution of `instr`.
+    #[inline(always)]
+    pub fn record(&mut self, instr: Instruction) {
+        if let Some(prev) = self.prev.replace(instr) {
+            self.pairs.record(prev, instr, 1);
+        }
+    }
+}
+
+impl Drop for InstrRecorder<'_> {
+    fn drop(&mut self) {
+        self.profile.lock().executed.merge(&self.pairs);
+    }
+}
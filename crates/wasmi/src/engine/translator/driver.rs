@@ -1,5 +1,6 @@
 use crate::{
-    engine::{code_map::CompiledFuncEntity, WasmTranslator},
+    engine::{code_map::CompiledFuncEntity, TranslationError, WasmTranslator},
+    errors::ErrorKind,
     Error,
 };
 use wasmparser::FunctionBody;
@@ -45,7 +46,15 @@ where
             return Ok(allocations);
         }
         self.translate_locals()?;
-        let offset = self.translate_operators()?;
+        let offset = match self.translate_operators() {
+            Err(error) if is_out_of_registers(&error) && self.translator.restart()? => {
+                // Note: The function ran out of registers for its expression stack
+                //       so we translate it again with more registers reserved for it.
+                self.translate_locals()?;
+                self.translate_operators()?
+            }
+            result => result?,
+        };
         let allocations = self.finish(offset, finalize)?;
         Ok(allocations)
     }
@@ -88,3 +97,11 @@ where
         Ok(reader.original_position())
     }
 }
+
+/// Returns `true` if `error` is caused by a function running out of registers.
+fn is_out_of_registers(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::AllocatedTooManyRegisters)
+    )
+}
@@ -0,0 +1,408 @@
+//! Inlining of small leaf functions at their `call` sites.
+//!
+//! # Note
+//!
+//! A `call` to an internal function pushes a new call frame, copies the call
+//! parameters into it and resets the instance cache. For tiny functions such as
+//! accessors this overhead dominates the execution time of the function itself.
+//! Therefore the [`FuncTranslator`] may instead place the Wasmi bytecode of
+//! the called function directly at the `call` site.
+//!
+//! The called function is given its own block of registers in the call frame
+//! of the calling function. All registers of the inlined [`Instruction`] are
+//! shifted into this block. Since the called function must neither branch nor
+//! return early its [`Instruction`] sequence can be inlined as a whole.
+//!
+//! Inlining does not change fuel consumption:
+//!
+//! - The `call` itself is charged as before.
+//! - The inlined [`Instruction::ConsumeFuel`] charges the called function as before.
+//! - Copying parameters and results is not charged since the `call` was not either.
+//! - Registers of inlined call frames are not charged for the calling function.
+
+use super::{visit_register::VisitInputRegisters, FuelInfo, FuncTranslator, TypedProvider};
+use crate::{
+    engine::{
+        bytecode::{Instruction, Register, RegisterSpan},
+        code_map::CompiledFuncEntity,
+        CompiledFunc,
+    },
+    module::ModuleHeader,
+    Error,
+    FuncType,
+};
+use std::{vec, vec::Vec};
+
+/// A function that is about to be inlined at a `call` site.
+#[derive(Debug)]
+struct InlinedFunc {
+    /// The [`Instruction`] sequence of the function without its final `return`.
+    body: Vec<Instruction>,
+    /// The final `return` [`Instruction`] of the function.
+    ret: Instruction,
+    /// The number of registers in the call frame of the function.
+    len_cells: u16,
+}
+
+impl InlinedFunc {
+    /// Returns the [`InlinedFunc`] for `func` if it can be inlined within `budget`.
+    ///
+    /// # Note
+    ///
+    /// The `budget` is the maximum number of [`Instruction`] of `func`
+    /// not counting [`Instruction::ConsumeFuel`].
+    fn new(func: &CompiledFuncEntity, budget: u32) -> Option<Self> {
+        if !func.consts().is_empty() {
+            // Note: Function local constants would need to be relocated
+            //       to the calling function which might alter fuel consumption.
+            return None;
+        }
+        if func.len_spilled() != 0 {
+            // Note: Spilled local variables are addressed relative to the call frame
+            //       and would need their own space in the frame of the calling function.
+            return None;
+        }
+        let (ret, body) = func.instrs().split_last()?;
+        if !matches!(
+            ret,
+            Instruction::Return
+                | Instruction::ReturnReg { .. }
+                | Instruction::ReturnImm32 { .. }
+                | Instruction::ReturnI64Imm32 { .. }
+                | Instruction::ReturnF64Imm32 { .. }
+        ) {
+            return None;
+        }
+        let len_instrs = body
+            .iter()
+            .filter(|instr| !matches!(instr, Instruction::ConsumeFuel(_)))
+            .count()
+            + 1;
+        if len_instrs > budget as usize {
+            return None;
+        }
+        if !body.iter().all(is_inlinable) {
+            return None;
+        }
+        Some(Self {
+            body: body.to_vec(),
+            ret: *ret,
+            len_cells: func.len_cells(),
+        })
+    }
+
+    /// Returns the registers of the [`InlinedFunc`] that may be read before they are written.
+    ///
+    /// # Note
+    ///
+    /// Those registers refer to local variables of the [`InlinedFunc`] which
+    /// are zero initialized upon a `call`. The first `len_params` registers
+    /// are initialized by the call parameters.
+    fn uninit_reads(
+        &self,
+        len_params: usize,
+        module: &ModuleHeader,
+    ) -> Result<Vec<Register>, Error> {
+        let mut written = vec![false; usize::from(self.len_cells)];
+        written[..len_params].fill(true);
+        let mut uninit = vec![false; usize::from(self.len_cells)];
+        // Note: The results of an instruction are written after all of its
+        //       parameter instruction words have been read.
+        let mut pending_writes = Vec::new();
+        let mut in_select_imm = false;
+        for instr in &self.body {
+            let mut instr = *instr;
+            let is_select_imm = is_select_imm(&instr);
+            let is_param = is_param_word(&instr) || (in_select_imm && is_select_imm);
+            if !is_param {
+                for register in pending_writes.drain(..) {
+                    mark(&mut written, register);
+                }
+            }
+            visit_reads(&mut instr, in_select_imm, |register| {
+                let Ok(index) = usize::try_from(register.to_i16()) else {
+                    return;
+                };
+                if !written[index] {
+                    uninit[index] = true;
+                }
+            });
+            visit_writes(&mut instr, in_select_imm, module, |register| {
+                pending_writes.push(register)
+            })?;
+            in_select_imm = is_select_imm && !in_select_imm;
+        }
+        let uninit = uninit
+            .iter()
+            .enumerate()
+            .filter(|(_, uninit)| **uninit)
+            .map(|(index, _)| Register::from_i16(index as i16))
+            .collect();
+        Ok(uninit)
+    }
+}
+
+/// Marks `register` as written.
+fn mark(written: &mut [bool], register: Register) {
+    if let Ok(index) = usize::try_from(register.to_i16()) {
+        written[index] = true;
+    }
+}
+
+/// Returns `true` if `instr` may be part of an inlined function body.
+///
+/// # Note
+///
+/// Only leaf functions without control flow are inlined.
+fn is_inlinable(instr: &Instruction) -> bool {
+    use Instruction as I;
+    if instr.branch_offset().is_some() {
+        return false;
+    }
+    !matches!(
+        instr,
+        I::BranchTable { .. }
+            | I::BranchCmpFallback { .. }
+            | I::Return
+            | I::ReturnReg { .. }
+            | I::ReturnReg2 { .. }
+            | I::ReturnReg3 { .. }
+            | I::ReturnImm32 { .. }
+            | I::ReturnI64Imm32 { .. }
+            | I::ReturnF64Imm32 { .. }
+            | I::ReturnSpan { .. }
+            | I::ReturnMany { .. }
+            | I::ReturnNez { .. }
+            | I::ReturnNezReg { .. }
+            | I::ReturnNezReg2 { .. }
+            | I::ReturnNezImm32 { .. }
+            | I::ReturnNezI64Imm32 { .. }
+            | I::ReturnNezF64Imm32 { .. }
+            | I::ReturnNezSpan { .. }
+            | I::ReturnNezMany { .. }
+            | I::CallInternal0 { .. }
+            | I::CallInternal { .. }
+            | I::CallImported0 { .. }
+            | I::CallImported { .. }
+            | I::CallIndirect0 { .. }
+            | I::CallIndirect { .. }
+            | I::CallIndirectParams(_)
+            | I::CallIndirectParamsImm16(_)
+            | I::ReturnCallInternal0 { .. }
+            | I::ReturnCallInternal { .. }
+            | I::ReturnCallImported0 { .. }
+            | I::ReturnCallImported { .. }
+            | I::ReturnCallIndirect0 { .. }
+            | I::ReturnCallIndirect { .. }
+    )
+}
+
+/// Returns `true` if `instr` is an instruction word that only carries parameters.
+fn is_param_word(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::TableIdx(_)
+            | Instruction::DataSegmentIdx(_)
+            | Instruction::ElementSegmentIdx(_)
+            | Instruction::Const32(_)
+            | Instruction::I64Const32(_)
+            | Instruction::F64Const32(_)
+            | Instruction::Register(_)
+            | Instruction::Register2(_)
+            | Instruction::Register3(_)
+            | Instruction::RegisterList(_)
+    )
+}
+
+/// Returns `true` if `instr` is a `select` with immediate values.
+///
+/// # Note
+///
+/// Those are encoded as two instruction words of the same variant where
+/// the first word holds the `result` and the second word holds the `condition`.
+fn is_select_imm(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::SelectImm32 { .. }
+            | Instruction::SelectI64Imm32 { .. }
+            | Instruction::SelectF64Imm32 { .. }
+    )
+}
+
+/// Calls `f` on all registers read by `instr`.
+///
+/// `is_param` is `true` if `instr` is the second word of a `select` with immediate values.
+fn visit_reads(instr: &mut Instruction, is_param: bool, mut f: impl FnMut(Register)) {
+    match instr {
+        Instruction::Copy { result: _, value } => f(*value),
+        Instruction::CopySpan { values, len, .. }
+        | Instruction::CopySpanNonOverlapping { values, len, .. } => {
+            values.iter_u16(*len).for_each(f);
+        }
+        Instruction::SelectImm32 { .. }
+        | Instruction::SelectI64Imm32 { .. }
+        | Instruction::SelectF64Imm32 { .. }
+            if !is_param => {}
+        _ => instr.visit_input_registers(|register| f(*register)),
+    }
+}
+
+/// Calls `f` on all registers written by `instr`.
+///
+/// `is_param` is `true` if `instr` is the second word of a `select` with immediate values.
+///
+/// # Note
+///
+/// This might not yield all registers written by `instr`
+/// which is fine since it is only used conservatively.
+fn visit_writes(
+    instr: &mut Instruction,
+    is_param: bool,
+    module: &ModuleHeader,
+    mut f: impl FnMut(Register),
+) -> Result<(), Error> {
+    match instr {
+        Instruction::Copy { result, .. } => f(*result),
+        Instruction::SelectImm32 {
+            result_or_condition,
+            ..
+        }
+        | Instruction::SelectI64Imm32 {
+            result_or_condition,
+            ..
+        }
+        | Instruction::SelectF64Imm32 {
+            result_or_condition,
+            ..
+        } => {
+            if !is_param {
+                f(*result_or_condition)
+            }
+        }
+        Instruction::Copy2 { results, .. } => results.iter(2).for_each(f),
+        Instruction::CopySpan { results, len, .. }
+        | Instruction::CopySpanNonOverlapping { results, len, .. } => {
+            results.iter_u16(*len).for_each(f)
+        }
+        _ => {
+            if let Some(result) = instr.result_mut(module)? {
+                f(*result)
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shifts all registers of `instr` into the inlined call `frame`.
+fn relocate(
+    instr: &mut Instruction,
+    frame: RegisterSpan,
+    module: &ModuleHeader,
+) -> Result<(), Error> {
+    let relocate = |register: &mut Register| {
+        debug_assert!(!register.is_const());
+        *register = Register::from_i16(frame.head().to_i16() + register.to_i16());
+    };
+    instr.visit_input_registers(relocate);
+    match instr {
+        Instruction::Copy { .. }
+        | Instruction::SelectImm32 { .. }
+        | Instruction::SelectI64Imm32 { .. }
+        | Instruction::SelectF64Imm32 { .. } => {
+            // Note: The results of those instructions are already
+            //       visited by `visit_input_registers`.
+        }
+        Instruction::Copy2 { results, .. }
+        | Instruction::CopySpan { results, .. }
+        | Instruction::CopySpanNonOverlapping { results, .. }
+        | Instruction::CopyMany { results, .. }
+        | Instruction::CopyManyNonOverlapping { results, .. } => relocate(results.head_mut()),
+        _ => {
+            if let Some(result) = instr.result_mut(module)? {
+                relocate(result)
+            }
+        }
+    }
+    Ok(())
+}
+
+impl FuncTranslator {
+    /// Tries to inline the `call` to the internal `func` of type `func_type`.
+    ///
+    /// Returns `false` if `func` cannot be inlined in which case nothing has been translated.
+    ///
+    /// # Note
+    ///
+    /// Only functions that have already been compiled can be inlined.
+    pub(super) fn try_inline_call(
+        &mut self,
+        func: CompiledFunc,
+        func_type: &FuncType,
+    ) -> Result<bool, Error> {
+        let budget = self.engine().config().get_inline_budget();
+        if budget == 0 {
+            return Ok(false);
+        }
+        let Some(Some(inlined)) = self
+            .engine()
+            .resolve_compiled_func(func, |func| InlinedFunc::new(func, budget))
+        else {
+            return Ok(false);
+        };
+        let (params, results) = func_type.params_results();
+        let params_buffer = &mut self.alloc.buffer;
+        self.alloc.stack.pop_n(params.len(), params_buffer);
+        for param in params_buffer.iter_mut() {
+            // Note: Constant call parameters are allocated as function local
+            //       constants just like for calls that are not inlined
+            //       since fuel consumption depends on the number of registers.
+            if let TypedProvider::Const(value) = *param {
+                *param = TypedProvider::Register(self.alloc.stack.alloc_const(value)?);
+            }
+        }
+        let frame = self
+            .alloc
+            .stack
+            .alloc_inlined_frame(usize::from(inlined.len_cells))?;
+        self.alloc.instr_encoder.encode_copies(
+            &mut self.alloc.stack,
+            frame.iter(params.len()),
+            &self.alloc.buffer[..],
+            FuelInfo::None,
+        )?;
+        // Note: The inlined instructions must not be altered by optimizations
+        //       such as result relinking for `local.set` which refer to
+        //       the most recently pushed instruction.
+        self.alloc.instr_encoder.reset_last_instr();
+        for local in inlined.uninit_reads(params.len(), &self.module)? {
+            let mut instr = Instruction::copy_imm32(local, 0_i32);
+            relocate(&mut instr, frame, &self.module)?;
+            self.alloc.instr_encoder.append_instr(instr)?;
+        }
+        for mut instr in inlined.body {
+            relocate(&mut instr, frame, &self.module)?;
+            self.alloc.instr_encoder.append_instr(instr)?;
+        }
+        let results = self.alloc.stack.push_dynamic_n(results.len())?;
+        debug_assert_eq!(results.head(), frame.head());
+        let result = results.head();
+        let ret = match inlined.ret {
+            Instruction::ReturnReg { value } => {
+                let value = Register::from_i16(frame.head().to_i16() + value.to_i16());
+                self.alloc.instr_encoder.encode_copy(
+                    &mut self.alloc.stack,
+                    result,
+                    TypedProvider::Register(value),
+                    FuelInfo::None,
+                )?;
+                return Ok(true);
+            }
+            Instruction::ReturnImm32 { value } => Instruction::copy_imm32(result, value),
+            Instruction::ReturnI64Imm32 { value } => Instruction::copy_i64imm32(result, value),
+            Instruction::ReturnF64Imm32 { value } => Instruction::copy_f64imm32(result, value),
+            _ => return Ok(true),
+        };
+        self.alloc.instr_encoder.push_instr(ret)?;
+        Ok(true)
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    optimizer,
     visit_register::VisitInputRegisters,
     FuelInfo,
     LabelRef,
@@ -8,6 +9,7 @@ use super::{
 use crate::{
     engine::{
         bytecode::{
+            AnyConst32,
             BinInstr,
             BinInstrImm16,
             BranchComparator,
@@ -17,10 +19,12 @@ use crate::{
             Const16,
             Const32,
             Instruction,
+            LoadOffset16Instr,
             Provider,
             Register,
             RegisterSpan,
             RegisterSpanIter,
+            StoreOffset16Instr,
         },
         translator::{stack::RegisterSpace, ValueStack},
         FuelCosts,
@@ -88,6 +92,14 @@ pub struct InstrEncoder {
     labels: LabelRegistry,
     /// The last [`Instruction`] created via [`InstrEncoder::push_instr`].
     last_instr: Option<Instr>,
+    /// The last [`Instruction`] created via [`InstrEncoder::push_instr`] that may be fused.
+    ///
+    /// # Note
+    ///
+    /// Unlike `last_instr` this is not reset by `local.get` since superinstructions
+    /// do not alter the results of their fused instructions. However, it is reset
+    /// whenever a label is pinned since fusing across branch targets is invalid.
+    fuse_candidate: Option<Instr>,
     /// The first encoded [`Instr`] that is affected by a `local.set` preservation.
     ///
     /// # Note
@@ -204,6 +216,7 @@ impl InstrEncoder {
         self.instrs.reset();
         self.labels.reset();
         self.reset_last_instr();
+        self.fuse_candidate = None;
         self.notified_preservation = None;
     }
 
@@ -231,6 +244,15 @@ impl InstrEncoder {
         self.instrs.drain()
     }
 
+    /// Applies post-translation optimizations to the encoded [`Instruction`] sequence.
+    ///
+    /// # Note
+    ///
+    /// This must be called after all branch offsets have been resolved.
+    pub fn optimize(&mut self) {
+        optimizer::optimize(&mut self.instrs.instrs);
+    }
+
     /// Creates a new unresolved label and returns its [`LabelRef`].
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -246,6 +268,7 @@ impl InstrEncoder {
     /// the given label can be resolved properly.
     /// This usually takes place when encountering the Wasm `End` operand for example.
     pub fn pin_label_if_unpinned(&mut self, label: LabelRef) {
+        self.fuse_candidate = None;
         self.labels.try_pin_label(label, self.instrs.next_instr())
     }
 
@@ -261,6 +284,7 @@ impl InstrEncoder {
     ///
     /// If the label has already been resolved.
     pub fn pin_label(&mut self, label: LabelRef) {
+        self.fuse_candidate = None;
         self.labels
             .pin_label(label, self.instrs.next_instr())
             .unwrap_or_else(|err| panic!("failed to pin label: {err}"));
@@ -309,6 +333,7 @@ impl InstrEncoder {
     pub fn push_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
         let last_instr = self.instrs.push(instr)?;
         self.last_instr = Some(last_instr);
+        self.fuse_candidate = Some(last_instr);
         Ok(last_instr)
     }
 
@@ -351,6 +376,11 @@ impl InstrEncoder {
                     // Optimization: copying from register `x` into `x` is a no-op.
                     return Ok(None);
                 }
+                if let Some(instr) = self.try_fuse_copy(stack, result, value) {
+                    // Optimization: chains of copies into adjacent registers are fused.
+                    self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+                    return Ok(Some(instr));
+                }
                 Instruction::copy(result, value)
             }
             TypedProvider::Const(value) => match value.ty() {
@@ -373,6 +403,50 @@ impl InstrEncoder {
         Ok(Some(instr))
     }
 
+    /// Tries to fuse a `copy result <- value` with the last [`Instruction::Copy`].
+    ///
+    /// Returns the [`Instr`] of the fused [`Instruction::Copy2`] if fusion was possible.
+    ///
+    /// # Note
+    ///
+    /// This is possible if the last [`Instruction::Copy`] copies into the [`Register`]
+    /// right before `result` and `value` is not the result of the last copy.
+    fn try_fuse_copy(
+        &mut self,
+        stack: &ValueStack,
+        result: Register,
+        value: Register,
+    ) -> Option<Instr> {
+        let last_instr = self.fuse_candidate.take()?;
+        if last_instr.distance(self.instrs.next_instr()) != 1 {
+            return None;
+        }
+        let Instruction::Copy {
+            result: last_result,
+            value: last_value,
+        } = *self.instrs.get(last_instr)
+        else {
+            return None;
+        };
+        if last_result.next() != result || last_result == value {
+            return None;
+        }
+        if [last_result, result]
+            .into_iter()
+            .any(|reg| matches!(stack.get_register_space(reg), RegisterSpace::Preserve))
+        {
+            // Preserved registers are subject to defragmentation and thus not
+            // guaranteed to stay adjacent.
+            return None;
+        }
+        _ = mem::replace(
+            self.instrs.get_mut(last_instr),
+            Instruction::copy2(RegisterSpan::new(last_result), last_value, value),
+        );
+        self.last_instr = Some(last_instr);
+        Some(last_instr)
+    }
+
     /// Encode a generic `copy` instruction.
     ///
     /// # Note
@@ -790,6 +864,7 @@ impl InstrEncoder {
                 .push_before(last_instr, Instruction::copy(preserved, local))?;
             self.notify_preserved_register(last_instr);
             self.last_instr = Some(shifted_last_instr);
+            self.fuse_candidate = None;
         }
         Ok(())
     }
@@ -827,6 +902,26 @@ impl InstrEncoder {
         Ok(())
     }
 
+    /// Relocates the slots of all spilled local variable accesses behind the registers of the call frame.
+    ///
+    /// # Note
+    ///
+    /// This must be called after [`InstrEncoder::defrag_registers`] since only then
+    /// the number of registers used by the function is known.
+    pub fn relocate_spilled_locals(&mut self, stack: &ValueStack) {
+        if stack.len_spilled() == 0 {
+            return;
+        }
+        let len_cells = u32::from(stack.len_cells());
+        for instr in self.instrs.get_slice_at_mut(Instr::from_u32(0)) {
+            if let Instruction::LocalGetSpilled { slot, .. }
+            | Instruction::LocalSetSpilled { slot, .. } = instr
+            {
+                *slot += len_cells;
+            }
+        }
+    }
+
     /// Translates a Wasm `i32.eqz` instruction.
     ///
     /// Tries to fuse `i32.eqz` with a previous `i32.{and,or,xor}` instruction if possible.
@@ -897,6 +992,83 @@ impl InstrEncoder {
         true
     }
 
+    /// Tries to fuse `instr` with the last [`Instruction`] into a superinstruction.
+    ///
+    /// Returns the [`Instr`] of the superinstruction if fusion was possible.
+    /// Otherwise returns `None` and `instr` is not encoded.
+    ///
+    /// # Note
+    ///
+    /// The fused instruction pairs are among the most frequent adjacent pairs
+    /// found by profiling the benchmark corpus. Fusion is only applied if the
+    /// intermediate result [`Register`] of the last [`Instruction`] is a dynamic
+    /// [`Register`] that is consumed by `instr` since it is unobservable otherwise.
+    pub fn try_fuse_instr(
+        &mut self,
+        stack: &ValueStack,
+        instr: Instruction,
+    ) -> Result<Option<Instr>, Error> {
+        let Some(last_instr) = self.fuse_candidate.take() else {
+            return Ok(None);
+        };
+        if last_instr.distance(self.instrs.next_instr()) != 1 {
+            // The last instruction has trailing parameter words.
+            return Ok(None);
+        }
+        let is_intermediate =
+            |reg: Register| matches!(stack.get_register_space(reg), RegisterSpace::Dynamic);
+        let (fused_instr, param) = match (*self.instrs.get(last_instr), instr) {
+            (Instruction::I32LoadOffset16(load), Instruction::I32Add(add))
+                if is_intermediate(load.result) && add.lhs != add.rhs =>
+            {
+                let rhs = match (add.lhs == load.result, add.rhs == load.result) {
+                    (true, false) => add.rhs,
+                    (false, true) => add.lhs,
+                    _ => return Ok(None),
+                };
+                (
+                    Instruction::I32LoadOffset16Add(LoadOffset16Instr::new(
+                        add.result,
+                        load.ptr,
+                        load.offset,
+                    )),
+                    Instruction::Register(rhs),
+                )
+            }
+            (Instruction::I32LoadOffset16(load), Instruction::I32AddImm16(add))
+                if is_intermediate(load.result) && add.reg_in == load.result =>
+            {
+                (
+                    Instruction::I32LoadOffset16AddImm(LoadOffset16Instr::new(
+                        add.result,
+                        load.ptr,
+                        load.offset,
+                    )),
+                    Instruction::Const32(AnyConst32::from(i32::from(add.imm_in))),
+                )
+            }
+            (Instruction::I32AddImm16(add), Instruction::I32StoreOffset16(store))
+                if is_intermediate(add.result)
+                    && store.value == add.result
+                    && store.ptr != add.result =>
+            {
+                (
+                    Instruction::I32StoreOffset16AddImm(StoreOffset16Instr::new(
+                        store.ptr,
+                        store.offset,
+                        add.reg_in,
+                    )),
+                    Instruction::Const32(AnyConst32::from(i32::from(add.imm_in))),
+                )
+            }
+            _ => return Ok(None),
+        };
+        _ = mem::replace(self.instrs.get_mut(last_instr), fused_instr);
+        self.append_instr(param)?;
+        self.last_instr = Some(last_instr);
+        Ok(Some(last_instr))
+    }
+
     /// Encodes a `branch_eqz` instruction and tries to fuse it with a previous comparison instruction.
     pub fn encode_branch_eqz(
         &mut self,
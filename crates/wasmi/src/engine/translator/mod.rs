@@ -4,8 +4,10 @@ mod control_frame;
 mod control_stack;
 mod driver;
 mod error;
+mod inline;
 mod instr_encoder;
 mod labels;
+mod optimizer;
 mod relink_result;
 mod stack;
 mod typed_value;
@@ -55,6 +57,7 @@ use crate::{
         config::FuelCosts,
         BlockType,
         CompiledFunc,
+        OptLevel,
     },
     module::{FuncIdx, FuncTypeIdx, ModuleHeader},
     Engine,
@@ -110,6 +113,15 @@ pub struct ValidatingFuncTranslator<T> {
     validator: FuncValidator,
     /// The chosen function translator.
     translator: T,
+    /// The number of Wasm operators validated by the current translation attempt.
+    len_validated: usize,
+    /// The number of Wasm operators already validated by a previous translation attempt.
+    ///
+    /// # Note
+    ///
+    /// Those Wasm operators are only translated but not validated again
+    /// when the Wasm function body is translated again after a restart.
+    len_prevalidated: usize,
 }
 
 /// Reusable heap allocations for function validation and translation.
@@ -181,6 +193,15 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     /// - Initialized the [`CompiledFunc`] in the [`Engine`].
     /// - Returns the allocations used for translation.
     fn finish(self, finalize: impl FnOnce(CompiledFuncEntity)) -> Result<Self::Allocations, Error>;
+
+    /// Restarts the translation of the Wasm function after it ran out of registers.
+    ///
+    /// - Returns `true` if the [`WasmTranslator`] is ready to translate the Wasm function
+    ///   again, starting with its local variables via [`WasmTranslator::translate_locals`].
+    /// - Returns `false` if the [`WasmTranslator`] cannot provide more registers.
+    fn restart(&mut self) -> Result<bool, Error> {
+        Ok(false)
+    }
 }
 
 impl<T> ValidatingFuncTranslator<T> {
@@ -190,6 +211,8 @@ impl<T> ValidatingFuncTranslator<T> {
             pos: 0,
             validator,
             translator,
+            len_validated: 0,
+            len_prevalidated: 0,
         })
     }
 
@@ -208,7 +231,10 @@ impl<T> ValidatingFuncTranslator<T> {
         Validate: FnOnce(&mut FuncValidator) -> Result<(), BinaryReaderError>,
         Translate: FnOnce(&mut T) -> Result<(), Error>,
     {
-        validate(&mut self.validator)?;
+        if self.len_validated >= self.len_prevalidated {
+            validate(&mut self.validator)?;
+        }
+        self.len_validated += 1;
         translate(&mut self.translator)?;
         Ok(())
     }
@@ -232,8 +258,10 @@ where
         amount: u32,
         value_type: wasmparser::ValType,
     ) -> Result<(), Error> {
-        self.validator
-            .define_locals(self.current_pos(), amount, value_type)?;
+        if self.len_prevalidated == 0 {
+            self.validator
+                .define_locals(self.current_pos(), amount, value_type)?;
+        }
         self.translator.translate_locals(amount, value_type)?;
         Ok(())
     }
@@ -261,6 +289,17 @@ where
         };
         Ok(allocations)
     }
+
+    fn restart(&mut self) -> Result<bool, Error> {
+        if !self.translator.restart()? {
+            return Ok(false);
+        }
+        // Note: The Wasm operators up to and including the one that ran out of
+        //       registers have been validated and must not be validated again.
+        self.len_prevalidated = self.len_validated;
+        self.len_validated = 0;
+        Ok(true)
+    }
 }
 
 macro_rules! impl_visit_operator {
@@ -295,6 +334,9 @@ macro_rules! impl_visit_operator {
     ( @tail_call $($rest:tt)* ) => {
         impl_visit_operator!(@@supported $($rest)*);
     };
+    ( @threads $($rest:tt)* ) => {
+        impl_visit_operator!(@@supported $($rest)*);
+    };
     ( @@supported $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         fn $visit(&mut self $($(,$arg: $argty)*)?) -> Self::Output {
             let offset = self.current_pos();
@@ -466,10 +508,20 @@ pub struct FuncTranslator {
 impl<'parser> WasmTranslator<'parser> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
-    fn setup(&mut self, _bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
+        self.alloc.stack.reserve_registers(bytes.len());
         Ok(false)
     }
 
+    fn restart(&mut self) -> Result<bool, Error> {
+        if self.alloc.stack.all_registers_reserved() {
+            return Ok(false);
+        }
+        self.reset()?;
+        self.alloc.stack.reserve_all_registers();
+        Ok(true)
+    }
+
     fn translate_locals(
         &mut self,
         amount: u32,
@@ -492,10 +544,14 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
         self.alloc
             .instr_encoder
             .defrag_registers(&mut self.alloc.stack)?;
+        self.alloc
+            .instr_encoder
+            .relocate_spilled_locals(&self.alloc.stack);
         self.alloc
             .instr_encoder
             .update_branch_offsets(&mut self.alloc.stack)?;
         let len_registers = self.alloc.stack.len_registers();
+        let len_spilled = self.alloc.stack.len_spilled();
         if let Some(fuel_costs) = self.fuel_costs() {
             // Note: Fuel metering is enabled so we need to bump the fuel
             //       of the function enclosing Wasm `block` by an amount
@@ -503,17 +559,29 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
             //       the compiled function.
             // Note: The function enclosing block fuel instruction is always
             //       the instruction at the 0th index if fuel metering is enabled.
+            // Note: Registers of inlined call frames are not charged since
+            //       the fuel consumption must not depend on inlining.
+            // Note: Spilled local variables are charged the same as registers.
             let fuel_instr = Instr::from_u32(0);
             let fuel_info = FuelInfo::some(*fuel_costs, fuel_instr);
+            let len_registers_uninlined = self.alloc.stack.len_registers_uninlined();
             self.alloc
                 .instr_encoder
                 .bump_fuel_consumption(fuel_info, |costs| {
-                    costs.fuel_for_copies(u64::from(len_registers))
+                    costs.fuel_for_copies(
+                        u64::from(len_registers_uninlined) + u64::from(len_spilled),
+                    )
                 })?;
         }
+        if let OptLevel::Speed = self.engine().config().get_opt_level() {
+            self.alloc.instr_encoder.optimize();
+        }
         let func_consts = self.alloc.stack.func_local_consts();
         let instrs = self.alloc.instr_encoder.drain_instrs();
-        finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
+        let entity = CompiledFuncEntity::new(len_registers, len_spilled, instrs, func_consts);
+        #[cfg(feature = "instr-profiling")]
+        self.engine().record_translated_instrs(entity.instrs());
+        finalize(entity);
         Ok(self.into_allocations())
     }
 }
@@ -593,10 +661,17 @@ impl FuncTranslator {
 
     /// Initializes a newly constructed [`FuncTranslator`].
     fn init(mut self) -> Result<Self, Error> {
+        self.reset()?;
+        Ok(self)
+    }
+
+    /// Resets the [`FuncTranslator`] to start translating its function from scratch.
+    fn reset(&mut self) -> Result<(), Error> {
         self.alloc.reset();
+        self.reachable = true;
         self.init_func_body_block()?;
         self.init_func_params()?;
-        Ok(self)
+        Ok(())
     }
 
     /// Registers the `block` control frame surrounding the entire function body.
@@ -727,9 +802,30 @@ impl FuncTranslator {
         F: FnOnce(&FuelCosts) -> u64,
     {
         self.bump_fuel_consumption(f)?;
+        if let Some(instr) = self
+            .alloc
+            .instr_encoder
+            .try_fuse_instr(&self.alloc.stack, instr)?
+        {
+            return Ok(instr);
+        }
         self.alloc.instr_encoder.push_instr(instr)
     }
 
+    /// Translates a Wasm `local.set` or `local.tee` of the spilled local variable at `slot`.
+    fn translate_local_set_spilled(
+        &mut self,
+        slot: u32,
+        value: TypedProvider,
+    ) -> Result<(), Error> {
+        let value = match value {
+            TypedProvider::Register(value) => value,
+            TypedProvider::Const(value) => self.alloc.stack.alloc_const(value)?,
+        };
+        self.push_base_instr(Instruction::local_set_spilled(slot, value))?;
+        Ok(())
+    }
+
     /// Utility function for pushing a new [`Instruction`] with basic fuel costs.
     ///
     /// # Note
@@ -2007,6 +2103,131 @@ impl FuncTranslator {
         }
     }
 
+    /// Returns the [`Register`] of `provider` allocating a function local constant if necessary.
+    fn provider_to_register(&mut self, provider: TypedProvider) -> Result<Register, Error> {
+        match provider {
+            TypedProvider::Register(register) => Ok(register),
+            TypedProvider::Const(value) => self.alloc.stack.alloc_const(value),
+        }
+    }
+
+    /// Translates Wasm atomic `load` instructions to Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// Unlike non-atomic loads there are no specialized encodings since
+    /// atomic operations must check the alignment of their effective address.
+    ///
+    /// # Usage
+    ///
+    /// Used for translating the following Wasm operators to Wasmi bytecode:
+    ///
+    /// - `{i32, i64}.atomic.load`
+    /// - `{i32, i64}.atomic.{load8_u, load16_u}`
+    /// - `i64.atomic.load32_u`
+    fn translate_atomic_load(
+        &mut self,
+        memarg: MemArg,
+        make_instr: fn(result: Register, ptr: Register) -> Instruction,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        let offset = Self::memarg_offset(memarg);
+        let ptr = self.alloc.stack.pop();
+        let ptr = self.provider_to_register(ptr)?;
+        let result = self.alloc.stack.push_dynamic()?;
+        self.push_fueled_instr(make_instr(result, ptr), FuelCosts::load)?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
+    /// Translates Wasm atomic `store` instructions to Wasmi bytecode.
+    ///
+    /// # Usage
+    ///
+    /// Used for translating the following Wasm operators to Wasmi bytecode:
+    ///
+    /// - `{i32, i64}.atomic.store`
+    /// - `{i32, i64}.atomic.{store8, store16}`
+    /// - `i64.atomic.store32`
+    fn translate_atomic_store(
+        &mut self,
+        memarg: MemArg,
+        make_instr: fn(ptr: Register, offset: Const32<u32>) -> Instruction,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        let offset = Self::memarg_offset(memarg);
+        let (ptr, value) = self.alloc.stack.pop2();
+        let ptr = self.provider_to_register(ptr)?;
+        let value = self.provider_to_register(value)?;
+        self.push_fueled_instr(make_instr(ptr, Const32::from(offset)), FuelCosts::store)?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::Register(value))?;
+        Ok(())
+    }
+
+    /// Translates Wasm atomic read-modify-write instructions to Wasmi bytecode.
+    ///
+    /// # Usage
+    ///
+    /// Used for translating the following Wasm operators to Wasmi bytecode:
+    ///
+    /// - `memory.atomic.notify`
+    /// - `{i32, i64}.atomic.rmw.{add, sub, and, or, xor, xchg}`
+    /// - `{i32, i64}.atomic.{rmw8, rmw16}.{add, sub, and, or, xor, xchg}_u`
+    /// - `i64.atomic.rmw32.{add, sub, and, or, xor, xchg}_u`
+    fn translate_atomic_rmw(
+        &mut self,
+        memarg: MemArg,
+        make_instr: fn(result: Register, ptr: Register, value: Register) -> Instruction,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        let offset = Self::memarg_offset(memarg);
+        let (ptr, value) = self.alloc.stack.pop2();
+        let ptr = self.provider_to_register(ptr)?;
+        let value = self.provider_to_register(value)?;
+        let result = self.alloc.stack.push_dynamic()?;
+        self.push_fueled_instr(make_instr(result, ptr, value), FuelCosts::store)?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
+    /// Translates Wasm atomic instructions with three inputs to Wasmi bytecode.
+    ///
+    /// # Usage
+    ///
+    /// Used for translating the following Wasm operators to Wasmi bytecode:
+    ///
+    /// - `memory.atomic.{wait32, wait64}`
+    /// - `{i32, i64}.atomic.rmw.cmpxchg`
+    /// - `{i32, i64}.atomic.{rmw8, rmw16}.cmpxchg_u`
+    /// - `i64.atomic.rmw32.cmpxchg_u`
+    fn translate_atomic_ternary(
+        &mut self,
+        memarg: MemArg,
+        make_instr: fn(result: Register, ptr: Register, value: Register) -> Instruction,
+    ) -> Result<(), Error> {
+        bail_unreachable!(self);
+        let offset = Self::memarg_offset(memarg);
+        let (ptr, value, param) = self.alloc.stack.pop3();
+        let ptr = self.provider_to_register(ptr)?;
+        let value = self.provider_to_register(value)?;
+        let param = self.provider_to_register(param)?;
+        let result = self.alloc.stack.push_dynamic()?;
+        self.push_fueled_instr(make_instr(result, ptr, value), FuelCosts::store)?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::Register(param))?;
+        self.alloc
+            .instr_encoder
+            .append_instr(Instruction::const32(offset))?;
+        Ok(())
+    }
+
     /// Translates a Wasm `select` or `select <ty>` instruction.
     ///
     /// # Note
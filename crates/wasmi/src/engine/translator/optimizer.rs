@@ -0,0 +1,378 @@
+//! Optimizations applied to the Wasmi bytecode of a function after its translation.
+//!
+//! # Note
+//!
+//! The [`FuncTranslator`] translates in a single pass and thus cannot optimize
+//! across the boundaries of Wasm control flow. The optimizations in this module
+//! run over the finished [`Instruction`] sequence of a function with all branch
+//! offsets resolved.
+//!
+//! None of the optimizations add or alter [`Instruction::ConsumeFuel`] instructions.
+//! Therefore fuel consumption is the same no matter which [`OptLevel`] is used.
+//!
+//! [`FuncTranslator`]: super::FuncTranslator
+//! [`OptLevel`]: crate::OptLevel
+
+use crate::engine::bytecode::{BranchOffset, BranchOffset16, Instruction, Register};
+use std::{vec, vec::Vec};
+
+/// The maximum number of `branch` instructions followed when threading a branch.
+///
+/// # Note
+///
+/// This protects against cycles such as `(loop (br 0))`.
+const MAX_THREADING_DEPTH: usize = 16;
+
+/// Applies all post-translation optimizations to `instrs`.
+pub fn optimize(instrs: &mut Vec<Instruction>) {
+    thread_branches(instrs);
+    fold_branches_to_return(instrs);
+    remove_dead_instrs(instrs);
+}
+
+impl Instruction {
+    /// Returns the [`BranchOffset`] of a branch [`Instruction`] if any.
+    pub(super) fn branch_offset(&self) -> Option<BranchOffset> {
+        macro_rules! branch_offset {
+            ( $( $op:ident ),* $(,)? ) => {
+                match self {
+                    Self::Branch { offset } => Some(*offset),
+                    $( Self::$op(instr) => Some(BranchOffset::from(instr.offset)), )*
+                    _ => None,
+                }
+            };
+        }
+        for_each_branch_cmp_instr!(branch_offset)
+    }
+
+    /// Sets the [`BranchOffset`] of a branch [`Instruction`] to `offset`.
+    ///
+    /// Returns `false` if `offset` cannot be encoded for the branch [`Instruction`].
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not a branch [`Instruction`].
+    fn set_branch_offset(&mut self, new_offset: BranchOffset) -> bool {
+        macro_rules! set_branch_offset {
+            ( $( $op:ident ),* $(,)? ) => {
+                match self {
+                    Self::Branch { offset } => {
+                        *offset = new_offset;
+                        true
+                    }
+                    $(
+                        Self::$op(instr) => match BranchOffset16::try_from(new_offset) {
+                            Ok(offset) => {
+                                instr.offset = offset;
+                                true
+                            }
+                            Err(_) => false,
+                        },
+                    )*
+                    _ => panic!("tried to set branch offset of a non-branch instruction: {self:?}"),
+                }
+            };
+        }
+        for_each_branch_cmp_instr!(set_branch_offset)
+    }
+
+    /// Returns the result [`Register`] of a single word [`Instruction`] without side effects.
+    ///
+    /// # Note
+    ///
+    /// Such an [`Instruction`] can be removed if its result is never read.
+    /// Instructions that may trap, e.g. integer division, are not included.
+    fn pure_result(&self) -> Option<Register> {
+        macro_rules! pure_result {
+            ( $( $op:ident ),* $(,)? ) => {
+                match self {
+                    Self::Copy { result, .. }
+                    | Self::CopyImm32 { result, .. }
+                    | Self::CopyI64Imm32 { result, .. }
+                    | Self::CopyF64Imm32 { result, .. } => Some(*result),
+                    $( Self::$op(instr) => Some(instr.result), )*
+                    _ => None,
+                }
+            };
+        }
+        for_each_pure_instr!(pure_result)
+    }
+
+    /// Returns `true` if `self` is a return [`Instruction`] without parameter words.
+    fn is_single_word_return(&self) -> bool {
+        matches!(
+            self,
+            Self::Return
+                | Self::ReturnReg { .. }
+                | Self::ReturnReg2 { .. }
+                | Self::ReturnReg3 { .. }
+                | Self::ReturnImm32 { .. }
+                | Self::ReturnI64Imm32 { .. }
+                | Self::ReturnF64Imm32 { .. }
+                | Self::ReturnSpan { .. }
+        )
+    }
+}
+
+/// Calls `$mac` with the names of all fused compare and branch [`Instruction`] variants.
+macro_rules! for_each_branch_cmp_instr {
+    ($mac:ident) => {
+        $mac! {
+            BranchI32And, BranchI32AndImm, BranchI32Or, BranchI32OrImm,
+            BranchI32Xor, BranchI32XorImm, BranchI32AndEqz, BranchI32AndEqzImm,
+            BranchI32OrEqz, BranchI32OrEqzImm, BranchI32XorEqz, BranchI32XorEqzImm,
+            BranchI32Eq, BranchI32EqImm, BranchI32Ne, BranchI32NeImm,
+            BranchI32LtS, BranchI32LtSImm, BranchI32LtU, BranchI32LtUImm,
+            BranchI32LeS, BranchI32LeSImm, BranchI32LeU, BranchI32LeUImm,
+            BranchI32GtS, BranchI32GtSImm, BranchI32GtU, BranchI32GtUImm,
+            BranchI32GeS, BranchI32GeSImm, BranchI32GeU, BranchI32GeUImm,
+            BranchI64Eq, BranchI64EqImm, BranchI64Ne, BranchI64NeImm,
+            BranchI64LtS, BranchI64LtSImm, BranchI64LtU, BranchI64LtUImm,
+            BranchI64LeS, BranchI64LeSImm, BranchI64LeU, BranchI64LeUImm,
+            BranchI64GtS, BranchI64GtSImm, BranchI64GtU, BranchI64GtUImm,
+            BranchI64GeS, BranchI64GeSImm, BranchI64GeU, BranchI64GeUImm,
+            BranchF32Eq, BranchF32Ne, BranchF32Lt, BranchF32Le, BranchF32Gt, BranchF32Ge,
+            BranchF64Eq, BranchF64Ne, BranchF64Lt, BranchF64Le, BranchF64Gt, BranchF64Ge,
+        }
+    };
+}
+use for_each_branch_cmp_instr;
+
+/// Calls `$mac` with the names of all single word [`Instruction`] variants without side effects
+/// that store their result into a single register.
+macro_rules! for_each_pure_instr {
+    ($mac:ident) => {
+        $mac! {
+            I32Eq, I32EqImm16, I64Eq, I64EqImm16, I32Ne, I32NeImm16, I64Ne, I64NeImm16, I32LtS,
+            I32LtU, I32LtSImm16, I32LtUImm16, I64LtS, I64LtU, I64LtSImm16, I64LtUImm16, I32GtS,
+            I32GtU, I32GtSImm16, I32GtUImm16, I64GtS, I64GtU, I64GtSImm16, I64GtUImm16, I32LeS,
+            I32LeU, I32LeSImm16, I32LeUImm16, I64LeS, I64LeU, I64LeSImm16, I64LeUImm16, I32GeS,
+            I32GeU, I32GeSImm16, I32GeUImm16, I64GeS, I64GeU, I64GeSImm16, I64GeUImm16, F32Eq,
+            F64Eq, F32Ne, F64Ne, F32Lt, F64Lt, F32Le, F64Le, F32Gt, F64Gt, F32Ge, F64Ge, I32Clz,
+            I64Clz, I32Ctz, I64Ctz, I32Popcnt, I64Popcnt, I32Add, I64Add, I32AddImm16, I64AddImm16,
+            I32Sub, I64Sub, I32SubImm16Rev, I64SubImm16Rev, I32Mul, I64Mul, I32MulImm16,
+            I64MulImm16, I32And, I32AndEqz, I32AndEqzImm16, I32AndImm16, I64And, I64AndImm16, I32Or,
+            I32OrEqz, I32OrEqzImm16, I32OrImm16, I64Or, I64OrImm16, I32Xor, I32XorEqz,
+            I32XorEqzImm16, I32XorImm16, I64Xor, I64XorImm16, I32Shl, I64Shl, I32ShlImm, I64ShlImm,
+            I32ShlImm16Rev, I64ShlImm16Rev, I32ShrU, I64ShrU, I32ShrUImm, I64ShrUImm,
+            I32ShrUImm16Rev, I64ShrUImm16Rev, I32ShrS, I64ShrS, I32ShrSImm, I64ShrSImm,
+            I32ShrSImm16Rev, I64ShrSImm16Rev, I32Rotl, I64Rotl, I32RotlImm, I64RotlImm,
+            I32RotlImm16Rev, I64RotlImm16Rev, I32Rotr, I64Rotr, I32RotrImm, I64RotrImm,
+            I32RotrImm16Rev, I64RotrImm16Rev, F32Abs, F64Abs, F32Neg, F64Neg, F32Ceil, F64Ceil,
+            F32Floor, F64Floor, F32Trunc, F64Trunc, F32Nearest, F64Nearest, F32Sqrt, F64Sqrt,
+            F32Add, F64Add, F32Sub, F64Sub, F32Mul, F64Mul, F32Div, F64Div, F32Min, F64Min, F32Max,
+            F64Max, F32Copysign, F64Copysign, F32CopysignImm, F64CopysignImm, I32WrapI64,
+            I64ExtendI32S, I64ExtendI32U, I32TruncSatF32S, I32TruncSatF32U, I32TruncSatF64S,
+            I32TruncSatF64U, I64TruncSatF32S, I64TruncSatF32U, I64TruncSatF64S, I64TruncSatF64U,
+            I32Extend8S, I32Extend16S, I64Extend8S, I64Extend16S, I64Extend32S, F32DemoteF64,
+            F64PromoteF32, F32ConvertI32S, F32ConvertI32U, F32ConvertI64S, F32ConvertI64U,
+            F64ConvertI32S, F64ConvertI32U, F64ConvertI64S, F64ConvertI64U,
+        }
+    };
+}
+use for_each_pure_instr;
+
+/// Returns the index of the [`Instruction`] that a branch at `src` with `offset` targets.
+fn branch_destination(src: usize, offset: BranchOffset) -> usize {
+    let dst = src as isize + offset.to_i32() as isize;
+    usize::try_from(dst).unwrap_or_else(|_| panic!("branch destination out of bounds: {dst}"))
+}
+
+/// Returns the [`BranchOffset`] for a branch from `src` to `dst`.
+fn offset_between(src: usize, dst: usize) -> BranchOffset {
+    BranchOffset::from(dst as i32 - src as i32)
+}
+
+/// Redirects branches that target a `branch` instruction to the final destination.
+///
+/// # Example
+///
+/// `br_if` to the end of a Wasm `block` that is immediately followed by
+/// a `br` to the end of its enclosing `block` directly branches to the
+/// end of the enclosing `block` instead.
+fn thread_branches(instrs: &mut [Instruction]) {
+    for src in 0..instrs.len() {
+        let Some(offset) = instrs[src].branch_offset() else {
+            continue;
+        };
+        let dst = branch_destination(src, offset);
+        let mut final_dst = dst;
+        for _ in 0..MAX_THREADING_DEPTH {
+            match instrs[final_dst] {
+                Instruction::Branch { offset } => {
+                    final_dst = branch_destination(final_dst, offset);
+                }
+                _ => break,
+            }
+        }
+        if final_dst != dst {
+            // Note: Fused compare and branch instructions keep their
+            //       original destination if the new offset does not fit.
+            instrs[src].set_branch_offset(offset_between(src, final_dst));
+        }
+    }
+}
+
+/// Replaces `branch` instructions that target a `return` with a copy of that `return`.
+fn fold_branches_to_return(instrs: &mut [Instruction]) {
+    for src in 0..instrs.len() {
+        let Instruction::Branch { offset } = instrs[src] else {
+            continue;
+        };
+        let dst = branch_destination(src, offset);
+        if instrs[dst].is_single_word_return() {
+            instrs[src] = instrs[dst];
+        }
+    }
+}
+
+/// Removes `copy` instructions that are redundant and instructions with dead results.
+///
+/// # Note
+///
+/// An instruction is removed if
+///
+/// - it is a `copy` of a register into itself,
+/// - it has no side effects and its result register is never read by any
+///   instruction of the function, or
+/// - it is a `copy` identical to its preceding instruction and not a branch target.
+///
+/// Instructions that only feed removed instructions are kept since the read
+/// registers are computed once for the whole function.
+fn remove_dead_instrs(instrs: &mut Vec<Instruction>) {
+    if instrs
+        .iter()
+        .any(|instr| matches!(instr, Instruction::BranchCmpFallback { .. }))
+    {
+        // Note: The branch offsets of `BranchCmpFallback` instructions are stored
+        //       in function local constants which cannot be updated here.
+        return;
+    }
+    let read = ReadRegisters::new(instrs);
+    let mut is_branch_target = vec![false; instrs.len()];
+    for (src, instr) in instrs.iter().enumerate() {
+        if let Some(offset) = instr.branch_offset() {
+            is_branch_target[branch_destination(src, offset)] = true;
+        }
+    }
+    let mut keep = vec![true; instrs.len()];
+    for (index, instr) in instrs.iter().enumerate() {
+        if let Instruction::Copy { result, value } = *instr {
+            if result == value {
+                keep[index] = false;
+                continue;
+            }
+        }
+        let Some(result) = instr.pure_result() else {
+            continue;
+        };
+        if !read.contains(result) {
+            keep[index] = false;
+            continue;
+        }
+        let is_copy = matches!(
+            instr,
+            Instruction::Copy { .. }
+                | Instruction::CopyImm32 { .. }
+                | Instruction::CopyI64Imm32 { .. }
+                | Instruction::CopyF64Imm32 { .. }
+        );
+        if is_copy && index > 0 && !is_branch_target[index] && instrs[index - 1] == *instr {
+            keep[index] = false;
+        }
+    }
+    if keep.iter().all(|keep| *keep) {
+        return;
+    }
+    // Note: `new_index[i]` is the index of the `i`-th instruction after the removals.
+    //       Removed instructions map to the next kept instruction which is
+    //       correct for branch targets since removed instructions are no-ops.
+    let mut new_index = Vec::with_capacity(instrs.len() + 1);
+    let mut len_kept = 0;
+    for keep in &keep {
+        new_index.push(len_kept);
+        len_kept += usize::from(*keep);
+    }
+    new_index.push(len_kept);
+    for src in 0..instrs.len() {
+        let Some(offset) = instrs[src].branch_offset() else {
+            continue;
+        };
+        let dst = branch_destination(src, offset);
+        let new_offset = offset_between(new_index[src], new_index[dst]);
+        // Note: Branch offsets never grow when removing instructions
+        //       and thus always fit into their current encoding.
+        let updated = instrs[src].set_branch_offset(new_offset);
+        debug_assert!(updated);
+    }
+    let mut index = 0;
+    instrs.retain(|_| {
+        let keep = keep[index];
+        index += 1;
+        keep
+    });
+}
+
+/// The set of registers that are read by at least one instruction of a function.
+struct ReadRegisters {
+    /// `read[i]` is `true` if the register with index `i` is read.
+    read: Vec<bool>,
+}
+
+impl ReadRegisters {
+    /// Computes the [`ReadRegisters`] of `instrs`.
+    fn new(instrs: &[Instruction]) -> Self {
+        let mut this = Self { read: Vec::new() };
+        for instr in instrs {
+            this.visit(*instr);
+        }
+        this
+    }
+
+    /// Marks all registers read by `instr`.
+    fn visit(&mut self, mut instr: Instruction) {
+        use super::visit_register::VisitInputRegisters as _;
+        match instr {
+            Instruction::Copy { result: _, value } => self.insert(value),
+            Instruction::ReturnSpan { values } => {
+                self.insert_span(values.span().head(), values.len_as_u16());
+            }
+            Instruction::ReturnNezSpan { condition, values } => {
+                self.insert(condition);
+                self.insert_span(values.span().head(), values.len_as_u16());
+            }
+            Instruction::CopySpan { values, len, .. }
+            | Instruction::CopySpanNonOverlapping { values, len, .. } => {
+                self.insert_span(values.head(), len);
+            }
+            _ => instr.visit_input_registers(|register| self.insert(*register)),
+        }
+    }
+
+    /// Marks the `len` registers starting at `head` as read.
+    fn insert_span(&mut self, head: Register, len: u16) {
+        for offset in 0..len {
+            self.insert(Register::from_i16(head.to_i16() + offset as i16));
+        }
+    }
+
+    /// Marks `register` as read.
+    fn insert(&mut self, register: Register) {
+        let Ok(index) = usize::try_from(register.to_i16()) else {
+            // Note: Function local constants are never written to.
+            return;
+        };
+        if index >= self.read.len() {
+            self.read.resize(index + 1, false);
+        }
+        self.read[index] = true;
+    }
+
+    /// Returns `true` if `register` is read.
+    fn contains(&self, register: Register) -> bool {
+        let Ok(index) = usize::try_from(register.to_i16()) else {
+            return true;
+        };
+        self.read.get(index).copied().unwrap_or(false)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     engine::{
         bytecode::{
+            AtomicRmwInstr,
             BinInstr,
             BinInstrImm,
             FuncIdx,
@@ -22,13 +23,34 @@ use crate::{
 };
 
 impl Instruction {
-    #[rustfmt::skip]
+    /// Relinks the single `result` [`Register`] of `self` from `old_result` to `new_result`.
+    ///
+    /// Returns `true` if relinking was successful.
     pub fn relink_result(
         &mut self,
         module: &ModuleHeader,
         new_result: Register,
         old_result: Register,
     ) -> Result<bool, Error> {
+        let Some(result) = self.result_mut(module)? else {
+            return Ok(false);
+        };
+        if *result != old_result {
+            // Note: This is a safeguard to prevent miscompilations.
+            return Ok(false);
+        }
+        debug_assert_ne!(*result, new_result);
+        *result = new_result;
+        Ok(true)
+    }
+
+    /// Returns an exclusive reference to the single `result` [`Register`] of `self` if any.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` for [`Instruction`] that have no or multiple `result` [`Register`].
+    #[rustfmt::skip]
+    pub fn result_mut(&mut self, module: &ModuleHeader) -> Result<Option<&mut Register>, Error> {
         use Instruction as I;
         match self {
             I::TableIdx(_)
@@ -128,11 +150,11 @@ impl Instruction {
             | I::BranchF64Lt(_)
             | I::BranchF64Le(_)
             | I::BranchF64Gt(_)
-            | I::BranchF64Ge(_) => Ok(false),
+            | I::BranchF64Ge(_) => Ok(None),
             I::Copy { result, .. }
             | I::CopyImm32 { result, .. }
             | I::CopyI64Imm32 { result, .. }
-            | I::CopyF64Imm32 { result, .. } => relink_simple(result, new_result, old_result),
+            | I::CopyF64Imm32 { result, .. } => Ok(Some(result.result_mut())),
             I::CopySpan { .. }
             | I::CopySpanNonOverlapping { .. }
             | I::Copy2 { .. }
@@ -143,15 +165,15 @@ impl Instruction {
             | I::ReturnCallImported0 { .. }
             | I::ReturnCallImported { .. }
             | I::ReturnCallIndirect0 { .. }
-            | I::ReturnCallIndirect { .. } => Ok(false),
+            | I::ReturnCallIndirect { .. } => Ok(None),
             I::CallInternal0 { results, func } | I::CallInternal { results, func } => {
-                relink_call_internal(results, *func, module, new_result, old_result)
+                call_result_internal(results, *func, module)
             }
             I::CallImported0 { results, func } | I::CallImported { results, func } => {
-                relink_call_imported(results, *func, module, new_result, old_result)
+                call_result_imported(results, *func, module)
             }
             I::CallIndirect0 { results, func_type } | I::CallIndirect { results, func_type } => {
-                relink_call_indirect(results, *func_type, module, new_result, old_result)
+                call_result_indirect(results, *func_type, module)
             }
             I::Select { result, .. }
             | I::SelectRev { result, .. }
@@ -170,12 +192,12 @@ impl Instruction {
                 // Note: the `result_or_condition` necessarily points to the actual `result`
                 //       register since we make sure elsewhere that only the correct instruction
                 //       word is given to this method.
-                relink_simple(result, new_result, old_result)
+                Ok(Some(result.result_mut()))
             }
             I::RefFunc { result, .. }
             | I::TableGet { result, .. }
             | I::TableGetImm { result, .. }
-            | I::TableSize { result, .. } => relink_simple(result, new_result, old_result),
+            | I::TableSize { result, .. } => Ok(Some(result.result_mut())),
             I::TableSet { .. }
             | I::TableSetAt { .. }
             | I::TableCopy { .. }
@@ -197,14 +219,14 @@ impl Instruction {
             | I::TableFill { .. }
             | I::TableFillAt { .. }
             | I::TableFillExact { .. }
-            | I::TableFillAtExact { .. } => Ok(false),
+            | I::TableFillAtExact { .. } => Ok(None),
             I::TableGrow { result, .. } | I::TableGrowImm { result, .. } => {
-                relink_simple(result, new_result, old_result)
+                Ok(Some(result.result_mut()))
             }
-            I::ElemDrop(_) | I::DataDrop(_) => Ok(false),
+            I::ElemDrop(_) | I::DataDrop(_) => Ok(None),
             I::MemorySize { result }
             | I::MemoryGrow { result, .. }
-            | I::MemoryGrowBy { result, .. } => relink_simple(result, new_result, old_result),
+            | I::MemoryGrowBy { result, .. } => Ok(Some(result.result_mut())),
             I::MemoryCopy { .. }
             | I::MemoryCopyTo { .. }
             | I::MemoryCopyFrom { .. }
@@ -228,10 +250,12 @@ impl Instruction {
             | I::MemoryInitExact { .. }
             | I::MemoryInitToExact { .. }
             | I::MemoryInitFromExact { .. }
-            | I::MemoryInitFromToExact { .. } => Ok(false),
-            I::GlobalGet { result, .. } => relink_simple(result, new_result, old_result),
+            | I::MemoryInitFromToExact { .. } => Ok(None),
+            I::GlobalGet { result, .. } => Ok(Some(result.result_mut())),
+            I::LocalGetSpilled { result, .. } => Ok(Some(result.result_mut())),
+            I::LocalSetSpilled { .. } => Ok(None),
             I::GlobalSet { .. } | I::GlobalSetI32Imm16 { .. } | I::GlobalSetI64Imm16 { .. } => {
-                Ok(false)
+                Ok(None)
             }
             I::I32Load(instr) |
             I::I64Load(instr) |
@@ -246,7 +270,7 @@ impl Instruction {
             I::I64Load16s(instr) |
             I::I64Load16u(instr) |
             I::I64Load32s(instr) |
-            I::I64Load32u(instr) => relink_simple(instr, new_result, old_result),
+            I::I64Load32u(instr) => Ok(Some(instr.result_mut())),
             I::I32LoadAt(instr) |
             I::I64LoadAt(instr) |
             I::F32LoadAt(instr) |
@@ -260,8 +284,10 @@ impl Instruction {
             I::I64Load16sAt(instr) |
             I::I64Load16uAt(instr) |
             I::I64Load32sAt(instr) |
-            I::I64Load32uAt(instr) => relink_simple(instr, new_result, old_result),
+            I::I64Load32uAt(instr) => Ok(Some(instr.result_mut())),
             I::I32LoadOffset16(instr) |
+            I::I32LoadOffset16Add(instr) |
+            I::I32LoadOffset16AddImm(instr) |
             I::I64LoadOffset16(instr) |
             I::F32LoadOffset16(instr) |
             I::F64LoadOffset16(instr) |
@@ -274,10 +300,11 @@ impl Instruction {
             I::I64Load16sOffset16(instr) |
             I::I64Load16uOffset16(instr) |
             I::I64Load32sOffset16(instr) |
-            I::I64Load32uOffset16(instr) => relink_simple(instr, new_result, old_result),
+            I::I64Load32uOffset16(instr) => Ok(Some(instr.result_mut())),
             I::I32Store(_)
             | I::I32StoreOffset16(_)
             | I::I32StoreOffset16Imm16(_)
+            | I::I32StoreOffset16AddImm(_)
             | I::I32StoreAt(_)
             | I::I32StoreAtImm16(_)
             | I::I32Store8(_)
@@ -315,7 +342,74 @@ impl Instruction {
             | I::F32StoreAt(_)
             | I::F64Store(_)
             | I::F64StoreOffset16(_)
-            | I::F64StoreAt(_) => Ok(false),
+            | I::F64StoreAt(_) => Ok(None),
+            I::AtomicFence => Ok(None),
+            I::I32AtomicLoad(instr) |
+            I::I64AtomicLoad(instr) |
+            I::I32AtomicLoad8u(instr) |
+            I::I32AtomicLoad16u(instr) |
+            I::I64AtomicLoad8u(instr) |
+            I::I64AtomicLoad16u(instr) |
+            I::I64AtomicLoad32u(instr) => Ok(Some(instr.result_mut())),
+            I::I32AtomicStore(_)
+            | I::I64AtomicStore(_)
+            | I::I32AtomicStore8(_)
+            | I::I32AtomicStore16(_)
+            | I::I64AtomicStore8(_)
+            | I::I64AtomicStore16(_)
+            | I::I64AtomicStore32(_) => Ok(None),
+            I::MemoryAtomicNotify(instr) |
+            I::MemoryAtomicWait32(instr) |
+            I::MemoryAtomicWait64(instr) |
+            I::I32AtomicRmwAdd(instr) |
+            I::I64AtomicRmwAdd(instr) |
+            I::I32AtomicRmw8AddU(instr) |
+            I::I32AtomicRmw16AddU(instr) |
+            I::I64AtomicRmw8AddU(instr) |
+            I::I64AtomicRmw16AddU(instr) |
+            I::I64AtomicRmw32AddU(instr) |
+            I::I32AtomicRmwSub(instr) |
+            I::I64AtomicRmwSub(instr) |
+            I::I32AtomicRmw8SubU(instr) |
+            I::I32AtomicRmw16SubU(instr) |
+            I::I64AtomicRmw8SubU(instr) |
+            I::I64AtomicRmw16SubU(instr) |
+            I::I64AtomicRmw32SubU(instr) |
+            I::I32AtomicRmwAnd(instr) |
+            I::I64AtomicRmwAnd(instr) |
+            I::I32AtomicRmw8AndU(instr) |
+            I::I32AtomicRmw16AndU(instr) |
+            I::I64AtomicRmw8AndU(instr) |
+            I::I64AtomicRmw16AndU(instr) |
+            I::I64AtomicRmw32AndU(instr) |
+            I::I32AtomicRmwOr(instr) |
+            I::I64AtomicRmwOr(instr) |
+            I::I32AtomicRmw8OrU(instr) |
+            I::I32AtomicRmw16OrU(instr) |
+            I::I64AtomicRmw8OrU(instr) |
+            I::I64AtomicRmw16OrU(instr) |
+            I::I64AtomicRmw32OrU(instr) |
+            I::I32AtomicRmwXor(instr) |
+            I::I64AtomicRmwXor(instr) |
+            I::I32AtomicRmw8XorU(instr) |
+            I::I32AtomicRmw16XorU(instr) |
+            I::I64AtomicRmw8XorU(instr) |
+            I::I64AtomicRmw16XorU(instr) |
+            I::I64AtomicRmw32XorU(instr) |
+            I::I32AtomicRmwXchg(instr) |
+            I::I64AtomicRmwXchg(instr) |
+            I::I32AtomicRmw8XchgU(instr) |
+            I::I32AtomicRmw16XchgU(instr) |
+            I::I64AtomicRmw8XchgU(instr) |
+            I::I64AtomicRmw16XchgU(instr) |
+            I::I64AtomicRmw32XchgU(instr) |
+            I::I32AtomicRmwCmpxchg(instr) |
+            I::I64AtomicRmwCmpxchg(instr) |
+            I::I32AtomicRmw8CmpxchgU(instr) |
+            I::I32AtomicRmw16CmpxchgU(instr) |
+            I::I64AtomicRmw8CmpxchgU(instr) |
+            I::I64AtomicRmw16CmpxchgU(instr) |
+            I::I64AtomicRmw32CmpxchgU(instr) => Ok(Some(instr.result_mut())),
             I::I32Eq(instr) |
             I::I64Eq(instr) |
             I::I32Ne(instr) |
@@ -347,34 +441,34 @@ impl Instruction {
             I::F64Lt(instr) |
             I::F64Le(instr) |
             I::F64Gt(instr) |
-            I::F64Ge(instr) => relink_simple(instr, new_result, old_result),
+            I::F64Ge(instr) => Ok(Some(instr.result_mut())),
             I::I32EqImm16(instr) |
             I::I32NeImm16(instr) |
             I::I32LtSImm16(instr) |
             I::I32LeSImm16(instr) |
             I::I32GtSImm16(instr) |
-            I::I32GeSImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I32GeSImm16(instr) => Ok(Some(instr.result_mut())),
             I::I32LtUImm16(instr) |
             I::I32LeUImm16(instr) |
             I::I32GtUImm16(instr) |
-            I::I32GeUImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I32GeUImm16(instr) => Ok(Some(instr.result_mut())),
             I::I64EqImm16(instr) |
             I::I64NeImm16(instr) |
             I::I64LtSImm16(instr) |
             I::I64LeSImm16(instr) |
             I::I64GtSImm16(instr) |
-            I::I64GeSImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I64GeSImm16(instr) => Ok(Some(instr.result_mut())),
             I::I64LtUImm16(instr) |
             I::I64LeUImm16(instr) |
             I::I64GtUImm16(instr) |
-            I::I64GeUImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I64GeUImm16(instr) => Ok(Some(instr.result_mut())),
 
             I::I32Clz(instr) |
             I::I32Ctz(instr) |
             I::I32Popcnt(instr) |
             I::I64Clz(instr) |
             I::I64Ctz(instr) |
-            I::I64Popcnt(instr) => relink_simple(instr, new_result, old_result),
+            I::I64Popcnt(instr) => Ok(Some(instr.result_mut())),
 
             I::I32Add(instr) |
             I::I32Sub(instr) |
@@ -408,7 +502,7 @@ impl Instruction {
             I::I64ShrS(instr) |
             I::I64ShrU(instr) |
             I::I64Rotl(instr) |
-            I::I64Rotr(instr) => relink_simple(instr, new_result, old_result),
+            I::I64Rotr(instr) => Ok(Some(instr.result_mut())),
 
             I::F32Abs(instr) |
             I::F32Neg(instr) |
@@ -423,7 +517,7 @@ impl Instruction {
             I::F64Floor(instr) |
             I::F64Trunc(instr) |
             I::F64Nearest(instr) |
-            I::F64Sqrt(instr) => relink_simple(instr, new_result, old_result),
+            I::F64Sqrt(instr) => Ok(Some(instr.result_mut())),
 
             I::F32Add(instr) |
             I::F32Sub(instr) |
@@ -431,24 +525,24 @@ impl Instruction {
             I::F32Div(instr) |
             I::F32Min(instr) |
             I::F32Max(instr) |
-            I::F32Copysign(instr) => relink_simple(instr, new_result, old_result),
+            I::F32Copysign(instr) => Ok(Some(instr.result_mut())),
             I::F64Add(instr) |
             I::F64Sub(instr) |
             I::F64Mul(instr) |
             I::F64Div(instr) |
             I::F64Min(instr) |
             I::F64Max(instr) |
-            I::F64Copysign(instr) => relink_simple(instr, new_result, old_result),
+            I::F64Copysign(instr) => Ok(Some(instr.result_mut())),
 
             I::F32CopysignImm(instr) |
-            I::F64CopysignImm(instr) => relink_simple(instr, new_result, old_result),
+            I::F64CopysignImm(instr) => Ok(Some(instr.result_mut())),
 
             I::I32AddImm16(instr) |
             I::I32SubImm16Rev(instr) |
-            I::I32MulImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I32DivSImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I32DivSImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I32RemSImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I32MulImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I32DivSImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I32DivSImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I32RemSImm16(instr) => Ok(Some(instr.result_mut())),
             I::I32RemSImm16Rev(instr) |
             I::I32AndEqzImm16(instr) |
             I::I32AndImm16(instr) |
@@ -465,18 +559,18 @@ impl Instruction {
             I::I32RotlImm(instr) |
             I::I32RotlImm16Rev(instr) |
             I::I32RotrImm(instr) |
-            I::I32RotrImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I32DivUImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I32DivUImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I32RemUImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I32RemUImm16Rev(instr) => relink_simple(instr, new_result, old_result),
+            I::I32RotrImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I32DivUImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I32DivUImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I32RemUImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I32RemUImm16Rev(instr) => Ok(Some(instr.result_mut())),
 
             I::I64AddImm16(instr) |
             I::I64SubImm16Rev(instr) |
-            I::I64MulImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I64DivSImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I64DivSImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I64RemSImm16(instr) => relink_simple(instr, new_result, old_result),
+            I::I64MulImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I64DivSImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I64DivSImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I64RemSImm16(instr) => Ok(Some(instr.result_mut())),
             I::I64RemSImm16Rev(instr) |
             I::I64AndImm16(instr) |
             I::I64OrImm16(instr) |
@@ -490,11 +584,11 @@ impl Instruction {
             I::I64RotlImm(instr) |
             I::I64RotlImm16Rev(instr) |
             I::I64RotrImm(instr) |
-            I::I64RotrImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I64DivUImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I64DivUImm16Rev(instr) => relink_simple(instr, new_result, old_result),
-            I::I64RemUImm16(instr) => relink_simple(instr, new_result, old_result),
-            I::I64RemUImm16Rev(instr) => relink_simple(instr, new_result, old_result),
+            I::I64RotrImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I64DivUImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I64DivUImm16Rev(instr) => Ok(Some(instr.result_mut())),
+            I::I64RemUImm16(instr) => Ok(Some(instr.result_mut())),
+            I::I64RemUImm16Rev(instr) => Ok(Some(instr.result_mut())),
 
             I::I32WrapI64(instr) |
             I::I64ExtendI32S(instr) |
@@ -529,29 +623,11 @@ impl Instruction {
             I::F64ConvertI32S(instr) |
             I::F64ConvertI32U(instr) |
             I::F64ConvertI64S(instr) |
-            I::F64ConvertI64U(instr) => relink_simple(instr, new_result, old_result),
+            I::F64ConvertI64U(instr) => Ok(Some(instr.result_mut())),
         }
     }
 }
 
-fn relink_simple<T>(
-    result: &mut T,
-    new_result: Register,
-    old_result: Register,
-) -> Result<bool, Error>
-where
-    T: ResultMut,
-{
-    let result = result.result_mut();
-    if *result != old_result {
-        // Note: This is a safeguard to prevent miscompilations.
-        return Ok(false);
-    }
-    debug_assert_ne!(*result, new_result);
-    *result = new_result;
-    Ok(true)
-}
-
 fn get_engine(module: &ModuleHeader) -> Engine {
     module.engine().upgrade().unwrap_or_else(|| {
         panic!(
@@ -561,13 +637,11 @@ fn get_engine(module: &ModuleHeader) -> Engine {
     })
 }
 
-fn relink_call_internal(
-    results: &mut RegisterSpan,
+fn call_result_internal<'a>(
+    results: &'a mut RegisterSpan,
     func: CompiledFunc,
     module: &ModuleHeader,
-    new_result: Register,
-    old_result: Register,
-) -> Result<bool, Error> {
+) -> Result<Option<&'a mut Register>, Error> {
     let Some(module_func) = module.get_func_index(func) else {
         panic!("missing module func for compiled func: {func:?}")
     };
@@ -575,43 +649,39 @@ fn relink_call_internal(
     let func_type = module.get_type_of_func(module_func);
     let len_results = engine.resolve_func_type(func_type, FuncType::len_results);
     if len_results != 1 {
-        return Ok(false);
+        return Ok(None);
     }
-    relink_simple(results.head_mut(), new_result, old_result)
+    Ok(Some(results.head_mut()))
 }
 
-fn relink_call_imported(
-    results: &mut RegisterSpan,
+fn call_result_imported<'a>(
+    results: &'a mut RegisterSpan,
     func: FuncIdx,
     module: &ModuleHeader,
-    new_result: Register,
-    old_result: Register,
-) -> Result<bool, Error> {
+) -> Result<Option<&'a mut Register>, Error> {
     let engine = get_engine(module);
     let func_idx = func.to_u32().into();
     let func_type = module.get_type_of_func(func_idx);
     let len_results = engine.resolve_func_type(func_type, |func_type| func_type.results().len());
     if len_results != 1 {
-        return Ok(false);
+        return Ok(None);
     }
-    relink_simple(results.head_mut(), new_result, old_result)
+    Ok(Some(results.head_mut()))
 }
 
-fn relink_call_indirect(
-    results: &mut RegisterSpan,
+fn call_result_indirect<'a>(
+    results: &'a mut RegisterSpan,
     func_type: SignatureIdx,
     module: &ModuleHeader,
-    new_result: Register,
-    old_result: Register,
-) -> Result<bool, Error> {
+) -> Result<Option<&'a mut Register>, Error> {
     let engine = get_engine(module);
     let func_type_idx = func_type.to_u32().into();
     let func_type = module.get_func_type(func_type_idx);
     let len_results = engine.resolve_func_type(func_type, |func_type| func_type.results().len());
     if len_results != 1 {
-        return Ok(false);
+        return Ok(None);
     }
-    relink_simple(results.head_mut(), new_result, old_result)
+    Ok(Some(results.head_mut()))
 }
 
 trait ResultMut {
@@ -630,6 +700,12 @@ impl ResultMut for LoadInstr {
     }
 }
 
+impl ResultMut for AtomicRmwInstr {
+    fn result_mut(&mut self) -> &mut Register {
+        &mut self.result
+    }
+}
+
 impl ResultMut for LoadAtInstr {
     fn result_mut(&mut self) -> &mut Register {
         &mut self.result
@@ -132,6 +132,43 @@ impl ValueStack {
         self.consts.len_consts() + self.reg_alloc.len_registers()
     }
 
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding function local constants.
+    pub fn len_cells(&self) -> u16 {
+        self.reg_alloc.len_registers()
+    }
+
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding inlined call frames.
+    ///
+    /// # Note
+    ///
+    /// This is the number of registers the function would use if no function had been inlined.
+    pub fn len_registers_uninlined(&self) -> u16 {
+        self.consts.len_consts() + self.reg_alloc.len_registers_uninlined()
+    }
+
+    /// Reserves registers for the dynamically allocated and preserved registers of a function body with `len_body` bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the [`RegisterAlloc`] is not in its initialization phase.
+    pub fn reserve_registers(&mut self, len_body: usize) {
+        self.reg_alloc.reserve_registers(len_body)
+    }
+
+    /// Reserves all registers not used by function parameters for dynamically allocated and preserved registers.
+    ///
+    /// # Panics
+    ///
+    /// If the [`RegisterAlloc`] is not in its initialization phase.
+    pub fn reserve_all_registers(&mut self) {
+        self.reg_alloc.reserve_all_registers()
+    }
+
+    /// Returns `true` if all registers not used by function parameters are reserved.
+    pub fn all_registers_reserved(&self) -> bool {
+        self.reg_alloc.all_registers_reserved()
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
@@ -142,11 +179,21 @@ impl ValueStack {
     ///
     /// If the [`RegisterAlloc`] is not in its initialization phase.
     pub fn register_locals(&mut self, amount: u32) -> Result<(), Error> {
-        self.providers.register_locals(amount);
-        self.reg_alloc.register_locals(amount)?;
+        let len_registered = self.reg_alloc.register_locals(amount)?;
+        self.providers.register_locals(len_registered);
         Ok(())
     }
 
+    /// Returns the number of spilled local variables.
+    pub fn len_spilled(&self) -> u32 {
+        self.reg_alloc.len_spilled()
+    }
+
+    /// Returns the spill slot of the local variable at `local_index` if it is spilled.
+    pub fn spilled_local(&self, local_index: u32) -> Option<u32> {
+        self.reg_alloc.spilled_local(local_index)
+    }
+
     /// Finishes the local variable registration phase.
     ///
     /// # Note
@@ -374,6 +421,20 @@ impl ValueStack {
         Ok(registers)
     }
 
+    /// Returns a [`RegisterSpan`] of `n` registers for the call frame of an inlined function.
+    ///
+    /// # Note
+    ///
+    /// - This procedure does not push anything onto the [`ValueStack`].
+    /// - The registers start at the same index as the next dynamically allocated register.
+    ///
+    /// # Errors
+    ///
+    /// If this procedure would allocate more registers than are available.
+    pub fn alloc_inlined_frame(&mut self, n: usize) -> Result<RegisterSpan, Error> {
+        self.reg_alloc.alloc_inlined_frame(n)
+    }
+
     /// Finalizes register allocation and allows to defragment the register space.
     pub fn finalize_alloc(&mut self) {
         self.reg_alloc.finalize_alloc()
@@ -75,11 +75,35 @@ pub struct RegisterAlloc {
     /// The current phase of the register allocation procedure.
     phase: AllocPhase,
     /// The combined number of registered function inputs and local variables.
+    ///
+    /// # Note
+    ///
+    /// This does not include spilled local variables.
     len_locals: u16,
+    /// The number of local variables that did not fit into the register space.
+    ///
+    /// # Note
+    ///
+    /// Those local variables are spilled into slots behind the registers of
+    /// the call frame and accessed via dedicated instructions.
+    len_spilled: u32,
+    /// The number of registers reserved for dynamically allocated and preserved registers.
+    ///
+    /// # Note
+    ///
+    /// Local variables that do not fit into the register space next to them are spilled.
+    len_reserved: u16,
     /// The index for the next dynamically allocated register.
     next_dynamic: i16,
     /// The maximum index registered for a dynamically allocated register.
     max_dynamic: i16,
+    /// The maximum index registered for a dynamically allocated register outside of inlined call frames.
+    ///
+    /// # Note
+    ///
+    /// This is used to charge fuel for the call frame of the function as if no
+    /// function had been inlined so that inlining does not alter fuel consumption.
+    max_dynamic_uninlined: i16,
     /// The minimum index registered for a preservation allocated register.
     min_preserve: i16,
     /// The offset for the defragmentation register index.
@@ -127,16 +151,26 @@ pub enum RegisterSpace {
 }
 
 impl RegisterAlloc {
-    /// The maximum amount of local variables (and function parameters) a function may define.
-    const MAX_LEN_LOCALS: u16 = i16::MAX as u16;
+    /// The granularity in which registers are reserved for dynamically allocated and preserved registers.
+    const RESERVE_GRANULARITY: u16 = 1024;
+
+    /// The maximum amount of registers reserved for dynamically allocated and preserved registers.
+    ///
+    /// # Note
+    ///
+    /// This leaves at least half of the register space to function parameters and local variables.
+    const MAX_LEN_RESERVED: u16 = (i16::MAX as u16) / 2 + 1;
 
     /// Resets the [`RegisterAlloc`] to start compiling a new function.
     pub fn reset(&mut self) {
         self.preservations.clear();
         self.phase = AllocPhase::Init;
         self.len_locals = 0;
+        self.len_spilled = 0;
+        self.len_reserved = Self::MAX_LEN_RESERVED;
         self.next_dynamic = 0;
         self.max_dynamic = 0;
+        self.max_dynamic_uninlined = 0;
         self.min_preserve = i16::MAX;
     }
 
@@ -176,6 +210,18 @@ impl RegisterAlloc {
         self.len_locals
     }
 
+    /// Returns the number of spilled local variables.
+    pub fn len_spilled(&self) -> u32 {
+        self.len_spilled
+    }
+
+    /// Returns the spill slot of the local variable at `local_index` if it is spilled.
+    pub fn spilled_local(&self, local_index: u32) -> Option<u32> {
+        local_index
+            .checked_sub(u32::from(self.len_locals))
+            .filter(|slot| *slot < self.len_spilled)
+    }
+
     /// Returns the minimum index of any dynamically allocated [`Register`].
     fn min_dynamic(&self) -> i16 {
         self.len_locals() as i16
@@ -186,32 +232,103 @@ impl RegisterAlloc {
         (i16::MAX as u16) - self.max_dynamic.abs_diff(self.min_preserve)
     }
 
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding inlined call frames.
+    pub fn len_registers_uninlined(&self) -> u16 {
+        (i16::MAX as u16) - self.max_dynamic_uninlined.abs_diff(self.min_preserve)
+    }
+
+    /// Reserves registers for the dynamically allocated and preserved registers of a function body with `len_body` bytes.
+    ///
+    /// # Note
+    ///
+    /// Every Wasm operator is encoded with at least one byte and usually allocates
+    /// at most a single register, thus `len_body` bounds the expression stack depth.
+    /// An additional [`Self::RESERVE_GRANULARITY`] is reserved for the other cases.
+    /// Functions with deeper expression stacks run out of registers once their
+    /// dynamically allocated and preserved registers meet and are translated again
+    /// after [`RegisterAlloc::reserve_all_registers`].
+    ///
+    /// # Panics
+    ///
+    /// If the current [`AllocPhase`] is not [`AllocPhase::Init`].
+    pub fn reserve_registers(&mut self, len_body: usize) {
+        assert!(matches!(self.phase, AllocPhase::Init));
+        let granularity = usize::from(Self::RESERVE_GRANULARITY);
+        let len_reserved = len_body
+            .div_ceil(granularity)
+            .saturating_add(1)
+            .saturating_mul(granularity);
+        self.len_reserved = u16::try_from(len_reserved)
+            .unwrap_or(u16::MAX)
+            .min(Self::MAX_LEN_RESERVED);
+    }
+
+    /// Reserves all registers not used by function parameters for the dynamically allocated and preserved registers.
+    ///
+    /// # Note
+    ///
+    /// This spills all local variables registered afterwards and is used to translate
+    /// functions again whose expression stacks did not fit into the registers reserved
+    /// via [`RegisterAlloc::reserve_registers`].
+    ///
+    /// # Panics
+    ///
+    /// If the current [`AllocPhase`] is not [`AllocPhase::Init`].
+    pub fn reserve_all_registers(&mut self) {
+        assert!(matches!(self.phase, AllocPhase::Init));
+        self.len_reserved = i16::MAX as u16;
+    }
+
+    /// Returns `true` if all registers not used by function parameters are reserved.
+    ///
+    /// # Note
+    ///
+    /// In this case the function cannot be translated with more registers for its expression stack.
+    pub fn all_registers_reserved(&self) -> bool {
+        self.len_reserved == i16::MAX as u16
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
+    /// Returns the number of registered local variables that have been assigned to registers.
+    ///
+    /// # Note
+    ///
+    /// Local variables that no longer fit into the register space next to
+    /// the registers reserved via [`RegisterAlloc::reserve_registers`] are spilled.
+    ///
     /// # Errors
     ///
-    /// If too many registers have been registered.
+    /// If too many local variables have been registered.
     ///
     /// # Panics
     ///
     /// If the current [`AllocPhase`] is not [`AllocPhase::Init`].
-    pub fn register_locals(&mut self, amount: u32) -> Result<(), Error> {
-        /// Bumps `len_locals` by `amount` if possible.
-        fn bump_locals(len_locals: u16, amount: u32) -> Option<u16> {
-            let amount = u16::try_from(amount).ok()?;
-            let new_len = len_locals.checked_add(amount)?;
-            if new_len >= RegisterAlloc::MAX_LEN_LOCALS {
-                return None;
-            }
-            Some(new_len)
-        }
+    pub fn register_locals(&mut self, amount: u32) -> Result<u32, Error> {
         assert!(matches!(self.phase, AllocPhase::Init));
-        self.len_locals = bump_locals(self.len_locals, amount)
+        let len_free = if self.len_spilled == 0 {
+            (i16::MAX as u16)
+                .saturating_sub(self.len_reserved)
+                .saturating_sub(self.len_locals)
+        } else {
+            // Note: Once local variables have been spilled all following
+            //       local variables are spilled as well so that spilled
+            //       local variables are consecutive.
+            0
+        };
+        let len_registered = min(amount, u32::from(len_free));
+        let len_spilled = amount - len_registered;
+        self.len_spilled = self
+            .len_spilled
+            .checked_add(len_spilled)
             .ok_or_else(|| Error::from(TranslationError::AllocatedTooManyRegisters))?;
+        // Note: `len_registered` fits into `u16` since it is at most `len_free`.
+        self.len_locals += len_registered as u16;
         // We can convert `len_locals` to `i16` because it is always without bounds of `0..i16::MAX`.
         self.next_dynamic = self.len_locals as i16;
         self.max_dynamic = self.len_locals as i16;
-        Ok(())
+        self.max_dynamic_uninlined = self.len_locals as i16;
+        Ok(len_registered)
     }
 
     /// Finishes [`AllocPhase::Init`].
@@ -249,6 +366,7 @@ impl RegisterAlloc {
         let reg = Register::from_i16(self.next_dynamic);
         self.next_dynamic += 1;
         self.max_dynamic = max(self.max_dynamic, self.next_dynamic);
+        self.max_dynamic_uninlined = max(self.max_dynamic_uninlined, self.next_dynamic);
         Ok(reg)
     }
 
@@ -271,6 +389,7 @@ impl RegisterAlloc {
             let register = RegisterSpan::new(Register::from_i16(this.next_dynamic));
             this.next_dynamic += n;
             this.max_dynamic = max(this.max_dynamic, this.next_dynamic);
+            this.max_dynamic_uninlined = max(this.max_dynamic_uninlined, this.next_dynamic);
             Some(register)
         }
         self.assert_alloc_phase();
@@ -278,6 +397,28 @@ impl RegisterAlloc {
             .ok_or_else(|| Error::from(TranslationError::AllocatedTooManyRegisters))
     }
 
+    /// Allocates `n` registers for the call frame of an inlined function and returns them.
+    ///
+    /// # Note
+    ///
+    /// - The registers are not pushed onto the dynamic allocation stack.
+    /// - The registers are not accounted for by [`RegisterAlloc::len_registers_uninlined`].
+    ///
+    /// # Errors
+    ///
+    /// If too many registers have been registered.
+    ///
+    /// # Panics
+    ///
+    /// If the current [`AllocPhase`] is not [`AllocPhase::Alloc`].
+    pub fn alloc_inlined_frame(&mut self, n: usize) -> Result<RegisterSpan, Error> {
+        let max_dynamic_uninlined = self.max_dynamic_uninlined;
+        let registers = self.push_dynamic_n(n)?;
+        self.pop_dynamic_n(n);
+        self.max_dynamic_uninlined = max_dynamic_uninlined;
+        Ok(registers)
+    }
+
     /// Pops the top-most dynamically allocated [`Register`] from the allocation stack.
     ///
     /// # Panics
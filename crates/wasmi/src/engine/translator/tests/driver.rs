@@ -185,6 +185,12 @@ impl TranslationTest {
         &self.config
     }
 
+    /// Applies `f` to the [`Config`] used for the test case.
+    pub fn configure(&mut self, f: impl FnOnce(&mut Config)) -> &mut Self {
+        f(&mut self.config);
+        self
+    }
+
     /// Returns the WebAssembly bytes used for the test case.
     fn wasm(&self) -> &[u8] {
         &self.wasm
@@ -0,0 +1,211 @@
+//! Tests for inlining of small leaf functions enabled via [`Config::inline_budget`].
+
+use super::*;
+use crate::engine::{CompiledFunc, RegisterSpan};
+
+/// Returns a [`TranslationTest`] for `wasm` with an inline budget of `budget`.
+fn inline_test(wasm: &[u8], budget: u32) -> TranslationTest {
+    let mut test = TranslationTest::new(wasm);
+    test.configure(|config| {
+        config.inline_budget(budget);
+    });
+    test
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn identity() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32) (result i32)
+                (local.get 0)
+            )
+            (func (param i32) (result i32)
+                (call $f (local.get 0))
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 10)
+        .expect_func_instrs([Instruction::return_reg(Register::from_i16(0))])
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn binary() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+            (func (param i32 i32) (result i32)
+                (call $f (local.get 1) (local.get 0))
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 10)
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .expect_func_instrs([
+            Instruction::copy2(
+                RegisterSpan::new(Register::from_i16(2)),
+                Register::from_i16(1),
+                Register::from_i16(0),
+            ),
+            Instruction::i32_add(
+                Register::from_i16(4),
+                Register::from_i16(2),
+                Register::from_i16(3),
+            ),
+            Instruction::copy(Register::from_i16(2), Register::from_i16(4)),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn const_params() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (result i32)
+                (call $f (i32.const 10))
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 10)
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::copy(Register::from_i16(0), Register::from_i16(-1)),
+                Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1),
+                Instruction::copy(Register::from_i16(0), Register::from_i16(1)),
+                Instruction::return_reg(Register::from_i16(0)),
+            ])
+            .consts([10_i32]),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn uninit_local() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32) (result i32)
+                (local i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+            (func (param i32) (result i32)
+                (call $f (local.get 0))
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 10)
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::copy_imm32(Register::from_i16(2), 0_i32),
+            Instruction::i32_add(
+                Register::from_i16(3),
+                Register::from_i16(1),
+                Register::from_i16(2),
+            ),
+            Instruction::copy(Register::from_i16(1), Register::from_i16(3)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn exceeds_budget() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (param i32) (result i32)
+                (call $f (local.get 0))
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 1)
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .expect_func_instrs([
+            Instruction::call_internal(
+                RegisterSpan::new(Register::from_i16(1)),
+                CompiledFunc::from_u32(0),
+            ),
+            Instruction::register(0),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn not_a_leaf() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "g" (func $g))
+            (func $f
+                (call $g)
+            )
+            (func
+                (call $f)
+            )
+        )
+    "#,
+    );
+    inline_test(&wasm, 10)
+        .expect_func_instrs([
+            Instruction::call_imported_0(RegisterSpan::new(Register::from_i16(0)), 0),
+            Instruction::Return,
+        ])
+        .expect_func_instrs([
+            Instruction::call_internal_0(
+                RegisterSpan::new(Register::from_i16(0)),
+                CompiledFunc::from_u32(0),
+            ),
+            Instruction::Return,
+        ])
+        .run();
+}
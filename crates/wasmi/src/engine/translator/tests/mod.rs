@@ -2,8 +2,12 @@
 
 mod display_wasm;
 pub mod driver;
+mod inline;
 mod op;
+mod optimize;
 mod regression;
+mod spill;
+mod superinstr;
 pub mod wasm_type;
 
 use self::{
@@ -0,0 +1,243 @@
+//! Tests for the post-translation optimizations enabled via [`OptLevel::Speed`].
+
+use super::*;
+use crate::{
+    engine::bytecode::{BranchOffset, BranchOffset16},
+    OptLevel,
+};
+
+/// Asserts that `wasm` translates to `before` without and to `after` with optimizations.
+fn assert_optimized<B, A>(wasm: &[u8], before: B, after: A)
+where
+    B: IntoIterator<Item = Instruction>,
+    A: IntoIterator<Item = Instruction>,
+{
+    TranslationTest::new(wasm).expect_func_instrs(before).run();
+    TranslationTest::new(wasm)
+        .configure(|config| {
+            config.opt_level(OptLevel::Speed);
+        })
+        .expect_func_instrs(after)
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn redundant_copy() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (local.set 1 (local.get 0))
+                (local.set 1 (local.get 0))
+                (local.get 1)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+        [
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dead_copy() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32 i32)
+                (local.set 1 (local.get 0))
+                (local.set 2 (i32.const 10))
+                (local.get 0)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::copy_imm32(Register::from_i16(2), 10_i32),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+        [Instruction::return_reg(Register::from_i16(0))],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dead_pure_instr() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32)
+                (local i32 i32)
+                (local.set 2 (i32.add (local.get 0) (local.get 1)))
+                (local.set 3 (i32.clz (local.get 1)))
+                (i32.div_s (local.get 0) (local.get 1))
+                (drop)
+                (local.get 0)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::i32_clz(Register::from_i16(3), Register::from_i16(1)),
+            Instruction::i32_div_s(
+                Register::from_i16(4),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+        [
+            Instruction::i32_div_s(
+                Register::from_i16(4),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_threading() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32)
+                (block
+                    (block
+                        (br_if 0 (local.get 0))
+                        (local.set 1 (i32.const 10))
+                    )
+                    (br 0)
+                )
+                (local.get 1)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::branch_i32_nez(Register::from_i16(0), BranchOffset16::from(2)),
+            Instruction::copy_imm32(Register::from_i16(1), 10_i32),
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+        [
+            Instruction::branch_i32_nez(Register::from_i16(0), BranchOffset16::from(3)),
+            Instruction::copy_imm32(Register::from_i16(1), 10_i32),
+            Instruction::return_reg(Register::from_i16(1)),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_to_return() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (if (local.get 0)
+                    (then
+                        (local.set 1 (i32.const 10))
+                    )
+                    (else
+                        (local.set 1 (i32.const 20))
+                    )
+                )
+                (local.get 1)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::branch_i32_eqz(Register::from_i16(0), BranchOffset16::from(3)),
+            Instruction::copy_imm32(Register::from_i16(1), 10_i32),
+            Instruction::branch(BranchOffset::from(2)),
+            Instruction::copy_imm32(Register::from_i16(1), 20_i32),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+        [
+            Instruction::branch_i32_eqz(Register::from_i16(0), BranchOffset16::from(3)),
+            Instruction::copy_imm32(Register::from_i16(1), 10_i32),
+            Instruction::return_reg(Register::from_i16(1)),
+            Instruction::copy_imm32(Register::from_i16(1), 20_i32),
+            Instruction::return_reg(Register::from_i16(1)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dead_copy_adjusts_branches() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (local i32)
+                (block
+                    (br_if 0 (local.get 0))
+                    (local.set 1 (i32.const 10))
+                    (local.set 0 (i32.const 20))
+                )
+                (local.get 0)
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [
+            Instruction::branch_i32_nez(Register::from_i16(0), BranchOffset16::from(3)),
+            Instruction::copy_imm32(Register::from_i16(1), 10_i32),
+            Instruction::copy_imm32(Register::from_i16(0), 20_i32),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+        [
+            Instruction::branch_i32_nez(Register::from_i16(0), BranchOffset16::from(2)),
+            Instruction::copy_imm32(Register::from_i16(0), 20_i32),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn branch_cycle() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func
+                (loop (br 0))
+            )
+        )",
+    );
+    assert_optimized(
+        &wasm,
+        [Instruction::branch(BranchOffset::from(0))],
+        [Instruction::branch(BranchOffset::from(0))],
+    )
+}
@@ -0,0 +1,133 @@
+//! Tests for functions with more local variables than fit into the register space.
+
+use super::*;
+use crate::{Linker, Store};
+use std::string::String;
+
+/// The number of local variables that are assigned to registers for function bodies with less than 1024 bytes.
+const LEN_REGISTER_LOCALS: i16 = i16::MAX - 2048;
+
+/// Returns the `wat` of a function with `len_locals` additional `i32` local variables and `body`.
+fn wat_with_locals(len_locals: usize, body: &str) -> String {
+    let locals = "i32 ".repeat(len_locals);
+    format!(
+        r#"
+        (module
+            (func (export "f") (param i32) (result i32)
+                (local {locals})
+                {body}
+            )
+        )
+    "#
+    )
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn local_get_set() {
+    let wasm = wat2wasm(&wat_with_locals(
+        40_000,
+        "(local.set 39000 (local.get 0)) (local.get 39000)",
+    ));
+    let result = Register::from_i16(LEN_REGISTER_LOCALS);
+    let len_cells = LEN_REGISTER_LOCALS as u32 + 1;
+    let slot = len_cells + 39000 - LEN_REGISTER_LOCALS as u32;
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::local_set_spilled(slot, Register::from_i16(0)),
+            Instruction::local_get_spilled(result, slot),
+            Instruction::return_reg(result),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn local_tee_imm() {
+    let wasm = wat2wasm(&wat_with_locals(
+        40_000,
+        "(local.tee 40000 (i32.const 100000))",
+    ));
+    let len_cells = LEN_REGISTER_LOCALS as u32;
+    let slot = len_cells + 40000 - LEN_REGISTER_LOCALS as u32;
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::local_set_spilled(slot, Register::from_i16(-1)),
+                Instruction::return_imm32(100_000_i32),
+            ])
+            .consts([100_000_i32]),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn register_locals_unaffected() {
+    let wasm = wat2wasm(&wat_with_locals(
+        40_000,
+        "(local.set 100 (local.get 0)) (local.get 100)",
+    ));
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(100), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(100)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn many_locals_not_spilled() {
+    let wasm = wat2wasm(&wat_with_locals(
+        30_000,
+        "(local.set 29000 (local.get 0)) (local.get 29000)",
+    ));
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(29000), Register::from_i16(0)),
+            Instruction::return_reg(Register::from_i16(29000)),
+        ])
+        .run();
+}
+
+/// Returns the `wat` of a function with `len_locals` local variables and an expression stack of `depth` values.
+fn wat_with_deep_stack(len_locals: usize, depth: usize) -> String {
+    let push = "(i32.add (local.get 0) (local.get 1))".repeat(depth);
+    let drop = "(drop)".repeat(depth - 1);
+    wat_with_locals(len_locals, &format!("{push} {drop}"))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deep_stack_next_to_locals() {
+    let wasm = wat2wasm(&wat_with_deep_stack(30_000, 2_000));
+    Module::new(&Engine::default(), &wasm[..]).unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deep_stack_spills_all_locals() {
+    // Note: the expression stack does not fit next to the local variables
+    //       so the function is translated again with all its locals spilled.
+    let push = "(i32.add (local.get 0) (local.get 39000))".repeat(17_000);
+    let add = "(i32.add)".repeat(16_999);
+    let wasm = wat2wasm(&wat_with_locals(
+        40_000,
+        &format!("(local.set 39000 (i32.const 1)) {push} {add}"),
+    ));
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<i32, i32>(&store, "f")
+        .unwrap()
+        .call(&mut store, 2)
+        .unwrap();
+    assert_eq!(result, 17_000 * 3);
+}
@@ -0,0 +1,236 @@
+//! Tests for the fusion of frequent instruction pairs into superinstructions.
+
+use super::*;
+use crate::engine::RegisterSpan;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_add() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i32) (result i32)
+                (i32.add
+                    (i32.load offset=4 (local.get 0))
+                    (local.get 1)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::register(1),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_add_rev() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i32) (result i32)
+                (i32.add
+                    (local.get 1)
+                    (i32.load offset=4 (local.get 0))
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::register(1),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_add_imm() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32) (result i32)
+                (i32.add
+                    (i32.load offset=4 (local.get 0))
+                    (i32.const -5)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16_add_imm(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::const32(-5),
+            Instruction::return_reg(Register::from_i16(1)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn store_add_imm() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i32)
+                (i32.store offset=8
+                    (local.get 0)
+                    (i32.add (local.get 1) (i32.const 3))
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_store_offset16_add_imm(
+                Register::from_i16(0),
+                8,
+                Register::from_i16(1),
+            ),
+            Instruction::const32(3),
+            Instruction::Return,
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn load_local_tee_add() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func (param i32 i32) (result i32)
+                (local i32)
+                (i32.add
+                    (local.tee 2 (i32.load offset=4 (local.get 0)))
+                    (local.get 1)
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_load_offset16(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                <Const16<u32>>::from(4),
+            ),
+            Instruction::i32_add(
+                Register::from_i16(3),
+                Register::from_i16(2),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(3)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn copy_chain() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32 i32) (result i32)
+                (local i32 i32)
+                (local.set 2 (local.get 1))
+                (local.set 3 (local.get 0))
+                (i32.sub (local.get 2) (local.get 3))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy2(RegisterSpan::new(Register::from_i16(2)), 1, 0),
+            Instruction::i32_sub(
+                Register::from_i16(4),
+                Register::from_i16(2),
+                Register::from_i16(3),
+            ),
+            Instruction::return_reg(Register::from_i16(4)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn copy_chain_dependent() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32) (result i32)
+                (local i32 i32)
+                (local.set 1 (local.get 0))
+                (local.set 2 (local.get 1))
+                (i32.sub (local.get 1) (local.get 2))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(1, 0),
+            Instruction::copy(2, 1),
+            Instruction::i32_sub(
+                Register::from_i16(3),
+                Register::from_i16(1),
+                Register::from_i16(2),
+            ),
+            Instruction::return_reg(Register::from_i16(3)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn copy_chain_across_loop_header() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param i32 i32)
+                (local i32 i32)
+                (local.set 2 (local.get 1))
+                (loop
+                    (local.set 3 (local.get 0))
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(2, 1),
+            Instruction::copy(3, 0),
+            Instruction::Return,
+        ])
+        .run();
+}
@@ -62,6 +62,9 @@ macro_rules! impl_visit_operator {
     ( @tail_call $($rest:tt)* ) => {
         impl_visit_operator!(@@skipped $($rest)*);
     };
+    ( @threads $($rest:tt)* ) => {
+        impl_visit_operator!(@@skipped $($rest)*);
+    };
     ( @@skipped $op:ident $({ $($arg:ident: $argty:ty),* })? => $visit:ident $($rest:tt)* ) => {
         // We skip Wasm operators that we already implement manually.
         impl_visit_operator!($($rest)*);
@@ -638,6 +641,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.bump_fuel_consumption(FuelCosts::call)?;
         let func_idx = FuncIdx::from(function_index);
         let func_type = self.func_type_of(func_idx);
+        if let Some(compiled_func) = self.module.get_compiled_func(func_idx) {
+            if self.try_inline_call(compiled_func, &func_type)? {
+                return Ok(());
+            }
+        }
         let (params, results) = func_type.params_results();
         let provider_params = &mut self.alloc.buffer;
         self.alloc.stack.pop_n(params.len(), provider_params);
@@ -799,6 +807,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_local_get(&mut self, local_index: u32) -> Self::Output {
         bail_unreachable!(self);
+        if let Some(slot) = self.alloc.stack.spilled_local(local_index) {
+            let result = self.alloc.stack.push_dynamic()?;
+            self.push_base_instr(Instruction::local_get_spilled(result, slot))?;
+            return Ok(());
+        }
         self.alloc.stack.push_local(local_index)?;
         self.alloc.instr_encoder.reset_last_instr();
         Ok(())
@@ -807,6 +820,9 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     fn visit_local_set(&mut self, local_index: u32) -> Self::Output {
         bail_unreachable!(self);
         let value = self.alloc.stack.pop();
+        if let Some(slot) = self.alloc.stack.spilled_local(local_index) {
+            return self.translate_local_set_spilled(slot, value);
+        }
         let local = Register::try_from(local_index)?;
         if let TypedProvider::Register(value) = value {
             if value == local {
@@ -833,6 +849,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     fn visit_local_tee(&mut self, local_index: u32) -> Self::Output {
         bail_unreachable!(self);
         let input = self.alloc.stack.peek();
+        if let Some(slot) = self.alloc.stack.spilled_local(local_index) {
+            // Note: Unlike for local variables in registers the value
+            //       remains on the stack since it was copied into the slot.
+            return self.translate_local_set_spilled(slot, input);
+        }
         self.visit_local_set(local_index)?;
         match input {
             Provider::Register(_register) => {
@@ -3530,4 +3551,274 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         self.push_fueled_instr(Instruction::table_size(result, table), FuelCosts::entity)?;
         Ok(())
     }
+
+    fn visit_memory_atomic_notify(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::memory_atomic_notify)
+    }
+
+    fn visit_memory_atomic_wait32(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::memory_atomic_wait32)
+    }
+
+    fn visit_memory_atomic_wait64(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::memory_atomic_wait64)
+    }
+
+    fn visit_atomic_fence(&mut self) -> Self::Output {
+        bail_unreachable!(self);
+        self.push_fueled_instr(Instruction::atomic_fence(), FuelCosts::base)?;
+        Ok(())
+    }
+
+    fn visit_i32_atomic_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i32_atomic_load)
+    }
+
+    fn visit_i64_atomic_load(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i64_atomic_load)
+    }
+
+    fn visit_i32_atomic_load8_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i32_atomic_load8_u)
+    }
+
+    fn visit_i32_atomic_load16_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i32_atomic_load16_u)
+    }
+
+    fn visit_i64_atomic_load8_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i64_atomic_load8_u)
+    }
+
+    fn visit_i64_atomic_load16_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i64_atomic_load16_u)
+    }
+
+    fn visit_i64_atomic_load32_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_load(memarg, Instruction::i64_atomic_load32_u)
+    }
+
+    fn visit_i32_atomic_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i32_atomic_store)
+    }
+
+    fn visit_i64_atomic_store(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i64_atomic_store)
+    }
+
+    fn visit_i32_atomic_store8(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i32_atomic_store8)
+    }
+
+    fn visit_i32_atomic_store16(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i32_atomic_store16)
+    }
+
+    fn visit_i64_atomic_store8(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i64_atomic_store8)
+    }
+
+    fn visit_i64_atomic_store16(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i64_atomic_store16)
+    }
+
+    fn visit_i64_atomic_store32(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_store(memarg, Instruction::i64_atomic_store32)
+    }
+
+    fn visit_i32_atomic_rmw_add(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_add)
+    }
+
+    fn visit_i64_atomic_rmw_add(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_add)
+    }
+
+    fn visit_i32_atomic_rmw8_add_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_add_u)
+    }
+
+    fn visit_i32_atomic_rmw16_add_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_add_u)
+    }
+
+    fn visit_i64_atomic_rmw8_add_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_add_u)
+    }
+
+    fn visit_i64_atomic_rmw16_add_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_add_u)
+    }
+
+    fn visit_i64_atomic_rmw32_add_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_add_u)
+    }
+
+    fn visit_i32_atomic_rmw_sub(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_sub)
+    }
+
+    fn visit_i64_atomic_rmw_sub(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_sub)
+    }
+
+    fn visit_i32_atomic_rmw8_sub_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_sub_u)
+    }
+
+    fn visit_i32_atomic_rmw16_sub_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_sub_u)
+    }
+
+    fn visit_i64_atomic_rmw8_sub_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_sub_u)
+    }
+
+    fn visit_i64_atomic_rmw16_sub_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_sub_u)
+    }
+
+    fn visit_i64_atomic_rmw32_sub_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_sub_u)
+    }
+
+    fn visit_i32_atomic_rmw_and(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_and)
+    }
+
+    fn visit_i64_atomic_rmw_and(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_and)
+    }
+
+    fn visit_i32_atomic_rmw8_and_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_and_u)
+    }
+
+    fn visit_i32_atomic_rmw16_and_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_and_u)
+    }
+
+    fn visit_i64_atomic_rmw8_and_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_and_u)
+    }
+
+    fn visit_i64_atomic_rmw16_and_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_and_u)
+    }
+
+    fn visit_i64_atomic_rmw32_and_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_and_u)
+    }
+
+    fn visit_i32_atomic_rmw_or(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_or)
+    }
+
+    fn visit_i64_atomic_rmw_or(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_or)
+    }
+
+    fn visit_i32_atomic_rmw8_or_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_or_u)
+    }
+
+    fn visit_i32_atomic_rmw16_or_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_or_u)
+    }
+
+    fn visit_i64_atomic_rmw8_or_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_or_u)
+    }
+
+    fn visit_i64_atomic_rmw16_or_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_or_u)
+    }
+
+    fn visit_i64_atomic_rmw32_or_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_or_u)
+    }
+
+    fn visit_i32_atomic_rmw_xor(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_xor)
+    }
+
+    fn visit_i64_atomic_rmw_xor(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_xor)
+    }
+
+    fn visit_i32_atomic_rmw8_xor_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_xor_u)
+    }
+
+    fn visit_i32_atomic_rmw16_xor_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_xor_u)
+    }
+
+    fn visit_i64_atomic_rmw8_xor_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_xor_u)
+    }
+
+    fn visit_i64_atomic_rmw16_xor_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_xor_u)
+    }
+
+    fn visit_i64_atomic_rmw32_xor_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_xor_u)
+    }
+
+    fn visit_i32_atomic_rmw_xchg(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw_xchg)
+    }
+
+    fn visit_i64_atomic_rmw_xchg(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw_xchg)
+    }
+
+    fn visit_i32_atomic_rmw8_xchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw8_xchg_u)
+    }
+
+    fn visit_i32_atomic_rmw16_xchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i32_atomic_rmw16_xchg_u)
+    }
+
+    fn visit_i64_atomic_rmw8_xchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw8_xchg_u)
+    }
+
+    fn visit_i64_atomic_rmw16_xchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw16_xchg_u)
+    }
+
+    fn visit_i64_atomic_rmw32_xchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_rmw(memarg, Instruction::i64_atomic_rmw32_xchg_u)
+    }
+
+    fn visit_i32_atomic_rmw_cmpxchg(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i32_atomic_rmw_cmpxchg)
+    }
+
+    fn visit_i64_atomic_rmw_cmpxchg(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i64_atomic_rmw_cmpxchg)
+    }
+
+    fn visit_i32_atomic_rmw8_cmpxchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i32_atomic_rmw8_cmpxchg_u)
+    }
+
+    fn visit_i32_atomic_rmw16_cmpxchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i32_atomic_rmw16_cmpxchg_u)
+    }
+
+    fn visit_i64_atomic_rmw8_cmpxchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i64_atomic_rmw8_cmpxchg_u)
+    }
+
+    fn visit_i64_atomic_rmw16_cmpxchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i64_atomic_rmw16_cmpxchg_u)
+    }
+
+    fn visit_i64_atomic_rmw32_cmpxchg_u(&mut self, memarg: wasmparser::MemArg) -> Self::Output {
+        self.translate_atomic_ternary(memarg, Instruction::i64_atomic_rmw32_cmpxchg_u)
+    }
 }
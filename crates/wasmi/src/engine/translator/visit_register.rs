@@ -1,4 +1,5 @@
 use crate::engine::bytecode::{
+    AtomicRmwInstr,
     BinInstr,
     BinInstrImm,
     BranchBinOpInstr,
@@ -194,8 +195,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::SelectImm32 { result_or_condition, .. } |
             Instruction::SelectI64Imm32 { result_or_condition, .. } |
             Instruction::SelectF64Imm32 { result_or_condition, .. } => f(result_or_condition),
+            Instruction::TableGet { result: _, index } => f(index),
             Instruction::RefFunc { .. } |
-            Instruction::TableGet { .. } |
             Instruction::TableGetImm { .. } |
             Instruction::TableSize { .. } => {},
             Instruction::TableSet { index, value } => visit_registers!(f, index, value),
@@ -251,6 +252,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::MemoryInitToExact { dst: _, src, len: _ } => f(src),
             Instruction::MemoryInitFromExact { dst, src: _, len: _ } => f(dst),
             Instruction::MemoryInitFromToExact { dst: _, src: _, len: _ } => {},
+            Instruction::LocalGetSpilled { result: _, slot: _ } => {},
+            Instruction::LocalSetSpilled { slot: _, value } => f(value),
             Instruction::GlobalGet { result: _, global: _ } => {},
             Instruction::GlobalSet { global: _, input } => f(input),
             Instruction::GlobalSetI32Imm16 { global: _, input: _ } |
@@ -258,6 +261,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::I32Load(instr) => instr.visit_input_registers(f),
             Instruction::I32LoadAt(instr) => instr.visit_input_registers(f),
             Instruction::I32LoadOffset16(instr) => instr.visit_input_registers(f),
+            Instruction::I32LoadOffset16Add(instr) => instr.visit_input_registers(f),
+            Instruction::I32LoadOffset16AddImm(instr) => instr.visit_input_registers(f),
             Instruction::I64Load(instr) => instr.visit_input_registers(f),
             Instruction::I64LoadAt(instr) => instr.visit_input_registers(f),
             Instruction::I64LoadOffset16(instr) => instr.visit_input_registers(f),
@@ -300,6 +305,7 @@ impl VisitInputRegisters for Instruction {
             Instruction::I32Store(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreOffset16(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreOffset16Imm16(instr) => instr.visit_input_registers(f),
+            Instruction::I32StoreOffset16AddImm(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreAt(instr) => instr.visit_input_registers(f),
             Instruction::I32StoreAtImm16(instr) => instr.visit_input_registers(f),
             Instruction::I32Store8(instr) => instr.visit_input_registers(f),
@@ -338,6 +344,73 @@ impl VisitInputRegisters for Instruction {
             Instruction::F64Store(instr) => instr.visit_input_registers(f),
             Instruction::F64StoreOffset16(instr) => instr.visit_input_registers(f),
             Instruction::F64StoreAt(instr) => instr.visit_input_registers(f),
+            Instruction::AtomicFence => {},
+            Instruction::MemoryAtomicNotify(instr) => instr.visit_input_registers(f),
+            Instruction::MemoryAtomicWait32(instr) => instr.visit_input_registers(f),
+            Instruction::MemoryAtomicWait64(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicLoad(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicLoad(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicLoad8u(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicLoad16u(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicLoad8u(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicLoad16u(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicLoad32u(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicStore(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicStore(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicStore8(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicStore16(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicStore8(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicStore16(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicStore32(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwAdd(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwAdd(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8AddU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16AddU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8AddU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16AddU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32AddU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwSub(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwSub(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8SubU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16SubU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8SubU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16SubU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32SubU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwAnd(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwAnd(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8AndU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16AndU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8AndU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16AndU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32AndU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwOr(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwOr(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8OrU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16OrU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8OrU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16OrU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32OrU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwXor(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwXor(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8XorU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16XorU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8XorU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16XorU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32XorU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwXchg(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwXchg(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8XchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16XchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8XchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16XchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32XchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmwCmpxchg(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmwCmpxchg(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw8CmpxchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I32AtomicRmw16CmpxchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw8CmpxchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw16CmpxchgU(instr) => instr.visit_input_registers(f),
+            Instruction::I64AtomicRmw32CmpxchgU(instr) => instr.visit_input_registers(f),
             Instruction::I32Eq(instr) => instr.visit_input_registers(f),
             Instruction::I32EqImm16(instr) => instr.visit_input_registers(f),
             Instruction::I64Eq(instr) => instr.visit_input_registers(f),
@@ -586,6 +659,12 @@ impl<T> BranchBinOpInstrImm<T> {
     }
 }
 
+impl VisitInputRegisters for AtomicRmwInstr {
+    fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
+        visit_registers!(f, &mut self.ptr, &mut self.value)
+    }
+}
+
 impl VisitInputRegisters for StoreInstr {
     fn visit_input_registers(&mut self, mut f: impl FnMut(&mut Register)) {
         f(&mut self.ptr);
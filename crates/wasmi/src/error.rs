@@ -16,6 +16,9 @@ use core::{fmt, fmt::Display};
 use std::{boxed::Box, string::String};
 use wasmparser::BinaryReaderError as WasmError;
 
+#[cfg(feature = "component-model")]
+use crate::component::ComponentError;
+
 /// The generic Wasmi root error type.
 #[derive(Debug)]
 pub struct Error {
@@ -167,6 +170,9 @@ pub enum ErrorKind {
     Wasm(WasmError),
     /// Encountered when there is a Wasm to Wasmi translation error.
     Translation(TranslationError),
+    /// A Wasm component error.
+    #[cfg(feature = "component-model")]
+    Component(ComponentError),
 }
 
 impl ErrorKind {
@@ -231,6 +237,8 @@ impl Display for ErrorKind {
             Self::Read(error) => Display::fmt(error, f),
             Self::Wasm(error) => Display::fmt(error, f),
             Self::Translation(error) => Display::fmt(error, f),
+            #[cfg(feature = "component-model")]
+            Self::Component(error) => Display::fmt(error, f),
         }
     }
 }
@@ -262,6 +270,15 @@ impl_from! {
     impl From<FuncError> for Error::Func;
 }
 
+#[cfg(feature = "component-model")]
+impl From<ComponentError> for Error {
+    #[inline]
+    #[cold]
+    fn from(error: ComponentError) -> Self {
+        Self::from_kind(ErrorKind::Component(error))
+    }
+}
+
 /// An error that can occur upon `memory.grow` or `table.grow`.
 #[derive(Copy, Clone)]
 pub enum EntityGrowError {
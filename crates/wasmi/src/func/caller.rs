@@ -1,5 +1,17 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{store::FuelError, Engine, Extern, Instance};
+use crate::{
+    errors::MemoryError,
+    store::FuelError,
+    Engine,
+    Extern,
+    Global,
+    Instance,
+    Memory,
+    MemoryView,
+    MemoryViewMut,
+    Pod,
+    Table,
+};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
@@ -34,6 +46,42 @@ impl<'a, T> Caller<'a, T> {
             .and_then(|instance| instance.get_export(self, name))
     }
 
+    /// Returns the [`Instance`] of the caller if any.
+    ///
+    /// This is `None` if the host function was called from the host side.
+    pub fn instance(&self) -> Option<Instance> {
+        self.instance
+    }
+
+    /// Returns the linear memory at `index` of the caller's [`Instance`].
+    ///
+    /// Unlike [`Caller::get_export`] this also finds imported or non-exported memories.
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller's [`Instance`] has no linear memory at `index`.
+    pub fn get_memory(&self, index: u32) -> Option<Memory> {
+        self.instance
+            .and_then(|instance| instance.get_memory_by_index(self, index))
+    }
+
+    /// Returns the table at `index` of the caller's [`Instance`].
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller's [`Instance`] has no table at `index`.
+    pub fn get_table(&self, index: u32) -> Option<Table> {
+        self.instance
+            .and_then(|instance| instance.get_table_by_index(self, index))
+    }
+
+    /// Returns the global variable at `index` of the caller's [`Instance`].
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller's [`Instance`] has no global variable at `index`.
+    pub fn get_global(&self, index: u32) -> Option<Global> {
+        self.instance
+            .and_then(|instance| instance.get_global_by_index(self, index))
+    }
+
     /// Returns a shared reference to the user provided host data.
     pub fn data(&self) -> &T {
         self.ctx.store.data()
@@ -69,6 +117,13 @@ impl<'a, T> Caller<'a, T> {
         self.ctx.store.fuel_consumed()
     }
 
+    /// Returns the amount of fuel remaining for executions of the [`Store`](crate::Store).
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.ctx.store.remaining_fuel()
+    }
+
     /// Synthetically consumes an amount of fuel for the [`Store`](crate::Store).
     ///
     /// Returns the remaining amount of fuel after this operation.
@@ -80,6 +135,56 @@ impl<'a, T> Caller<'a, T> {
     pub fn consume_fuel(&mut self, delta: u64) -> Result<u64, FuelError> {
         self.ctx.store.consume_fuel(delta)
     }
+
+    /// Returns a shared view of `len` consecutive values of type `P` at `ptr` in `memory`.
+    ///
+    /// # Errors
+    ///
+    /// If the viewed values are not entirely within the bounds of `memory`.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from the same store as the [`Caller`].
+    pub fn view<P: Pod>(
+        &self,
+        memory: &Memory,
+        ptr: usize,
+        len: usize,
+    ) -> Result<MemoryView<'_, P>, MemoryError> {
+        memory.view(self, ptr, len)
+    }
+
+    /// Returns an exclusive view of `len` consecutive values of type `P` at `ptr` in `memory`.
+    ///
+    /// # Errors
+    ///
+    /// If the viewed values are not entirely within the bounds of `memory`.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from the same store as the [`Caller`].
+    pub fn view_mut<P: Pod>(
+        &mut self,
+        memory: &Memory,
+        ptr: usize,
+        len: usize,
+    ) -> Result<MemoryViewMut<'_, P>, MemoryError> {
+        memory.view_mut(self, ptr, len)
+    }
+
+    /// Returns the UTF-8 encoded string of `len` bytes at `ptr` in `memory`.
+    ///
+    /// # Errors
+    ///
+    /// - If the string is not entirely within the bounds of `memory`.
+    /// - If the string is not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from the same store as the [`Caller`].
+    pub fn read_str(&self, memory: &Memory, ptr: usize, len: usize) -> Result<&str, MemoryError> {
+        memory.read_str(self, ptr, len)
+    }
 }
 
 impl<T> AsContext for Caller<'_, T> {
@@ -98,11 +98,11 @@ pub trait WasmRet {
     fn into_fallible(self) -> Result<<Self as WasmRet>::Ok, Error>;
 }
 
-impl<T1> WasmRet for T1
+impl<T> WasmRet for T
 where
-    T1: WasmType,
+    T: WasmTypeList,
 {
-    type Ok = T1;
+    type Ok = T;
 
     #[inline]
     fn into_fallible(self) -> Result<Self::Ok, Error> {
@@ -110,11 +110,11 @@ where
     }
 }
 
-impl<T1> WasmRet for Result<T1, Error>
+impl<T> WasmRet for Result<T, Error>
 where
-    T1: WasmType,
+    T: WasmTypeList,
 {
-    type Ok = T1;
+    type Ok = T;
 
     #[inline]
     fn into_fallible(self) -> Result<<Self as WasmRet>::Ok, Error> {
@@ -122,39 +122,6 @@ where
     }
 }
 
-macro_rules! impl_wasm_return_type {
-    ( $n:literal $( $tuple:ident )* ) => {
-        impl<$($tuple),*> WasmRet for ($($tuple,)*)
-        where
-            $(
-                $tuple: WasmType
-            ),*
-        {
-            type Ok = ($($tuple,)*);
-
-            #[inline]
-            fn into_fallible(self) -> Result<Self::Ok, Error> {
-                Ok(self)
-            }
-        }
-
-        impl<$($tuple),*> WasmRet for Result<($($tuple,)*), Error>
-        where
-            $(
-                $tuple: WasmType
-            ),*
-        {
-            type Ok = ($($tuple,)*);
-
-            #[inline]
-            fn into_fallible(self) -> Result<<Self as WasmRet>::Ok, Error> {
-                self
-            }
-        }
-    };
-}
-for_each_tuple!(impl_wasm_return_type);
-
 /// Types that can be used as parameters or results of host functions.
 pub trait WasmType: From<UntypedValue> + Into<UntypedValue> + Send {
     /// Returns the value type of the Wasm type.
@@ -24,6 +24,8 @@ use super::{
 };
 use crate::{engine::ResumableCall, Engine, Error, Value};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
+use smallvec::SmallVec;
+use spin::Mutex;
 use std::{boxed::Box, sync::Arc};
 use wasmi_arena::ArenaIndex;
 
@@ -172,6 +174,9 @@ impl WasmFuncEntity {
     }
 }
 
+/// The maximum number of parameters and results of [`Func::new`] host functions kept on the stack.
+const MAX_INLINE_PARAMS_RESULTS: usize = 32;
+
 /// A host function instance.
 pub struct HostFuncTrampolineEntity<T> {
     /// The type of the associated host function.
@@ -213,16 +218,32 @@ impl<T> HostFuncTrampolineEntity<T> {
         let results_iter = ty.results().iter().copied().map(Value::default);
         let len_params = ty.params().len();
         let params_results: Box<[Value]> = params_iter.chain(results_iter).collect();
+        // Parameters and results that do not fit on the stack use a buffer which is reused
+        // across calls. Only if the host function is re-entered while the buffer is in use we clone it.
+        let buffer = (params_results.len() > MAX_INLINE_PARAMS_RESULTS)
+            .then(|| Mutex::new(params_results.clone()));
         let trampoline = <TrampolineEntity<T>>::new(move |caller, args| {
-            // We are required to clone the buffer because we are operating within a `Fn`.
-            // This way the trampoline closure only has to own a single slice buffer.
-            // Note: An alternative solution is to use interior mutability but that solution
-            //       comes with its own downsides.
-            let mut params_results = params_results.clone();
-            let (params, results) = params_results.split_at_mut(len_params);
-            let func_results = args.decode_params_into_slice(params).unwrap();
-            func(caller, params, results)?;
-            Ok(func_results.encode_results_from_slice(results).unwrap())
+            let call = |params_results: &mut [Value]| {
+                let (params, results) = params_results.split_at_mut(len_params);
+                let func_results = args.decode_params_into_slice(params).unwrap();
+                func(caller, params, results)?;
+                Ok(func_results.encode_results_from_slice(results).unwrap())
+            };
+            let Some(buffer) = &buffer else {
+                let mut inline = params_results
+                    .iter()
+                    .cloned()
+                    .collect::<SmallVec<[Value; MAX_INLINE_PARAMS_RESULTS]>>();
+                return call(&mut inline);
+            };
+            match buffer.try_lock() {
+                Some(mut buffer) => {
+                    // Reset results so that host functions observe the same initial values as before.
+                    buffer[len_params..].clone_from_slice(&params_results[len_params..]);
+                    call(&mut buffer)
+                }
+                None => call(&mut params_results.clone()),
+            }
         });
         let ty = engine.alloc_func_type(ty.clone());
         Self { ty, trampoline }
@@ -331,6 +352,10 @@ impl Func {
     /// - Prefer using [`Func::wrap`] over this method if possible since [`Func`] instances
     ///   created using this constructor have runtime overhead for every invocation that
     ///   can be avoided by using [`Func::wrap`].
+    /// - Use this method for host functions with more than 64 parameters or results
+    ///   which is the maximum supported by [`Func::wrap`]. Calls do not allocate:
+    ///   up to 32 parameters and results in total are kept on the stack and larger
+    ///   buffers are reused across invocations unless the host function is re-entered.
     pub fn new<T>(
         mut ctx: impl AsContextMut<UserState = T>,
         ty: FuncType,
@@ -78,6 +78,11 @@ impl InstanceEntity {
         InstanceEntityBuilder::new(module)
     }
 
+    /// Returns `true` if the [`InstanceEntity`] has been instantiated from the [`Module`].
+    pub fn is_instance_of(&self, module: &Module) -> bool {
+        Arc::ptr_eq(&self.func_types, module.func_types())
+    }
+
     /// Returns `true` if the [`InstanceEntity`] has been fully initialized.
     pub fn is_initialized(&self) -> bool {
         self.initialized
@@ -177,6 +182,48 @@ impl Instance {
             .get_func(index)
     }
 
+    /// Returns the linear memory at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_memory_by_index(&self, store: impl AsContext, index: u32) -> Option<Memory> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .get_memory(index)
+    }
+
+    /// Returns the table at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_table_by_index(&self, store: impl AsContext, index: u32) -> Option<Table> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .get_table(index)
+    }
+
+    /// Returns the global at the `index` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub(crate) fn get_global_by_index(&self, store: impl AsContext, index: u32) -> Option<Global> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .get_global(index)
+    }
+
     /// Returns the value exported to the given `name` if any.
     ///
     /// # Panics
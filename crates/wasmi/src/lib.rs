@@ -76,7 +76,7 @@
     clippy::default_trait_access,
     clippy::items_after_statements
 )]
-#![recursion_limit = "750"]
+#![recursion_limit = "1000"]
 
 #[cfg(not(feature = "std"))]
 extern crate alloc as std;
@@ -102,6 +102,15 @@ mod store;
 mod table;
 mod value;
 
+/// Runtime support for bindings generated by [`bindgen!`].
+#[cfg(feature = "derive")]
+pub mod bindgen;
+#[cfg(any(feature = "derive", feature = "component-model"))]
+mod canonical_abi;
+
+#[cfg(feature = "component-model")]
+pub mod component;
+
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
 pub use wasmi_core as core;
@@ -118,13 +127,20 @@ pub mod errors {
         store::FuelError,
         table::TableError,
     };
+
+    #[cfg(feature = "component-model")]
+    pub use super::component::ComponentError;
 }
 
+#[cfg(feature = "instr-profiling")]
+pub use self::engine::{InstrPair, InstrPairs, InstrProfile};
 pub use self::{
     engine::{
         CompilationMode,
         Config,
         Engine,
+        MemoryBackend,
+        OptLevel,
         ResumableCall,
         ResumableInvocation,
         StackLimits,
@@ -150,11 +166,23 @@ pub use self::{
     instance::{Export, ExportsIter, Extern, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
     linker::Linker,
-    memory::{Memory, MemoryType},
+    memory::{
+        LinearMemory,
+        Memory,
+        MemoryCreator,
+        MemoryParker,
+        MemoryType,
+        MemoryView,
+        MemoryViewMut,
+        ParkResult,
+        Pod,
+        SharedMemory,
+    },
     module::{
         ExportType,
         ImportType,
         InstancePre,
+        InstanceSnapshot,
         Module,
         ModuleExportsIter,
         ModuleImportsIter,
@@ -172,3 +200,9 @@ use self::{
     store::{StoreInner, Stored},
     table::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx, TableEntity, TableIdx},
 };
+/// Generates host and guest bindings for a WIT-like interface.
+#[cfg(feature = "derive")]
+pub use wasmi_derive::bindgen;
+/// Derives [`WasmTypeList`] for structs whose fields are all [`WasmType`].
+#[cfg(feature = "derive")]
+pub use wasmi_derive::WasmTypeList;
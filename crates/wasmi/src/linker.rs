@@ -11,7 +11,9 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    Instance,
     InstancePre,
+    InstanceSnapshot,
     IntoFunc,
     MemoryType,
     Module,
@@ -660,6 +662,40 @@ impl<T> Linker<T> {
         module.instantiate(context, externals)
     }
 
+    /// Instantiates the [`Module`] from an [`InstanceSnapshot`] of one of its instances.
+    ///
+    /// # Note
+    ///
+    /// - The internally defined linear memories, tables and global variables of the
+    ///   returned [`Instance`] are restored from the `snapshot` instead of being
+    ///   initialized by the data and element segments of the [`Module`].
+    /// - The `start` function of the [`Module`] is not executed since its effects
+    ///   are already part of the `snapshot`. Therefore the returned [`Instance`]
+    ///   is ready to use.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the `snapshot` has not been taken from an instance of the [`Module`].
+    /// - If the linker does not define imports of the instantiated [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    pub fn instantiate_snapshot(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+        module: &Module,
+        snapshot: &InstanceSnapshot,
+    ) -> Result<Instance, Error> {
+        assert!(Engine::same(self.engine(), context.as_context().engine()));
+        let externals = module
+            .imports()
+            .map(|import| self.process_import(&mut context, import))
+            .collect::<Result<Vec<Extern>, Error>>()?;
+        module.instantiate_snapshot(context, externals, snapshot)
+    }
+
     /// Processes a single [`Module`] import.
     ///
     /// # Panics
@@ -1,52 +1,274 @@
-use std::{vec, vec::Vec};
+#[cfg(all(feature = "std", target_os = "linux"))]
+use super::vmem::VirtualMemory;
+use super::{LinearMemory, MemoryError, MemoryImage, SharedMemory};
+use std::{boxed::Box, vec, vec::Vec};
 
-/// A `Vec`-based byte buffer implementation.
+/// A byte buffer underlying a linear memory.
 ///
 /// # Note
 ///
-/// This is less efficient than the byte buffer implementation that is
-/// based on actual OS provided virtual memory but it is a safe fallback
-/// solution fitting any platform.
+/// The byte buffer is either backed by a `Vec` which fits any platform,
+/// by OS provided virtual memory on platforms supporting it or by
+/// host provided memory.
 #[derive(Debug)]
 pub struct ByteBuffer {
-    bytes: Vec<u8>,
+    storage: Storage,
+}
+
+/// The storage backing a [`ByteBuffer`].
+#[derive(Debug)]
+enum Storage {
+    /// A `Vec`-based byte buffer.
+    ///
+    /// # Note
+    ///
+    /// This is less efficient than the byte buffer implementation that is
+    /// based on actual OS provided virtual memory but it is a safe fallback
+    /// solution fitting any platform.
+    Vec(Vec<u8>),
+    /// A byte buffer based on OS provided virtual memory.
+    ///
+    /// # Note
+    ///
+    /// This reserves the maximum size of the byte buffer upfront so
+    /// that growing it never reallocates or copies its bytes.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    Virtual(VirtualMemory),
+    /// A byte buffer provided by a [`MemoryCreator`](super::MemoryCreator).
+    Custom(Box<dyn LinearMemory>),
+    /// A byte buffer operating on a user provided static buffer.
+    ///
+    /// # Note
+    ///
+    /// Only the first `len` bytes of `buffer` are accessible.
+    /// This is primarily useful on `no_std` targets without a global allocator
+    /// suited for large allocations.
+    Static {
+        buffer: &'static mut [u8],
+        len: usize,
+    },
+    /// A byte buffer shared with other stores and threads.
+    Shared(SharedMemory),
 }
 
 impl ByteBuffer {
-    /// Creates a new byte buffer with the given initial length.
+    /// Creates a new `Vec`-based byte buffer with the given initial length.
+    pub fn new(initial_len: usize) -> Self {
+        Self {
+            storage: Storage::Vec(vec![0x00_u8; initial_len]),
+        }
+    }
+
+    /// Creates a new byte buffer backed by OS provided virtual memory.
+    ///
+    /// # Note
+    ///
+    /// The byte buffer reserves virtual memory for `maximum_len` bytes upfront
+    /// so that it can be grown in place up to `maximum_len` bytes.
+    ///
+    /// Falls back to a `Vec`-based byte buffer on platforms that do not
+    /// support virtual memory.
     ///
     /// # Errors
     ///
-    /// - If the initial length is 0.
-    /// - If the initial length exceeds the maximum supported limit.
-    pub fn new(initial_len: usize) -> Self {
+    /// If the OS fails to reserve the required virtual memory.
+    pub fn new_virtual(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        #[cfg(all(feature = "std", target_os = "linux"))]
+        {
+            let memory = VirtualMemory::new(initial_len, maximum_len)?;
+            Ok(Self {
+                storage: Storage::Virtual(memory),
+            })
+        }
+        #[cfg(not(all(feature = "std", target_os = "linux")))]
+        {
+            let _ = maximum_len;
+            Ok(Self::new(initial_len))
+        }
+    }
+
+    /// Creates a new byte buffer like [`ByteBuffer::new_virtual`] initialized with the `image`.
+    ///
+    /// # Note
+    ///
+    /// On platforms supporting virtual memory the bytes of the `image` are mapped
+    /// copy-on-write if possible. Otherwise they are copied into the byte buffer.
+    ///
+    /// # Errors
+    ///
+    /// - If the OS fails to reserve the required virtual memory or to map the `image`.
+    /// - If the `image` is larger than `initial_len`.
+    pub fn new_virtual_with_image(
+        initial_len: usize,
+        maximum_len: usize,
+        image: &MemoryImage,
+    ) -> Result<Self, MemoryError> {
+        #[cfg(all(feature = "std", target_os = "linux"))]
+        {
+            let memory = VirtualMemory::with_image(initial_len, maximum_len, image)?;
+            Ok(Self {
+                storage: Storage::Virtual(memory),
+            })
+        }
+        #[cfg(not(all(feature = "std", target_os = "linux")))]
+        {
+            let mut buffer = Self::new_virtual(initial_len, maximum_len)?;
+            image.copy_to(buffer.data_mut())?;
+            Ok(buffer)
+        }
+    }
+
+    /// Creates a new byte buffer from host provided [`LinearMemory`].
+    ///
+    /// # Errors
+    ///
+    /// - If the size of `memory` does not match `initial_len`.
+    /// - If the bytes of `memory` are not zero initialized.
+    pub fn new_custom(
+        memory: Box<dyn LinearMemory>,
+        initial_len: usize,
+    ) -> Result<Self, MemoryError> {
+        if memory.byte_size() != initial_len || memory.data().len() != initial_len {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        if memory.data().iter().any(|&byte| byte != 0x00_u8) {
+            return Err(MemoryError::UninitializedMemory);
+        }
+        Ok(Self {
+            storage: Storage::Custom(memory),
+        })
+    }
+
+    /// Creates a new byte buffer operating on the user provided static `buffer`.
+    ///
+    /// The first `initial_len` bytes of `buffer` are zero initialized.
+    ///
+    /// # Errors
+    ///
+    /// If `buffer` is smaller than `initial_len` bytes.
+    pub fn new_static(buffer: &'static mut [u8], initial_len: usize) -> Result<Self, MemoryError> {
+        let Some(bytes) = buffer.get_mut(..initial_len) else {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        };
+        bytes.fill(0x00_u8);
+        Ok(Self {
+            storage: Storage::Static {
+                buffer,
+                len: initial_len,
+            },
+        })
+    }
+
+    /// Creates a new byte buffer operating on the bytes of the `shared` memory.
+    pub fn new_shared(shared: SharedMemory) -> Self {
         Self {
-            bytes: vec![0x00_u8; initial_len],
+            storage: Storage::Shared(shared),
+        }
+    }
+
+    /// Returns the [`SharedMemory`] of the byte buffer if it is shared.
+    pub fn shared(&self) -> Option<&SharedMemory> {
+        match &self.storage {
+            Storage::Shared(shared) => Some(shared),
+            _ => None,
         }
     }
 
     /// Grows the byte buffer to the given `new_size`.
     ///
+    /// # Errors
+    ///
+    /// If the byte buffer cannot be grown to `new_size` bytes.
+    ///
     /// # Panics
     ///
     /// If the current size of the [`ByteBuffer`] is larger than `new_size`.
-    pub fn grow(&mut self, new_size: usize) {
+    ///
+    /// # Note
+    ///
+    /// For shared byte buffers the caller must hold the lock returned by
+    /// [`SharedMemory::lock_growth`].
+    pub fn grow(&mut self, new_size: usize) -> Result<(), MemoryError> {
         assert!(new_size >= self.len());
-        self.bytes.resize(new_size, 0x00_u8);
+        match &mut self.storage {
+            Storage::Vec(bytes) => {
+                bytes.resize(new_size, 0x00_u8);
+                Ok(())
+            }
+            #[cfg(all(feature = "std", target_os = "linux"))]
+            Storage::Virtual(memory) => memory.grow(new_size),
+            Storage::Custom(memory) => {
+                memory.grow_to(new_size)?;
+                if memory.byte_size() != new_size || memory.data().len() != new_size {
+                    return Err(MemoryError::OutOfBoundsGrowth);
+                }
+                Ok(())
+            }
+            Storage::Static { buffer, len } => {
+                let Some(bytes) = buffer.get_mut(*len..new_size) else {
+                    return Err(MemoryError::OutOfBoundsGrowth);
+                };
+                bytes.fill(0x00_u8);
+                *len = new_size;
+                Ok(())
+            }
+            Storage::Shared(shared) => shared.grow_to(new_size),
+        }
     }
 
     /// Returns the length of the byte buffer in bytes.
     pub fn len(&self) -> usize {
-        self.bytes.len()
+        match &self.storage {
+            Storage::Vec(bytes) => bytes.len(),
+            #[cfg(all(feature = "std", target_os = "linux"))]
+            Storage::Virtual(memory) => memory.len(),
+            Storage::Custom(memory) => memory.byte_size(),
+            Storage::Static { len, .. } => *len,
+            Storage::Shared(shared) => shared.data_size(),
+        }
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the byte buffer is backed by a [`SharedMemory`].
     pub fn data(&self) -> &[u8] {
-        &self.bytes[..]
+        match &self.storage {
+            Storage::Vec(bytes) => &bytes[..],
+            #[cfg(all(feature = "std", target_os = "linux"))]
+            Storage::Virtual(memory) => memory.data(),
+            Storage::Custom(memory) => memory.data(),
+            Storage::Static { buffer, len } => &buffer[..*len],
+            Storage::Shared(_) => shared_bytes_unavailable(),
+        }
     }
 
     /// Returns an exclusive slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the byte buffer is backed by a [`SharedMemory`].
     pub fn data_mut(&mut self) -> &mut [u8] {
-        &mut self.bytes[..]
+        match &mut self.storage {
+            Storage::Vec(bytes) => &mut bytes[..],
+            #[cfg(all(feature = "std", target_os = "linux"))]
+            Storage::Virtual(memory) => memory.data_mut(),
+            Storage::Custom(memory) => memory.data_mut(),
+            Storage::Static { buffer, len } => &mut buffer[..*len],
+            Storage::Shared(_) => shared_bytes_unavailable(),
+        }
     }
 }
+
+/// Panics because the bytes of a [`SharedMemory`] must not be borrowed.
+///
+/// # Note
+///
+/// Other threads may access the bytes of a [`SharedMemory`] concurrently.
+/// Use [`SharedMemory::read`] and [`SharedMemory::write`] instead.
+#[cold]
+#[inline(never)]
+fn shared_bytes_unavailable() -> ! {
+    panic!("cannot borrow the bytes of a shared linear memory")
+}
@@ -0,0 +1,87 @@
+use super::{MemoryError, MemoryType};
+use core::fmt;
+use std::boxed::Box;
+
+/// A host provided byte buffer backing a Wasm linear memory.
+///
+/// # Note
+///
+/// Implementations are created by a [`MemoryCreator`] and allow embedders
+/// to control where the bytes of a linear memory are located, e.g. within
+/// a shared memory region that is mapped by another process.
+///
+/// # Requirements
+///
+/// Wasmi relies on the following invariants for correct Wasm semantics:
+///
+/// - [`LinearMemory::data`] and [`LinearMemory::data_mut`] must return exactly
+///   [`LinearMemory::byte_size`] bytes.
+/// - The initial `minimum` bytes of a [`LinearMemory`] returned by [`MemoryCreator::new_memory`]
+///   must be zero initialized. Otherwise creating the linear memory fails.
+/// - All bytes that become accessible via [`LinearMemory::grow_to`] must be zero initialized.
+pub trait LinearMemory: Send + Sync {
+    /// Returns the current size of the [`LinearMemory`] in bytes.
+    fn byte_size(&self) -> usize;
+
+    /// Grows the [`LinearMemory`] to `new_size` bytes.
+    ///
+    /// # Note
+    ///
+    /// Wasmi makes sure that `new_size` is never smaller than the current size
+    /// and does not exceed the maximum size the [`LinearMemory`] was created with.
+    ///
+    /// # Errors
+    ///
+    /// If the [`LinearMemory`] cannot be grown to `new_size` bytes.
+    fn grow_to(&mut self, new_size: usize) -> Result<(), MemoryError>;
+
+    /// Returns a shared slice to the bytes of the [`LinearMemory`].
+    fn data(&self) -> &[u8];
+
+    /// Returns an exclusive slice to the bytes of the [`LinearMemory`].
+    fn data_mut(&mut self) -> &mut [u8];
+}
+
+impl fmt::Debug for dyn LinearMemory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LinearMemory")
+            .field("byte_size", &self.byte_size())
+            .finish()
+    }
+}
+
+/// Allocates host provided [`LinearMemory`] for Wasm linear memories.
+///
+/// # Note
+///
+/// Register a [`MemoryCreator`] via [`Engine::with_memory_creator`] to make
+/// the [`Engine`] call it whenever a new linear memory is created. This
+/// takes precedence over the [`MemoryBackend`] of the [`Config`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Engine::with_memory_creator`]: crate::Engine::with_memory_creator
+/// [`MemoryBackend`]: crate::MemoryBackend
+/// [`Config`]: crate::Config
+pub trait MemoryCreator: Send + Sync {
+    /// Creates a new [`LinearMemory`] for a linear memory of type `ty`.
+    ///
+    /// - `minimum` is the initial size of the linear memory in bytes.
+    ///   The returned [`LinearMemory`] must have exactly this size and all of its bytes must be zero.
+    /// - `maximum` is the maximum size of the linear memory in bytes if any.
+    ///
+    /// # Errors
+    ///
+    /// If the [`LinearMemory`] cannot be created.
+    fn new_memory(
+        &self,
+        ty: MemoryType,
+        minimum: usize,
+        maximum: Option<usize>,
+    ) -> Result<Box<dyn LinearMemory>, MemoryError>;
+}
+
+impl fmt::Debug for dyn MemoryCreator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryCreator").finish_non_exhaustive()
+    }
+}
@@ -44,6 +44,16 @@ impl DataSegment {
         let entity = DataSegmentEntity::from(segment);
         ctx.as_context_mut().store.inner.alloc_data_segment(entity)
     }
+
+    /// Allocates a new [`DataSegment`] whose bytes are dropped.
+    ///
+    /// # Note
+    ///
+    /// This is equivalent to dropping the bytes of a newly allocated [`DataSegment`].
+    pub fn new_dropped(mut ctx: impl AsContextMut) -> Self {
+        let entity = DataSegmentEntity::empty();
+        ctx.as_context_mut().store.inner.alloc_data_segment(entity)
+    }
 }
 
 /// An instantiated [`DataSegmentEntity`].
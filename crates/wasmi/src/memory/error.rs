@@ -22,6 +22,14 @@ pub enum MemoryError {
     },
     /// Tried to create too many memories
     TooManyMemories,
+    /// Tried to read a string from linear memory that is not valid UTF-8.
+    InvalidUtf8,
+    /// Tried to read a zero terminated string that exceeds its length limit.
+    StringTooLong,
+    /// Tried to borrow the bytes of a shared linear memory.
+    SharedMemoryBorrow,
+    /// A host provided linear memory was not zero initialized.
+    UninitializedMemory,
 }
 
 impl Display for MemoryError {
@@ -45,6 +53,18 @@ impl Display for MemoryError {
             Self::TooManyMemories => {
                 write!(f, "too many memories")
             }
+            Self::InvalidUtf8 => {
+                write!(f, "string in linear memory is not valid UTF-8")
+            }
+            Self::StringTooLong => {
+                write!(f, "string in linear memory exceeds its length limit")
+            }
+            Self::SharedMemoryBorrow => {
+                write!(f, "cannot borrow the bytes of a shared linear memory")
+            }
+            Self::UninitializedMemory => {
+                write!(f, "host provided linear memory is not zero initialized")
+            }
         }
     }
 }
@@ -0,0 +1,143 @@
+use super::MemoryError;
+use crate::Engine;
+use std::boxed::Box;
+#[cfg(all(feature = "std", target_os = "linux"))]
+use std::{fs::File, io::Write as _, os::unix::fs::FileExt as _};
+
+/// The initial bytes of a linear memory, e.g. as captured by an [`InstanceSnapshot`].
+///
+/// # Note
+///
+/// If the linear memories created from the [`MemoryImage`] are backed by
+/// [`MemoryBackend::VirtualMemory`] instead of a [`MemoryCreator`] on Linux with
+/// the `std` crate feature enabled the bytes are stored in an anonymous in-memory
+/// file. Those linear memories map this file copy-on-write so that they do not
+/// need to copy the bytes upon creation. Otherwise or if creating the file failed
+/// the bytes are stored on the heap.
+///
+/// [`InstanceSnapshot`]: crate::InstanceSnapshot
+/// [`MemoryBackend::VirtualMemory`]: crate::MemoryBackend::VirtualMemory
+/// [`MemoryCreator`]: crate::MemoryCreator
+#[derive(Debug)]
+pub struct MemoryImage {
+    /// The number of bytes of the [`MemoryImage`].
+    len: usize,
+    /// The storage of the bytes of the [`MemoryImage`].
+    bytes: ImageBytes,
+}
+
+/// The storage of the bytes of a [`MemoryImage`].
+#[derive(Debug)]
+enum ImageBytes {
+    /// The bytes are stored on the heap.
+    Heap(Box<[u8]>),
+    /// The bytes are stored in an anonymous in-memory file.
+    ///
+    /// # Note
+    ///
+    /// The length of the file is rounded up to a multiple of the OS page size
+    /// so that it can be mapped in its entirety. The padding bytes are zero.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    File(File),
+}
+
+impl MemoryImage {
+    /// Creates a new [`MemoryImage`] holding a copy of `bytes`.
+    ///
+    /// The linear memories created from the [`MemoryImage`] are allocated by the `engine`.
+    pub fn new(bytes: &[u8], engine: &Engine) -> Self {
+        #[cfg(all(feature = "std", target_os = "linux"))]
+        if Self::is_mappable(engine) && !bytes.is_empty() {
+            if let Some(file) = create_image_file(bytes) {
+                return Self {
+                    len: bytes.len(),
+                    bytes: ImageBytes::File(file),
+                };
+            }
+        }
+        #[cfg(not(all(feature = "std", target_os = "linux")))]
+        let _ = engine;
+        Self {
+            len: bytes.len(),
+            bytes: ImageBytes::Heap(bytes.into()),
+        }
+    }
+
+    /// Returns `true` if the linear memories allocated by the `engine` map memory images.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    fn is_mappable(engine: &Engine) -> bool {
+        use crate::MemoryBackend;
+        let backend = engine.config().get_memory_backend();
+        matches!(backend, MemoryBackend::VirtualMemory) && engine.memory_creator().is_none()
+    }
+
+    /// Returns the in-memory file holding the bytes of the [`MemoryImage`] if any.
+    ///
+    /// # Note
+    ///
+    /// The returned file is [`MemoryImage::mapped_len`] bytes long.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    pub fn file(&self) -> Option<&File> {
+        match &self.bytes {
+            ImageBytes::File(file) => Some(file),
+            ImageBytes::Heap(_) => None,
+        }
+    }
+
+    /// Returns the number of bytes of the [`MemoryImage`] rounded up to the OS page size.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    pub fn mapped_len(&self) -> usize {
+        round_to_page_size(self.len)
+    }
+
+    /// Copies the bytes of the [`MemoryImage`] to the start of `dst`.
+    ///
+    /// # Errors
+    ///
+    /// - If `dst` is smaller than the [`MemoryImage`].
+    /// - If the bytes of the [`MemoryImage`] cannot be read.
+    pub fn copy_to(&self, dst: &mut [u8]) -> Result<(), MemoryError> {
+        let dst = dst
+            .get_mut(..self.len)
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        match &self.bytes {
+            ImageBytes::Heap(bytes) => {
+                dst.copy_from_slice(bytes);
+                Ok(())
+            }
+            // Note: Reading from an in-memory file only fails if the host ran out of
+            //       resources which is treated the same as a failed memory allocation.
+            #[cfg(all(feature = "std", target_os = "linux"))]
+            ImageBytes::File(file) => file
+                .read_exact_at(dst, 0)
+                .map_err(|_| MemoryError::OutOfBoundsAllocation),
+        }
+    }
+}
+
+/// Creates an anonymous in-memory file holding `bytes` padded to the OS page size.
+///
+/// Returns `None` if the file cannot be created or written.
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn create_image_file(bytes: &[u8]) -> Option<File> {
+    use std::os::fd::FromRawFd as _;
+    // Safety: The name is a valid zero terminated string and the flags are supported.
+    let fd = unsafe { libc::memfd_create(c"wasmi_memory_image".as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return None;
+    }
+    // Safety: `fd` is a freshly created file descriptor that is exclusively owned by `file`.
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    file.write_all(bytes).ok()?;
+    file.set_len(round_to_page_size(bytes.len()) as u64).ok()?;
+    Some(file)
+}
+
+/// Rounds `len` up to the next multiple of the OS page size.
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn round_to_page_size(len: usize) -> usize {
+    // Safety: Querying the page size has no preconditions.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    let page_size = usize::try_from(page_size).unwrap_or(0x1000);
+    len.next_multiple_of(page_size)
+}
@@ -1,20 +1,35 @@
 mod buffer;
+mod creator;
 mod data;
 mod error;
+mod image;
+mod parker;
+mod shared;
+mod view;
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod vmem;
 
 #[cfg(test)]
 mod tests;
 
-use self::buffer::ByteBuffer;
+pub(crate) use self::image::MemoryImage;
+use self::{buffer::ByteBuffer, parker::DefaultParker};
 pub use self::{
+    creator::{LinearMemory, MemoryCreator},
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    parker::{MemoryParker, ParkResult},
+    shared::SharedMemory,
+    view::{MemoryView, MemoryViewMut, Pod},
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{
+    engine::MemoryBackend,
     error::EntityGrowError,
     store::{Fuel, ResourceLimiterRef},
+    Engine,
 };
+use std::vec;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{Pages, TrapCode};
 
@@ -40,6 +55,7 @@ impl ArenaIndex for MemoryIdx {
 pub struct MemoryType {
     initial_pages: Pages,
     maximum_pages: Option<Pages>,
+    shared: bool,
 }
 
 impl MemoryType {
@@ -60,9 +76,34 @@ impl MemoryType {
         Ok(Self {
             initial_pages,
             maximum_pages,
+            shared: false,
         })
     }
 
+    /// Creates a new shared memory type with initial and maximum pages.
+    ///
+    /// # Note
+    ///
+    /// Shared linear memories are part of the Wasm [`threads`] proposal
+    /// and must always declare their maximum size.
+    ///
+    /// # Errors
+    ///
+    /// If the linear memory type initial or maximum size exceeds the
+    /// maximum limits of 2^16 pages.
+    ///
+    /// [`threads`]: https://github.com/WebAssembly/threads
+    pub fn new_shared(initial: u32, maximum: u32) -> Result<Self, MemoryError> {
+        let mut memory_type = Self::new(initial, Some(maximum))?;
+        memory_type.shared = true;
+        Ok(memory_type)
+    }
+
+    /// Returns `true` if the memory type is shared.
+    pub fn is_shared(self) -> bool {
+        self.shared
+    }
+
     /// Returns the initial pages of the memory type.
     pub fn initial_pages(self) -> Pages {
         self.initial_pages
@@ -110,6 +151,9 @@ impl MemoryType {
     /// [import subtyping]:
     /// https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
     pub(crate) fn is_subtype_of(&self, other: &MemoryType) -> bool {
+        if self.is_shared() != other.is_shared() {
+            return false;
+        }
         if self.initial_pages() < other.initial_pages() {
             return false;
         }
@@ -131,11 +175,120 @@ pub struct MemoryEntity {
 
 impl MemoryEntity {
     /// Creates a new memory entity with the given memory type.
+    ///
+    /// The bytes of the memory entity are allocated using the [`MemoryCreator`]
+    /// of the `engine` if any or its configured [`MemoryBackend`] otherwise.
+    /// Shared memory entities are always backed by a new [`SharedMemory`].
     pub fn new(
         memory_type: MemoryType,
+        engine: &Engine,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<Self, MemoryError> {
+        Self::new_impl(memory_type, memory_type.initial_pages(), None, engine, limiter)
+    }
+
+    /// Creates a new memory entity with the given memory type and `pages` accessible
+    /// pages whose first bytes are initialized with the bytes of the `image`.
+    ///
+    /// # Note
+    ///
+    /// Linear memories backed by [`MemoryBackend::VirtualMemory`] map the
+    /// bytes of the `image` copy-on-write instead of copying them if possible.
+    ///
+    /// # Errors
+    ///
+    /// - If `memory_type` is shared.
+    /// - If the `image` does not fit into `pages` pages.
+    /// - If the bytes of the linear memory cannot be allocated.
+    pub fn new_with_image(
+        memory_type: MemoryType,
+        pages: Pages,
+        image: &MemoryImage,
+        engine: &Engine,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<Self, MemoryError> {
+        if memory_type.is_shared() {
+            return Err(MemoryError::InvalidMemoryType);
+        }
+        Self::new_impl(memory_type, pages, Some(image), engine, limiter)
+    }
+
+    /// Creates a new memory entity with the given memory type and `initial_pages`.
+    ///
+    /// If an `image` is given the first bytes of the memory entity are initialized with it.
+    fn new_impl(
+        memory_type: MemoryType,
+        initial_pages: Pages,
+        image: Option<&MemoryImage>,
+        engine: &Engine,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<Self, MemoryError> {
+        let backend = engine.config().get_memory_backend();
+        let creator = engine.memory_creator();
+        Self::new_with(memory_type, initial_pages, limiter, |initial_len, maximum_len| {
+            if memory_type.is_shared() {
+                return SharedMemory::new(memory_type).map(ByteBuffer::new_shared);
+            }
+            let mut bytes = match (creator, backend, maximum_len) {
+                (Some(creator), _, _) => {
+                    let memory = creator.new_memory(memory_type, initial_len, maximum_len)?;
+                    ByteBuffer::new_custom(memory, initial_len)?
+                }
+                (None, MemoryBackend::VirtualMemory, Some(maximum_len)) => {
+                    if let Some(image) = image {
+                        return ByteBuffer::new_virtual_with_image(initial_len, maximum_len, image);
+                    }
+                    ByteBuffer::new_virtual(initial_len, maximum_len)?
+                }
+                // Note: We fall back to the `Vec` based byte buffer if the maximum
+                //       size of the linear memory cannot be reserved upfront since
+                //       it cannot be represented on this platform.
+                _ => ByteBuffer::new(initial_len),
+            };
+            if let Some(image) = image {
+                image.copy_to(bytes.data_mut())?;
+            }
+            Ok(bytes)
+        })
+    }
+
+    /// Creates a new memory entity with the given memory type operating on `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If `buffer` is too small to hold the initial pages of `memory_type`.
+    pub fn new_static(
+        memory_type: MemoryType,
+        buffer: &'static mut [u8],
         limiter: &mut ResourceLimiterRef<'_>,
     ) -> Result<Self, MemoryError> {
         let initial_pages = memory_type.initial_pages();
+        Self::new_with(memory_type, initial_pages, limiter, |initial_len, _maximum_len| {
+            ByteBuffer::new_static(buffer, initial_len)
+        })
+    }
+
+    /// Creates a new memory entity operating on the bytes of the `shared` memory.
+    pub fn new_shared(shared: SharedMemory) -> Self {
+        let memory_type = shared.ty();
+        let current_pages = shared.current_pages();
+        Self {
+            bytes: ByteBuffer::new_shared(shared),
+            memory_type,
+            current_pages,
+        }
+    }
+
+    /// Creates a new memory entity with the given memory type and `initial_pages`.
+    ///
+    /// The bytes of the memory entity are allocated by `alloc` which is
+    /// given the initial and maximum size of the linear memory in bytes.
+    fn new_with(
+        memory_type: MemoryType,
+        initial_pages: Pages,
+        limiter: &mut ResourceLimiterRef<'_>,
+        alloc: impl FnOnce(usize, Option<usize>) -> Result<ByteBuffer, MemoryError>,
+    ) -> Result<Self, MemoryError> {
         let initial_len = initial_pages.to_bytes();
         let maximum_pages = memory_type.maximum_pages().unwrap_or_else(Pages::max);
         let maximum_len = maximum_pages.to_bytes();
@@ -149,19 +302,22 @@ impl MemoryEntity {
             }
         }
 
-        if let Some(initial_len) = initial_len {
-            let memory = Self {
-                bytes: ByteBuffer::new(initial_len),
+        let bytes = match initial_len {
+            Some(initial_len) => alloc(initial_len, maximum_len),
+            None => Err(MemoryError::OutOfBoundsAllocation),
+        };
+        match bytes {
+            Ok(bytes) => Ok(Self {
+                bytes,
                 memory_type,
                 current_pages: initial_pages,
-            };
-            Ok(memory)
-        } else {
-            let err = MemoryError::OutOfBoundsAllocation;
-            if let Some(limiter) = limiter.as_resource_limiter() {
-                limiter.memory_grow_failed(&err)
+            }),
+            Err(err) => {
+                if let Some(limiter) = limiter.as_resource_limiter() {
+                    limiter.memory_grow_failed(&err)
+                }
+                Err(err)
             }
-            Err(err)
         }
     }
 
@@ -177,15 +333,23 @@ impl MemoryEntity {
     /// This respects the current size of the [`MemoryEntity`] as
     /// its minimum size and is useful for import subtyping checks.
     pub fn dynamic_ty(&self) -> MemoryType {
-        let current_pages = self.current_pages().into();
-        let maximum_pages = self.ty().maximum_pages().map(Into::into);
-        MemoryType::new(current_pages, maximum_pages)
-            .unwrap_or_else(|_| panic!("must result in valid memory type due to invariants"))
+        MemoryType {
+            initial_pages: self.current_pages(),
+            ..self.ty()
+        }
     }
 
     /// Returns the amount of pages in use by the linear memory.
     pub fn current_pages(&self) -> Pages {
-        self.current_pages
+        match self.bytes.shared() {
+            Some(shared) => shared.current_pages(),
+            None => self.current_pages,
+        }
+    }
+
+    /// Returns the [`SharedMemory`] of the linear memory if it is shared.
+    pub fn shared(&self) -> Option<&SharedMemory> {
+        self.bytes.shared()
     }
 
     /// Grows the linear memory by the given amount of new pages.
@@ -212,6 +376,10 @@ impl MemoryEntity {
             Err(err)
         }
 
+        // Note: Growth of shared linear memories must not interleave with
+        //       concurrent growth operations from other stores or threads.
+        let shared = self.bytes.shared().cloned();
+        let _guard = shared.as_ref().map(SharedMemory::lock_growth);
         let current_pages = self.current_pages();
         if additional == Pages::from(0) {
             // Nothing to do in this case. Bail out early.
@@ -259,17 +427,27 @@ impl MemoryEntity {
         // 1. The resource limiter validated the memory consumption.
         // 2. The growth is within bounds.
         // 3. There is enough fuel for the operation.
-        self.bytes.grow(new_size);
+        if self.bytes.grow(new_size).is_err() {
+            return notify_limiter(limiter, EntityGrowError::InvalidGrow);
+        }
         self.current_pages = new_pages;
         Ok(current_pages)
     }
 
     /// Returns a shared slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the linear memory is shared.
     pub fn data(&self) -> &[u8] {
         self.bytes.data()
     }
 
     /// Returns an exclusive slice to the bytes underlying to the byte buffer.
+    ///
+    /// # Panics
+    ///
+    /// If the linear memory is shared.
     pub fn data_mut(&mut self) -> &mut [u8] {
         self.bytes.data_mut()
     }
@@ -281,6 +459,9 @@ impl MemoryEntity {
     ///
     /// If this operation accesses out of bounds linear memory.
     pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        if let Some(shared) = self.shared() {
+            return shared.read(offset, buffer);
+        }
         let len_buffer = buffer.len();
         let slice = self
             .data()
@@ -297,6 +478,9 @@ impl MemoryEntity {
     ///
     /// If this operation accesses out of bounds linear memory.
     pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        if let Some(shared) = self.shared() {
+            return shared.write(offset, buffer);
+        }
         let len_buffer = buffer.len();
         let slice = self
             .data_mut()
@@ -334,7 +518,83 @@ impl Memory {
             .store
             .store_inner_and_resource_limiter_ref();
 
-        let entity = MemoryEntity::new(ty, &mut resource_limiter)?;
+        let entity = MemoryEntity::new(ty, inner.engine(), &mut resource_limiter)?;
+        let memory = inner.alloc_memory(entity);
+        Ok(memory)
+    }
+
+    /// Creates a new linear memory to the store with `pages` accessible pages
+    /// whose first bytes are initialized with the bytes of the `image`.
+    ///
+    /// # Errors
+    ///
+    /// - If `ty` is shared or the `image` does not fit into `pages` pages.
+    /// - If more than [`u32::MAX`] much linear memory is allocated.
+    pub(crate) fn new_with_image(
+        mut ctx: impl AsContextMut,
+        ty: MemoryType,
+        pages: Pages,
+        image: &MemoryImage,
+    ) -> Result<Self, MemoryError> {
+        let (inner, mut resource_limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        let entity =
+            MemoryEntity::new_with_image(ty, pages, image, inner.engine(), &mut resource_limiter)?;
+        let memory = inner.alloc_memory(entity);
+        Ok(memory)
+    }
+
+    /// Creates a new linear memory to the store operating on the `shared` memory.
+    ///
+    /// # Note
+    ///
+    /// The returned [`Memory`] refers to the same bytes as all other [`Memory`]
+    /// instances created from the same [`SharedMemory`], even across stores.
+    pub fn new_shared(mut ctx: impl AsContextMut, shared: &SharedMemory) -> Self {
+        let entity = MemoryEntity::new_shared(shared.clone());
+        ctx.as_context_mut().store.inner.alloc_memory(entity)
+    }
+
+    /// Returns the [`SharedMemory`] of the [`Memory`] if it is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn shared(&self, ctx: impl AsContext) -> Option<SharedMemory> {
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .shared()
+            .cloned()
+    }
+
+    /// Creates a new linear memory to the store operating on the user provided `buffer`.
+    ///
+    /// # Note
+    ///
+    /// - The linear memory never allocates and cannot grow beyond the length of `buffer`.
+    /// - The initial bytes of `buffer` are zero initialized upon creation and newly
+    ///   accessible bytes are zero initialized upon growth.
+    /// - This is primarily useful on `no_std` targets where linear memory shall
+    ///   reside in a statically allocated region.
+    ///
+    /// # Errors
+    ///
+    /// - If `buffer` is too small to hold the initial pages of `ty`.
+    /// - If more than [`u32::MAX`] much linear memory is allocated.
+    pub fn new_static(
+        mut ctx: impl AsContextMut,
+        ty: MemoryType,
+        buffer: &'static mut [u8],
+    ) -> Result<Self, MemoryError> {
+        let (inner, mut resource_limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        let entity = MemoryEntity::new_static(ty, buffer, &mut resource_limiter)?;
         let memory = inner.alloc_memory(entity);
         Ok(memory)
     }
@@ -410,26 +670,65 @@ impl Memory {
     ///
     /// # Panics
     ///
-    /// Panics if `ctx` does not own this [`Memory`].
+    /// - If `ctx` does not own this [`Memory`].
+    /// - If the [`Memory`] is shared. Use [`Memory::try_data`] or [`Memory::read`] instead.
     pub fn data<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> &'a [u8] {
         ctx.into().store.inner.resolve_memory(self).data()
     }
 
-    /// Returns an exclusive slice to the bytes underlying the [`Memory`].
+    /// Returns a shared slice to the bytes underlying the [`Memory`] if it is not shared.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Memory`] is shared. Use [`Memory::read`] and [`Memory::write`] instead.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
+    pub fn try_data<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, T>>,
+    ) -> Result<&'a [u8], MemoryError> {
+        let memory = ctx.into().store.inner.resolve_memory(self);
+        if memory.shared().is_some() {
+            return Err(MemoryError::SharedMemoryBorrow);
+        }
+        Ok(memory.data())
+    }
+
+    /// Returns an exclusive slice to the bytes underlying the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// - If `ctx` does not own this [`Memory`].
+    /// - If the [`Memory`] is shared. Use [`Memory::try_data_mut`] or [`Memory::write`] instead.
     pub fn data_mut<'a, T: 'a>(&self, ctx: impl Into<StoreContextMut<'a, T>>) -> &'a mut [u8] {
         ctx.into().store.inner.resolve_memory_mut(self).data_mut()
     }
 
+    /// Returns an exclusive slice to the bytes underlying the [`Memory`] if it is not shared.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Memory`] is shared. Use [`Memory::read`] and [`Memory::write`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn try_data_mut<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+    ) -> Result<&'a mut [u8], MemoryError> {
+        self.try_data_and_store_mut(ctx).map(|(data, _)| data)
+    }
+
     /// Returns an exclusive slice to the bytes underlying the [`Memory`], and an exclusive
     /// reference to the user provided state.
     ///
     /// # Panics
     ///
-    /// Panics if `ctx` does not own this [`Memory`].
+    /// - If `ctx` does not own this [`Memory`].
+    /// - If the [`Memory`] is shared. Use [`Memory::try_data_and_store_mut`] instead.
     pub fn data_and_store_mut<'a, T: 'a>(
         &self,
         ctx: impl Into<StoreContextMut<'a, T>>,
@@ -438,6 +737,27 @@ impl Memory {
         (memory.data_mut(), store)
     }
 
+    /// Returns an exclusive slice to the bytes underlying the [`Memory`] if it is not shared,
+    /// and an exclusive reference to the user provided state.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Memory`] is shared. Use [`Memory::read`] and [`Memory::write`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn try_data_and_store_mut<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+    ) -> Result<(&'a mut [u8], &'a mut T), MemoryError> {
+        let (memory, store) = ctx.into().store.resolve_memory_and_state_mut(self);
+        if memory.shared().is_some() {
+            return Err(MemoryError::SharedMemoryBorrow);
+        }
+        Ok((memory.data_mut(), store))
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -483,4 +803,147 @@ impl Memory {
             .resolve_memory_mut(self)
             .write(offset, buffer)
     }
+
+    /// Reads the value of type `T` at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_value<T: Pod>(&self, ctx: impl AsContext, ptr: usize) -> Result<T, MemoryError> {
+        let memory = ctx.as_context().store.inner.resolve_memory(self);
+        if let Some(shared) = memory.shared() {
+            let mut bytes = vec![0x00_u8; T::SIZE];
+            shared.read(ptr, &mut bytes)?;
+            return Ok(T::from_le(&bytes));
+        }
+        let data = memory.data();
+        let range = view::typed_range::<T>(data.len(), ptr, 1)?;
+        Ok(T::from_le(&data[range]))
+    }
+
+    /// Writes `value` of type `T` to `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_value<T: Pod>(
+        &self,
+        mut ctx: impl AsContextMut,
+        ptr: usize,
+        value: T,
+    ) -> Result<(), MemoryError> {
+        let memory = ctx.as_context_mut().store.inner.resolve_memory_mut(self);
+        if let Some(shared) = memory.shared() {
+            let mut bytes = vec![0x00_u8; T::SIZE];
+            value.to_le(&mut bytes);
+            return shared.write(ptr, &bytes);
+        }
+        let data = memory.data_mut();
+        let range = view::typed_range::<T>(data.len(), ptr, 1)?;
+        value.to_le(&mut data[range]);
+        Ok(())
+    }
+
+    /// Returns a shared view of `len` consecutive values of type `T` starting at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If the viewed values are not entirely within the bounds of the linear memory.
+    /// - If the [`Memory`] is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view<'a, T: Pod, S: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, S>>,
+        ptr: usize,
+        len: usize,
+    ) -> Result<MemoryView<'a, T>, MemoryError> {
+        let data = self.try_data(ctx)?;
+        let range = view::typed_range::<T>(data.len(), ptr, len)?;
+        Ok(MemoryView::new(&data[range]))
+    }
+
+    /// Returns an exclusive view of `len` consecutive values of type `T` starting at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If the viewed values are not entirely within the bounds of the linear memory.
+    /// - If the [`Memory`] is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view_mut<'a, T: Pod, S: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, S>>,
+        ptr: usize,
+        len: usize,
+    ) -> Result<MemoryViewMut<'a, T>, MemoryError> {
+        let memory = ctx.into().store.inner.resolve_memory_mut(self);
+        if memory.shared().is_some() {
+            return Err(MemoryError::SharedMemoryBorrow);
+        }
+        let data = memory.data_mut();
+        let range = view::typed_range::<T>(data.len(), ptr, len)?;
+        Ok(MemoryViewMut::new(&mut data[range]))
+    }
+
+    /// Returns the UTF-8 encoded string of `len` bytes starting at `ptr`.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If the bytes are not valid UTF-8.
+    /// - If the [`Memory`] is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_str<'a, S: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, S>>,
+        ptr: usize,
+        len: usize,
+    ) -> Result<&'a str, MemoryError> {
+        let bytes = self.view::<u8, S>(ctx, ptr, len)?.as_bytes();
+        view::decode_str(bytes)
+    }
+
+    /// Returns the zero terminated UTF-8 encoded string starting at `ptr`.
+    ///
+    /// At most `max_len` bytes are scanned for the zero terminator
+    /// which is not part of the returned string.
+    ///
+    /// # Errors
+    ///
+    /// - If this operation accesses out of bounds linear memory.
+    /// - If the string is longer than `max_len` bytes.
+    /// - If the bytes are not valid UTF-8.
+    /// - If the [`Memory`] is shared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_c_str<'a, S: 'a>(
+        &self,
+        ctx: impl Into<StoreContext<'a, S>>,
+        ptr: usize,
+        max_len: usize,
+    ) -> Result<&'a str, MemoryError> {
+        let bytes = self
+            .try_data(ctx)?
+            .get(ptr..)
+            .ok_or(MemoryError::OutOfBoundsAccess)?;
+        view::decode_str(view::c_str_bytes(bytes, max_len)?)
+    }
 }
@@ -0,0 +1,208 @@
+use core::{fmt, time::Duration};
+
+/// The outcome of parking a thread via [`MemoryParker::park`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParkResult {
+    /// The thread was parked and has been woken up by [`MemoryParker::unpark`].
+    Woken,
+    /// The thread was not parked since the validation failed.
+    Mismatch,
+    /// The thread was parked and woke up after its timeout elapsed.
+    TimedOut,
+}
+
+impl ParkResult {
+    /// Returns the `memory.atomic.wait` result code of the [`ParkResult`].
+    pub(crate) fn to_wait_result(self) -> u32 {
+        match self {
+            Self::Woken => 0,
+            Self::Mismatch => 1,
+            Self::TimedOut => 2,
+        }
+    }
+}
+
+/// Parks and unparks threads for `memory.atomic.wait` and `memory.atomic.notify`.
+///
+/// # Note
+///
+/// Every [`SharedMemory`] uses a [`MemoryParker`] to coordinate the threads
+/// that are waiting on its addresses. Embedders may provide their own
+/// [`MemoryParker`] via [`SharedMemory::with_parker`], e.g. on `no_std`
+/// targets that offer their own threading primitives.
+///
+/// [`SharedMemory`]: crate::SharedMemory
+/// [`SharedMemory::with_parker`]: crate::SharedMemory::with_parker
+pub trait MemoryParker: Send + Sync {
+    /// Parks the calling thread at `address` until it is unparked or `timeout` elapsed.
+    ///
+    /// # Note
+    ///
+    /// - `validate` must be called exactly once and atomically with respect to
+    ///   [`MemoryParker::unpark`]. If it returns `false` the calling thread
+    ///   must not be parked and [`ParkResult::Mismatch`] is returned.
+    /// - A `timeout` of `None` parks the calling thread indefinitely.
+    fn park(
+        &self,
+        address: u32,
+        validate: &mut dyn FnMut() -> bool,
+        timeout: Option<Duration>,
+    ) -> ParkResult;
+
+    /// Unparks up to `count` threads parked at `address`.
+    ///
+    /// Returns the number of threads that have been unparked.
+    fn unpark(&self, address: u32, count: u32) -> u32;
+}
+
+impl fmt::Debug for dyn MemoryParker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryParker").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::std_parker::DefaultParker;
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std_parker::DefaultParker;
+
+#[cfg(feature = "std")]
+mod std_parker {
+    use super::{MemoryParker, ParkResult};
+    use core::time::Duration;
+    use std::{
+        collections::{BTreeMap, BTreeSet, VecDeque},
+        sync::{Condvar, Mutex, MutexGuard, PoisonError},
+        time::Instant,
+        vec::Vec,
+    };
+
+    /// The default [`MemoryParker`] based on the threading primitives of the Rust standard library.
+    #[derive(Debug, Default)]
+    pub struct DefaultParker {
+        state: Mutex<ParkerState>,
+        condvar: Condvar,
+    }
+
+    /// The internal state of the [`DefaultParker`].
+    #[derive(Debug, Default)]
+    struct ParkerState {
+        /// The ticket given to the next parked thread.
+        next_ticket: u64,
+        /// The tickets of all parked threads in the order they were parked per address.
+        parked: BTreeMap<u32, VecDeque<u64>>,
+        /// The tickets of all threads that have been unparked but did not yet wake up.
+        unparked: BTreeSet<u64>,
+    }
+
+    impl ParkerState {
+        /// Removes the parked thread with the `ticket` at `address`.
+        fn remove(&mut self, address: u32, ticket: u64) {
+            if let Some(queue) = self.parked.get_mut(&address) {
+                queue.retain(|parked| *parked != ticket);
+                if queue.is_empty() {
+                    self.parked.remove(&address);
+                }
+            }
+        }
+    }
+
+    impl DefaultParker {
+        /// Locks the [`ParkerState`] of the [`DefaultParker`].
+        fn lock(&self) -> MutexGuard<'_, ParkerState> {
+            self.state.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+
+    impl MemoryParker for DefaultParker {
+        fn park(
+            &self,
+            address: u32,
+            validate: &mut dyn FnMut() -> bool,
+            timeout: Option<Duration>,
+        ) -> ParkResult {
+            let mut state = self.lock();
+            if !validate() {
+                return ParkResult::Mismatch;
+            }
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.parked.entry(address).or_default().push_back(ticket);
+            let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+            loop {
+                if state.unparked.remove(&ticket) {
+                    return ParkResult::Woken;
+                }
+                state = match deadline {
+                    None => self
+                        .condvar
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner),
+                    Some(deadline) => {
+                        let now = Instant::now();
+                        if now >= deadline {
+                            state.remove(address, ticket);
+                            return ParkResult::TimedOut;
+                        }
+                        self.condvar
+                            .wait_timeout(state, deadline - now)
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .0
+                    }
+                };
+            }
+        }
+
+        fn unpark(&self, address: u32, count: u32) -> u32 {
+            let mut state = self.lock();
+            let Some(queue) = state.parked.get_mut(&address) else {
+                return 0;
+            };
+            let len_unparked = queue.len().min(count as usize);
+            let unparked = queue.drain(..len_unparked).collect::<Vec<_>>();
+            if queue.is_empty() {
+                state.parked.remove(&address);
+            }
+            state.unparked.extend(unparked);
+            if len_unparked > 0 {
+                self.condvar.notify_all();
+            }
+            len_unparked as u32
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod no_std_parker {
+    use super::{MemoryParker, ParkResult};
+    use core::time::Duration;
+
+    /// The default [`MemoryParker`] on `no_std` targets.
+    ///
+    /// # Note
+    ///
+    /// Without threading primitives this [`MemoryParker`] cannot block the calling
+    /// thread. Therefore parking a thread times out immediately if the validation
+    /// succeeds and no thread is ever unparked.
+    #[derive(Debug, Default)]
+    pub struct DefaultParker;
+
+    impl MemoryParker for DefaultParker {
+        fn park(
+            &self,
+            _address: u32,
+            validate: &mut dyn FnMut() -> bool,
+            _timeout: Option<Duration>,
+        ) -> ParkResult {
+            match validate() {
+                true => ParkResult::TimedOut,
+                false => ParkResult::Mismatch,
+            }
+        }
+
+        fn unpark(&self, _address: u32, _count: u32) -> u32 {
+            0
+        }
+    }
+}
@@ -0,0 +1,449 @@
+#[cfg(all(feature = "std", target_os = "linux"))]
+use super::vmem;
+use super::{DefaultParker, MemoryError, MemoryParker, MemoryType};
+use core::{
+    ptr::NonNull,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+    time::Duration,
+};
+use spin::{Mutex, MutexGuard};
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+use std::alloc::{self, Layout};
+use std::sync::Arc;
+use wasmi_core::Pages;
+
+/// A Wasm linear memory that can be shared across stores and threads.
+///
+/// # Note
+///
+/// - Shared linear memories are part of the Wasm [`threads`] proposal.
+/// - The address space for the maximum size of a [`SharedMemory`] is reserved
+///   upfront so that its bytes never move when the [`SharedMemory`] grows.
+/// - Use [`Memory::new_shared`] to make a [`SharedMemory`] usable within a
+///   [`Store`], e.g. in order to define it in a [`Linker`].
+/// - Cloning a [`SharedMemory`] is cheap and yields a handle to the same linear memory.
+/// - Other threads may access the bytes of a [`SharedMemory`] at any time. Therefore
+///   they are never borrowed as slices but only accessed via [`SharedMemory::read`],
+///   [`SharedMemory::write`] and the atomic operations of the executed Wasm code.
+///
+/// [`threads`]: https://github.com/WebAssembly/threads
+/// [`Memory::new_shared`]: crate::Memory::new_shared
+/// [`Store`]: crate::Store
+/// [`Linker`]: crate::Linker
+#[derive(Debug, Clone)]
+pub struct SharedMemory {
+    inner: Arc<SharedMemoryInner>,
+}
+
+/// The internal state of a [`SharedMemory`].
+#[derive(Debug)]
+struct SharedMemoryInner {
+    /// The type of the shared linear memory.
+    ty: MemoryType,
+    /// The zero initialized bytes of the shared linear memory.
+    bytes: SharedBytes,
+    /// The number of accessible bytes.
+    len: AtomicUsize,
+    /// Serializes concurrent growth operations.
+    grow_lock: Mutex<()>,
+    /// Parks and unparks threads waiting on the shared linear memory.
+    parker: Arc<dyn MemoryParker>,
+}
+
+/// The bytes of a [`SharedMemory`] which never move.
+///
+/// # Note
+///
+/// - On platforms with OS provided virtual memory only the address space for the
+///   maximum size is reserved upfront. The bytes are committed as the
+///   [`SharedMemory`] grows.
+/// - On all other platforms the bytes for the maximum size are allocated upfront.
+#[derive(Debug)]
+struct SharedBytes {
+    /// The start of the bytes.
+    ptr: NonNull<u8>,
+    /// The maximum number of accessible bytes.
+    capacity: usize,
+    /// The total number of reserved bytes.
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    reserved: usize,
+}
+
+/// The alignment of the bytes of a [`SharedMemory`].
+///
+/// # Note
+///
+/// This guarantees that naturally aligned Wasm atomic operations
+/// are also aligned on the host for all supported access widths.
+const SHARED_BYTES_ALIGN: usize = 8;
+
+// Safety: `SharedBytes` exclusively owns its allocation. Concurrent accesses
+//         to the allocated bytes are mediated by the Wasm threads semantics.
+unsafe impl Send for SharedBytes {}
+unsafe impl Sync for SharedBytes {}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+impl SharedBytes {
+    /// Reserves `capacity` bytes of which the first `initial_len` are committed.
+    ///
+    /// # Errors
+    ///
+    /// If the OS fails to reserve or commit the bytes.
+    fn new(initial_len: usize, capacity: usize) -> Result<Self, MemoryError> {
+        let (ptr, reserved) = vmem::reserve(capacity)?;
+        // Note: The reservation is page aligned which satisfies `SHARED_BYTES_ALIGN`.
+        debug_assert!(ptr.as_ptr().align_offset(SHARED_BYTES_ALIGN) == 0);
+        let bytes = Self {
+            ptr,
+            capacity,
+            reserved,
+        };
+        bytes.commit(0, initial_len)?;
+        Ok(bytes)
+    }
+
+    /// Commits the zero initialized bytes in `[from, to)`.
+    ///
+    /// # Errors
+    ///
+    /// - If `to` exceeds the capacity.
+    /// - If the OS fails to commit the bytes.
+    fn commit(&self, from: usize, to: usize) -> Result<(), MemoryError> {
+        if to > self.capacity {
+            return Err(MemoryError::OutOfBoundsGrowth);
+        }
+        if from >= to {
+            return Ok(());
+        }
+        // Safety: The range `[from, to)` is part of the reservation since
+        //         `to` does not exceed the capacity.
+        unsafe { vmem::commit(self.ptr, from, to) }
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+impl Drop for SharedBytes {
+    fn drop(&mut self) {
+        // Safety: The reservation was created in `SharedBytes::new`.
+        unsafe { vmem::release(self.ptr, self.reserved) }
+    }
+}
+
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+impl SharedBytes {
+    /// Allocates `capacity` zero initialized bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the allocation failed.
+    fn new(_initial_len: usize, capacity: usize) -> Result<Self, MemoryError> {
+        let layout = Self::layout(capacity)?;
+        if capacity == 0 {
+            return Ok(Self {
+                ptr: NonNull::<u64>::dangling().cast(),
+                capacity,
+            });
+        }
+        // Safety: `layout` has a non-zero size.
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).ok_or(MemoryError::OutOfBoundsAllocation)?;
+        Ok(Self { ptr, capacity })
+    }
+
+    /// Returns the [`Layout`] of an allocation of `capacity` bytes.
+    fn layout(capacity: usize) -> Result<Layout, MemoryError> {
+        Layout::from_size_align(capacity, SHARED_BYTES_ALIGN)
+            .map_err(|_| MemoryError::OutOfBoundsAllocation)
+    }
+
+    /// Commits the zero initialized bytes in `[from, to)`.
+    ///
+    /// # Note
+    ///
+    /// All bytes are allocated and zero initialized upfront.
+    ///
+    /// # Errors
+    ///
+    /// If `to` exceeds the capacity.
+    fn commit(&self, _from: usize, to: usize) -> Result<(), MemoryError> {
+        if to > self.capacity {
+            return Err(MemoryError::OutOfBoundsGrowth);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(all(feature = "std", target_os = "linux")))]
+impl Drop for SharedBytes {
+    fn drop(&mut self) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Safety: The allocation was created in `SharedBytes::new` using the same layout.
+        unsafe {
+            alloc::dealloc(
+                self.ptr.as_ptr(),
+                Layout::from_size_align_unchecked(self.capacity, SHARED_BYTES_ALIGN),
+            )
+        }
+    }
+}
+
+impl SharedMemory {
+    /// Creates a new [`SharedMemory`] of type `ty` using the default [`MemoryParker`].
+    ///
+    /// # Note
+    ///
+    /// The default [`MemoryParker`] uses the threading primitives of the Rust
+    /// standard library. On `no_std` targets it cannot block and waiting
+    /// on a [`SharedMemory`] times out immediately.
+    ///
+    /// # Errors
+    ///
+    /// - If `ty` is not a shared [`MemoryType`].
+    /// - If the bytes for the maximum size of `ty` cannot be reserved.
+    pub fn new(ty: MemoryType) -> Result<Self, MemoryError> {
+        Self::with_parker(ty, Arc::new(DefaultParker::default()))
+    }
+
+    /// Creates a new [`SharedMemory`] of type `ty` using the given [`MemoryParker`].
+    ///
+    /// # Errors
+    ///
+    /// - If `ty` is not a shared [`MemoryType`].
+    /// - If the bytes for the maximum size of `ty` cannot be reserved.
+    pub fn with_parker(ty: MemoryType, parker: Arc<dyn MemoryParker>) -> Result<Self, MemoryError> {
+        let Some(maximum_pages) = ty.maximum_pages().filter(|_| ty.is_shared()) else {
+            return Err(MemoryError::InvalidMemoryType);
+        };
+        let initial_len = ty
+            .initial_pages()
+            .to_bytes()
+            .ok_or(MemoryError::OutOfBoundsAllocation)?;
+        let maximum_len = maximum_pages
+            .to_bytes()
+            .ok_or(MemoryError::OutOfBoundsAllocation)?;
+        if initial_len > maximum_len {
+            return Err(MemoryError::InvalidMemoryType);
+        }
+        let bytes = SharedBytes::new(initial_len, maximum_len)?;
+        Ok(Self {
+            inner: Arc::new(SharedMemoryInner {
+                ty,
+                bytes,
+                len: AtomicUsize::new(initial_len),
+                grow_lock: Mutex::new(()),
+                parker,
+            }),
+        })
+    }
+
+    /// Returns the [`MemoryType`] of the [`SharedMemory`].
+    pub fn ty(&self) -> MemoryType {
+        self.inner.ty
+    }
+
+    /// Returns the amount of pages in use by the [`SharedMemory`].
+    pub fn current_pages(&self) -> Pages {
+        let pages = self.data_size() / bytes_per_page();
+        Pages::new(pages as u32)
+            .unwrap_or_else(|| panic!("shared memory has invalid amount of pages: {pages}"))
+    }
+
+    /// Returns the size of the [`SharedMemory`] in bytes.
+    pub fn data_size(&self) -> usize {
+        self.inner.len.load(Ordering::Acquire)
+    }
+
+    /// Grows the [`SharedMemory`] by the given amount of new pages.
+    ///
+    /// Returns the amount of pages before the operation upon success.
+    ///
+    /// # Errors
+    ///
+    /// If the [`SharedMemory`] would grow beyond its maximum limit.
+    pub fn grow(&self, additional: Pages) -> Result<Pages, MemoryError> {
+        let _guard = self.lock_growth();
+        let current_pages = self.current_pages();
+        let new_size = current_pages
+            .checked_add(additional)
+            .and_then(Pages::to_bytes)
+            .ok_or(MemoryError::OutOfBoundsGrowth)?;
+        self.grow_to(new_size)?;
+        Ok(current_pages)
+    }
+
+    /// Returns `true` if `a` and `b` refer to the same [`SharedMemory`].
+    pub fn same(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.inner, &b.inner)
+    }
+
+    /// Locks the [`SharedMemory`] for growth operations.
+    ///
+    /// # Note
+    ///
+    /// The caller must hold the returned guard while growing the [`SharedMemory`]
+    /// via [`SharedMemory::grow_to`] so that concurrent growth operations do not interleave.
+    pub(super) fn lock_growth(&self) -> MutexGuard<'_, ()> {
+        self.inner.grow_lock.lock()
+    }
+
+    /// Grows the [`SharedMemory`] to `new_size` bytes.
+    ///
+    /// # Note
+    ///
+    /// The caller must hold the guard returned by [`SharedMemory::lock_growth`].
+    ///
+    /// # Errors
+    ///
+    /// If `new_size` exceeds the maximum size of the [`SharedMemory`].
+    pub(super) fn grow_to(&self, new_size: usize) -> Result<(), MemoryError> {
+        // Note: Holding the growth lock guarantees that the committed bytes
+        //       end at the current size.
+        let current_size = self.data_size();
+        self.inner.bytes.commit(current_size, new_size)?;
+        self.inner.len.fetch_max(new_size, Ordering::AcqRel);
+        Ok(())
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Note
+    ///
+    /// The bytes are read one at a time using relaxed atomic loads since
+    /// other threads may write them concurrently.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        self.check_bounds(offset, buffer.len())?;
+        for (index, byte) in (offset..).zip(buffer) {
+            *byte = self.byte(index).load(Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Note
+    ///
+    /// The bytes are written one at a time using relaxed atomic stores since
+    /// other threads may access them concurrently.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn write(&self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        self.check_bounds(offset, buffer.len())?;
+        for (index, byte) in (offset..).zip(buffer) {
+            self.byte(index).store(*byte, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Sets `n` bytes starting at `offset` to `value`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub(crate) fn fill(&self, offset: usize, value: u8, len: usize) -> Result<(), MemoryError> {
+        self.check_bounds(offset, len)?;
+        for index in offset..offset + len {
+            self.byte(index).store(value, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Copies `len` bytes from `src` to `dst` where both ranges may overlap.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub(crate) fn copy_within(
+        &self,
+        src: usize,
+        dst: usize,
+        len: usize,
+    ) -> Result<(), MemoryError> {
+        self.check_bounds(src, len)?;
+        self.check_bounds(dst, len)?;
+        let copy = |index: usize| {
+            let byte = self.byte(src + index).load(Ordering::Relaxed);
+            self.byte(dst + index).store(byte, Ordering::Relaxed);
+        };
+        // Note: The copy direction makes sure that overlapping source
+        //       bytes are read before they are overwritten.
+        match dst <= src {
+            true => (0..len).for_each(copy),
+            false => (0..len).rev().for_each(copy),
+        }
+        Ok(())
+    }
+
+    /// Returns a raw pointer to the first byte of the [`SharedMemory`].
+    ///
+    /// # Note
+    ///
+    /// The pointer stays valid for as long as the [`SharedMemory`] is alive
+    /// since its bytes never move. The bytes must only be accessed via
+    /// atomic operations and never be borrowed as slices.
+    pub(crate) fn data_ptr(&self) -> *mut u8 {
+        self.inner.bytes.ptr.as_ptr()
+    }
+
+    /// Returns `Ok` if `len` bytes starting at `offset` are in bounds.
+    ///
+    /// # Note
+    ///
+    /// Since a [`SharedMemory`] never shrinks the bytes stay in bounds afterwards.
+    pub(crate) fn check_bounds(&self, offset: usize, len: usize) -> Result<(), MemoryError> {
+        match offset.checked_add(len) {
+            Some(end) if end <= self.data_size() => Ok(()),
+            _ => Err(MemoryError::OutOfBoundsAccess),
+        }
+    }
+
+    /// Returns the byte at `index` as atomic.
+    ///
+    /// # Note
+    ///
+    /// The caller must make sure that `index` is in bounds.
+    fn byte(&self, index: usize) -> &AtomicU8 {
+        debug_assert!(index < self.data_size());
+        // Safety: The first `data_size` bytes are allocated and initialized and
+        //         `AtomicU8` has the same size and alignment as `u8`.
+        unsafe { &*self.data_ptr().add(index).cast::<AtomicU8>() }
+    }
+
+    /// Parks the calling thread at `address` if `validate` returns `true`.
+    ///
+    /// Returns the `memory.atomic.wait` result code.
+    pub(crate) fn wait(
+        &self,
+        address: u32,
+        validate: &mut dyn FnMut() -> bool,
+        timeout: Option<Duration>,
+    ) -> u32 {
+        self.inner
+            .parker
+            .park(address, validate, timeout)
+            .to_wait_result()
+    }
+
+    /// Unparks up to `count` threads parked at `address`.
+    ///
+    /// Returns the number of unparked threads.
+    pub(crate) fn notify(&self, address: u32, count: u32) -> u32 {
+        self.inner.parker.unpark(address, count)
+    }
+}
+
+/// Returns the number of bytes per linear memory page.
+fn bytes_per_page() -> usize {
+    Pages::from(1)
+        .to_bytes()
+        .unwrap_or_else(|| panic!("the size of a single page must fit into usize"))
+}
@@ -14,3 +14,189 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+#[test]
+fn shared_subtyping_works() {
+    let shared = MemoryType::new_shared(1, 2).unwrap();
+    assert!(shared.is_subtype_of(&MemoryType::new_shared(1, 2).unwrap()));
+    assert!(shared.is_subtype_of(&MemoryType::new_shared(0, 3).unwrap()));
+    assert!(!shared.is_subtype_of(&memory_type(1, 2)));
+    assert!(!memory_type(1, 2).is_subtype_of(&shared));
+}
+
+#[test]
+fn shared_memory_grow_works() {
+    let page_size = Pages::from(1).to_bytes().unwrap();
+    let shared = SharedMemory::new(MemoryType::new_shared(1, 2).unwrap()).unwrap();
+    let other = shared.clone();
+    assert!(SharedMemory::same(&shared, &other));
+    assert_eq!(shared.data_size(), page_size);
+    assert_eq!(other.grow(Pages::from(1)).unwrap(), Pages::from(1));
+    assert_eq!(shared.current_pages(), Pages::from(2));
+    let mut bytes = vec![0xFF_u8; 2 * page_size];
+    shared.read(0, &mut bytes).unwrap();
+    assert!(bytes.iter().all(|byte| *byte == 0));
+    assert!(shared.read(1, &mut bytes).is_err());
+    assert!(shared.grow(Pages::from(1)).is_err());
+    assert!(SharedMemory::new(memory_type(1, 2)).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn shared_memory_commits_lazily() {
+    let page_size = Pages::from(1).to_bytes().unwrap();
+    // Note: Only the address space for the maximum size is reserved upfront.
+    let shared = SharedMemory::new(MemoryType::new_shared(1, 65536).unwrap()).unwrap();
+    assert_eq!(shared.grow(Pages::from(2)).unwrap(), Pages::from(1));
+    shared.write(3 * page_size - 1, &[42]).unwrap();
+    let mut byte = [0x00_u8];
+    shared.read(3 * page_size - 1, &mut byte).unwrap();
+    assert_eq!(byte, [42]);
+    assert!(shared.write(3 * page_size, &[42]).is_err());
+}
+
+#[test]
+fn vec_byte_buffer_grow_works() {
+    let mut buffer = ByteBuffer::new(10);
+    buffer.data_mut()[9] = 42;
+    buffer.grow(20).unwrap();
+    assert_eq!(buffer.len(), 20);
+    assert_eq!(buffer.data()[9], 42);
+    assert!(buffer.data()[10..].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn virtual_byte_buffer_grow_works() {
+    let page_size = Pages::from(1).to_bytes().unwrap();
+    let mut buffer = ByteBuffer::new_virtual(page_size, 4 * page_size).unwrap();
+    let ptr = buffer.data().as_ptr();
+    buffer.data_mut()[page_size - 1] = 42;
+    buffer.grow(3 * page_size).unwrap();
+    assert_eq!(buffer.len(), 3 * page_size);
+    assert_eq!(buffer.data()[page_size - 1], 42);
+    assert!(buffer.data()[page_size..].iter().all(|byte| *byte == 0));
+    // Growing virtual memory happens in place.
+    assert_eq!(buffer.data().as_ptr(), ptr);
+    buffer.grow(4 * page_size).unwrap();
+    assert_eq!(buffer.len(), 4 * page_size);
+}
+
+#[test]
+#[cfg(all(feature = "std", target_os = "linux"))]
+fn virtual_byte_buffer_grow_beyond_maximum_fails() {
+    let page_size = Pages::from(1).to_bytes().unwrap();
+    let mut buffer = ByteBuffer::new_virtual(0, page_size).unwrap();
+    assert!(buffer.grow(2 * page_size).is_err());
+    assert_eq!(buffer.len(), 0);
+    assert!(ByteBuffer::new_virtual(2 * page_size, page_size).is_err());
+}
+
+fn memory_with_store() -> (crate::Store<()>, Memory) {
+    let mut store = crate::Store::new(&Engine::default(), ());
+    let memory = Memory::new(&mut store, memory_type(1, None)).unwrap();
+    (store, memory)
+}
+
+#[test]
+fn typed_values_are_little_endian() {
+    let (mut store, memory) = memory_with_store();
+    memory.write_value(&mut store, 3, 0x1122_3344_u32).unwrap();
+    assert_eq!(memory.data(&store)[3..7], [0x44, 0x33, 0x22, 0x11]);
+    assert_eq!(memory.read_value::<u32>(&store, 3).unwrap(), 0x1122_3344);
+    assert_eq!(memory.read_value::<u16>(&store, 5).unwrap(), 0x1122);
+    memory.write_value(&mut store, 8, [1.5_f64, -2.0]).unwrap();
+    assert_eq!(
+        memory.read_value::<[f64; 2]>(&store, 8).unwrap(),
+        [1.5, -2.0]
+    );
+}
+
+#[test]
+fn typed_values_out_of_bounds() {
+    let (mut store, memory) = memory_with_store();
+    let len = memory.data(&store).len();
+    assert!(memory.read_value::<u8>(&store, len - 1).is_ok());
+    assert!(matches!(
+        memory.read_value::<u32>(&store, len - 3),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        memory.write_value(&mut store, usize::MAX, 0_u64),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
+
+#[test]
+fn memory_views_work() {
+    let (mut store, memory) = memory_with_store();
+    let mut view = memory.view_mut::<i32, _>(&mut store, 4, 3).unwrap();
+    assert_eq!(view.len(), 3);
+    view.copy_from_slice(&[1, -2, 3]).unwrap();
+    view.set(1, 20).unwrap();
+    assert!(view.set(3, 0).is_err());
+    assert!(view.copy_from_slice(&[1]).is_err());
+    let view = memory.view::<i32, _>(&store, 4, 3).unwrap();
+    assert_eq!(view.to_vec(), [1, 20, 3]);
+    assert_eq!(view.get(2), Some(3));
+    assert_eq!(view.get(3), None);
+    let len = memory.data(&store).len();
+    assert!(memory.view::<u64, _>(&store, len - 8, 1).is_ok());
+    assert!(memory.view::<u64, _>(&store, len - 8, 2).is_err());
+    assert!(memory.view::<u64, _>(&store, 0, usize::MAX).is_err());
+}
+
+#[test]
+fn zero_sized_memory_views_work() {
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    struct Unit;
+    impl Pod for Unit {
+        const SIZE: usize = 0;
+        fn from_le(_bytes: &[u8]) -> Self {
+            Self
+        }
+        fn to_le(self, _bytes: &mut [u8]) {}
+    }
+    let (mut store, memory) = memory_with_store();
+    memory.write_value(&mut store, 0, [Unit; 4]).unwrap();
+    assert_eq!(
+        memory.read_value::<[Unit; 4]>(&store, 0).unwrap(),
+        [Unit; 4]
+    );
+    let mut view = memory.view_mut::<Unit, _>(&mut store, 0, 4).unwrap();
+    assert!(view.is_empty());
+    view.copy_from_slice(&[]).unwrap();
+    assert!(view.copy_from_slice(&[Unit]).is_err());
+    let mut view = memory.view_mut::<[Unit; 2], _>(&mut store, 0, 4).unwrap();
+    view.copy_from_slice(&[]).unwrap();
+}
+
+#[test]
+fn memory_strings_work() {
+    let (mut store, memory) = memory_with_store();
+    memory.write(&mut store, 0, b"hello\0\xFF\0").unwrap();
+    assert_eq!(memory.read_str(&store, 0, 5).unwrap(), "hello");
+    assert_eq!(memory.read_c_str(&store, 0, 5).unwrap(), "hello");
+    assert!(matches!(
+        memory.read_c_str(&store, 0, 4),
+        Err(MemoryError::StringTooLong)
+    ));
+    assert!(matches!(
+        memory.read_str(&store, 0, 7),
+        Err(MemoryError::InvalidUtf8)
+    ));
+    assert!(matches!(
+        memory.read_c_str(&store, 6, 10),
+        Err(MemoryError::InvalidUtf8)
+    ));
+    let len = memory.data(&store).len();
+    memory.write(&mut store, len - 2, b"ab").unwrap();
+    assert!(matches!(
+        memory.read_c_str(&store, len - 2, 100),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+    assert!(matches!(
+        memory.read_str(&store, len - 2, 3),
+        Err(MemoryError::OutOfBoundsAccess)
+    ));
+}
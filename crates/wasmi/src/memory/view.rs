@@ -0,0 +1,232 @@
+use super::MemoryError;
+use core::{marker::PhantomData, str};
+use std::vec::Vec;
+
+/// Plain old data types that can be copied from and to linear memory.
+///
+/// Values are encoded in little-endian byte order, the same as Wasm does.
+///
+/// # Note
+///
+/// This is implemented for all primitive integer and float types as well as
+/// for arrays of [`Pod`] types. Users may implement it for their own `struct`
+/// types by encoding their fields one after another.
+///
+/// Views of zero sized [`Pod`] types, i.e. with a [`Pod::SIZE`] of zero, are always empty.
+pub trait Pod: Copy {
+    /// The number of bytes a value of this type occupies in linear memory.
+    const SIZE: usize;
+
+    /// Decodes a value from exactly [`Pod::SIZE`] little-endian `bytes`.
+    fn from_le(bytes: &[u8]) -> Self;
+
+    /// Encodes `self` into exactly [`Pod::SIZE`] little-endian `bytes`.
+    fn to_le(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_pod_for {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl Pod for $ty {
+                const SIZE: usize = core::mem::size_of::<$ty>();
+
+                fn from_le(bytes: &[u8]) -> Self {
+                    let mut buffer = [0x00_u8; core::mem::size_of::<$ty>()];
+                    buffer.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buffer)
+                }
+
+                fn to_le(self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes())
+                }
+            }
+        )*
+    };
+}
+impl_pod_for!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+impl<T: Pod, const N: usize> Pod for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+
+    fn from_le(bytes: &[u8]) -> Self {
+        core::array::from_fn(|i| T::from_le(&bytes[i * T::SIZE..][..T::SIZE]))
+    }
+
+    fn to_le(self, bytes: &mut [u8]) {
+        for (i, value) in self.into_iter().enumerate() {
+            value.to_le(&mut bytes[i * T::SIZE..][..T::SIZE]);
+        }
+    }
+}
+
+/// Returns the bytes of `len` consecutive values of type `T` at `ptr`.
+///
+/// # Errors
+///
+/// If the values are not entirely within the bounds of `data`.
+pub(super) fn typed_range<T: Pod>(
+    data_len: usize,
+    ptr: usize,
+    len: usize,
+) -> Result<core::ops::Range<usize>, MemoryError> {
+    let end = len
+        .checked_mul(T::SIZE)
+        .and_then(|size| ptr.checked_add(size))
+        .filter(|&end| end <= data_len)
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    Ok(ptr..end)
+}
+
+/// Decodes the UTF-8 encoded `bytes` as string slice.
+///
+/// # Errors
+///
+/// If `bytes` are not valid UTF-8.
+pub(super) fn decode_str(bytes: &[u8]) -> Result<&str, MemoryError> {
+    str::from_utf8(bytes).map_err(|_| MemoryError::InvalidUtf8)
+}
+
+/// Returns the bytes of the zero terminated string at the start of `bytes`.
+///
+/// The returned bytes do not include the zero terminator.
+///
+/// # Errors
+///
+/// - If `bytes` contains no zero terminator.
+/// - If there is no zero terminator within the first `max_len` string bytes.
+pub(super) fn c_str_bytes(bytes: &[u8], max_len: usize) -> Result<&[u8], MemoryError> {
+    let limit = max_len.saturating_add(1);
+    let Some(len) = bytes.iter().take(limit).position(|&byte| byte == 0) else {
+        if bytes.len() < limit {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        return Err(MemoryError::StringTooLong);
+    };
+    Ok(&bytes[..len])
+}
+
+/// A bounds checked shared view of consecutive values of type `T` in linear memory.
+///
+/// Created via [`Memory::view`](crate::Memory::view).
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryView<'a, T> {
+    /// The underlying bytes of the viewed values.
+    bytes: &'a [u8],
+    /// The type of the viewed values.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Pod> MemoryView<'a, T> {
+    /// Creates a new [`MemoryView`] for the `bytes` of some linear memory.
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        debug_assert_eq!(bytes.len() % T::SIZE.max(1), 0);
+        Self {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of values in the [`MemoryView`].
+    pub fn len(&self) -> usize {
+        self.bytes.len().checked_div(T::SIZE).unwrap_or(0)
+    }
+
+    /// Returns `true` if the [`MemoryView`] contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at `index` or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(T::from_le(&self.bytes[index * T::SIZE..][..T::SIZE]))
+    }
+
+    /// Returns an iterator over all values of the [`MemoryView`].
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + 'a {
+        let bytes = self.bytes;
+        (0..self.len()).map(move |index| T::from_le(&bytes[index * T::SIZE..][..T::SIZE]))
+    }
+
+    /// Copies all values of the [`MemoryView`] into a new `Vec`.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    /// Returns the raw little-endian bytes of the [`MemoryView`].
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+/// A bounds checked exclusive view of consecutive values of type `T` in linear memory.
+///
+/// Created via [`Memory::view_mut`](crate::Memory::view_mut).
+#[derive(Debug)]
+pub struct MemoryViewMut<'a, T> {
+    /// The underlying bytes of the viewed values.
+    bytes: &'a mut [u8],
+    /// The type of the viewed values.
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Pod> MemoryViewMut<'a, T> {
+    /// Creates a new [`MemoryViewMut`] for the `bytes` of some linear memory.
+    pub(super) fn new(bytes: &'a mut [u8]) -> Self {
+        debug_assert_eq!(bytes.len() % T::SIZE.max(1), 0);
+        Self {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a shared [`MemoryView`] to the same values.
+    pub fn as_view(&self) -> MemoryView<'_, T> {
+        MemoryView::new(self.bytes)
+    }
+
+    /// Returns the number of values in the [`MemoryViewMut`].
+    pub fn len(&self) -> usize {
+        self.as_view().len()
+    }
+
+    /// Returns `true` if the [`MemoryViewMut`] contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at `index` or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.as_view().get(index)
+    }
+
+    /// Sets the value at `index` to `value`.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) -> Result<(), MemoryError> {
+        if index >= self.len() {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        value.to_le(&mut self.bytes[index * T::SIZE..][..T::SIZE]);
+        Ok(())
+    }
+
+    /// Copies all `values` into the [`MemoryViewMut`].
+    ///
+    /// # Errors
+    ///
+    /// If the number of `values` does not match the length of the [`MemoryViewMut`].
+    pub fn copy_from_slice(&mut self, values: &[T]) -> Result<(), MemoryError> {
+        if values.len() != self.len() {
+            return Err(MemoryError::OutOfBoundsAccess);
+        }
+        for (index, value) in values.iter().enumerate() {
+            value.to_le(&mut self.bytes[index * T::SIZE..][..T::SIZE]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use super::{MemoryError, MemoryImage};
+use core::{ptr::NonNull, slice};
+use std::os::fd::AsRawFd as _;
+
+/// The size of the inaccessible guard region following the reserved linear memory.
+///
+/// # Note
+///
+/// Wasmi bounds checks all linear memory accesses. The guard region exists as
+/// defense in depth so that accesses slipping past those checks fault instead
+/// of silently reading or writing memory that does not belong to the buffer.
+const GUARD_SIZE: usize = 0x1_0000;
+
+/// A byte buffer that is backed by OS provided virtual memory.
+///
+/// # Note
+///
+/// - The virtual address space for the maximum size of the buffer is reserved
+///   upon construction so that growing the buffer never reallocates or copies.
+/// - Only the first `len` bytes are accessible. All remaining bytes of the
+///   reservation as well as the guard region are mapped as inaccessible.
+#[derive(Debug)]
+pub struct VirtualMemory {
+    /// The start of the reserved virtual memory.
+    ptr: NonNull<u8>,
+    /// The number of accessible bytes starting at `ptr`.
+    len: usize,
+    /// The number of accessible bytes the buffer is allowed to grow to.
+    capacity: usize,
+    /// The total number of reserved bytes including the guard region.
+    reserved: usize,
+}
+
+// Safety: `VirtualMemory` exclusively owns its mapping just like a `Vec<u8>`
+//         owns its heap allocation. Therefore it is safe to send or share
+//         it across thread boundaries.
+unsafe impl Send for VirtualMemory {}
+unsafe impl Sync for VirtualMemory {}
+
+impl VirtualMemory {
+    /// Creates a new [`VirtualMemory`] with `initial_len` accessible bytes
+    /// that can grow up to `maximum_len` bytes without reallocation.
+    ///
+    /// # Errors
+    ///
+    /// - If `initial_len` is greater than `maximum_len`.
+    /// - If the OS fails to reserve the required virtual address space.
+    pub fn new(initial_len: usize, maximum_len: usize) -> Result<Self, MemoryError> {
+        if initial_len > maximum_len {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        let (ptr, reserved) = reserve(maximum_len)?;
+        let mut memory = Self {
+            ptr,
+            len: 0,
+            capacity: maximum_len,
+            reserved,
+        };
+        memory.grow(initial_len)?;
+        Ok(memory)
+    }
+
+    /// Creates a new [`VirtualMemory`] like [`VirtualMemory::new`] whose first bytes are
+    /// mapped copy-on-write from the in-memory file of the `image`.
+    ///
+    /// # Note
+    ///
+    /// Falls back to copying the bytes of the `image` if it is not backed by a file.
+    ///
+    /// # Errors
+    ///
+    /// - If the [`VirtualMemory`] cannot be created.
+    /// - If the `image` does not fit into the `initial_len` accessible bytes.
+    /// - If the OS fails to map the `image`.
+    pub fn with_image(
+        initial_len: usize,
+        maximum_len: usize,
+        image: &MemoryImage,
+    ) -> Result<Self, MemoryError> {
+        let mut memory = Self::new(initial_len, maximum_len)?;
+        let Some(file) = image.file() else {
+            image.copy_to(memory.data_mut())?;
+            return Ok(memory);
+        };
+        let mapped_len = image.mapped_len();
+        if mapped_len > memory.len {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        // Safety: The range `[0, mapped_len)` is part of the accessible bytes of the
+        //         reservation which is replaced by a private mapping of the file.
+        //         Writes to the mapping copy the affected pages and never alter the file.
+        let ptr = unsafe {
+            libc::mmap(
+                memory.ptr.as_ptr().cast(),
+                mapped_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_FIXED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(MemoryError::OutOfBoundsAllocation);
+        }
+        Ok(memory)
+    }
+
+    /// Grows the accessible bytes of the [`VirtualMemory`] to `new_len` in place.
+    ///
+    /// # Note
+    ///
+    /// The newly accessible bytes are zero initialized by the OS.
+    ///
+    /// # Errors
+    ///
+    /// - If `new_len` exceeds the reserved capacity.
+    /// - If the OS fails to make the newly requested bytes accessible.
+    ///
+    /// # Panics
+    ///
+    /// If `new_len` is smaller than the current length.
+    pub fn grow(&mut self, new_len: usize) -> Result<(), MemoryError> {
+        assert!(new_len >= self.len);
+        if new_len > self.capacity {
+            return Err(MemoryError::OutOfBoundsGrowth);
+        }
+        if new_len == self.len {
+            return Ok(());
+        }
+        // Safety: The range `[len, new_len)` is part of the reserved mapping
+        //         since `new_len` does not exceed the capacity.
+        unsafe { commit(self.ptr, self.len, new_len)? };
+        self.len = new_len;
+        Ok(())
+    }
+
+    /// Returns the number of accessible bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a shared slice to the accessible bytes.
+    pub fn data(&self) -> &[u8] {
+        // Safety: The first `len` bytes are mapped as readable and writable.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns an exclusive slice to the accessible bytes.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        // Safety: The first `len` bytes are mapped as readable and writable.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for VirtualMemory {
+    fn drop(&mut self) {
+        // Safety: The mapping was created in `VirtualMemory::new` with
+        //         the same base pointer and reservation length.
+        unsafe { release(self.ptr, self.reserved) }
+    }
+}
+
+/// Reserves inaccessible virtual memory for `len` bytes followed by the guard region.
+///
+/// Returns the start of the reservation and the total number of reserved bytes.
+///
+/// # Errors
+///
+/// If the OS fails to reserve the required virtual address space.
+pub(super) fn reserve(len: usize) -> Result<(NonNull<u8>, usize), MemoryError> {
+    let reserved = len
+        .checked_add(GUARD_SIZE)
+        .ok_or(MemoryError::OutOfBoundsAllocation)?;
+    // Safety: We request a fresh anonymous private mapping that does not
+    //         alias any memory that is owned by Rust.
+    let ptr = unsafe {
+        libc::mmap(
+            core::ptr::null_mut(),
+            reserved,
+            libc::PROT_NONE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_NORESERVE,
+            -1,
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(MemoryError::OutOfBoundsAllocation);
+    }
+    let ptr = NonNull::new(ptr.cast::<u8>()).ok_or(MemoryError::OutOfBoundsAllocation)?;
+    Ok((ptr, reserved))
+}
+
+/// Makes the reserved bytes in `[from, to)` relative to `ptr` accessible.
+///
+/// # Note
+///
+/// The newly accessible bytes are zero initialized by the OS.
+///
+/// # Safety
+///
+/// The range must be part of a reservation starting at `ptr`
+/// that was created via [`reserve`].
+///
+/// # Errors
+///
+/// If the OS fails to make the requested bytes accessible.
+pub(super) unsafe fn commit(ptr: NonNull<u8>, from: usize, to: usize) -> Result<(), MemoryError> {
+    debug_assert!(from <= to);
+    let result = libc::mprotect(
+        ptr.as_ptr().add(from).cast(),
+        to - from,
+        libc::PROT_READ | libc::PROT_WRITE,
+    );
+    if result != 0 {
+        return Err(MemoryError::OutOfBoundsGrowth);
+    }
+    Ok(())
+}
+
+/// Releases the `reserved` bytes of a reservation starting at `ptr`.
+///
+/// # Safety
+///
+/// The reservation must have been created via [`reserve`] and
+/// must no longer be accessed afterwards.
+pub(super) unsafe fn release(ptr: NonNull<u8>, reserved: usize) {
+    libc::munmap(ptr.as_ptr().cast(), reserved);
+}
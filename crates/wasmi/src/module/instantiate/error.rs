@@ -50,6 +50,29 @@ pub enum InstantiationError {
         index: u32,
     },
     TooManyInstances,
+    /// Caused when an [`InstanceSnapshot`] is used with a [`Module`] it was not taken from.
+    ///
+    /// [`InstanceSnapshot`]: crate::InstanceSnapshot
+    /// [`Module`]: crate::Module
+    SnapshotModuleMismatch,
+    /// Caused when an [`InstanceSnapshot`] cannot represent a reference of the snapshotted instance.
+    ///
+    /// # Note
+    ///
+    /// This is the case for non-`null` external references as well as for function
+    /// references to functions that are neither defined nor imported by the instance.
+    ///
+    /// [`InstanceSnapshot`]: crate::InstanceSnapshot
+    UnsupportedSnapshotReference,
+    /// Caused when an [`InstanceSnapshot`] is taken of an instance that defines a shared linear memory.
+    ///
+    /// # Note
+    ///
+    /// The bytes of a shared linear memory may be modified concurrently by other threads
+    /// and therefore cannot be captured consistently.
+    ///
+    /// [`InstanceSnapshot`]: crate::InstanceSnapshot
+    UnsupportedSnapshotMemory,
 }
 
 #[cfg(feature = "std")]
@@ -86,7 +109,16 @@ impl Display for InstantiationError {
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::SnapshotModuleMismatch => {
+                write!(f, "instance snapshot does not belong to the instantiated module")
+            }
+            Self::UnsupportedSnapshotReference => {
+                write!(f, "encountered a reference that cannot be stored in an instance snapshot")
+            }
+            Self::UnsupportedSnapshotMemory => {
+                write!(f, "cannot take an instance snapshot of a shared linear memory")
+            }
         }
     }
 }
@@ -1,12 +1,15 @@
 mod error;
 mod pre;
+mod snapshot;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::{error::InstantiationError, pre::InstancePre};
+use self::snapshot::SnapshotValue;
+pub use self::{error::InstantiationError, pre::InstancePre, snapshot::InstanceSnapshot};
 use super::{element::ElementSegmentKind, export, ConstExpr, DataSegmentKind, Module};
 use crate::{
+    errors::TableError,
     func::WasmFuncEntity,
     memory::{DataSegment, MemoryError},
     value::WithType,
@@ -77,6 +80,58 @@ impl Module {
         Ok(InstancePre::new(handle, builder))
     }
 
+    /// Instantiates a new [`Instance`] from the given [`InstanceSnapshot`] of the [`Module`].
+    ///
+    /// Uses the given `context` to store the instance data to.
+    /// The given `externals` are joined with the imports in the same order in which they occurred.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Module::instantiate`] this neither evaluates the active data and element
+    /// segments of the [`Module`] nor executes its `start` function. Instead the internally
+    /// defined linear memories, tables and global variables are restored from the `snapshot`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `snapshot` has not been taken from an instance of the [`Module`].
+    /// - If the given `externals` do not satisfy the required imports.
+    /// - If the restored linear memories or tables exceed the resource limits of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    pub(crate) fn instantiate_snapshot<I>(
+        &self,
+        mut context: impl AsContextMut,
+        externals: I,
+        snapshot: &InstanceSnapshot,
+    ) -> Result<Instance, Error>
+    where
+        I: IntoIterator<Item = Extern>,
+    {
+        snapshot.ensure_module(self)?;
+        context
+            .as_context_mut()
+            .store
+            .check_new_instances_limit(1)?;
+        let handle = context.as_context_mut().store.inner.alloc_instance();
+        let mut builder = InstanceEntity::build(self);
+
+        self.extract_imports(&context, &mut builder, externals)?;
+        self.extract_functions(&mut context, &mut builder, handle);
+        self.extract_tables(&mut context, &mut builder)?;
+        self.restore_memories(&mut context, &mut builder, snapshot)?;
+        self.extract_globals(&mut context, &mut builder);
+        self.extract_exports(&mut builder);
+
+        self.restore_snapshot(&mut context, &mut builder, snapshot)?;
+
+        context
+            .as_context_mut()
+            .store
+            .inner
+            .initialize_instance(handle, builder.finish());
+        Ok(handle)
+    }
+
     /// Extract the Wasm imports from the module and zips them with the given external values.
     ///
     /// This also stores imported references into the [`Instance`] under construction.
@@ -344,6 +399,104 @@ impl Module {
         Ok(())
     }
 
+    /// Creates the internally defined linear memories of the [`Instance`] from the [`InstanceSnapshot`].
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Module::extract_memories`] the linear memories are directly created with
+    /// the size and bytes of the snapshotted linear memories. Linear memories backed by
+    /// virtual memory map the snapshotted bytes copy-on-write if possible.
+    ///
+    /// # Errors
+    ///
+    /// If the linear memories cannot be created, e.g. due to resource limits of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    fn restore_memories(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+        snapshot: &InstanceSnapshot,
+    ) -> Result<(), MemoryError> {
+        context
+            .as_context_mut()
+            .store
+            .check_new_memories_limit(self.len_memories())?;
+        for (memory_type, memory) in self.internal_memories().zip(snapshot.memories()) {
+            let memory = Memory::new_with_image(
+                context.as_context_mut(),
+                *memory_type,
+                memory.pages(),
+                memory.image(),
+            )?;
+            builder.push_memory(memory);
+        }
+        Ok(())
+    }
+
+    /// Restores the internally defined entities of the [`Instance`] from the [`InstanceSnapshot`].
+    ///
+    /// This also registers the data and element segments of the [`Module`] with
+    /// the [`Instance`] under construction, dropping those that have been dropped
+    /// at the time the [`InstanceSnapshot`] has been taken.
+    fn restore_snapshot(
+        &self,
+        context: &mut impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+        snapshot: &InstanceSnapshot,
+    ) -> Result<(), Error> {
+        let imports = &self.header.inner.imports;
+        let restore_value = |value: SnapshotValue| match value {
+            SnapshotValue::Value(value) => value,
+            SnapshotValue::Func(index) => FuncRef::new(builder.get_func(index)).into(),
+        };
+        for (index, table) in (imports.len_tables..).zip(snapshot.tables()) {
+            let target = builder.get_table(index as u32);
+            let (inner, mut limiter) = context
+                .as_context_mut()
+                .store
+                .store_inner_and_resource_limiter_ref();
+            let target = inner.resolve_table_mut(&target);
+            let len_elements = table.elements().len() as u32;
+            if let Some(delta) = len_elements.checked_sub(target.size()) {
+                target
+                    .grow_untyped(delta, UntypedValue::default(), None, &mut limiter)
+                    .map_err(|_| TableError::GrowOutOfBounds {
+                        maximum: target.ty().maximum().unwrap_or(u32::MAX),
+                        current: target.size(),
+                        delta,
+                    })?;
+            }
+            for (index, element) in (0..).zip(table.elements()) {
+                target.set_untyped(index, restore_value(*element))?;
+            }
+        }
+        for (index, value) in (imports.len_globals..).zip(snapshot.globals()) {
+            let target = builder.get_global(index as u32);
+            context
+                .as_context_mut()
+                .store
+                .inner
+                .resolve_global_mut(&target)
+                .set_untyped(restore_value(*value));
+        }
+        for (index, segment) in self.header.inner.element_segments.iter().enumerate() {
+            let element = match snapshot.is_elem_dropped(index) {
+                true => ElementSegment::new_dropped(context.as_context_mut(), segment),
+                false => ElementSegment::new(context.as_context_mut(), segment),
+            };
+            builder.push_element_segment(element);
+        }
+        for (index, segment) in self.data_segments.iter().enumerate() {
+            let data = match snapshot.is_data_dropped(index) {
+                true => DataSegment::new_dropped(context.as_context_mut()),
+                false => DataSegment::new(context.as_context_mut(), segment),
+            };
+            builder.push_data_segment(data);
+        }
+        Ok(())
+    }
+
     /// Initializes the [`Instance`] linear memories with the Wasm data segments of the [`Module`].
     fn initialize_memory_data(
         &self,
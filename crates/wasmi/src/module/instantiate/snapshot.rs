@@ -0,0 +1,304 @@
+use super::InstantiationError;
+use crate::{
+    core::ValueType,
+    engine::DedupFuncType,
+    memory::MemoryImage,
+    module::Module,
+    AsContext,
+    FuncRef,
+    Instance,
+    InstanceEntity,
+};
+use std::{boxed::Box, collections::BTreeMap, sync::Arc};
+use wasmi_core::{Pages, UntypedValue};
+
+/// A snapshot of the internal state of a fully initialized [`Instance`].
+///
+/// # Note
+///
+/// An [`InstanceSnapshot`] captures the contents of all linear memories, tables
+/// and global variables that are defined by an [`Instance`], including all the
+/// side effects of its `start` function or any other function executed before
+/// the snapshot has been taken.
+///
+/// New instances of the same [`Module`] can be created from an [`InstanceSnapshot`]
+/// via [`Linker::instantiate_snapshot`] which skips the evaluation of all active data
+/// and element segments as well as the execution of the `start` function.
+/// Cloning an [`InstanceSnapshot`] is cheap and it can be shared across threads.
+///
+/// Imported entities are not part of an [`InstanceSnapshot`] since they are owned by
+/// the host. Therefore they are not restored upon instantiation from a snapshot.
+///
+/// [`Linker::instantiate_snapshot`]: crate::Linker::instantiate_snapshot
+#[derive(Debug, Clone)]
+pub struct InstanceSnapshot {
+    inner: Arc<InstanceSnapshotInner>,
+}
+
+/// The internal data of an [`InstanceSnapshot`].
+#[derive(Debug)]
+struct InstanceSnapshotInner {
+    /// The function types of the snapshotted [`Module`] used to identify it.
+    func_types: Arc<[DedupFuncType]>,
+    /// The snapshots of all internally defined linear memories.
+    memories: Box<[MemorySnapshot]>,
+    /// The snapshots of all internally defined tables.
+    tables: Box<[TableSnapshot]>,
+    /// The values of all internally defined global variables.
+    globals: Box<[SnapshotValue]>,
+    /// Is `true` for every data segment that has been dropped.
+    dropped_datas: Box<[bool]>,
+    /// Is `true` for every element segment that has been dropped.
+    dropped_elems: Box<[bool]>,
+}
+
+/// The snapshot of a single linear memory.
+#[derive(Debug)]
+pub(super) struct MemorySnapshot {
+    /// The amount of pages of the linear memory at the time of the snapshot.
+    pages: Pages,
+    /// The bytes of the linear memory without trailing zeros.
+    ///
+    /// # Note
+    ///
+    /// - Since newly allocated linear memory is always zero initialized
+    ///   we do not need to store and copy the trailing zero bytes.
+    /// - Linear memories backed by virtual memory map the image copy-on-write
+    ///   upon restoration if possible instead of copying it.
+    image: MemoryImage,
+}
+
+impl MemorySnapshot {
+    /// Returns the amount of pages of the snapshotted linear memory.
+    pub fn pages(&self) -> Pages {
+        self.pages
+    }
+
+    /// Returns the non-zero prefix of the bytes of the snapshotted linear memory.
+    pub fn image(&self) -> &MemoryImage {
+        &self.image
+    }
+}
+
+/// The snapshot of a single table.
+#[derive(Debug)]
+pub(super) struct TableSnapshot {
+    /// The elements of the table at the time of the snapshot.
+    elements: Box<[SnapshotValue]>,
+}
+
+impl TableSnapshot {
+    /// Returns the elements of the snapshotted table.
+    pub fn elements(&self) -> &[SnapshotValue] {
+        &self.elements[..]
+    }
+}
+
+/// A value stored in an [`InstanceSnapshot`].
+///
+/// # Note
+///
+/// Function references cannot be stored as is since they are bound to the
+/// [`Store`] of the snapshotted [`Instance`]. Instead they are stored as
+/// function indices into the function index space of the [`Instance`] and
+/// are remapped upon instantiation.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Copy, Clone)]
+pub(super) enum SnapshotValue {
+    /// A value that is independent of its [`Instance`].
+    Value(UntypedValue),
+    /// A non-`null` function reference by its index within the [`Instance`].
+    Func(u32),
+}
+
+impl InstanceSnapshot {
+    /// Creates a new [`InstanceSnapshot`] from the current state of the `instance`.
+    ///
+    /// # Note
+    ///
+    /// The `instance` must have been instantiated from `module`.
+    ///
+    /// # Errors
+    ///
+    /// - If `instance` has not been instantiated from `module`.
+    /// - If `instance` defines a shared linear memory.
+    /// - If a table or global variable of the `instance` refers to a function that is
+    ///   neither defined nor imported by the `instance`.
+    /// - If a table or global variable of the `instance` holds a non-`null` [`ExternRef`].
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` does not own the `instance`.
+    ///
+    /// [`ExternRef`]: crate::ExternRef
+    pub fn new(
+        ctx: impl AsContext,
+        module: &Module,
+        instance: &Instance,
+    ) -> Result<Self, InstantiationError> {
+        let store = &ctx.as_context().store.inner;
+        let entity = store.resolve_instance(instance);
+        if !entity.is_instance_of(module) {
+            return Err(InstantiationError::SnapshotModuleMismatch);
+        }
+        let imports = &module.header.inner.imports;
+        let funcs = FuncIndices::new(entity);
+        let memories = (imports.len_memories..)
+            .zip(module.internal_memories())
+            .map(|(index, _)| {
+                let memory = instance_entity_get(entity.get_memory(index as u32), index);
+                let memory = store.resolve_memory(&memory);
+                if memory.shared().is_some() {
+                    return Err(InstantiationError::UnsupportedSnapshotMemory);
+                }
+                let data = memory.data();
+                let len_image = data
+                    .iter()
+                    .rposition(|byte| *byte != 0)
+                    .map(|last| last + 1)
+                    .unwrap_or(0);
+                Ok(MemorySnapshot {
+                    pages: memory.current_pages(),
+                    image: MemoryImage::new(&data[..len_image], store.engine()),
+                })
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+        let tables = (imports.len_tables..)
+            .zip(module.internal_tables())
+            .map(|(index, _)| {
+                let table = instance_entity_get(entity.get_table(index as u32), index);
+                let table = store.resolve_table(&table);
+                let ty = table.ty().element();
+                let elements = (0..table.size())
+                    .map(|index| {
+                        let value = table
+                            .get_untyped(index)
+                            .unwrap_or_else(|| panic!("table element {index} is out of bounds"));
+                        funcs.snapshot_value(ty, value)
+                    })
+                    .collect::<Result<Box<[_]>, _>>()?;
+                Ok(TableSnapshot { elements })
+            })
+            .collect::<Result<Box<[_]>, InstantiationError>>()?;
+        let globals = (imports.len_globals..)
+            .zip(module.internal_globals())
+            .map(|(index, _)| {
+                let global = instance_entity_get(entity.get_global(index as u32), index);
+                let global = store.resolve_global(&global);
+                funcs.snapshot_value(global.ty().content(), global.get_untyped())
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+        let dropped_datas = (0..module.data_segments.len())
+            .map(|index| {
+                let segment = instance_entity_get(entity.get_data_segment(index as u32), index);
+                store.resolve_data_segment(&segment).bytes().is_empty()
+            })
+            .collect::<Box<[_]>>();
+        let dropped_elems = (0..module.header.inner.element_segments.len())
+            .map(|index| {
+                let segment = instance_entity_get(entity.get_element_segment(index as u32), index);
+                store.resolve_element_segment(&segment).size() == 0
+            })
+            .collect::<Box<[_]>>();
+        Ok(Self {
+            inner: Arc::new(InstanceSnapshotInner {
+                func_types: module.func_types_cloned(),
+                memories,
+                tables,
+                globals,
+                dropped_datas,
+                dropped_elems,
+            }),
+        })
+    }
+
+    /// Returns `Ok` if the [`InstanceSnapshot`] has been taken from an instance of `module`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`InstanceSnapshot`] does not belong to the `module`.
+    pub(super) fn ensure_module(&self, module: &Module) -> Result<(), InstantiationError> {
+        if !Arc::ptr_eq(&self.inner.func_types, module.func_types()) {
+            return Err(InstantiationError::SnapshotModuleMismatch);
+        }
+        Ok(())
+    }
+
+    /// Returns the snapshots of the internally defined linear memories.
+    pub(super) fn memories(&self) -> &[MemorySnapshot] {
+        &self.inner.memories[..]
+    }
+
+    /// Returns the snapshots of the internally defined tables.
+    pub(super) fn tables(&self) -> &[TableSnapshot] {
+        &self.inner.tables[..]
+    }
+
+    /// Returns the values of the internally defined global variables.
+    pub(super) fn globals(&self) -> &[SnapshotValue] {
+        &self.inner.globals[..]
+    }
+
+    /// Returns `true` if the data segment at `index` has been dropped.
+    pub(super) fn is_data_dropped(&self, index: usize) -> bool {
+        self.inner.dropped_datas[index]
+    }
+
+    /// Returns `true` if the element segment at `index` has been dropped.
+    pub(super) fn is_elem_dropped(&self, index: usize) -> bool {
+        self.inner.dropped_elems[index]
+    }
+}
+
+/// Returns the entity of an [`InstanceEntity`] query.
+///
+/// # Panics
+///
+/// If the entity is missing which is a bug since the instance has been validated.
+fn instance_entity_get<T>(entity: Option<T>, index: usize) -> T {
+    entity.unwrap_or_else(|| panic!("missing instance entity at index {index}"))
+}
+
+/// Maps encoded function references of an [`InstanceEntity`] to their function indices.
+#[derive(Debug)]
+struct FuncIndices {
+    indices: BTreeMap<u64, u32>,
+}
+
+impl FuncIndices {
+    /// Creates a new [`FuncIndices`] for all functions of the [`InstanceEntity`].
+    fn new(instance: &InstanceEntity) -> Self {
+        let indices = (0..)
+            .map_while(|index| instance.get_func(index).map(|func| (func, index)))
+            .map(|(func, index)| (u64::from(UntypedValue::from(FuncRef::new(func))), index))
+            .collect();
+        Self { indices }
+    }
+
+    /// Converts the untyped `value` of type `ty` into a [`SnapshotValue`].
+    ///
+    /// # Errors
+    ///
+    /// - If `value` is a function reference that is unknown to the instance.
+    /// - If `value` is a non-`null` external reference.
+    fn snapshot_value(
+        &self,
+        ty: ValueType,
+        value: UntypedValue,
+    ) -> Result<SnapshotValue, InstantiationError> {
+        let bits = u64::from(value);
+        match ty {
+            ValueType::FuncRef if bits != 0 => self
+                .indices
+                .get(&bits)
+                .copied()
+                .map(SnapshotValue::Func)
+                .ok_or(InstantiationError::UnsupportedSnapshotReference),
+            ValueType::ExternRef if bits != 0 => {
+                Err(InstantiationError::UnsupportedSnapshotReference)
+            }
+            _ => Ok(SnapshotValue::Value(value)),
+        }
+    }
+}
@@ -27,7 +27,7 @@ pub use self::{
     export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
-    instantiate::{InstancePre, InstantiationError},
+    instantiate::{InstancePre, InstanceSnapshot, InstantiationError},
     read::{Read, ReadError},
 };
 use crate::{
@@ -294,6 +294,15 @@ impl Module {
         self.header.inner.func_types.clone()
     }
 
+    /// Returns a shared reference to the function types of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// The function types are unique to the [`Module`] and can be compared by reference.
+    pub(crate) fn func_types(&self) -> &Arc<[DedupFuncType]> {
+        &self.header.inner.func_types
+    }
+
     /// Returns an iterator over the imports of the [`Module`].
     pub fn imports(&self) -> ModuleImportsIter {
         let len_imported_funcs = self.header.inner.imports.len_funcs;
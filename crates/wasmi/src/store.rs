@@ -281,6 +281,12 @@ impl Fuel {
         Some(consumed)
     }
 
+    /// Returns the amount of [`Fuel`] remaining for executions of the [`Store`].
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.check_fuel_metering_enabled().ok()?;
+        Some(self.remaining)
+    }
+
     /// Synthetically consumes an amount of [`Fuel`] from the [`Store`].
     ///
     /// Returns the remaining amount of [`Fuel`] after this operation.
@@ -692,7 +698,6 @@ impl StoreInner {
     ///
     /// - If the [`ElementSegment`] does not originate from this [`Store`].
     /// - If the [`ElementSegment`] cannot be resolved to its entity.
-    #[allow(unused)] // Note: We allow this unused API to exist to uphold code symmetry.
     pub fn resolve_element_segment(&self, segment: &ElementSegment) -> &ElementSegmentEntity {
         self.resolve(segment.as_inner(), &self.elems)
     }
@@ -779,6 +784,16 @@ impl StoreInner {
         (mem, data, fuel)
     }
 
+    /// Returns a shared reference to the [`DataSegmentEntity`] associated to the given [`DataSegment`].
+    ///
+    /// # Panics
+    ///
+    /// - If the [`DataSegment`] does not originate from this [`Store`].
+    /// - If the [`DataSegment`] cannot be resolved to its entity.
+    pub fn resolve_data_segment(&self, segment: &DataSegment) -> &DataSegmentEntity {
+        self.resolve(segment.as_inner(), &self.datas)
+    }
+
     /// Returns an exclusive reference to the [`DataSegmentEntity`] associated to the given [`DataSegment`].
     ///
     /// # Panics
@@ -940,6 +955,13 @@ impl<T> Store<T> {
         self.inner.fuel.fuel_consumed()
     }
 
+    /// Returns the amount of fuel remaining for executions of the [`Store`].
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    pub fn remaining_fuel(&self) -> Option<u64> {
+        self.inner.fuel.remaining_fuel()
+    }
+
     /// Synthetically consumes an amount of fuel for the [`Store`].
     ///
     /// Returns the remaining amount of fuel after this operation.
@@ -54,6 +54,20 @@ impl ElementSegment {
             .alloc_element_segment(entity)
     }
 
+    /// Allocates a new [`ElementSegment`] of the same type as `segment` whose items are dropped.
+    ///
+    /// # Note
+    ///
+    /// This is equivalent to dropping the items of a newly allocated [`ElementSegment`]
+    /// but avoids cloning the items of `segment` in the first place.
+    pub fn new_dropped(mut ctx: impl AsContextMut, segment: &module::ElementSegment) -> Self {
+        let entity = ElementSegmentEntity::empty(segment.ty());
+        ctx.as_context_mut()
+            .store
+            .inner
+            .alloc_element_segment(entity)
+    }
+
     /// Returns the number of items in the [`ElementSegment`].
     pub fn size(&self, ctx: impl AsContext) -> u32 {
         ctx.as_context()
@@ -0,0 +1,76 @@
+//! Tests for the [`Caller`] accessors available to host functions.
+
+use wasmi::{Caller, Config, Engine, Func, Linker, Memory, MemoryType, Module, Store, Value};
+
+#[test]
+fn caller_accessors_work() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = <Store<Vec<i64>>>::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<i64>>>::new(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let host = Func::wrap(&mut store, |mut caller: Caller<Vec<i64>>| {
+        assert!(caller.instance().is_some());
+        // The imported memory is neither exported nor otherwise reachable by name.
+        let memory = caller.get_memory(0).unwrap();
+        assert!(caller.get_memory(1).is_none());
+        let value = memory.read_value::<u32>(&caller, 8).unwrap();
+        let table = caller.get_table(0).unwrap();
+        let size = table.size(&caller);
+        let global = caller.get_global(0).unwrap();
+        let Value::I64(global) = global.get(&caller) else {
+            panic!("unexpected global value type")
+        };
+        assert!(caller.get_table(1).is_none());
+        assert!(caller.get_global(1).is_none());
+        let fuel = caller.remaining_fuel().unwrap();
+        caller.consume_fuel(10).unwrap();
+        assert_eq!(caller.remaining_fuel(), Some(fuel - 10));
+        caller
+            .data_mut()
+            .extend([i64::from(value), i64::from(size), global]);
+    });
+    linker.define("env", "memory", memory).unwrap();
+    linker.define("env", "host", host).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "memory" (memory 1))
+            (import "env" "host" (func $host))
+            (table 3 funcref)
+            (global i64 (i64.const -7))
+            (data (i32.const 8) "\2a\00\00\00")
+            (func (export "run")
+                (call $host)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    store.add_fuel(1_000).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), ()>(&store, "run").unwrap();
+    run.call(&mut store, ()).unwrap();
+    assert_eq!(store.data(), &[42, 3, -7]);
+    assert!(store.remaining_fuel().unwrap() < 1_000 - 10);
+}
+
+#[test]
+fn caller_from_host_side_has_no_instance() {
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let host = Func::wrap(&mut store, |caller: Caller<()>| {
+        assert!(caller.instance().is_none());
+        assert!(caller.get_memory(0).is_none());
+        assert!(caller.get_table(0).is_none());
+        assert!(caller.get_global(0).is_none());
+        assert!(caller.remaining_fuel().is_none());
+    });
+    host.call(&mut store, &[], &mut []).unwrap();
+}
@@ -0,0 +1,543 @@
+//! Tests for instantiating and calling Wasm components.
+
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    component::{is_component, Component, ComponentError, ComponentInstance, Linker, Val},
+    errors::ErrorKind,
+    Engine,
+    Error,
+    Store,
+};
+
+/// Parses the component `wat` and instantiates it with the `linker` definitions.
+fn instantiate<T: 'static>(
+    data: T,
+    wat: &str,
+    define: impl FnOnce(&mut Linker<T>),
+) -> Result<(Store<T>, ComponentInstance), Error> {
+    let wasm = wat::parse_str(wat).unwrap();
+    assert!(is_component(&wasm));
+    let engine = Engine::default();
+    let component = Component::new(&engine, &wasm)?;
+    let mut store = Store::new(&engine, data);
+    let mut linker = <Linker<T>>::new(&engine);
+    define(&mut linker);
+    let instance = linker.instantiate(&mut store, &component)?;
+    Ok((store, instance))
+}
+
+/// Calls the exported function `name` with `params` and returns its result.
+fn call<T>(
+    store: &mut Store<T>,
+    instance: &ComponentInstance,
+    name: &str,
+    params: &[Val],
+) -> Option<Val> {
+    let func = instance.get_func(name).unwrap();
+    let mut results = vec![Val::Bool(false); usize::from(func.ty().result().is_some())];
+    func.call(store, params, &mut results).unwrap();
+    results.pop()
+}
+
+/// Asserts that `error` is the [`ComponentError`] matched by `f`.
+fn assert_component_error(error: Error, f: impl FnOnce(&ComponentError) -> bool) {
+    match error.kind() {
+        ErrorKind::Component(error) => assert!(f(error), "unexpected error: {error}"),
+        kind => panic!("expected a component error but found: {kind}"),
+    }
+}
+
+#[test]
+fn scalars() {
+    let (mut store, instance) = instantiate(
+        (),
+        r#"
+        (component
+            (core module $m
+                (func (export "add") (param i64 i64) (result i64)
+                    (i64.add (local.get 0) (local.get 1))
+                )
+                (func (export "not") (param i32) (result i32)
+                    (i32.eqz (local.get 0))
+                )
+                (func (export "scale") (param f64 f32) (result f64)
+                    (f64.mul (local.get 0) (f64.promote_f32 (local.get 1)))
+                )
+                (func (export "next") (param i32) (result i32)
+                    (i32.add (local.get 0) (i32.const 1))
+                )
+            )
+            (core instance $m (instantiate $m))
+            (func (export "add") (param "a" s64) (param "b" s64) (result s64)
+                (canon lift (core func $m "add"))
+            )
+            (func (export "not") (param "x" bool) (result bool)
+                (canon lift (core func $m "not"))
+            )
+            (func (export "scale") (param "x" f64) (param "y" f32) (result f64)
+                (canon lift (core func $m "scale"))
+            )
+            (func (export "next") (param "c" char) (result char)
+                (canon lift (core func $m "next"))
+            )
+        )
+        "#,
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(
+        call(&mut store, &instance, "add", &[Val::S64(-5), Val::S64(3)]),
+        Some(Val::S64(-2))
+    );
+    assert_eq!(
+        call(&mut store, &instance, "not", &[Val::Bool(true)]),
+        Some(Val::Bool(false))
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "scale",
+            &[Val::F64(1.5), Val::F32(4.0)]
+        ),
+        Some(Val::F64(6.0))
+    );
+    assert_eq!(
+        call(&mut store, &instance, "next", &[Val::Char('a')]),
+        Some(Val::Char('b'))
+    );
+}
+
+#[test]
+fn strings() {
+    let (mut store, instance) = instantiate(
+        (),
+        r#"
+        (component
+            (import "prefix" (func $prefix (result string)))
+            (core module $libc
+                (memory (export "memory") 1)
+                (global $bump (mut i32) (i32.const 1024))
+                (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr
+                        (i32.and
+                            (i32.add (global.get $bump) (i32.sub (local.get 2) (i32.const 1)))
+                            (i32.sub (i32.const 0) (local.get 2))
+                        )
+                    )
+                    (global.set $bump (i32.add (local.get $ptr) (local.get 3)))
+                    (local.get $ptr)
+                )
+            )
+            (core instance $libc (instantiate $libc))
+            (alias core export $libc "memory" (core memory $mem))
+            (alias core export $libc "realloc" (core func $realloc))
+            (core func $prefix (canon lower (func $prefix) (memory $mem) (realloc $realloc)))
+            (core module $m
+                (import "libc" "memory" (memory 1))
+                (import "libc" "realloc" (func $realloc (param i32 i32 i32 i32) (result i32)))
+                (import "host" "prefix" (func $prefix (param i32)))
+                (global $calls (mut i32) (i32.const 0))
+                (func (export "greet") (param $name i32) (param $len i32) (result i32)
+                    (local $p i32) (local $plen i32) (local $out i32)
+                    (call $prefix (i32.const 16))
+                    (local.set $p (i32.load (i32.const 16)))
+                    (local.set $plen (i32.load (i32.const 20)))
+                    (local.set $out
+                        (call $realloc
+                            (i32.const 0) (i32.const 0) (i32.const 1)
+                            (i32.add (local.get $plen) (local.get $len))
+                        )
+                    )
+                    (memory.copy (local.get $out) (local.get $p) (local.get $plen))
+                    (memory.copy
+                        (i32.add (local.get $out) (local.get $plen))
+                        (local.get $name)
+                        (local.get $len)
+                    )
+                    (i32.store (i32.const 32) (local.get $out))
+                    (i32.store (i32.const 36) (i32.add (local.get $plen) (local.get $len)))
+                    (i32.const 32)
+                )
+                (func (export "post-greet") (param i32)
+                    (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+                )
+                (func (export "calls") (result i32)
+                    (global.get $calls)
+                )
+            )
+            (core instance $m
+                (instantiate $m
+                    (with "libc" (instance $libc))
+                    (with "host" (instance (export "prefix" (func $prefix))))
+                )
+            )
+            (alias core export $m "post-greet" (core func $post))
+            (func (export "greet") (param "name" string) (result string)
+                (canon lift (core func $m "greet") (memory $mem) (realloc $realloc) (post-return $post))
+            )
+            (func (export "calls") (result u32)
+                (canon lift (core func $m "calls"))
+            )
+        )
+        "#,
+        |linker| {
+            linker
+                .root()
+                .func_new("prefix", |_ctx, _params, results| {
+                    results[0] = Val::String("Hello, ".into());
+                    Ok(())
+                })
+                .unwrap();
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "greet",
+            &[Val::String("Wasmi".into())]
+        ),
+        Some(Val::String("Hello, Wasmi".into()))
+    );
+    assert_eq!(
+        call(&mut store, &instance, "greet", &[Val::String("λ".into())]),
+        Some(Val::String("Hello, λ".into()))
+    );
+    assert_eq!(call(&mut store, &instance, "calls", &[]), Some(Val::U32(2)));
+}
+
+#[test]
+fn records_and_variants() {
+    let (mut store, instance) = instantiate(
+        (),
+        r#"
+        (component
+            (type $point (record (field "x" s32) (field "y" s32)))
+            (type $sign (enum "negative" "zero" "positive"))
+            (type $shape (variant (case "circle" f32) (case "square" u64) (case "none")))
+            (core module $m
+                (memory (export "memory") 1)
+                (func (export "swap") (param i32 i32) (result i32)
+                    (i32.store (i32.const 8) (local.get 1))
+                    (i32.store (i32.const 12) (local.get 0))
+                    (i32.const 8)
+                )
+                (func (export "sign") (param i32) (result i32)
+                    (select
+                        (i32.const 0)
+                        (select (i32.const 2) (i32.const 1) (local.get 0))
+                        (i32.lt_s (local.get 0) (i32.const 0))
+                    )
+                )
+                (func (export "unwrap-or") (param i32 i32 i32) (result i32)
+                    (select (local.get 1) (local.get 2) (local.get 0))
+                )
+                (func (export "area") (param i32 i64) (result f64)
+                    (if (result f64) (i32.eqz (local.get 0))
+                        (then
+                            (f64.mul
+                                (f64.promote_f32 (f32.reinterpret_i32 (i32.wrap_i64 (local.get 1))))
+                                (f64.const 3)
+                            )
+                        )
+                        (else
+                            (if (result f64) (i32.eq (local.get 0) (i32.const 1))
+                                (then
+                                    (f64.convert_i64_u (i64.mul (local.get 1) (local.get 1)))
+                                )
+                                (else (f64.const 0))
+                            )
+                        )
+                    )
+                )
+                (func (export "checked-div") (param i32 i32) (result i32)
+                    (if (i32.eqz (local.get 1))
+                        (then
+                            (i32.store8 (i32.const 16) (i32.const 1))
+                        )
+                        (else
+                            (i32.store8 (i32.const 16) (i32.const 0))
+                            (i32.store (i32.const 20) (i32.div_u (local.get 0) (local.get 1)))
+                        )
+                    )
+                    (i32.const 16)
+                )
+            )
+            (core instance $m (instantiate $m))
+            (alias core export $m "memory" (core memory $mem))
+            (func (export "swap") (param "p" $point) (result $point)
+                (canon lift (core func $m "swap") (memory $mem))
+            )
+            (func (export "sign") (param "x" s32) (result $sign)
+                (canon lift (core func $m "sign"))
+            )
+            (func (export "unwrap-or") (param "x" (option u32)) (param "default" u32) (result u32)
+                (canon lift (core func $m "unwrap-or"))
+            )
+            (func (export "area") (param "s" $shape) (result f64)
+                (canon lift (core func $m "area"))
+            )
+            (func (export "checked-div") (param "a" u32) (param "b" u32) (result (result u32))
+                (canon lift (core func $m "checked-div") (memory $mem))
+            )
+        )
+        "#,
+        |_| {},
+    )
+    .unwrap();
+    let point = |x, y| Val::Record(vec![("x".into(), Val::S32(x)), ("y".into(), Val::S32(y))]);
+    assert_eq!(
+        call(&mut store, &instance, "swap", &[point(1, -2)]),
+        Some(point(-2, 1))
+    );
+    for (x, sign) in [(-7, "negative"), (0, "zero"), (3, "positive")] {
+        assert_eq!(
+            call(&mut store, &instance, "sign", &[Val::S32(x)]),
+            Some(Val::Enum(sign.into()))
+        );
+    }
+    let some = |x| Val::Option(Some(Box::new(Val::U32(x))));
+    assert_eq!(
+        call(&mut store, &instance, "unwrap-or", &[some(5), Val::U32(9)]),
+        Some(Val::U32(5))
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "unwrap-or",
+            &[Val::Option(None), Val::U32(9)]
+        ),
+        Some(Val::U32(9))
+    );
+    let shape = |name: &str, payload: Option<Val>| Val::Variant(name.into(), payload.map(Box::new));
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "area",
+            &[shape("circle", Some(Val::F32(2.0)))]
+        ),
+        Some(Val::F64(6.0))
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "area",
+            &[shape("square", Some(Val::U64(3)))]
+        ),
+        Some(Val::F64(9.0))
+    );
+    assert_eq!(
+        call(&mut store, &instance, "area", &[shape("none", None)]),
+        Some(Val::F64(0.0))
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "checked-div",
+            &[Val::U32(7), Val::U32(2)]
+        ),
+        Some(Val::Result(Ok(Some(Box::new(Val::U32(3))))))
+    );
+    assert_eq!(
+        call(
+            &mut store,
+            &instance,
+            "checked-div",
+            &[Val::U32(7), Val::U32(0)]
+        ),
+        Some(Val::Result(Err(None)))
+    );
+    let func = instance.get_func("sign").unwrap();
+    let error = func
+        .call(&mut store, &[Val::U32(1)], &mut [Val::Bool(false)])
+        .unwrap_err();
+    assert_component_error(error, |error| {
+        matches!(error, ComponentError::TypeMismatch { .. })
+    });
+}
+
+#[test]
+fn host_resources() {
+    let dropped = Arc::new(Mutex::new(Vec::new()));
+    let (mut store, instance) = instantiate(
+        dropped.clone(),
+        r#"
+        (component
+            (import "host" (instance $host
+                (export "counter" (type $counter (sub resource)))
+                (export "make" (func (param "start" u32) (result (own $counter))))
+                (export "get" (func (param "c" (borrow $counter)) (result u32)))
+            ))
+            (alias export $host "counter" (type $counter))
+            (alias export $host "make" (func $make))
+            (alias export $host "get" (func $get))
+            (core func $make (canon lower (func $make)))
+            (core func $get (canon lower (func $get)))
+            (core func $drop (canon resource.drop $counter))
+            (core module $m
+                (import "host" "make" (func $make (param i32) (result i32)))
+                (import "host" "get" (func $get (param i32) (result i32)))
+                (import "host" "drop" (func $drop (param i32)))
+                (func (export "run") (param i32) (result i32)
+                    (local $handle i32) (local $result i32)
+                    (local.set $handle (call $make (local.get 0)))
+                    (local.set $result (call $get (local.get $handle)))
+                    (call $drop (local.get $handle))
+                    (local.get $result)
+                )
+            )
+            (core instance $m
+                (instantiate $m
+                    (with "host" (instance
+                        (export "make" (func $make))
+                        (export "get" (func $get))
+                        (export "drop" (func $drop))
+                    ))
+                )
+            )
+            (func (export "run") (param "start" u32) (result u32)
+                (canon lift (core func $m "run"))
+            )
+        )
+        "#,
+        |linker| {
+            linker
+                .instance("host")
+                .resource("counter", |ctx, rep| {
+                    ctx.data().lock().unwrap().push(rep);
+                    Ok(())
+                })
+                .unwrap()
+                .func_new("make", |_ctx, params, results| {
+                    let Val::U32(start) = params[0] else {
+                        panic!("unexpected parameter: {}", params[0])
+                    };
+                    results[0] = Val::Own(start);
+                    Ok(())
+                })
+                .unwrap()
+                .func_new("get", |_ctx, params, results| {
+                    let Val::Borrow(rep) = params[0] else {
+                        panic!("unexpected parameter: {}", params[0])
+                    };
+                    results[0] = Val::U32(rep + 1);
+                    Ok(())
+                })
+                .unwrap();
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        call(&mut store, &instance, "run", &[Val::U32(41)]),
+        Some(Val::U32(42))
+    );
+    assert_eq!(
+        call(&mut store, &instance, "run", &[Val::U32(10)]),
+        Some(Val::U32(11))
+    );
+    assert_eq!(*dropped.lock().unwrap(), [41, 10]);
+}
+
+/// A component importing `double` from the instance `instance`.
+fn versioned_component(instance: &str) -> String {
+    format!(
+        r#"
+        (component
+            (import "{instance}" (instance $host (export "double" (func (param "x" u32) (result u32)))))
+            (alias export $host "double" (func $double))
+            (core func $double (canon lower (func $double)))
+            (core module $m
+                (import "host" "double" (func $double (param i32) (result i32)))
+                (func (export "run") (param i32) (result i32)
+                    (call $double (local.get 0))
+                )
+            )
+            (core instance $m (instantiate $m (with "host" (instance (export "double" (func $double))))))
+            (func (export "run") (param "x" u32) (result u32)
+                (canon lift (core func $m "run"))
+            )
+        )
+        "#
+    )
+}
+
+/// Defines `double` for the instance `instance`.
+fn define_double(instance: &str) -> impl FnOnce(&mut Linker<()>) + '_ {
+    move |linker| {
+        linker
+            .instance(instance)
+            .func_new("double", |_ctx, params, results| {
+                let Val::U32(x) = params[0] else {
+                    panic!("unexpected parameter: {}", params[0])
+                };
+                results[0] = Val::U32(2 * x);
+                Ok(())
+            })
+            .unwrap();
+    }
+}
+
+#[test]
+fn compatible_versions() {
+    for (import, defined) in [
+        ("test:pkg/host@0.2.1", "test:pkg/host@0.2.0"),
+        ("test:pkg/host@1.3.0", "test:pkg/host@1.0.2"),
+    ] {
+        let (mut store, instance) =
+            instantiate((), &versioned_component(import), define_double(defined)).unwrap();
+        assert_eq!(
+            call(&mut store, &instance, "run", &[Val::U32(21)]),
+            Some(Val::U32(42))
+        );
+    }
+}
+
+#[test]
+fn missing_definition() {
+    for (import, defined) in [
+        ("test:pkg/host@0.3.0", "test:pkg/host@0.2.0"),
+        ("test:pkg/host@2.0.0", "test:pkg/host@1.0.0"),
+        ("test:pkg/host", "test:pkg/other"),
+    ] {
+        let error = instantiate((), &versioned_component(import), define_double(defined))
+            .map(|_| ())
+            .unwrap_err();
+        assert_component_error(
+            error,
+            |error| matches!(error, ComponentError::MissingDefinition { name } if name == &format!("{import}#double")),
+        );
+    }
+}
+
+#[test]
+fn duplicate_definition() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let mut root = linker.root();
+    root.func_new("f", |_, _, _| Ok(())).unwrap();
+    let error = root
+        .func_new("f", |_, _, _| Ok(()))
+        .map(|_| ())
+        .unwrap_err();
+    assert_component_error(
+        error,
+        |error| matches!(error, ComponentError::DuplicateDefinition { name } if name == "f"),
+    );
+}
+
+#[test]
+fn reject_core_modules() {
+    let wasm = wat::parse_str("(module)").unwrap();
+    assert!(!is_component(&wasm));
+    let error = Component::new(&Engine::default(), &wasm).unwrap_err();
+    assert_component_error(error, |error| {
+        matches!(error, ComponentError::Malformed { .. })
+    });
+}
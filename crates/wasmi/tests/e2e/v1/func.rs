@@ -207,7 +207,7 @@ fn static_duplicate_works() {
 
 fn setup_many_params() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(
         &mut store,
         |_0: i32,
@@ -295,7 +295,7 @@ fn static_many_params_works() {
 
 fn setup_many_results() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(&mut store, ascending_tuple);
     (store, func)
 }
@@ -328,7 +328,7 @@ fn static_many_results_works() {
 
 fn setup_many_params_many_results() -> (Store<()>, Func) {
     let mut store = test_setup();
-    // Function taking 16 arguments (maximum) and doing nothing.
+    // Function taking 16 arguments and doing nothing.
     let func = Func::wrap(
         &mut store,
         |v0: i32,
@@ -379,7 +379,7 @@ fn static_many_params_many_results_works() {
 #[test]
 fn dynamic_many_types_works() {
     let mut store = test_setup();
-    // Function taking no arguments and returning 16 results as tuple (maximum).
+    // Function taking no arguments and returning 16 results as tuple.
     let func = Func::wrap(
         &mut store,
         |v0: i32, v1: u32, v2: i64, v3: u64, v4: F32, v5: F64| (v0, v1, v2, v3, v4, v5),
@@ -405,7 +405,7 @@ fn dynamic_many_types_works() {
 #[test]
 fn static_many_types_works() {
     let mut store = test_setup();
-    // Function taking no arguments and returning 16 results as tuple (maximum).
+    // Function taking no arguments and returning 16 results as tuple.
     let func = Func::wrap(
         &mut store,
         |v0: i32, v1: u32, v2: i64, v3: u64, v4: F32, v5: F64| (v0, v1, v2, v3, v4, v5),
@@ -531,3 +531,221 @@ fn static_type_check_works() {
         ErrorKind::Func(FuncError::MismatchingResultType)
     );
 }
+
+#[test]
+fn static_64_params_and_results_works() {
+    let mut store = test_setup();
+    // Function taking 64 arguments (maximum) and returning them in reverse order.
+    let func = Func::wrap(
+        &mut store,
+        |v0: i64,
+         v1: i64,
+         v2: i64,
+         v3: i64,
+         v4: i64,
+         v5: i64,
+         v6: i64,
+         v7: i64,
+         v8: i64,
+         v9: i64,
+         v10: i64,
+         v11: i64,
+         v12: i64,
+         v13: i64,
+         v14: i64,
+         v15: i64,
+         v16: i64,
+         v17: i64,
+         v18: i64,
+         v19: i64,
+         v20: i64,
+         v21: i64,
+         v22: i64,
+         v23: i64,
+         v24: i64,
+         v25: i64,
+         v26: i64,
+         v27: i64,
+         v28: i64,
+         v29: i64,
+         v30: i64,
+         v31: i64,
+         v32: i64,
+         v33: i64,
+         v34: i64,
+         v35: i64,
+         v36: i64,
+         v37: i64,
+         v38: i64,
+         v39: i64,
+         v40: i64,
+         v41: i64,
+         v42: i64,
+         v43: i64,
+         v44: i64,
+         v45: i64,
+         v46: i64,
+         v47: i64,
+         v48: i64,
+         v49: i64,
+         v50: i64,
+         v51: i64,
+         v52: i64,
+         v53: i64,
+         v54: i64,
+         v55: i64,
+         v56: i64,
+         v57: i64,
+         v58: i64,
+         v59: i64,
+         v60: i64,
+         v61: i64,
+         v62: i64,
+         v63: i64| {
+            (
+                v63, v62, v61, v60, v59, v58, v57, v56, v55, v54, v53, v52, v51, v50, v49, v48,
+                v47, v46, v45, v44, v43, v42, v41, v40, v39, v38, v37, v36, v35, v34, v33, v32,
+                v31, v30, v29, v28, v27, v26, v25, v24, v23, v22, v21, v20, v19, v18, v17, v16,
+                v15, v14, v13, v12, v11, v10, v9, v8, v7, v6, v5, v4, v3, v2, v1, v0,
+            )
+        },
+    );
+    assert_eq!(func.ty(&store).params().len(), 64);
+    assert_eq!(func.ty(&store).results().len(), 64);
+    let inputs: [i64; 64] = core::array::from_fn(|i| i as i64);
+    let mut results = [0; 64].map(Value::I64);
+    func.call(&mut store, &inputs.map(Value::I64), &mut results)
+        .unwrap();
+    let mut expected = inputs;
+    expected.reverse();
+    assert_eq!(results.map(|result| result.i64().unwrap()), expected);
+    type I64x64 = (
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+    );
+    let typed_func = func.typed::<I64x64, I64x64>(&store).unwrap();
+    let result = typed_func
+        .call(
+            &mut store,
+            (
+                0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+                23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43,
+                44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63,
+            ),
+        )
+        .unwrap();
+    assert_eq!(result.0, 63);
+    assert_eq!(result.31, 32);
+    assert_eq!(result.63, 0);
+}
+
+/// Asserts that a [`Func::new`] host function with `len_params` parameters can call itself.
+///
+/// Only the first parameter is used; the remaining ones are always zero.
+fn assert_dynamic_reentrant_call(len_params: usize) {
+    let engine = Engine::default();
+    let mut store = <Store<Option<Func>>>::new(&engine, None);
+    let params = vec![ValueType::I32; len_params];
+    let args = move |n: i32| {
+        let mut args = vec![Value::I32(0); len_params];
+        args[0] = Value::I32(n);
+        args
+    };
+    // Computes `n + (n - 1) + ... + 0` by recursively calling itself through the host side.
+    let sum = Func::new(
+        &mut store,
+        FuncType::new(params, [ValueType::I32]),
+        move |mut caller, inputs: &[Value], results: &mut [Value]| {
+            let n = inputs[0].i32().unwrap();
+            assert_eq!(results[0].i32(), Some(0));
+            if n == 0 {
+                return Ok(());
+            }
+            let sum = caller.data().unwrap();
+            let mut result = [Value::I32(0)];
+            sum.call(&mut caller, &args(n - 1), &mut result)?;
+            // The parameters must be unaffected by the re-entrant call.
+            assert_eq!(inputs[0].i32(), Some(n));
+            results[0] = Value::I32(n + result[0].i32().unwrap());
+            Ok(())
+        },
+    );
+    *store.data_mut() = Some(sum);
+    for _ in 0..3 {
+        let mut result = [Value::I32(0)];
+        sum.call(&mut store, &args(10), &mut result).unwrap();
+        assert_eq!(result[0].i32(), Some(55));
+    }
+}
+
+#[test]
+fn dynamic_reentrant_call_works() {
+    assert_dynamic_reentrant_call(1);
+}
+
+#[test]
+fn dynamic_reentrant_call_many_params_works() {
+    assert_dynamic_reentrant_call(100);
+}
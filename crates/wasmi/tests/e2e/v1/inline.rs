@@ -0,0 +1,98 @@
+//! Tests to check that inlining of small leaf functions does not alter behavior.
+
+use wasmi::{Config, Engine, Linker, Module, Store, TypedFunc};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// The test module exporting a function as `"test"`.
+///
+/// # Note
+///
+/// The `test` function calls a variety of small leaf functions that
+/// are inlined for a non-zero inline budget. It traps for `n == 0`.
+fn test_module() -> &'static str {
+    r#"
+    (module
+        (memory 1)
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func $const (result i32)
+            (i32.const 42)
+        )
+        (func $uninit (param i32) (result i32)
+            (local i32)
+            (local.set 1 (i32.add (local.get 1) (local.get 0)))
+            (local.get 1)
+        )
+        (func $select (param i32 i32) (result i32)
+            (select (local.get 0) (i32.const 7) (local.get 1))
+        )
+        (func $div (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func $store (param i32 i32)
+            (i32.store (local.get 0) (local.get 1))
+        )
+        (func $load (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func (export "test") (param $n i32) (result i32)
+            (local $i i32)
+            (local $acc i32)
+            (local.set $i (local.get $n))
+            (loop $continue
+                (local.set $acc (call $add (local.get $acc) (call $const)))
+                (local.set $acc (call $add (local.get $acc) (call $uninit (local.get $i))))
+                (local.set $acc (call $add (local.get $acc) (call $select (local.get $i) (i32.and (local.get $i) (i32.const 1)))))
+                (call $store (i32.const 8) (local.get $acc))
+                (local.set $acc (call $div (call $load (i32.const 8)) (local.get $n)))
+                (br_if $continue
+                    (local.tee $i (i32.sub (local.get $i) (i32.const 1)))
+                )
+            )
+            (local.get $acc)
+        )
+    )"#
+}
+
+/// Runs `test(n)` with the given `inline_budget` and returns its result and the consumed fuel.
+fn run(inline_budget: u32, n: i32) -> (Option<i32>, u64) {
+    let mut config = Config::default();
+    config.consume_fuel(true).inline_budget(inline_budget);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(1_000_000).unwrap();
+    let module = Module::new(&engine, &wat2wasm(test_module())[..]).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func: TypedFunc<i32, i32> = instance.get_typed_func(&store, "test").unwrap();
+    let result = func.call(&mut store, n).ok();
+    (result, store.fuel_consumed().unwrap())
+}
+
+#[test]
+fn inlining_preserves_results_and_fuel() {
+    for n in [1, 2, 3, 10, 100] {
+        let expected = run(0, n);
+        assert!(expected.0.is_some());
+        for budget in [1, 2, 5, 100] {
+            assert_eq!(run(budget, n), expected);
+        }
+    }
+}
+
+#[test]
+fn inlining_preserves_traps_and_fuel() {
+    let expected = run(0, 0);
+    assert!(expected.0.is_none());
+    for budget in [1, 2, 5, 100] {
+        assert_eq!(run(budget, 0), expected);
+    }
+}
@@ -0,0 +1,181 @@
+//! Tests for instantiation from `InstanceSnapshot`s in Wasmi.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::Pages,
+    errors::{ErrorKind, InstantiationError},
+    Config,
+    Engine,
+    Instance,
+    InstanceSnapshot,
+    Linker,
+    MemoryBackend,
+    Module,
+    Store,
+};
+
+/// Compiles the `wat` source into a [`Module`] for the `engine`.
+fn compile(engine: &Engine, wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+const WAT: &str = r#"
+    (module
+        (memory (export "memory") 1 4)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (table $table 2 funcref)
+        (elem (i32.const 0) $get_counter)
+        (data (i32.const 0) "\01\02\03")
+        (func $get_counter (result i32)
+            (global.get $counter)
+        )
+        (func $inc (export "inc") (result i32)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (global.get $counter)
+        )
+        (func $call_table (export "call_table") (param i32) (result i32)
+            (call_indirect (result i32) (local.get 0))
+        )
+        (func $start
+            (drop (memory.grow (i32.const 1)))
+            (i32.store8 (i32.const 100) (i32.const 42))
+            (table.set $table (i32.const 1) (ref.func $inc))
+            (drop (call $inc))
+        )
+        (start $start)
+    )
+"#;
+
+/// Instantiates the module of [`WAT`] and returns its [`InstanceSnapshot`].
+///
+/// Before taking the snapshot the exported `inc` function is called once.
+fn setup_snapshot(engine: &Engine, module: &Module) -> InstanceSnapshot {
+    let mut store = <Store<()>>::new(engine, ());
+    let linker = <Linker<()>>::new(engine);
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let inc = instance.get_typed_func::<(), i32>(&store, "inc").unwrap();
+    assert_eq!(inc.call(&mut store, ()).unwrap(), 2);
+    InstanceSnapshot::new(&store, module, &instance).unwrap()
+}
+
+/// Instantiates `module` from `snapshot` in a new [`Store`].
+fn instantiate_snapshot(
+    engine: &Engine,
+    module: &Module,
+    snapshot: &InstanceSnapshot,
+) -> (Store<()>, Instance) {
+    let mut store = <Store<()>>::new(engine, ());
+    let linker = <Linker<()>>::new(engine);
+    let instance = linker
+        .instantiate_snapshot(&mut store, module, snapshot)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn snapshot_restores_memory() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let (store, instance) = instantiate_snapshot(&engine, &module, &snapshot);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 2);
+    let data = memory.data(&store);
+    assert_eq!(&data[..4], &[1, 2, 3, 0]);
+    assert_eq!(data[100], 42);
+    assert!(data[101..].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn snapshot_restores_globals_and_skips_start() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let (mut store, instance) = instantiate_snapshot(&engine, &module, &snapshot);
+    let counter = instance.get_global(&store, "counter").unwrap();
+    assert_eq!(counter.get(&store).i32(), Some(2));
+    let inc = instance.get_typed_func::<(), i32>(&store, "inc").unwrap();
+    assert_eq!(inc.call(&mut store, ()).unwrap(), 3);
+}
+
+#[test]
+fn snapshot_restores_tables() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let (mut store, instance) = instantiate_snapshot(&engine, &module, &snapshot);
+    let call_table = instance
+        .get_typed_func::<i32, i32>(&store, "call_table")
+        .unwrap();
+    // Both table entries must refer to the functions of the new instance.
+    assert_eq!(call_table.call(&mut store, 0).unwrap(), 2);
+    assert_eq!(call_table.call(&mut store, 1).unwrap(), 3);
+    assert_eq!(call_table.call(&mut store, 0).unwrap(), 3);
+}
+
+#[test]
+fn snapshot_instances_are_independent() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let (mut store_a, instance_a) = instantiate_snapshot(&engine, &module, &snapshot);
+    let (mut store_b, instance_b) = instantiate_snapshot(&engine, &module, &snapshot);
+    let inc_a = instance_a
+        .get_typed_func::<(), i32>(&store_a, "inc")
+        .unwrap();
+    let inc_b = instance_b
+        .get_typed_func::<(), i32>(&store_b, "inc")
+        .unwrap();
+    assert_eq!(inc_a.call(&mut store_a, ()).unwrap(), 3);
+    assert_eq!(inc_a.call(&mut store_a, ()).unwrap(), 4);
+    assert_eq!(inc_b.call(&mut store_b, ()).unwrap(), 3);
+}
+
+#[test]
+fn snapshot_virtual_memories_are_independent() {
+    let mut config = Config::default();
+    config.memory_backend(MemoryBackend::VirtualMemory);
+    let engine = Engine::new(&config);
+    let module = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let (mut store_a, instance_a) = instantiate_snapshot(&engine, &module, &snapshot);
+    let (store_b, instance_b) = instantiate_snapshot(&engine, &module, &snapshot);
+    let memory_a = instance_a.get_memory(&store_a, "memory").unwrap();
+    let memory_b = instance_b.get_memory(&store_b, "memory").unwrap();
+    memory_a.data_mut(&mut store_a)[0] = 0xFF;
+    memory_a.data_mut(&mut store_a)[100] = 0xFF;
+    memory_a.grow(&mut store_a, Pages::new(1).unwrap()).unwrap();
+    let data_b = memory_b.data(&store_b);
+    assert_eq!(&data_b[..4], &[1, 2, 3, 0]);
+    assert_eq!(data_b[100], 42);
+    // Instances restored after the modifications still observe the snapshotted bytes.
+    let (store_c, instance_c) = instantiate_snapshot(&engine, &module, &snapshot);
+    let memory_c = instance_c.get_memory(&store_c, "memory").unwrap();
+    assert_eq!(u32::from(memory_c.current_pages(&store_c)), 2);
+    let data_c = memory_c.data(&store_c);
+    assert_eq!(&data_c[..4], &[1, 2, 3, 0]);
+    assert_eq!(data_c[100], 42);
+    assert!(data_c[101..].iter().all(|byte| *byte == 0));
+}
+
+#[test]
+fn snapshot_module_mismatch() {
+    let engine = Engine::default();
+    let module = compile(&engine, WAT);
+    let other = compile(&engine, WAT);
+    let snapshot = setup_snapshot(&engine, &module);
+    let mut store = <Store<()>>::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let error = linker
+        .instantiate_snapshot(&mut store, &other, &snapshot)
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::SnapshotModuleMismatch)
+    );
+}
@@ -0,0 +1,164 @@
+//! Tests for host provided linear memory via `MemoryCreator` and static buffers.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use wasmi::{
+    core::Pages,
+    errors::MemoryError,
+    Config,
+    Engine,
+    LinearMemory,
+    Linker,
+    Memory,
+    MemoryCreator,
+    MemoryType,
+    Module,
+    Store,
+};
+
+/// A [`LinearMemory`] backed by a `Vec`.
+struct VecMemory {
+    bytes: Vec<u8>,
+}
+
+impl LinearMemory for VecMemory {
+    fn byte_size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn grow_to(&mut self, new_size: usize) -> Result<(), MemoryError> {
+        self.bytes.resize(new_size, 0x00);
+        Ok(())
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.bytes[..]
+    }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[..]
+    }
+}
+
+/// A [`MemoryCreator`] counting the number of created [`VecMemory`] instances.
+#[derive(Default)]
+struct CountingCreator {
+    created: AtomicUsize,
+}
+
+impl MemoryCreator for CountingCreator {
+    fn new_memory(
+        &self,
+        _ty: MemoryType,
+        minimum: usize,
+        _maximum: Option<usize>,
+    ) -> Result<Box<dyn LinearMemory>, MemoryError> {
+        self.created.fetch_add(1, Ordering::Relaxed);
+        Ok(Box::new(VecMemory {
+            bytes: vec![0x00; minimum],
+        }))
+    }
+}
+
+#[test]
+fn memory_creator_is_used_for_module_memories() {
+    let creator = Arc::new(CountingCreator::default());
+    let engine = Engine::with_memory_creator(&Config::default(), creator.clone());
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (memory (export "memory") 1 2)
+            (func (export "grow_and_store") (result i32)
+                (drop (memory.grow (i32.const 1)))
+                (i32.store (i32.const 65536) (i32.const 42))
+                (i32.load (i32.const 65536))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let linker = <Linker<()>>::new(&engine);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(creator.created.load(Ordering::Relaxed), 1);
+    let func = instance
+        .get_typed_func::<(), i32>(&store, "grow_and_store")
+        .unwrap();
+    assert_eq!(func.call(&mut store, ()).unwrap(), 42);
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(memory.data(&store).len(), 2 * 65536);
+}
+
+#[test]
+fn memory_creator_invalid_initial_size() {
+    struct EmptyCreator;
+    impl MemoryCreator for EmptyCreator {
+        fn new_memory(
+            &self,
+            _ty: MemoryType,
+            _minimum: usize,
+            _maximum: Option<usize>,
+        ) -> Result<Box<dyn LinearMemory>, MemoryError> {
+            Ok(Box::new(VecMemory { bytes: Vec::new() }))
+        }
+    }
+    let engine = Engine::with_memory_creator(&Config::default(), Arc::new(EmptyCreator));
+    let mut store = <Store<()>>::new(&engine, ());
+    let ty = MemoryType::new(1, None).unwrap();
+    assert!(Memory::new(&mut store, ty).is_err());
+}
+
+#[test]
+fn memory_creator_uninitialized_memory() {
+    struct DirtyCreator;
+    impl MemoryCreator for DirtyCreator {
+        fn new_memory(
+            &self,
+            _ty: MemoryType,
+            minimum: usize,
+            _maximum: Option<usize>,
+        ) -> Result<Box<dyn LinearMemory>, MemoryError> {
+            Ok(Box::new(VecMemory {
+                bytes: vec![0xFF; minimum],
+            }))
+        }
+    }
+    let engine = Engine::with_memory_creator(&Config::default(), Arc::new(DirtyCreator));
+    let mut store = <Store<()>>::new(&engine, ());
+    let ty = MemoryType::new(1, None).unwrap();
+    assert!(matches!(
+        Memory::new(&mut store, ty),
+        Err(MemoryError::UninitializedMemory)
+    ));
+}
+
+#[test]
+fn static_memory_works() {
+    let buffer: &'static mut [u8] = Box::leak(vec![0xFF_u8; 2 * 65536].into_boxed_slice());
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let memory = Memory::new_static(&mut store, MemoryType::new(1, None).unwrap(), buffer).unwrap();
+    assert_eq!(memory.data(&store).len(), 65536);
+    assert!(memory.data(&store).iter().all(|byte| *byte == 0x00));
+    memory.grow(&mut store, Pages::new(1).unwrap()).unwrap();
+    assert_eq!(memory.data(&store).len(), 2 * 65536);
+    assert!(memory.data(&store).iter().all(|byte| *byte == 0x00));
+    // The static buffer cannot grow beyond its length.
+    assert!(memory.grow(&mut store, Pages::new(1).unwrap()).is_err());
+}
+
+#[test]
+fn static_memory_too_small() {
+    let buffer: &'static mut [u8] = Box::leak(vec![0x00_u8; 100].into_boxed_slice());
+    let engine = Engine::default();
+    let mut store = <Store<()>>::new(&engine, ());
+    let ty = MemoryType::new(1, None).unwrap();
+    assert!(Memory::new_static(&mut store, ty, buffer).is_err());
+}
@@ -0,0 +1,68 @@
+//! Tests for the typed linear memory views used by host functions.
+
+use wasmi::{errors::MemoryError, Caller, Engine, Error, Extern, Func, Linker, Module, Store};
+
+#[test]
+fn host_reads_and_writes_through_caller_views() {
+    let engine = Engine::default();
+    let mut store = <Store<Vec<String>>>::new(&engine, Vec::new());
+    let mut linker = <Linker<Vec<String>>>::new(&engine);
+    // Writes the sum of the `len` 32-bit integers at `ptr` to `ptr + 4 * len`
+    // and records the zero terminated string at `name`.
+    let sum = Func::wrap(
+        &mut store,
+        |mut caller: Caller<Vec<String>>, name: u32, ptr: u32, len: u32| -> Result<(), Error> {
+            let memory = caller
+                .get_export("memory")
+                .and_then(Extern::into_memory)
+                .unwrap();
+            let name = memory.read_c_str(&caller, name as usize, 16)?.to_owned();
+            caller.data_mut().push(name);
+            let (ptr, len) = (ptr as usize, len as usize);
+            let sum = caller
+                .view::<i32>(&memory, ptr, len)?
+                .iter()
+                .fold(0_i32, i32::wrapping_add);
+            caller.view_mut(&memory, ptr + 4 * len, 1)?.set(0, sum)?;
+            Ok(())
+        },
+    );
+    linker.define("env", "sum", sum).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "sum" (func $sum (param i32 i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "sum\00")
+            (data (i32.const 16) "\01\00\00\00\02\00\00\00\fd\ff\ff\ff\0a\00\00\00")
+            (func (export "run") (param i32 i32) (result i32)
+                (call $sum (i32.const 0) (local.get 0) (local.get 1))
+                (i32.load
+                    (i32.add
+                        (local.get 0)
+                        (i32.mul (local.get 1) (i32.const 4))
+                    )
+                )
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance
+        .get_typed_func::<(u32, u32), i32>(&store, "run")
+        .unwrap();
+    assert_eq!(run.call(&mut store, (16, 4)).unwrap(), 10);
+    assert_eq!(run.call(&mut store, (20, 2)).unwrap(), -1);
+    assert_eq!(store.data(), &["sum", "sum"]);
+    let error = run.call(&mut store, (65532, 1)).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        wasmi::errors::ErrorKind::Memory(MemoryError::OutOfBoundsAccess)
+    ));
+}
@@ -1,6 +1,16 @@
+mod caller;
+#[cfg(feature = "component-model")]
+mod component;
 mod fuel_consumption;
 mod fuel_metering;
 mod func;
 mod host_calls_wasm;
+mod inline;
+mod instance_snapshot;
+mod memory_creator;
+mod memory_view;
 mod resource_limiter;
 mod resumable_call;
+mod spilled_locals;
+mod superinstr;
+mod threads;
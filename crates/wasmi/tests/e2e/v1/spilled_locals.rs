@@ -0,0 +1,83 @@
+//! Tests for functions with more local variables than fit into the register space.
+
+use wasmi::{Config, Engine, Linker, Module, Store, TypedFunc};
+
+/// The test module with functions defining `len_locals` local variables.
+///
+/// # Note
+///
+/// - `sum(n)` recursively computes the sum of `0..=n` keeping intermediate
+///   values in spilled local variables of its call frame.
+/// - `tee(n)` stores `n` and a constant in spilled local variables via `local.tee`.
+fn test_module(len_locals: usize) -> String {
+    assert!(len_locals >= 40_000);
+    let locals = "i64 ".repeat(len_locals);
+    format!(
+        r#"
+        (module
+            (func $sum (export "sum") (param $n i64) (result i64)
+                (local {locals})
+                (local.set 39999 (local.get $n))
+                (local.set 30000 (i64.const 0))
+                (if (i64.ne (local.get $n) (i64.const 0))
+                    (then
+                        (local.set 30000 (call $sum (i64.sub (local.get $n) (i64.const 1))))
+                    )
+                )
+                (i64.add (local.get 30000) (local.get 39999))
+            )
+            (func (export "tee") (param $n i64) (result i64)
+                (local {locals})
+                (i64.add
+                    (local.tee 20000 (local.get $n))
+                    (local.tee 20001 (i64.const 0x1_0000_0000))
+                )
+                (i64.add (local.get 20000))
+                (i64.add (local.get 20001))
+            )
+        )"#
+    )
+}
+
+/// Runs the exported function `name` of the [`test_module`] with `input` and returns its result and the consumed fuel.
+fn run(name: &str, input: i64) -> (i64, u64) {
+    run_with(40_000, name, input)
+}
+
+/// Runs [`run`] for a [`test_module`] with `len_locals` local variables.
+fn run_with(len_locals: usize, name: &str, input: i64) -> (i64, u64) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(u64::MAX).unwrap();
+    let wasm = wat::parse_str(test_module(len_locals)).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func: TypedFunc<i64, i64> = instance.get_typed_func(&store, name).unwrap();
+    let result = func.call(&mut store, input).unwrap();
+    (result, store.fuel_consumed().unwrap())
+}
+
+#[test]
+fn spilled_locals_are_per_call_frame() {
+    // Note: The default value stack limit allows for only a few of these huge call frames.
+    for n in [0, 1, 2] {
+        assert_eq!(run("sum", n).0, n * (n + 1) / 2);
+    }
+}
+
+#[test]
+fn spilled_locals_tee() {
+    assert_eq!(run("tee", 5).0, 2 * (5 + 0x1_0000_0000));
+}
+
+#[test]
+fn spilled_locals_charge_fuel() {
+    // Note: Call frames are charged for spilled local variables like for registers.
+    assert!(run_with(49_000, "sum", 0).1 > run_with(40_000, "sum", 0).1);
+}
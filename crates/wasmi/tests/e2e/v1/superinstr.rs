@@ -0,0 +1,92 @@
+//! Tests to check that fused superinstructions behave like their unfused instruction pairs.
+
+use wasmi::{core::TrapCode, Engine, Instance, Linker, Module, Store, TypedFunc, WasmParams};
+
+/// Converts the `wat` string source into `wasm` encoded byte.
+fn wat2wasm(wat: &str) -> Vec<u8> {
+    wat::parse_str(wat).unwrap()
+}
+
+/// The test module exporting functions that are translated into superinstructions.
+fn test_module() -> &'static str {
+    r#"
+    (module
+        (memory 1)
+        (data (i32.const 4) "\ff\ff\ff\7f")
+        (func (export "load_add") (param i32 i32) (result i32)
+            (i32.add (i32.load offset=4 (local.get 0)) (local.get 1))
+        )
+        (func (export "load_add_imm") (param i32) (result i32)
+            (i32.add (i32.load offset=4 (local.get 0)) (i32.const -5))
+        )
+        (func (export "store_add_imm") (param i32 i32) (result i32)
+            (i32.store offset=8 (local.get 0) (i32.add (local.get 1) (i32.const 3)))
+            (i32.load offset=8 (local.get 0))
+        )
+        (func (export "swap_sub") (param i32 i32) (result i32)
+            (local i32 i32)
+            (local.set 2 (local.get 1))
+            (local.set 3 (local.get 0))
+            (i32.sub (local.get 2) (local.get 3))
+        )
+    )"#
+}
+
+/// Instantiates the [`test_module`].
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wat2wasm(test_module())[..]).unwrap();
+    let instance = Linker::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of the [`test_module`] with `params`.
+fn call<Params: WasmParams>(name: &str, params: Params) -> Result<i32, wasmi::Error> {
+    let (mut store, instance) = setup();
+    let func: TypedFunc<Params, i32> = instance.get_typed_func(&store, name).unwrap();
+    func.call(&mut store, params)
+}
+
+/// Asserts that `result` is a memory out of bounds trap.
+fn assert_out_of_bounds(result: Result<i32, wasmi::Error>) {
+    assert_eq!(
+        result.unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+}
+
+#[test]
+fn load_add() {
+    assert_eq!(call("load_add", (0, 1)).unwrap(), i32::MIN);
+    assert_eq!(call("load_add", (0, -1)).unwrap(), i32::MAX - 1);
+    assert_eq!(call("load_add", (4, 10)).unwrap(), 10);
+    assert_out_of_bounds(call("load_add", (65532, 1)));
+}
+
+#[test]
+fn load_add_imm() {
+    assert_eq!(call("load_add_imm", 0).unwrap(), i32::MAX - 5);
+    assert_eq!(call("load_add_imm", 4).unwrap(), -5);
+    assert_out_of_bounds(call("load_add_imm", 65532));
+}
+
+#[test]
+fn store_add_imm() {
+    assert_eq!(call("store_add_imm", (0, 39)).unwrap(), 42);
+    assert_eq!(
+        call("store_add_imm", (100, i32::MAX)).unwrap(),
+        i32::MIN + 2
+    );
+    assert_out_of_bounds(call("store_add_imm", (65530, 0)));
+}
+
+#[test]
+fn copy_chain() {
+    assert_eq!(call("swap_sub", (10, 3)).unwrap(), -7);
+    assert_eq!(call("swap_sub", (3, 10)).unwrap(), 7);
+}
@@ -0,0 +1,275 @@
+//! Tests for the Wasm `threads` proposal: shared linear memories and atomic operations.
+
+use std::{sync::Arc, thread, time::Duration};
+use wasmi::{
+    core::{Pages, TrapCode},
+    errors::MemoryError,
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    SharedMemory,
+    Store,
+};
+
+/// Creates an [`Engine`] with the Wasm `threads` proposal enabled.
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.wasm_threads(true);
+    Engine::new(&config)
+}
+
+/// Atomic operations on the linear memory `mem`.
+const ATOMICS: &str = r#"
+    (func (export "add") (param i32 i32) (result i32)
+        (i32.atomic.rmw.add (local.get 0) (local.get 1))
+    )
+    (func (export "load") (param i32) (result i32)
+        (i32.atomic.load (local.get 0))
+    )
+    (func (export "cmpxchg") (param i32 i32 i32) (result i32)
+        (i32.atomic.rmw.cmpxchg (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "store16") (param i32 i64)
+        (i64.atomic.store16 (local.get 0) (local.get 1))
+    )
+    (func (export "load8") (param i32) (result i64)
+        (i64.atomic.load8_u (local.get 0))
+    )
+    (func (export "wait") (param i32 i32 i64) (result i32)
+        (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "notify") (param i32 i32) (result i32)
+        (memory.atomic.notify (local.get 0) (local.get 1))
+    )
+"#;
+
+/// Returns a Wasm module with [`ATOMICS`] that defines its own linear memory `mem`.
+fn module_with_memory(memory: &str) -> Vec<u8> {
+    let wat = format!("(module (memory (export \"mem\") {memory}) {ATOMICS})");
+    wat::parse_str(wat).unwrap()
+}
+
+/// Returns a Wasm module with [`ATOMICS`] that imports the shared linear memory `env.mem`.
+fn module_importing_memory() -> Vec<u8> {
+    let wat = format!("(module (import \"env\" \"mem\" (memory 1 2 shared)) {ATOMICS})");
+    wat::parse_str(wat).unwrap()
+}
+
+/// Instantiates `module` in a new [`Store`] that defines `shared` as `env.mem`.
+fn instantiate_shared(
+    engine: &Engine,
+    module: &Module,
+    shared: &SharedMemory,
+) -> (Store<()>, Instance) {
+    let mut store = <Store<()>>::new(engine, ());
+    let memory = Memory::new_shared(&mut store, shared);
+    let mut linker = <Linker<()>>::new(engine);
+    linker.define("env", "mem", memory).unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn threads_disabled_by_default() {
+    let wasm = module_with_memory("1 2 shared");
+    assert!(Module::new(&Engine::default(), &wasm[..]).is_err());
+}
+
+#[test]
+fn atomic_operations_work() {
+    let engine = engine();
+    let wasm = module_with_memory("1 2 shared");
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    assert!(memory.ty(&store).is_shared());
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    let load = instance.get_typed_func::<i32, i32>(&store, "load").unwrap();
+    let cmpxchg = instance
+        .get_typed_func::<(i32, i32, i32), i32>(&store, "cmpxchg")
+        .unwrap();
+    let store16 = instance
+        .get_typed_func::<(i32, i64), ()>(&store, "store16")
+        .unwrap();
+    let load8 = instance
+        .get_typed_func::<i32, i64>(&store, "load8")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (8, 5)).unwrap(), 0);
+    assert_eq!(add.call(&mut store, (8, 5)).unwrap(), 5);
+    assert_eq!(load.call(&mut store, 8).unwrap(), 10);
+    assert_eq!(cmpxchg.call(&mut store, (8, 1, 42)).unwrap(), 10);
+    assert_eq!(cmpxchg.call(&mut store, (8, 10, 42)).unwrap(), 10);
+    assert_eq!(load.call(&mut store, 8).unwrap(), 42);
+    store16.call(&mut store, (16, 0x1_2345)).unwrap();
+    assert_eq!(load8.call(&mut store, 16).unwrap(), 0x45);
+    assert_eq!(load8.call(&mut store, 17).unwrap(), 0x23);
+    assert_eq!(load8.call(&mut store, 18).unwrap(), 0x00);
+    assert_eq!(
+        load.call(&mut store, 9).unwrap_err().as_trap_code(),
+        Some(TrapCode::UnalignedAtomic)
+    );
+    assert_eq!(
+        load.call(&mut store, 65536).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+}
+
+#[test]
+fn wait_on_unshared_memory_traps() {
+    let engine = engine();
+    let wasm = module_with_memory("1 2");
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = <Store<()>>::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wait = instance
+        .get_typed_func::<(i32, i32, i64), i32>(&store, "wait")
+        .unwrap();
+    let notify = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "notify")
+        .unwrap();
+    assert_eq!(
+        wait.call(&mut store, (0, 0, 0)).unwrap_err().as_trap_code(),
+        Some(TrapCode::AtomicWaitNonSharedMemory)
+    );
+    assert_eq!(notify.call(&mut store, (0, 1)).unwrap(), 0);
+}
+
+#[test]
+fn wait_mismatch_and_timeout() {
+    let engine = engine();
+    let module = Module::new(&engine, &module_importing_memory()[..]).unwrap();
+    let shared = SharedMemory::new(MemoryType::new_shared(1, 2).unwrap()).unwrap();
+    let (mut store, instance) = instantiate_shared(&engine, &module, &shared);
+    let wait = instance
+        .get_typed_func::<(i32, i32, i64), i32>(&store, "wait")
+        .unwrap();
+    assert_eq!(wait.call(&mut store, (0, 1, -1)).unwrap(), 1);
+    assert_eq!(wait.call(&mut store, (0, 0, 1_000_000)).unwrap(), 2);
+}
+
+#[test]
+fn shared_memory_across_threads() {
+    let engine = engine();
+    let module = Arc::new(Module::new(&engine, &module_importing_memory()[..]).unwrap());
+    let shared = SharedMemory::new(MemoryType::new_shared(1, 2).unwrap()).unwrap();
+    let waiter = thread::spawn({
+        let (engine, module, shared) = (engine.clone(), module.clone(), shared.clone());
+        move || {
+            let (mut store, instance) = instantiate_shared(&engine, &module, &shared);
+            let wait = instance
+                .get_typed_func::<(i32, i32, i64), i32>(&store, "wait")
+                .unwrap();
+            wait.call(&mut store, (0, 0, -1)).unwrap()
+        }
+    });
+    let adders = (0..4)
+        .map(|_| {
+            let (engine, module, shared) = (engine.clone(), module.clone(), shared.clone());
+            thread::spawn(move || {
+                let (mut store, instance) = instantiate_shared(&engine, &module, &shared);
+                let add = instance
+                    .get_typed_func::<(i32, i32), i32>(&store, "add")
+                    .unwrap();
+                for _ in 0..1000 {
+                    add.call(&mut store, (8, 1)).unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    let (mut store, instance) = instantiate_shared(&engine, &module, &shared);
+    let notify = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "notify")
+        .unwrap();
+    let load = instance.get_typed_func::<i32, i32>(&store, "load").unwrap();
+    // Note: The waiter might not be parked yet so we retry notifying it.
+    while notify.call(&mut store, (0, 1)).unwrap() == 0 {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(waiter.join().unwrap(), 0);
+    for adder in adders {
+        adder.join().unwrap();
+    }
+    assert_eq!(load.call(&mut store, 8).unwrap(), 4000);
+}
+
+#[test]
+fn plain_accesses_to_shared_memory_work() {
+    let engine = engine();
+    let wat = r#"
+        (module
+            (import "env" "mem" (memory 1 2 shared))
+            (func (export "store") (param i32 i64)
+                (i64.store16 offset=1 (local.get 0) (local.get 1))
+            )
+            (func (export "load") (param i32) (result i64)
+                (i64.load offset=1 (local.get 0))
+            )
+            (func (export "fill") (param i32 i32 i32)
+                (memory.fill (local.get 0) (local.get 1) (local.get 2))
+            )
+            (func (export "copy") (param i32 i32 i32)
+                (memory.copy (local.get 0) (local.get 1) (local.get 2))
+            )
+        )
+    "#;
+    let module = Module::new(&engine, &wat::parse_str(wat).unwrap()[..]).unwrap();
+    let shared = SharedMemory::new(MemoryType::new_shared(1, 2).unwrap()).unwrap();
+    let (mut store, instance) = instantiate_shared(&engine, &module, &shared);
+    let store16 = instance
+        .get_typed_func::<(i32, i64), ()>(&store, "store")
+        .unwrap();
+    let load = instance.get_typed_func::<i32, i64>(&store, "load").unwrap();
+    let fill = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "fill")
+        .unwrap();
+    let copy = instance
+        .get_typed_func::<(i32, i32, i32), ()>(&store, "copy")
+        .unwrap();
+    store16.call(&mut store, (3, 0x1_2345)).unwrap();
+    assert_eq!(load.call(&mut store, 3).unwrap(), 0x2345);
+    fill.call(&mut store, (8, 0xAB, 4)).unwrap();
+    copy.call(&mut store, (10, 8, 4)).unwrap();
+    let mut bytes = [0x00_u8; 8];
+    shared.read(8, &mut bytes).unwrap();
+    assert_eq!(bytes, [0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x00, 0x00]);
+    // Accesses to pages grown by another handle are in bounds immediately.
+    let page_size = 65536;
+    assert_eq!(
+        load.call(&mut store, page_size).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    assert_eq!(
+        load.call(&mut store, page_size - 8).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    shared.grow(Pages::new(1).unwrap()).unwrap();
+    store16.call(&mut store, (page_size, 0x42)).unwrap();
+    assert_eq!(load.call(&mut store, page_size).unwrap(), 0x42);
+    // The bytes of a shared memory are accessible via the host API but never borrowed.
+    let memory = Memory::new_shared(&mut store, &shared);
+    memory.write(&mut store, 100, &[1, 2, 3]).unwrap();
+    assert_eq!(memory.read_value::<u16>(&store, 101).unwrap(), 0x0302);
+    assert!(matches!(
+        memory.view::<u8, ()>(&store, 0, 1),
+        Err(MemoryError::SharedMemoryBorrow)
+    ));
+}
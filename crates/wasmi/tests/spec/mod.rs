@@ -1,15 +1,5 @@
-mod context;
-mod descriptor;
-mod error;
-mod profile;
 mod run;
 
-use self::{
-    context::TestContext,
-    descriptor::{TestDescriptor, TestSpan},
-    error::TestError,
-    profile::TestProfile,
-};
 use wasmi::Config;
 
 macro_rules! define_tests {
@@ -0,0 +1 @@
+mod e2e;
@@ -2,6 +2,7 @@ use super::{TestDescriptor, TestError, TestProfile, TestSpan};
 use anyhow::Result;
 use std::collections::HashMap;
 use wasmi::{
+    core::{ValueType, F32, F64},
     Config,
     Engine,
     Extern,
@@ -18,10 +19,10 @@ use wasmi::{
     TableType,
     Value,
 };
-use wasmi_core::{ValueType, F32, F64};
+
 use wast::token::{Id, Span};
 
-/// The context of a single Wasm test spec suite run.
+/// The context of a single `.wast` test run.
 #[derive(Debug)]
 pub struct TestContext<'a> {
     /// The Wasmi engine used for executing functions used during the test.
@@ -43,12 +44,12 @@ pub struct TestContext<'a> {
     /// The descriptor of the test.
     ///
     /// Useful for printing better debug messages in case of failure.
-    descriptor: &'a TestDescriptor,
+    descriptor: &'a TestDescriptor<'a>,
 }
 
 impl<'a> TestContext<'a> {
     /// Creates a new [`TestContext`] with the given [`TestDescriptor`].
-    pub fn new(descriptor: &'a TestDescriptor, config: Config) -> Self {
+    pub fn new(descriptor: &'a TestDescriptor<'a>, config: Config) -> Self {
         let engine = Engine::new(&config);
         let mut linker = Linker::new(&engine);
         let mut store = Store::new(&engine, ());
@@ -116,14 +117,14 @@ impl<'a> TestContext<'a> {
     }
 }
 
-impl TestContext<'_> {
-    /// Returns the file path of the associated `.wast` test file.
-    fn test_path(&self) -> &str {
-        self.descriptor.path()
+impl<'a> TestContext<'a> {
+    /// Returns the [`TestDescriptor`] of the test context.
+    pub fn descriptor(&self) -> &'a TestDescriptor<'a> {
+        self.descriptor
     }
 
-    /// Returns the [`TestDescriptor`] of the test context.
-    pub fn spanned(&self, span: Span) -> TestSpan {
+    /// Returns a [`TestSpan`] to print the location of `span` within the `.wast` test file.
+    pub fn spanned(&self, span: Span) -> TestSpan<'_> {
         self.descriptor.spanned(span)
     }
 
@@ -142,8 +143,13 @@ impl TestContext<'_> {
         &mut self.store
     }
 
+    /// Returns a shared reference to the test profile.
+    pub fn profile(&self) -> &TestProfile {
+        &self.profile
+    }
+
     /// Returns an exclusive reference to the test profile.
-    pub fn profile(&mut self) -> &mut TestProfile {
+    pub fn profile_mut(&mut self) -> &mut TestProfile {
         &mut self.profile
     }
 
@@ -151,19 +157,14 @@ impl TestContext<'_> {
     ///
     /// # Errors
     ///
-    /// If creating the [`Module`] fails.
+    /// - If encoding the `.wast` module into `.wasm` fails.
+    /// - If creating or instantiating the [`Module`] fails.
     pub fn compile_and_instantiate(
         &mut self,
         mut module: wast::core::Module,
     ) -> Result<Instance, TestError> {
         let module_name = module.id.map(|id| id.name());
-        let wasm = module.encode().unwrap_or_else(|error| {
-            panic!(
-                "encountered unexpected failure to encode `.wast` module into `.wasm`:{}: {}",
-                self.test_path(),
-                error
-            )
-        });
+        let wasm = module.encode().map_err(TestError::Encode)?;
         let module = Module::new(self.engine(), &wasm[..])?;
         let instance_pre = self.linker.instantiate(&mut self.store, &module)?;
         let instance = instance_pre.start(&mut self.store)?;
@@ -204,25 +205,22 @@ impl TestContext<'_> {
     }
 
     /// Registers the given [`Instance`] with the given `name` and sets it as the last instance.
-    pub fn register_instance(&mut self, name: &str, instance: Instance) {
+    ///
+    /// # Errors
+    ///
+    /// If the exports of the [`Instance`] cannot be defined in the linker.
+    pub fn register_instance(&mut self, name: &str, instance: Instance) -> Result<(), TestError> {
         if self.instances.contains_key(name) {
             // Already registered the instance.
-            return;
+            return Ok(());
         }
         self.instances.insert(name.to_string(), instance);
         for export in instance.exports(&self.store) {
             self.linker
-                .define(name, export.name(), export.clone().into_extern())
-                .unwrap_or_else(|error| {
-                    let field_name = export.name();
-                    let export = export.clone().into_extern();
-                    panic!(
-                        "failed to define export {name}::{field_name}: \
-                        {export:?}: {error}",
-                    )
-                });
+                .define(name, export.name(), export.into_extern())?;
         }
         self.last_instance = Some(instance);
+        Ok(())
     }
 
     /// Invokes the [`Func`] identified by `func_name` in [`Instance`] identified by `module_name`.
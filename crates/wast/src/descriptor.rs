@@ -1,37 +1,33 @@
 use std::fmt::{self, Display};
 use wast::token::Span;
 
-/// The desciptor of a Wasm spec test suite run.
+/// The desciptor of a `.wast` test run.
 #[derive(Debug)]
-pub struct TestDescriptor {
-    /// The path of the Wasm spec test `.wast` file.
-    path: &'static str,
-    /// The contents of the Wasm spec test `.wast` file.
-    file: &'static str,
+pub struct TestDescriptor<'a> {
+    /// The path of the `.wast` test file.
+    path: &'a str,
+    /// The contents of the `.wast` test file.
+    file: &'a str,
 }
 
-impl TestDescriptor {
-    /// Creates a new Wasm spec [`TestDescriptor`].
-    ///
-    /// # Errors
-    ///
-    /// If the corresponding Wasm test spec file cannot properly be read.
-    pub fn new(path: &'static str, file: &'static str) -> Self {
+impl<'a> TestDescriptor<'a> {
+    /// Creates a new [`TestDescriptor`] for the `.wast` file at `path` with contents `file`.
+    pub fn new(path: &'a str, file: &'a str) -> Self {
         Self { path, file }
     }
 
-    /// Returns the path of the Wasm spec test `.wast` file.
+    /// Returns the path of the `.wast` test file.
     pub fn path(&self) -> &str {
         self.path
     }
 
-    /// Returns the contents of the Wasm spec test `.wast` file.
+    /// Returns the contents of the `.wast` test file.
     pub fn file(&self) -> &str {
         self.file
     }
 
     /// Creates a [`TestSpan`] which can be used to print the location within the `.wast` test file.
-    pub fn spanned(&self, span: Span) -> TestSpan {
+    pub fn spanned(&self, span: Span) -> TestSpan<'_> {
         TestSpan {
             path: self.path(),
             contents: self.file(),
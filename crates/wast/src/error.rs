@@ -1,10 +1,11 @@
 use std::{error::Error, fmt, fmt::Display};
 use wasmi::Error as WasmiError;
 
-/// Errors that may occur upon Wasm spec test suite execution.
+/// Errors that may occur upon `.wast` test execution.
 #[derive(Debug)]
 pub enum TestError {
     Wasmi(WasmiError),
+    Encode(wast::Error),
     InstanceNotRegistered {
         name: String,
     },
@@ -17,6 +18,9 @@ pub enum TestError {
         module_name: Option<String>,
         global_name: String,
     },
+    UnsupportedArgument {
+        arg: String,
+    },
 }
 
 impl Error for TestError {}
@@ -45,6 +49,12 @@ impl Display for TestError {
                     "missing global variable exported as: {module_name:?}::{global_name}",
                 )
             }
+            Self::UnsupportedArgument { arg } => {
+                write!(f, "encountered unsupported argument: {arg}")
+            }
+            Self::Encode(error) => {
+                write!(f, "failed to encode `.wast` module into `.wasm`: {error}")
+            }
             Self::Wasmi(wasmi_error) => Display::fmt(wasmi_error, f),
         }
     }
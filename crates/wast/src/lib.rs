@@ -0,0 +1,38 @@
+//! Runs `.wast` script files with the Wasmi interpreter.
+//!
+//! This is the runner used by the official Wasm spec testsuite of Wasmi.
+//! Use [`run_wast`] to execute all directives of a `.wast` file, such as
+//! `assert_return`, `assert_trap` or `register`, within a [`TestContext`].
+//!
+//! # Example
+//!
+//! ```
+//! use wasmi::Config;
+//! use wasmi_wast::{run_wast, TestContext, TestDescriptor};
+//!
+//! let wast = r#"
+//!     (module
+//!         (func (export "add") (param i32 i32) (result i32)
+//!             (i32.add (local.get 0) (local.get 1))
+//!         )
+//!     )
+//!     (assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+//! "#;
+//! let test = TestDescriptor::new("add.wast", wast);
+//! let mut context = TestContext::new(&test, Config::default());
+//! run_wast(&mut context).unwrap();
+//! ```
+
+mod context;
+mod descriptor;
+mod error;
+mod profile;
+mod run;
+
+pub use self::{
+    context::TestContext,
+    descriptor::{TestDescriptor, TestSpan},
+    error::TestError,
+    profile::TestProfile,
+    run::run_wast,
+};
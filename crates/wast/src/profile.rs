@@ -1,4 +1,6 @@
-/// Test profiles collected during the Wasm spec test run.
+use std::fmt::{self, Display};
+
+/// Test profiles collected during the `.wast` test run.
 #[derive(Debug, Default)]
 pub struct TestProfile {
     /// The total amount of executed `.wast` directives.
@@ -88,3 +90,27 @@ impl TestProfile {
         self.assert_exception += 1;
     }
 }
+
+impl Display for TestProfile {
+    /// Writes a summary of the executed directives, omitting kinds that did not occur.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "directives: {}", self.directives)?;
+        let counters = [
+            ("module", self.module),
+            ("quote_module", self.quote_module),
+            ("assert_malformed", self.assert_malformed),
+            ("assert_invalid", self.assert_invalid),
+            ("register", self.register),
+            ("invoke", self.invoke),
+            ("assert_trap", self.assert_trap),
+            ("assert_return", self.assert_return),
+            ("assert_exhaustion", self.assert_exhaustion),
+            ("assert_unlinkable", self.assert_unlinkable),
+            ("assert_exception", self.assert_exception),
+        ];
+        for (name, count) in counters.into_iter().filter(|(_, count)| *count != 0) {
+            writeln!(f, " - {name}: {count}")?;
+        }
+        Ok(())
+    }
+}